@@ -6,9 +6,16 @@ use std::{
 
 use clap::{Parser, Subcommand, ValueEnum};
 
+pub mod check;
 pub mod compile;
 pub mod cryptography;
+pub mod diff;
+pub mod format;
 pub mod inspect;
+pub mod serve;
+pub mod validate;
+pub mod vectors;
+pub mod watch;
 
 /// Command-line arguments for the `codabase` terminal interface.
 #[derive(Parser, Debug)]
@@ -23,11 +30,18 @@ impl Args {
     /// Execute the subcommand in these arguments.
     pub fn execute(self) {
         match self.command {
+            Command::Check(cmd) => check::execute_check_command(cmd),
             Command::Compile(cmd) => compile::execute_compile_command(cmd),
+            Command::Diff(cmd) => diff::execute_diff_command(cmd),
+            Command::Format(cmd) => format::execute_format_command(cmd),
             Command::Inspect(cmd) => inspect::execute_inspect_command(cmd),
+            Command::Serve(cmd) => serve::execute_serve_command(cmd),
+            Command::Validate(cmd) => validate::execute_validate_command(cmd),
             Command::Crypt(cmd) => {
                 cryptography::execute_cryptography_command(cmd);
             }
+            Command::Vectors(cmd) => vectors::execute_vectors_command(cmd),
+            Command::Watch(cmd) => watch::execute_watch_command(cmd),
         }
     }
 }
@@ -36,15 +50,52 @@ impl Args {
 #[derive(Subcommand, Debug, Clone)]
 #[command()]
 pub enum Command {
+    /// Check that a coda is valid.
+    Check(CheckCommand),
+
     /// Compile language-specific bindings for codas.
     Compile(CompileCommand),
 
+    /// Summarize the changes between two versions of a coda.
+    Diff(DiffCommand),
+
+    /// Rewrite codas in a canonical markdown style.
+    Format(FormatCommand),
+
     /// Inspect binary coda-encoded data.
     Inspect(InspectCommand),
 
+    /// Serve a browsable web interface for codas.
+    Serve(ServeCommand),
+
+    /// Validate the structure of codas.
+    Validate(ValidateCommand),
+
     /// Cryptography-related utilities.
     #[command(subcommand)]
     Crypt(CryptographyCommand),
+
+    /// Generate canonical codec test vectors.
+    Vectors(VectorsCommand),
+
+    /// Recompile codas whenever they change.
+    Watch(WatchCommand),
+}
+
+/// Arguments passed to [Command::Check].
+#[derive(clap::Args, Debug, Clone)]
+pub struct CheckCommand {
+    /// Path to a coda markdown file.
+    ///
+    /// If unspecified, coda markdown will be read
+    /// from standard input.
+    #[arg(short, long)]
+    source: Option<PathBuf>,
+
+    /// Verify that each worked example in the
+    /// coda's docs encodes to its declared bytes.
+    #[arg(long)]
+    verify_examples: bool,
 }
 
 /// Arguments passed to [Command::Compile].
@@ -71,6 +122,51 @@ pub struct CompileCommand {
     /// all codas in `--source` to all languages in `--target`.
     #[arg(short, long)]
     lang: Option<Lang>,
+
+    /// Allow multiple codas in `--source` to
+    /// declare the same global (or local) name.
+    ///
+    /// Only used in batch mode. When set, the output
+    /// files of conflicting codas are suffixed with
+    /// a prefix of each coda's hash.
+    #[arg(long)]
+    allow_duplicate_names: bool,
+
+    /// Generate a builder for each generated
+    /// Rust struct.
+    #[arg(long)]
+    builder: bool,
+}
+
+/// Arguments passed to [Command::Watch].
+#[derive(clap::Args, Debug, Clone)]
+pub struct WatchCommand {
+    /// Path to a directory of coda files.
+    ///
+    /// If unspecified, the working directory is used.
+    #[arg(short, long)]
+    source: Option<PathBuf>,
+
+    /// Output directory for compiled files.
+    #[arg(short, long, default_value_os_t = get_working_directory().join("target"))]
+    target: PathBuf,
+
+    /// Only compile to this language.
+    ///
+    /// If unspecified, codas are compiled
+    /// to all supported languages.
+    #[arg(short, long)]
+    lang: Option<Lang>,
+
+    /// Allow multiple codas in `--source` to
+    /// declare the same global (or local) name.
+    #[arg(long)]
+    allow_duplicate_names: bool,
+
+    /// Generate a builder for each generated
+    /// Rust struct.
+    #[arg(long)]
+    builder: bool,
 }
 
 /// Supported target languages for code generation.
@@ -79,10 +175,47 @@ pub enum Lang {
     Rust,
     Python,
     Typescript,
+    Go,
+    Protobuf,
     OpenApi,
+    JsonSchema,
+    Graphql,
     Sql,
 }
 
+/// Arguments passed to [Command::Diff].
+#[derive(clap::Args, Debug, Clone)]
+pub struct DiffCommand {
+    /// Path to the old version of a coda markdown file.
+    #[arg(long)]
+    old: PathBuf,
+
+    /// Path to the new version of a coda markdown file.
+    #[arg(long)]
+    new: PathBuf,
+
+    /// Exit with an error if any changes are breaking.
+    #[arg(long)]
+    check: bool,
+}
+
+/// Arguments passed to [Command::Format].
+#[derive(clap::Args, Debug, Clone)]
+pub struct FormatCommand {
+    /// Path to a directory containing coda markdown
+    /// files, which will be searched recursively.
+    ///
+    /// If unspecified, the working directory is used.
+    #[arg(short, long)]
+    source: Option<PathBuf>,
+
+    /// Print the files that would be reformatted,
+    /// without changing them, and exit with an
+    /// error if there are any.
+    #[arg(long)]
+    check: bool,
+}
+
 /// Arguments passed to [Command::Inspect].
 #[derive(clap::Args, Debug, Clone)]
 pub struct InspectCommand {
@@ -91,6 +224,74 @@ pub struct InspectCommand {
     /// If unspecified, data will be read from standard input.
     #[arg(short, long)]
     source: Option<PathBuf>,
+
+    /// Path to a coda markdown file used to
+    /// name the data types being inspected.
+    #[arg(short, long)]
+    coda: Option<PathBuf>,
+
+    /// Decode and print the values of all data,
+    /// instead of only printing its structure.
+    #[arg(long)]
+    values: bool,
+}
+
+/// Arguments passed to [Command::Serve].
+#[derive(clap::Args, Debug, Clone)]
+pub struct ServeCommand {
+    /// Path to a directory of coda files.
+    ///
+    /// If unspecified, the working directory is used.
+    #[arg(short, long)]
+    source: Option<PathBuf>,
+
+    /// Address to listen on.
+    ///
+    /// To share codas with other machines,
+    /// listen on `0.0.0.0`.
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on.
+    #[arg(short, long, default_value_t = 8080)]
+    port: u16,
+}
+
+/// Arguments passed to [Command::Validate].
+#[derive(clap::Args, Debug, Clone)]
+pub struct ValidateCommand {
+    /// Path to a coda markdown file, or a directory
+    /// of coda files.
+    ///
+    /// If unspecified, the working directory is used.
+    #[arg(short, long)]
+    source: Option<PathBuf>,
+
+    /// Treat warnings as errors.
+    #[arg(long)]
+    strict: bool,
+}
+
+/// Arguments passed to [Command::Vectors].
+#[derive(clap::Args, Debug, Clone)]
+pub struct VectorsCommand {
+    /// Path to write the vectors to.
+    ///
+    /// If unspecified, the vectors will be
+    /// written to standard output.
+    #[arg(short, long)]
+    target: Option<PathBuf>,
+
+    /// Format to write the vectors in.
+    #[arg(short, long, default_value = "json")]
+    format: VectorsFormat,
+}
+
+/// Supported formats for test vectors.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum VectorsFormat {
+    Json,
+    Markdown,
 }
 
 /// Subcommand passed to [Command::Crypt].
@@ -0,0 +1,38 @@
+use std::io::Read;
+
+use codas::{examples, parse};
+
+use super::{open_file_or_stdin, CheckCommand};
+
+/// Executes `command` locally.
+pub fn execute_check_command(command: CheckCommand) {
+    let mut input = open_file_or_stdin(command.source).expect("source doesn't exist");
+    let mut markdown = String::new();
+    input
+        .read_to_string(&mut markdown)
+        .expect("failed to read source");
+
+    let coda = match parse::parse(&markdown) {
+        Ok(coda) => coda,
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    eprintln!("`{}` is a valid coda", coda.global_name);
+
+    if !command.verify_examples {
+        return;
+    }
+
+    match examples::verify(&coda) {
+        Ok(verified) => eprintln!("verified {verified} example(s)"),
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("error: {error}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
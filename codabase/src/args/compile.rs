@@ -4,21 +4,24 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use codas::{langs, parse, types::Coda};
+use codas::{
+    langs, parse,
+    types::{cryptography::HasCryptoHash, Coda},
+};
 
 use super::{open_file_or_stdin, CompileCommand, Lang};
 
 /// Executes `command` locally.
 pub fn execute_compile_command(command: CompileCommand) {
     match command.lang {
-        Some(lang) => pipe_mode(command.source, lang),
+        Some(lang) => pipe_mode(&command, lang),
         None => batch_mode(command),
     }
 }
 
 /// Compile a single coda and write the output to stdout.
-fn pipe_mode(source: Option<PathBuf>, lang: Lang) {
-    let mut input = open_file_or_stdin(source).expect("source doesn't exist");
+fn pipe_mode(command: &CompileCommand, lang: Lang) {
+    let mut input = open_file_or_stdin(command.source.clone()).expect("source doesn't exist");
     let mut markdown = String::new();
     input
         .read_to_string(&mut markdown)
@@ -27,7 +30,7 @@ fn pipe_mode(source: Option<PathBuf>, lang: Lang) {
     let coda = parse::parse(&markdown).expect("failed to parse coda");
     let mut stdout = std::io::stdout().lock();
 
-    generate(&coda, lang, &mut stdout);
+    generate(&coda, lang, command, &mut stdout);
 }
 
 /// Compile all codas found in a source directory to all
@@ -35,6 +38,7 @@ fn pipe_mode(source: Option<PathBuf>, lang: Lang) {
 fn batch_mode(command: CompileCommand) {
     let source = command
         .source
+        .clone()
         .unwrap_or_else(|| std::env::current_dir().unwrap());
 
     if !source.is_dir() {
@@ -45,55 +49,181 @@ fn batch_mode(command: CompileCommand) {
         std::process::exit(1);
     }
 
-    let codas = discover_codas(&source);
+    let codas = match discover_codas(&source, command.allow_duplicate_names) {
+        Ok(codas) => codas,
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+    };
 
     if codas.is_empty() {
         eprintln!("no codas found in {}", source.display());
         return;
     }
 
-    let langs = [
-        Lang::Rust,
-        Lang::Python,
-        Lang::Typescript,
-        Lang::OpenApi,
-        Lang::Sql,
-    ];
+    compile_codas(&codas, &ALL_LANGS, &command);
+
+    eprintln!(
+        "compiled {} coda(s) to {} language(s)",
+        codas.len(),
+        ALL_LANGS.len()
+    );
+}
+
+/// Every language compiled to in batch mode.
+pub(super) const ALL_LANGS: [Lang; 9] = [
+    Lang::Rust,
+    Lang::Python,
+    Lang::Typescript,
+    Lang::Go,
+    Lang::Protobuf,
+    Lang::OpenApi,
+    Lang::JsonSchema,
+    Lang::Graphql,
+    Lang::Sql,
+];
 
-    for lang in langs {
+/// Compile `codas` to each of `languages`, writing output
+/// files (and any shared runtimes) into `--target/<lang>/`.
+pub(super) fn compile_codas(
+    codas: &[DiscoveredCoda],
+    languages: &[Lang],
+    command: &CompileCommand,
+) {
+    for &lang in languages {
         let lang_dir = command.target.join(lang.dir_name());
         fs::create_dir_all(&lang_dir).expect("failed to create output directory");
 
-        for (path, coda) in &codas {
-            let file_name = lang.file_name(&coda.local_name);
+        // Typescript codecs import a shared runtime.
+        if matches!(lang, Lang::Typescript) {
+            let runtime_path = lang_dir.join(langs::typescript::RUNTIME_FILE_NAME);
+            let mut file = fs::File::create(&runtime_path).expect("failed to create runtime file");
+            langs::typescript::generate_runtime(&mut file).expect("failed to write output");
+            eprintln!("  -> {}", runtime_path.display());
+        }
+
+        // Go codecs share a runtime within their package.
+        if matches!(lang, Lang::Go) {
+            let runtime_path = lang_dir.join(langs::golang::RUNTIME_FILE_NAME);
+            let mut file = fs::File::create(&runtime_path).expect("failed to create runtime file");
+            langs::golang::generate_runtime(&mut file).expect("failed to write output");
+            eprintln!("  -> {}", runtime_path.display());
+        }
+
+        for discovered in codas {
+            let file_name = lang.file_name(&discovered.output_stem);
             let out_path = lang_dir.join(&file_name);
             let mut file = fs::File::create(&out_path).expect("failed to create output file");
 
-            generate(coda, lang, &mut file);
-            eprintln!("  {} -> {}", path.display(), out_path.display());
+            generate(&discovered.coda, lang, command, &mut file);
+            eprintln!("  {} -> {}", discovered.path.display(), out_path.display());
+
+            // Python types are accompanied by stubs.
+            if matches!(lang, Lang::Python) {
+                let stub_path = out_path.with_extension("pyi");
+                let mut file = fs::File::create(&stub_path).expect("failed to create stub file");
+                langs::python::generate_stubs(&discovered.coda, &mut file)
+                    .expect("failed to write output");
+                eprintln!("  {} -> {}", discovered.path.display(), stub_path.display());
+            }
         }
     }
+}
 
-    eprintln!(
-        "compiled {} coda(s) to {} language(s)",
-        codas.len(),
-        langs.len()
-    );
+/// A coda discovered in a source directory.
+pub(super) struct DiscoveredCoda {
+    /// Path of the coda's markdown file.
+    pub path: PathBuf,
+
+    /// The parsed coda.
+    pub coda: Coda,
+
+    /// File stem used for the coda's output files.
+    pub output_stem: String,
 }
 
 /// Recursively discover and parse all coda markdown files
 /// under `dir`, returning the successfully parsed codas
 /// alongside their source paths.
-fn discover_codas(dir: &Path) -> Vec<(PathBuf, Coda)> {
-    let mut codas = Vec::new();
-    collect_md_files(dir, &mut codas);
-    codas.sort_by(|(a, _), (b, _)| a.cmp(b));
-    codas
+///
+/// If two _different_ codas would be compiled to the same
+/// output files (because they declare the same global name,
+/// or local names with the same snake_case form), an error
+/// listing their paths is returned unless `allow_duplicate_names`
+/// is true; in that case, each conflicting coda's output stem
+/// is suffixed with a prefix of its hash. Codas with the same
+/// global name _and_ contents are only returned once.
+pub(super) fn discover_codas(
+    dir: &Path,
+    allow_duplicate_names: bool,
+) -> Result<Vec<DiscoveredCoda>, String> {
+    let mut paths = Vec::new();
+    collect_md_files(dir, &mut paths);
+    paths.sort();
+    let codas: Vec<(PathBuf, Coda)> = paths
+        .into_iter()
+        .filter_map(|path| {
+            let markdown = fs::read_to_string(&path).ok()?;
+            let coda = parse::parse(&markdown).ok()?;
+            Some((path, coda))
+        })
+        .collect();
+
+    // Drop identical duplicates, keeping the first path.
+    let mut hashed: Vec<(PathBuf, Coda, String)> = Vec::with_capacity(codas.len());
+    for (path, coda) in codas {
+        let hash = coda.crypto_hasher().finalize().to_hex().to_string();
+        if !hashed.iter().any(|(_, other, other_hash)| {
+            other.global_name == coda.global_name && *other_hash == hash
+        }) {
+            hashed.push((path, coda, hash));
+        }
+    }
+
+    // Detect codas with conflicting output files.
+    let mut discovered = Vec::with_capacity(hashed.len());
+    for (path, coda, hash) in &hashed {
+        let mut output_stem = to_snake_case(&coda.local_name);
+        let conflicts: Vec<(&PathBuf, &Coda)> = hashed
+            .iter()
+            .filter(|(other_path, other, _)| {
+                other_path != path && to_snake_case(&other.local_name) == output_stem
+            })
+            .map(|(other_path, other, _)| (other_path, other))
+            .collect();
+
+        if let Some((conflict_path, conflict)) = conflicts.first() {
+            if !allow_duplicate_names {
+                return Err(format!(
+                    "codas `{}` ({}) and `{}` ({}) would both be compiled to `{output_stem}` (pass --allow-duplicate-names to compile both)",
+                    coda.global_name,
+                    path.display(),
+                    conflict.global_name,
+                    conflict_path.display(),
+                ));
+            }
+
+            output_stem = format!("{output_stem}_{}", &hash[..DUPLICATE_SUFFIX_LEN]);
+        }
+
+        discovered.push(DiscoveredCoda {
+            path: path.clone(),
+            coda: coda.clone(),
+            output_stem,
+        });
+    }
+
+    Ok(discovered)
 }
 
-/// Recursively collects `.md` files from `dir`, attempting
-/// to parse each as a coda.
-fn collect_md_files(dir: &Path, codas: &mut Vec<(PathBuf, Coda)>) {
+/// Number of hexadecimal hash characters appended
+/// to the outputs of codas with duplicate names.
+const DUPLICATE_SUFFIX_LEN: usize = 8;
+
+/// Recursively collects the paths of
+/// all `.md` files in `dir`.
+pub(super) fn collect_md_files(dir: &Path, paths: &mut Vec<PathBuf>) {
     let entries = match fs::read_dir(dir) {
         Ok(entries) => entries,
         Err(_) => return,
@@ -103,27 +233,32 @@ fn collect_md_files(dir: &Path, codas: &mut Vec<(PathBuf, Coda)>) {
         let path = entry.path();
 
         if path.is_dir() {
-            collect_md_files(&path, codas);
+            collect_md_files(&path, paths);
         } else if path.extension().is_some_and(|ext| ext == "md") {
-            let markdown = match fs::read_to_string(&path) {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-
-            if let Ok(coda) = parse::parse(&markdown) {
-                codas.push((path, coda));
-            }
+            paths.push(path);
         }
     }
 }
 
-/// Generate code for `coda` in the given `lang`, writing to `out`.
-fn generate(coda: &Coda, lang: Lang, out: &mut impl std::io::Write) {
+/// Generate code for `coda` in the given `lang`,
+/// configured by `command`, writing to `out`.
+fn generate(coda: &Coda, lang: Lang, command: &CompileCommand, out: &mut impl std::io::Write) {
     match lang {
-        Lang::Rust => langs::rust::generate_types(coda, out, true),
+        Lang::Rust => {
+            let options = langs::rust::RustCodegenOptions {
+                serde: true,
+                builder_pattern: command.builder,
+                ..Default::default()
+            };
+            langs::rust::generate_types(coda, out, &options)
+        }
         Lang::Python => langs::python::generate_types(coda, out),
         Lang::Typescript => langs::typescript::generate_types(coda, out),
+        Lang::Go => langs::golang::generate_types(coda, out),
+        Lang::Protobuf => langs::protobuf::generate_proto(coda, out),
         Lang::OpenApi => langs::open_api::generate_spec(coda, out),
+        Lang::JsonSchema => langs::json_schema::generate_schema(coda, out),
+        Lang::Graphql => langs::graphql::generate_schema(coda, out),
         Lang::Sql => langs::sql::generate_types(coda, out),
     }
     .expect("failed to write output");
@@ -136,19 +271,27 @@ impl Lang {
             Lang::Rust => "rust",
             Lang::Python => "python",
             Lang::Typescript => "typescript",
+            Lang::Go => "go",
+            Lang::Protobuf => "protobuf",
             Lang::OpenApi => "open-api",
+            Lang::JsonSchema => "json-schema",
+            Lang::Graphql => "graphql",
             Lang::Sql => "sql",
         }
     }
 
-    /// Output file name for a coda with the given local name.
-    fn file_name(self, local_name: &str) -> String {
-        let snake = to_snake_case(local_name);
+    /// Output file name for a coda with the given
+    /// snake_case file stem.
+    fn file_name(self, snake: &str) -> String {
         match self {
             Lang::Rust => format!("{snake}.rs"),
             Lang::Python => format!("{snake}.py"),
             Lang::Typescript => format!("{snake}.ts"),
+            Lang::Go => format!("{snake}.go"),
+            Lang::Protobuf => format!("{snake}.proto"),
             Lang::OpenApi => format!("{snake}.yaml"),
+            Lang::JsonSchema => format!("{snake}.json"),
+            Lang::Graphql => format!("{snake}.graphql"),
             Lang::Sql => format!("{snake}.sql"),
         }
     }
@@ -165,3 +308,114 @@ fn to_snake_case(s: &str) -> String {
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an empty temporary directory named `name`.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codabase-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let dir = temp_dir("rejects-duplicate-names");
+        fs::write(
+            dir.join("a.md"),
+            "# `Greeter` Coda\n\n## `Request` Data\n+ `message` text",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("b.md"),
+            "# `Greeter` Coda\n\n## `Response` Data\n+ `message` text",
+        )
+        .unwrap();
+
+        let error = discover_codas(&dir, false)
+            .err()
+            .expect("duplicates should be rejected");
+        assert!(error.contains("a.md"), "{error}");
+        assert!(error.contains("b.md"), "{error}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn allows_duplicate_names() {
+        let dir = temp_dir("allows-duplicate-names");
+        fs::write(
+            dir.join("a.md"),
+            "# `Greeter` Coda\n\n## `Request` Data\n+ `message` text",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("b.md"),
+            "# `Greeter` Coda\n\n## `Response` Data\n+ `message` text",
+        )
+        .unwrap();
+
+        let codas = discover_codas(&dir, true).unwrap();
+        assert_eq!(2, codas.len());
+        assert_ne!(codas[0].output_stem, codas[1].output_stem);
+        assert!(codas[0].output_stem.starts_with("greeter_"));
+        assert!(codas[1].output_stem.starts_with("greeter_"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_conflicting_output_stems() {
+        let dir = temp_dir("rejects-conflicting-output-stems");
+
+        // Different global names with the same local name.
+        fs::write(
+            dir.join("a.md"),
+            "# `a/Greeter` Coda\n\n## `Request` Data\n+ `message` text",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("b.md"),
+            "# `b/Greeter` Coda\n\n## `Response` Data\n+ `message` text",
+        )
+        .unwrap();
+        let error = discover_codas(&dir, false)
+            .err()
+            .expect("conflicting local names should be rejected");
+        assert!(error.contains("a.md"), "{error}");
+        assert!(error.contains("b.md"), "{error}");
+        assert!(error.contains("`greeter`"), "{error}");
+
+        // Local names differing only in case.
+        fs::write(
+            dir.join("b.md"),
+            "# `greeter` Coda\n\n## `Response` Data\n+ `message` text",
+        )
+        .unwrap();
+        assert!(discover_codas(&dir, false).is_err());
+
+        // Both are compiled when duplicates are allowed.
+        let codas = discover_codas(&dir, true).unwrap();
+        assert_eq!(2, codas.len());
+        assert_ne!(codas[0].output_stem, codas[1].output_stem);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dedupes_identical_codas() {
+        let dir = temp_dir("dedupes-identical-codas");
+        let markdown = "# `Greeter` Coda\n\n## `Request` Data\n+ `message` text";
+        fs::write(dir.join("a.md"), markdown).unwrap();
+        fs::write(dir.join("b.md"), markdown).unwrap();
+
+        let codas = discover_codas(&dir, false).unwrap();
+        assert_eq!(1, codas.len());
+        assert_eq!("greeter", codas[0].output_stem);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
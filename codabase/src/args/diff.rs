@@ -0,0 +1,47 @@
+use std::{fs, path::Path};
+
+use codas::{
+    parse,
+    types::{self, Coda},
+};
+
+use super::DiffCommand;
+
+/// Executes `command` locally.
+pub fn execute_diff_command(command: DiffCommand) {
+    let old = parse_or_exit(&command.old);
+    let new = parse_or_exit(&command.new);
+
+    let diff = types::diff(&old, &new);
+    if diff.is_empty() {
+        eprintln!("no changes");
+        return;
+    }
+
+    print!("{diff}");
+
+    if command.check && diff.is_breaking() {
+        eprintln!("error: breaking changes detected");
+        std::process::exit(1);
+    }
+}
+
+/// Parses the coda at `path`, exiting
+/// with an error if it can't be parsed.
+fn parse_or_exit(path: &Path) -> Coda {
+    let markdown = match fs::read_to_string(path) {
+        Ok(markdown) => markdown,
+        Err(error) => {
+            eprintln!("error: failed to read {}: {error}", path.display());
+            std::process::exit(1);
+        }
+    };
+
+    match parse::parse(&markdown) {
+        Ok(coda) => coda,
+        Err(error) => {
+            eprintln!("error: {}: {error}", path.display());
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::{fs, path::Path};
+
+use codas::parse::{self, ParseError};
+
+use super::{compile::collect_md_files, FormatCommand};
+
+/// Executes `command` locally.
+pub fn execute_format_command(command: FormatCommand) {
+    let source = command
+        .source
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+
+    let mut paths = Vec::new();
+    if source.is_dir() {
+        collect_md_files(&source, &mut paths);
+        paths.sort();
+    } else {
+        paths.push(source);
+    }
+
+    let mut changed = 0;
+    let mut errors = 0;
+    for path in &paths {
+        match format_file(path, command.check) {
+            Ok(false) => {}
+            Ok(true) => {
+                changed += 1;
+                if command.check {
+                    println!("{}", path.display());
+                }
+            }
+            Err(error) => {
+                errors += 1;
+                eprintln!("error: {}: {error}", path.display());
+            }
+        }
+    }
+
+    if command.check {
+        eprintln!("{changed} file(s) would be reformatted");
+    } else {
+        eprintln!("reformatted {changed} file(s)");
+    }
+
+    if errors > 0 || (command.check && changed > 0) {
+        std::process::exit(1);
+    }
+}
+
+/// Formats the coda markdown at `path` with [`parse::format`],
+/// returning true iff the formatted markdown differs.
+///
+/// Unless `check` is true, the formatted markdown is written
+/// back to `path`. Markdown files which don't start with
+/// a coda are left unchanged.
+fn format_file(path: &Path, check: bool) -> Result<bool, String> {
+    let markdown = fs::read_to_string(path).map_err(|error| error.to_string())?;
+
+    let formatted = match parse::format(&markdown) {
+        Ok(formatted) => formatted,
+        Err(ParseError::ExpectedCoda { .. }) => return Ok(false),
+        Err(error) => return Err(error.to_string()),
+    };
+
+    if formatted == markdown {
+        return Ok(false);
+    }
+
+    if !check {
+        fs::write(path, formatted).map_err(|error| error.to_string())?;
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_files() {
+        let dir =
+            std::env::temp_dir().join(format!("codabase-{}-formats-files", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("greeter.md");
+        let markdown =
+            "# `Greeter` Coda\n## `request` Data\n+ `message` text\n  The message.\n+ `id` u32";
+        fs::write(&path, markdown).unwrap();
+
+        // Checking doesn't change files.
+        assert_eq!(Ok(true), format_file(&path, true));
+        assert_eq!(markdown, fs::read_to_string(&path).unwrap());
+
+        assert_eq!(Ok(true), format_file(&path, false));
+        let formatted = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            "# `Greeter` Coda\n\n## `Request` Data\n\n+ `id` u32\n+ `message` text\n\n    The message.\n",
+            formatted
+        );
+
+        // Formatting is idempotent.
+        assert_eq!(Ok(false), format_file(&path, false));
+        assert_eq!(formatted, fs::read_to_string(&path).unwrap());
+
+        // Markdown that isn't a coda is skipped.
+        let notes = dir.join("notes.md");
+        fs::write(&notes, "Some notes.").unwrap();
+        assert_eq!(Ok(false), format_file(&notes, false));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,10 @@
+use std::io::{Read, Write};
+
 use codas::{
-    codec::{CodecError, DataHeader, ReadsDecodable, TEMP_BUFFER_SIZE},
-    types::binary::hex_from_bytes,
+    codec::{dump, DumpOptions},
+    parse,
+    stream::TrackedReader,
+    types::Unspecified,
 };
 
 use super::{open_file_or_stdin, InspectCommand};
@@ -9,66 +13,60 @@ use super::{open_file_or_stdin, InspectCommand};
 pub fn execute_inspect_command(command: InspectCommand) {
     // Open input source.
     let mut bytes = open_file_or_stdin(command.source).expect("source doesn't exist");
-    let mut buffer = Vec::with_capacity(TEMP_BUFFER_SIZE);
-    bytes.read_to_end(&mut buffer).expect("source read failed");
-
-    // Inspect the data.
-    inspect_data(&mut buffer.as_slice(), 0).unwrap();
-}
-
-fn inspect_data(data: &mut (impl ReadsDecodable + ?Sized), depth: usize) -> Result<(), CodecError> {
-    // Decode header.
-    let header: DataHeader = data.read_data()?;
-    let format = header.format;
-    let count = header.count;
 
-    eprint!("|-");
-    for _ in 0..depth {
-        eprint!("-");
+    // Only decode values when requested; otherwise,
+    // stream the structure of the data.
+    if command.values {
+        let mut buffer = vec![];
+        bytes.read_to_end(&mut buffer).expect("source read failed");
+        inspect_values(&buffer);
+        return;
     }
 
-    if format.ordinal != 0 {
-        eprintln!(
-            " {} O({}) - {} Bytes, {} Data",
-            count, format.ordinal, format.blob_size, format.data_fields
-        );
-    } else {
-        eprintln!(
-            " {} Unspecified - {} Bytes, {} Data",
-            count, format.blob_size, format.data_fields
-        );
-    }
-
-    // Decode contents.
-    for c in 0..count {
-        // Decode blob.
-        if format.blob_size > 0 {
-            if format.blob_size != 1 || format.data_fields > 0 || c == 0 {
-                eprint!("|.");
-                for _ in 0..depth {
-                    eprint!(".");
-                }
-                eprint!(" ");
-            }
+    // Load the coda naming the data, if any.
+    let coda = command.coda.map(|path| {
+        let markdown = std::fs::read_to_string(path).expect("coda doesn't exist");
+        parse::parse(&markdown).expect("coda is invalid")
+    });
+    let options = DumpOptions {
+        coda: coda.as_ref(),
+        ..Default::default()
+    };
 
-            for _ in 0..format.blob_size {
-                let mut buffer = [0u8; 1];
-                assert_eq!(1, data.read(&mut buffer)?);
-                eprint!("{}", hex_from_bytes(&buffer));
-            }
-
-            if format.blob_size != 1 || format.data_fields > 0 || c + 1 == count {
-                eprintln!();
-            } else {
-                eprint!("..");
-            }
+    let mut out = StderrWriter(std::io::stderr().lock());
+    let mut reader = TrackedReader::new(bytes);
+    let stats = match dump(&mut reader, &mut out, &options) {
+        Ok(stats) => stats,
+        Err(error) => {
+            eprintln!("error: {}", error.with_offset(reader.offset()));
+            std::process::exit(1);
         }
+    };
+    eprintln!(
+        "{} records, {} bytes, {} levels deep",
+        stats.records, stats.bytes, stats.max_depth
+    );
+}
 
-        // Decode data.
-        for _ in 0..format.data_fields {
-            inspect_data(data, depth + 1)?;
+/// Decodes and prints all data in `bytes`.
+fn inspect_values(bytes: &[u8]) {
+    let mut reader = TrackedReader::new(bytes);
+    while reader.offset() < bytes.len() as u64 {
+        match reader.read_data_at::<Unspecified>() {
+            Ok(value) => eprintln!("{value:#?}"),
+            Err(error) => {
+                eprintln!("error: {error}");
+                std::process::exit(1);
+            }
         }
     }
+}
+
+/// [`core::fmt::Write`] over a locked standard error.
+struct StderrWriter<'a>(std::io::StderrLock<'a>);
 
-    Ok(())
+impl core::fmt::Write for StderrWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0.write_all(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
 }
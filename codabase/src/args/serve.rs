@@ -0,0 +1,429 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Write as _,
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, RwLock,
+    },
+};
+
+use codas::{
+    langs,
+    types::{DataType, Type},
+};
+use notify::{RecursiveMode, Watcher};
+
+use super::{
+    compile::{discover_codas, DiscoveredCoda},
+    watch::{collect_changes, DEBOUNCE_DELAY},
+    ServeCommand,
+};
+
+/// Codas being served, shared between
+/// the server and the source watcher.
+type SharedCodas = Arc<RwLock<Vec<DiscoveredCoda>>>;
+
+/// Styles embedded in every HTML page, so
+/// pages are viewable without network access.
+const STYLE: &str = "body{font-family:sans-serif;max-width:50em;margin:2em auto;padding:0 1em;line-height:1.5;color:#222}\
+a{color:#0a58ca}code{font-family:monospace}\
+.docs{white-space:pre-wrap}\
+table{border-collapse:collapse;width:100%}\
+th,td{text-align:left;vertical-align:top;padding:.25em .5em;border-bottom:1px solid #ddd}";
+
+/// Executes `command` locally.
+pub fn execute_serve_command(command: ServeCommand) {
+    let source = command
+        .source
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+    let source = match fs::canonicalize(&source) {
+        Ok(source) if source.is_dir() => source,
+        _ => {
+            eprintln!(
+                "error: --source must be a directory (got {})",
+                source.display()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let codas = match discover_codas(&source, true) {
+        Ok(codas) => Arc::new(RwLock::new(codas)),
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    // Reload codas whenever their sources change.
+    let reloaded = codas.clone();
+    let watched = source.clone();
+    std::thread::spawn(move || watch_codas(&watched, &reloaded));
+
+    let listener = match TcpListener::bind((command.host.as_str(), command.port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!(
+                "error: failed to listen on {}:{}: {error}",
+                command.host, command.port
+            );
+            std::process::exit(1);
+        }
+    };
+    eprintln!(
+        "serving codas in {} at http://{}:{}/",
+        source.display(),
+        command.host,
+        command.port
+    );
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let codas = codas.clone();
+                std::thread::spawn(move || {
+                    if let Err(error) = handle_connection(stream, &codas) {
+                        eprintln!("error: {error}");
+                    }
+                });
+            }
+            Err(error) => eprintln!("error: {error}"),
+        }
+    }
+}
+
+/// Rediscovers the codas in `source` into `codas`
+/// whenever any coda in `source` changes.
+fn watch_codas(source: &Path, codas: &SharedCodas) {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).expect("failed to create watcher");
+    watcher
+        .watch(source, RecursiveMode::Recursive)
+        .expect("failed to watch source");
+
+    while let Ok(event) = receiver.recv() {
+        let mut changed: BTreeSet<PathBuf> = BTreeSet::new();
+        collect_changes(event, &mut changed);
+
+        // Batch changes until they stop for the debounce delay.
+        loop {
+            match receiver.recv_timeout(DEBOUNCE_DELAY) {
+                Ok(event) => collect_changes(event, &mut changed),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        match discover_codas(source, true) {
+            Ok(discovered) => {
+                eprintln!("reloaded {} coda(s)", discovered.len());
+                *codas.write().unwrap() = discovered;
+            }
+            Err(error) => eprintln!("error: {error}"),
+        }
+    }
+}
+
+/// Reads a single HTTP request from `stream`
+/// and writes the response to it.
+fn handle_connection(mut stream: TcpStream, codas: &SharedCodas) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip the request's headers.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) => route(path, &codas.read().unwrap()),
+        (Some(_), Some(_)) => Response::text("405 Method Not Allowed", "method not allowed"),
+        _ => Response::text("400 Bad Request", "bad request"),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// An HTTP response.
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// Returns a successful response with `body`.
+    fn ok(content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    /// Returns a plain-text response with `status`.
+    fn text(status: &'static str, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+}
+
+/// Returns the response to a `GET` request for `path`.
+///
+/// Codas are identified in paths by their output
+/// file stems (like `greeter` for a `Greeter` coda),
+/// which are unique even if coda names aren't.
+fn route(path: &str, codas: &[DiscoveredCoda]) -> Response {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let find = |stem: &str| codas.iter().find(|coda| coda.output_stem == stem);
+    match segments.as_slice() {
+        [] => Response::ok("text/html; charset=utf-8", render_index(codas)),
+
+        ["coda", stem] => match find(stem) {
+            Some(discovered) => Response::ok("text/html; charset=utf-8", render_coda(discovered)),
+            None => not_found(),
+        },
+
+        ["coda", stem, "openapi"] => match find(stem) {
+            Some(discovered) => {
+                let mut spec = vec![];
+                langs::open_api::generate_spec(&discovered.coda, &mut spec)
+                    .expect("failed to generate OpenAPI spec");
+                Response::ok("application/yaml; charset=utf-8", spec)
+            }
+            None => not_found(),
+        },
+
+        ["coda", stem, "schema"] => match find(stem) {
+            Some(discovered) => {
+                let mut schema = vec![];
+                langs::json_schema::generate_schema(&discovered.coda, &mut schema)
+                    .expect("failed to generate JSON Schema");
+                Response::ok("application/schema+json", schema)
+            }
+            None => not_found(),
+        },
+
+        _ => not_found(),
+    }
+}
+
+/// Returns a `404 Not Found` response.
+fn not_found() -> Response {
+    Response::text("404 Not Found", "not found")
+}
+
+/// Returns an HTML page titled `title` containing `body`.
+fn render_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+/// Returns an HTML index of `codas`.
+fn render_index(codas: &[DiscoveredCoda]) -> String {
+    let mut body = String::from("<h1>Codas</h1>\n");
+    if codas.is_empty() {
+        body += "<p>No codas found.</p>\n";
+    } else {
+        body += "<ul>\n";
+        for discovered in codas {
+            let _ = writeln!(
+                body,
+                "<li><a href=\"/coda/{}\"><code>{}</code></a> ({} data types)</li>",
+                discovered.output_stem,
+                escape(&discovered.coda.global_name),
+                discovered.coda.iter().count()
+            );
+        }
+        body += "</ul>\n";
+    }
+
+    render_page("Codas", &body)
+}
+
+/// Returns a human-readable HTML rendering of a `discovered` coda.
+fn render_coda(discovered: &DiscoveredCoda) -> String {
+    let coda = &discovered.coda;
+    let name = escape(&coda.global_name);
+    let mut body =
+        format!("<p><a href=\"/\">All codas</a></p>\n<h1><code>{name}</code> Coda</h1>\n");
+    if let Some(docs) = &coda.docs {
+        let _ = writeln!(body, "<p class=\"docs\">{}</p>", escape(docs));
+    }
+    let _ = writeln!(
+        body,
+        "<p><a href=\"/coda/{0}/openapi\">OpenAPI</a> · <a href=\"/coda/{0}/schema\">JSON Schema</a></p>",
+        discovered.output_stem
+    );
+
+    for data in coda.iter() {
+        render_data(data, &mut body);
+    }
+
+    render_page(&coda.global_name, &body)
+}
+
+/// Appends a human-readable HTML rendering of `data` to `body`.
+fn render_data(data: &DataType, body: &mut String) {
+    let name = escape(&data.name);
+    let _ = writeln!(
+        body,
+        "<h2 id=\"{name}\"><code>{name}</code> Data <small>(ordinal {})</small></h2>",
+        data.format().as_data_format().ordinal
+    );
+    if let Some(docs) = &data.docs {
+        let _ = writeln!(body, "<p class=\"docs\">{}</p>", escape(docs));
+    }
+
+    if data.iter().next().is_none() {
+        *body += "<p>No fields.</p>\n";
+        return;
+    }
+
+    *body += "<table>\n<tr><th>Field</th><th>Type</th><th>Docs</th></tr>\n";
+    for field in data.iter() {
+        let mut typing = render_type(&field.typing);
+        if field.optional {
+            typing = format!("optional {typing}");
+        }
+        if field.flattened {
+            typing = format!("flattened {typing}");
+        }
+
+        let _ = writeln!(
+            body,
+            "<tr><td><code>{}</code></td><td>{typing}</td><td class=\"docs\">{}</td></tr>",
+            escape(&field.name),
+            field.docs.as_deref().map(escape).unwrap_or_default()
+        );
+    }
+    *body += "</table>\n";
+}
+
+/// Returns an HTML description of `typing`, linking
+/// to the rendering of any data type it refers to,
+/// and listing the variants of any enum.
+fn render_type(typing: &Type) -> String {
+    match typing {
+        Type::Data(data) => {
+            let name = escape(&data.name);
+            format!("<a href=\"#{name}\"><code>{name}</code></a>")
+        }
+        Type::Enum(typing) => {
+            let variants: Vec<String> = typing
+                .variants
+                .iter()
+                .map(|variant| format!("<code>{}</code>", escape(variant)))
+                .collect();
+            format!(
+                "<code>{}</code> (one of {})",
+                escape(&typing.name),
+                variants.join(", ")
+            )
+        }
+        Type::List(typing) => format!("list of {}", render_type(typing)),
+        Type::Map(typing) => format!(
+            "map of {} to {}",
+            render_type(&typing.0),
+            render_type(&typing.1)
+        ),
+        typing => {
+            let name = format!("{typing:?}").to_lowercase();
+            format!("<code>{name}</code>")
+        }
+    }
+}
+
+/// Returns `text` with HTML special characters escaped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&#39;",
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use codas::parse;
+
+    use super::*;
+
+    fn discovered(markdown: &str) -> Vec<DiscoveredCoda> {
+        vec![DiscoveredCoda {
+            path: PathBuf::from("greeter.md"),
+            coda: parse::parse(markdown).unwrap(),
+            output_stem: String::from("greeter"),
+        }]
+    }
+
+    #[test]
+    fn routes_requests() {
+        let codas = discovered(
+            "# `Greeter` Coda\n\nSays <hello>.\n\n## `Request` Data\n\n+ `message` text\n\n    The message.\n\n## `Response` Data\n\n+ `request` optional Request\n+ `mood` one of [Happy, Sad]\n",
+        );
+
+        let index = route("/", &codas);
+        assert_eq!("200 OK", index.status);
+        let index = String::from_utf8(index.body).unwrap();
+        assert!(index.contains("<a href=\"/coda/greeter\"><code>Greeter</code></a>"));
+
+        let page = route("/coda/greeter", &codas);
+        assert_eq!("200 OK", page.status);
+        let page = String::from_utf8(page.body).unwrap();
+        assert!(page.contains("Says &lt;hello&gt;."));
+        assert!(page.contains("<td class=\"docs\">The message.</td>"));
+        assert!(page.contains("optional <a href=\"#Request\"><code>Request</code></a>"));
+        assert!(page
+            .contains("<code>ResponseMood</code> (one of <code>Happy</code>, <code>Sad</code>)"));
+        assert!(page.contains("<a href=\"/coda/greeter/schema\">"));
+        assert!(!page.contains("http://") && !page.contains("https://"));
+
+        let spec = route("/coda/greeter/openapi", &codas);
+        assert_eq!("200 OK", spec.status);
+        assert!(spec.content_type.starts_with("application/yaml"));
+        assert!(!spec.body.is_empty());
+
+        let schema = route("/coda/greeter/schema?download", &codas);
+        assert_eq!("200 OK", schema.status);
+        assert_eq!("application/schema+json", schema.content_type);
+        assert!(!schema.body.is_empty());
+
+        assert_eq!("404 Not Found", route("/coda/missing", &codas).status);
+        assert_eq!("404 Not Found", route("/coda/greeter/other", &codas).status);
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use codas::{
+    parse::{self, ParseError},
+    types::{ValidationIssue, ValidationSeverity},
+};
+
+use super::{
+    compile::{collect_md_files, discover_codas},
+    ValidateCommand,
+};
+
+/// Executes `command` locally.
+pub fn execute_validate_command(command: ValidateCommand) {
+    let source = command
+        .source
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+
+    // In directory mode, markdown files that aren't
+    // codas are skipped instead of reported.
+    let mut paths = Vec::new();
+    let in_dir = source.is_dir();
+    if in_dir {
+        collect_md_files(&source, &mut paths);
+        paths.sort();
+    } else {
+        paths.push(source.clone());
+    }
+
+    let mut diagnostics = Vec::new();
+    for path in &paths {
+        match fs::read_to_string(path) {
+            Ok(markdown) => diagnostics.extend(validate_markdown(path, &markdown, in_dir)),
+            Err(error) => diagnostics.push(Diagnostic {
+                path: path.clone(),
+                line: 1,
+                column: 1,
+                severity: ValidationSeverity::Error,
+                message: format!("failed to read file: {error}"),
+            }),
+        }
+    }
+
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+
+    let mut errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == ValidationSeverity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    // Codas in different files may not share a global name.
+    if in_dir {
+        if let Err(error) = discover_codas(&source, false) {
+            println!("error: {error}");
+            errors += 1;
+        }
+    }
+
+    eprintln!(
+        "validated {} file(s): {errors} error(s), {warnings} warning(s)",
+        paths.len()
+    );
+
+    if errors > 0 || (command.strict && warnings > 0) {
+        std::process::exit(1);
+    }
+}
+
+/// Parses and validates the coda in `markdown`, read
+/// from `path`, returning any resulting diagnostics.
+///
+/// If `skip_non_codas` is true and `markdown` doesn't
+/// start with a coda header, no diagnostics are returned.
+fn validate_markdown(path: &Path, markdown: &str, skip_non_codas: bool) -> Vec<Diagnostic> {
+    let coda = match parse::parse(markdown) {
+        Ok(coda) => coda,
+        Err(ParseError::ExpectedCoda { .. }) if skip_non_codas => return vec![],
+        Err(error) => {
+            let (line, column) = error
+                .location()
+                .map(|location| (location.line, location.column))
+                .unwrap_or((1, 1));
+            return vec![Diagnostic {
+                path: path.to_path_buf(),
+                line,
+                column,
+                severity: ValidationSeverity::Error,
+                message: error.to_string(),
+            }];
+        }
+    };
+
+    coda.validate()
+        .into_iter()
+        .map(|issue| {
+            let (line, column) = locate(markdown, &issue);
+            Diagnostic {
+                path: path.to_path_buf(),
+                line,
+                column,
+                severity: issue.severity,
+                message: issue.message.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the line and column in `markdown` declaring
+/// the data type (or field) with `issue`, or `(1, 1)` if
+/// the declaration can't be found.
+fn locate(markdown: &str, issue: &ValidationIssue) -> (usize, usize) {
+    let type_name = format!("`{}`", issue.data_type);
+    let field_name = issue.field.as_ref().map(|field| format!("`{field}`"));
+
+    let mut in_type = false;
+    for (i, line) in markdown.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let byte = if trimmed.starts_with('#') {
+            in_type = trimmed.contains(&type_name);
+            match (in_type, &field_name) {
+                (true, None) => line.find(&type_name),
+                _ => None,
+            }
+        } else if let (true, Some(field_name)) = (in_type, &field_name) {
+            let item = trimmed.trim_start_matches(['+', '*', '-']).trim_start();
+            (item.len() < trimmed.len() && item.starts_with(field_name.as_str()))
+                .then(|| line.len() - item.len())
+        } else {
+            None
+        };
+
+        if let Some(byte) = byte {
+            return (i + 1, line[..byte].chars().count() + 1);
+        }
+    }
+
+    (1, 1)
+}
+
+/// A validation issue located in a source file.
+struct Diagnostic {
+    path: PathBuf,
+    line: usize,
+    column: usize,
+    severity: ValidationSeverity,
+    message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.severity,
+            self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_issues() {
+        let markdown = "# `Greeter` Coda\n\n## `Request` Data\n\n+ `reply` Response\n+ `sender` Sender\n\n## `Response` Data\n";
+        let path = Path::new("greeter.md");
+
+        let diagnostics: Vec<String> = validate_markdown(path, markdown, false)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            vec![
+                "greeter.md:6:3: error: field `sender` refers to unknown type `Sender`",
+                "greeter.md:8:4: warning: data type `Response` has no fields",
+            ],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let path = Path::new("notes.md");
+        assert!(validate_markdown(path, "Some notes.", true).is_empty());
+
+        let diagnostics = validate_markdown(path, "Some notes.", false);
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(ValidationSeverity::Error, diagnostics[0].severity);
+    }
+}
@@ -0,0 +1,24 @@
+use std::io::Write;
+
+use codas::codec::vectors::{test_vectors, vectors_to_json, vectors_to_markdown};
+
+use super::{VectorsCommand, VectorsFormat};
+
+/// Executes `command` locally.
+pub fn execute_vectors_command(command: VectorsCommand) {
+    let vectors = test_vectors();
+    let rendered = match command.format {
+        VectorsFormat::Json => vectors_to_json(&vectors),
+        VectorsFormat::Markdown => vectors_to_markdown(&vectors),
+    };
+
+    match command.target {
+        Some(path) => {
+            std::fs::write(&path, rendered.as_bytes()).expect("failed to write target");
+            eprintln!("wrote {} vectors to {}", vectors.len(), path.display());
+        }
+        None => std::io::stdout()
+            .write_all(rendered.as_bytes())
+            .expect("failed to write vectors"),
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, SystemTime},
+};
+
+use codas::parse::{self, ParseError};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use super::{
+    compile::{collect_md_files, compile_codas, discover_codas, ALL_LANGS},
+    CompileCommand, Lang, WatchCommand,
+};
+
+/// Delay after a change during which further
+/// changes are batched into one recompilation.
+pub(super) const DEBOUNCE_DELAY: Duration = Duration::from_millis(50);
+
+/// Executes `command` locally.
+pub fn execute_watch_command(command: WatchCommand) {
+    let source = command
+        .source
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+    let source = match fs::canonicalize(&source) {
+        Ok(source) if source.is_dir() => source,
+        _ => {
+            eprintln!(
+                "error: --source must be a directory (got {})",
+                source.display()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let compile = CompileCommand {
+        source: Some(source.clone()),
+        target: command.target,
+        lang: command.lang,
+        allow_duplicate_names: command.allow_duplicate_names,
+        builder: command.builder,
+    };
+    let languages = match command.lang {
+        Some(lang) => vec![lang],
+        None => ALL_LANGS.to_vec(),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).expect("failed to create watcher");
+    watcher
+        .watch(&source, RecursiveMode::Recursive)
+        .expect("failed to watch source");
+
+    // Compile everything once, so that the
+    // outputs start in sync with the sources.
+    let mut paths = Vec::new();
+    collect_md_files(&source, &mut paths);
+    recompile(&source, paths.into_iter().collect(), &languages, &compile);
+    eprintln!("watching {} for changes", source.display());
+
+    while let Ok(event) = receiver.recv() {
+        let mut changed = BTreeSet::new();
+        collect_changes(event, &mut changed);
+
+        // Batch changes until they stop for the debounce delay.
+        loop {
+            match receiver.recv_timeout(DEBOUNCE_DELAY) {
+                Ok(event) => collect_changes(event, &mut changed),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if !changed.is_empty() {
+            recompile(&source, changed, &languages, &compile);
+        }
+    }
+}
+
+/// Adds the paths of any created or modified
+/// markdown files in `event` to `changed`.
+pub(super) fn collect_changes(event: notify::Result<Event>, changed: &mut BTreeSet<PathBuf>) {
+    let event = match event {
+        Ok(event) => event,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return;
+        }
+    };
+
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return;
+    }
+
+    for path in event.paths {
+        if path.extension().is_some_and(|ext| ext == "md") && path.is_file() {
+            changed.insert(fs::canonicalize(&path).unwrap_or(path));
+        }
+    }
+}
+
+/// Recompiles the codas at `changed` paths in `source`
+/// to `languages`, printing any errors without exiting.
+fn recompile(
+    source: &Path,
+    changed: BTreeSet<PathBuf>,
+    languages: &[Lang],
+    command: &CompileCommand,
+) {
+    // Report parse errors in changed codas, which
+    // are otherwise skipped during discovery.
+    for path in &changed {
+        let markdown = match fs::read_to_string(path) {
+            Ok(markdown) => markdown,
+            Err(error) => {
+                eprintln!("error: failed to read {}: {error}", path.display());
+                continue;
+            }
+        };
+
+        match parse::parse(&markdown) {
+            Ok(_) | Err(ParseError::ExpectedCoda { .. }) => {}
+            Err(error) => eprintln!("error: {}: {error}", path.display()),
+        }
+    }
+
+    // Discover all codas, so that output
+    // file names match a full compilation.
+    let codas = match discover_codas(source, command.allow_duplicate_names) {
+        Ok(codas) => codas,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return;
+        }
+    };
+    let codas: Vec<_> = codas
+        .into_iter()
+        .filter(|discovered| changed.contains(&discovered.path))
+        .collect();
+
+    if codas.is_empty() {
+        return;
+    }
+
+    compile_codas(&codas, languages, command);
+    eprintln!("[{}] Recompiled {} files", timestamp(), codas.len());
+}
+
+/// Returns the current (UTC) time of day as `HH:MM:SS`.
+fn timestamp() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default()
+        % (24 * 60 * 60);
+
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
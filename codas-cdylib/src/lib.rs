@@ -5,15 +5,19 @@
 #[cfg(all(feature = "wasm", feature = "python"))]
 compile_error!("features `wasm` and `python` are mutually exclusive");
 
+use std::collections::BTreeMap;
+
 use ::codas::{
     parse::ParseError,
     stream::StreamError,
     types::{
         binary::{self, hex_from_bytes, BinaryError},
         cryptography::{
-            CryptoError, CryptoKeys, CryptoSigns, EncryptedData, HasCryptoPublicKey,
-            PrivateKeyBytes,
+            CryptoError, CryptoKeys, CryptoSigns, CryptoVerifier, CryptoVerifies, EncryptedData,
+            HasCryptoPublicKey, PrivateKeyBytes, PublicKeyBytes, SignatureBytes,
         },
+        dynamic::{decode_typed, to_json, TypedDecodeError},
+        Unspecified,
     },
 };
 
@@ -24,6 +28,7 @@ fn codas(m: &pyo3::prelude::Bound<'_, pyo3::prelude::PyModule>) -> pyo3::prelude
 
     m.add_function(wrap_pyfunction!(parse, m)?)?;
     m.add_function(wrap_pyfunction!(codegen, m)?)?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
     Ok(())
 }
 
@@ -45,10 +50,16 @@ pub fn codegen(coda: &Coda, language: &str) -> Result<String, Error> {
     match language.trim().to_lowercase().as_str() {
         "open-api" => ::codas::langs::open_api::generate_spec(&coda.coda, &mut codegen),
         "python" => ::codas::langs::python::generate_types(&coda.coda, &mut codegen),
-        "rust" => ::codas::langs::rust::generate_types(&coda.coda, &mut codegen, true),
+        "rust" => {
+            let options = ::codas::langs::rust::RustCodegenOptions {
+                serde: true,
+                ..Default::default()
+            };
+            ::codas::langs::rust::generate_types(&coda.coda, &mut codegen, &options)
+        }
         "typescript" => ::codas::langs::typescript::generate_types(&coda.coda, &mut codegen),
         language => {
-            return Err(Error::Internal(format!(
+            return Err(Error::Unsupported(format!(
                 "unsuppored coda codegen language: {language}"
             )))
         }
@@ -57,6 +68,28 @@ pub fn codegen(coda: &Coda, language: &str) -> Result<String, Error> {
     Ok(String::from_utf8_lossy(&codegen).to_string())
 }
 
+/// ## Unstable
+///
+/// Decodes `bytes` containing data of any type in `coda`,
+/// returning a JSON object of the data's type name to the
+/// data's value (like `{"MyDataType": {"field": 1}}`).
+///
+/// The data's type is identified by the ordinal in its
+/// header. Any bytes following the data are ignored.
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyfunction)]
+pub fn decode(coda: &Coda, bytes: &[u8]) -> Result<String, Error> {
+    let (name, value) = decode_typed(&coda.coda, &mut &bytes[..])?;
+
+    let mut json = vec![];
+    to_json(
+        &Unspecified::Map(BTreeMap::from([(name, value)])),
+        &mut json,
+    )?;
+
+    Ok(String::from_utf8_lossy(&json).to_string())
+}
+
 /// ## Unstable
 ///
 /// Encrypts `string` with `key`, returning
@@ -117,6 +150,60 @@ pub fn sign(private_key: &str, message: &str) -> Result<String, Error> {
     Ok(binary::hex_from_bytes(&private_key.sign(&[message.as_bytes()])?).to_string())
 }
 
+/// ## Unstable
+///
+/// Generates a new pair of HEX-encoded keys.
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyfunction)]
+pub fn generate_keys() -> Result<KeyPair, Error> {
+    let keys = CryptoKeys::generate();
+    let public_key = binary::hex_from_bytes(&keys.public_key_bytes()).to_string();
+    let private_key = binary::hex_from_bytes(&keys.into_private()).to_string();
+
+    Ok(KeyPair {
+        private_key,
+        public_key,
+    })
+}
+
+/// ## Unstable
+///
+/// Returns true iff the HEX-encoded `signature` is a valid
+/// signature from `public_key` (also HEX-encoded) for `message`.
+///
+/// Returns an error if `public_key` or `signature`
+/// aren't valid HEX, or `public_key` isn't a valid key.
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyfunction)]
+pub fn verify_signature(public_key: &str, message: &str, signature: &str) -> Result<bool, Error> {
+    let mut public_key_bytes = PublicKeyBytes::NULL;
+    public_key_bytes.from_hex(public_key)?;
+    let verifier = CryptoVerifier::try_from(&public_key_bytes)?;
+
+    let mut signature_bytes = SignatureBytes::NULL;
+    signature_bytes.from_hex(signature)?;
+
+    match verifier.verify(&[message.as_bytes()], &signature_bytes) {
+        Ok(()) => Ok(true),
+        Err(CryptoError::InvalidSignature { .. }) => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// HEX-encoded pair of keys returned by [`generate_keys`].
+#[cfg_attr(
+    feature = "wasm",
+    wasm_bindgen::prelude::wasm_bindgen(getter_with_clone)
+)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyclass(get_all))]
+pub struct KeyPair {
+    /// HEX-encoded private key.
+    pub private_key: String,
+
+    /// HEX-encoded public key.
+    pub public_key: String,
+}
+
 /// Exported representation of a [`::codas::types::Coda`].
 #[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass)]
@@ -124,58 +211,215 @@ pub struct Coda {
     coda: ::codas::types::Coda,
 }
 
-#[derive(Debug)]
+/// Error returned by this library's functions.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Error {
-    Internal(String),
+    /// A coda's Markdown couldn't be parsed.
+    Parse {
+        message: String,
+
+        /// The line (starting from `1`) containing
+        /// the unparseable text, if known.
+        line: Option<usize>,
+
+        /// The column (starting from `1`) of
+        /// the unparseable text, if known.
+        column: Option<usize>,
+    },
+
+    /// A cryptographic operation failed.
+    Crypto(String),
+
+    /// Binary data (like encoded data or
+    /// HEX-encoded keys) was malformed.
+    Binary(String),
+
+    /// Data couldn't be written to a stream.
+    Stream(String),
+
+    /// An unsupported operation was requested.
+    Unsupported(String),
 }
 
+impl Error {
+    /// Returns the name of this error's kind (like `"parse"`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Parse { .. } => "parse",
+            Error::Crypto(_) => "crypto",
+            Error::Binary(_) => "binary",
+            Error::Stream(_) => "stream",
+            Error::Unsupported(_) => "unsupported",
+        }
+    }
+
+    /// Returns this error's human-readable message.
+    pub fn message(&self) -> &str {
+        match self {
+            Error::Parse { message, .. }
+            | Error::Crypto(message)
+            | Error::Binary(message)
+            | Error::Stream(message)
+            | Error::Unsupported(message) => message,
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<ParseError> for Error {
     fn from(value: ParseError) -> Self {
-        Self::Internal(value.to_string())
+        let location = value.location();
+        Self::Parse {
+            line: location.map(|location| location.line),
+            column: location.map(|location| location.column),
+            message: value.to_string(),
+        }
     }
 }
 
 impl From<StreamError> for Error {
     fn from(value: StreamError) -> Self {
-        Self::Internal(value.to_string())
+        Self::Stream(value.to_string())
     }
 }
 
 impl From<BinaryError> for Error {
     fn from(value: BinaryError) -> Self {
-        Self::Internal(value.to_string())
+        Self::Binary(value.to_string())
+    }
+}
+
+impl From<TypedDecodeError> for Error {
+    fn from(value: TypedDecodeError) -> Self {
+        Self::Binary(value.to_string())
     }
 }
 
 impl From<CryptoError> for Error {
     fn from(value: CryptoError) -> Self {
-        Self::Internal(value.to_string())
+        Self::Crypto(value.to_string())
     }
 }
 
+/// Errors become JS objects like
+/// `{ kind, message, line, column }`, with
+/// a `null` line and column unless the
+/// error is a `"parse"` error with a location.
 #[cfg(feature = "wasm")]
 impl From<Error> for wasm_bindgen::JsValue {
     fn from(value: Error) -> Self {
-        match value {
-            Error::Internal(value) => value.into(),
+        use wasm_bindgen::JsValue;
+
+        let (line, column) = match &value {
+            Error::Parse { line, column, .. } => (*line, *column),
+            _ => (None, None),
+        };
+        let position = |position: Option<usize>| match position {
+            Some(position) => JsValue::from(position as f64),
+            None => JsValue::NULL,
+        };
+
+        let object = js_sys::Object::new();
+        for (key, field) in [
+            ("kind", JsValue::from(value.kind())),
+            ("message", JsValue::from(value.message())),
+            ("line", position(line)),
+            ("column", position(column)),
+        ] {
+            let _ = js_sys::Reflect::set(&object, &JsValue::from(key), &field);
         }
+
+        object.into()
     }
 }
 
+/// Crypto and stream errors raise a `RuntimeError`;
+/// all other errors raise a `ValueError`.
 #[cfg(feature = "python")]
 impl From<Error> for pyo3::PyErr {
     fn from(value: Error) -> Self {
+        use pyo3::exceptions::{PyRuntimeError, PyValueError};
+
         match value {
-            Error::Internal(value) => pyo3::exceptions::PyValueError::new_err(value),
+            Error::Crypto(message) | Error::Stream(message) => PyRuntimeError::new_err(message),
+            Error::Parse { message, .. } | Error::Binary(message) | Error::Unsupported(message) => {
+                PyValueError::new_err(message)
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use codas::types::binary::bytes_from_hex;
+    use codas::{
+        codec::{DataFormat, DataHeader, WritesEncodable},
+        types::{binary::bytes_from_hex, Text},
+    };
 
-    use crate::{decrypt_hex, encrypt_str};
+    use crate::{
+        codegen, decode, decrypt_hex, encrypt_str, extract_public_key, generate_keys, parse, sign,
+        verify_signature, Error,
+    };
+
+    #[test]
+    pub fn decoding() {
+        let coda = parse("# `Web` Coda\n\n## `Point` Data\n\n+ `n` u32\n+ `s` text\n").unwrap();
+
+        let mut bytes = vec![];
+        bytes
+            .write_data(&DataHeader {
+                count: 1,
+                format: DataFormat {
+                    blob_size: 4,
+                    data_fields: 1,
+                    ordinal: 1,
+                },
+            })
+            .unwrap();
+        bytes.write_data(&5u32).unwrap();
+        bytes.write_data(&Text::from("hi")).unwrap();
+        assert_eq!(
+            r#"{"Point":{"n":5,"s":"hi"}}"#,
+            decode(&coda, &bytes).unwrap()
+        );
+
+        // Errors include the path of the undecodable data.
+        let error = decode(&coda, &bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!("binary", error.kind());
+        assert!(error.message().contains("`Point.s`"), "{error}");
+    }
+
+    #[test]
+    pub fn errors() {
+        // Parse errors include their location.
+        let Err(Error::Parse { line, column, .. }) =
+            parse("# `Web` Coda\n\n## `Point` Data\n\n+ `n` u32\n+ `n` text\n")
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((Some(6), Some(1)), (line, column));
+
+        let Err(error) = codegen(&parse("# `Web` Coda").unwrap(), "cobol") else {
+            panic!("expected an unsupported language");
+        };
+        assert!(matches!(error, Error::Unsupported(_)), "{error:?}");
+
+        let Err(error) = extract_public_key("not hex") else {
+            panic!("expected malformed HEX");
+        };
+        assert_eq!("binary", error.kind());
+        let Err(error) = decrypt_hex("key", "not hex") else {
+            panic!("expected malformed encrypted data");
+        };
+        assert_eq!("crypto", error.kind());
+    }
 
     #[test]
     pub fn encryption() {
@@ -186,4 +430,26 @@ mod test {
             String::from_utf8_lossy(&bytes_from_hex(&decrypted).unwrap())
         );
     }
+
+    #[test]
+    pub fn signing() {
+        let keys = generate_keys().unwrap();
+        assert_eq!(
+            keys.public_key,
+            extract_public_key(&keys.private_key).unwrap()
+        );
+
+        let signature = sign(&keys.private_key, "message").unwrap();
+        assert!(verify_signature(&keys.public_key, "message", &signature).unwrap());
+        assert!(!verify_signature(&keys.public_key, "massage", &signature).unwrap());
+
+        // Signatures from other keys are invalid.
+        let other = generate_keys().unwrap();
+        assert_ne!(keys.private_key, other.private_key);
+        assert!(!verify_signature(&other.public_key, "message", &signature).unwrap());
+
+        // Malformed keys and signatures are errors.
+        assert!(verify_signature("not hex", "message", &signature).is_err());
+        assert!(verify_signature(&keys.public_key, "message", "not hex").is_err());
+    }
 }
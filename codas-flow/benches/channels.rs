@@ -1,3 +1,7 @@
+// Benchmarks run on a single thread, so RefCells
+// borrowed across await points are never contended.
+#![allow(clippy::await_holding_refcell_ref)]
+
 use std::cell::RefCell;
 
 use codas_flow::{
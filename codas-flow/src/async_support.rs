@@ -7,6 +7,8 @@ use core::{
     task::{Poll, RawWaker, RawWakerVTable, Waker},
 };
 
+use crate::Error;
+
 /// Returns a future that becomes ready
 /// after one poll, emulating a yield on
 /// most async runtimes.
@@ -40,6 +42,47 @@ impl Future for YieldNow {
     }
 }
 
+/// Future which races a future against a deadline.
+///
+/// This future completes with the output of `future`
+/// if it completes before `deadline`, and completes with
+/// [`Error::TimedOut`] otherwise. `future` is always
+/// polled before `deadline`.
+pub(crate) struct BeforeDeadline<F, D> {
+    future: F,
+    deadline: D,
+}
+
+impl<F, D> BeforeDeadline<F, D> {
+    /// Returns a new future racing `future` against `deadline`.
+    pub(crate) fn new(future: F, deadline: D) -> Self {
+        Self { future, deadline }
+    }
+}
+
+impl<F, D, T> Future for BeforeDeadline<F, D>
+where
+    F: Future<Output = Result<T, Error>> + Unpin,
+    D: Future<Output = ()>,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        // Safety: `deadline` is never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if let Poll::Ready(result) = Pin::new(&mut this.future).poll(cx) {
+            return Poll::Ready(result);
+        }
+
+        let deadline = unsafe { Pin::new_unchecked(&mut this.deadline) };
+        match deadline.poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Error::TimedOut)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 /// Returns an asynchronous [`Waker`] that
 /// does nothing at all.
 ///
@@ -0,0 +1,203 @@
+//! Flows of coda-encoded byte frames.
+//!
+//! Publishing encoded data into a `Flow<Vec<u8>>` reuses
+//! each slot's allocation, but nothing stops a slot
+//! from growing to fit the largest frame it ever held.
+//! [`FrameBuffer`]s cap the capacity each slot retains
+//! between publishes, keeping allocations stable even
+//! when frame sizes vary wildly.
+
+use core::ops::Deref;
+
+use alloc::vec::Vec;
+use codas::codec::{CodecError, Encodable, WritesEncodable};
+
+use crate::UnpublishedData;
+
+/// Default maximum capacity retained by
+/// a [`FrameBuffer`] between publishes (64 KiB).
+pub const DEFAULT_MAX_FRAME_CAPACITY: usize = 64 * 1024;
+
+/// Capacity-capped buffer of coda-encoded bytes.
+///
+/// Any capacity in excess of [`Self::max_capacity`] is
+/// released each time a new frame is encoded into the
+/// buffer. Frames larger than the maximum capacity are
+/// still encoded in full; their excess capacity is
+/// released when the buffer is next reused.
+///
+/// Flows created with [`Flow::new`](crate::Flow::new) contain
+/// buffers with the [`DEFAULT_MAX_FRAME_CAPACITY`]; other
+/// maximums may be set for every buffer in a flow via
+/// [`Flow::new_with_init`](crate::Flow::new_with_init),
+/// or for a single buffer via [`Self::set_max_capacity`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameBuffer {
+    bytes: Vec<u8>,
+    max_capacity: usize,
+}
+
+impl FrameBuffer {
+    /// Returns a new, empty buffer which retains no
+    /// more than `max_capacity` bytes between frames.
+    pub fn with_max_capacity(max_capacity: usize) -> Self {
+        Self {
+            bytes: Vec::new(),
+            max_capacity,
+        }
+    }
+
+    /// Returns the maximum capacity retained
+    /// by this buffer between frames.
+    pub fn max_capacity(&self) -> usize {
+        self.max_capacity
+    }
+
+    /// Sets the maximum capacity retained by this buffer
+    /// between frames, taking effect when the next
+    /// frame is encoded into the buffer.
+    pub fn set_max_capacity(&mut self, max_capacity: usize) {
+        self.max_capacity = max_capacity;
+    }
+
+    /// Returns the capacity currently
+    /// allocated by this buffer.
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Replaces the contents of this buffer with
+    /// the coda-encoded bytes of `data`.
+    ///
+    /// If encoding fails, the buffer is left empty.
+    pub fn encode_from(&mut self, data: &(impl Encodable + ?Sized)) -> Result<(), CodecError> {
+        self.bytes.clear();
+        self.bytes.shrink_to(self.max_capacity);
+        if let Err(error) = self.bytes.write_data(data) {
+            self.bytes.clear();
+            return Err(error);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        Self::with_max_capacity(DEFAULT_MAX_FRAME_CAPACITY)
+    }
+}
+
+impl Deref for FrameBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl AsRef<[u8]> for FrameBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl UnpublishedData<'_, FrameBuffer> {
+    /// Encodes `data` into this sequence's
+    /// [`FrameBuffer`] and publishes it.
+    ///
+    /// If encoding fails, an empty frame is published
+    /// instead, so subscribers never receive a
+    /// partially encoded frame.
+    pub fn encode_from(mut self, data: &(impl Encodable + ?Sized)) -> Result<(), CodecError> {
+        FrameBuffer::encode_from(&mut self, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codas::{
+        codec::{Format, ReadsDecodable},
+        stream::StreamError,
+        types::Text,
+    };
+
+    use crate::Flow;
+
+    use super::*;
+
+    #[test]
+    fn capacity_stays_bounded() {
+        let max_capacity = 256;
+        let (mut flow, [mut sub]) = Flow::<FrameBuffer>::new(2);
+
+        for size in [8usize, 10_000, 16, 100_000, 0, 64, 50_000, 1] {
+            let payload: Vec<u8> = (0..size).map(|i| i as u8).collect();
+
+            let mut next = flow.try_next().unwrap();
+            next.set_max_capacity(max_capacity);
+            next.encode_from(&payload).unwrap();
+
+            let frame = sub.try_next().unwrap();
+            let decoded: Vec<u8> = (&frame[..]).read_data().unwrap();
+            assert_eq!(payload, decoded);
+
+            // Small frames never retain more than the maximum capacity,
+            // even if the slot previously held a very large frame.
+            if frame.len() <= max_capacity {
+                assert!(
+                    frame.capacity() <= max_capacity,
+                    "capacity {} exceeds {max_capacity} for a {size} byte payload",
+                    frame.capacity()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sets_max_capacity_of_flows() {
+        let (mut flow, [mut sub]) = Flow::new_with_init(2, || FrameBuffer::with_max_capacity(16));
+        flow.try_next()
+            .unwrap()
+            .encode_from(&[0u8; 64][..])
+            .unwrap();
+        assert_eq!(16, sub.try_next().unwrap().max_capacity());
+    }
+
+    #[test]
+    fn publishes_empty_frames_on_error() {
+        /// Data which fails to encode after
+        /// writing some of its bytes.
+        struct Torn;
+
+        impl Encodable for Torn {
+            const FORMAT: Format = Format::data(0).with(u64::FORMAT);
+
+            fn encode(
+                &self,
+                writer: &mut (impl WritesEncodable + ?Sized),
+            ) -> Result<(), CodecError> {
+                writer.write_data(&42u64)?;
+                Err(StreamError::Closed.into())
+            }
+        }
+
+        let (mut flow, [mut sub]) = Flow::<FrameBuffer>::new(2);
+        flow.try_next().unwrap().encode_from(&1u64).unwrap();
+        assert!(flow.try_next().unwrap().encode_from(&Torn).is_err());
+
+        assert!(!sub.try_next().unwrap().is_empty());
+        assert!(sub.try_next().unwrap().is_empty());
+    }
+
+    #[test]
+    fn encodes_frames() {
+        let (mut flow, [mut sub]) = Flow::<FrameBuffer>::new(2);
+        let text = Text::from("Hello, frames!");
+        flow.try_next().unwrap().encode_from(&text).unwrap();
+
+        let frame = sub.try_next().unwrap();
+        let decoded: Text = (&frame[..]).read_data().unwrap();
+        assert_eq!(text, decoded);
+    }
+}
@@ -6,7 +6,6 @@
 extern crate alloc;
 
 use core::{
-    cell::UnsafeCell,
     fmt::Debug,
     future::Future,
     ops::{Deref, DerefMut, Range},
@@ -16,18 +15,21 @@ use core::{
 };
 
 use alloc::{boxed::Box, vec::Vec};
-use portable_atomic::AtomicU64;
-use portable_atomic_util::{Arc, Weak};
+use async_support::BeforeDeadline;
 use snafu::Snafu;
+use sync::{spin_loop, Arc, AtomicU64, ConstPtr, MutPtr, UnsafeCell};
 
 pub mod async_support;
+#[cfg(any(feature = "codas", test))]
+pub mod frame;
 pub mod stage;
+mod sync;
 
 /// Bounded queue for publishing and receiving
 /// data from (a)synchronous tasks.
 ///
 /// Refer to the [crate] docs for more info.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Flow<T: Flows> {
     state: Arc<FlowState<T>>,
 }
@@ -43,39 +45,56 @@ impl<T: Flows> Flow<T> {
     /// (like `2`, `32`, `256`, and so on).
     pub fn new<const SUB: usize>(capacity: usize) -> (Self, [FlowSubscriber<T>; SUB])
     where
-        T: Default,
+        T: FlowsDefault,
     {
+        Self::new_with_init(capacity, T::default)
+    }
+
+    /// Returns a tuple of `(flow, [subscribers])`,
+    /// where `capacity` is the maximum capacity
+    /// of the flow, and each of the flow's data
+    /// is initialized by invoking `init`.
+    ///
+    /// Unlike [`Self::new`], this function does
+    /// not require that `T` implement [`Default`].
+    ///
+    /// # Panics
+    ///
+    /// Iff `capacity` is _not_ a power of two
+    /// (like `2`, `32`, `256`, and so on).
+    pub fn new_with_init<const SUB: usize>(
+        capacity: usize,
+        mut init: impl FnMut() -> T,
+    ) -> (Self, [FlowSubscriber<T>; SUB]) {
         assert!(capacity & (capacity - 1) == 0, "flow capacity _must_ be a power of two (like `2`, `4`, `256`, `2048`...), not {capacity}");
 
         // Allocate the flow buffer.
         let mut buffer = Vec::with_capacity(capacity);
         for _ in 0..capacity {
-            buffer.push(UnsafeCell::new(T::default()));
+            buffer.push(UnsafeCell::new(init()));
         }
         let buffer = buffer.into_boxed_slice();
 
+        // Allocate subscriber sequences.
+        let mut next_receivable_seqs = Vec::with_capacity(SUB);
+        for _ in 0..SUB {
+            next_receivable_seqs.push(AtomicU64::new(0));
+        }
+
         // Build the flow state.
-        let mut flow_state = FlowState {
+        let flow_state = Arc::new(FlowState {
             buffer,
             next_writable_seq: AtomicU64::new(0),
             next_publishable_seq: AtomicU64::new(0),
-            next_receivable_seqs: Vec::with_capacity(SUB),
-        };
-
-        // Add subscribers to the state.
-        let mut subscriber_seqs = Vec::with_capacity(SUB);
-        for _ in 0..SUB {
-            subscriber_seqs.push(flow_state.add_subscriber_seq());
-        }
+            next_receivable_seqs: next_receivable_seqs.into_boxed_slice(),
+            publishers: AtomicU64::new(1),
+        });
 
-        // Finalize flow state and wrap subscriber
-        // sequences in the subscriber API.
-        let flow_state = Arc::new(flow_state);
-        let subscribers: Vec<FlowSubscriber<T>> = subscriber_seqs
-            .into_iter()
-            .map(|seq| FlowSubscriber {
+        // Wrap subscriber sequences in the subscriber API.
+        let subscribers: Vec<FlowSubscriber<T>> = (0..SUB)
+            .map(|index| FlowSubscriber {
                 flow_state: flow_state.clone(),
-                next_receivable_seq: seq,
+                index,
             })
             .collect();
 
@@ -97,6 +116,19 @@ impl<T: Flows> Flow<T> {
         PublishNextFuture { flow: self }
     }
 
+    /// Awaits and claims the next publishable sequence
+    /// in the flow, returning a [`UnpublishedData`]
+    /// iff successful before `deadline` completes.
+    ///
+    /// If `deadline` completes first, [`Error::TimedOut`]
+    /// is returned and no sequence is claimed.
+    pub fn next_before(
+        &mut self,
+        deadline: impl Future<Output = ()>,
+    ) -> impl Future<Output = Result<UnpublishedData<'_, T>, Error>> {
+        BeforeDeadline::new(PublishNextFuture { flow: self }, deadline)
+    }
+
     /// Implementation of [`Self::try_next`] that
     /// takes `self` as an immutable reference with
     /// interior mutability.
@@ -115,6 +147,21 @@ impl<T: Flows> Flow<T> {
     }
 }
 
+impl<T: Flows> Clone for Flow<T> {
+    fn clone(&self) -> Self {
+        self.state.publishers.fetch_add(1, Ordering::SeqCst);
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: Flows> Drop for Flow<T> {
+    fn drop(&mut self) {
+        self.state.publishers.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Future returned by [`Flow::next`].
 struct PublishNextFuture<'a, T: Flows> {
     flow: &'a Flow<T>,
@@ -175,22 +222,23 @@ struct FlowState<T: Flows> {
     /// All data entries with sequences less than
     /// the _lowest_ of these sequence numbers are
     /// assumed to be overwritable.
-    next_receivable_seqs: Vec<Weak<AtomicU64>>,
+    ///
+    /// When a subscriber is dropped, its sequence is
+    /// set to [`DROPPED_SUBSCRIBER_SEQ`] so that it
+    /// never blocks publishing.
+    next_receivable_seqs: Box<[AtomicU64]>,
+
+    /// The number of [`Flow`]s which may
+    /// publish data into the flow.
+    ///
+    /// When this number is zero, the flow is closed.
+    publishers: AtomicU64,
 }
 
 impl<T> FlowState<T>
 where
     T: Flows,
 {
-    /// Adds and returns a new subscriber sequence
-    /// number to the flow.
-    fn add_subscriber_seq(&mut self) -> Arc<AtomicU64> {
-        let next_receivable_seq = Arc::new(AtomicU64::new(0));
-        self.next_receivable_seqs
-            .push(Arc::downgrade(&next_receivable_seq));
-        next_receivable_seq
-    }
-
     /// Tries to claim and return the next
     /// publishable data sequence in the flow.
     ///
@@ -202,21 +250,26 @@ where
     /// Iff `None` is returned, the flow is full.
     #[inline(always)]
     fn try_claim_publishable(&self) -> Option<u64> {
-        let next_writable = self.next_writable_seq.load(Ordering::SeqCst);
-
-        // Calculate the minimum receivable sequence
-        // across all subscribers, defaulting to the
-        // current sequence that's publishable.
-        let mut min_receivable_seq = self.next_publishable_seq.load(Ordering::SeqCst);
-        for next_received_seq in self.next_receivable_seqs.iter() {
-            if let Some(seq) = next_received_seq.upgrade() {
-                min_receivable_seq = min_receivable_seq.min(seq.load(Ordering::SeqCst));
+        loop {
+            let next_writable = self.next_writable_seq.load(Ordering::SeqCst);
+
+            // Calculate the minimum receivable sequence
+            // across all subscribers, defaulting to the
+            // current sequence that's publishable.
+            let mut min_receivable_seq = self.next_publishable_seq.load(Ordering::SeqCst);
+            for next_received_seq in self.next_receivable_seqs.iter() {
+                min_receivable_seq =
+                    min_receivable_seq.min(next_received_seq.load(Ordering::SeqCst));
+            }
+
+            // Only claim if there's space.
+            if min_receivable_seq + self.buffer.len() as u64 <= next_writable {
+                return None;
             }
-        }
 
-        // Only claim if there's space.
-        if min_receivable_seq + self.buffer.len() as u64 > next_writable
-            && self
+            // Retry if another publisher claimed the sequence
+            // first, instead of spuriously reporting a full flow.
+            if self
                 .next_writable_seq
                 .compare_exchange(
                     next_writable,
@@ -225,11 +278,10 @@ where
                     Ordering::SeqCst,
                 )
                 .is_ok()
-        {
-            return Some(next_writable);
+            {
+                return Some(next_writable);
+            }
         }
-
-        None
     }
 
     /// Tries to publish `sequence`, returning
@@ -241,7 +293,7 @@ where
             .is_ok()
     }
 
-    /// Returns a reference to the data at `sequence`.
+    /// Returns a pointer to the data at `sequence`.
     ///
     /// Refer to [`Self::get_mut`] for information
     /// on the safety properties of this function.
@@ -250,19 +302,18 @@ where
     ///
     /// Iff any other thread attempts to acquire a _mutable_
     /// reference to `sequence` at the same time.
-    #[allow(clippy::mut_from_ref)]
     #[inline(always)]
-    unsafe fn get(&self, sequence: u64) -> &T {
+    unsafe fn get(&self, sequence: u64) -> ConstPtr<T> {
         assert!(self.buffer.len() & (self.buffer.len() - 1) == 0);
 
         // Convert sequence to an queue index.
         let index = (self.buffer.len() - 1) & sequence as usize;
 
         // Array access will always be within bounds.
-        &*self.buffer.get_unchecked(index).get()
+        self.buffer.get_unchecked(index).get()
     }
 
-    /// Returns a mutable reference to the data at `sequence`.
+    /// Returns a mutable pointer to the data at `sequence`.
     ///
     /// # Safety
     ///
@@ -286,16 +337,15 @@ where
     ///
     /// Iff the same or different tasks attempt to acquire
     /// more than one _mutable_ reference to `sequence`.
-    #[allow(clippy::mut_from_ref)]
     #[inline(always)]
-    unsafe fn get_mut(&self, sequence: u64) -> &mut T {
+    unsafe fn get_mut(&self, sequence: u64) -> MutPtr<T> {
         assert!(self.buffer.len() & (self.buffer.len() - 1) == 0);
 
         // Convert sequence to an queue index.
         let index = (self.buffer.len() - 1) & sequence as usize;
 
         // Array access will always be within bounds.
-        &mut *self.buffer.get_unchecked(index).get()
+        self.buffer.get_unchecked(index).get_mut()
     }
 }
 
@@ -309,6 +359,7 @@ where
             .field("next_writable_seq", &self.next_writable_seq)
             .field("next_publishable_seq", &self.next_publishable_seq)
             .field("next_receivable_seqs", &self.next_receivable_seqs)
+            .field("publishers", &self.publishers)
             .finish()
     }
 }
@@ -317,8 +368,9 @@ where
 pub struct FlowSubscriber<T: Flows> {
     flow_state: Arc<FlowState<T>>,
 
-    /// See [`FlowState::next_receivable_seqs`].
-    next_receivable_seq: Arc<AtomicU64>,
+    /// Index of this subscriber's sequence
+    /// in [`FlowState::next_receivable_seqs`].
+    index: usize,
 }
 
 impl<T: Flows> FlowSubscriber<T> {
@@ -336,6 +388,57 @@ impl<T: Flows> FlowSubscriber<T> {
         ReceiveNextFuture { subscriber: self }
     }
 
+    /// Awaits and returns a reference to the next
+    /// data in the flow, if the flow is active and
+    /// data is received before `deadline` completes.
+    ///
+    /// If `deadline` completes first, [`Error::TimedOut`]
+    /// is returned and no data is marked as received.
+    pub fn next_before(
+        &mut self,
+        deadline: impl Future<Output = ()>,
+    ) -> impl Future<Output = Result<impl Deref<Target = T> + '_, Error>> {
+        BeforeDeadline::new(ReceiveNextFuture { subscriber: self }, deadline)
+    }
+
+    /// Returns true iff the flow is closed, meaning
+    /// every [`Flow`] publishing into it was dropped.
+    ///
+    /// Once a flow is closed, no more data
+    /// will be published into it.
+    pub fn is_closed(&self) -> bool {
+        self.flow_state.publishers.load(Ordering::SeqCst) == 0
+    }
+
+    /// Returns clones of all data in the flow which
+    /// hasn't been received by this subscriber, in
+    /// the order the data was published, marking
+    /// the data as received.
+    ///
+    /// Iff the flow isn't [closed](Self::is_closed),
+    /// [`Error::Open`] is returned and no data
+    /// is marked as received.
+    pub fn drain(&mut self) -> Result<Vec<T>, Error>
+    where
+        T: Clone,
+    {
+        if !self.is_closed() {
+            return Err(Error::Open);
+        }
+
+        let receivable = self.receivable_seqs();
+        let mut drained = Vec::with_capacity((receivable.end - receivable.start) as usize);
+        for sequence in receivable.clone() {
+            drained.push(unsafe { self.flow_state.get(sequence).deref() }.clone());
+        }
+
+        if !receivable.is_empty() {
+            self.receive_up_to(receivable.end - 1);
+        }
+
+        Ok(drained)
+    }
+
     /// Implementation of [`Self::try_next`] that
     /// takes `self` as an immutable reference with
     /// interior mutability.
@@ -354,11 +457,19 @@ impl<T: Flows> FlowSubscriber<T> {
         }
     }
 
+    /// Returns this subscriber's sequence number.
+    ///
+    /// See [`FlowState::next_receivable_seqs`].
+    #[inline(always)]
+    fn next_receivable_seq(&self) -> &AtomicU64 {
+        &self.flow_state.next_receivable_seqs[self.index]
+    }
+
     /// Returns the range of data sequence numbers
     /// that are receivable by this subscriber.
     #[inline(always)]
     fn receivable_seqs(&self) -> Range<u64> {
-        self.next_receivable_seq.load(Ordering::SeqCst)
+        self.next_receivable_seq().load(Ordering::SeqCst)
             ..self.flow_state.next_publishable_seq.load(Ordering::SeqCst)
     }
 
@@ -366,11 +477,25 @@ impl<T: Flows> FlowSubscriber<T> {
     /// `sequence` as received by this subscriber.
     #[inline(always)]
     fn receive_up_to(&self, sequence: u64) {
-        self.next_receivable_seq
+        self.next_receivable_seq()
             .fetch_max(sequence + 1, Ordering::SeqCst);
     }
 }
 
+impl<T: Flows> Drop for FlowSubscriber<T> {
+    fn drop(&mut self) {
+        self.next_receivable_seq()
+            .store(DROPPED_SUBSCRIBER_SEQ, Ordering::SeqCst);
+    }
+}
+
+/// Sequence number of subscribers which were dropped.
+///
+/// This sequence is greater than any sequence a
+/// flow will publish, so dropped subscribers
+/// never block publishers.
+const DROPPED_SUBSCRIBER_SEQ: u64 = u64::MAX;
+
 /// Future returned by [`FlowSubscriber::next`].
 struct ReceiveNextFuture<'a, T: Flows> {
     subscriber: &'a FlowSubscriber<T>,
@@ -398,7 +523,7 @@ where
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("OutBarrier")
             .field("flow_state", &self.flow_state)
-            .field("next_receivable_seq", &self.next_receivable_seq)
+            .field("next_receivable_seq", self.next_receivable_seq())
             .finish()
     }
 }
@@ -412,6 +537,16 @@ unsafe impl<T> Sync for FlowState<T> where T: Flows {}
 pub trait Flows: Send + Sync + 'static {}
 impl<T> Flows for T where T: Send + Sync + 'static {}
 
+/// Blanket trait for data in a [`Flow`] which
+/// can be initialized via [`Default`].
+///
+/// Generic code written against [`Flows`] alone
+/// can create flows via [`Flow::new_with_init`];
+/// code that also requires `FlowsDefault` may
+/// use the more convenient [`Flow::new`].
+pub trait FlowsDefault: Flows + Default {}
+impl<T> FlowsDefault for T where T: Flows + Default {}
+
 /// Reference to mutable, unpublished data in a [`Flow`].
 ///
 /// When this reference is dropped, the data
@@ -420,7 +555,7 @@ impl<T> Flows for T where T: Send + Sync + 'static {}
 pub struct UnpublishedData<'a, T: Flows> {
     flow: &'a Flow<T>,
     sequence: u64,
-    data: &'a mut T,
+    data: MutPtr<T>,
 }
 
 impl<T: Flows> UnpublishedData<'_, T> {
@@ -430,8 +565,8 @@ impl<T: Flows> UnpublishedData<'_, T> {
     }
 
     /// Publishes `data` into this sequence.
-    pub fn publish(self, data: T) {
-        *self.data = data;
+    pub fn publish(mut self, data: T) {
+        *self = data;
         drop(self)
     }
 }
@@ -440,19 +575,23 @@ impl<T: Flows> Deref for UnpublishedData<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        self.data
+        // The sequence was claimed by this reference.
+        unsafe { self.data.deref() }
     }
 }
 
 impl<T: Flows> DerefMut for UnpublishedData<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.data
+        // The sequence was claimed by this reference.
+        unsafe { self.data.deref() }
     }
 }
 
 impl<T: Flows> Drop for UnpublishedData<'_, T> {
     fn drop(&mut self) {
-        while !self.flow.state.try_publish(self.sequence) {}
+        while !self.flow.state.try_publish(self.sequence) {
+            spin_loop();
+        }
     }
 }
 
@@ -465,14 +604,16 @@ impl<T: Flows> Drop for UnpublishedData<'_, T> {
 struct PublishedData<'a, T: Flows> {
     subscription: &'a FlowSubscriber<T>,
     sequence: u64,
-    data: &'a T,
+    data: ConstPtr<T>,
 }
 
 impl<T: Flows> Deref for PublishedData<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        self.data
+        // The sequence was published and isn't
+        // yet received by this subscriber.
+        unsafe { self.data.deref() }
     }
 }
 
@@ -494,6 +635,14 @@ pub enum Error {
     /// subscriber has already read all data presently
     /// in the flow.
     Ahead,
+
+    /// A deadline elapsed before the flow
+    /// was ready to publish or receive data.
+    TimedOut,
+
+    /// The operation requires the flow to be closed,
+    /// but data may still be published into it.
+    Open,
 }
 
 #[cfg(test)]
@@ -525,4 +674,88 @@ mod test {
 
         Ok(())
     }
+
+    /// Tests flows of data without a [`Default`].
+    #[test]
+    fn pubs_and_subs_without_default() -> Result<(), crate::Error> {
+        #[derive(Debug, PartialEq)]
+        enum Signal {
+            Idle,
+            Value(u32),
+        }
+
+        // Generic wrappers only need `Flows`.
+        fn relays<T: Flows + PartialEq>(value: T, expected: &T, init: fn() -> T) -> bool {
+            let (mut publisher, [mut subscriber]) = Flow::new_with_init(2, init);
+            publisher.try_next().unwrap().publish(value);
+            let received = subscriber.try_next().unwrap();
+            *received == *expected
+        }
+
+        let (mut publisher, [mut subscriber]) = Flow::new_with_init(4, || Signal::Idle);
+        publisher.try_next()?.publish(Signal::Value(42));
+        assert_eq!(Signal::Value(42), *subscriber.try_next()?);
+        assert!(relays(Signal::Value(7), &Signal::Value(7), || Signal::Idle));
+
+        Ok(())
+    }
+
+    /// Tests that data isn't lost when
+    /// a deadline elapses before receipt.
+    #[tokio::test]
+    async fn receives_after_deadline() -> Result<(), crate::Error> {
+        let (mut publisher, [mut subscriber]) = Flow::<u32>::new(2);
+
+        // Race an empty flow against a deadline.
+        let deadline = tokio::time::sleep(core::time::Duration::from_millis(10));
+        let result = subscriber.next_before(deadline).await.map(|data| *data);
+        assert_eq!(Err(Error::TimedOut), result);
+        assert_eq!(0..0, subscriber.receivable_seqs());
+
+        // Publish until the flow is full, then
+        // race the next publish against a deadline.
+        publisher.try_next()?.publish(1u32);
+        publisher.try_next()?.publish(2u32);
+        let deadline = tokio::time::sleep(core::time::Duration::from_millis(10));
+        let result = publisher.next_before(deadline).await.map(|data| *data);
+        assert_eq!(Err(Error::TimedOut), result);
+
+        // Data should still be received after the deadlines.
+        let deadline = tokio::time::sleep(core::time::Duration::from_secs(60));
+        assert_eq!(1, *subscriber.next_before(deadline).await?);
+        assert_eq!(2, *subscriber.next().await?);
+        assert_eq!(2..2, subscriber.receivable_seqs());
+
+        // Publishing should resume once data is received.
+        let deadline = tokio::time::sleep(core::time::Duration::from_secs(60));
+        publisher.next_before(deadline).await?.publish(3u32);
+        assert_eq!(3, *subscriber.try_next()?);
+
+        Ok(())
+    }
+
+    /// Tests draining unreceived data from a closed flow.
+    #[test]
+    fn drains_closed_flows() -> Result<(), crate::Error> {
+        let (mut publisher, [mut subscriber]) = Flow::<u32>::new(8);
+        for i in 0..5u32 {
+            publisher.try_next()?.publish(i);
+        }
+
+        assert_eq!(0, *subscriber.try_next()?);
+        assert_eq!(1, *subscriber.try_next()?);
+
+        // Draining fails until every publisher is dropped.
+        let cloned_publisher = publisher.clone();
+        drop(publisher);
+        assert_eq!(Err(Error::Open), subscriber.drain());
+        drop(cloned_publisher);
+
+        assert!(subscriber.is_closed());
+        assert_eq!(Ok(vec![2, 3, 4]), subscriber.drain());
+        assert_eq!(5..5, subscriber.receivable_seqs());
+        assert_eq!(Ok(vec![]), subscriber.drain());
+
+        Ok(())
+    }
 }
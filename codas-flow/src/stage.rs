@@ -8,6 +8,7 @@ use core::{
     future::Future,
     ops::Range,
     pin::Pin,
+    sync::atomic::Ordering,
     task::{Context, Waker},
 };
 
@@ -36,6 +37,10 @@ pub struct Stage<T: Flows> {
 impl<T: Flows> Stage<T> {
     /// Returns a [`Flow`] handle connected to the stage.
     pub fn flow(&self) -> Flow<T> {
+        self.subscriber
+            .flow_state
+            .publishers
+            .fetch_add(1, Ordering::SeqCst);
         Flow {
             state: self.subscriber.flow_state.clone(),
         }
@@ -85,7 +90,7 @@ impl<T: Flows> Stage<T> {
 
             // Invoke all processors.
             for proc in &mut self.processors {
-                (proc)(&mut self.context, data)
+                (proc)(&mut self.context, unsafe { data.deref() })
             }
 
             // End processing if we hit the last sequence
@@ -231,8 +236,6 @@ impl Default for Proc {
 #[cfg(test)]
 mod tests {
 
-    use core::sync::atomic::Ordering;
-
     use portable_atomic::AtomicU64;
     use portable_atomic_util::Arc;
 
@@ -278,4 +281,22 @@ mod tests {
         assert_eq!(Ok(1), stage.proc());
         assert_eq!(2, invocations.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn flows_keep_stages_open() {
+        let (flow, [subscriber]) = Flow::<u32>::new(4);
+        let stage = Stage::from(subscriber);
+
+        // Dropping a stage's flow handle doesn't
+        // close the flow while other flows remain.
+        drop(stage.flow());
+        assert!(!stage.subscriber.is_closed());
+
+        // The flow closes once every handle is dropped.
+        let staged_flow = stage.flow();
+        drop(flow);
+        assert!(!stage.subscriber.is_closed());
+        drop(staged_flow);
+        assert!(stage.subscriber.is_closed());
+    }
 }
@@ -0,0 +1,126 @@
+//! Synchronization primitives used by flows.
+//!
+//! When compiled with `--cfg loom`, these primitives
+//! are replaced with [loom](https://docs.rs/loom)'s
+//! models, enabling exhaustive concurrency testing.
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{atomic::AtomicU64, Arc};
+#[cfg(not(loom))]
+pub(crate) use portable_atomic::AtomicU64;
+#[cfg(not(loom))]
+pub(crate) use portable_atomic_util::Arc;
+
+/// Signals that the caller is busy-waiting
+/// for another thread to make progress.
+#[inline(always)]
+pub(crate) fn spin_loop() {
+    #[cfg(loom)]
+    loom::thread::yield_now();
+    #[cfg(not(loom))]
+    core::hint::spin_loop();
+}
+
+/// Interior-mutable cell whose accesses
+/// are tracked when compiled with `--cfg loom`.
+#[derive(Debug)]
+pub(crate) struct UnsafeCell<T> {
+    #[cfg(loom)]
+    inner: loom::cell::UnsafeCell<T>,
+    #[cfg(not(loom))]
+    inner: core::cell::UnsafeCell<T>,
+}
+
+impl<T> UnsafeCell<T> {
+    /// Returns a new cell containing `data`.
+    pub(crate) fn new(data: T) -> Self {
+        Self {
+            #[cfg(loom)]
+            inner: loom::cell::UnsafeCell::new(data),
+            #[cfg(not(loom))]
+            inner: core::cell::UnsafeCell::new(data),
+        }
+    }
+
+    /// Returns an immutable pointer to the cell's data.
+    ///
+    /// When compiled with `--cfg loom`, the cell is
+    /// tracked as being read until the pointer is dropped.
+    #[inline(always)]
+    pub(crate) fn get(&self) -> ConstPtr<T> {
+        ConstPtr {
+            inner: self.inner.get(),
+        }
+    }
+
+    /// Returns a mutable pointer to the cell's data.
+    ///
+    /// When compiled with `--cfg loom`, the cell is
+    /// tracked as being written until the pointer is dropped.
+    #[inline(always)]
+    pub(crate) fn get_mut(&self) -> MutPtr<T> {
+        MutPtr {
+            #[cfg(loom)]
+            inner: self.inner.get_mut(),
+            #[cfg(not(loom))]
+            inner: self.inner.get(),
+        }
+    }
+}
+
+/// Immutable pointer to an [`UnsafeCell`]'s data.
+#[derive(Debug)]
+pub(crate) struct ConstPtr<T> {
+    #[cfg(loom)]
+    inner: loom::cell::ConstPtr<T>,
+    #[cfg(not(loom))]
+    inner: *const T,
+}
+
+impl<T> ConstPtr<T> {
+    /// Returns a reference to the pointed-to data.
+    ///
+    /// # Safety
+    ///
+    /// The same as dereferencing a `*const T`.
+    #[inline(always)]
+    pub(crate) unsafe fn deref(&self) -> &T {
+        #[cfg(loom)]
+        return self.inner.deref();
+        #[cfg(not(loom))]
+        return &*self.inner;
+    }
+}
+
+/// Mutable pointer to an [`UnsafeCell`]'s data.
+#[derive(Debug)]
+pub(crate) struct MutPtr<T> {
+    #[cfg(loom)]
+    inner: loom::cell::MutPtr<T>,
+    #[cfg(not(loom))]
+    inner: *mut T,
+}
+
+impl<T> MutPtr<T> {
+    /// Returns a mutable reference to the pointed-to data.
+    ///
+    /// # Safety
+    ///
+    /// The same as dereferencing a `*mut T`; the
+    /// returned reference must be unique.
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    pub(crate) unsafe fn deref(&self) -> &mut T {
+        #[cfg(loom)]
+        return self.inner.deref();
+        #[cfg(not(loom))]
+        return &mut *self.inner;
+    }
+}
+
+// Pointers may be sent between threads
+// like the references they stand in for.
+unsafe impl<T: Sync> Send for ConstPtr<T> {}
+unsafe impl<T: Sync> Sync for ConstPtr<T> {}
+unsafe impl<T: Send> Send for MutPtr<T> {}
+unsafe impl<T: Sync> Sync for MutPtr<T> {}
@@ -0,0 +1,165 @@
+//! Exhaustive concurrency tests for flows.
+//!
+//! These tests only run when compiled with `--cfg loom`:
+//!
+//! ```sh
+//! RUSTFLAGS="--cfg loom" cargo test -p codas-flow --test loom --release
+//! ```
+//!
+//! Loom can't exhaust models in which more than one
+//! thread busy-waits on another, so each model has at
+//! most one thread waiting for data or space in a flow.
+//! Models with more threads (like two publishers racing
+//! two subscribers) have their other threads make a fixed
+//! number of attempts without waiting, which explores every
+//! interleaving of those attempts but not of the retries a
+//! waiting thread would make after them.
+#![cfg(loom)]
+
+use codas_flow::{Error, Flow, FlowSubscriber};
+use loom::{model::Builder, thread};
+
+/// Maximum number of times a thread may be
+/// preempted in each explored interleaving.
+const PREEMPTION_BOUND: usize = 3;
+
+/// Data published in each model: a pair of values
+/// which are always bitwise complements of each other.
+///
+/// A subscriber observing a mismatched
+/// pair observed a torn write.
+type Pair = (u64, u64);
+
+#[test]
+fn publishers_race_for_sequences() {
+    model(|| {
+        let (mut publisher_a, [mut subscriber]) = Flow::<Pair>::new(2);
+        let mut publisher_b = publisher_a.clone();
+
+        // Publish from two threads, one of which
+        // may wait for the other to publish first.
+        let publisher = thread::spawn(move || publish(&mut publisher_b, 2));
+        publish(&mut publisher_a, 1);
+        publisher.join().unwrap();
+
+        let mut received = receive(&mut subscriber, 2);
+        received.sort_unstable();
+        assert_eq!(vec![1, 2], received);
+    });
+}
+
+#[test]
+fn subscribers_race_publishers() {
+    model(|| {
+        let (mut publisher, [mut subscriber]) = Flow::<Pair>::new(1);
+
+        // Publish more data than the flow can hold
+        // while the subscriber receives it.
+        let publisher = thread::spawn(move || {
+            publish(&mut publisher, 1);
+            publish(&mut publisher, 2);
+        });
+        assert_eq!(vec![1, 2], receive(&mut subscriber, 2));
+        publisher.join().unwrap();
+    });
+}
+
+#[test]
+fn publishers_wait_for_every_subscriber() {
+    model(|| {
+        let (mut publisher, [mut subscriber_a, mut subscriber_b]) = Flow::<Pair>::new(1);
+        publish(&mut publisher, 1);
+
+        // Receive the published data from two threads
+        // while publishing into the (full) flow.
+        let subscribers = [
+            thread::spawn(move || (receive(&mut subscriber_a, 1), subscriber_a)),
+            thread::spawn(move || (receive(&mut subscriber_b, 1), subscriber_b)),
+        ];
+        publish(&mut publisher, 2);
+
+        for subscriber in subscribers {
+            let (received, mut subscriber) = subscriber.join().unwrap();
+            assert_eq!(vec![1], received);
+            assert_eq!(vec![2], receive(&mut subscriber, 1));
+        }
+    });
+}
+
+#[test]
+fn publishers_race_subscribers() {
+    model(|| {
+        let (mut publisher_a, [mut subscriber_a, mut subscriber_b]) = Flow::<Pair>::new(2);
+        let mut publisher_b = publisher_a.clone();
+
+        // Publish from two threads, filling the flow, while
+        // two others attempt to receive without waiting
+        // (so that only one thread ever busy-waits).
+        let publisher = thread::spawn(move || publish(&mut publisher_b, 2));
+        let subscribers = [
+            thread::spawn(move || (try_receive(&mut subscriber_a, 2), subscriber_a)),
+            thread::spawn(move || (try_receive(&mut subscriber_b, 2), subscriber_b)),
+        ];
+        publish(&mut publisher_a, 1);
+        publisher.join().unwrap();
+
+        for subscriber in subscribers {
+            let (mut received, mut subscriber) = subscriber.join().unwrap();
+            let remaining = 2 - received.len();
+            received.extend(receive(&mut subscriber, remaining));
+            received.sort_unstable();
+            assert_eq!(vec![1, 2], received);
+        }
+    });
+}
+
+/// Checks `f` with a bounded number of preemptions.
+fn model(f: impl Fn() + Sync + Send + 'static) {
+    let mut builder = Builder::new();
+    builder.preemption_bound = Some(PREEMPTION_BOUND);
+    builder.check(f);
+}
+
+/// Publishes `value` into `publisher`,
+/// yielding while the flow is full.
+fn publish(publisher: &mut Flow<Pair>, value: u64) {
+    loop {
+        match publisher.try_next() {
+            Ok(next) => return next.publish((value, !value)),
+            Err(Error::Full) => thread::yield_now(),
+            Err(e) => panic!("unexpected error: {e:?}"),
+        }
+    }
+}
+
+/// Receives `count` values from `subscriber`,
+/// asserting no received value was torn.
+fn receive(subscriber: &mut FlowSubscriber<Pair>, count: usize) -> Vec<u64> {
+    let mut received = Vec::with_capacity(count);
+    while received.len() < count {
+        match try_receive(subscriber, 1).pop() {
+            Some(value) => received.push(value),
+            None => thread::yield_now(),
+        }
+    }
+    received
+}
+
+/// Makes `attempts` attempts to receive a value from
+/// `subscriber` without waiting, returning every value
+/// received and asserting none of them were torn.
+fn try_receive(subscriber: &mut FlowSubscriber<Pair>, attempts: usize) -> Vec<u64> {
+    let mut received = Vec::with_capacity(attempts);
+    for _ in 0..attempts {
+        match subscriber.try_next() {
+            Ok(next) => {
+                let (value, complement) = *next;
+                assert_eq!(!value, complement, "torn read of {value}");
+                received.push(value);
+            }
+            Err(Error::Ahead) => {}
+            Err(e) => panic!("unexpected error: {e:?}"),
+        }
+    }
+    received
+}
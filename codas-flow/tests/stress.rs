@@ -0,0 +1,82 @@
+//! Multi-threaded stress tests for flows.
+//!
+//! These tests are slow, and only run when requested:
+//!
+//! ```sh
+//! cargo test -p codas-flow --test stress --release -- --ignored
+//! ```
+
+use std::thread;
+
+use codas_flow::{Error, Flow, FlowSubscriber};
+
+/// Number of values published by each publisher.
+const VALUES_PER_PUBLISHER: u64 = 2_000_000;
+
+#[test]
+#[ignore]
+fn stresses_many_publishers_and_subscribers() {
+    let (publisher, subscribers) = Flow::<(u64, u64)>::new::<2>(64);
+
+    // Each publisher tags its values with its
+    // index in the top bit, and publishes each
+    // value next to its complement.
+    let publishers: Vec<_> = [publisher.clone(), publisher]
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut publisher)| {
+            thread::spawn(move || {
+                let tag = (index as u64) << 63;
+                for value in 0..VALUES_PER_PUBLISHER {
+                    let value = tag | value;
+                    loop {
+                        match publisher.try_next() {
+                            Ok(mut next) => {
+                                *next = (value, !value);
+                                break;
+                            }
+                            Err(Error::Full) => core::hint::spin_loop(),
+                            Err(e) => panic!("unexpected error: {e:?}"),
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let subscribers: Vec<_> = subscribers
+        .into_iter()
+        .map(|mut subscriber| thread::spawn(move || receive_all(&mut subscriber)))
+        .collect();
+
+    for publisher in publishers {
+        publisher.join().unwrap();
+    }
+    for subscriber in subscribers {
+        subscriber.join().unwrap();
+    }
+}
+
+/// Receives every published value, asserting that
+/// no value is torn, skipped, or received twice.
+fn receive_all(subscriber: &mut FlowSubscriber<(u64, u64)>) {
+    let mut next_expected = [0u64; 2];
+    let mut remaining = 2 * VALUES_PER_PUBLISHER;
+    while remaining > 0 {
+        match subscriber.try_next() {
+            Ok(next) => {
+                let (value, complement) = *next;
+                assert_eq!(!value, complement, "torn read of {value}");
+
+                // Values from each publisher are received in order.
+                let publisher = (value >> 63) as usize;
+                let value = value & !(1 << 63);
+                assert_eq!(next_expected[publisher], value);
+                next_expected[publisher] += 1;
+                remaining -= 1;
+            }
+            Err(Error::Ahead) => core::hint::spin_loop(),
+            Err(e) => panic!("unexpected error: {e:?}"),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Implementations of the `Encodable` and `Decodable` derive macros.
+//!
+//! These macros don't depend on a Rust parser: the derived
+//! struct's name, `#[coda(...)]` attributes, and fields are
+//! read directly from the macro's input tokens.
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// A struct with named fields that
+/// a codec is being derived for.
+pub(crate) struct DerivedStruct {
+    /// The struct's name.
+    name: String,
+
+    /// The ordinal from the struct's `#[coda(ordinal = N)]`.
+    ordinal: u8,
+
+    /// The struct's fields, in declaration order.
+    fields: Vec<DerivedField>,
+}
+
+/// A named field of a [`DerivedStruct`].
+struct DerivedField {
+    /// The field's name.
+    name: String,
+
+    /// The field's type, as Rust source.
+    typing: String,
+}
+
+impl DerivedStruct {
+    /// Parses a struct from the `tokens` of a derive
+    /// macro's input, returning an error message
+    /// if the tokens aren't a supported struct.
+    pub fn parse(tokens: TokenStream) -> Result<Self, String> {
+        let mut ordinal = None;
+        let mut tokens = tokens.into_iter().peekable();
+
+        // Parse attributes and visibility,
+        // up to the `struct` keyword.
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                    if let Some(TokenTree::Group(attribute)) = tokens.next() {
+                        if let Some(value) = parse_coda_attribute(attribute.stream())? {
+                            ordinal = Some(value);
+                        }
+                    }
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                    // Skip restrictions like `pub(crate)`.
+                    if let Some(TokenTree::Group(group)) = tokens.peek() {
+                        if group.delimiter() == Delimiter::Parenthesis {
+                            tokens.next();
+                        }
+                    }
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => break,
+                Some(TokenTree::Ident(ident)) => {
+                    return Err(format!(
+                        "codecs can only be derived for structs, not `{ident}`s"
+                    ))
+                }
+                _ => return Err("expected a struct".into()),
+            }
+        }
+
+        // Parse the struct's name.
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected a struct name".into()),
+        };
+
+        // Parse the struct's fields.
+        let fields = match tokens.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                parse_fields(group.stream())?
+            }
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+                return Err(format!(
+                    "codecs can't be derived for generic structs like `{name}`"
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "codecs can only be derived for `{name}` if it has named fields"
+                ))
+            }
+        };
+
+        let Some(ordinal) = ordinal else {
+            return Err(format!(
+                "`{name}` must declare its ordinal with `#[coda(ordinal = N)]`"
+            ));
+        };
+
+        Ok(Self {
+            name,
+            ordinal,
+            fields,
+        })
+    }
+
+    /// Returns an `Encodable` implementation
+    /// for this struct, using the `codas` crate
+    /// at `codas_path`.
+    pub fn encodable(&self, codas_path: &str) -> String {
+        let name = &self.name;
+        let ordinal = self.ordinal;
+
+        // Compose the format in declaration order; blob
+        // fields add to the format's blob size regardless
+        // of where they're declared.
+        let mut format = format!("{codas_path}::codec::Format::data({ordinal})");
+        for field in &self.fields {
+            format += &format!(
+                "\n.with(<{} as {codas_path}::codec::Encodable>::FORMAT)",
+                field.typing
+            );
+        }
+
+        let encoders = self.each_field_blobs_first(codas_path, |field| {
+            format!("writer.write_data(&self.{})?;", field.name)
+        });
+
+        format!(
+            r#"
+            impl {codas_path}::codec::Encodable for {name} {{
+                const FORMAT: {codas_path}::codec::Format = {format};
+
+                fn encode(
+                    &self,
+                    writer: &mut (impl {codas_path}::codec::WritesEncodable + ?Sized),
+                ) -> core::result::Result<(), {codas_path}::codec::CodecError> {{
+                    {encoders}
+                    Ok(())
+                }}
+            }}
+            "#
+        )
+    }
+
+    /// Returns a `Decodable` implementation
+    /// for this struct, using the `codas` crate
+    /// at `codas_path`.
+    pub fn decodable(&self, codas_path: &str) -> String {
+        let name = &self.name;
+
+        // Any blob fields and data fields unknown
+        // to this decoder are skipped.
+        let decoder =
+            |field: &DerivedField| format!("reader.read_data_into(&mut self.{})?;", field.name);
+        let format = format!("<Self as {codas_path}::codec::Encodable>::FORMAT");
+        let blob_decoders = self.each_field(codas_path, false, decoder);
+        let data_decoders = self.each_field(codas_path, true, decoder);
+
+        format!(
+            r#"
+            impl {codas_path}::codec::Decodable for {name} {{
+                fn decode(
+                    &mut self,
+                    reader: &mut (impl {codas_path}::codec::ReadsDecodable + ?Sized),
+                    header: core::option::Option<{codas_path}::codec::DataHeader>,
+                ) -> core::result::Result<(), {codas_path}::codec::CodecError> {{
+                    let header = Self::ensure_format(header)?;
+                    {blob_decoders}
+                    reader.skip_excess_blob(&header, {format})?;
+                    {data_decoders}
+                    reader.skip_excess_data(&header, {format})?;
+                    Ok(())
+                }}
+            }}
+            "#
+        )
+    }
+
+    /// Returns the concatenated `statement`s for each field,
+    /// with the statements for all blob fields preceding
+    /// the statements for all data fields (matching the
+    /// order of fields in a `codas::types::DataType`).
+    fn each_field_blobs_first(
+        &self,
+        codas_path: &str,
+        statement: impl Fn(&DerivedField) -> String,
+    ) -> String {
+        self.each_field(codas_path, false, &statement)
+            + &self.each_field(codas_path, true, &statement)
+    }
+
+    /// Returns the concatenated `statement`s for each
+    /// field that is (or isn't) `structured`.
+    ///
+    /// Whether a field is a blob is only known once its
+    /// type's `FORMAT` is evaluated, so each statement is
+    /// guarded by a (constant) check of the field's format.
+    fn each_field(
+        &self,
+        codas_path: &str,
+        structured: bool,
+        statement: impl Fn(&DerivedField) -> String,
+    ) -> String {
+        let negation = if structured { "" } else { "!" };
+        let mut statements = String::new();
+        for field in &self.fields {
+            statements += &format!(
+                "if {negation}<{} as {codas_path}::codec::Encodable>::FORMAT.is_structured() {{ {} }}\n",
+                field.typing,
+                statement(field)
+            );
+        }
+        statements
+    }
+}
+
+/// Parses the `ordinal` from the contents of an
+/// attribute like `#[coda(ordinal = N)]`, returning
+/// `None` if the attribute isn't a `coda` attribute.
+fn parse_coda_attribute(attribute: TokenStream) -> Result<Option<u8>, String> {
+    let mut tokens = attribute.into_iter();
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "coda" => {}
+        _ => return Ok(None),
+    }
+
+    let Some(TokenTree::Group(arguments)) = tokens.next() else {
+        return Err("expected `#[coda(ordinal = N)]`".into());
+    };
+    let arguments: Vec<_> = arguments.stream().into_iter().collect();
+    match arguments.as_slice() {
+        [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(value)]
+            if key.to_string() == "ordinal" && eq.as_char() == '=' =>
+        {
+            let value = value.to_string();
+            match value.trim_end_matches("u8").parse::<u8>() {
+                Ok(0) => Err("ordinal `0` is reserved for unspecified data".into()),
+                Ok(ordinal) => Ok(Some(ordinal)),
+                Err(_) => Err(format!("`{value}` isn't a valid ordinal (1 to 255)")),
+            }
+        }
+        _ => Err("expected `#[coda(ordinal = N)]`".into()),
+    }
+}
+
+/// Parses the named fields within
+/// the braces of a struct declaration.
+fn parse_fields(tokens: TokenStream) -> Result<Vec<DerivedField>, String> {
+    let mut fields = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+
+    while tokens.peek().is_some() {
+        // Skip attributes and visibility.
+        let name = loop {
+            match tokens.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                    tokens.next();
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                    if let Some(TokenTree::Group(group)) = tokens.peek() {
+                        if group.delimiter() == Delimiter::Parenthesis {
+                            tokens.next();
+                        }
+                    }
+                }
+                Some(TokenTree::Ident(ident)) => break ident.to_string(),
+                _ => return Err("expected a field name".into()),
+            }
+        };
+
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+            _ => return Err(format!("expected a type for field `{name}`")),
+        }
+
+        // Collect the field's type up to the next
+        // comma that isn't within angle brackets.
+        let mut typing = TokenStream::new();
+        let mut depth = 0usize;
+        for token in tokens.by_ref() {
+            if let TokenTree::Punct(punct) = &token {
+                match punct.as_char() {
+                    '<' => depth += 1,
+                    '>' => depth = depth.saturating_sub(1),
+                    ',' if depth == 0 => break,
+                    _ => {}
+                }
+            }
+            typing.extend([token]);
+        }
+
+        fields.push(DerivedField {
+            name,
+            typing: typing.to_string(),
+        });
+    }
+
+    Ok(fields)
+}
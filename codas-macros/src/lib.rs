@@ -1,9 +1,13 @@
 #![doc = include_str!("../README.md")]
 //! > _Note_: This documentation is auto-generated
 //! > from the project's README.md file.
-use std::{path::PathBuf, process::Command};
+use std::{cell::RefCell, path::PathBuf, process::Command};
 
-use proc_macro::{TokenStream, TokenTree};
+use codas::{codec::encode_to_vec, parse::ParseError, types::ValidationSeverity};
+use derive::DerivedStruct;
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+mod derive;
 
 /// Loads a coda from a file, generating Rust data
 /// structures and codecs for the coda and exporting
@@ -14,6 +18,188 @@ use proc_macro::{TokenStream, TokenTree};
 pub fn export_coda(tokens: TokenStream) -> TokenStream {
     let coda_path = parse_token_string(tokens);
 
+    let (coda, paths) = match load_coda(&coda_path) {
+        Ok(loaded) => loaded,
+        Err(message) => return compile_error_at(&message, Span::call_site()),
+    };
+    if let Err(message) = validate_coda(&coda, &coda_path) {
+        return compile_error_at(&message, Span::call_site());
+    }
+
+    let mut codegen = include_paths(&paths);
+    codegen += &generate_rust(&coda);
+    codegen.parse().unwrap()
+}
+
+/// Loads an old and a new version of a coda from
+/// files, failing compilation if any data type in
+/// both versions of the coda is incompatible.
+///
+/// Refer to the [crate] docs for more info.
+#[proc_macro]
+pub fn assert_coda_compat(tokens: TokenStream) -> TokenStream {
+    let span = Span::call_site();
+
+    let mut tokens = tokens.into_iter();
+    let (old_path, new_path) = match (tokens.next(), tokens.next(), tokens.next()) {
+        (
+            Some(TokenTree::Literal(old_path)),
+            Some(TokenTree::Punct(comma)),
+            Some(TokenTree::Literal(new_path)),
+        ) if comma.as_char() == ',' => (
+            parse_string_literal(&old_path.to_string()),
+            parse_string_literal(&new_path.to_string()),
+        ),
+        _ => (None, None),
+    };
+    let (Some(old_path), Some(new_path)) = (old_path, new_path) else {
+        return compile_error_at("expected the paths of an old and a new coda", span);
+    };
+
+    let (old, mut paths) = match load_coda(&old_path) {
+        Ok(loaded) => loaded,
+        Err(message) => return compile_error_at(&message, span),
+    };
+    let (new, new_paths) = match load_coda(&new_path) {
+        Ok(loaded) => loaded,
+        Err(message) => return compile_error_at(&message, span),
+    };
+    paths.extend(new_paths);
+
+    if let Err(reasons) = codas::types::check_coda_compat(&old, &new) {
+        let reasons: Vec<String> = reasons.iter().map(ToString::to_string).collect();
+        return compile_error_at(
+            &format!(
+                "{new_path} is incompatible with {old_path}:\n{}",
+                reasons.join("\n")
+            ),
+            span,
+        );
+    }
+
+    include_paths(&paths).parse().unwrap()
+}
+
+/// Loads a coda from a file, expanding to a
+/// `&'static codas::types::Coda` equal to the
+/// coda parsed from the file.
+///
+/// Refer to the [crate] docs for more info.
+#[proc_macro]
+pub fn coda_schema(tokens: TokenStream) -> TokenStream {
+    let coda_path = parse_token_string(tokens);
+
+    let (coda, paths) = match load_coda(&coda_path) {
+        Ok(loaded) => loaded,
+        Err(message) => return compile_error_at(&message, Span::call_site()),
+    };
+
+    // Embed the encoded coda, decoding it on first use.
+    let encoded = encode_to_vec(&coda).unwrap();
+    let encoded = Literal::byte_string(&encoded);
+    let codas = codas_path();
+    format!(
+        r#"{{
+        {}
+        static CODA: ::std::sync::OnceLock<{codas}::types::Coda> = ::std::sync::OnceLock::new();
+        CODA.get_or_init(|| {{
+            let mut encoded: &[u8] = {encoded};
+            {codas}::codec::ReadsDecodable::read_data(&mut encoded)
+                .expect("embedded coda should decode")
+        }})
+    }}"#,
+        include_paths(&paths)
+    )
+    .parse()
+    .unwrap()
+}
+
+/// Parses a coda from an inline string literal,
+/// generating Rust data structures and codecs for
+/// the coda and exporting them into the module that
+/// called this macro.
+///
+/// Refer to the [crate] docs for more info.
+#[proc_macro]
+pub fn inline_coda(tokens: TokenStream) -> TokenStream {
+    let span = Span::call_site();
+
+    let mut tokens = tokens.into_iter();
+    let markdown = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => parse_string_literal(&literal.to_string()),
+        _ => None,
+    };
+    let Some(markdown) = markdown else {
+        return compile_error_at("expected a single string literal containing a coda", span);
+    };
+
+    let coda = match codas::parse::parse(&markdown) {
+        Ok(coda) => coda,
+        Err(error) => return compile_error_at(&format!("invalid coda: {error}"), span),
+    };
+    if let Err(message) = validate_coda(&coda, "inline coda") {
+        return compile_error_at(&message, span);
+    }
+
+    generate_rust(&coda).parse().unwrap()
+}
+
+/// Derives `codas::codec::Encodable` for a struct
+/// with named fields.
+///
+/// Refer to the [crate] docs for more info.
+#[proc_macro_derive(Encodable, attributes(coda))]
+pub fn derive_encodable(tokens: TokenStream) -> TokenStream {
+    match DerivedStruct::parse(tokens) {
+        Ok(data) => data.encodable(&codas_path()).parse().unwrap(),
+        Err(message) => compile_error(&message),
+    }
+}
+
+/// Derives `codas::codec::Decodable` for a struct
+/// with named fields.
+///
+/// Refer to the [crate] docs for more info.
+#[proc_macro_derive(Decodable, attributes(coda))]
+pub fn derive_decodable(tokens: TokenStream) -> TokenStream {
+    match DerivedStruct::parse(tokens) {
+        Ok(data) => data.decodable(&codas_path()).parse().unwrap(),
+        Err(message) => compile_error(&message),
+    }
+}
+
+/// Returns a `compile_error!` invocation
+/// reporting `message`.
+fn compile_error(message: &str) -> TokenStream {
+    format!("compile_error!({message:?});").parse().unwrap()
+}
+
+/// Returns a `compile_error!` invocation
+/// reporting `message` at `span`.
+fn compile_error_at(message: &str, span: Span) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let mut arguments = Group::new(Delimiter::Parenthesis, TokenTree::from(literal).into());
+    arguments.set_span(span);
+
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut semicolon = Punct::new(';', Spacing::Alone);
+    semicolon.set_span(span);
+
+    TokenStream::from_iter([
+        TokenTree::from(Ident::new("compile_error", span)),
+        bang.into(),
+        arguments.into(),
+        semicolon.into(),
+    ])
+}
+
+/// Loads and parses the coda at `coda_path`
+/// relative to the workspace root, returning
+/// the coda and the paths of every file it
+/// was loaded from (including imports).
+fn load_coda(coda_path: &str) -> Result<(codas::types::Coda, Vec<PathBuf>), String> {
     // Locate the workspace root path.
     let workspace_toml = Command::new(env!("CARGO"))
         .arg("locate-project")
@@ -29,34 +215,138 @@ pub fn export_coda(tokens: TokenStream) -> TokenStream {
     // Locate the coda relative to the workspace root.
     let path = workspace_root.join(coda_path);
 
-    // Load and parse the coda from the file.
-    let coda = std::fs::read_to_string(path.clone()).unwrap();
-    let coda = codas::parse::parse(&coda).unwrap();
+    // Load and parse the coda from the file, loading
+    // any imported codas relative to the workspace root.
+    let paths = RefCell::new(vec![path.clone()]);
+    let coda = std::fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read {coda_path}: {error}"))?;
+    let coda = codas::parse::parse_with_loader(&coda, coda_path, |import_path| {
+        let path = workspace_root.join(import_path);
+        let markdown = std::fs::read_to_string(&path).map_err(|_| ParseError::MissingImport {
+            name: Default::default(),
+            path: import_path.into(),
+            location: None,
+        })?;
+        paths.borrow_mut().push(path);
+        Ok(markdown)
+    })
+    .map_err(|error| format!("invalid coda in {coda_path}: {error}"))?;
 
-    // Generate Rust code.
-    let mut codegen = vec![];
-    #[cfg(feature = "serde")]
-    {
-        codas::langs::rust::generate_types(&coda, &mut codegen, true).unwrap();
+    Ok((coda, paths.into_inner()))
+}
+
+/// Returns statements which will trigger a rebuild of
+/// the code calling a macro whenever any of `paths` change.
+fn include_paths(paths: &[PathBuf]) -> String {
+    let mut statements = String::new();
+    for path in paths {
+        statements += &format!(
+            r#"
+        const _: &str = include_str!({:?});
+    "#,
+            path.display().to_string()
+        );
     }
-    #[cfg(not(feature = "serde"))]
-    {
-        codas::langs::rust::generate_types(&coda, &mut codegen, false).unwrap();
+    statements
+}
+
+/// Returns an error describing every issue in `coda`
+/// (loaded from `source`) which would prevent it from
+/// generating valid Rust code, like fields referring
+/// to data types that don't exist.
+fn validate_coda(coda: &codas::types::Coda, source: &str) -> Result<(), String> {
+    let errors: Vec<String> = coda
+        .validate()
+        .into_iter()
+        .filter(|issue| issue.severity == ValidationSeverity::Error)
+        .map(|issue| match &issue.field {
+            Some(field) => format!(
+                "{source}: in field `{field}` of `{}`: {}",
+                issue.data_type, issue.message
+            ),
+            None => format!("{source}: in `{}`: {}", issue.data_type, issue.message),
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
     }
-    let codegen = String::from_utf8_lossy(&codegen);
-
-    // Prepend the generated code with a statement
-    // that will trigger a rebuild of the code whenever
-    // the source file changes.
-    let mut codegen_prefix = format!(
-        r#"
-        const _: &str = include_str!("{}");
-    "#,
-        path.display()
-    );
-    codegen_prefix += &codegen;
+}
+
+/// Returns the Rust data structures and codecs
+/// generated for `coda`.
+fn generate_rust(coda: &codas::types::Coda) -> String {
+    let mut codegen = vec![];
+    let options = codas::langs::rust::RustCodegenOptions {
+        serde: cfg!(feature = "serde"),
+        display: cfg!(feature = "display"),
+        builder_pattern: cfg!(feature = "builder"),
+        preserve_unknown: cfg!(feature = "preserve-unknown"),
+        codas_path: Some(codas_path().into()),
+        module_path: None,
+    };
+    codas::langs::rust::generate_types(coda, &mut codegen, &options).unwrap();
+    String::from_utf8_lossy(&codegen).into_owned()
+}
+
+/// Returns the name the crate calling this macro
+/// uses for its `codas` dependency, which may be
+/// renamed in the crate's manifest.
+fn codas_path() -> String {
+    std::env::var("CARGO_MANIFEST_DIR")
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(PathBuf::from(dir).join("Cargo.toml")).ok())
+        .and_then(|manifest| find_codas_dependency(&manifest))
+        .unwrap_or_else(|| "codas".into())
+}
+
+/// Returns the name of the first dependency on the
+/// `codas` package in the Cargo `manifest`, if any.
+///
+/// Dependency names are returned as Rust identifiers
+/// (with `-` replaced by `_`).
+fn find_codas_dependency(manifest: &str) -> Option<String> {
+    let mut in_dependencies = false;
+    for line in manifest.lines().map(str::trim) {
+        // Track whether we're in a dependency table.
+        if let Some(table) = line.strip_prefix('[') {
+            let table = table.trim_end_matches(']');
+            in_dependencies = table.ends_with("dependencies");
+
+            // Dependencies declared as tables, like
+            // `[dependencies.codas]`, are matched by name.
+            if let Some((section, name)) = table.rsplit_once('.') {
+                if section.ends_with("dependencies") && name == "codas" {
+                    return Some(name.into());
+                }
+            }
+            continue;
+        }
 
-    codegen_prefix.parse().unwrap()
+        if !in_dependencies {
+            continue;
+        }
+
+        // Dependencies are declared like `name = ...`,
+        // `name.workspace = true`, or (when renamed)
+        // `name = { package = "codas", ... }`.
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        let name = name.split_once('.').map_or(name, |(name, _)| name);
+        let renamed = value
+            .split_once("package")
+            .and_then(|(_, package)| package.trim_start().strip_prefix('='))
+            .map(|package| package.trim_start().starts_with("\"codas\""));
+        if renamed.unwrap_or(name == "codas") {
+            return Some(name.replace('-', "_"));
+        }
+    }
+
+    None
 }
 
 /// Parses the first quoted string from `tokens`,
@@ -81,3 +371,130 @@ fn parse_token_string(tokens: TokenStream) -> String {
 
     coda
 }
+
+/// Returns the value of the Rust string `literal`,
+/// which may be a (possibly raw) string literal,
+/// or `None` if `literal` isn't a string literal.
+fn parse_string_literal(literal: &str) -> Option<String> {
+    // Raw strings are used verbatim.
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = raw[hashes..].strip_suffix(&raw[..hashes])?;
+        return raw.strip_prefix('"')?.strip_suffix('"').map(String::from);
+    }
+
+    let escaped = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut string = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => string.push('\n'),
+            'r' => string.push('\r'),
+            't' => string.push('\t'),
+            '0' => string.push('\0'),
+            '\\' => string.push('\\'),
+            '"' => string.push('"'),
+            '\'' => string.push('\''),
+            'x' => {
+                let code: String = chars.by_ref().take(2).collect();
+                string.push(u8::from_str_radix(&code, 16).ok()?.into());
+            }
+            'u' => {
+                chars.next_if_eq(&'{')?;
+                let code: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                string.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+
+            // Escaped line breaks skip the
+            // whitespace which follows them.
+            '\n' => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+
+            _ => return None,
+        }
+    }
+
+    Some(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_type_references() {
+        let coda = codas::parse::parse(
+            "# `Shapes` Coda\n\n## `Shape` Data\n\n+ `origin` Point\n+ `scale` i32",
+        )
+        .unwrap();
+        assert_eq!(
+            Err("shapes.md: in field `origin` of `Shape`: field `origin` refers to unknown type `Point`".into()),
+            validate_coda(&coda, "shapes.md")
+        );
+
+        // Warnings don't prevent code generation.
+        let coda = codas::parse::parse(
+            "# `Shapes` Coda\n\n## `Shape` Data\n\n## `Point` Data\n\n+ `x` i32",
+        )
+        .unwrap();
+        assert_eq!(Ok(()), validate_coda(&coda, "shapes.md"));
+    }
+
+    #[test]
+    fn parses_string_literals() {
+        assert_eq!(
+            Some("# `A` Coda\n\t\"\\".into()),
+            parse_string_literal(r##""# `A` Coda\n\t\"\\""##)
+        );
+        assert_eq!(
+            Some("ab\u{e9}c".into()),
+            parse_string_literal("\"a\\\n    b\\u{e9}\\x63\"")
+        );
+        assert_eq!(
+            Some("# `A` Coda\\n".into()),
+            parse_string_literal(r###"r#"# `A` Coda\n"#"###)
+        );
+        assert_eq!(
+            Some("\"#".into()),
+            parse_string_literal(r####"r##""#"##"####)
+        );
+        assert_eq!(None, parse_string_literal("42"));
+        assert_eq!(None, parse_string_literal(r#""\q""#));
+    }
+
+    #[test]
+    fn finds_codas_dependency() {
+        let manifest = r#"
+[package]
+name = "codas"
+
+[dependencies]
+serde = "1"
+codas.workspace = true
+"#;
+        assert_eq!(Some("codas".into()), find_codas_dependency(manifest));
+
+        let manifest = r#"
+[dependencies]
+codas-macros = "0.7"
+my-codas = { package = "codas", version = "0.7" }
+"#;
+        assert_eq!(Some("my_codas".into()), find_codas_dependency(manifest));
+
+        let manifest = r#"
+[target.'cfg(unix)'.dependencies.codas]
+version = "0.7"
+"#;
+        assert_eq!(Some("codas".into()), find_codas_dependency(manifest));
+
+        let manifest = r#"
+[dependencies]
+codas-macros = "0.7"
+"#;
+        assert_eq!(None, find_codas_dependency(manifest));
+    }
+}
@@ -0,0 +1,26 @@
+//! Tests for [`codas_macros::assert_coda_compat`].
+use codas::{parse::parse, types::check_coda_compat};
+use codas_macros::assert_coda_compat;
+
+// Adding an optional field is compatible.
+assert_coda_compat!(
+    "codas-macros/tests/greeter_coda.md",
+    "codas-macros/tests/greeter_coda_v2.md"
+);
+
+/// Tests that codas are checked for
+/// compatibility at runtime.
+#[test]
+pub fn checks_coda_compatibility() {
+    let old = parse(include_str!("greeter_coda.md")).unwrap();
+    let new = parse(include_str!("greeter_coda_v2.md")).unwrap();
+    assert_eq!(Ok(()), check_coda_compat(&old, &new));
+
+    // Changing a field's type is incompatible.
+    let retyped =
+        include_str!("greeter_coda.md").replace("+ `request_id` u32", "+ `request_id` u64");
+    let retyped = parse(&retyped).unwrap();
+    let reasons = check_coda_compat(&old, &retyped).unwrap_err();
+    assert_eq!(1, reasons.len());
+    assert_eq!("Nesting", reasons[0].data_type);
+}
@@ -0,0 +1,51 @@
+//! Tests for default values of fields generated
+//! by [`codas_macros::inline_coda`].
+
+extern crate alloc;
+
+use codas::{
+    codec::{decode_from_slice, encode_to_vec},
+    types::{dynamic::DynamicValue, Text, Timestamp, Unspecified},
+};
+
+codas_macros::inline_coda! {
+    "# `Jobs` Coda\n\n## `Job` Data\n\n+ `retries` u32 = 3\n+ `weight` f32 = -0.5\n+ `enabled` bool = true\n+ `name` text = \"the \\\"first\\\" job\"\n+ `status` one of [Queued, Running] = Running\n+ `started_at` optional timestamp = 1700000000000\n+ `label` text"
+}
+
+/// Tests that fields are constructed
+/// with their default values.
+#[test]
+pub fn constructs_defaults() {
+    let job = Job::default();
+    assert_eq!(3, job.retries);
+    assert_eq!(-0.5, job.weight);
+    assert!(job.enabled);
+    assert_eq!("the \"first\" job", job.name);
+    assert_eq!(JobStatus::Running, job.status);
+    assert_eq!(Some(Timestamp(1_700_000_000_000)), job.started_at);
+    assert_eq!(Text::default(), job.label);
+
+    // Defaults are described by the data type.
+    let data_type = Job::data_type();
+    let default = |name: &str| data_type.field(name).unwrap().default.clone();
+    assert_eq!(Some(Unspecified::U32(3)), default("retries"));
+    assert_eq!(Some(JobStatus::Running.to_dynamic()), default("status"));
+    assert_eq!(None, default("label"));
+}
+
+/// Tests that defaults don't affect
+/// the encoding of data.
+#[test]
+pub fn codes_defaults() {
+    let job = Job {
+        retries: 0,
+        started_at: None,
+        ..Default::default()
+    };
+
+    let bytes = encode_to_vec(&job).unwrap();
+    let decoded: Job = decode_from_slice(&bytes).unwrap();
+    assert_eq!(job, decoded);
+    assert_eq!(0, decoded.retries);
+    assert_eq!(None, decoded.started_at);
+}
@@ -0,0 +1,48 @@
+//! Tests for deprecated data types and fields
+//! generated by [`codas_macros::inline_coda`].
+
+extern crate alloc;
+
+use codas::codec::{decode_from_slice, encode_to_vec};
+
+codas_macros::inline_coda! {
+    "# `Users` Coda\n\n## `Account` Data\n\n+ `name` text\n\n## `User` Data\n\n> Deprecated: Use `Account`.\n\n+ `id` u32\n+ `old_id` deprecated u64\n+ `nickname` optional deprecated text\n\n    > Deprecated: Use `Account.name`.\n\n+ `account` deprecated Account"
+}
+
+/// Tests that deprecated types and fields are
+/// described as deprecated, and remain usable
+/// by code that allows deprecations.
+#[test]
+#[allow(deprecated)]
+pub fn codes_deprecations() {
+    let user = User {
+        id: 1,
+        old_id: 2,
+        nickname: Some("Ada".into()),
+        account: Account {
+            name: "Ada Lovelace".into(),
+        },
+    };
+
+    let bytes = encode_to_vec(&user).unwrap();
+    let decoded: User = decode_from_slice(&bytes).unwrap();
+    assert_eq!(user, decoded);
+
+    let data_type = User::data_type();
+    assert!(data_type.deprecated);
+    assert_eq!(
+        Some("Use `Account`."),
+        data_type.deprecation_note.as_deref()
+    );
+    assert!(!data_type.field("id").unwrap().deprecated);
+    assert!(data_type.field("old_id").unwrap().deprecated);
+    assert_eq!(
+        Some("Use `Account.name`."),
+        data_type
+            .field("nickname")
+            .unwrap()
+            .deprecation_note
+            .as_deref()
+    );
+    assert!(!Account::data_type().deprecated);
+}
@@ -0,0 +1,164 @@
+#![cfg(test)]
+//! Tests of the `Encodable` and `Decodable` derive macros.
+
+use codas::{
+    codec::{
+        decode_from_slice, encode_to_vec, CodecError, Compatibility, DataHeader, Decodable,
+        Encodable, Format, ReadsDecodable, WritesEncodable,
+    },
+    types::Text,
+};
+use codas_macros::{Decodable, Encodable};
+
+/// Data with a derived codec, declaring
+/// its data fields before its blob fields.
+#[derive(Default, Debug, Clone, PartialEq, Encodable, Decodable)]
+#[coda(ordinal = 3)]
+pub struct Derived {
+    pub text: Text,
+    pub number: i32,
+    pub(crate) list: Vec<u8>,
+    flag: bool,
+    optional: Option<u16>,
+}
+
+/// Data with the same fields as [`Derived`],
+/// but with a hand-written codec.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct HandWritten {
+    pub text: Text,
+    pub number: i32,
+    pub list: Vec<u8>,
+    pub flag: bool,
+    pub optional: Option<u16>,
+}
+
+impl Encodable for HandWritten {
+    const FORMAT: Format = Format::data(3)
+        .with(i32::FORMAT)
+        .with(bool::FORMAT)
+        .with(Text::FORMAT)
+        .with(<Vec<u8>>::FORMAT)
+        .with(<Option<u16>>::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        writer.write_data(&self.number)?;
+        writer.write_data(&self.flag)?;
+        writer.write_data(&self.text)?;
+        writer.write_data(&self.list)?;
+        writer.write_data(&self.optional)?;
+        Ok(())
+    }
+}
+
+impl Decodable for HandWritten {
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let _ = Self::ensure_header(header, &[3])?;
+        reader.read_data_into(&mut self.number)?;
+        reader.read_data_into(&mut self.flag)?;
+        reader.read_data_into(&mut self.text)?;
+        reader.read_data_into(&mut self.list)?;
+        reader.read_data_into(&mut self.optional)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn derives_codecs() -> Result<(), CodecError> {
+    let derived = Derived {
+        text: "derived!".into(),
+        number: -1337,
+        list: vec![1, 2, 3],
+        flag: true,
+        optional: Some(7),
+    };
+    let hand_written = HandWritten {
+        text: derived.text.clone(),
+        number: derived.number,
+        list: derived.list.clone(),
+        flag: derived.flag,
+        optional: derived.optional,
+    };
+
+    // Formats match, with blob fields first.
+    assert_eq!(HandWritten::FORMAT, Derived::FORMAT);
+    assert_eq!(5, Derived::FORMAT.as_data_format().blob_size);
+    assert_eq!(3, Derived::FORMAT.as_data_format().data_fields);
+    assert_eq!(3, Derived::FORMAT.as_data_format().ordinal);
+
+    // Encodings are byte-identical.
+    let mut derived_bytes = vec![];
+    derived_bytes.write_data(&derived)?;
+    let hand_written_bytes = encode_to_vec(&hand_written)?;
+    assert_eq!(hand_written_bytes, derived_bytes);
+
+    // Encodings round-trip.
+    let decoded: Derived = decode_from_slice(&derived_bytes)?;
+    assert_eq!(derived, decoded);
+
+    // Data with other ordinals isn't decoded.
+    derived_bytes[7] = 4;
+    assert!(matches!(
+        derived_bytes.as_slice().read_data::<Derived>(),
+        Err(CodecError::UnsupportedDataFormat { ordinal: 4, .. })
+    ));
+
+    Ok(())
+}
+
+/// An older version of [`Extended`].
+#[derive(Default, Debug, Clone, PartialEq, Encodable, Decodable)]
+#[coda(ordinal = 5)]
+pub struct Original {
+    pub number: i32,
+    pub text: Text,
+}
+
+/// A newer version of [`Original`], with added fields.
+#[derive(Default, Debug, Clone, PartialEq, Encodable, Decodable)]
+#[coda(ordinal = 5)]
+pub struct Extended {
+    pub number: i32,
+    pub text: Text,
+    pub flag: bool,
+    pub list: Vec<Text>,
+}
+
+#[test]
+fn skips_unknown_fields() -> Result<(), CodecError> {
+    let extended = Extended {
+        number: 42,
+        text: "extended".into(),
+        flag: true,
+        list: vec!["unknown".into()],
+    };
+    assert_eq!(
+        Compatibility::ReaderTolerates,
+        Extended::FORMAT.compatible_with(&Original::FORMAT)
+    );
+
+    // Unknown fields are skipped, leaving
+    // any following data intact.
+    let mut bytes = vec![];
+    bytes.write_data(&extended)?;
+    bytes.write_data(&7u32)?;
+    let mut reader = bytes.as_slice();
+    let original: Original = reader.read_data()?;
+    assert_eq!(42, original.number);
+    assert_eq!("extended", &*original.text);
+    assert_eq!(7u32, reader.read_data::<u32>()?);
+    assert!(reader.is_empty());
+
+    // Missing fields can't be decoded.
+    let bytes = encode_to_vec(&original)?;
+    assert!(matches!(
+        bytes.as_slice().read_data::<Extended>(),
+        Err(CodecError::MissingBlobLength { length: 1, .. })
+    ));
+
+    Ok(())
+}
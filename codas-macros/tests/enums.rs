@@ -0,0 +1,76 @@
+//! Tests for enums generated by [`codas_macros::inline_coda`].
+
+extern crate alloc;
+
+use codas::{
+    codec::{decode_from_slice, encode_to_vec, CodecError, ReadsDecodable},
+    types::{dynamic::DynamicValue, Unspecified},
+};
+
+codas_macros::inline_coda! {
+    "# `Accounts` Coda\n\n## `Account` Data\n\n+ `status` one of [Active, Suspended, Deleted]\n+ `role` optional one of [Admin, Member]"
+}
+
+/// Tests that enums are encoded as
+/// the `u16` ordinals of their variants.
+#[test]
+pub fn codes_enums() {
+    let account = Account {
+        status: AccountStatus::Suspended,
+        role: Some(AccountRole::Member),
+    };
+
+    let bytes = encode_to_vec(&account).unwrap();
+    let decoded: Account = decode_from_slice(&bytes).unwrap();
+    assert_eq!(account, decoded);
+
+    let bytes = encode_to_vec(&AccountStatus::Deleted).unwrap();
+    assert_eq!(2u16.to_le_bytes().as_slice(), bytes.as_slice());
+    assert_eq!(AccountStatus::Active, Account::default().status);
+    assert_eq!("Deleted", AccountStatus::Deleted.name());
+    assert_eq!(Some(2), AccountStatus::enum_type().ordinal_of("Deleted"));
+}
+
+/// Tests that unknown ordinals are rejected.
+#[test]
+pub fn rejects_unknown_variants() {
+    assert_eq!(Ok(AccountStatus::Deleted), AccountStatus::try_from(2));
+    assert_eq!(Err(3), AccountStatus::try_from(3));
+
+    let bytes = encode_to_vec(&3u16).unwrap();
+    assert!(matches!(
+        bytes.as_slice().read_data::<AccountStatus>(),
+        Err(CodecError::InvalidEnumVariant { ordinal: 3 })
+    ));
+
+    // Unknown ordinals are preserved by dynamic values.
+    let dynamic = Unspecified::Enum {
+        typing: AccountStatus::enum_type(),
+        variant: 3,
+    };
+    assert!(AccountStatus::try_from_dynamic(&dynamic).is_err());
+    assert_eq!(
+        AccountStatus::Suspended.to_dynamic(),
+        Unspecified::Enum {
+            typing: AccountStatus::enum_type(),
+            variant: 1,
+        }
+    );
+}
+
+/// Tests that enums are serialized
+/// as the names of their variants.
+#[test]
+pub fn serializes_enums() {
+    let account = Account {
+        status: AccountStatus::Deleted,
+        role: None,
+    };
+    let json = serde_json::to_value(&account).unwrap();
+    assert_eq!(
+        serde_json::json!({ "status": "Deleted", "role": null }),
+        json
+    );
+    assert_eq!(account, serde_json::from_value(json).unwrap());
+    assert_eq!("Suspended", AccountStatus::Suspended.to_string());
+}
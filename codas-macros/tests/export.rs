@@ -79,7 +79,7 @@ pub fn unspecified_map_via_rust() {
         .insert("enabled".into(), Unspecified::Bool(true));
     example
         .request_data
-        .insert("ratio".into(), Unspecified::F64(3.14));
+        .insert("ratio".into(), Unspecified::F64(1.5));
 
     assert_eq!(example.request_data.len(), 4);
     assert_eq!(
@@ -201,3 +201,194 @@ pub fn unspecified_map_from_arbitrary_json() {
     assert_eq!(rd["metadata"]["version"], json!(2));
     assert_eq!(rd["metadata"]["draft"], json!(false));
 }
+
+/// Tests that generated types are human-readable
+/// via their generated `Display` implementations.
+#[test]
+pub fn displays_generated_types() {
+    let request = Request {
+        message: Text::from("Hi!"),
+    };
+    assert_eq!("Request { message: Hi! }", format!("{request}"));
+
+    let response = Response {
+        message: Text::from("Hello!"),
+        original_request: request,
+    };
+    assert_eq!(
+        "Response { message: Hello!, original_request: Request { message: Hi! } }",
+        format!("{response}")
+    );
+
+    // The coda's enum includes the variant's ordinal.
+    let data = GreeterData::from(response);
+    assert_eq!(
+        "2: Response { message: Hello!, original_request: Request { message: Hi! } }",
+        format!("{data}")
+    );
+
+    // Fields without a `Display` implementation are debugged.
+    let example = Example {
+        optional_message: Some(Text::from("World!")),
+        ..Default::default()
+    };
+    let displayed = format!("{example}");
+    assert!(
+        displayed.contains("optional_message: Some(\"World!\")"),
+        "{displayed}"
+    );
+    assert!(displayed.contains("listing: []"), "{displayed}");
+}
+
+#[test]
+pub fn builds_generated_types() {
+    use codas::codec::encode_to_vec;
+
+    let request = Request {
+        message: Text::from("Hi!"),
+    };
+    let response = Response {
+        message: Text::from("Hello!"),
+        original_request: request.clone(),
+    };
+
+    let built = Response::builder()
+        .message(Text::from("Hello!"))
+        .original_request(Request::builder().message(Text::from("Hi!")).build())
+        .build();
+    assert_eq!(response, built);
+
+    let encoded = encode_to_vec(&response).unwrap();
+    let built_encoded = encode_to_vec(&built).unwrap();
+    assert_eq!(encoded, built_encoded);
+
+    // Optional fields accept options.
+    let example = Example::builder()
+        .optional_message(Some(Text::from("World!")))
+        .build();
+    assert_eq!(Some(Text::from("World!")), example.optional_message);
+}
+
+#[test]
+pub fn converts_generated_types_to_and_from_unspecified() {
+    let example = Example {
+        num_1: 3u8,
+        num_5: -3i8,
+        num_10: 3.33f64,
+        boolean: true,
+        message: Text::from("Hello!"),
+        listing: vec![Text::from("a"), Text::from("b")],
+        mapping: BTreeMap::from([(Text::from("half"), 1.5f32)]),
+        optional_message: Some(Text::from("World!")),
+        request_data: BTreeMap::from([(Text::from("count"), Unspecified::U64(42))]),
+        ..Default::default()
+    };
+
+    let dynamic = Unspecified::from(&example);
+    let Unspecified::Map(fields) = &dynamic else {
+        panic!("expected a map, found {dynamic:?}");
+    };
+    assert_eq!(Some(&Unspecified::U8(3)), fields.get("num_1"));
+    assert_eq!(
+        Some(&Unspecified::Text(Text::from("World!"))),
+        fields.get("optional_message")
+    );
+    assert_eq!(example, Example::try_from(&dynamic).unwrap());
+
+    // Nested data is converted recursively.
+    let response = Response {
+        message: Text::from("Hello!"),
+        original_request: Request {
+            message: Text::from("Hi!"),
+        },
+    };
+    let dynamic = Unspecified::from(&response);
+    assert_eq!(response, Response::try_from(&dynamic).unwrap());
+
+    // Missing optional fields are `None`; missing
+    // required fields are errors.
+    let Unspecified::Map(mut fields) = Unspecified::from(&example) else {
+        unreachable!();
+    };
+    fields.remove("optional_message");
+    let converted = Example::try_from(&Unspecified::Map(fields.clone())).unwrap();
+    assert_eq!(None, converted.optional_message);
+    fields.remove("num_1");
+    assert!(Example::try_from(&Unspecified::Map(fields)).is_err());
+}
+
+#[test]
+pub fn describes_generated_types() {
+    use codas::{codec::Encodable, types::Type};
+
+    let data_type = Response::data_type();
+    assert_eq!("Response", data_type.name);
+    assert_eq!(<Response as Encodable>::FORMAT, *data_type.format());
+
+    // Nested data types are described recursively.
+    let original_request = data_type.iter().nth(1).unwrap();
+    assert_eq!("original_request", original_request.name);
+    assert_eq!(Type::Data(Request::data_type()), original_request.typing);
+
+    // Dynamic conversions match the data type.
+    let response = Response {
+        message: Text::from("Hello!"),
+        original_request: Request {
+            message: Text::from("Hi!"),
+        },
+    };
+    Unspecified::from(&response)
+        .verify_type(&Type::Data(data_type))
+        .unwrap();
+    assert_eq!(
+        <Example as Encodable>::FORMAT,
+        *Example::data_type().format()
+    );
+}
+
+/// Tests that data with ordinals unknown to a coda
+/// are preserved when decoded by the coda's enum.
+#[test]
+pub fn preserves_unknown_data() {
+    use codas::codec::{decode_from_slice, encode_to_vec};
+
+    // `Response`s aren't in the `Full` coda.
+    let response = GreeterData::from(Response {
+        message: Text::from("Hello!"),
+        original_request: Request {
+            message: Text::from("Hi!"),
+        },
+    });
+    let bytes = encode_to_vec(&response).unwrap();
+
+    let decoded: FullData = decode_from_slice(&bytes).unwrap();
+    assert!(matches!(
+        decoded,
+        FullData::Unspecified(Unspecified::Data { .. })
+    ));
+
+    let reencoded = encode_to_vec(&decoded).unwrap();
+    assert_eq!(bytes, reencoded);
+
+    // The preserved data is still decodable.
+    let redecoded: GreeterData = decode_from_slice(&reencoded).unwrap();
+    assert_eq!(response, redecoded);
+}
+
+/// Tests that generated optional fields preserve
+/// the presence of default values.
+#[test]
+pub fn round_trips_present_default_optionals() {
+    use codas::codec::{decode_from_slice, encode_to_vec};
+
+    for optional_message in [Some(Text::from("")), Some(Text::from("World!")), None] {
+        let example = Example {
+            optional_message,
+            ..Default::default()
+        };
+
+        let encoded = encode_to_vec(&example).unwrap();
+        let decoded: Example = decode_from_slice(&encoded).unwrap();
+        assert_eq!(example, decoded);
+    }
+}
@@ -0,0 +1,67 @@
+//! Tests for [`codas_macros::inline_coda`].
+
+extern crate alloc;
+
+use codas::codec::{decode_from_slice, encode_to_vec};
+
+mod escaped {
+    codas_macros::inline_coda! { "# `MyCoda` Coda\n\n## `Foo` Data\n\n+ `x` i32" }
+}
+
+mod raw {
+    codas_macros::inline_coda! {
+        r#"# `Inline` Coda
+
+Codas defined in-source.
+
+## `Foo` Data
+
++ `x` i32
+
+## `Bar` Data
+
++ `name` text
+
+    The bar's "name".
+
++ `foo` optional Foo
+"#
+    }
+}
+
+/// Tests that an inline coda generates
+/// the same types as an exported coda.
+#[test]
+pub fn generates_inline_types() {
+    let foo = escaped::Foo { x: 42 };
+    let data = escaped::MyCodaData::from(foo.clone());
+
+    let bytes = encode_to_vec(&data).unwrap();
+    let decoded: escaped::MyCodaData = decode_from_slice(&bytes).unwrap();
+    assert_eq!(data, decoded);
+    assert!(matches!(
+        decoded,
+        escaped::MyCodaData::Foo(escaped::Foo { x: 42 })
+    ));
+}
+
+/// Tests that inline codas may be
+/// written as raw, multiline strings.
+#[test]
+pub fn generates_raw_inline_types() {
+    let bar = raw::Bar {
+        name: "Bar".into(),
+        foo: Some(raw::Foo { x: 7 }),
+    };
+
+    let bytes = encode_to_vec(&bar).unwrap();
+    let decoded: raw::Bar = decode_from_slice(&bytes).unwrap();
+    assert_eq!(bar, decoded);
+
+    let name = raw::Bar::data_type()
+        .iter()
+        .find(|field| field.name == "name")
+        .cloned()
+        .unwrap();
+    assert_eq!(Some("The bar's \"name\".".into()), name.docs);
+}
@@ -0,0 +1,25 @@
+//! Tests for [`codas_macros::coda_schema`].
+use codas::{parse::parse, types::Coda};
+use codas_macros::coda_schema;
+
+/// Tests that embedded codas match
+/// the codas parsed from their files.
+#[test]
+pub fn embeds_coda_schemas() {
+    assert_eq!(
+        "Greeter",
+        coda_schema!("codas-macros/tests/greeter_coda.md").local_name
+    );
+
+    let coda: &'static Coda = coda_schema!("codas-macros/tests/greeter_coda.md");
+    assert_eq!(&parse(include_str!("greeter_coda.md")).unwrap(), coda);
+    assert_eq!(
+        "request_id",
+        coda.data_type("Nesting")
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap()
+            .name
+    );
+}
@@ -1,5 +1,15 @@
+extern crate alloc;
+
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+};
+
 use ::prost::Message;
-use codas::codec::{ReadsDecodable, WritesEncodable};
+use codas::{
+    codec::{DataHeader, Encodable, ReadsDecodable, WritesEncodable},
+    stream::{BufferedReads, BufferedWrites},
+};
 use codas_macros::export_coda;
 use criterion::{criterion_group, criterion_main, Criterion};
 
@@ -7,12 +17,9 @@ mod prost_boat;
 
 export_coda!("codas/benches/sailboat.md");
 
-fn codecs(c: &mut Criterion) {
-    let mut group = c.benchmark_group("Codecs");
-    group.throughput(criterion::Throughput::Elements(1));
-
-    // Create a Codas sailboat.
-    let codas_boat = Boat {
+/// Returns a new Codas sailboat.
+fn codas_boat() -> Boat {
+    Boat {
         name: "In Amber Clad".into(),
         seaworthy: true,
         sail: Sail {
@@ -29,7 +36,15 @@ fn codecs(c: &mut Criterion) {
             manufacture_year: "2547".into(),
             model_year: "2515".into(),
         },
-    };
+    }
+}
+
+fn codecs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Codecs");
+    group.throughput(criterion::Throughput::Elements(1));
+
+    // Create a Codas sailboat.
+    let codas_boat = codas_boat();
 
     // Pre-encode it's bytes for decoding later.
     let mut codas_boat_bytes = vec![];
@@ -110,7 +125,141 @@ fn codecs(c: &mut Criterion) {
     });
 }
 
+fn bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Bytes");
+    let payload: Vec<u8> = (0..=255).cycle().take(1024 * 1024).collect();
+    group.throughput(criterion::Throughput::Bytes(payload.len() as u64));
+
+    // Pre-encode the payload for decoding later.
+    let mut payload_bytes = vec![];
+    payload_bytes.write_data(&payload).unwrap();
+    let payload_bytes = payload_bytes;
+
+    // Encoding (element-by-element, like any other list)
+    group.bench_function("Elements - Encode", |b| {
+        let mut bytes = vec![];
+        b.iter(|| {
+            bytes.clear();
+            payload.encode_header(&mut bytes).unwrap();
+            for byte in &payload {
+                bytes.write_data(byte).unwrap();
+            }
+            assert_eq!(payload_bytes.len(), bytes.len());
+        });
+    });
+
+    // Encoding (single pass)
+    group.bench_function("Vec<u8> - Encode", |b| {
+        let mut bytes = vec![];
+        b.iter(|| {
+            bytes.clear();
+            bytes.write_data(&payload).unwrap();
+            assert_eq!(payload_bytes.len(), bytes.len());
+        });
+    });
+
+    // Decoding (element-by-element, like any other list)
+    group.bench_function("Elements - Decode", |b| {
+        let mut decoded = vec![];
+        b.iter(|| {
+            let mut reader = payload_bytes.as_slice();
+            let header: DataHeader = reader.read_data().unwrap();
+            decoded.clear();
+            for _ in 0..header.count {
+                decoded.push(reader.read_data::<u8>().unwrap());
+            }
+            assert_eq!(payload.len(), decoded.len());
+        });
+    });
+
+    // Decoding (single pass)
+    group.bench_function("Vec<u8> - Decode", |b| {
+        let mut decoded: Vec<u8> = vec![];
+        b.iter(|| {
+            payload_bytes
+                .as_slice()
+                .read_data_into(&mut decoded)
+                .unwrap();
+            assert_eq!(payload.len(), decoded.len());
+        });
+    });
+}
+
+fn sockets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Sockets");
+    const BOATS: usize = 100;
+    group.throughput(criterion::Throughput::Elements(BOATS as u64));
+
+    let boat = codas_boat();
+    let mut boat_bytes = vec![];
+    boat_bytes.write_data(&boat).unwrap();
+
+    // Accept connections which discard all received
+    // bytes, while sending an endless stream of boats.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut sink = stream.try_clone().unwrap();
+            std::thread::spawn(move || std::io::copy(&mut sink, &mut std::io::sink()));
+            let boat_bytes = boat_bytes.clone();
+            std::thread::spawn(move || while stream.write_all(&boat_bytes).is_ok() {});
+        }
+    });
+    let connect = || {
+        let stream = TcpStream::connect(address).unwrap();
+        stream.set_nodelay(true).unwrap();
+        stream
+    };
+
+    // Encoding (Unbuffered)
+    group.bench_function("Unbuffered - Encode", |b| {
+        let mut stream = connect();
+        b.iter(|| {
+            for _ in 0..BOATS {
+                stream.write_data(&boat).unwrap();
+            }
+        });
+    });
+
+    // Encoding (Buffered)
+    group.bench_function("Buffered - Encode", |b| {
+        let mut stream = BufferedWrites::<_>::new(connect());
+        b.iter(|| {
+            for _ in 0..BOATS {
+                stream.write_data(&boat).unwrap();
+            }
+            stream.flush().unwrap();
+        });
+    });
+
+    // Decoding (Unbuffered)
+    group.bench_function("Unbuffered - Decode", |b| {
+        let mut stream = connect();
+        let mut decoded = Boat::default();
+        b.iter(|| {
+            for _ in 0..BOATS {
+                stream.read_data_into(&mut decoded).unwrap();
+            }
+            assert_eq!(boat, decoded);
+        });
+    });
+
+    // Decoding (Buffered)
+    group.bench_function("Buffered - Decode", |b| {
+        let mut stream = BufferedReads::<_>::new(connect());
+        let mut decoded = Boat::default();
+        b.iter(|| {
+            for _ in 0..BOATS {
+                stream.read_data_into(&mut decoded).unwrap();
+            }
+            assert_eq!(boat, decoded);
+        });
+    });
+}
+
 // Create a new group named `benches` and
 // run it with all benchmark methods.
-criterion_group!(benches, codecs);
+criterion_group!(benches, codecs, bytes, sockets);
 criterion_main!(benches);
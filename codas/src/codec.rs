@@ -122,16 +122,44 @@
 //! provides enough information to _traverse_ any data,
 //! but the data's contents won't be useful without
 //! having the data's corresponding documentation.
+//!
+//! Canonical encodings of sample data are available
+//! as test vectors in the `vectors` module, when the
+//! `vectors` feature is enabled.
+//!
+//! ## Untrusted Data
+//!
+//! Decoding never panics, no matter what bytes are
+//! decoded: malformed or malicious data always results
+//! in a [`CodecError`]. Decoders read through a
+//! [`LimitedReader`] by default, so that data can't
+//! force unbounded allocations, recursion, or loops.
+//!
+//! This invariant is checked by the fuzz tests
+//! in `tests/fuzz.rs`, which decode random and
+//! randomly-mutated bytes as codas, data types,
+//! and dynamic values.
+use alloc::format;
+
 use snafu::{Backtrace, Snafu};
 
-use crate::stream::StreamError;
+use crate::{stream::StreamError, types::Text};
 
 // Expose encoder and decoder APIs as part of this module,
 // while keeping them in separate files to reduce clutter.
+#[cfg(feature = "async")]
+pub mod asynchronous;
+mod checksum;
 mod decode;
+mod dump;
 mod encode;
-pub(crate) use decode::DecodingScope;
+#[cfg(any(feature = "vectors", test))]
+pub mod vectors;
+pub mod view;
+pub use checksum::CHECKED_FRAME_MAGIC;
 pub use decode::*;
+pub(crate) use decode::{read_bytes_into, DecodingScope};
+pub use dump::*;
 pub use encode::*;
 
 /// The low-level encoding format of some data.
@@ -176,16 +204,35 @@ impl Format {
     /// This operation is _not_ commutative; that
     /// is, `self.with(other)` and `other.with(self)`
     /// may return different formats.
+    ///
+    /// # Panics
+    ///
+    /// Iff the new format would overflow, as described by
+    /// [`Self::checked_with`]. When evaluated in a const
+    /// context (like an [`Encodable::FORMAT`]), an overflow
+    /// is reported as a compile-time error.
     pub const fn with(self, other: Self) -> Self {
-        match (self, other) {
+        match self.checked_with(other) {
+            Some(format) => format,
+            None => panic!(
+                "format overflow in Format::with: data may contain at most \
+                 65535 bytes of blob fields and 255 data fields"
+            ),
+        }
+    }
+
+    /// Returns a new `self` containing additional
+    /// data with `other`'s format, or `None` if the
+    /// new format would contain more than [`u16::MAX`]
+    /// bytes of blob fields or more than [`u8::MAX`]
+    /// data fields.
+    pub const fn checked_with(self, other: Self) -> Option<Self> {
+        let format = match (self, other) {
             // Adding blobs together yields a bigger blob.
-            (Format::Blob(f1), Format::Blob(f2)) => {
-                assert!(
-                    f1 as u32 + f2 as u32 <= u16::MAX as u32,
-                    "blob size overflow in Format::with"
-                );
-                Self::Blob(f1 + f2)
-            }
+            (Format::Blob(f1), Format::Blob(f2)) => match f1.checked_add(f2) {
+                Some(size) => Self::Blob(size),
+                None => return None,
+            },
 
             // Adding data to a blob yields data containing
             // the blob and a single data field.
@@ -201,12 +248,11 @@ impl Format {
             // Adding blobs to data yields the same data,
             // with a bigger blob.
             (Format::Data(format), Format::Blob(size)) => {
-                assert!(
-                    format.blob_size as u32 + size as u32 <= u16::MAX as u32,
-                    "blob size overflow in Format::with"
-                );
+                let Some(blob_size) = format.blob_size.checked_add(size) else {
+                    return None;
+                };
                 DataFormat {
-                    blob_size: format.blob_size + size,
+                    blob_size,
                     data_fields: format.data_fields,
                     ordinal: format.ordinal,
                 }
@@ -216,13 +262,12 @@ impl Format {
             // Adding data to data yields the same data,
             // with more data fields.
             (Format::Data(format), Format::Data(_)) | (Format::Data(format), Format::Fluid) => {
-                assert!(
-                    (format.data_fields as u16) < u8::MAX as u16,
-                    "data fields overflow in Format::with"
-                );
+                let Some(data_fields) = format.data_fields.checked_add(1) else {
+                    return None;
+                };
                 DataFormat {
                     blob_size: format.blob_size,
-                    data_fields: format.data_fields + 1,
+                    data_fields,
                     ordinal: format.ordinal,
                 }
                 .as_format()
@@ -232,6 +277,59 @@ impl Format {
             (Format::Fluid, Format::Blob(_))
             | (Format::Fluid, Format::Data(_))
             | (Format::Fluid, Format::Fluid) => Format::Fluid,
+        };
+
+        Some(format)
+    }
+
+    /// Returns the [`Compatibility`] of data encoded
+    /// with `self` with a decoder expecting `reader`.
+    ///
+    /// Decoders of data with a non-zero ordinal (like
+    /// the data types of a coda) skip any blob bytes or data
+    /// fields in excess of their own; data with missing blob
+    /// bytes or data fields can't be decoded. Sequences of
+    /// unspecified data (like lists and text) are decoded
+    /// element-by-element, and must have identical formats.
+    pub fn compatible_with(&self, reader: &Format) -> Compatibility {
+        match (self, reader) {
+            (writer, reader) if writer == reader => Compatibility::Identical,
+
+            (Format::Data(writer), Format::Data(reader)) => {
+                if writer.ordinal != reader.ordinal {
+                    Compatibility::incompatible(format!(
+                        "data with ordinal {} can't be decoded as ordinal {}",
+                        writer.ordinal, reader.ordinal
+                    ))
+                } else if writer.ordinal == 0 {
+                    Compatibility::incompatible(format!(
+                        "unspecified data with format {writer:?} can't be decoded as {reader:?}"
+                    ))
+                } else if writer.blob_size < reader.blob_size {
+                    Compatibility::incompatible(format!(
+                        "data is missing {} bytes of blob fields",
+                        reader.blob_size - writer.blob_size
+                    ))
+                } else if writer.data_fields < reader.data_fields {
+                    Compatibility::incompatible(format!(
+                        "data is missing {} data fields",
+                        reader.data_fields - writer.data_fields
+                    ))
+                } else {
+                    Compatibility::ReaderTolerates
+                }
+            }
+
+            // Fluid data can be decoded from (and
+            // skips) any structured data.
+            (Format::Data(_), Format::Fluid) => Compatibility::ReaderTolerates,
+            (Format::Fluid, Format::Data(_)) => {
+                Compatibility::incompatible("fluid data may be encoded with any format")
+            }
+
+            (writer, reader) => {
+                Compatibility::incompatible(format!("{writer:?} can't be decoded as {reader:?}"))
+            }
         }
     }
 
@@ -259,6 +357,49 @@ impl Format {
         }
     }
 
+    /// Returns the exact number of bytes any data with
+    /// this format encodes to, or `None` if the size
+    /// varies (as is the case for [`Format::Fluid`], or
+    /// for [`Format::Data`] containing data fields, like
+    /// lists or text).
+    ///
+    /// The size of [`Format::Data`] includes the
+    /// [`DataHeader`] preceding the data.
+    pub const fn fixed_size(self) -> Option<usize> {
+        match self.size_hint() {
+            (min, Some(max)) if min == max => Some(min),
+            _ => None,
+        }
+    }
+
+    /// Returns the minimum number of bytes any data with
+    /// this format encodes to, and the maximum number of
+    /// bytes (if known).
+    ///
+    /// Data fields are assumed to encode to (at least)
+    /// their own [`DataHeader`], as is the case for
+    /// empty data fields.
+    pub const fn size_hint(self) -> (usize, Option<usize>) {
+        let header_size = DataHeader::FORMAT.as_data_format().blob_size as usize;
+
+        match self {
+            Format::Blob(size) => (size as usize, Some(size as usize)),
+
+            Format::Data(format) => {
+                let min = header_size
+                    + format.blob_size as usize
+                    + format.data_fields as usize * header_size;
+                if format.data_fields == 0 {
+                    (min, Some(min))
+                } else {
+                    (min, None)
+                }
+            }
+
+            Format::Fluid => (header_size, None),
+        }
+    }
+
     /// Encodes this format's default value to `writer`.
     pub fn encode_default_value(
         &self,
@@ -387,13 +528,64 @@ impl Decodable for Format {
                 *self = Format::Fluid;
             }
 
-            _ => unreachable!(),
+            ordinal => {
+                return UnsupportedDataFormatSnafu {
+                    ordinal,
+                    offset: None,
+                }
+                .fail();
+            }
         }
 
         Ok(())
     }
 }
 
+/// Whether data encoded with one format (or data type)
+/// can be decoded by a decoder expecting another.
+///
+/// Refer to [`Format::compatible_with`] and
+/// [`DataType::compatible_with`](crate::types::DataType::compatible_with).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Compatibility {
+    /// The formats are identical.
+    Identical,
+
+    /// The formats differ, but the reader can decode the
+    /// data by skipping trailing blob bytes or data fields.
+    ReaderTolerates,
+
+    /// The reader can't decode the data.
+    Incompatible {
+        /// Why the reader can't decode the data.
+        reason: Text,
+    },
+}
+
+impl Compatibility {
+    /// Returns a new [`Compatibility::Incompatible`] with `reason`.
+    pub(crate) fn incompatible(reason: impl Into<Text>) -> Self {
+        Self::Incompatible {
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns true iff the reader can decode the data.
+    pub fn is_compatible(&self) -> bool {
+        !matches!(self, Self::Incompatible { .. })
+    }
+
+    /// Returns the least compatible of `self` and `other`.
+    pub(crate) fn and(self, other: Self) -> Self {
+        match (self, other) {
+            (incompatible @ Self::Incompatible { .. }, _) => incompatible,
+            (_, incompatible @ Self::Incompatible { .. }) => incompatible,
+            (Self::Identical, Self::Identical) => Self::Identical,
+            _ => Self::ReaderTolerates,
+        }
+    }
+}
+
 /// Contents of a [`Format::Data`].
 ///
 /// Fields are ordered to match wire layout.
@@ -411,9 +603,9 @@ pub struct DataFormat {
     /// corresponding documentation, or `0` if the type
     /// is unspecified.
     ///
-    /// Built-in types count down from 255; user-defined
-    /// types count up from 1, giving ~241 user ordinals
-    /// per coda.
+    /// Built-in types count down from 255 (reserving
+    /// 224–255); user-defined types count up from 1,
+    /// giving 223 user ordinals per coda.
     pub ordinal: u8,
 }
 
@@ -495,7 +687,7 @@ impl Decodable for DataHeader {
         self.format.data_fields = buf[6];
         self.format.ordinal = buf[7];
 
-        Ok(())
+        reader.count_elements(self.count)
     }
 }
 
@@ -513,30 +705,45 @@ pub enum CodecError {
     },
 
     /// A header for the wrong data format was given to a decoder.
-    #[snafu(display("expected to decode {expected:?}, but found {actual:?}"))]
+    #[snafu(display(
+        "expected to decode {expected:?}, but found {actual:?}{}",
+        AtOffset(*offset)
+    ))]
     UnexpectedDataFormat {
         expected: Format,
         actual: Option<DataHeader>,
+        offset: Option<u64>,
         backtrace: Backtrace,
     },
 
     /// An unsupported data format ordinal was given to a decoder.
-    #[snafu(display("unsupported data format (ordinal {ordinal:?})"))]
-    UnsupportedDataFormat { ordinal: u8, backtrace: Backtrace },
+    #[snafu(display("unsupported data format (ordinal {ordinal:?}){}", AtOffset(*offset)))]
+    UnsupportedDataFormat {
+        ordinal: u8,
+        offset: Option<u64>,
+        backtrace: Backtrace,
+    },
 
     /// A decoder expected to decode more blob fields' data.
-    #[snafu(display("expected to decode {length} more bytes of blob field data"))]
-    MissingBlobLength { length: u16 },
+    #[snafu(display(
+        "expected to decode {length} more bytes of blob field data{}",
+        AtOffset(*offset)
+    ))]
+    MissingBlobLength { length: u16, offset: Option<u64> },
 
     /// A decoder expected to decode more data fields.
-    #[snafu(display("expected to decode {count} more fields of data"))]
-    MissingDataFields { count: u8 },
+    #[snafu(display("expected to decode {count} more fields of data{}", AtOffset(*offset)))]
+    MissingDataFields { count: u8, offset: Option<u64> },
 
     /// A sequence length exceeded the maximum
     /// representable count ([`u32::MAX`]).
     #[snafu(display("sequence length {length} exceeds maximum count ({})", u32::MAX))]
     CountOverflow { length: usize },
 
+    /// A map's keys and values had different lengths.
+    #[snafu(display("a map has {keys} keys but {values} values"))]
+    MapLengthMismatch { keys: usize, values: usize },
+
     /// A map key was not a Text value while decoding unspecified data.
     #[snafu(display("an unspecified map's keys must be Text, but found ordinal {ordinal}"))]
     UnsupportedUnspecifiedMapKey { ordinal: u8 },
@@ -545,27 +752,165 @@ pub enum CodecError {
     #[snafu(display("an unspecified map has {keys} keys but {values} values"))]
     UnspecifiedMapLengthMismatch { keys: usize, values: usize },
 
+    /// Text's encoded bytes weren't valid UTF-8,
+    /// starting at byte `at` of the text.
+    #[snafu(display("text contained invalid UTF-8 at byte {at}"))]
+    InvalidText { at: usize },
+
+    /// A character's encoded value wasn't a Unicode scalar value.
+    #[snafu(display("{value:#x} is not a valid unicode scalar value"))]
+    InvalidChar { value: u32 },
+
+    /// An enum's encoded ordinal didn't correspond
+    /// to any of the enum's known variants.
+    #[snafu(display("{ordinal} is not the ordinal of a known enum variant"))]
+    InvalidEnumVariant { ordinal: u16 },
+
+    /// A variable-length integer's encoding was malformed,
+    /// redundant, or overflowed a 64-bit integer.
+    #[snafu(display("invalid variable-length integer"))]
+    InvalidVarInt,
+
     /// The stream ended before the expected number of bytes were read.
     #[snafu(display("unexpected end of stream"))]
     UnexpectedEof,
 
+    /// Framed data was decoded from fewer bytes than its frame contained.
+    #[snafu(display("expected to decode {length} bytes of framed data, but decoded {decoded}"))]
+    FrameLengthMismatch { length: u32, decoded: u64 },
+
+    /// A checked frame of data didn't start with
+    /// the [`CHECKED_FRAME_MAGIC`] number.
+    #[snafu(display(
+        "expected a checked frame (magic {CHECKED_FRAME_MAGIC:#06x}), but found magic {magic:#06x}"
+    ))]
+    BadMagic { magic: u16 },
+
+    /// A checked frame of data's checksum didn't match its contents.
+    #[snafu(display(
+        "checked frame is corrupt: expected checksum {expected:#010x}, but computed {actual:#010x}"
+    ))]
+    CorruptFrame { expected: u32, actual: u32 },
+
     /// The byte limit for decoding was exceeded.
     #[snafu(display("byte limit exceeded during decoding"))]
     ByteLimitExceeded,
 
+    /// The element limit for decoding was exceeded.
+    #[snafu(display("element limit exceeded during decoding"))]
+    ElementLimitExceeded,
+
     /// The nesting depth limit for decoding was exceeded.
     #[snafu(display("nesting depth limit exceeded during decoding"))]
     DepthLimitExceeded,
 
     /// An error occurred while reading or
     /// writing the underlying data stream.
-    #[snafu(display("error when reading or writing from a data stream: {source}"))]
-    Stream { source: StreamError },
+    #[snafu(display(
+        "error when reading or writing from a data stream{}: {source}",
+        AtOffset(*offset)
+    ))]
+    Stream {
+        source: StreamError,
+        offset: Option<u64>,
+    },
+
+    /// An error occurred while writing a
+    /// textual representation of data.
+    #[snafu(display("error when writing a textual representation of data"))]
+    Formatting,
+}
+
+impl CodecError {
+    /// Returns the byte offset in the decoded stream
+    /// where this error occurred, if known.
+    ///
+    /// Offsets are only known for errors returned
+    /// by [`TrackedReader::read_data_at`](crate::stream::TrackedReader::read_data_at).
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            Self::UnexpectedDataFormat { offset, .. }
+            | Self::UnsupportedDataFormat { offset, .. }
+            | Self::MissingBlobLength { offset, .. }
+            | Self::MissingDataFields { offset, .. }
+            | Self::Stream { offset, .. } => *offset,
+            _ => None,
+        }
+    }
+
+    /// Returns this error with its byte offset set to
+    /// `at`, if this error supports offsets.
+    pub fn with_offset(mut self, at: u64) -> Self {
+        match &mut self {
+            Self::UnexpectedDataFormat { offset, .. }
+            | Self::UnsupportedDataFormat { offset, .. }
+            | Self::MissingBlobLength { offset, .. }
+            | Self::MissingDataFields { offset, .. }
+            | Self::Stream { offset, .. } => *offset = Some(at),
+            _ => {}
+        }
+
+        self
+    }
 }
 
 impl From<StreamError> for CodecError {
     fn from(value: StreamError) -> Self {
-        Self::Stream { source: value }
+        Self::Stream {
+            source: value,
+            offset: None,
+        }
+    }
+}
+
+/// [`core::fmt::Display`] suffix describing
+/// an optional byte offset of a [`CodecError`].
+struct AtOffset(Option<u64>);
+
+impl core::fmt::Display for AtOffset {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(offset) => write!(f, " at byte {offset}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<core::fmt::Error> for CodecError {
+    fn from(_: core::fmt::Error) -> Self {
+        Self::Formatting
+    }
+}
+
+/// Converts a [`CodecError`] into an [`std::io::Error`]
+/// containing the original error, with a kind of:
+///
+/// - [`BrokenPipe`](std::io::ErrorKind::BrokenPipe) for a closed stream.
+/// - [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) for an
+///   empty stream, or an [`CodecError::UnexpectedEof`].
+/// - The original kind of errors from an [`std::io`] stream.
+/// - [`InvalidData`](std::io::ErrorKind::InvalidData) for all other errors.
+#[cfg(any(feature = "std", test))]
+impl From<CodecError> for std::io::Error {
+    fn from(value: CodecError) -> Self {
+        let kind = match &value {
+            CodecError::Stream {
+                source: StreamError::Closed,
+                ..
+            } => std::io::ErrorKind::BrokenPipe,
+            CodecError::Stream {
+                source: StreamError::Empty,
+                ..
+            }
+            | CodecError::UnexpectedEof => std::io::ErrorKind::UnexpectedEof,
+            CodecError::Stream {
+                source: StreamError::Io { source },
+                ..
+            } => source.kind(),
+            _ => std::io::ErrorKind::InvalidData,
+        };
+
+        std::io::Error::new(kind, value)
     }
 }
 
@@ -584,6 +929,30 @@ mod tests {
 
     use crate::{stream::Writes, types::Text};
 
+    #[test]
+    fn converts_to_io_errors() {
+        use std::error::Error;
+
+        let error = std::io::Error::from(CodecError::from(StreamError::Closed));
+        assert_eq!(std::io::ErrorKind::BrokenPipe, error.kind());
+        assert_eq!(
+            Some(&StreamError::Closed),
+            error.source().and_then(|e| e.downcast_ref::<StreamError>())
+        );
+
+        let error = std::io::Error::from(CodecError::from(StreamError::Empty));
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error.kind());
+        assert!(error.source().is_some());
+
+        let error = std::io::Error::from(CodecError::ByteLimitExceeded);
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+        assert_eq!("byte limit exceeded during decoding", error.to_string());
+        assert!(matches!(
+            error.get_ref().and_then(|e| e.downcast_ref::<CodecError>()),
+            Some(CodecError::ByteLimitExceeded)
+        ));
+    }
+
     /// Test data for codecs.
     pub(super) struct TestData {
         pub num_a: i32,
@@ -650,14 +1019,34 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn hints_format_sizes() {
+        use crate::types::tests::{NestedTestData, TestData};
+
+        // Text lists can be any size.
+        assert_eq!(None, TestData::FORMAT.fixed_size());
+        assert_eq!((8 + 12 + 4 * 8, None), TestData::FORMAT.size_hint());
+
+        // Data with only blob fields is always
+        // one header and its blob fields.
+        assert_eq!(Some(8 + 1), NestedTestData::FORMAT.fixed_size());
+        assert_eq!(Some(4), Format::Blob(4).fixed_size());
+        assert_eq!((8, None), Format::Fluid.size_hint());
+        assert_eq!(None, Format::Fluid.fixed_size());
+
+        // Fixed sizes match actual encodings.
+        let bytes = encode_to_vec(&NestedTestData { boolean: true }).unwrap();
+        assert_eq!(Some(bytes.len()), NestedTestData::FORMAT.fixed_size());
+    }
+
     /// Test codec for [`Format`]s.
     #[test]
     fn format_codec() {
         // Blobs.
         let blob_format = Format::Blob(69);
-        let mut bytes = vec![];
-        bytes.write_data(&blob_format).unwrap();
-        assert_eq!(blob_format, bytes.as_slice().read_data().unwrap());
+        let bytes = encode_to_vec(&blob_format).unwrap();
+        let decoded: Format = decode_from_slice(&bytes).unwrap();
+        assert_eq!(blob_format, decoded);
 
         // Data.
         let data_format = Format::Data(DataFormat {
@@ -665,14 +1054,82 @@ mod tests {
             data_fields: 42,
             ordinal: 137,
         });
-        let mut bytes = vec![];
-        bytes.write_data(&data_format).unwrap();
-        assert_eq!(data_format, bytes.as_slice().read_data().unwrap());
+        let bytes = encode_to_vec(&data_format).unwrap();
+        let decoded: Format = decode_from_slice(&bytes).unwrap();
+        assert_eq!(data_format, decoded);
 
         // Fluids.
         let fluid_format = Format::Fluid;
-        let mut bytes = vec![];
-        bytes.write_data(&fluid_format).unwrap();
-        assert_eq!(fluid_format, bytes.as_slice().read_data().unwrap());
+        let bytes = encode_to_vec(&fluid_format).unwrap();
+        let decoded: Format = decode_from_slice(&bytes).unwrap();
+        assert_eq!(fluid_format, decoded);
+    }
+
+    #[test]
+    fn detects_format_overflow() {
+        assert_eq!(
+            Some(Format::Blob(u16::MAX)),
+            Format::Blob(u16::MAX - 1).checked_with(Format::Blob(1))
+        );
+        assert_eq!(None, Format::Blob(u16::MAX).checked_with(Format::Blob(1)));
+        assert_eq!(
+            None,
+            Format::data(1)
+                .with(Format::Blob(u16::MAX))
+                .checked_with(Format::Blob(1))
+        );
+
+        let mut format = Format::data(1);
+        for _ in 0..u8::MAX {
+            format = format.with(Format::Fluid);
+        }
+        assert_eq!(u8::MAX, format.as_data_format().data_fields);
+        assert_eq!(None, format.checked_with(Format::Fluid));
+    }
+
+    #[test]
+    fn checks_format_compatibility() {
+        let format = Format::data(1).with(u32::FORMAT).with(Text::FORMAT);
+        assert_eq!(Compatibility::Identical, format.compatible_with(&format));
+
+        // Added trailing blob and data fields are tolerated.
+        let added = format.with(u16::FORMAT).with(Text::FORMAT);
+        assert_eq!(
+            Compatibility::ReaderTolerates,
+            added.compatible_with(&format)
+        );
+        assert_eq!(
+            Compatibility::ReaderTolerates,
+            added.compatible_with(&Format::Fluid)
+        );
+
+        // Removed fields aren't.
+        assert!(!format.compatible_with(&added).is_compatible());
+        let removed = Format::data(1).with(Text::FORMAT).with(Text::FORMAT);
+        assert!(matches!(
+            removed.compatible_with(&format),
+            Compatibility::Incompatible { reason } if &*reason == "data is missing 4 bytes of blob fields"
+        ));
+
+        // Neither are changed ordinals, nor
+        // differing unspecified sequences.
+        assert!(!Format::data(2)
+            .with(u32::FORMAT)
+            .with(Text::FORMAT)
+            .compatible_with(&format)
+            .is_compatible());
+        assert!(!<Vec<u32>>::FORMAT
+            .compatible_with(&<Vec<u16>>::FORMAT)
+            .is_compatible());
+        assert!(!Format::Blob(4)
+            .compatible_with(&Format::Blob(2))
+            .is_compatible());
+        assert!(!Format::Fluid.compatible_with(&format).is_compatible());
+    }
+
+    #[test]
+    #[should_panic(expected = "format overflow")]
+    fn panics_on_format_overflow() {
+        let _ = Format::Blob(u16::MAX).with(Format::Blob(1));
     }
 }
@@ -0,0 +1,461 @@
+//! Asynchronous codec extensions.
+//!
+//! These traits mirror their synchronous counterparts
+//! (like [`AsyncReads`] and [`Reads`](crate::stream::Reads)),
+//! including their method names, so they aren't re-exported
+//! from [`crate::codec`]: import them from this module
+//! where they're used.
+//!
+//! Decoders are synchronous: [`AsyncReadsDecodable::read_data`]
+//! asynchronously reads the complete encoding of some data,
+//! walking its [`DataHeader`]s, into an in-memory frame
+//! which is then decoded with [`ReadsDecodable`].
+//! Similarly, [`AsyncWritesEncodable::write_data`] encodes
+//! data into memory before asynchronously writing it.
+//!
+//! Frames of up to [`STACK_FRAME_SIZE`] bytes are kept
+//! on the stack (within each future), so that reading
+//! and writing small data doesn't allocate any more
+//! than the synchronous codec. Larger frames are
+//! allocated on the heap.
+use alloc::vec::Vec;
+
+use crate::stream::{StreamError, Writes};
+
+use super::{
+    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable, WritesEncodable,
+    DEFAULT_MAX_BYTES, DEFAULT_MAX_DEPTH, TEMP_BUFFER_SIZE,
+};
+
+/// Size of an encoded [`DataHeader`], in bytes.
+const HEADER_SIZE: usize = DataHeader::FORMAT.as_data_format().blob_size as usize;
+
+/// Maximum size in bytes of frames which the asynchronous
+/// codec will read or write on the stack, instead of
+/// allocating them on the heap.
+pub const STACK_FRAME_SIZE: usize = TEMP_BUFFER_SIZE;
+
+/// A thing that asynchronously reads from a stream of bytes.
+///
+/// This trait mirrors [`Reads`](crate::stream::Reads).
+pub trait AsyncReads {
+    /// Reads bytes into `buf`, returning the number
+    /// of bytes read.
+    ///
+    /// No more than `buf.len()` bytes will be read.
+    ///
+    /// If an error occurs, the state of `buf` and
+    /// the number of bytes read is undefined.
+    fn read(
+        &mut self,
+        buf: &mut [u8],
+    ) -> impl core::future::Future<Output = Result<usize, StreamError>>;
+
+    /// Reads _exactly_ `buf.len()` bytes into `buf`.
+    ///
+    /// If an error occurs, the state of `buf` and
+    /// the number of bytes read is undefined.
+    fn read_exact(
+        &mut self,
+        buf: &mut [u8],
+    ) -> impl core::future::Future<Output = Result<(), StreamError>> {
+        async move {
+            let mut read = 0;
+
+            while read < buf.len() {
+                match self.read(&mut buf[read..]).await? {
+                    0 => return Err(StreamError::Empty),
+                    n => read += n,
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl AsyncReads for &[u8] {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+        let amt = core::cmp::min(buf.len(), self.len());
+        let (a, b) = self.split_at(amt);
+        buf[..amt].copy_from_slice(a);
+        *self = b;
+        Ok(amt)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncReads for T
+where
+    T: tokio::io::AsyncRead + Unpin,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+        tokio::io::AsyncReadExt::read(self, buf)
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe => StreamError::Closed,
+                std::io::ErrorKind::UnexpectedEof => StreamError::Empty,
+                _ => StreamError::Io { source: e.into() },
+            })
+    }
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), StreamError> {
+        tokio::io::AsyncReadExt::read_exact(self, buf)
+            .await
+            .map(|_| ())
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe => StreamError::Closed,
+                std::io::ErrorKind::UnexpectedEof => StreamError::Empty,
+                _ => StreamError::Io { source: e.into() },
+            })
+    }
+}
+
+/// A thing that asynchronously writes to a stream of bytes.
+///
+/// This trait mirrors [`Writes`](crate::stream::Writes).
+pub trait AsyncWrites {
+    /// Writes bytes from `buf`, returning the number
+    /// of bytes written.
+    ///
+    /// No more than `buf.len()` bytes will be written.
+    ///
+    /// If an error occurs, the number of bytes written
+    /// is undefined.
+    fn write(
+        &mut self,
+        buf: &[u8],
+    ) -> impl core::future::Future<Output = Result<usize, StreamError>>;
+
+    /// Writes _all_ bytes from `buf`.
+    ///
+    /// If an error occurs, the number of bytes written
+    /// is undefined.
+    fn write_all(
+        &mut self,
+        buf: &[u8],
+    ) -> impl core::future::Future<Output = Result<(), StreamError>> {
+        async move {
+            let mut written = 0;
+
+            while written < buf.len() {
+                match self.write(&buf[written..]).await? {
+                    0 => return Err(StreamError::Closed),
+                    n => written += n,
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl AsyncWrites for Vec<u8> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncWrites for T
+where
+    T: tokio::io::AsyncWrite + Unpin,
+{
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        let written = tokio::io::AsyncWriteExt::write(self, buf)
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::UnexpectedEof => StreamError::Closed,
+                _ => StreamError::Io { source: e.into() },
+            })?;
+
+        // See the `std::io::Write` implementation of `Writes`.
+        if written == 0 {
+            Err(StreamError::Closed)
+        } else {
+            Ok(written)
+        }
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> Result<(), StreamError> {
+        tokio::io::AsyncWriteExt::write_all(self, buf)
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::UnexpectedEof => StreamError::Closed,
+                _ => StreamError::Io { source: e.into() },
+            })
+    }
+}
+
+/// A thing that asynchronously reads [`Decodable`] data.
+///
+/// This trait is automatically implemented for all [`AsyncReads`],
+/// and enforces the same [`DEFAULT_MAX_BYTES`] and
+/// [`DEFAULT_MAX_DEPTH`] limits as [`ReadsDecodable`].
+pub trait AsyncReadsDecodable {
+    /// Reads and decodes a sequence of data into
+    /// a new, default instance of `T`.
+    fn read_data<T: Decodable + Default>(
+        &mut self,
+    ) -> impl core::future::Future<Output = Result<T, CodecError>>;
+}
+
+impl<R: AsyncReads + ?Sized> AsyncReadsDecodable for R {
+    async fn read_data<T: Decodable + Default>(&mut self) -> Result<T, CodecError> {
+        // Small, unstructured data is read on the stack.
+        if let Format::Blob(size @ 0..=STACK_FRAME_SIZE_U16) = T::FORMAT {
+            let mut frame = [0u8; STACK_FRAME_SIZE];
+            let frame = &mut frame[..size as usize];
+            self.read_exact(frame).await?;
+            return ReadsDecodable::read_data(&mut &*frame);
+        }
+
+        let frame = read_frame(self, T::FORMAT).await?;
+        ReadsDecodable::read_data(&mut frame.as_slice())
+    }
+}
+
+/// A thing that asynchronously writes [`Encodable`] data.
+///
+/// This trait is automatically implemented for all [`AsyncWrites`].
+pub trait AsyncWritesEncodable {
+    /// Encodes and writes a sequence of `data`,
+    /// including its [`DataHeader`] (if any).
+    fn write_data<T: Encodable + ?Sized>(
+        &mut self,
+        data: &T,
+    ) -> impl core::future::Future<Output = Result<(), CodecError>>;
+}
+
+impl<W: AsyncWrites + ?Sized> AsyncWritesEncodable for W {
+    async fn write_data<T: Encodable + ?Sized>(&mut self, data: &T) -> Result<(), CodecError> {
+        let length = data.encoded_len();
+
+        // Small data is encoded on the stack.
+        if length <= STACK_FRAME_SIZE {
+            let mut frame = StackFrame {
+                bytes: [0; STACK_FRAME_SIZE],
+                len: 0,
+            };
+            WritesEncodable::write_data(&mut frame, data)?;
+            return Ok(self.write_all(&frame.bytes[..frame.len]).await?);
+        }
+
+        let mut encoded = Vec::with_capacity(length);
+        WritesEncodable::write_data(&mut encoded, data)?;
+        Ok(self.write_all(&encoded).await?)
+    }
+}
+
+/// [`STACK_FRAME_SIZE`] as a `u16`, for matching [`Format::Blob`]s.
+const STACK_FRAME_SIZE_U16: u16 = STACK_FRAME_SIZE as u16;
+
+/// [`Writes`] into a fixed-size frame on the stack.
+struct StackFrame {
+    bytes: [u8; STACK_FRAME_SIZE],
+
+    /// Number of bytes written to the frame.
+    len: usize,
+}
+
+impl Writes for StackFrame {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        let written = buf.len().min(STACK_FRAME_SIZE - self.len);
+        if written == 0 && !buf.is_empty() {
+            return Err(StreamError::Closed);
+        }
+
+        self.bytes[self.len..self.len + written].copy_from_slice(&buf[..written]);
+        self.len += written;
+        Ok(written)
+    }
+}
+
+/// A sequence of data within a frame being
+/// read by [`read_frame`].
+#[derive(Default, Clone, Copy)]
+struct Sequence {
+    /// The sequence's header.
+    header: DataHeader,
+
+    /// Number of data remaining in the sequence.
+    remaining_data: u32,
+
+    /// Number of data fields remaining
+    /// in the current data of the sequence.
+    remaining_fields: u8,
+}
+
+/// Asynchronously reads the complete encoding of
+/// data with `format` from `reader` into a new frame.
+///
+/// Headers are read and walked in place (without
+/// recursion), so that the frame contains exactly the
+/// bytes that a [`Decodable`] would read.
+async fn read_frame<R: AsyncReads + ?Sized>(
+    reader: &mut R,
+    format: Format,
+) -> Result<Vec<u8>, CodecError> {
+    let mut frame = Vec::new();
+
+    // Unstructured data is a single blob.
+    if let Format::Blob(size) = format {
+        read_into_frame(reader, &mut frame, size as usize).await?;
+        return Ok(frame);
+    }
+
+    // Sequences being read, which are limited to
+    // `DEFAULT_MAX_DEPTH` and thus kept on the stack.
+    let mut sequences = [Sequence::default(); DEFAULT_MAX_DEPTH as usize];
+    let header = read_header(reader, &mut frame).await?;
+    sequences[0] = Sequence {
+        header,
+        remaining_data: header.count,
+        remaining_fields: 0,
+    };
+    let mut depth = 1;
+
+    while depth > 0 {
+        let sequence = &mut sequences[depth - 1];
+        if sequence.remaining_fields > 0 {
+            // Read the next data field's sequence.
+            sequence.remaining_fields -= 1;
+            if depth >= sequences.len() {
+                return Err(CodecError::DepthLimitExceeded);
+            }
+
+            let header = read_header(reader, &mut frame).await?;
+            sequences[depth] = Sequence {
+                header,
+                remaining_data: header.count,
+                remaining_fields: 0,
+            };
+            depth += 1;
+        } else if sequence.remaining_data > 0 {
+            let format = sequence.header.format;
+            let blob_size = format.blob_size as usize;
+
+            // Read the next data's blob or, if the data
+            // have no data fields, all remaining blobs.
+            let length = if format.data_fields == 0 {
+                let length = (sequence.remaining_data as usize).saturating_mul(blob_size);
+                sequence.remaining_data = 0;
+                length
+            } else {
+                sequence.remaining_data -= 1;
+                sequence.remaining_fields = format.data_fields;
+                blob_size
+            };
+            read_into_frame(reader, &mut frame, length).await?;
+        } else {
+            depth -= 1;
+        }
+    }
+
+    Ok(frame)
+}
+
+/// Asynchronously reads a [`DataHeader`] from
+/// `reader`, appending its bytes to `frame`.
+async fn read_header<R: AsyncReads + ?Sized>(
+    reader: &mut R,
+    frame: &mut Vec<u8>,
+) -> Result<DataHeader, CodecError> {
+    let mut bytes = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut bytes).await?;
+    ensure_frame_size(frame.len() + HEADER_SIZE)?;
+    frame.extend_from_slice(&bytes);
+
+    let mut header = DataHeader::default();
+    header.decode(&mut bytes.as_slice(), None)?;
+    Ok(header)
+}
+
+/// Asynchronously reads `length` bytes
+/// from `reader`, appending them to `frame`.
+async fn read_into_frame<R: AsyncReads + ?Sized>(
+    reader: &mut R,
+    frame: &mut Vec<u8>,
+    length: usize,
+) -> Result<(), CodecError> {
+    let start = frame.len();
+    let end = start.saturating_add(length);
+    ensure_frame_size(end)?;
+    frame.resize(end, 0);
+    reader.read_exact(&mut frame[start..]).await?;
+    Ok(())
+}
+
+/// Returns `Ok(())` iff a frame with `length`
+/// bytes is within [`DEFAULT_MAX_BYTES`].
+fn ensure_frame_size(length: usize) -> Result<(), CodecError> {
+    if length as u64 > DEFAULT_MAX_BYTES {
+        return Err(CodecError::ByteLimitExceeded);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{codec::encode_to_vec, stream::StreamError, types::Text};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_and_writes_data() -> Result<(), CodecError> {
+        let data: Vec<Vec<Text>> = vec![vec!["a".into(), "bc".into()], vec![], vec!["d".into()]];
+        let mut encoded = vec![];
+        AsyncWritesEncodable::write_data(&mut encoded, &data).await?;
+        AsyncWritesEncodable::write_data(&mut encoded, &7u32).await?;
+
+        // Writes match the synchronous codec.
+        let mut expected = vec![];
+        WritesEncodable::write_data(&mut expected, &data)?;
+        WritesEncodable::write_data(&mut expected, &7u32)?;
+        assert_eq!(expected, encoded);
+
+        // Reads consume exactly one sequence at a time.
+        let mut reader = encoded.as_slice();
+        let decoded: Vec<Vec<Text>> = AsyncReadsDecodable::read_data(&mut reader).await?;
+        assert_eq!(data, decoded);
+        let decoded: u32 = AsyncReadsDecodable::read_data(&mut reader).await?;
+        assert_eq!(7, decoded);
+        assert!(reader.is_empty());
+
+        // Data larger than a stack frame matches the synchronous codec.
+        let large: Vec<u8> = (0..=255).cycle().take(STACK_FRAME_SIZE * 4).collect();
+        let mut large_encoded = vec![];
+        AsyncWritesEncodable::write_data(&mut large_encoded, &large).await?;
+        let expected = encode_to_vec(&large)?;
+        assert_eq!(expected, large_encoded);
+        let decoded: Vec<u8> =
+            AsyncReadsDecodable::read_data(&mut large_encoded.as_slice()).await?;
+        assert_eq!(large, decoded);
+
+        // Truncated data can't be read.
+        let mut reader = &encoded[..encoded.len() - 6];
+        assert!(matches!(
+            AsyncReadsDecodable::read_data::<Vec<Vec<Text>>>(&mut reader).await,
+            Err(CodecError::Stream {
+                source: StreamError::Empty,
+                ..
+            })
+        ));
+
+        Ok(())
+    }
+}
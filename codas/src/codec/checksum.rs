@@ -0,0 +1,98 @@
+//! Checksums for checked frames of data.
+use crate::stream::{StreamError, Writes};
+
+/// Magic number preceding each frame of data written by
+/// [`WritesEncodable::write_data_checked`](super::WritesEncodable::write_data_checked).
+pub const CHECKED_FRAME_MAGIC: u16 = 0xC0DA;
+
+/// Lookup table for [`Crc32`], using the
+/// (reflected) IEEE polynomial `0xEDB88320`.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Incremental CRC-32 (IEEE) checksum,
+/// as used by Ethernet, gzip, and PNG.
+#[derive(Clone, Copy)]
+pub(crate) struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    /// Returns a new checksum of zero bytes.
+    pub fn new() -> Self {
+        Self { crc: u32::MAX }
+    }
+
+    /// Updates the checksum with `bytes`.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            let index = (self.crc ^ *byte as u32) as u8;
+            self.crc = (self.crc >> 8) ^ CRC32_TABLE[index as usize];
+        }
+    }
+
+    /// Returns the checksum of all bytes
+    /// this checksum was updated with.
+    pub fn finish(&self) -> u32 {
+        !self.crc
+    }
+}
+
+/// [`Writes`] wrapper that checksums
+/// all bytes written to a `writer`.
+pub(crate) struct ChecksumWriter<'a, W: Writes + ?Sized> {
+    pub writer: &'a mut W,
+    pub checksum: Crc32,
+}
+
+impl<W: Writes + ?Sized> Writes for ChecksumWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        let n = self.writer.write(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), StreamError> {
+        self.writer.write_all(buf)?;
+        self.checksum.update(buf);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_bytes() {
+        // Standard CRC-32 check value.
+        let mut checksum = Crc32::new();
+        checksum.update(b"123456789");
+        assert_eq!(0xCBF4_3926, checksum.finish());
+
+        // Checksums are identical when updated incrementally.
+        let mut incremental = Crc32::new();
+        incremental.update(b"1234");
+        incremental.update(b"56789");
+        assert_eq!(checksum.finish(), incremental.finish());
+
+        assert_eq!(0, Crc32::new().finish());
+    }
+}
@@ -1,14 +1,30 @@
 //! Codec decoder implementations.
+use core::marker::PhantomData;
+
 use snafu::ensure;
 
+use alloc::vec::Vec;
+
 use crate::stream::Reads;
 
-use super::{encode::Encodable, CodecError, DataFormat, DataHeader, UnexpectedDataFormatSnafu};
+use super::{
+    checksum::Crc32, encode::Encodable, BadMagicSnafu, CodecError, CorruptFrameSnafu, DataFormat,
+    DataHeader, Format, FrameLengthMismatchSnafu, UnexpectedDataFormatSnafu,
+    UnsupportedDataFormatSnafu, CHECKED_FRAME_MAGIC,
+};
 
 /// Default size used for temporary,
 /// stack-allocated buffers.
 pub const TEMP_BUFFER_SIZE: usize = 1024;
 
+/// Returns a new `T` decoded from `bytes`,
+/// as by [`ReadsDecodable::read_data`].
+///
+/// Any bytes following the encoded `T` are ignored.
+pub fn decode_from_slice<T: Decodable + Default>(mut bytes: &[u8]) -> Result<T, CodecError> {
+    bytes.read_data()
+}
+
 /// A thing that decodes from
 /// [`codec`](super)-compliant data.
 pub trait Decodable: Encodable {
@@ -43,6 +59,7 @@ pub trait Decodable: Encodable {
             UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: header,
+                offset: None,
             }
             .build()
         })?;
@@ -51,7 +68,39 @@ pub trait Decodable: Encodable {
         ensure!(
             supported_ordinals.contains(&header.format.ordinal),
             UnsupportedDataFormatSnafu {
-                ordinal: header.format.ordinal
+                ordinal: header.format.ordinal,
+                offset: None,
+            }
+        );
+
+        Ok(header)
+    }
+
+    /// Returns `Ok(header)` iff `header` exists and
+    /// describes data that can be decoded as [`Self::FORMAT`]
+    /// (refer to [`Format::compatible_with`]).
+    ///
+    /// Decoders using this function must skip any blob bytes
+    /// and data fields in `header` beyond their own, via
+    /// [`ReadsDecodable::skip_excess_blob`] and
+    /// [`ReadsDecodable::skip_excess_data`].
+    fn ensure_format(header: Option<DataHeader>) -> Result<DataHeader, CodecError> {
+        use super::{MissingBlobLengthSnafu, MissingDataFieldsSnafu};
+
+        let expected = Self::FORMAT.as_data_format();
+        let header = Self::ensure_header(header, &[expected.ordinal])?;
+        ensure!(
+            header.format.blob_size >= expected.blob_size,
+            MissingBlobLengthSnafu {
+                length: expected.blob_size - header.format.blob_size,
+                offset: None,
+            }
+        );
+        ensure!(
+            header.format.data_fields >= expected.data_fields,
+            MissingDataFieldsSnafu {
+                count: expected.data_fields - header.format.data_fields,
+                offset: None,
             }
         );
 
@@ -66,6 +115,7 @@ pub trait Decodable: Encodable {
             UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: header,
+                offset: None,
             }
         );
 
@@ -77,8 +127,8 @@ pub trait Decodable: Encodable {
 ///
 /// This trait is automatically implemented for all [`Reads`].
 /// This automatic implementation wraps each top-level decoder in
-/// a [`LimitedReader`] with default limits of [`DEFAULT_MAX_BYTES`]
-/// and [`DEFAULT_MAX_DEPTH`].
+/// a [`LimitedReader`] with default limits of [`DEFAULT_MAX_BYTES`],
+/// [`DEFAULT_MAX_DEPTH`], and [`DEFAULT_MAX_ELEMENTS`].
 ///
 /// For custom limits, construct a [`LimitedReader`] explicitly
 /// instead of using this trait's blanket implementation.
@@ -108,6 +158,12 @@ pub trait ReadsDecodable {
     /// Called when exiting a nested data scope during decoding.
     fn exit_scope(&mut self) {}
 
+    /// Called when a [`DataHeader`] declaring
+    /// `count` data is decoded.
+    fn count_elements(&mut self, _count: u32) -> Result<(), CodecError> {
+        Ok(())
+    }
+
     /// Reads and decodes a sequence of data into
     /// a new, default instance of `T`.
     ///
@@ -119,6 +175,24 @@ pub trait ReadsDecodable {
         Ok(default)
     }
 
+    /// Reads and decodes a sequence of data into a new,
+    /// default instance of `T`, reading at most `max_bytes`.
+    ///
+    /// If decoding `T` would read more than `max_bytes`,
+    /// [`CodecError::ByteLimitExceeded`] is returned. This
+    /// is the preferred way of decoding untrusted data
+    /// whose expected size is known, like a network message.
+    fn read_data_with_limit<T: Decodable + Default>(
+        &mut self,
+        max_bytes: u64,
+    ) -> Result<T, CodecError> {
+        ByteLimitedReader {
+            reader: self,
+            remaining: max_bytes,
+        }
+        .read_data()
+    }
+
     /// Reads and decodes a sequence of data into `data`.
     ///
     /// This function will attempt to read a [`DataHeader`]
@@ -135,6 +209,111 @@ pub trait ReadsDecodable {
         Ok(())
     }
 
+    /// Reads the header of a list of `T` (like a [`Vec<T>`]),
+    /// returning an iterator which lazily decodes each `T`
+    /// in the list.
+    ///
+    /// Once the iterator is exhausted (or
+    /// [`DataIter::skip_rest`] is called), this reader
+    /// will be positioned after the end of the list.
+    ///
+    /// Maps (like a [`BTreeMap<K, V>`](alloc::collections::BTreeMap))
+    /// may be iterated by reading the map's [`DataHeader`]
+    /// and then iterating its keys (as a list of `K`) followed
+    /// by its values (as a list of `V`).
+    fn read_data_iter<T: Decodable + Default>(
+        &mut self,
+    ) -> Result<DataIter<'_, Self, T>, CodecError> {
+        let mut reader = DecodingScope::enter(self)?;
+        let header: DataHeader = reader.read_data()?;
+        ensure!(
+            header.format.ordinal == 0,
+            UnsupportedDataFormatSnafu {
+                ordinal: header.format.ordinal,
+                offset: None,
+            }
+        );
+
+        Ok(DataIter {
+            reader,
+            remaining: header.count,
+            _data: PhantomData,
+        })
+    }
+
+    /// Reads and decodes framed data written by
+    /// [`WritesEncodable::write_framed_data`](super::WritesEncodable::write_framed_data)
+    /// into a new, default instance of `T`.
+    ///
+    /// The data's decoder may not read beyond the end of
+    /// the frame, and must read the frame in its entirety.
+    fn read_framed_data<T: Decodable + Default>(&mut self) -> Result<T, CodecError> {
+        let mut length = [0u8; 4];
+        self.read_exact(&mut length)?;
+        let length = u32::from_le_bytes(length);
+
+        let mut framed = FramedReader {
+            reader: self,
+            remaining: length as u64,
+        };
+        let data = framed.read_data()?;
+
+        ensure!(
+            framed.remaining == 0,
+            FrameLengthMismatchSnafu {
+                length,
+                decoded: length as u64 - framed.remaining,
+            }
+        );
+
+        Ok(data)
+    }
+
+    /// Reads and decodes data within a checked frame written by
+    /// [`WritesEncodable::write_data_checked`](super::WritesEncodable::write_data_checked)
+    /// into a new, default instance of `T`.
+    ///
+    /// The frame's magic number and checksum are verified
+    /// _before_ the data is decoded, and the data's decoder
+    /// must read the frame in its entirety. Frames larger
+    /// than [`DEFAULT_MAX_BYTES`] won't be read.
+    fn read_data_checked<T: Decodable + Default>(&mut self) -> Result<T, CodecError> {
+        let mut magic = [0u8; 2];
+        self.read_exact(&mut magic)?;
+        let magic = u16::from_le_bytes(magic);
+        ensure!(magic == CHECKED_FRAME_MAGIC, BadMagicSnafu { magic });
+
+        let mut length = [0u8; 4];
+        self.read_exact(&mut length)?;
+        let length = u32::from_le_bytes(length);
+        if length as u64 > DEFAULT_MAX_BYTES {
+            return Err(CodecError::ByteLimitExceeded);
+        }
+
+        let mut payload = Vec::new();
+        read_bytes_into(self, &mut payload, length as usize)?;
+
+        let mut expected = [0u8; 4];
+        self.read_exact(&mut expected)?;
+        let expected = u32::from_le_bytes(expected);
+        let mut checksum = Crc32::new();
+        checksum.update(&payload);
+        let actual = checksum.finish();
+        ensure!(expected == actual, CorruptFrameSnafu { expected, actual });
+
+        let mut framed = payload.as_slice();
+        let data = framed.read_data()?;
+        ensure!(
+            framed.is_empty(),
+            FrameLengthMismatchSnafu {
+                length,
+                decoded: (length as usize - framed.len()) as u64,
+            }
+        );
+
+        Ok(data)
+    }
+
     /// Skips to the end of the next `length` bytes of data.
     fn skip_blob(&mut self, length: usize) -> Result<(), CodecError> {
         let mut skipped = 0;
@@ -154,6 +333,23 @@ pub trait ReadsDecodable {
         Ok(())
     }
 
+    /// Skips any blob bytes of the data following `header`
+    /// in excess of the `blob_size` of `format`.
+    fn skip_excess_blob(&mut self, header: &DataHeader, format: Format) -> Result<(), CodecError> {
+        let blob_size = format.as_data_format().blob_size;
+        self.skip_blob(header.format.blob_size.saturating_sub(blob_size) as usize)
+    }
+
+    /// Skips any data fields of the data following `header`
+    /// in excess of the `data_fields` of `format`.
+    fn skip_excess_data(&mut self, header: &DataHeader, format: Format) -> Result<(), CodecError> {
+        for _ in format.as_data_format().data_fields..header.format.data_fields {
+            self.skip_data()?;
+        }
+
+        Ok(())
+    }
+
     /// Skips to the end of the next encoded sequence of data,
     /// returning the total number of bytes skipped.
     fn skip_data(&mut self) -> Result<usize, CodecError> {
@@ -165,6 +361,12 @@ pub trait ReadsDecodable {
         read += DataHeader::FORMAT.as_data_format().blob_size as usize;
         let data_format = header.format;
 
+        // Data with an empty format has nothing to skip,
+        // no matter how many data the header declares.
+        if data_format.blob_size == 0 && data_format.data_fields == 0 {
+            return Ok(read);
+        }
+
         // Decode all data in the sequence, skipping
         // their blobs and recursively skipping data fields.
         for _ in 0..header.count {
@@ -193,6 +395,64 @@ pub trait ReadsDecodable {
     }
 }
 
+/// Iterator which lazily decodes a list of `T` from a
+/// reader, returned by [`ReadsDecodable::read_data_iter`].
+///
+/// If any `T` fails to decode, the error is
+/// returned and iteration stops.
+pub struct DataIter<'a, R: ReadsDecodable + ?Sized, T> {
+    reader: DecodingScope<'a, R>,
+    remaining: u32,
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<R: ReadsDecodable + ?Sized, T: Decodable> DataIter<'_, R, T> {
+    /// Returns the number of data in the
+    /// list which haven't been decoded yet.
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// Skips all data in the list which haven't
+    /// been decoded yet, positioning the reader
+    /// after the end of the list.
+    pub fn skip_rest(mut self) -> Result<(), CodecError> {
+        let remaining = core::mem::take(&mut self.remaining);
+        match T::FORMAT {
+            Format::Blob(size) => self.reader.skip_blob(size as usize * remaining as usize),
+            Format::Data(_) | Format::Fluid => {
+                for _ in 0..remaining {
+                    self.reader.skip_data()?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<R: ReadsDecodable + ?Sized, T: Decodable + Default> Iterator for DataIter<'_, R, T> {
+    type Item = Result<T, CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let result = self.reader.read_data();
+        match result {
+            Ok(_) => self.remaining -= 1,
+            Err(_) => self.remaining = 0,
+        }
+
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
 /// RAII guard around a [`ReadsDecodable`] that calls
 /// [`ReadsDecodable::exit_scope`] on drop.
 ///
@@ -243,13 +503,108 @@ impl<R: Reads> ReadsDecodable for R {
         LimitedReader::new(&mut *self).read_data_into(data)
     }
 
+    fn read_data_with_limit<T: Decodable + Default>(
+        &mut self,
+        max_bytes: u64,
+    ) -> Result<T, CodecError> {
+        LimitedReader::new(&mut *self)
+            .max_bytes(max_bytes)
+            .read_data()
+    }
+
     fn skip_data(&mut self) -> Result<usize, CodecError> {
         LimitedReader::new(&mut *self).skip_data()
     }
 }
 
-/// A [`Reads`] wrapper that enforces byte and depth limits
-/// during decoding, protecting against malicious or malformed input.
+/// [`ReadsDecodable`] wrapper that prevents reads
+/// beyond the end of a frame of `remaining` bytes.
+struct FramedReader<'a, R: ReadsDecodable + ?Sized> {
+    reader: &'a mut R,
+    remaining: u64,
+}
+
+impl<R: ReadsDecodable + ?Sized> ReadsDecodable for FramedReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, CodecError> {
+        if self.remaining == 0 && !buf.is_empty() {
+            return Err(CodecError::UnexpectedEof);
+        }
+        let limit = buf.len().min(self.remaining as usize);
+        let n = self.reader.read(&mut buf[..limit])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), CodecError> {
+        let len = buf.len() as u64;
+        if len > self.remaining {
+            return Err(CodecError::UnexpectedEof);
+        }
+        self.reader.read_exact(buf)?;
+        self.remaining -= len;
+        Ok(())
+    }
+
+    fn enter_scope(&mut self) -> Result<(), CodecError> {
+        self.reader.enter_scope()
+    }
+
+    fn exit_scope(&mut self) {
+        self.reader.exit_scope()
+    }
+
+    fn count_elements(&mut self, count: u32) -> Result<(), CodecError> {
+        self.reader.count_elements(count)
+    }
+}
+
+/// [`ReadsDecodable`] wrapper that prevents reads
+/// of more than `remaining` bytes, used by
+/// [`ReadsDecodable::read_data_with_limit`].
+struct ByteLimitedReader<'a, R: ReadsDecodable + ?Sized> {
+    reader: &'a mut R,
+    remaining: u64,
+}
+
+impl<R: ReadsDecodable + ?Sized> ReadsDecodable for ByteLimitedReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, CodecError> {
+        if self.remaining == 0 && !buf.is_empty() {
+            return Err(CodecError::ByteLimitExceeded);
+        }
+        let limit = buf
+            .len()
+            .min(self.remaining.min(usize::MAX as u64) as usize);
+        let n = self.reader.read(&mut buf[..limit])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), CodecError> {
+        let len = buf.len() as u64;
+        if len > self.remaining {
+            return Err(CodecError::ByteLimitExceeded);
+        }
+        self.reader.read_exact(buf)?;
+        self.remaining -= len;
+        Ok(())
+    }
+
+    fn enter_scope(&mut self) -> Result<(), CodecError> {
+        self.reader.enter_scope()
+    }
+
+    fn exit_scope(&mut self) {
+        self.reader.exit_scope()
+    }
+
+    fn count_elements(&mut self, count: u32) -> Result<(), CodecError> {
+        self.reader.count_elements(count)
+    }
+}
+
+/// A [`Reads`] wrapper that enforces byte, depth, and element
+/// limits during decoding, protecting against malicious or
+/// malformed input.
 ///
 /// The blanket [`ReadsDecodable`] implementation automatically wraps
 /// each top-level decode in a limited reader with default limits.
@@ -265,6 +620,7 @@ impl<R: Reads> ReadsDecodable for R {
 /// let data: u32 = LimitedReader::new(&mut slice)
 ///     .max_bytes(1024)
 ///     .max_depth(8)
+///     .max_elements(256)
 ///     .read_data()?;
 ///
 /// // No effective limits (trusted data):
@@ -276,18 +632,26 @@ impl<R: Reads> ReadsDecodable for R {
 /// ```
 ///
 /// Limits are cumulative within the `LimitedReader`'s lifetime: every
-/// sub-field's bytes and nesting depth count against the same instance.
+/// sub-field's bytes, nesting depth, and elements count against the
+/// same instance.
+///
+/// Elements are counted from each [`DataHeader`]'s `count` as soon as
+/// the header is decoded, so a header declaring an excessive number of
+/// elements fails to decode before any of its elements are read.
 pub struct LimitedReader<'a, R: Reads> {
     reader: &'a mut R,
     bytes_read: u64,
     max_bytes: u64,
     depth: u32,
     max_depth: u32,
+    elements_read: u64,
+    max_elements: u64,
 }
 
 impl<'a, R: Reads> LimitedReader<'a, R> {
     /// Creates a new `LimitedReader` with default limits
-    /// ([`DEFAULT_MAX_BYTES`] and [`DEFAULT_MAX_DEPTH`]).
+    /// ([`DEFAULT_MAX_BYTES`], [`DEFAULT_MAX_DEPTH`],
+    /// and [`DEFAULT_MAX_ELEMENTS`]).
     pub fn new(reader: &'a mut R) -> Self {
         Self {
             reader,
@@ -295,6 +659,8 @@ impl<'a, R: Reads> LimitedReader<'a, R> {
             max_bytes: DEFAULT_MAX_BYTES,
             depth: 0,
             max_depth: DEFAULT_MAX_DEPTH,
+            elements_read: 0,
+            max_elements: DEFAULT_MAX_ELEMENTS,
         }
     }
 
@@ -306,6 +672,8 @@ impl<'a, R: Reads> LimitedReader<'a, R> {
             max_bytes: u64::MAX,
             depth: 0,
             max_depth: u32::MAX,
+            elements_read: 0,
+            max_elements: u64::MAX,
         }
     }
 
@@ -321,10 +689,23 @@ impl<'a, R: Reads> LimitedReader<'a, R> {
         self
     }
 
+    /// Sets the maximum number of elements (as declared
+    /// by [`DataHeader`]s) this reader will allow.
+    pub fn max_elements(mut self, max: u64) -> Self {
+        self.max_elements = max;
+        self
+    }
+
     /// Returns the total number of bytes read so far.
     pub fn bytes_read(&self) -> u64 {
         self.bytes_read
     }
+
+    /// Returns the total number of elements
+    /// declared by [`DataHeader`]s read so far.
+    pub fn elements_read(&self) -> u64 {
+        self.elements_read
+    }
 }
 
 impl<R: Reads> ReadsDecodable for LimitedReader<'_, R> {
@@ -360,6 +741,14 @@ impl<R: Reads> ReadsDecodable for LimitedReader<'_, R> {
     fn exit_scope(&mut self) {
         self.depth = self.depth.saturating_sub(1);
     }
+
+    fn count_elements(&mut self, count: u32) -> Result<(), CodecError> {
+        self.elements_read += count as u64;
+        if self.elements_read > self.max_elements {
+            return Err(CodecError::ElementLimitExceeded);
+        }
+        Ok(())
+    }
 }
 
 /// Default maximum bytes a [`LimitedReader`] will read (64 MiB).
@@ -368,10 +757,47 @@ pub const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
 /// Default maximum nesting depth a [`LimitedReader`] will allow.
 pub const DEFAULT_MAX_DEPTH: u32 = 64;
 
+/// Default maximum elements a [`LimitedReader`] will allow (64 Mi).
+///
+/// Elements with a non-empty format occupy at least one byte each,
+/// so this limit mostly guards against headers declaring huge
+/// numbers of elements with an empty format, which would otherwise
+/// be decoded (or skipped) without reading any bytes.
+pub const DEFAULT_MAX_ELEMENTS: u64 = 64 * 1024 * 1024;
+
+/// Reads _exactly_ `length` bytes from `reader`, appending them to `bytes`.
+///
+/// Rather than allocating `length` bytes up-front, `bytes` grows
+/// (at most doubling) as bytes are read, so that a `length` read from
+/// untrusted data can't force a large allocation before the reader
+/// runs out of bytes. If reading fails, `bytes` is left unchanged.
+pub(crate) fn read_bytes_into(
+    reader: &mut (impl ReadsDecodable + ?Sized),
+    bytes: &mut Vec<u8>,
+    length: usize,
+) -> Result<(), CodecError> {
+    let start = bytes.len();
+    let end = start + length;
+    while bytes.len() < end {
+        let chunk_start = bytes.len();
+        let chunk = (end - chunk_start).min((chunk_start - start).max(TEMP_BUFFER_SIZE));
+        bytes.resize(chunk_start + chunk, 0);
+        if let Err(e) = reader.read_exact(&mut bytes[chunk_start..]) {
+            bytes.truncate(start);
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{codec::tests::*, types::Text};
+    use crate::{
+        codec::{encode_to_vec, tests::*},
+        types::Text,
+    };
 
     #[test]
     fn decodes() -> Result<(), CodecError> {
@@ -388,8 +814,8 @@ mod tests {
         assert_eq!(1, header.format.data_fields);
 
         // Decode blob fields.
-        assert_eq!(TestData::default().num_a, bytes.read_data()?);
-        assert_eq!(TestData::default().num_b, bytes.read_data()?);
+        assert_eq!(TestData::default().num_a, bytes.read_data::<i32>()?);
+        assert_eq!(TestData::default().num_b, bytes.read_data::<u64>()?);
 
         // Decode text.
         let mut text = Text::default();
@@ -400,13 +826,156 @@ mod tests {
     }
 
     #[test]
-    fn limited_reader_byte_limit() {
+    fn iterates_lists() -> Result<(), CodecError> {
+        use crate::codec::WritesEncodable;
+
+        let texts: Vec<Text> = (0..1_000).map(|i| alloc::format!("{i}").into()).collect();
+        let numbers: Vec<u32> = (0..1_000).collect();
+        let mut bytes = vec![];
+        bytes.write_data(&texts)?;
+        bytes.write_data(&numbers)?;
+        bytes.write_data(&Text::from("after"))?;
+        let mut bytes = bytes.as_slice();
+
+        // Iterate half of each list, skipping the rest.
+        let mut iter = bytes.read_data_iter::<Text>()?;
+        assert_eq!(1_000, iter.remaining());
+        for (i, text) in iter.by_ref().take(500).enumerate() {
+            assert_eq!(texts[i], text?);
+        }
+        assert_eq!(500, iter.remaining());
+        iter.skip_rest()?;
+
+        let mut iter = bytes.read_data_iter::<u32>()?;
+        for (i, number) in iter.by_ref().take(500).enumerate() {
+            assert_eq!(i as u32, number?);
+        }
+        iter.skip_rest()?;
+
+        // Decode the data following the lists.
+        assert_eq!("after", bytes.read_data::<Text>()?);
+        assert!(bytes.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn iterates_maps() -> Result<(), CodecError> {
+        use crate::codec::WritesEncodable;
+
+        let map: alloc::collections::BTreeMap<Text, u32> =
+            [("one".into(), 1), ("two".into(), 2)].into();
+        let mut bytes = vec![];
+        bytes.write_data(&map)?;
+        bytes.write_data(&7u32)?;
+        let mut bytes = bytes.as_slice();
+
+        // Iterate the keys and values of the map.
+        let _: DataHeader = bytes.read_data()?;
+        let keys = bytes.read_data_iter::<Text>()?;
+        assert_eq!(
+            map.keys().cloned().collect::<Vec<_>>(),
+            keys.collect::<Result<Vec<_>, _>>()?
+        );
+        let mut values = bytes.read_data_iter::<u32>()?;
+        assert_eq!(Some(1), values.next().transpose()?);
+        values.skip_rest()?;
+
+        assert_eq!(7u32, bytes.read_data::<u32>()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reads_framed_data() -> Result<(), CodecError> {
+        use crate::codec::WritesEncodable;
+
+        let text = Text::from("framed!");
+        let numbers: Vec<u32> = vec![1, 2, 3];
+        let mut bytes = vec![];
+        bytes.write_framed_data(&text)?;
+        bytes.write_framed_data(&numbers)?;
+
+        let mut slice = bytes.as_slice();
+        assert_eq!(text, slice.read_framed_data::<Text>()?);
+        assert_eq!(numbers, slice.read_framed_data::<Vec<u32>>()?);
+        assert!(slice.is_empty());
+
+        // Frames shorter than their data can't be decoded.
+        let mut short = bytes.clone();
+        short[..4].copy_from_slice(&4u32.to_le_bytes());
+        let result = short.as_slice().read_framed_data::<Text>();
+        assert!(
+            matches!(result, Err(CodecError::UnexpectedEof)),
+            "expected UnexpectedEof, got {result:?}"
+        );
+
+        // Frames longer than their data can't be decoded.
+        let mut long = bytes.clone();
+        long[..4].copy_from_slice(&(text.encoded_len() as u32 + 1).to_le_bytes());
+        let result = long.as_slice().read_framed_data::<Text>();
+        assert!(
+            matches!(
+                result,
+                Err(CodecError::FrameLengthMismatch {
+                    length: 16,
+                    decoded: 15
+                })
+            ),
+            "expected FrameLengthMismatch, got {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn reads_checked_data() -> Result<(), CodecError> {
         use crate::codec::WritesEncodable;
 
+        let text = Text::from("checked, and checked again!");
+        let numbers: Vec<u32> = vec![1, 2, 3];
+        let mut bytes = vec![];
+        bytes.write_data_checked(&text)?;
+        bytes.write_data_checked(&numbers)?;
+
+        let mut slice = bytes.as_slice();
+        assert_eq!(text, slice.read_data_checked::<Text>()?);
+        assert_eq!(numbers, slice.read_data_checked::<Vec<u32>>()?);
+        assert!(slice.is_empty());
+
+        // Frames with a flipped byte are corrupt.
+        let mut corrupt = bytes.clone();
+        corrupt[6 + text.encoded_len() / 2] ^= 0xFF;
+        let result = corrupt.as_slice().read_data_checked::<Text>();
+        assert!(
+            matches!(result, Err(CodecError::CorruptFrame { .. })),
+            "expected CorruptFrame, got {result:?}"
+        );
+
+        // Frames without the magic number aren't frames.
+        let unframed = encode_to_vec(&text)?;
+        let result = unframed.as_slice().read_data_checked::<Text>();
+        assert!(
+            matches!(result, Err(CodecError::BadMagic { .. })),
+            "expected BadMagic, got {result:?}"
+        );
+
+        // Truncated frames fail to decode, wherever they're truncated.
+        let frame_len = 6 + text.encoded_len() + 4;
+        for len in 0..frame_len {
+            let mut truncated = &bytes[..len];
+            let result = truncated.read_data_checked::<Text>();
+            assert!(result.is_err(), "decoded a frame truncated to {len} bytes");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn limited_reader_byte_limit() {
         // Encode a Text value (header + bytes).
         let text = Text::from("hello, limited world!");
-        let mut bytes = vec![];
-        bytes.write_data(&text).unwrap();
+        let bytes = encode_to_vec(&text).unwrap();
         let total = bytes.len();
 
         // Decoding with a limit smaller than the payload fails.
@@ -429,15 +998,70 @@ mod tests {
     }
 
     #[test]
-    fn limited_reader_depth_limit() {
+    fn reads_data_with_limit() {
         use crate::codec::WritesEncodable;
 
+        let data: Vec<Vec<u32>> = vec![vec![1, 2], vec![3, 4]];
+        let bytes = encode_to_vec(&data).unwrap();
+        let total = bytes.len() as u64;
+
+        // Limits apply to plain readers...
+        let result = bytes
+            .as_slice()
+            .read_data_with_limit::<Vec<Vec<u32>>>(total - 1);
+        assert!(
+            matches!(result, Err(CodecError::ByteLimitExceeded)),
+            "expected ByteLimitExceeded, got {result:?}"
+        );
+        let decoded: Vec<Vec<u32>> = bytes.as_slice().read_data_with_limit(total).unwrap();
+        assert_eq!(data, decoded);
+
+        // ...and to readers with their own limits,
+        // which continue to be enforced.
+        let mut slice = bytes.as_slice();
+        let result =
+            LimitedReader::new(&mut slice).read_data_with_limit::<Vec<Vec<u32>>>(total - 1);
+        assert!(
+            matches!(result, Err(CodecError::ByteLimitExceeded)),
+            "expected ByteLimitExceeded, got {result:?}"
+        );
+        let mut slice = bytes.as_slice();
+        let result = LimitedReader::new(&mut slice)
+            .max_depth(1)
+            .read_data_with_limit::<Vec<Vec<u32>>>(total);
+        assert!(
+            matches!(result, Err(CodecError::DepthLimitExceeded)),
+            "expected DepthLimitExceeded, got {result:?}"
+        );
+
+        // Headers declaring huge amounts of data fail
+        // as soon as they exceed the limit.
+        let mut bytes = vec![];
+        bytes
+            .write_data(&DataHeader {
+                count: u16::MAX as u32,
+                format: DataFormat {
+                    blob_size: u16::MAX,
+                    data_fields: 0,
+                    ordinal: 0,
+                },
+            })
+            .unwrap();
+        bytes.extend_from_slice(&[0xFF; 1024]);
+        let result = bytes.as_slice().read_data_with_limit::<Vec<u32>>(64);
+        assert!(
+            matches!(result, Err(CodecError::ByteLimitExceeded)),
+            "expected ByteLimitExceeded, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn limited_reader_depth_limit() {
         // Build a nested structure: Vec<Vec<u32>>.
         // Nesting: outer header → inner Vec header → u32 blobs.
         // That's 2 levels of structured data.
         let data: Vec<Vec<u32>> = vec![vec![1, 2], vec![3, 4]];
-        let mut bytes = vec![];
-        bytes.write_data(&data).unwrap();
+        let bytes = encode_to_vec(&data).unwrap();
 
         // max_depth=1 should fail (we need at least 2 levels).
         let mut slice = bytes.as_slice();
@@ -474,8 +1098,7 @@ mod tests {
         // A limit that covers the first field but not both should fail
         // partway through the second field, proving bytes are cumulative.
         let first_field_size = {
-            let mut tmp = vec![];
-            tmp.write_data(&text_a).unwrap();
+            let tmp = encode_to_vec(&text_a).unwrap();
             tmp.len()
         };
         let tight_limit = first_field_size as u64 + 4; // enough for first, not second
@@ -500,14 +1123,11 @@ mod tests {
 
     #[test]
     fn limited_reader_auto_wrap_succeeds() -> Result<(), CodecError> {
-        use crate::codec::WritesEncodable;
-
         // Normal decode through the blanket impl (auto-wrapping)
         // should work for well-formed data under default limits.
         let data: Vec<Vec<u32>> = vec![vec![1, 2, 3], vec![4, 5]];
-        let mut bytes = vec![];
-        bytes.write_data(&data).unwrap();
-        let decoded: Vec<Vec<u32>> = bytes.as_slice().read_data()?;
+        let bytes = encode_to_vec(&data).unwrap();
+        let decoded: Vec<Vec<u32>> = decode_from_slice(&bytes)?;
         assert_eq!(data, decoded);
         Ok(())
     }
@@ -519,11 +1139,8 @@ mod tests {
     /// against one shared limit.
     #[test]
     fn limited_reader_struct_byte_accumulation() {
-        use crate::codec::WritesEncodable;
-
         let data: Vec<Vec<u32>> = vec![vec![1, 2], vec![3, 4]];
-        let mut bytes = vec![];
-        bytes.write_data(&data).unwrap();
+        let bytes = encode_to_vec(&data).unwrap();
         let total = bytes.len();
 
         // One byte short of the total should fail, proving
@@ -547,6 +1164,102 @@ mod tests {
         assert_eq!(data, decoded);
     }
 
+    #[test]
+    fn limited_reader_element_limit() {
+        use crate::codec::WritesEncodable;
+
+        let data: Vec<u32> = vec![1, 2, 3, 4];
+        let mut bytes = vec![];
+        bytes.write_data(&data).unwrap();
+
+        let mut slice = bytes.as_slice();
+        let result = LimitedReader::new(&mut slice)
+            .max_elements(3)
+            .read_data::<Vec<u32>>();
+        assert!(
+            matches!(result, Err(CodecError::ElementLimitExceeded)),
+            "expected ElementLimitExceeded, got {result:?}"
+        );
+
+        let mut slice = bytes.as_slice();
+        let mut limited = LimitedReader::new(&mut slice).max_elements(4);
+        assert_eq!(data, limited.read_data::<Vec<u32>>().unwrap());
+        assert_eq!(4, limited.elements_read());
+    }
+
+    /// Headers declaring far more data than
+    /// follows them must fail quickly and cleanly.
+    #[test]
+    fn rejects_adversarial_headers() {
+        use crate::{codec::WritesEncodable, types::Unspecified};
+
+        let adversarial = |count: u32, format: DataFormat| {
+            let mut bytes = vec![];
+            bytes.write_data(&DataHeader { count, format }).unwrap();
+            bytes.extend_from_slice(&[0xFF; 16]);
+            bytes
+        };
+        let empty = DataFormat {
+            blob_size: 0,
+            data_fields: 0,
+            ordinal: 7,
+        };
+        let bytes_format = <[u8]>::FORMAT.as_data_format();
+        let text_format = Text::FORMAT.as_data_format();
+        let texts_format = <Vec<Text>>::FORMAT.as_data_format();
+
+        // Huge counts of empty data exceed the element limit
+        // instead of being skipped (or decoded) one by one.
+        let bytes = adversarial(u32::MAX, empty);
+        let result = bytes.as_slice().skip_data();
+        assert!(
+            matches!(result, Err(CodecError::ElementLimitExceeded)),
+            "expected ElementLimitExceeded, got {result:?}"
+        );
+        let result = bytes.as_slice().read_data::<Unspecified>();
+        assert!(
+            matches!(result, Err(CodecError::ElementLimitExceeded)),
+            "expected ElementLimitExceeded, got {result:?}"
+        );
+        let result = adversarial(u32::MAX, texts_format)
+            .as_slice()
+            .read_data::<Vec<Text>>();
+        assert!(
+            matches!(result, Err(CodecError::ElementLimitExceeded)),
+            "expected ElementLimitExceeded, got {result:?}"
+        );
+
+        // Large counts within the limits run out of
+        // bytes, without allocating for every element.
+        let count = (DEFAULT_MAX_ELEMENTS - 1) as u32;
+        let result = adversarial(count, bytes_format)
+            .as_slice()
+            .read_data::<Vec<u8>>();
+        assert!(
+            matches!(
+                result,
+                Err(CodecError::UnexpectedEof | CodecError::Stream { .. })
+            ),
+            "expected end of stream, got {result:?}"
+        );
+        let result = adversarial(count, text_format)
+            .as_slice()
+            .read_data::<Text>();
+        assert!(
+            matches!(
+                result,
+                Err(CodecError::UnexpectedEof | CodecError::Stream { .. })
+            ),
+            "expected end of stream, got {result:?}"
+        );
+        let mut decoded: Vec<Text> = vec![];
+        let result = adversarial(count, texts_format)
+            .as_slice()
+            .read_data_into(&mut decoded);
+        assert!(result.is_err(), "decoded {} texts", decoded.len());
+        assert!(decoded.capacity() <= 1024);
+    }
+
     #[test]
     fn splits_off_group_sequences() -> Result<(), CodecError> {
         // Pre encode a sequence of expected data.
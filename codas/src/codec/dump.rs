@@ -0,0 +1,371 @@
+//! Structural dumps of encoded data.
+//!
+//! Dumps are produced by walking the headers and
+//! blobs of encoded data, without decoding any of
+//! the data into values. Dumping never allocates,
+//! making it suitable for inspecting arbitrarily
+//! large streams of data.
+use core::fmt::Write;
+
+use crate::types::{Coda, Type};
+
+use super::{CodecError, DataFormat, DataHeader, Encodable, ReadsDecodable, DEFAULT_MAX_DEPTH};
+
+/// Options for [`dump`].
+#[derive(Debug, Clone, Copy)]
+pub struct DumpOptions<'a> {
+    /// Coda used to name data by its ordinal.
+    ///
+    /// If `None`, only built-in data is named.
+    pub coda: Option<&'a Coda>,
+
+    /// If true, the bytes of all blobs are
+    /// printed in hexadecimal; otherwise,
+    /// blobs are skipped.
+    pub blobs: bool,
+
+    /// Maximum depth of data that will be dumped
+    /// before [`CodecError::DepthLimitExceeded`]
+    /// is returned.
+    pub max_depth: u32,
+}
+
+impl Default for DumpOptions<'_> {
+    fn default() -> Self {
+        Self {
+            coda: None,
+            blobs: true,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+/// Statistics about data dumped by [`dump`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DumpStats {
+    /// Number of data headers read, including
+    /// the headers of all nested data.
+    pub records: u64,
+
+    /// Total number of bytes read.
+    pub bytes: u64,
+
+    /// Deepest level of nesting reached, where
+    /// top-level data has a depth of `1`.
+    pub max_depth: u32,
+}
+
+/// Writes the structure of all data in `reader` to `out`,
+/// returning statistics about the dumped data.
+///
+/// `reader` is read until it ends; it may contain any number
+/// of consecutive, top-level encoded data. Each header is
+/// written on its own line, indented by its depth and prefixed
+/// with its byte offset (like `@8`), containing the number of
+/// data following the header, the data's ordinal, its name (if
+/// known), and its format. Blobs are written on their own lines
+/// (if [`DumpOptions::blobs`] is set):
+///
+/// ```text
+/// @0 1 O(1) Point - 8 Bytes, 1 Data
+///   @8 0a00000014000000
+///   @16 5 Unspecified - 1 Bytes, 0 Data
+///     @24 706f696e74
+/// ```
+///
+/// Nested data is walked recursively; no memory is allocated.
+pub fn dump(
+    reader: &mut (impl ReadsDecodable + ?Sized),
+    out: &mut impl Write,
+    options: &DumpOptions<'_>,
+) -> Result<DumpStats, CodecError> {
+    let mut dumper = Dumper {
+        reader,
+        out,
+        options,
+        stats: DumpStats::default(),
+    };
+
+    while let Some(header) = dumper.read_header()? {
+        dumper.dump_data(header, 1)?;
+    }
+
+    Ok(dumper.stats)
+}
+
+/// State of an in-progress [`dump`].
+struct Dumper<'a, R: ReadsDecodable + ?Sized, W: Write> {
+    reader: &'a mut R,
+    out: &'a mut W,
+    options: &'a DumpOptions<'a>,
+    stats: DumpStats,
+}
+
+impl<'a, R: ReadsDecodable + ?Sized, W: Write> Dumper<'a, R, W> {
+    /// Reads the next header from the reader, returning
+    /// `None` if the reader ended before the header.
+    fn read_header(&mut self) -> Result<Option<DataHeader>, CodecError> {
+        let mut bytes = [0u8; DataHeader::FORMAT.as_data_format().blob_size as usize];
+        if self.reader.read(&mut bytes[..1])? == 0 {
+            return Ok(None);
+        }
+        self.reader.read_exact(&mut bytes[1..])?;
+
+        Ok(Some(DataHeader {
+            count: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            format: DataFormat {
+                blob_size: u16::from_le_bytes([bytes[4], bytes[5]]),
+                data_fields: bytes[6],
+                ordinal: bytes[7],
+            },
+        }))
+    }
+
+    /// Dumps the data following `header`,
+    /// which was read at `depth`.
+    fn dump_data(&mut self, header: DataHeader, depth: u32) -> Result<(), CodecError> {
+        if depth > self.options.max_depth {
+            return Err(CodecError::DepthLimitExceeded);
+        }
+
+        let offset = self.stats.bytes;
+        self.stats.bytes += DataHeader::FORMAT.as_data_format().blob_size as u64;
+        self.stats.records += 1;
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+
+        // Dump the header.
+        let format = header.format;
+        self.indent(depth - 1)?;
+        write!(self.out, "@{offset} {}", header.count)?;
+        if format.ordinal == 0 {
+            write!(self.out, " Unspecified")?;
+        } else {
+            write!(self.out, " O({})", format.ordinal)?;
+            if let Some(name) = self.name_of(format.ordinal) {
+                write!(self.out, " {name}")?;
+            }
+        }
+        writeln!(
+            self.out,
+            " - {} Bytes, {} Data",
+            format.blob_size, format.data_fields
+        )?;
+
+        // Blobs of data without data fields are contiguous,
+        // so they're dumped all at once.
+        if format.data_fields == 0 {
+            return self.dump_blob(header.count as u64 * format.blob_size as u64, depth);
+        }
+
+        for _ in 0..header.count {
+            self.dump_blob(format.blob_size as u64, depth)?;
+            for _ in 0..format.data_fields {
+                let Some(header) = self.read_header()? else {
+                    return Err(CodecError::UnexpectedEof);
+                };
+                self.dump_data(header, depth + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dumps the next `length` bytes of blob
+    /// data contained by data at `depth`.
+    fn dump_blob(&mut self, length: u64, depth: u32) -> Result<(), CodecError> {
+        if length == 0 {
+            return Ok(());
+        }
+
+        let offset = self.stats.bytes;
+        self.stats.bytes += length;
+
+        if !self.options.blobs {
+            return self.reader.skip_blob(length as usize);
+        }
+
+        self.indent(depth)?;
+        write!(self.out, "@{offset} ")?;
+        let mut buffer = [0u8; 64];
+        let mut remaining = length;
+        while remaining > 0 {
+            let chunk = &mut buffer[..remaining.min(64) as usize];
+            self.reader.read_exact(chunk)?;
+            for byte in chunk.iter() {
+                write!(self.out, "{byte:02x}")?;
+            }
+            remaining -= chunk.len() as u64;
+        }
+        writeln!(self.out)?;
+
+        Ok(())
+    }
+
+    /// Writes indentation for `depth` levels of nesting.
+    fn indent(&mut self, depth: u32) -> Result<(), CodecError> {
+        for _ in 0..depth {
+            self.out.write_str("  ")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the name of the data with `ordinal`, if known.
+    fn name_of(&self, ordinal: u8) -> Option<&'a str> {
+        // Built-in types are matched by ordinal (instead of
+        // via `Type::from_ordinal`) to avoid allocating.
+        let builtin = [
+            Type::U8,
+            Type::U16,
+            Type::U32,
+            Type::U64,
+            Type::I8,
+            Type::I16,
+            Type::I32,
+            Type::I64,
+            Type::U128,
+            Type::I128,
+            Type::F32,
+            Type::F64,
+            Type::Bool,
+            Type::Text,
+            Type::Char,
+            Type::UVar,
+            Type::IVar,
+            Type::Bytes,
+            Type::Timestamp,
+        ];
+        let names = [
+            "u8",
+            "u16",
+            "u32",
+            "u64",
+            "i8",
+            "i16",
+            "i32",
+            "i64",
+            "u128",
+            "i128",
+            "f32",
+            "f64",
+            "bool",
+            "text",
+            "char",
+            "uvar",
+            "ivar",
+            "bytes",
+            "timestamp",
+        ];
+        for (typing, name) in builtin.iter().zip(names) {
+            if typing.ordinal() == ordinal {
+                return Some(name);
+            }
+        }
+
+        match ordinal {
+            243 => Some("list"),
+            242 => Some("map"),
+            234 => Some("enum"),
+            _ => self
+                .options
+                .coda?
+                .iter()
+                .find(|data| data.format().as_data_format().ordinal == ordinal)
+                .map(|data| data.name.as_ref()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::{
+        codec::{tests::*, WritesEncodable},
+        types::{DataField, DataType, Text},
+    };
+
+    use super::*;
+
+    #[test]
+    fn dumps_structure() -> Result<(), CodecError> {
+        let mut bytes = vec![];
+        encode_test_data(&mut bytes);
+        bytes.write_data(&Text::from("hi"))?;
+
+        let mut out = String::new();
+        let stats = dump(&mut bytes.as_slice(), &mut out, &DumpOptions::default())?;
+
+        assert_eq!(
+            "\
+@0 1 Unspecified - 12 Bytes, 1 Data
+  @8 fdffffff4d01000000000000
+  @20 17 Unspecified - 1 Bytes, 0 Data
+    @28 7661722d6c656e677468206669656c6421
+@45 2 Unspecified - 1 Bytes, 0 Data
+  @53 6869
+",
+            out
+        );
+        assert_eq!(
+            DumpStats {
+                records: 3,
+                bytes: bytes.len() as u64,
+                max_depth: 2,
+            },
+            stats
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn dumps_names_and_skips_blobs() -> Result<(), CodecError> {
+        let point = DataType::new("Point".into(), None, 1, &[], &[]).with(DataField {
+            name: "x".into(),
+            docs: None,
+            typing: Type::I32,
+            optional: false,
+            flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
+        });
+        let coda = Coda::new("Points".into(), "Points".into(), None, &[point]);
+
+        let mut bytes = vec![];
+        bytes.write_data(&DataHeader {
+            count: 1,
+            format: DataFormat {
+                blob_size: 4,
+                data_fields: 0,
+                ordinal: 1,
+            },
+        })?;
+        bytes.write_data(&7i32)?;
+
+        let mut out = String::new();
+        let options = DumpOptions {
+            coda: Some(&coda),
+            blobs: false,
+            ..Default::default()
+        };
+        let stats = dump(&mut bytes.as_slice(), &mut out, &options)?;
+
+        assert_eq!("@0 1 O(1) Point - 4 Bytes, 0 Data\n", out);
+        assert_eq!(12, stats.bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let mut bytes = vec![];
+        encode_test_data(&mut bytes);
+        bytes.truncate(bytes.len() - 1);
+
+        let mut out = String::new();
+        let result = dump(&mut bytes.as_slice(), &mut out, &DumpOptions::default());
+        assert!(result.is_err(), "{result:?}");
+    }
+}
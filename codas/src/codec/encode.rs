@@ -1,7 +1,12 @@
 //! Codec encoder implementations.
-use crate::stream::Writes;
+use alloc::vec::Vec;
 
-use super::{CodecError, DataHeader, Format};
+use crate::stream::{CountingWrites, StreamError, Writes};
+
+use super::{
+    checksum::{ChecksumWriter, Crc32},
+    CodecError, DataHeader, Format, CHECKED_FRAME_MAGIC,
+};
 
 /// A thing that encodes into
 /// [`codec`](super)-compliant data.
@@ -57,6 +62,55 @@ pub trait Encodable {
             }
         }
     }
+
+    /// Returns the number of bytes [`WritesEncodable::write_data`]
+    /// writes when encoding this thing, _including_ its [`DataHeader`].
+    ///
+    /// By default, this thing is encoded into a
+    /// [`CountingWrites`] that discards every byte written.
+    /// Implementations whose size is known up-front
+    /// should override this function.
+    ///
+    /// ```rust
+    /// # use codas::types::Text;
+    /// # use crate::codas::codec::{Encodable, WritesEncodable};
+    /// let data = Text::from("cupcakes!");
+    ///
+    /// let mut encoded = vec![];
+    /// encoded.write_data(&data).unwrap();
+    ///
+    /// assert_eq!(encoded.len(), data.encoded_len());
+    /// ```
+    fn encoded_len(&self) -> usize {
+        let mut counter = CountingWrites::new(Discard);
+        let _ = counter.write_data(self);
+        counter.bytes_written()
+    }
+
+    /// Returns this thing encoded into a new vector,
+    /// as by [`encode_to_vec`].
+    fn encoded_bytes(&self) -> Result<Vec<u8>, CodecError> {
+        encode_to_vec(self)
+    }
+}
+
+/// Returns `data` encoded into a new vector,
+/// as by [`WritesEncodable::write_data`].
+///
+/// ```rust
+/// # use codas::types::Text;
+/// # use codas::codec::{decode_from_slice, encode_to_vec};
+/// let data = Text::from("cupcakes!");
+///
+/// let encoded = encode_to_vec(&data).unwrap();
+/// let decoded: Text = decode_from_slice(&encoded).unwrap();
+///
+/// assert_eq!(data, decoded);
+/// ```
+pub fn encode_to_vec<T: Encodable + ?Sized>(data: &T) -> Result<Vec<u8>, CodecError> {
+    let mut encoded = Vec::new();
+    encoded.write_data(data)?;
+    Ok(encoded)
 }
 
 /// A thing that [`Writes`] [`Encodable`] data.
@@ -90,14 +144,113 @@ pub trait WritesEncodable: Writes {
 
         Ok(())
     }
+
+    /// Encodes and writes the [`DataHeader`] of a
+    /// sequence of `count` data of type `T`.
+    ///
+    /// The header must be followed by exactly `count` calls
+    /// to [`Self::write_data_value`], each writing a `T`. The
+    /// resulting sequence is encoded identically to a list
+    /// of `T` (like a [`Vec<T>`](alloc::vec::Vec)), allowing
+    /// lists to be written incrementally:
+    ///
+    /// ```rust
+    /// # use codas::types::Text;
+    /// # use crate::codas::codec::{WritesEncodable, ReadsDecodable};
+    /// let mut encoded = vec![];
+    /// encoded.write_data_header_for::<Text>(2).unwrap();
+    /// encoded.write_data_value(&Text::from("cup")).unwrap();
+    /// encoded.write_data_value(&Text::from("cakes!")).unwrap();
+    ///
+    /// let decoded: Vec<Text> = encoded.as_slice().read_data().unwrap();
+    /// assert_eq!(vec![Text::from("cup"), Text::from("cakes!")], decoded);
+    /// ```
+    fn write_data_header_for<T: Encodable + ?Sized>(
+        &mut self,
+        count: u32,
+    ) -> Result<(), CodecError> {
+        DataHeader {
+            count,
+            format: Format::data(0).with(T::FORMAT).as_data_format(),
+        }
+        .encode(self)
+    }
+
+    /// Encodes and writes `value` as one of the data in a
+    /// sequence started by [`Self::write_data_header_for`].
+    ///
+    /// If `T`'s format is [`Format::is_structured`], `value`
+    /// is preceded by its own [`DataHeader`]; otherwise, only
+    /// `value`'s blob is written, as described by the
+    /// sequence's header.
+    fn write_data_value<T: Encodable + ?Sized>(&mut self, value: &T) -> Result<(), CodecError> {
+        value.encode_header(self)?;
+        value.encode(self)
+    }
+
+    /// Encodes and writes `data` prefixed by the
+    /// number of bytes it encodes into, as a little-endian
+    /// [`u32`].
+    ///
+    /// Framed data can be read with
+    /// [`ReadsDecodable::read_framed_data`](super::ReadsDecodable::read_framed_data).
+    fn write_framed_data<T: Encodable + ?Sized>(&mut self, data: &T) -> Result<(), CodecError> {
+        let length = super::try_count(data.encoded_len())?;
+        self.write_all(&length.to_le_bytes())?;
+        self.write_data(data)
+    }
+
+    /// Encodes and writes `data` within a checked frame,
+    /// containing (in order):
+    ///
+    /// Type | Description
+    /// -----|-----------
+    /// `u16`| The [`CHECKED_FRAME_MAGIC`] number.
+    /// `u32`| The number of bytes `data` encodes into.
+    /// ...  | The encoded `data`.
+    /// `u32`| The CRC-32 (IEEE) checksum of the encoded `data`.
+    ///
+    /// Checked frames can be read with
+    /// [`ReadsDecodable::read_data_checked`](super::ReadsDecodable::read_data_checked).
+    fn write_data_checked<T: Encodable + ?Sized>(&mut self, data: &T) -> Result<(), CodecError> {
+        let length = super::try_count(data.encoded_len())?;
+        self.write_all(&CHECKED_FRAME_MAGIC.to_le_bytes())?;
+        self.write_all(&length.to_le_bytes())?;
+
+        let mut writer = ChecksumWriter {
+            writer: self,
+            checksum: Crc32::new(),
+        };
+        writer.write_data(data)?;
+        let checksum = writer.checksum.finish();
+
+        self.write_all(&checksum.to_le_bytes())?;
+        Ok(())
+    }
 }
 
 impl<T: Writes + ?Sized> WritesEncodable for T {}
 
+/// [`Writes`] that discards all bytes.
+struct Discard;
+
+impl Writes for Discard {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, _buf: &[u8]) -> Result<(), StreamError> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::codec::tests::*;
+    use crate::{
+        codec::{decode_from_slice, tests::*},
+        types::Text,
+    };
 
     #[test]
     fn encodes() -> Result<(), CodecError> {
@@ -126,4 +279,92 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn encodes_to_vecs() -> Result<(), CodecError> {
+        let test_data = TestData::default();
+        let mut expected = Vec::new();
+        expected.write_data(&test_data)?;
+
+        assert_eq!(expected, encode_to_vec(&test_data)?);
+        assert_eq!(expected, test_data.encoded_bytes()?);
+
+        // Bytes following the data are ignored.
+        let text = Text::from("trailing");
+        let mut bytes = encode_to_vec(&text)?;
+        bytes.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(text, decode_from_slice::<Text>(&bytes)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hints_encoded_len() -> Result<(), CodecError> {
+        let test_data = TestData::default();
+        let bytes = encode_to_vec(&test_data)?;
+        assert_eq!(bytes.len(), test_data.encoded_len());
+
+        // Check each of the test data's fields.
+        let bytes = encode_to_vec(&test_data.num_a)?;
+        assert_eq!(bytes.len(), test_data.num_a.encoded_len());
+        let bytes = encode_to_vec(&test_data.num_b)?;
+        assert_eq!(bytes.len(), test_data.num_b.encoded_len());
+        let bytes = encode_to_vec(&test_data.text)?;
+        assert_eq!(bytes.len(), test_data.text.encoded_len());
+
+        // Check sequences of the test data.
+        let sequence = vec![TestData::default(), TestData::default()];
+        let bytes = encode_to_vec(&sequence)?;
+        assert_eq!(bytes.len(), sequence.encoded_len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn writes_sequences_incrementally() -> Result<(), CodecError> {
+        use crate::{codec::ReadsDecodable, types::Text};
+
+        // Structured data.
+        let texts = vec![Text::from("one"), Text::from(""), Text::from("three")];
+        let mut bytes = Vec::new();
+        bytes.write_data_header_for::<Text>(3)?;
+        for text in &texts {
+            bytes.write_data_value(text)?;
+        }
+        assert_eq!(texts, bytes.as_slice().read_data::<Vec<Text>>()?);
+        let expected = encode_to_vec(&texts)?;
+        assert_eq!(expected, bytes);
+
+        // Blob data.
+        let numbers = vec![1u32, 2, 3];
+        let mut bytes = Vec::new();
+        bytes.write_data_header_for::<u32>(3)?;
+        for number in &numbers {
+            bytes.write_data_value(number)?;
+        }
+        assert_eq!(numbers, bytes.as_slice().read_data::<Vec<u32>>()?);
+
+        // Nested data.
+        let mut bytes = Vec::new();
+        bytes.write_data_header_for::<TestData>(2)?;
+        bytes.write_data_value(&TestData::default())?;
+        bytes.write_data_value(&TestData::default())?;
+        let expected = encode_to_vec(&vec![TestData::default(), TestData::default()])?;
+        assert_eq!(expected, bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn writes_framed_data() -> Result<(), CodecError> {
+        let mut bytes = Vec::new();
+        bytes.write_framed_data(&TestData::default())?;
+
+        let mut expected = Vec::new();
+        encode_test_data(&mut expected);
+        assert_eq!((expected.len() as u32).to_le_bytes(), bytes[..4]);
+        assert_eq!(expected, bytes[4..]);
+
+        Ok(())
+    }
 }
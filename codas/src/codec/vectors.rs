@@ -0,0 +1,477 @@
+//! Canonical test vectors for the [`codec`](super).
+//!
+//! Each [`TestVector`] names a sample value (like
+//! each primitive, text, optionals, lists, maps, and
+//! nested data) alongside the exact bytes it encodes
+//! into. Codec implementations on other platforms can
+//! check their encodings against these vectors, and
+//! golden-file tests can check that the encoding of
+//! each vector never changes unintentionally.
+//!
+//! [`test_vectors`] returns the registry of all vectors,
+//! which can be rendered as JSON (with [`vectors_to_json`])
+//! or as a markdown table (with [`vectors_to_markdown`]).
+//! Vectors rendered as JSON can be parsed back with
+//! [`vectors_from_json`], and then [`compare`]d against
+//! the current registry.
+//!
+//! # Unstable
+//!
+//! The APIs exposed by this module are _primarily_
+//! for use by automated tooling (tests, CLIs, etc.);
+//! the exact APIs are subject to change, and may
+//! not be well-optimized.
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{Debug, Write};
+
+use snafu::Snafu;
+
+use crate::types::{
+    binary::{bytes_from_hex, hex_from_bytes},
+    Text,
+};
+
+use super::{encode_to_vec, CodecError, Encodable, Format, WritesEncodable};
+
+/// A named sample value and its canonical encoding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestVector {
+    /// Unique name of the vector.
+    pub name: Text,
+
+    /// Human-readable (Rust debug) representation
+    /// of the vector's value.
+    pub value: Text,
+
+    /// Bytes the vector's value encodes into
+    /// with [`WritesEncodable::write_data`].
+    pub bytes: Vec<u8>,
+}
+
+impl TestVector {
+    /// Returns a new vector named `name`
+    /// containing the encoding of `value`.
+    pub fn new<T: Encodable + Debug + ?Sized>(name: &str, value: &T) -> Result<Self, CodecError> {
+        let bytes = encode_to_vec(value)?;
+
+        Ok(Self {
+            name: name.to_string().into(),
+            value: format!("{value:?}").into(),
+            bytes,
+        })
+    }
+
+    /// Returns the vector's bytes as
+    /// a lowercase hexadecimal string.
+    pub fn hex(&self) -> Text {
+        hex_from_bytes(&self.bytes)
+    }
+}
+
+/// Returns the registry of all canonical test vectors.
+pub fn test_vectors() -> Vec<TestVector> {
+    let mut registry = Registry::default();
+
+    // Unsigned integers.
+    registry.add("u8 zero", &0u8);
+    registry.add("u8 max", &u8::MAX);
+    registry.add("u16 little-endian", &0x1234u16);
+    registry.add("u16 max", &u16::MAX);
+    registry.add("u32 little-endian", &0x1234_5678u32);
+    registry.add("u32 max", &u32::MAX);
+    registry.add("u64 little-endian", &0x0123_4567_89AB_CDEFu64);
+    registry.add("u64 max", &u64::MAX);
+    registry.add("u128 one", &1u128);
+    registry.add("u128 max", &u128::MAX);
+
+    // Signed integers.
+    registry.add("i8 negative one", &-1i8);
+    registry.add("i8 min", &i8::MIN);
+    registry.add("i16 negative", &-300i16);
+    registry.add("i16 min", &i16::MIN);
+    registry.add("i32 negative", &-3i32);
+    registry.add("i32 min", &i32::MIN);
+    registry.add("i64 negative", &-333i64);
+    registry.add("i64 min", &i64::MIN);
+    registry.add("i128 negative one", &-1i128);
+    registry.add("i128 min", &i128::MIN);
+
+    // Floating-point numbers.
+    registry.add("f32 one and a half", &1.5f32);
+    registry.add("f32 negative zero", &-0.0f32);
+    registry.add("f32 infinity", &f32::INFINITY);
+    registry.add("f64 one and a half", &1.5f64);
+    registry.add("f64 negative", &-2.25f64);
+    registry.add("f64 infinity", &f64::NEG_INFINITY);
+
+    // Booleans.
+    registry.add("bool false", &false);
+    registry.add("bool true", &true);
+
+    // Text.
+    registry.add("text empty", &Text::from(""));
+    registry.add("text ascii", &Text::from("var-length field!"));
+    registry.add("text unicode", &Text::from("héllo, 世界! 🦀"));
+
+    // Optionals.
+    registry.add("optional u32 none", &None::<u32>);
+    registry.add("optional u32 some", &Some(5u32));
+    registry.add("optional text none", &None::<Text>);
+    registry.add("optional text some", &Some(Text::from("hi")));
+
+    // Lists.
+    registry.add("list of u8", &vec![1u8, 2, 3]);
+    registry.add("list of i32 empty", &Vec::<i32>::new());
+    registry.add("list of i32", &vec![1i32, -1]);
+    registry.add("list of text", &vec![Text::from("a"), Text::from("bc")]);
+    registry.add("list of list of u16", &vec![vec![1u16], vec![]]);
+    registry.add("array of u8", &[1u8, 2, 3, 4]);
+    registry.add("array of u16", &[1u16, 2]);
+
+    // Maps.
+    registry.add("map of text to i32 empty", &BTreeMap::<Text, i32>::new());
+    registry.add(
+        "map of text to i32",
+        &BTreeMap::from([(Text::from("two"), 2i32), (Text::from("one"), 1i32)]),
+    );
+    registry.add(
+        "map of u8 to list of text",
+        &BTreeMap::from([(7u8, vec![Text::from("seven")])]),
+    );
+
+    // Data.
+    registry.add("data", &TestData::default());
+    registry.add("list of data", &vec![TestData::default()]);
+    registry.add("optional data some", &Some(TestData::default()));
+    registry.add("nested data", &NestedTestData::default());
+
+    registry.vectors
+}
+
+/// Returns `vectors` as a JSON array of objects,
+/// each containing a vector's `name`, `value`,
+/// and `hex`-encoded bytes.
+pub fn vectors_to_json(vectors: &[TestVector]) -> Text {
+    let mut json = String::from("[");
+    for (i, vector) in vectors.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        let _ = write!(json, "\n  {{\n    \"name\": ");
+        write_json_string(&mut json, &vector.name);
+        let _ = write!(json, ",\n    \"value\": ");
+        write_json_string(&mut json, &vector.value);
+        let _ = write!(json, ",\n    \"hex\": \"{}\"\n  }}", vector.hex());
+    }
+    json.push_str("\n]\n");
+    json.into()
+}
+
+/// Returns `vectors` as a markdown table, with one
+/// row containing each vector's name, value, and
+/// hex-encoded bytes.
+pub fn vectors_to_markdown(vectors: &[TestVector]) -> Text {
+    let mut markdown = String::from("Name | Value | Hex\n-----|-------|----\n");
+    for vector in vectors {
+        let _ = writeln!(
+            markdown,
+            "{} | `{}` | `{}`",
+            vector.name.replace('|', "\\|"),
+            vector.value.replace('|', "\\|"),
+            vector.hex()
+        );
+    }
+    markdown.into()
+}
+
+/// Parses vectors from `json` rendered
+/// by [`vectors_to_json`].
+pub fn vectors_from_json(json: &str) -> Result<Vec<TestVector>, VectorError> {
+    let json: serde_json::Value =
+        serde_json::from_str(json).map_err(|error| VectorError::InvalidJson {
+            message: format!("{error}").into(),
+        })?;
+    let Some(entries) = json.as_array() else {
+        return Err(VectorError::InvalidJson {
+            message: "expected an array of vectors".into(),
+        });
+    };
+
+    let mut vectors = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let field = |field: &'static str| {
+            entry[field]
+                .as_str()
+                .ok_or(VectorError::MissingField { index, field })
+        };
+        let name = field("name")?;
+        let value = field("value")?;
+        let bytes = bytes_from_hex(field("hex")?).map_err(|error| VectorError::InvalidHex {
+            name: name.to_string().into(),
+            message: format!("{error}").into(),
+        })?;
+
+        vectors.push(TestVector {
+            name: name.to_string().into(),
+            value: value.to_string().into(),
+            bytes,
+        });
+    }
+
+    Ok(vectors)
+}
+
+/// Compares the `actual` encodings of vectors against
+/// their `expected` encodings, returning a mismatch for
+/// each vector that's missing, unexpected, or changed.
+///
+/// Vectors are matched by name.
+pub fn compare(expected: &[TestVector], actual: &[TestVector]) -> Vec<VectorMismatch> {
+    let mut mismatches = Vec::new();
+
+    for expected in expected {
+        let Some(actual) = actual.iter().find(|actual| actual.name == expected.name) else {
+            mismatches.push(VectorMismatch::Missing {
+                name: expected.name.clone(),
+            });
+            continue;
+        };
+
+        if expected.bytes == actual.bytes {
+            continue;
+        }
+
+        let offset = expected
+            .bytes
+            .iter()
+            .zip(actual.bytes.iter())
+            .position(|(expected, actual)| expected != actual)
+            .unwrap_or(expected.bytes.len().min(actual.bytes.len()));
+
+        mismatches.push(VectorMismatch::Changed {
+            name: expected.name.clone(),
+            offset,
+            expected: expected.hex(),
+            actual: actual.hex(),
+        });
+    }
+
+    for actual in actual {
+        if !expected.iter().any(|expected| expected.name == actual.name) {
+            mismatches.push(VectorMismatch::Unexpected {
+                name: actual.name.clone(),
+            });
+        }
+    }
+
+    mismatches
+}
+
+/// Enumeration of errors that may occur
+/// while parsing [`TestVector`]s.
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum VectorError {
+    #[snafu(display("the vectors' JSON is invalid: {message}"))]
+    InvalidJson { message: Text },
+
+    #[snafu(display("vector {} is missing its `{field}`", index + 1))]
+    MissingField { index: usize, field: &'static str },
+
+    #[snafu(display("the encoding of vector `{name}` isn't valid hexadecimal: {message}"))]
+    InvalidHex { name: Text, message: Text },
+}
+
+/// Enumeration of differences between
+/// expected and actual [`TestVector`]s.
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum VectorMismatch {
+    #[snafu(display("vector `{name}` is expected, but no longer exists"))]
+    Missing { name: Text },
+
+    #[snafu(display("vector `{name}` exists, but isn't expected"))]
+    Unexpected { name: Text },
+
+    #[snafu(display(
+        "expected vector `{name}` to encode to `{expected}`, but it encoded to `{actual}` (first difference at byte {offset})"
+    ))]
+    Changed {
+        name: Text,
+        offset: usize,
+        expected: Text,
+        actual: Text,
+    },
+}
+
+/// Sample data containing blob and data fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestData {
+    pub num_a: i32,
+    pub num_b: u64,
+    pub text: Text,
+}
+
+impl Default for TestData {
+    fn default() -> Self {
+        Self {
+            num_a: -3i32,
+            num_b: 333u64,
+            text: "var-length field!".into(),
+        }
+    }
+}
+
+impl Encodable for TestData {
+    const FORMAT: Format = Format::data(1)
+        .with(i32::FORMAT)
+        .with(u64::FORMAT)
+        .with(Text::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        writer.write_data(&self.num_a)?;
+        writer.write_data(&self.num_b)?;
+        writer.write_data(&self.text)?;
+        Ok(())
+    }
+}
+
+/// Sample data containing nested [`TestData`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NestedTestData {
+    pub flag: bool,
+    pub data: TestData,
+    pub list: Vec<TestData>,
+    pub optional: Option<TestData>,
+}
+
+impl Default for NestedTestData {
+    fn default() -> Self {
+        Self {
+            flag: true,
+            data: TestData::default(),
+            list: vec![TestData {
+                num_a: 1,
+                num_b: 2,
+                text: "listed".into(),
+            }],
+            optional: None,
+        }
+    }
+}
+
+impl Encodable for NestedTestData {
+    const FORMAT: Format = Format::data(2)
+        .with(bool::FORMAT)
+        .with(TestData::FORMAT)
+        .with(Vec::<TestData>::FORMAT)
+        .with(Option::<TestData>::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        writer.write_data(&self.flag)?;
+        writer.write_data(&self.data)?;
+        writer.write_data(&self.list)?;
+        writer.write_data(&self.optional)?;
+        Ok(())
+    }
+}
+
+/// Registry of vectors being built by [`test_vectors`].
+#[derive(Default)]
+struct Registry {
+    vectors: Vec<TestVector>,
+}
+
+impl Registry {
+    /// Adds a vector named `name` containing `value`.
+    fn add<T: Encodable + Debug + ?Sized>(&mut self, name: &str, value: &T) {
+        debug_assert!(
+            !self.vectors.iter().any(|vector| vector.name == name),
+            "duplicate vector name: {name}"
+        );
+        let vector = TestVector::new(name, value).expect("test vectors must be encodable");
+        self.vectors.push(vector);
+    }
+}
+
+/// Writes `string` to `json` as a quoted JSON string.
+fn write_json_string(json: &mut String, string: &str) {
+    json.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_and_parses_vectors() {
+        let vectors = test_vectors();
+
+        let json = vectors_to_json(&vectors);
+        assert!(json.contains(
+            "\n  {\n    \"name\": \"u16 little-endian\",\n    \"value\": \"4660\",\n    \"hex\": \"3412\"\n  },\n"
+        ));
+        assert!(json.contains("\"value\": \"\\\"héllo, 世界! 🦀\\\"\""));
+        assert_eq!(vectors, vectors_from_json(&json).unwrap());
+
+        let markdown = vectors_to_markdown(&vectors);
+        assert!(markdown.starts_with("Name | Value | Hex\n-----|-------|----\n"));
+        assert!(markdown.contains("\nu16 little-endian | `4660` | `3412`\n"));
+
+        assert_eq!(
+            Err(VectorError::MissingField {
+                index: 0,
+                field: "hex"
+            }),
+            vectors_from_json(r#"[{ "name": "a", "value": "b" }]"#)
+        );
+    }
+
+    #[test]
+    fn compares_vectors() {
+        let expected = test_vectors();
+        assert_eq!(Vec::<VectorMismatch>::new(), compare(&expected, &expected));
+
+        let mut actual = expected.clone();
+        actual[0].bytes = vec![0xFF];
+        actual.remove(1);
+        actual.push(TestVector::new("novel", &1u8).unwrap());
+
+        assert_eq!(
+            vec![
+                VectorMismatch::Changed {
+                    name: "u8 zero".into(),
+                    offset: 0,
+                    expected: "00".into(),
+                    actual: "ff".into(),
+                },
+                VectorMismatch::Missing {
+                    name: "u8 max".into()
+                },
+                VectorMismatch::Unexpected {
+                    name: "novel".into()
+                },
+            ],
+            compare(&expected, &actual)
+        );
+    }
+}
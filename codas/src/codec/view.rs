@@ -0,0 +1,314 @@
+//! Lazy, read-only views over encoded data.
+//!
+//! A [`DataView`] reads the headers, blobs, and data fields
+//! of encoded data directly from a slice of bytes, without
+//! decoding any of the data into values. Views never allocate,
+//! making them suitable for inspecting a few fields of a large
+//! message (e.g., when routing it).
+//!
+//! ```rust
+//! # use codas::codec::{view::DataView, WritesEncodable};
+//! # use codas::types::Text;
+//! let mut encoded = vec![];
+//! encoded.write_data(&Text::from("cupcakes!")).unwrap();
+//!
+//! let view = DataView::new(&encoded).unwrap();
+//! assert_eq!("cupcakes!", view.as_text().unwrap());
+//! ```
+use crate::types::Text;
+
+use super::{
+    CodecError, DataHeader, Decodable, Encodable, ReadsDecodable, UnexpectedDataFormatSnafu,
+    DEFAULT_MAX_DEPTH,
+};
+
+/// Size of an encoded [`DataHeader`], in bytes.
+const HEADER_SIZE: usize = DataHeader::FORMAT.as_data_format().blob_size as usize;
+
+/// A view over a single sequence of encoded data,
+/// beginning with its [`DataHeader`].
+///
+/// If the sequence contains more than one data (i.e., it's
+/// a list), the view's blob and fields are those of the
+/// _first_ data in the sequence.
+#[derive(Debug, Clone, Copy)]
+pub struct DataView<'a> {
+    header: DataHeader,
+    bytes: &'a [u8],
+}
+
+impl<'a> DataView<'a> {
+    /// Returns a new view over the sequence
+    /// of data at the start of `bytes`.
+    ///
+    /// Any bytes following the sequence
+    /// are excluded from the view.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, CodecError> {
+        let len = encoded_len(bytes, 0)?;
+        Ok(Self {
+            header: read_header(bytes)?,
+            bytes: &bytes[..len],
+        })
+    }
+
+    /// Returns the view's header.
+    pub fn header(&self) -> DataHeader {
+        self.header
+    }
+
+    /// Returns the ordinal of the viewed data's type.
+    pub fn ordinal(&self) -> u8 {
+        self.header.format.ordinal
+    }
+
+    /// Returns the number of data in the viewed sequence.
+    pub fn count(&self) -> u32 {
+        self.header.count
+    }
+
+    /// Returns all bytes in the view,
+    /// including its header.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the bytes of the viewed data's blob fields,
+    /// which are empty if the sequence is empty.
+    pub fn blob(&self) -> &'a [u8] {
+        if self.header.count == 0 {
+            return &[];
+        }
+
+        let blob_size = self.header.format.blob_size as usize;
+        &self.bytes[HEADER_SIZE..HEADER_SIZE + blob_size]
+    }
+
+    /// Returns a view over the viewed data's `n`th
+    /// (zero-indexed) data field.
+    pub fn field(&self, n: u8) -> Result<DataView<'a>, CodecError> {
+        if self.header.count == 0 || n >= self.header.format.data_fields {
+            return Err(CodecError::MissingDataFields {
+                count: n.saturating_add(1),
+                offset: None,
+            });
+        }
+
+        // Skip the blob and all preceding fields.
+        let mut start = HEADER_SIZE + self.header.format.blob_size as usize;
+        for _ in 0..n {
+            start += encoded_len(&self.bytes[start..], 1)?;
+        }
+
+        DataView::new(&self.bytes[start..])
+    }
+
+    /// Returns the viewed data as text, if it's [`Text`].
+    pub fn as_text(&self) -> Result<&'a str, CodecError> {
+        let format = self.header.format;
+        if format.blob_size != 1 || format.data_fields != 0 || format.ordinal != 0 {
+            return UnexpectedDataFormatSnafu {
+                expected: Text::FORMAT,
+                actual: Some(self.header),
+                offset: None,
+            }
+            .fail();
+        }
+
+        // Malformed UTF-8 is rejected, matching
+        // the behavior of `Text`'s decoder.
+        core::str::from_utf8(&self.bytes[HEADER_SIZE..]).map_err(|error| CodecError::InvalidText {
+            at: error.valid_up_to(),
+        })
+    }
+
+    /// Returns the viewed data as `T`,
+    /// decoding it (and only it) from the view.
+    pub fn decode<T: Decodable + Default>(&self) -> Result<T, CodecError> {
+        let mut bytes = self.bytes;
+        bytes.read_data()
+    }
+}
+
+/// Implements accessors for numbers
+/// in the blob of a [`DataView`].
+macro_rules! numeric_accessors {
+    ($($primitive_type:ident => $name:ident),+ $(,)?) => {
+        impl DataView<'_> {
+            $(
+                #[doc = concat!(
+                    "Returns the [`", stringify!($primitive_type), "`] ",
+                    "starting at `offset` bytes into the viewed data's blob."
+                )]
+                pub fn $name(&self, offset: usize) -> Result<$primitive_type, CodecError> {
+                    const SIZE: usize = core::mem::size_of::<$primitive_type>();
+                    let bytes = self.blob_bytes::<SIZE>(offset)?;
+                    Ok($primitive_type::from_le_bytes(bytes))
+                }
+            )+
+        }
+    };
+}
+
+numeric_accessors!(
+    u8 => u8_at,
+    u16 => u16_at,
+    u32 => u32_at,
+    u64 => u64_at,
+    u128 => u128_at,
+    i8 => i8_at,
+    i16 => i16_at,
+    i32 => i32_at,
+    i64 => i64_at,
+    i128 => i128_at,
+    f32 => f32_at,
+    f64 => f64_at,
+);
+
+impl DataView<'_> {
+    /// Returns the [`bool`] at `offset`
+    /// bytes into the viewed data's blob.
+    pub fn bool_at(&self, offset: usize) -> Result<bool, CodecError> {
+        Ok(self.u8_at(offset)? == 1)
+    }
+
+    /// Returns the `SIZE` bytes starting at
+    /// `offset` bytes into the viewed data's blob.
+    fn blob_bytes<const SIZE: usize>(&self, offset: usize) -> Result<[u8; SIZE], CodecError> {
+        let blob = self.blob();
+        match blob.get(offset..offset.saturating_add(SIZE)) {
+            Some(bytes) => Ok(bytes.try_into().expect("slice has SIZE bytes")),
+            None => Err(CodecError::MissingBlobLength {
+                length: offset
+                    .saturating_add(SIZE)
+                    .saturating_sub(blob.len())
+                    .try_into()
+                    .unwrap_or(u16::MAX),
+                offset: None,
+            }),
+        }
+    }
+}
+
+/// Reads the [`DataHeader`] at the start of `bytes`.
+fn read_header(bytes: &[u8]) -> Result<DataHeader, CodecError> {
+    let mut bytes = bytes;
+    let mut header = DataHeader::default();
+    header.decode(&mut bytes, None)?;
+    Ok(header)
+}
+
+/// Returns the number of bytes in the sequence of data
+/// at the start of `bytes` (including its header),
+/// which is nested `depth` levels deep.
+///
+/// Mirrors [`ReadsDecodable::skip_data`](super::ReadsDecodable::skip_data),
+/// without reading any bytes.
+fn encoded_len(bytes: &[u8], depth: u32) -> Result<usize, CodecError> {
+    if depth >= DEFAULT_MAX_DEPTH {
+        return Err(CodecError::DepthLimitExceeded);
+    }
+
+    let header = read_header(bytes)?;
+    let blob_size = header.format.blob_size as usize;
+    let mut len = HEADER_SIZE;
+
+    // Blobs without data fields are skipped all at once.
+    if header.format.data_fields == 0 {
+        len = len.saturating_add((header.count as usize).saturating_mul(blob_size));
+        return match len <= bytes.len() {
+            true => Ok(len),
+            false => Err(CodecError::UnexpectedEof),
+        };
+    }
+
+    for _ in 0..header.count {
+        // Skip the blob.
+        len += blob_size;
+        if len > bytes.len() {
+            return Err(CodecError::UnexpectedEof);
+        }
+
+        // Skip all data fields recursively.
+        for _ in 0..header.format.data_fields {
+            len += encoded_len(&bytes[len..], depth + 1)?;
+        }
+    }
+
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{encode_to_vec, tests::*, WritesEncodable};
+
+    #[test]
+    fn views_test_data() -> Result<(), CodecError> {
+        let test_data = TestData::default();
+        let mut encoded = vec![];
+        encode_test_data(&mut encoded);
+
+        let view = DataView::new(&encoded)?;
+        assert_eq!(TestData::FORMAT.as_data_format(), view.header().format);
+        assert_eq!(0, view.ordinal());
+        assert_eq!(1, view.count());
+        assert_eq!(encoded.as_slice(), view.as_bytes());
+
+        // Read blob fields.
+        assert_eq!(12, view.blob().len());
+        assert_eq!(test_data.num_a, view.i32_at(0)?);
+        assert_eq!(test_data.num_b, view.u64_at(4)?);
+        assert!(matches!(
+            view.u64_at(8),
+            Err(CodecError::MissingBlobLength { length: 4, .. })
+        ));
+
+        // Read data fields.
+        let text = view.field(0)?;
+        assert_eq!(test_data.text.as_bytes(), text.as_text()?.as_bytes());
+        assert_eq!(test_data.text, text.decode::<Text>()?);
+        assert!(matches!(
+            view.field(1),
+            Err(CodecError::MissingDataFields { .. })
+        ));
+
+        // Views match typed decoding byte-for-byte.
+        let mut bytes = encoded.as_slice();
+        let _: DataHeader = bytes.read_data()?;
+        let num_a: i32 = bytes.read_data()?;
+        assert_eq!(num_a.to_le_bytes(), view.blob()[..4]);
+        let num_b: u64 = bytes.read_data()?;
+        assert_eq!(num_b.to_le_bytes(), view.blob()[4..]);
+        let text_encoded = encode_to_vec(&bytes.read_data::<Text>()?)?;
+        assert_eq!(text_encoded.as_slice(), text.as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn views_nested_fields() -> Result<(), CodecError> {
+        let data: Vec<Vec<Text>> = vec![vec!["a".into(), "bc".into()], vec!["d".into()]];
+        let mut encoded = vec![];
+        encoded.write_data(&data)?;
+
+        // Trailing bytes are excluded from the view.
+        encoded.extend_from_slice(&[1, 2, 3]);
+        let view = DataView::new(&encoded)?;
+        assert_eq!(2, view.count());
+        assert_eq!(encoded.len() - 3, view.as_bytes().len());
+
+        // Fields are viewed for the first data in a list.
+        let first = view.field(0)?;
+        assert_eq!(2, first.count());
+        assert_eq!("a", first.field(0)?.as_text()?);
+        assert_eq!(data[0], first.decode::<Vec<Text>>()?);
+
+        // Non-text can't be viewed as text.
+        assert!(first.as_text().is_err());
+
+        // Truncated data can't be viewed.
+        assert!(DataView::new(&encoded[..encoded.len() - 5]).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,629 @@
+//! Verification of worked [`Example`]s in codas.
+//!
+//! Each example declares some data as JSON, along
+//! with the expected hexadecimal encoding of that data.
+//! [`verify`] parses each example's JSON according to
+//! its data type (exactly like [`from_json_in`]), encodes
+//! it, and checks the result against the example's
+//! declared encoding.
+//!
+//! # Unstable
+//!
+//! The APIs exposed by this module are _primarily_
+//! for use by automated tooling (macros, CLIs, etc.);
+//! the exact APIs are subject to change, and may
+//! not be well-optimized.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use snafu::Snafu;
+
+use crate::{
+    codec::{DataFormat, DataHeader, Encodable, Format, WritesEncodable},
+    types::{
+        binary::{bytes_from_hex, hex_from_bytes},
+        dynamic::{from_json_in, JsonError},
+        number::{VarI64, VarU64},
+        Coda, DataType, Example, Text, Type, Unspecified,
+    },
+};
+
+/// Maximum depth of nested data types that
+/// will be traversed while encoding an example.
+const MAX_DEPTH: usize = 64;
+
+/// Verifies all examples in `coda`, returning
+/// the number of examples verified.
+///
+/// Iff any examples fail verification, an error
+/// is returned for _each_ failing example.
+pub fn verify(coda: &Coda) -> Result<usize, Vec<ExampleError>> {
+    let mut verified = 0;
+    let mut errors = Vec::new();
+
+    for data_type in coda.iter() {
+        for (index, example) in data_type.examples.iter().enumerate() {
+            match verify_example(coda, data_type, example) {
+                Ok(()) => verified += 1,
+                Err(reason) => errors.push(ExampleError {
+                    data_type: data_type.name.clone(),
+                    index,
+                    reason,
+                }),
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(verified)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Verifies a single `example` of `data_type` in `coda`.
+fn verify_example(
+    coda: &Coda,
+    data_type: &DataType,
+    example: &Example,
+) -> Result<(), ExampleFailure> {
+    let Some(hex) = &example.hex else {
+        return Err(ExampleFailure::MissingEncoding);
+    };
+
+    let expected = bytes_from_hex(hex).map_err(|error| ExampleFailure::InvalidHex {
+        message: format!("{error}").into(),
+    })?;
+
+    let value = from_json_in(coda, data_type, &example.json).map_err(|error| match error {
+        JsonError::InvalidJson { .. } => ExampleFailure::InvalidJson {
+            message: format!("{error}").into(),
+        },
+        error => ExampleFailure::Unencodable {
+            message: format!("{error}").into(),
+        },
+    })?;
+
+    let mut encoder = ExampleEncoder {
+        coda,
+        bytes: Vec::new(),
+    };
+    encoder
+        .write_value(&value, &Type::Data(data_type.clone()), false, "", 0)
+        .map_err(|message| ExampleFailure::Unencodable {
+            message: message.into(),
+        })?;
+    let actual = encoder.bytes;
+
+    if expected == actual {
+        return Ok(());
+    }
+
+    let offset = expected
+        .iter()
+        .zip(actual.iter())
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or(expected.len().min(actual.len()));
+
+    Err(ExampleFailure::Mismatch {
+        offset,
+        expected: hex_from_bytes(&expected),
+        actual: hex_from_bytes(&actual),
+    })
+}
+
+/// Encodes dynamic values parsed by [`from_json_in`]
+/// according to the types in a coda.
+struct ExampleEncoder<'a> {
+    coda: &'a Coda,
+    bytes: Vec<u8>,
+}
+
+impl ExampleEncoder<'_> {
+    /// Returns the encoding format of `typing`,
+    /// with all data type references resolved.
+    fn format_of(&self, typing: &Type, optional: bool, depth: usize) -> Result<Format, String> {
+        if depth > MAX_DEPTH {
+            return Err(format!("data types nested deeper than {MAX_DEPTH}"));
+        }
+
+        let format = match typing {
+            Type::Text => Text::FORMAT,
+            Type::Unspecified => Unspecified::FORMAT,
+            Type::List(item) => Format::data(0).with(self.format_of(item, false, depth + 1)?),
+            Type::Map(map) => Format::data(0)
+                .with(Format::data(0).with(self.format_of(&map.0, false, depth + 1)?))
+                .with(Format::data(0).with(self.format_of(&map.1, false, depth + 1)?)),
+            Type::Data(data_type) => {
+                let data_type = resolve(self.coda, data_type)?;
+                let mut format = Format::data(data_type.format().as_data_format().ordinal);
+                for field in data_type.iter() {
+                    format =
+                        format.with(self.format_of(&field.typing, field.optional, depth + 1)?);
+                }
+                format
+            }
+            scalar => scalar.format(),
+        };
+
+        if optional {
+            Ok(Format::data(0).with(format))
+        } else {
+            Ok(format)
+        }
+    }
+
+    /// Writes `value` as data of `typing`, including
+    /// a header iff the data's format is structured.
+    ///
+    /// `path` describes the location of `value` in
+    /// the example, and is used in error messages.
+    fn write_value(
+        &mut self,
+        value: &Unspecified,
+        typing: &Type,
+        optional: bool,
+        path: &str,
+        depth: usize,
+    ) -> Result<(), String> {
+        if depth > MAX_DEPTH {
+            return Err(format!("data types nested deeper than {MAX_DEPTH}"));
+        }
+
+        if optional {
+            if matches!(value, Unspecified::Default) {
+                return self.write_header(0, DataFormat::default());
+            }
+
+            let format = self.format_of(typing, true, depth)?;
+            self.write_header(1, format.as_data_format())?;
+            return self.write_value(value, typing, false, path, depth + 1);
+        }
+
+        match (typing, value) {
+            (Type::Unspecified, value) => self.bytes.write_data(value).map_err(|e| format!("{e}")),
+
+            (Type::Text, Unspecified::Text(text)) => {
+                self.bytes.write_data(text).map_err(|e| format!("{e}"))
+            }
+
+            (Type::Bytes, Unspecified::Bytes(bytes)) => {
+                self.bytes.write_data(bytes).map_err(|e| format!("{e}"))
+            }
+
+            (Type::Bool, Unspecified::Bool(value)) => {
+                self.bytes.write_data(value).map_err(|e| format!("{e}"))
+            }
+
+            (Type::Char, Unspecified::Char(value)) => {
+                self.bytes.write_data(value).map_err(|e| format!("{e}"))
+            }
+
+            (Type::List(item_typing), Unspecified::List(items)) => {
+                let format = self.format_of(typing, false, depth)?;
+                let count = u32::try_from(items.len())
+                    .map_err(|_| format!("too many items in `{path}`"))?;
+                self.write_header(count, format.as_data_format())?;
+
+                for (i, item) in items.iter().enumerate() {
+                    let path = format!("{path}[{i}]");
+                    self.write_value(item, item_typing, false, &path, depth + 1)?;
+                }
+
+                Ok(())
+            }
+
+            // Maps with text keys are parsed as maps, while all
+            // other maps are parsed as lists of `[key, value]` pairs.
+            (Type::Map(_), Unspecified::Map(entries)) => {
+                let entries = entries
+                    .iter()
+                    .map(|(key, value)| (Unspecified::Text(key.clone()), value))
+                    .collect();
+                self.write_map(entries, typing, path, depth)
+            }
+            (Type::Map(_), Unspecified::List(pairs)) => {
+                let mut entries = Vec::with_capacity(pairs.len());
+                for pair in pairs.iter() {
+                    match pair {
+                        Unspecified::List(pair) if pair.len() == 2 => {
+                            entries.push((pair[0].clone(), &pair[1]));
+                        }
+                        _ => return Err(format!("expected [key, value] pairs in `{path}`")),
+                    }
+                }
+                self.write_map(entries, typing, path, depth)
+            }
+
+            (Type::Data(data_type), Unspecified::Map(fields)) => {
+                let data_type = resolve(self.coda, data_type)?;
+                let format = self.format_of(typing, false, depth)?;
+                self.write_header(1, format.as_data_format())?;
+
+                for field in data_type.iter() {
+                    let path = if path.is_empty() {
+                        field.name.to_string()
+                    } else {
+                        format!("{path}.{}", field.name)
+                    };
+
+                    match fields.get(&field.name) {
+                        Some(value) => {
+                            self.write_value(
+                                value,
+                                &field.typing,
+                                field.optional,
+                                &path,
+                                depth + 1,
+                            )?;
+                        }
+                        None => return Err(format!("missing field `{path}`")),
+                    }
+                }
+
+                Ok(())
+            }
+
+            (scalar, value) => self.write_scalar(value, scalar, path),
+        }
+    }
+
+    /// Writes `entries` as a map of `typing`.
+    fn write_map(
+        &mut self,
+        mut entries: Vec<(Unspecified, &Unspecified)>,
+        typing: &Type,
+        path: &str,
+        depth: usize,
+    ) -> Result<(), String> {
+        let Type::Map(map_typing) = typing else {
+            return Err(format!("expected {typing:?} at `{path}`, found a map"));
+        };
+        let (key_typing, value_typing) = &**map_typing;
+        let format = self.format_of(typing, false, depth)?;
+        self.write_header(1, format.as_data_format())?;
+
+        // Order entries as they would be in a native map.
+        entries.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+        let count =
+            u32::try_from(entries.len()).map_err(|_| format!("too many entries in `{path}`"))?;
+        let key_format = Format::data(0).with(self.format_of(key_typing, false, depth)?);
+        self.write_header(count, key_format.as_data_format())?;
+        for (key, _) in entries.iter() {
+            self.write_value(key, key_typing, false, path, depth + 1)?;
+        }
+
+        let value_format = Format::data(0).with(self.format_of(value_typing, false, depth)?);
+        self.write_header(count, value_format.as_data_format())?;
+        for (key, value) in entries.iter() {
+            let path = format!("{path}[{}]", display_key(key));
+            self.write_value(value, value_typing, false, &path, depth + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `value` as a numeric (or enum) `typing`.
+    fn write_scalar(
+        &mut self,
+        value: &Unspecified,
+        typing: &Type,
+        path: &str,
+    ) -> Result<(), String> {
+        let mismatch = || format!("expected {typing:?} at `{path}`, found {value:?}");
+
+        // Floats accept any number, while
+        // integers only accept integers.
+        let int = as_integer(value);
+        let float = as_float(value);
+        let bytes = &mut self.bytes;
+        let result = match typing {
+            Type::F32 => bytes.write_data(&(float.ok_or_else(mismatch)? as f32)),
+            Type::F64 => bytes.write_data(&float.ok_or_else(mismatch)?),
+            Type::U8 => bytes.write_data(&narrow::<u8>(int).ok_or_else(mismatch)?),
+            Type::U16 => bytes.write_data(&narrow::<u16>(int).ok_or_else(mismatch)?),
+            Type::U32 => bytes.write_data(&narrow::<u32>(int).ok_or_else(mismatch)?),
+            Type::U64 => bytes.write_data(&narrow::<u64>(int).ok_or_else(mismatch)?),
+            Type::I8 => bytes.write_data(&narrow::<i8>(int).ok_or_else(mismatch)?),
+            Type::I16 => bytes.write_data(&narrow::<i16>(int).ok_or_else(mismatch)?),
+            Type::I32 => bytes.write_data(&narrow::<i32>(int).ok_or_else(mismatch)?),
+            Type::I64 => bytes.write_data(&narrow::<i64>(int).ok_or_else(mismatch)?),
+            Type::Timestamp => bytes.write_data(&narrow::<i64>(int).ok_or_else(mismatch)?),
+            Type::U128 => bytes.write_data(&narrow::<u128>(int).ok_or_else(mismatch)?),
+            Type::I128 => bytes.write_data(&int.ok_or_else(mismatch)?),
+            Type::UVar => bytes.write_data(&VarU64(narrow(int).ok_or_else(mismatch)?)),
+            Type::IVar => bytes.write_data(&VarI64(narrow(int).ok_or_else(mismatch)?)),
+
+            Type::Enum(_) => {
+                let variant = match value {
+                    Unspecified::Enum { variant, .. } => Some(*variant),
+                    _ => narrow::<u16>(int),
+                };
+                bytes.write_data(&variant.ok_or_else(mismatch)?)
+            }
+            _ => return Err(mismatch()),
+        };
+
+        result.map_err(|e| format!("{e}"))
+    }
+
+    /// Writes a data header to the encoder.
+    fn write_header(&mut self, count: u32, format: DataFormat) -> Result<(), String> {
+        self.bytes
+            .write_data(&DataHeader { count, format })
+            .map_err(|e| format!("{e}"))
+    }
+}
+
+/// Returns the data type in `coda` with the
+/// same name as `data_type`, or `data_type`
+/// itself if it isn't a fluid reference.
+///
+/// Names are compared ignoring their ASCII case, the
+/// same way the parser (and [`decode_typed`]) resolves
+/// references to data types.
+///
+/// [`decode_typed`]: crate::types::dynamic::decode_typed
+fn resolve<'a>(coda: &'a Coda, data_type: &'a DataType) -> Result<&'a DataType, String> {
+    if !matches!(data_type.format(), Format::Fluid) {
+        return Ok(data_type);
+    }
+
+    coda.data_type_ignore_ascii_case(&data_type.name)
+        .ok_or_else(|| format!("unknown data type `{}`", data_type.name))
+}
+
+/// Returns `value` as an integer, if it is one.
+fn as_integer(value: &Unspecified) -> Option<i128> {
+    match value {
+        Unspecified::U8(v) => Some(*v as i128),
+        Unspecified::U16(v) => Some(*v as i128),
+        Unspecified::U32(v) => Some(*v as i128),
+        Unspecified::U64(v) => Some(*v as i128),
+        Unspecified::I8(v) => Some(*v as i128),
+        Unspecified::I16(v) => Some(*v as i128),
+        Unspecified::I32(v) => Some(*v as i128),
+        Unspecified::I64(v) => Some(*v as i128),
+        Unspecified::U128(v) => i128::try_from(*v).ok(),
+        Unspecified::I128(v) => Some(*v),
+        Unspecified::UVar(v) => Some(v.0 as i128),
+        Unspecified::IVar(v) => Some(v.0 as i128),
+        Unspecified::Timestamp(v) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+/// Returns `int` as a `T`, if it fits.
+fn narrow<T: TryFrom<i128>>(int: Option<i128>) -> Option<T> {
+    int.and_then(|int| T::try_from(int).ok())
+}
+
+/// Returns `value` as a float, if it's any number.
+fn as_float(value: &Unspecified) -> Option<f64> {
+    match value {
+        Unspecified::F32(v) => Some(*v as f64),
+        Unspecified::F64(v) => Some(*v),
+        value => as_integer(value).map(|v| v as f64),
+    }
+}
+
+/// Compares two map keys of the same type.
+fn compare_keys(a: &Unspecified, b: &Unspecified) -> core::cmp::Ordering {
+    match (a, b) {
+        (Unspecified::Text(a), Unspecified::Text(b)) => a.cmp(b),
+        (Unspecified::Bool(a), Unspecified::Bool(b)) => a.cmp(b),
+        (Unspecified::Char(a), Unspecified::Char(b)) => a.cmp(b),
+        (a, b) => match (as_integer(a), as_integer(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => match (as_float(a), as_float(b)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => core::cmp::Ordering::Equal,
+            },
+        },
+    }
+}
+
+/// Formats a map key for a path.
+fn display_key(key: &Unspecified) -> String {
+    match key {
+        Unspecified::Text(v) => format!("{v:?}"),
+        Unspecified::Bool(v) => format!("{v}"),
+        Unspecified::Char(v) => format!("{v:?}"),
+        key => match as_integer(key) {
+            Some(v) => format!("{v}"),
+            None => format!("{key:?}"),
+        },
+    }
+}
+
+/// Error returned when an [`Example`] fails verification.
+#[derive(Debug, Snafu)]
+#[snafu(display("example {} of `{data_type}` failed verification: {reason}", index + 1))]
+pub struct ExampleError {
+    /// Name of the data type containing the example.
+    pub data_type: Text,
+
+    /// Index of the example in [`DataType::examples`].
+    pub index: usize,
+
+    /// Why the example failed verification.
+    pub reason: ExampleFailure,
+}
+
+/// Enumeration of reasons an [`Example`] may fail verification.
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum ExampleFailure {
+    #[snafu(display("the example has no `hex encoded` block"))]
+    MissingEncoding,
+
+    #[snafu(display("the example's JSON is invalid: {message}"))]
+    InvalidJson { message: Text },
+
+    #[snafu(display("the example's encoding isn't valid hexadecimal: {message}"))]
+    InvalidHex { message: Text },
+
+    #[snafu(display("the example's JSON can't be encoded: {message}"))]
+    Unencodable { message: Text },
+
+    #[snafu(display(
+        "expected encoding `{expected}`, but the example encoded to `{actual}` (first difference at byte {offset})"
+    ))]
+    Mismatch {
+        offset: usize,
+        expected: Text,
+        actual: Text,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_CODA_MARKDOWN: &str = r#"
+# `Examples` Coda
+
+## `Point` Data
+
+A point in space.
+
+```json example
+{ "x": 1, "y": -2, "label": "hi", "tags": ["a"] }
+```
+
+```hex encoded
+01000000 0800 02 01
+01000000
+feffffff
+02000000 0100 00 00 6869
+01000000 0000 01 00
+  01000000 0100 00 00 61
+```
+
+```json example
+{ "x": 1, "y": 2, "label": "", "tags": [] }
+```
+
+```hex encoded
+01000000 0800 02 01
+01000000
+03000000
+00000000 0100 00 00
+00000000 0000 01 00
+```
+
++ `x` i32
++ `y` i32
++ `label` text
++ `tags` list of text
+"#;
+
+    #[test]
+    fn parses_examples() {
+        let coda = crate::parse::parse(EXAMPLE_CODA_MARKDOWN).unwrap();
+        let point = coda.iter().next().unwrap();
+        assert_eq!(2, point.examples.len());
+        assert_eq!(
+            r#"{ "x": 1, "y": 2, "label": "", "tags": [] }"#,
+            &*point.examples[1].json
+        );
+        assert_eq!(
+            Some("0100000008000201010000000300000000000000010000000000000000000100"),
+            point.examples[1].hex.as_deref()
+        );
+    }
+
+    #[test]
+    fn verifies_examples() {
+        let coda = crate::parse::parse(EXAMPLE_CODA_MARKDOWN).unwrap();
+
+        // The first example is correct, but the
+        // second example's `y` is intentionally wrong.
+        let errors = verify(&coda).unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!("Point", errors[0].data_type);
+        assert_eq!(1, errors[0].index);
+        assert_eq!(
+            ExampleFailure::Mismatch {
+                offset: 12,
+                expected: "0100000008000201010000000300000000000000010000000000000000000100".into(),
+                actual: "0100000008000201010000000200000000000000010000000000000000000100".into(),
+            },
+            errors[0].reason
+        );
+    }
+
+    #[test]
+    fn verifies_examples_written_as_json() {
+        use crate::{
+            codec::encode_to_vec,
+            types::{
+                dynamic::{to_json, DynamicValue},
+                tests::{NestedTestData, TestData},
+            },
+        };
+
+        let data = TestData {
+            number: -7,
+            floaty: 1.5,
+            text_list: alloc::vec!["one".into(), "two".into()],
+            text: "quote \" and\nnewline".into(),
+            nested: NestedTestData { boolean: true },
+            two_d: alloc::vec![alloc::vec!["a".into()], alloc::vec!["b".into(), "c".into()]],
+        };
+        let mut json = Vec::new();
+        to_json(&data.to_dynamic(), &mut json).unwrap();
+
+        // Any JSON written by `to_json` is a valid example
+        // of the data it was written from.
+        let mut typing = TestData::typing();
+        typing.examples.push(Example {
+            json: String::from_utf8(json).unwrap().into(),
+            hex: Some(hex_from_bytes(&encode_to_vec(&data).unwrap())),
+        });
+        let coda = Coda::new("Tests".into(), "Tests".into(), None, &[typing]);
+        assert_eq!(1, verify(&coda).unwrap());
+    }
+
+    #[test]
+    fn verifies_examples_of_maps() {
+        use crate::codec::encode_to_vec;
+        use alloc::collections::BTreeMap;
+
+        // Maps without text keys are written as
+        // `[key, value]` pairs, in any order.
+        let markdown = r#"
+# `Examples` Coda
+
+## `Scores` Data
+
+```json example
+{ "scores": [[2, "b"], [1, "a"]] }
+```
+
+```hex encoded
+HEX
+```
+
++ `scores` map of u32 to text
+"#;
+        let scores = BTreeMap::from([(1u32, Text::from("a")), (2u32, Text::from("b"))]);
+        let mut expected = Vec::new();
+        expected
+            .write_data(&DataHeader {
+                count: 1,
+                format: Format::data(1)
+                    .with(BTreeMap::<u32, Text>::FORMAT)
+                    .as_data_format(),
+            })
+            .unwrap();
+        expected.extend(encode_to_vec(&scores).unwrap());
+
+        let markdown = markdown.replace("HEX", &hex_from_bytes(&expected));
+        let coda = crate::parse::parse(&markdown).unwrap();
+        assert_eq!(1, verify(&coda).unwrap());
+    }
+}
@@ -7,9 +7,21 @@
 //! the exact APIs are subject to change, and may
 //! not be well-optimized.
 
+#[cfg(any(feature = "langs-golang", test))]
+pub mod golang;
+
+#[cfg(any(feature = "langs-graphql", test))]
+pub mod graphql;
+
+#[cfg(any(feature = "langs-json-schema", test))]
+pub mod json_schema;
+
 #[cfg(any(feature = "langs-open-api", test))]
 pub mod open_api;
 
+#[cfg(any(feature = "langs-protobuf", test))]
+pub mod protobuf;
+
 #[cfg(any(feature = "langs-python", test))]
 pub mod python;
 
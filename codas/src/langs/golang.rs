@@ -0,0 +1,624 @@
+//! Go code generators.
+//!
+//! ## What's Here
+//!
+//! For a given coda, the following code will be
+//! generated:
+//!
+//! - A `struct` for each data type, with `PascalCase`
+//!   fields and `Encode(w io.Writer)`/`Decode(r io.Reader)`
+//!   methods that are compatible with the [`codec`](crate::codec).
+//!
+//! - An `interface` for the coda, implemented by
+//!   (pointers to) each data type's `struct`.
+//!
+//! - A `Decode{Coda}Data(r io.Reader)` function, which
+//!   decodes any of the coda's data types.
+//!
+//! Generated code belongs to the `codas` package, and
+//! depends on the [`RUNTIME`] in a `codas_runtime.go`
+//! file in the same package (see [`generate_runtime`]).
+//!
+//! Go types correspond to coda types as follows:
+//!
+//! - Numbers and booleans become their Go equivalents,
+//!   except for 128-bit numbers, which become their raw,
+//!   little-endian `[16]byte`s.
+//!
+//! - Text becomes `string`.
+//!
+//! - Enums become the `uint16` ordinals of their variants.
+//!
+//! - Lists become slices, encoded with explicit loops.
+//!
+//! - Maps become a `struct { Keys []K; Values []V }`. Like
+//!   Rust's maps, the keys should be unique and sorted.
+//!
+//! - Optional fields become pointers.
+//!
+//! - Unspecified data becomes the `[]byte` of its raw
+//!   encoding (or `nil`, for default data).
+use core::fmt::Write;
+
+use alloc::{format, string::String};
+
+use crate::{
+    codec::Format,
+    stream::{FmtWriter, StreamError, Writes},
+    types::{Coda, Text, Type},
+};
+
+/// Source of the Go runtime used by
+/// codecs generated by [`generate_types`].
+pub const RUNTIME: &str = include_str!("golang/codas_runtime.go");
+
+/// File name of the [`RUNTIME`], which must be written
+/// next to any Go generated by [`generate_types`].
+pub const RUNTIME_FILE_NAME: &str = "codas_runtime.go";
+
+/// Writes the Go [`RUNTIME`] to `stream`.
+pub fn generate_runtime(stream: &mut impl Writes) -> Result<(), StreamError> {
+    stream.write_all(RUNTIME.as_bytes())
+}
+
+/// Generates the Go types for `coda`.
+pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Generate package header.
+    let _ = writeln!(writer, "// Code generated by codas. DO NOT EDIT.\n");
+    let _ = writeln!(writer, "package codas\n");
+    let _ = writeln!(writer, "import \"io\"\n");
+
+    // Generate coda interface.
+    write_golang_doc(&mut writer, 0, coda_type_docs);
+    let _ = writeln!(writer, "type {coda_type_name} interface {{");
+    write_golang_doc(
+        &mut writer,
+        1,
+        "Ordinal returns the ordinal of this data's type in the coda.",
+    );
+    let _ = writeln!(writer, "\tOrdinal() uint16\n");
+    write_golang_doc(
+        &mut writer,
+        1,
+        "Encode encodes this data, with its header, into w.",
+    );
+    let _ = writeln!(writer, "\tEncode(w io.Writer) error");
+    let _ = writeln!(writer, "}}\n");
+
+    // Ensure every data type implements the interface.
+    let _ = writeln!(writer, "var _ {coda_type_name} = (*Unspecified)(nil)");
+    for data_type in coda.iter() {
+        let _ = writeln!(
+            writer,
+            "var _ {coda_type_name} = (*{})(nil)",
+            data_type.name
+        );
+    }
+    let _ = writeln!(writer);
+
+    // Generate coda decoder.
+    write_golang_doc(
+        &mut writer,
+        0,
+        &format!(
+            "Decode{coda_type_name} decodes {coda_type_name} from r.\n\n\
+            Data of an unknown type is decoded as Unspecified."
+        ),
+    );
+    let _ = writeln!(
+        writer,
+        "func Decode{coda_type_name}(r io.Reader) ({coda_type_name}, error) {{"
+    );
+    let _ = writeln!(writer, "\theader, err := ReadHeader(r)");
+    let _ = writeln!(writer, "{}", golang_return_err(1, "nil, "));
+    let _ = writeln!(writer, "\tswitch header.Ordinal {{");
+    for data_type in coda.iter() {
+        let ordinal = data_type.format().as_data_format().ordinal;
+        let _ = writeln!(writer, "\tcase {ordinal}:");
+        let _ = writeln!(writer, "\t\tdata := &{}{{}}", data_type.name);
+        let _ = writeln!(
+            writer,
+            "\t\tif err := data.DecodeWithHeader(r, header); err != nil {{"
+        );
+        let _ = writeln!(writer, "\t\t\treturn nil, err\n\t\t}}");
+        let _ = writeln!(writer, "\t\treturn data, nil");
+    }
+    let _ = writeln!(writer, "\tdefault:");
+    let _ = writeln!(writer, "\t\tdata := &Unspecified{{}}");
+    let _ = writeln!(
+        writer,
+        "\t\tif err := data.DecodeWithHeader(r, header); err != nil {{"
+    );
+    let _ = writeln!(writer, "\t\t\treturn nil, err\n\t\t}}");
+    let _ = writeln!(writer, "\t\treturn data, nil");
+    let _ = writeln!(writer, "\t}}");
+    let _ = writeln!(writer, "}}");
+
+    // Generate data types.
+    for data_type in coda.iter() {
+        let format = data_type.format().as_data_format();
+        let ordinal = format.ordinal;
+        let data_type_name = &data_type.name;
+        let data_type_docs = match &data_type.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+
+        // Declaration and fields.
+        let _ = writeln!(writer);
+        write_golang_doc(&mut writer, 0, data_type_docs);
+        if data_type.iter().count() == 0 {
+            let _ = writeln!(writer, "type {data_type_name} struct{{}}");
+        } else {
+            let _ = writeln!(writer, "type {data_type_name} struct {{");
+            for (i, field) in data_type.iter().enumerate() {
+                let field_docs = match &field.docs {
+                    Some(docs) => docs.trim(),
+                    None => "Undocumented Field. How could you? ;~;",
+                };
+                if i > 0 {
+                    let _ = writeln!(writer);
+                }
+                write_golang_doc(&mut writer, 1, field_docs);
+
+                let field_type = golang_type(&field.typing, 1);
+                let pointer = if field.optional { "*" } else { "" };
+                let _ = writeln!(
+                    writer,
+                    "\t{} {pointer}{field_type}",
+                    golang_name(&field.name)
+                );
+            }
+            let _ = writeln!(writer, "}}");
+        }
+
+        // Ordinal.
+        let _ = writeln!(writer);
+        write_golang_doc(
+            &mut writer,
+            0,
+            &format!("Ordinal returns the ordinal of {data_type_name} in the coda."),
+        );
+        let _ = writeln!(writer, "func (d *{data_type_name}) Ordinal() uint16 {{");
+        let _ = writeln!(writer, "\treturn {ordinal}");
+        let _ = writeln!(writer, "}}");
+
+        // Encoder.
+        let _ = writeln!(writer);
+        write_golang_doc(
+            &mut writer,
+            0,
+            "Encode encodes this data, with its header, into w.",
+        );
+        let _ = writeln!(
+            writer,
+            "func (d *{data_type_name}) Encode(w io.Writer) error {{"
+        );
+        let _ = write!(
+            writer,
+            "{}",
+            golang_try(
+                1,
+                &format!(
+                    "WriteHeader(w, DataHeader{{Count: 1, BlobSize: {}, DataFields: {}, Ordinal: {ordinal}}})",
+                    format.blob_size, format.data_fields
+                )
+            )
+        );
+        for field in data_type.iter() {
+            let value = format!("d.{}", golang_name(&field.name));
+            let encoder = if field.optional {
+                golang_optional_encoder(&field.typing, &value, 0, 1)
+            } else {
+                golang_encoder(&field.typing, &value, 0, 1)
+            };
+            let _ = write!(writer, "{encoder}");
+        }
+        let _ = writeln!(writer, "\treturn nil");
+        let _ = writeln!(writer, "}}");
+
+        // Decoders.
+        let _ = writeln!(writer);
+        write_golang_doc(
+            &mut writer,
+            0,
+            "Decode decodes this data, with its header, from r.",
+        );
+        let _ = writeln!(
+            writer,
+            "func (d *{data_type_name}) Decode(r io.Reader) error {{"
+        );
+        let _ = writeln!(writer, "\theader, err := ReadHeader(r)");
+        let _ = writeln!(writer, "{}", golang_return_err(1, ""));
+        let _ = writeln!(writer, "\treturn d.DecodeWithHeader(r, header)");
+        let _ = writeln!(writer, "}}");
+        let _ = writeln!(writer);
+        write_golang_doc(
+            &mut writer,
+            0,
+            "DecodeWithHeader decodes this data, following its header, from r.",
+        );
+        let _ = writeln!(
+            writer,
+            "func (d *{data_type_name}) DecodeWithHeader(r io.Reader, header DataHeader) error {{"
+        );
+        let _ = write!(
+            writer,
+            "{}",
+            golang_try(1, &format!("EnsureOrdinal(header, {ordinal})"))
+        );
+        for field in data_type.iter() {
+            let target = format!("d.{}", golang_name(&field.name));
+            let decoder = if field.optional {
+                golang_optional_decoder(&field.typing, &target, 0, 1)
+            } else {
+                golang_decoder(&field.typing, &target, 0, 1)
+            };
+            let _ = write!(writer, "{decoder}");
+        }
+        let _ = writeln!(writer, "\treturn nil");
+        let _ = writeln!(writer, "}}");
+    }
+
+    Ok(())
+}
+
+/// Writes a Go doc comment with leading
+/// `indentation` (in tabs) containing `docs` to `writer`.
+fn write_golang_doc<W: Writes>(writer: &mut FmtWriter<'_, W>, indentation: usize, docs: &str) {
+    let tabs = "\t".repeat(indentation);
+    for line in docs.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            let _ = writeln!(writer, "{tabs}//");
+        } else {
+            let _ = writeln!(writer, "{tabs}// {line}");
+        }
+    }
+}
+
+/// Returns `name` (e.g., a `snake_case` field name) in `PascalCase`.
+///
+/// Names starting with a digit are prefixed with `F`,
+/// since Go identifiers can't start with a digit.
+fn golang_name(name: &str) -> String {
+    let mut pascal = String::with_capacity(name.len() + 1);
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        pascal.push('F');
+    }
+    for part in name.split(['_', '-', ' ']) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            pascal.extend(first.to_uppercase());
+            pascal.extend(chars);
+        }
+    }
+    pascal
+}
+
+/// Returns Go statements indented by `indentation` tabs
+/// that return `prefix` and `err` if `err` isn't `nil`.
+fn golang_return_err(indentation: usize, prefix: &str) -> Text {
+    let tabs = "\t".repeat(indentation);
+    format!("{tabs}if err != nil {{\n{tabs}\treturn {prefix}err\n{tabs}}}").into()
+}
+
+/// Returns Go statements indented by `indentation` tabs
+/// that return the error returned by `call`, if any.
+fn golang_try(indentation: usize, call: &str) -> Text {
+    let tabs = "\t".repeat(indentation);
+    format!("{tabs}if err := {call}; err != nil {{\n{tabs}\treturn err\n{tabs}}}\n").into()
+}
+
+/// Returns Go statements indented by `indentation` tabs
+/// that encode `value` of `type` (and its header, if any)
+/// into `w`, using variables suffixed with `depth`.
+fn golang_encoder(typing: &Type, value: &str, depth: usize, indentation: usize) -> Text {
+    let tabs = "\t".repeat(indentation);
+    match typing {
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::I64
+        | Type::U128
+        | Type::I128
+        | Type::F32
+        | Type::F64
+        | Type::Bool
+        | Type::Char
+        | Type::Timestamp
+        | Type::Enum(_) => golang_try(indentation, &format!("WriteBlob(w, {value})")),
+        Type::UVar => golang_try(indentation, &format!("WriteUVar(w, {value})")),
+        Type::IVar => golang_try(indentation, &format!("WriteIVar(w, {value})")),
+        Type::Text => golang_try(indentation, &format!("WriteText(w, {value})")),
+        Type::Bytes => golang_try(indentation, &format!("WriteBytes(w, {value})")),
+        Type::Unspecified => golang_try(indentation, &format!("WriteUnspecified(w, {value})")),
+        Type::Data(_) => golang_try(indentation, &format!("{value}.Encode(w)")),
+        Type::List(typing) => {
+            let format = Format::data(0).with(typing.format()).as_data_format();
+            let item = format!("item{depth}");
+            let header = golang_try(
+                indentation,
+                &format!(
+                    "WriteHeader(w, DataHeader{{Count: uint32(len({value})), BlobSize: {}, DataFields: {}}})",
+                    format.blob_size, format.data_fields
+                ),
+            );
+            let item_encoder = golang_encoder(typing, &item, depth + 1, indentation + 1);
+            format!("{header}{tabs}for _, {item} := range {value} {{\n{item_encoder}{tabs}}}\n")
+                .into()
+        }
+        Type::Map(typing) => {
+            let header = golang_try(
+                indentation,
+                "WriteHeader(w, DataHeader{Count: 1, DataFields: 2})",
+            );
+            let keys = golang_encoder(
+                &Type::List(typing.0.clone().into()),
+                &format!("{value}.Keys"),
+                depth,
+                indentation,
+            );
+            let values = golang_encoder(
+                &Type::List(typing.1.clone().into()),
+                &format!("{value}.Values"),
+                depth,
+                indentation,
+            );
+            format!("{header}{keys}{values}").into()
+        }
+    }
+}
+
+/// Returns Go statements indented by `indentation` tabs
+/// that encode the optional (pointer) `value` of `type`
+/// (and its header) into `w`.
+fn golang_optional_encoder(typing: &Type, value: &str, depth: usize, indentation: usize) -> Text {
+    let tabs = "\t".repeat(indentation);
+    let format = Format::data(0).with(typing.format()).as_data_format();
+    let absent = golang_try(indentation + 1, "WriteHeader(w, DataHeader{})");
+    let present = golang_try(
+        indentation + 1,
+        &format!(
+            "WriteHeader(w, DataHeader{{Count: 1, BlobSize: {}, DataFields: {}}})",
+            format.blob_size, format.data_fields
+        ),
+    );
+    let inner = format!("value{depth}");
+    let encoder = golang_encoder(typing, &inner, depth + 1, indentation + 1);
+    format!(
+        "{tabs}if {value} == nil {{\n{absent}{tabs}}} else {{\n{tabs}\t{inner} := *{value}\n{present}{encoder}{tabs}}}\n"
+    )
+    .into()
+}
+
+/// Returns Go statements indented by `indentation` tabs
+/// that decode data of `type` (and its header, if any)
+/// from `r` into `target`, using variables suffixed with `depth`.
+fn golang_decoder(typing: &Type, target: &str, depth: usize, indentation: usize) -> Text {
+    let tabs = "\t".repeat(indentation);
+    match typing {
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::I64
+        | Type::U128
+        | Type::I128
+        | Type::F32
+        | Type::F64
+        | Type::Bool
+        | Type::Char
+        | Type::Timestamp
+        | Type::Enum(_) => golang_try(indentation, &format!("ReadBlob(r, &{target})")),
+        Type::UVar => golang_try(indentation, &format!("ReadUVar(r, &{target})")),
+        Type::IVar => golang_try(indentation, &format!("ReadIVar(r, &{target})")),
+        Type::Text => golang_try(indentation, &format!("ReadText(r, &{target})")),
+        Type::Bytes => golang_try(indentation, &format!("ReadBytes(r, &{target})")),
+        Type::Unspecified => golang_try(indentation, &format!("ReadUnspecified(r, &{target})")),
+        Type::Data(_) => golang_try(indentation, &format!("{target}.Decode(r)")),
+        Type::List(typing) => {
+            let header = format!("header{depth}");
+            let index = format!("i{depth}");
+            let item = format!("item{depth}");
+            let item_type = golang_type(typing, indentation + 2);
+            let item_decoder = golang_decoder(typing, &item, depth + 1, indentation + 2);
+            let mut decoder = format!("{tabs}{{\n{tabs}\t{header}, err := ReadHeader(r)\n");
+            decoder += &golang_return_err(indentation + 1, "");
+            decoder += "\n";
+            decoder += &golang_try(indentation + 1, &format!("EnsureOrdinal({header}, 0)"));
+            decoder += &format!("{tabs}\t{target} = {target}[:0]\n");
+            decoder += &format!(
+                "{tabs}\tfor {index} := uint32(0); {index} < {header}.Count; {index}++ {{\n"
+            );
+            decoder += &format!("{tabs}\t\tvar {item} {item_type}\n{item_decoder}");
+            decoder += &format!("{tabs}\t\t{target} = append({target}, {item})\n");
+            decoder += &format!("{tabs}\t}}\n{tabs}}}\n");
+            decoder.into()
+        }
+        Type::Map(typing) => {
+            let keys = golang_decoder(
+                &Type::List(typing.0.clone().into()),
+                &format!("{target}.Keys"),
+                depth,
+                indentation,
+            );
+            let values = golang_decoder(
+                &Type::List(typing.1.clone().into()),
+                &format!("{target}.Values"),
+                depth,
+                indentation,
+            );
+            format!(
+                "{tabs}if header, err := ReadHeader(r); err != nil {{\n{tabs}\treturn err\n{tabs}}} else if err := EnsureOrdinal(header, 0); err != nil {{\n{tabs}\treturn err\n{tabs}}}\n{keys}{values}"
+            )
+            .into()
+        }
+    }
+}
+
+/// Returns Go statements indented by `indentation` tabs
+/// that decode optional data of `type` (and its header)
+/// from `r` into the (pointer) `target`.
+fn golang_optional_decoder(typing: &Type, target: &str, depth: usize, indentation: usize) -> Text {
+    let tabs = "\t".repeat(indentation);
+    let header = format!("header{depth}");
+    let inner = format!("value{depth}");
+    let inner_type = golang_type(typing, indentation + 2);
+    let inner_decoder = golang_decoder(typing, &inner, depth + 1, indentation + 2);
+    let mut decoder = format!("{tabs}{{\n{tabs}\t{header}, err := ReadHeader(r)\n");
+    decoder += &golang_return_err(indentation + 1, "");
+    decoder += "\n";
+    decoder += &golang_try(indentation + 1, &format!("EnsureOrdinal({header}, 0)"));
+    decoder += &format!("{tabs}\tif {header}.Count > 0 {{\n");
+    decoder += &format!("{tabs}\t\tvar {inner} {inner_type}\n{inner_decoder}");
+    decoder += &format!("{tabs}\t\t{target} = &{inner}\n");
+    decoder += &format!("{tabs}\t}} else {{\n{tabs}\t\t{target} = nil\n{tabs}\t}}\n{tabs}}}\n");
+    decoder.into()
+}
+
+/// Returns the native Go type of `type`, whose first
+/// line is indented by `indentation` tabs.
+///
+/// If `type` is a [`Type::Data`], the data's name
+/// will be interpreted as a native Go identifier.
+fn golang_type(typing: &Type, indentation: usize) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("[]byte"),
+        Type::U8 => Text::Static("uint8"),
+        Type::U16 => Text::Static("uint16"),
+        Type::U32 => Text::Static("uint32"),
+        Type::U64 => Text::Static("uint64"),
+        Type::I8 => Text::Static("int8"),
+        Type::I16 => Text::Static("int16"),
+        Type::I32 => Text::Static("int32"),
+        Type::I64 => Text::Static("int64"),
+        Type::U128 => Text::Static("[16]byte"),
+        Type::I128 => Text::Static("[16]byte"),
+        Type::F32 => Text::Static("float32"),
+        Type::F64 => Text::Static("float64"),
+        Type::Bool => Text::Static("bool"),
+        Type::Char => Text::Static("rune"),
+        Type::UVar => Text::Static("uint64"),
+        Type::IVar => Text::Static("int64"),
+        Type::Text => Text::Static("string"),
+        Type::Bytes => Text::Static("[]byte"),
+        // Timestamps are milliseconds since the Unix
+        // epoch, as accepted by `time.UnixMilli`.
+        Type::Timestamp => Text::Static("int64"),
+        Type::Enum(_) => Text::Static("uint16"),
+        Type::Data(typing) => typing.name.clone(),
+        Type::List(typing) => format!("[]{}", golang_type(typing, indentation)).into(),
+        Type::Map(typing) => {
+            let tabs = "\t".repeat(indentation);
+            let key_type = golang_type(&typing.0, indentation + 1);
+            let value_type = golang_type(&typing.1, indentation + 1);
+            format!(
+                "struct {{\n{tabs}\tKeys   []{key_type}\n{tabs}\tValues []{value_type}\n{tabs}}}"
+            )
+            .into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::EnumType,
+    };
+
+    #[test]
+    fn generates_codecs() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut golang = vec![];
+        generate_types(&coda, &mut golang).unwrap();
+        let golang = alloc::string::String::from_utf8_lossy(&golang);
+
+        assert!(golang.starts_with(
+            "// Code generated by codas. DO NOT EDIT.\n\npackage codas\n\nimport \"io\"\n"
+        ));
+        assert!(golang.contains("\ntype MyCodaData interface {\n"));
+        assert!(golang.contains("\nvar _ MyCodaData = (*MyDataType)(nil)\n"));
+        assert!(golang.contains(
+            "\n\tcase 2:\n\t\tdata := &MyDataType{}\n\t\tif err := data.DecodeWithHeader(r, header); err != nil {\n"
+        ));
+
+        // Fields are PascalCase, and optional fields are pointers.
+        assert!(golang.contains("\n\tIntegralField int32\n"));
+        assert!(golang.contains("\n\tOptionalField *uint64\n"));
+        assert!(golang.contains("\n\tF3dField [][][]int32\n"));
+        assert!(golang
+            .contains("\n\tMapField struct {\n\t\tKeys   []string\n\t\tValues []int32\n\t}\n"));
+        assert!(golang.contains("\n\tUnspecifiedField []byte\n"));
+
+        // Fields are encoded in order, blob fields first.
+        assert!(golang.contains(
+            "\n\tif err := WriteHeader(w, DataHeader{Count: 1, BlobSize: 4, DataFields: 6, Ordinal: 2}); err != nil {\n\t\treturn err\n\t}\n\tif err := WriteBlob(w, d.IntegralField); err != nil {\n\t\treturn err\n\t}\n\tif err := WriteText(w, d.TextualField); err != nil {\n\t\treturn err\n\t}\n\tif err := d.NestedField.Encode(w); err != nil {\n"
+        ));
+        assert!(golang.contains(
+            "\n\t} else {\n\t\tvalue0 := *d.OptionalField\n\t\tif err := WriteHeader(w, DataHeader{Count: 1, BlobSize: 8, DataFields: 0}); err != nil {\n"
+        ));
+        assert!(golang.contains(
+            "\n\tfor _, item0 := range d.F3dField {\n\t\tif err := WriteHeader(w, DataHeader{Count: uint32(len(item0)), BlobSize: 0, DataFields: 1}); err != nil {\n"
+        ));
+
+        // Fields are decoded in the same order.
+        assert!(golang.contains(
+            "\n\tif err := EnsureOrdinal(header, 2); err != nil {\n\t\treturn err\n\t}\n\tif err := ReadBlob(r, &d.IntegralField); err != nil {\n\t\treturn err\n\t}\n\tif err := ReadText(r, &d.TextualField); err != nil {\n\t\treturn err\n\t}\n\tif err := d.NestedField.Decode(r); err != nil {\n"
+        ));
+        assert!(golang.contains(
+            "\n\t\tfor i0 := uint32(0); i0 < header0.Count; i0++ {\n\t\t\tvar item0 [][]int32\n"
+        ));
+        assert!(golang.contains("\n\t\t\td.OptionalField = &value0\n"));
+        assert!(golang
+            .contains("\n\tif err := ReadUnspecified(r, &d.UnspecifiedField); err != nil {\n"));
+    }
+
+    #[test]
+    fn maps_bytes() {
+        assert_eq!("[]byte", &*golang_type(&Type::Bytes, 0));
+        assert!(golang_encoder(&Type::Bytes, "d.Data", 1, 0).contains("WriteBytes(w, d.Data)"));
+        assert!(golang_decoder(&Type::Bytes, "d.Data", 1, 0).contains("ReadBytes(r, &d.Data)"));
+    }
+
+    #[test]
+    fn maps_timestamps() {
+        assert_eq!("int64", &*golang_type(&Type::Timestamp, 0));
+        assert!(golang_encoder(&Type::Timestamp, "d.At", 1, 0).contains("WriteBlob(w, d.At)"));
+        assert!(golang_decoder(&Type::Timestamp, "d.At", 1, 0).contains("ReadBlob(r, &d.At)"));
+    }
+
+    #[test]
+    fn maps_enums() {
+        let typing = Type::Enum(EnumType::new("UserStatus".into(), &["Active".into()]));
+        assert_eq!("uint16", &*golang_type(&typing, 0));
+        assert!(golang_encoder(&typing, "d.Status", 1, 0).contains("WriteBlob(w, d.Status)"));
+        assert!(golang_decoder(&typing, "d.Status", 1, 0).contains("ReadBlob(r, &d.Status)"));
+    }
+
+    #[test]
+    fn golang_names() {
+        assert_eq!("IntegralField", golang_name("integral_field"));
+        assert_eq!("F3dField", golang_name("3d_field"));
+        assert_eq!("KebabCase", golang_name("kebab-case"));
+    }
+}
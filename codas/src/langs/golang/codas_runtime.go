@@ -0,0 +1,314 @@
+// Package codas contains Go types and codecs generated from codas.
+//
+// This file is the runtime shared by every coda generated into this
+// package, and implements the low-level encoding of the Rust `codas`
+// crate's codec: data are preceded by a little-endian DataHeader,
+// followed by each datum's blob fields and then its data fields.
+package codas
+
+import (
+	"bytes"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"io"
+	"strings"
+	"unicode/utf8"
+)
+
+// DataHeaderSize is the size of an encoded DataHeader, in bytes.
+const DataHeaderSize = 8
+
+// MaxDepth is the maximum depth of nested data that may be skipped.
+const MaxDepth = 64
+
+// ErrDepthLimitExceeded is returned when data is nested too deeply.
+var ErrDepthLimitExceeded = errors.New("nesting depth limit exceeded during decoding")
+
+// DataHeader precedes a sequence of zero or more data
+// encoded with the same format.
+type DataHeader struct {
+	// Count is the number of data in the sequence.
+	Count uint32
+
+	// BlobSize is the size of each datum's blob fields, in bytes.
+	BlobSize uint16
+
+	// DataFields is the number of each datum's data fields.
+	DataFields uint8
+
+	// Ordinal is the ordinal of the data's type.
+	Ordinal uint8
+}
+
+// WriteHeader writes header to w.
+func WriteHeader(w io.Writer, header DataHeader) error {
+	var buf [DataHeaderSize]byte
+	binary.LittleEndian.PutUint32(buf[0:4], header.Count)
+	binary.LittleEndian.PutUint16(buf[4:6], header.BlobSize)
+	buf[6] = header.DataFields
+	buf[7] = header.Ordinal
+	_, err := w.Write(buf[:])
+	return err
+}
+
+// ReadHeader reads a DataHeader from r.
+func ReadHeader(r io.Reader) (DataHeader, error) {
+	var buf [DataHeaderSize]byte
+	if _, err := io.ReadFull(r, buf[:]); err != nil {
+		return DataHeader{}, err
+	}
+	return DataHeader{
+		Count:      binary.LittleEndian.Uint32(buf[0:4]),
+		BlobSize:   binary.LittleEndian.Uint16(buf[4:6]),
+		DataFields: buf[6],
+		Ordinal:    buf[7],
+	}, nil
+}
+
+// EnsureOrdinal returns an error unless header's ordinal is ordinal.
+func EnsureOrdinal(header DataHeader, ordinal uint8) error {
+	if header.Ordinal != ordinal {
+		return fmt.Errorf("unsupported data format (ordinal %d)", header.Ordinal)
+	}
+	return nil
+}
+
+// WriteBlob writes value, which must be a fixed-size
+// number, boolean, or array of bytes, to w.
+func WriteBlob(w io.Writer, value any) error {
+	return binary.Write(w, binary.LittleEndian, value)
+}
+
+// ReadBlob reads into value, which must point to a fixed-size
+// number, boolean, or array of bytes, from r.
+func ReadBlob(r io.Reader, value any) error {
+	return binary.Read(r, binary.LittleEndian, value)
+}
+
+// UVarOrdinal and IVarOrdinal are the ordinals of the headers
+// preceding variable-length unsigned and signed integers.
+const (
+	UVarOrdinal = 238
+	IVarOrdinal = 237
+)
+
+// WriteUVar writes value as a variable-length (LEB128)
+// unsigned integer, with its header, to w.
+func WriteUVar(w io.Writer, value uint64) error {
+	buf := make([]byte, binary.MaxVarintLen64)
+	n := binary.PutUvarint(buf, value)
+	return writeVarInt(w, buf[:n], UVarOrdinal)
+}
+
+// ReadUVar reads a variable-length (LEB128) unsigned
+// integer, with its header, from r into value.
+func ReadUVar(r io.Reader, value *uint64) error {
+	buf, err := readVarInt(r, UVarOrdinal)
+	if err != nil {
+		return err
+	}
+	*value, _ = binary.Uvarint(buf)
+	return nil
+}
+
+// WriteIVar writes value as a ZigZag- and variable-length (LEB128)
+// encoded signed integer, with its header, to w.
+func WriteIVar(w io.Writer, value int64) error {
+	buf := make([]byte, binary.MaxVarintLen64)
+	n := binary.PutVarint(buf, value)
+	return writeVarInt(w, buf[:n], IVarOrdinal)
+}
+
+// ReadIVar reads a ZigZag- and variable-length (LEB128) encoded
+// signed integer, with its header, from r into value.
+func ReadIVar(r io.Reader, value *int64) error {
+	buf, err := readVarInt(r, IVarOrdinal)
+	if err != nil {
+		return err
+	}
+	*value, _ = binary.Varint(buf)
+	return nil
+}
+
+// writeVarInt writes the variable-length integer
+// encoded in buf, with its header, to w.
+func writeVarInt(w io.Writer, buf []byte, ordinal uint8) error {
+	header := DataHeader{Count: uint32(len(buf)), BlobSize: 1, Ordinal: ordinal}
+	if err := WriteHeader(w, header); err != nil {
+		return err
+	}
+	_, err := w.Write(buf)
+	return err
+}
+
+// readVarInt reads the canonical encoding of a variable-length
+// integer, with its header, from r.
+func readVarInt(r io.Reader, ordinal uint8) ([]byte, error) {
+	header, err := ReadHeader(r)
+	if err != nil {
+		return nil, err
+	}
+	if err := EnsureOrdinal(header, ordinal); err != nil {
+		return nil, err
+	}
+	if header.BlobSize != 1 || header.DataFields != 0 ||
+		header.Count == 0 || header.Count > binary.MaxVarintLen64 {
+		return nil, errors.New("expected to decode a variable-length integer")
+	}
+	buf := make([]byte, header.Count)
+	if _, err := io.ReadFull(r, buf); err != nil {
+		return nil, err
+	}
+	if _, n := binary.Uvarint(buf); n != len(buf) || (n > 1 && buf[n-1] == 0) {
+		return nil, errors.New("invalid variable-length integer")
+	}
+	return buf, nil
+}
+
+// WriteText writes text, with its header, to w.
+func WriteText(w io.Writer, text string) error {
+	header := DataHeader{Count: uint32(len(text)), BlobSize: 1}
+	if err := WriteHeader(w, header); err != nil {
+		return err
+	}
+	_, err := io.WriteString(w, text)
+	return err
+}
+
+// ReadText reads text, with its header, from r into text.
+func ReadText(r io.Reader, text *string) error {
+	header, err := ReadHeader(r)
+	if err != nil {
+		return err
+	}
+	if header.BlobSize != 1 || header.DataFields != 0 || header.Ordinal != 0 {
+		return errors.New("expected to decode text")
+	}
+	var builder strings.Builder
+	if _, err := io.CopyN(&builder, r, int64(header.Count)); err != nil {
+		return err
+	}
+	if !utf8.ValidString(builder.String()) {
+		return errors.New("text contained invalid UTF-8")
+	}
+	*text = builder.String()
+	return nil
+}
+
+// WriteBytes writes opaque data, with its header, to w.
+func WriteBytes(w io.Writer, data []byte) error {
+	header := DataHeader{Count: uint32(len(data)), BlobSize: 1}
+	if err := WriteHeader(w, header); err != nil {
+		return err
+	}
+	_, err := w.Write(data)
+	return err
+}
+
+// ReadBytes reads opaque data, with its header, from r into data.
+func ReadBytes(r io.Reader, data *[]byte) error {
+	header, err := ReadHeader(r)
+	if err != nil {
+		return err
+	}
+	if header.BlobSize != 1 || header.DataFields != 0 || header.Ordinal != 0 {
+		return errors.New("expected to decode bytes")
+	}
+	var buf bytes.Buffer
+	if _, err := io.CopyN(&buf, r, int64(header.Count)); err != nil {
+		return err
+	}
+	*data = buf.Bytes()
+	return nil
+}
+
+// WriteUnspecified writes data, which must be empty or the
+// raw encoding of some data (with its header), to w.
+//
+// Empty data is written as default data.
+func WriteUnspecified(w io.Writer, data []byte) error {
+	if len(data) == 0 {
+		return WriteHeader(w, DataHeader{})
+	}
+	_, err := w.Write(data)
+	return err
+}
+
+// ReadUnspecified reads unspecified data, with its header,
+// from r into data as the raw encoding of the data.
+//
+// Default data is read as nil.
+func ReadUnspecified(r io.Reader, data *[]byte) error {
+	header, err := ReadHeader(r)
+	if err != nil {
+		return err
+	}
+	if header == (DataHeader{}) {
+		*data = nil
+		return nil
+	}
+	var buf bytes.Buffer
+	if err := WriteHeader(&buf, header); err != nil {
+		return err
+	}
+	if err := SkipData(io.TeeReader(r, &buf), header); err != nil {
+		return err
+	}
+	*data = buf.Bytes()
+	return nil
+}
+
+// SkipData skips the sequence of data following header in r.
+func SkipData(r io.Reader, header DataHeader) error {
+	return skipData(r, header, 0)
+}
+
+func skipData(r io.Reader, header DataHeader, depth int) error {
+	if depth >= MaxDepth {
+		return ErrDepthLimitExceeded
+	}
+	for i := uint32(0); i < header.Count; i++ {
+		if _, err := io.CopyN(io.Discard, r, int64(header.BlobSize)); err != nil {
+			return err
+		}
+		for j := uint8(0); j < header.DataFields; j++ {
+			field, err := ReadHeader(r)
+			if err != nil {
+				return err
+			}
+			if err := skipData(r, field, depth+1); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
+}
+
+// Unspecified is data of an unspecified type.
+type Unspecified struct{}
+
+// Ordinal returns the ordinal of unspecified data.
+func (d *Unspecified) Ordinal() uint16 {
+	return 0
+}
+
+// Encode encodes unspecified data as default data into w.
+func (d *Unspecified) Encode(w io.Writer) error {
+	return WriteHeader(w, DataHeader{})
+}
+
+// Decode decodes (and skips) unspecified data from r.
+func (d *Unspecified) Decode(r io.Reader) error {
+	header, err := ReadHeader(r)
+	if err != nil {
+		return err
+	}
+	return d.DecodeWithHeader(r, header)
+}
+
+// DecodeWithHeader decodes (and skips) unspecified
+// data following header from r.
+func (d *Unspecified) DecodeWithHeader(r io.Reader, header DataHeader) error {
+	return SkipData(r, header)
+}
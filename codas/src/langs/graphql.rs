@@ -0,0 +1,336 @@
+//! GraphQL schema definition language (SDL) generators.
+//!
+//! ## What's Here
+//!
+//! For a given coda, GraphQL type definitions will
+//! be generated containing:
+//!
+//! - A `type` for each data type, with one field for each
+//!   of the data type's fields, and an `_ordinal: Int!`
+//!   field containing the data type's ordinal. Optional
+//!   fields are nullable; all other fields are non-null.
+//!
+//! - A `union` named `{Coda}Data` of every data type.
+//!
+//! - An `enum` for each enum type, with the same variants.
+//!
+//! GraphQL's built-in scalars can't represent every coda
+//! type: integers of 32 bits or fewer (except `u32`) become
+//! an `Int`, larger integers become a (decimal) `String`,
+//! floats become a `Float`, and characters become a
+//! `String`. Fields with these types are
+//! followed by a comment noting their exact coda type.
+//! Unspecified data becomes a custom `Unspecified` scalar.
+//!
+//! GraphQL has no maps, so maps become a list of
+//! generated `KeyValuePairOf{Key}To{Value}` types.
+//!
+//! > _Note_: The ordinal field is named `_ordinal`
+//! > because names starting with `__` are
+//! > reserved for GraphQL's introspection.
+use core::fmt::Write;
+
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use crate::{
+    stream::{FmtWriter, StreamError, Writes},
+    types::{Coda, Text, Type},
+};
+
+/// Name of the field containing a data type's ordinal.
+const ORDINAL_FIELD_NAME: &str = "_ordinal";
+
+/// Generates the GraphQL type definitions for `coda`.
+pub fn generate_schema(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Key-value pair types for maps, by name,
+    // generated after all data types.
+    let mut pairs = BTreeMap::new();
+
+    // Generate file header.
+    let _ = writeln!(writer, "# Code generated by codas. DO NOT EDIT.\n");
+    write_graphql_doc(&mut writer, 0, "Unspecified data, as its raw encoding.");
+    let _ = writeln!(writer, "scalar Unspecified");
+
+    // Generate coda union.
+    if coda.iter().next().is_some() {
+        let members: Vec<&str> = coda.iter().map(|data_type| &*data_type.name).collect();
+        let _ = writeln!(writer);
+        write_graphql_doc(&mut writer, 0, coda_type_docs);
+        let _ = writeln!(writer, "union {coda_type_name} = {}", members.join(" | "));
+    }
+
+    // Generate data types.
+    for data_type in coda.iter() {
+        let data_type_docs = match &data_type.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+        let ordinal = data_type.format().as_data_format().ordinal;
+
+        let _ = writeln!(writer);
+        write_graphql_doc(&mut writer, 0, data_type_docs);
+        let _ = writeln!(writer, "type {} {{", data_type.name);
+        write_graphql_doc(&mut writer, 2, &format!("Always `{ordinal}`."));
+        let _ = writeln!(writer, "  {ORDINAL_FIELD_NAME}: Int!");
+        for field in data_type.iter() {
+            let field_docs = match &field.docs {
+                Some(docs) => docs.trim(),
+                None => "Undocumented Field. How could you? ;~;",
+            };
+            let _ = writeln!(writer);
+            write_graphql_doc(&mut writer, 2, field_docs);
+
+            let mut field_type = graphql_type(&field.typing, &mut pairs);
+            if field.optional {
+                field_type = field_type.trim_end_matches('!').into();
+            }
+            let _ = write!(
+                writer,
+                "  {}: {field_type}",
+                graphql_field_name(&field.name)
+            );
+            if !is_exact(&field.typing) {
+                let _ = write!(writer, " # {}", field.typing.description());
+            }
+            let _ = writeln!(writer);
+        }
+        let _ = writeln!(writer, "}}");
+    }
+
+    // Generate enums.
+    for typing in coda.enums() {
+        let _ = writeln!(writer);
+        write_graphql_doc(&mut writer, 0, &format!("Variants of `{}`.", typing.name));
+        let _ = writeln!(writer, "enum {} {{", typing.name);
+        for variant in typing.variants.iter() {
+            let _ = writeln!(writer, "  {variant}");
+        }
+        let _ = writeln!(writer, "}}");
+    }
+
+    // Generate key-value pairs.
+    for (name, fields) in pairs {
+        let _ = writeln!(writer);
+        let _ = writeln!(writer, "type {name} {{");
+        let _ = write!(writer, "{fields}");
+        let _ = writeln!(writer, "}}");
+    }
+
+    Ok(())
+}
+
+/// Writes a GraphQL block string description with
+/// leading `indentation` (in spaces) containing `docs` to `writer`.
+fn write_graphql_doc<W: Writes>(writer: &mut FmtWriter<'_, W>, indentation: usize, docs: &str) {
+    let spaces = " ".repeat(indentation);
+    let _ = writeln!(writer, "{spaces}\"\"\"");
+    for line in docs.lines() {
+        let line = line.trim_end().replace("\"\"\"", "\\\"\"\"");
+        if line.is_empty() {
+            let _ = writeln!(writer);
+        } else {
+            let _ = writeln!(writer, "{spaces}{line}");
+        }
+    }
+    let _ = writeln!(writer, "{spaces}\"\"\"");
+}
+
+/// Returns `name` as a GraphQL field name.
+///
+/// Names starting with a digit are prefixed with `f_`,
+/// since GraphQL names can't start with a digit.
+fn graphql_field_name(name: &str) -> Text {
+    let name = name.replace('-', "_");
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("f_{name}").into()
+    } else {
+        name.into()
+    }
+}
+
+/// Returns the non-null GraphQL type of `type`, adding
+/// any key-value pair types it requires to `pairs`.
+///
+/// If `type` is a [`Type::Data`], the data's name
+/// will be interpreted as a GraphQL type name.
+fn graphql_type(typing: &Type, pairs: &mut BTreeMap<Text, Text>) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("Unspecified!"),
+        Type::U8 | Type::U16 | Type::I8 | Type::I16 | Type::I32 => Text::Static("Int!"),
+        Type::U32
+        | Type::U64
+        | Type::I64
+        | Type::U128
+        | Type::I128
+        | Type::UVar
+        | Type::IVar
+        | Type::Timestamp => Text::Static("String!"),
+        Type::F32 | Type::F64 => Text::Static("Float!"),
+        Type::Bool => Text::Static("Boolean!"),
+        Type::Text | Type::Char => Text::Static("String!"),
+        // Bytes are Base64-encoded, as in JSON.
+        Type::Bytes => Text::Static("String!"),
+        Type::Data(typing) => format!("{}!", typing.name).into(),
+        Type::Enum(typing) => format!("{}!", typing.name).into(),
+        Type::List(typing) => format!("[{}]!", graphql_type(typing, pairs)).into(),
+        Type::Map(typing) => {
+            let name: Text = format!(
+                "KeyValuePairOf{}To{}",
+                graphql_pair_name(&typing.0),
+                graphql_pair_name(&typing.1)
+            )
+            .into();
+            if !pairs.contains_key(&name) {
+                let key_type = graphql_type(&typing.0, pairs);
+                let value_type = graphql_type(&typing.1, pairs);
+                let mut fields = String::new();
+                let _ = write!(fields, "  key: {key_type}");
+                if !is_exact(&typing.0) {
+                    let _ = write!(fields, " # {}", typing.0.description());
+                }
+                let _ = write!(fields, "\n  value: {value_type}");
+                if !is_exact(&typing.1) {
+                    let _ = write!(fields, " # {}", typing.1.description());
+                }
+                fields.push('\n');
+                pairs.insert(name.clone(), fields.into());
+            }
+            format!("[{name}!]!").into()
+        }
+    }
+}
+
+/// Returns the name of `type` within the
+/// names of generated key-value pair types.
+fn graphql_pair_name(typing: &Type) -> Text {
+    match typing {
+        Type::Data(typing) => typing.name.clone(),
+        Type::Enum(typing) => typing.name.clone(),
+        Type::List(typing) => format!("ListOf{}", graphql_pair_name(typing)).into(),
+        Type::Map(typing) => format!(
+            "MapOf{}To{}",
+            graphql_pair_name(&typing.0),
+            graphql_pair_name(&typing.1)
+        )
+        .into(),
+        typing => format!("{typing:?}").into(),
+    }
+}
+
+/// Returns true iff `type` is exactly represented
+/// by its GraphQL type, without any loss of
+/// precision or range constraints.
+fn is_exact(typing: &Type) -> bool {
+    match typing {
+        Type::Unspecified
+        | Type::I32
+        | Type::F64
+        | Type::Bool
+        | Type::Text
+        | Type::Data(_)
+        | Type::Enum(_) => true,
+        Type::List(typing) => is_exact(typing),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::EnumType,
+    };
+
+    #[test]
+    fn generates_schema() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut schema = vec![];
+        generate_schema(&coda, &mut schema).unwrap();
+        let schema = alloc::string::String::from_utf8_lossy(&schema);
+
+        assert!(schema.starts_with("# Code generated by codas. DO NOT EDIT.\n"));
+        assert!(schema.contains("\nscalar Unspecified\n"));
+        assert!(schema.contains(
+            "\n\"\"\"\nAn example Markdown Coda.\n\"\"\"\nunion MyCodaData = MyNestedDataType | MyDataType\n"
+        ));
+
+        // Data types have their ordinals and fields.
+        assert!(schema.contains(
+            "\ntype MyDataType {\n  \"\"\"\n  Always `2`.\n  \"\"\"\n  _ordinal: Int!\n"
+        ));
+        assert!(schema.contains("\n  floaty_field: Float! # f32\n"));
+        assert!(schema.contains("\n  listy_field: [String!]!\n"));
+        assert!(schema.contains("\n  integral_field: Int!\n"));
+        assert!(schema.contains("\n  textual_field: String!\n"));
+        assert!(schema.contains("\n  nested_field: MyNestedDataType!\n"));
+        assert!(schema.contains("\n  optional_field: String # u64\n"));
+        assert!(schema.contains("\n  f_3d_field: [[[Int!]!]!]!\n"));
+        assert!(
+            schema.contains("\n  map_field: [KeyValuePairOfTextToI32!]! # map of text to i32\n")
+        );
+        assert!(schema.contains("\n  unspecified_field: Unspecified!\n"));
+
+        // Maps are lists of key-value pairs.
+        assert!(
+            schema.contains("\ntype KeyValuePairOfTextToI32 {\n  key: String!\n  value: Int!\n}\n")
+        );
+    }
+
+    #[test]
+    fn generates_enums() {
+        let coda = parse(
+            "# `Accounts` Coda\n\n## `User` Data\n\n+ `status` one of [Active, Deleted]\n+ `role` optional one of [Admin]\n",
+        )
+        .unwrap();
+        let mut schema = vec![];
+        generate_schema(&coda, &mut schema).unwrap();
+        let schema = alloc::string::String::from_utf8_lossy(&schema);
+
+        assert!(schema.contains("\n  status: UserStatus!\n"));
+        assert!(schema.contains("\n  role: UserRole\n"));
+        assert!(schema.contains("\nenum UserStatus {\n  Active\n  Deleted\n}\n"));
+    }
+
+    #[test]
+    fn approximates_types() {
+        let mut pairs = BTreeMap::new();
+        assert_eq!("Int!", &*graphql_type(&Type::U16, &mut pairs));
+        assert_eq!("String!", &*graphql_type(&Type::U32, &mut pairs));
+        assert_eq!("String!", &*graphql_type(&Type::I128, &mut pairs));
+        assert_eq!("String!", &*graphql_type(&Type::Char, &mut pairs));
+        assert!(!is_exact(&Type::Char));
+        assert_eq!("String!", &*graphql_type(&Type::Bytes, &mut pairs));
+        assert!(!is_exact(&Type::Bytes));
+        assert_eq!("String!", &*graphql_type(&Type::Timestamp, &mut pairs));
+        assert!(!is_exact(&Type::Timestamp));
+        assert_eq!(
+            "Status!",
+            &*graphql_type(
+                &Type::Enum(EnumType::new("Status".into(), &["On".into()])),
+                &mut pairs
+            )
+        );
+        assert_eq!(
+            "[KeyValuePairOfU8ToListOfF32!]!",
+            &*graphql_type(
+                &Type::Map((Type::U8, Type::List(Type::F32.into())).into()),
+                &mut pairs
+            )
+        );
+        assert_eq!(
+            "  key: Int! # u8\n  value: [Float!]! # list of f32\n",
+            &*pairs["KeyValuePairOfU8ToListOfF32"]
+        );
+    }
+}
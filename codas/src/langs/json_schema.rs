@@ -0,0 +1,444 @@
+//! JSON Schema generators.
+//!
+//! ## What's Here
+//!
+//! For a given coda, a [JSON Schema 2020-12](https://json-schema.org/draft/2020-12/schema)
+//! document will be generated containing:
+//!
+//! - A schema in `$defs` for each data type, describing
+//!   an object with a property for each of the data
+//!   type's fields. Optional fields may be `null`,
+//!   and aren't `required`.
+//!
+//! - A top-level `oneOf` any of the data types, each wrapped
+//!   in an object with a single property named after the
+//!   data type (e.g., `{ "MyDataType": { ... } }`).
+//!
+//! Schemas describe the JSON representation of data
+//! produced by the `serde` implementations of Rust
+//! types generated by [`rust`](super::rust):
+//!
+//! - Numbers have `minimum` and `maximum` constraints
+//!   matching their type.
+//!
+//! - Maps are objects, with each key as a property name.
+//!
+//! - Enums are strings, restricted to their variants' names.
+//!
+//! - Unspecified data may be any JSON value.
+use core::fmt::Write;
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use crate::{
+    stream::{FmtWriter, StreamError, Writes},
+    types::{Coda, DataType, Text, Type},
+};
+
+/// URI of the JSON Schema dialect used by [`generate_schema`].
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Path of the schemas referenced by [`generate_schema`].
+const DEFS_PATH: &str = "#/$defs/";
+
+/// Number of spaces used for indenting
+/// each level of a JSON document.
+const JSON_INDENTATION_STEP: usize = 2;
+
+/// Generates the JSON Schema for `coda`.
+pub fn generate_schema(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    // Generate coda schema, which is one of each data type.
+    let mut variants = vec![];
+    let mut defs = vec![];
+    for data_type in coda.iter_all() {
+        let data_type_name = data_type.name.clone();
+        variants.push(Json::Object(vec![
+            ("type".into(), Json::string("object")),
+            (
+                "properties".into(),
+                Json::Object(vec![(
+                    data_type_name.clone(),
+                    Json::reference(&data_type_name),
+                )]),
+            ),
+            (
+                "required".into(),
+                Json::Array(vec![Json::String(data_type_name.clone())]),
+            ),
+            ("additionalProperties".into(), Json::Bool(false)),
+        ]));
+        defs.push((data_type_name, data_type_schema(data_type)));
+    }
+
+    let schema = Json::Object(vec![
+        ("$schema".into(), Json::string(JSON_SCHEMA_DIALECT)),
+        ("title".into(), Json::String(coda_type_name.into())),
+        ("description".into(), Json::string(coda_type_docs)),
+        ("oneOf".into(), Json::Array(variants)),
+        ("$defs".into(), Json::Object(defs)),
+    ]);
+
+    let mut writer = FmtWriter::from(stream);
+    schema.write(&mut writer, 0);
+    let _ = writeln!(writer);
+
+    Ok(())
+}
+
+/// Returns the schema of `data_type`.
+fn data_type_schema(data_type: &DataType) -> Json {
+    let data_type_docs = match &data_type.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Data. How could you? ;~;",
+    };
+
+    // Unspecified data may be anything.
+    if data_type.format().as_data_format().ordinal == 0 {
+        return Json::Object(vec![("description".into(), Json::string(data_type_docs))]);
+    }
+
+    let mut properties = vec![];
+    let mut required = vec![];
+    let mut flattened = vec![];
+    for field in data_type.iter() {
+        let field_docs = match &field.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Field. How could you? ;~;",
+        };
+
+        // Flattened fields' properties are
+        // inlined into the data's properties.
+        if field.flattened {
+            flattened.push(json_type(&field.typing));
+            continue;
+        }
+
+        let mut schema = match json_type(&field.typing) {
+            Json::Object(schema) => schema,
+            schema => vec![("allOf".into(), Json::Array(vec![schema]))],
+        };
+        if field.optional {
+            schema = vec![(
+                "anyOf".into(),
+                Json::Array(vec![
+                    Json::Object(schema),
+                    Json::Object(vec![("type".into(), Json::string("null"))]),
+                ]),
+            )];
+        } else {
+            required.push(Json::String(field.name.clone()));
+        }
+        schema.insert(0, ("description".into(), Json::string(field_docs)));
+
+        properties.push((field.name.clone(), Json::Object(schema)));
+    }
+
+    let mut schema = vec![
+        ("type".into(), Json::string("object")),
+        ("description".into(), Json::string(data_type_docs)),
+        ("properties".into(), Json::Object(properties)),
+        ("required".into(), Json::Array(required)),
+    ];
+    if !flattened.is_empty() {
+        schema.push(("allOf".into(), Json::Array(flattened)));
+    }
+    schema.push(("unevaluatedProperties".into(), Json::Bool(false)));
+
+    Json::Object(schema)
+}
+
+/// Returns the schema of `type`.
+///
+/// If `type` is a [`Type::Data`], the schema will
+/// reference the data's schema in `$defs`.
+fn json_type(typing: &Type) -> Json {
+    match typing {
+        Type::Unspecified => Json::Object(vec![]),
+        Type::U8 => json_integer("0", "255"),
+        Type::U16 => json_integer("0", "65535"),
+        Type::U32 => json_integer("0", "4294967295"),
+        Type::U64 | Type::UVar => json_integer("0", "18446744073709551615"),
+        Type::I8 => json_integer("-128", "127"),
+        Type::I16 => json_integer("-32768", "32767"),
+        Type::I32 => json_integer("-2147483648", "2147483647"),
+        Type::I64 | Type::IVar | Type::Timestamp => {
+            json_integer("-9223372036854775808", "9223372036854775807")
+        }
+        Type::U128 => json_integer("0", "340282366920938463463374607431768211455"),
+        Type::I128 => json_integer(
+            "-170141183460469231731687303715884105728",
+            "170141183460469231731687303715884105727",
+        ),
+        Type::F32 => Json::Object(vec![
+            ("type".into(), Json::string("number")),
+            ("minimum".into(), Json::number("-3.4028235e38")),
+            ("maximum".into(), Json::number("3.4028235e38")),
+        ]),
+        Type::F64 => Json::Object(vec![("type".into(), Json::string("number"))]),
+        Type::Bool => Json::Object(vec![("type".into(), Json::string("boolean"))]),
+        Type::Text => Json::Object(vec![("type".into(), Json::string("string"))]),
+        Type::Char => Json::Object(vec![
+            ("type".into(), Json::string("string")),
+            ("minLength".into(), Json::number("1")),
+            ("maxLength".into(), Json::number("1")),
+        ]),
+        Type::Bytes => Json::Object(vec![
+            ("type".into(), Json::string("string")),
+            ("contentEncoding".into(), Json::string("base64")),
+        ]),
+        Type::Data(typing) => Json::reference(&typing.name),
+        Type::Enum(typing) => Json::Object(vec![
+            ("type".into(), Json::string("string")),
+            (
+                "enum".into(),
+                Json::Array(typing.variants.iter().cloned().map(Json::String).collect()),
+            ),
+        ]),
+        Type::List(typing) => Json::Object(vec![
+            ("type".into(), Json::string("array")),
+            ("items".into(), json_type(typing)),
+        ]),
+        Type::Map(typing) => Json::Object(vec![
+            ("type".into(), Json::string("object")),
+            ("additionalProperties".into(), json_type(&typing.1)),
+        ]),
+    }
+}
+
+/// Returns the schema of an integer
+/// between `minimum` and `maximum`.
+fn json_integer(minimum: &'static str, maximum: &'static str) -> Json {
+    Json::Object(vec![
+        ("type".into(), Json::string("integer")),
+        ("minimum".into(), Json::number(minimum)),
+        ("maximum".into(), Json::number(maximum)),
+    ])
+}
+
+/// A JSON value within a generated schema.
+enum Json {
+    Bool(bool),
+
+    /// A number, as its JSON representation.
+    Number(Text),
+
+    String(Text),
+
+    Array(Vec<Json>),
+
+    /// An object's properties, in order.
+    Object(Vec<(Text, Json)>),
+}
+
+impl Json {
+    /// Returns a new [`Json::String`] containing `string`.
+    fn string(string: &str) -> Self {
+        Self::String(String::from(string).into())
+    }
+
+    /// Returns a new [`Json::Number`] represented by `number`.
+    fn number(number: &'static str) -> Self {
+        Self::Number(Text::Static(number))
+    }
+
+    /// Returns a new reference to the schema in `$defs` named `name`.
+    fn reference(name: &str) -> Self {
+        Self::Object(vec![(
+            "$ref".into(),
+            Self::String(format!("{DEFS_PATH}{name}").into()),
+        )])
+    }
+
+    /// Writes the pretty-printed JSON representation of this
+    /// value to `writer`, with nested values indented
+    /// relative to `indentation`.
+    fn write<W: Writes>(&self, writer: &mut FmtWriter<'_, W>, indentation: usize) {
+        let nested = " ".repeat(indentation + JSON_INDENTATION_STEP);
+        let closing = " ".repeat(indentation);
+        match self {
+            Json::Bool(value) => {
+                let _ = write!(writer, "{value}");
+            }
+            Json::Number(value) => {
+                let _ = write!(writer, "{value}");
+            }
+            Json::String(value) => write_json_string(writer, value),
+            Json::Array(values) if values.is_empty() => {
+                let _ = write!(writer, "[]");
+            }
+            Json::Array(values) => {
+                let _ = writeln!(writer, "[");
+                for (i, value) in values.iter().enumerate() {
+                    let _ = write!(writer, "{nested}");
+                    value.write(writer, indentation + JSON_INDENTATION_STEP);
+                    let separator = if i + 1 < values.len() { "," } else { "" };
+                    let _ = writeln!(writer, "{separator}");
+                }
+                let _ = write!(writer, "{closing}]");
+            }
+            Json::Object(properties) if properties.is_empty() => {
+                let _ = write!(writer, "{{}}");
+            }
+            Json::Object(properties) => {
+                let _ = writeln!(writer, "{{");
+                for (i, (name, value)) in properties.iter().enumerate() {
+                    let _ = write!(writer, "{nested}");
+                    write_json_string(writer, name);
+                    let _ = write!(writer, ": ");
+                    value.write(writer, indentation + JSON_INDENTATION_STEP);
+                    let separator = if i + 1 < properties.len() { "," } else { "" };
+                    let _ = writeln!(writer, "{separator}");
+                }
+                let _ = write!(writer, "{closing}}}");
+            }
+        }
+    }
+}
+
+/// Writes `value` to `writer` as a quoted and escaped JSON string.
+fn write_json_string<W: Writes>(writer: &mut FmtWriter<'_, W>, value: &str) {
+    let _ = write!(writer, "\"");
+    for c in value.chars() {
+        let _ = match c {
+            '"' => write!(writer, "\\\""),
+            '\\' => write!(writer, "\\\\"),
+            '\n' => write!(writer, "\\n"),
+            '\r' => write!(writer, "\\r"),
+            '\t' => write!(writer, "\\t"),
+            c if c.is_control() => write!(writer, "\\u{:04x}", c as u32),
+            c => write!(writer, "{c}"),
+        };
+    }
+    let _ = write!(writer, "\"");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::EnumType,
+    };
+
+    #[test]
+    fn generates_schema() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut schema = vec![];
+        generate_schema(&coda, &mut schema).unwrap();
+        let schema = alloc::string::String::from_utf8_lossy(&schema);
+
+        assert!(schema.starts_with(
+            "{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \"title\": \"MyCodaData\",\n  \"description\": \"An example Markdown Coda.\",\n  \"oneOf\": [\n"
+        ));
+        assert!(schema.contains(
+            "\n      \"properties\": {\n        \"MyDataType\": {\n          \"$ref\": \"#/$defs/MyDataType\"\n        }\n      },\n      \"required\": [\n        \"MyDataType\"\n      ],\n"
+        ));
+        assert!(schema.contains(
+            "\n        \"integral_field\": {\n          \"description\": \"A 32-bit signed integer field.\",\n          \"type\": \"integer\",\n          \"minimum\": -2147483648,\n          \"maximum\": 2147483647\n        },\n"
+        ));
+        assert!(schema.contains(
+            "\n        \"map_field\": {\n          \"description\": \"A field containing a map of text to numbers.\",\n          \"type\": \"object\",\n          \"additionalProperties\": {\n"
+        ));
+
+        // Optional fields may be null, and aren't required.
+        assert!(schema.contains(
+            "\n          \"anyOf\": [\n            {\n              \"type\": \"integer\",\n              \"minimum\": 0,\n              \"maximum\": 18446744073709551615\n            },\n            {\n              \"type\": \"null\"\n            }\n          ]\n"
+        ));
+        assert!(!schema.contains("\"optional_field\",\n"));
+        assert!(!schema.contains("\"optional_field\"\n"));
+    }
+
+    #[test]
+    fn encodes_bytes_as_base64() {
+        let mut schema = vec![];
+        json_type(&Type::Bytes).write(&mut FmtWriter::from(&mut schema), 0);
+        assert_eq!(
+            "{\n  \"type\": \"string\",\n  \"contentEncoding\": \"base64\"\n}",
+            alloc::string::String::from_utf8_lossy(&schema)
+        );
+    }
+
+    #[test]
+    fn encodes_timestamps_as_integers() {
+        let mut schema = vec![];
+        json_type(&Type::Timestamp).write(&mut FmtWriter::from(&mut schema), 0);
+        let mut expected = vec![];
+        json_type(&Type::I64).write(&mut FmtWriter::from(&mut expected), 0);
+        assert_eq!(expected, schema);
+    }
+
+    #[test]
+    fn encodes_enums_as_variant_names() {
+        let mut schema = vec![];
+        json_type(&Type::Enum(EnumType::new(
+            "Status".into(),
+            &["Active".into(), "Deleted".into()],
+        )))
+        .write(&mut FmtWriter::from(&mut schema), 0);
+        assert_eq!(
+            "{\n  \"type\": \"string\",\n  \"enum\": [\n    \"Active\",\n    \"Deleted\"\n  ]\n}",
+            alloc::string::String::from_utf8_lossy(&schema)
+        );
+    }
+
+    #[test]
+    fn generates_valid_schema() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut schema = vec![];
+        generate_schema(&coda, &mut schema).unwrap();
+        let schema: serde_json::Value = serde_json::from_slice(&schema).unwrap();
+
+        // The schema is valid JSON Schema.
+        let meta_schema = jsonschema::draft202012::new(&serde_json::json!({
+            "$ref": "https://json-schema.org/draft/2020-12/schema"
+        }))
+        .unwrap();
+        assert!(
+            meta_schema.is_valid(&schema),
+            "{:?}",
+            meta_schema.validate(&schema).map_err(|e| e.to_string())
+        );
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        // The schema accepts valid data...
+        let mut data = serde_json::json!({
+            "MyDataType": {
+                "integral_field": -7,
+                "textual_field": "text",
+                "nested_field": {
+                    "floaty_field": 1.5,
+                    "listy_field": ["a", "b"]
+                },
+                "optional_field": null,
+                "3d_field": [[[1, 2], [3]]],
+                "map_field": { "key": 9 },
+                "unspecified_field": { "anything": [true] }
+            }
+        });
+        assert!(validator.is_valid(&data));
+        assert!(validator.is_valid(&serde_json::json!({ "Unspecified": 42 })));
+
+        // ...and rejects out-of-range numbers,
+        // missing fields, and unknown fields.
+        data["MyDataType"]["integral_field"] = serde_json::json!(1u64 << 40);
+        assert!(!validator.is_valid(&data));
+        data["MyDataType"]["integral_field"] = serde_json::json!(7);
+        data["MyDataType"]
+            .as_object_mut()
+            .unwrap()
+            .remove("textual_field");
+        assert!(!validator.is_valid(&data));
+        data["MyDataType"]["textual_field"] = serde_json::json!("text");
+        data["MyDataType"]["unknown_field"] = serde_json::json!(true);
+        assert!(!validator.is_valid(&data));
+        assert!(!validator.is_valid(&serde_json::json!({ "NotAType": {} })));
+    }
+}
@@ -13,22 +13,75 @@
 //!
 //! Generated schemas can be validated manually via
 //! [Swagger's OpenAPI editor](https://editor.swagger.io).
+//!
+//! ## OpenAPI 3.1
+//!
+//! [`generate_spec`] generates OpenAPI 3.0.3, whose schemas
+//! are a subset of JSON Schema. [`generate_spec_v31`]
+//! generates OpenAPI 3.1.0, whose schemas are full
+//! [JSON Schema 2020-12](https://json-schema.org/draft/2020-12/schema):
+//!
+//! - The coda's schema is the only schema in `components.schemas`,
+//!   and declares each data type's schema in its `$defs`.
+//!
+//! - Each data type's schema has an `__ordinal` property with
+//!   a `const` value, which the coda's schema uses as the
+//!   `discriminator` between data types.
+//!
+//! - Data type schemas disallow unknown properties
+//!   via `unevaluatedProperties: false`.
+//!
+//! ## Enums
+//!
+//! Enums are described as `string`s with an `enum:` list
+//! of their variants' names, matching the JSON representation
+//! of [dynamic values](crate::types::dynamic).
+//!
+//! ## Defaults
+//!
+//! Fields with a declared default value are described
+//! with a `default:` key, except for timestamp fields
+//! (whose defaults are milliseconds since the Unix epoch,
+//! not date-times; refer to the section on timestamps).
+//!
+//! ## Deprecations
+//!
+//! Deprecated data types and fields are described with
+//! `deprecated: true`, except for OpenAPI 3.0 fields that
+//! reference other data types (since siblings of a `$ref`
+//! are ignored in OpenAPI 3.0).
+//!
+//! ## Timestamps
+//!
+//! Timestamps are described as `string`s with the
+//! `date-time` format (i.e., RFC 3339 date-times like
+//! `2024-01-31T12:00:00.000Z`). Since timestamps are
+//! _encoded_ as milliseconds since the Unix epoch, APIs
+//! serving these schemas must convert timestamps to (and
+//! from) date-times, with millisecond precision, in UTC.
 use core::fmt::Write;
 
 use alloc::boxed::Box;
 use alloc::format;
+use alloc::vec::Vec;
 
 use indoc::writedoc;
 
 use crate::{
     stream::{FmtWriter, StreamError, Writes},
-    types::{Coda, Text, Type, Unspecified},
+    types::{Coda, DataField, Text, Type, Unspecified},
 };
 
 /// Number of spaces used for indenting
 /// each level of a YAML document.
 const YAML_INDENTATION_STEP: usize = 2;
 
+/// Path of the schemas referenced by [`generate_spec`].
+const SCHEMAS_PATH: &str = "#/components/schemas/";
+
+/// URI of the JSON Schema dialect used by [`generate_spec_v31`].
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
 /// Generates the OpenAPI spec for `coda`.
 pub fn generate_spec(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
     // Extract coda metadata.
@@ -69,7 +122,7 @@ pub fn generate_spec(coda: &Coda, stream: &mut impl Writes) -> Result<(), Stream
     );
 
     // Generate data type schemas.
-    for data_type in [Unspecified::DATA_TYPE].iter().chain(coda.iter()) {
+    for data_type in coda.iter_all() {
         let data_type_name = &data_type.name;
         let data_type_docs = match &data_type.docs {
             Some(docs) => docs.trim(),
@@ -94,6 +147,9 @@ pub fn generate_spec(coda: &Coda, stream: &mut impl Writes) -> Result<(), Stream
             write_indentation(&mut writer, 8)?;
             let _ = writeln!(writer, "{line}");
         }
+        if data_type.deprecated {
+            let _ = writeln!(writer, "      deprecated: true");
+        }
 
         // Generate type fields.
         if data_type.iter().count() > 0 {
@@ -122,21 +178,159 @@ pub fn generate_spec(coda: &Coda, stream: &mut impl Writes) -> Result<(), Stream
             }
 
             // Generate field typing.
-            field_type.write_yaml(&mut writer, 10)?;
+            let is_reference = matches!(field_type, OpenApiTypeIdentifier::ObjectReference(..));
+            field_type.write_yaml(&mut writer, 10, SCHEMAS_PATH)?;
+            if let Some(default) = open_api_default(field) {
+                let _ = writeln!(writer, "          default: {default}");
+            }
+            if field.deprecated && !is_reference {
+                let _ = writeln!(writer, "          deprecated: true");
+            }
         }
     }
 
     // Generate coda schema.
     let _ = writeln!(writer, "    {coda_type_name}:");
     let _ = writeln!(writer, "      oneOf:");
-    for data_type in [Unspecified::DATA_TYPE].iter().chain(coda.iter()) {
+    for data_type in coda.iter_all() {
         let data_type_name = &data_type.name;
+        let _ = writeln!(writer, "        - $ref: '{SCHEMAS_PATH}{data_type_name}'");
+    }
+
+    Ok(())
+}
+
+/// Generates the OpenAPI 3.1 spec for `coda`.
+pub fn generate_spec_v31(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+    let defs_path = format!("{SCHEMAS_PATH}{coda_type_name}/$defs/");
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Generate info header.
+    let _ = writedoc!(
+        writer,
+        r#"
+    openapi: 3.1.0
+    jsonSchemaDialect: {JSON_SCHEMA_DIALECT}
+    info:
+      title: {coda_type_name}
+      version: 0.0.1
+      description: |-
+    "#
+    );
+
+    // Generate top-level docs.
+    for line in coda_type_docs.lines() {
+        write_indentation(&mut writer, 4)?;
+        let _ = writeln!(writer, "{line}");
+    }
+
+    // Generate coda schema.
+    let _ = writedoc!(
+        writer,
+        r#"
+    paths: {{}}
+    components:
+      schemas:
+        {coda_type_name}:
+          $schema: {JSON_SCHEMA_DIALECT}
+          description: |-
+    "#
+    );
+    for line in coda_type_docs.lines() {
+        write_indentation(&mut writer, 8)?;
+        let _ = writeln!(writer, "{line}");
+    }
+    let _ = writeln!(writer, "      oneOf:");
+    for data_type in coda.iter_all() {
+        let _ = writeln!(writer, "        - $ref: '{defs_path}{}'", data_type.name);
+    }
+
+    // Generate discriminator.
+    let _ = writeln!(writer, "      discriminator:");
+    let _ = writeln!(writer, "        propertyName: __ordinal");
+    let _ = writeln!(writer, "        mapping:");
+    for data_type in coda.iter_all() {
+        let ordinal = data_type.format().as_data_format().ordinal;
         let _ = writeln!(
             writer,
-            "        - $ref: '#/components/schemas/{data_type_name}'"
+            "          '{ordinal}': '{defs_path}{}'",
+            data_type.name
         );
     }
 
+    // Generate data type schemas.
+    let _ = writeln!(writer, "      $defs:");
+    for data_type in coda.iter_all() {
+        let ordinal = data_type.format().as_data_format().ordinal;
+        let data_type_name = &data_type.name;
+        let data_type_docs = match &data_type.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+
+        // Generate type header.
+        let _ = writeln!(writer, "        {data_type_name}:");
+        let _ = writeln!(writer, "          type: object");
+
+        // Generate required field list.
+        let _ = writeln!(writer, "          required:");
+        let _ = writeln!(writer, "            - __ordinal");
+        for field in data_type.iter().filter(|f| !f.optional) {
+            let _ = writeln!(writer, "            - {}", field.name);
+        }
+
+        // Generate type docs.
+        let _ = writeln!(writer, "          description: |-");
+        for line in data_type_docs.lines() {
+            write_indentation(&mut writer, 12)?;
+            let _ = writeln!(writer, "{line}");
+        }
+        if data_type.deprecated {
+            let _ = writeln!(writer, "          deprecated: true");
+        }
+
+        // Generate type fields, starting with the ordinal.
+        let _ = writeln!(writer, "          properties:");
+        let _ = writeln!(writer, "            __ordinal:");
+        let _ = writeln!(writer, "              const: {ordinal}");
+        for field in data_type.iter() {
+            let field_name = &field.name;
+            let field_docs = match &field.docs {
+                Some(docs) => docs.trim(),
+                None => "Undocumented Field. How could you? ;~;",
+            };
+
+            // Generate field header.
+            let _ = writeln!(writer, "            {field_name}:");
+
+            // Generate field docs, which (unlike OpenAPI 3.0)
+            // may be siblings of references.
+            let _ = writeln!(writer, "              description: |-");
+            for line in field_docs.lines() {
+                write_indentation(&mut writer, 16)?;
+                let _ = writeln!(writer, "{line}");
+            }
+
+            // Generate field typing.
+            open_api_type(&field.typing).write_yaml(&mut writer, 14, &defs_path)?;
+            if let Some(default) = open_api_default(field) {
+                let _ = writeln!(writer, "              default: {default}");
+            }
+            if field.deprecated {
+                let _ = writeln!(writer, "              deprecated: true");
+            }
+        }
+
+        let _ = writeln!(writer, "          unevaluatedProperties: false");
+    }
+
     Ok(())
 }
 
@@ -151,15 +345,23 @@ fn open_api_type(typing: &Type) -> OpenApiTypeIdentifier {
         Type::U8 => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
         Type::U16 => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
         Type::U32 => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
-        Type::U64 => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
+        Type::U64 | Type::UVar => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
         Type::I8 => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
         Type::I16 => OpenApiTypeIdentifier::Unformatted(Text::Static("integer")),
         Type::I32 => {
             OpenApiTypeIdentifier::Formatted(Text::Static("integer"), Text::Static("int32"))
         }
-        Type::I64 => {
+        Type::I64 | Type::IVar => {
             OpenApiTypeIdentifier::Formatted(Text::Static("integer"), Text::Static("int64"))
         }
+        // 128-bit integers exceed the precision of most
+        // JSON implementations, so they're encoded as strings.
+        Type::U128 => {
+            OpenApiTypeIdentifier::Formatted(Text::Static("string"), Text::Static("uint128"))
+        }
+        Type::I128 => {
+            OpenApiTypeIdentifier::Formatted(Text::Static("string"), Text::Static("int128"))
+        }
         Type::F32 => {
             OpenApiTypeIdentifier::Formatted(Text::Static("number"), Text::Static("float"))
         }
@@ -168,7 +370,18 @@ fn open_api_type(typing: &Type) -> OpenApiTypeIdentifier {
         }
         Type::Bool => OpenApiTypeIdentifier::Unformatted(Text::Static("boolean")),
         Type::Text => OpenApiTypeIdentifier::Unformatted(Text::Static("string")),
+        Type::Char => {
+            OpenApiTypeIdentifier::Formatted(Text::Static("string"), Text::Static("char"))
+        }
+        Type::Bytes => {
+            OpenApiTypeIdentifier::Formatted(Text::Static("string"), Text::Static("byte"))
+        }
+        // Refer to the module docs on timestamps.
+        Type::Timestamp => {
+            OpenApiTypeIdentifier::Formatted(Text::Static("string"), Text::Static("date-time"))
+        }
         Type::Data(typing) => OpenApiTypeIdentifier::ObjectReference(typing.name.clone()),
+        Type::Enum(typing) => OpenApiTypeIdentifier::Enumerated(typing.variants.clone()),
         Type::List(typing) => {
             let typing = open_api_type(typing.as_ref());
             OpenApiTypeIdentifier::Array(typing.into())
@@ -181,22 +394,71 @@ fn open_api_type(typing: &Type) -> OpenApiTypeIdentifier {
     }
 }
 
+/// Returns the YAML literal of the default value
+/// declared by `field`, if any.
+fn open_api_default(field: &DataField) -> Option<Text> {
+    Some(match field.default.as_ref()? {
+        Unspecified::U8(value) => format!("{value}").into(),
+        Unspecified::U16(value) => format!("{value}").into(),
+        Unspecified::U32(value) => format!("{value}").into(),
+        Unspecified::U64(value) => format!("{value}").into(),
+        Unspecified::I8(value) => format!("{value}").into(),
+        Unspecified::I16(value) => format!("{value}").into(),
+        Unspecified::I32(value) => format!("{value}").into(),
+        Unspecified::I64(value) => format!("{value}").into(),
+        Unspecified::UVar(value) => format!("{}", value.0).into(),
+        Unspecified::IVar(value) => format!("{}", value.0).into(),
+        // 128-bit integers are described as strings.
+        Unspecified::U128(value) => format!("'{value}'").into(),
+        Unspecified::I128(value) => format!("'{value}'").into(),
+        Unspecified::F32(value) => format!("{value:?}").into(),
+        Unspecified::F64(value) => format!("{value:?}").into(),
+        Unspecified::Bool(value) => format!("{value}").into(),
+        Unspecified::Char(value) => yaml_string(value.encode_utf8(&mut [0; 4])),
+        Unspecified::Text(value) => yaml_string(value),
+        Unspecified::Enum { typing, variant } => format!("'{}'", typing.variant(*variant)?).into(),
+        _ => return None,
+    })
+}
+
+/// Returns `value` as a double-quoted and escaped YAML string.
+fn yaml_string(value: &str) -> Text {
+    let mut string = alloc::string::String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => string += "\\\"",
+            '\\' => string += "\\\\",
+            '\n' => string += "\\n",
+            '\r' => string += "\\r",
+            '\t' => string += "\\t",
+            c if c.is_control() => string += &format!("\\u{:04x}", c as u32),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string.into()
+}
+
 /// Return value of [`open_api_type`].
 enum OpenApiTypeIdentifier {
     Unformatted(Text),
     Formatted(Text, Text),
     ObjectReference(Text),
+    /// A string with one of the listed values.
+    Enumerated(Vec<Text>),
     Array(Box<OpenApiTypeIdentifier>),
     Map(Box<(OpenApiTypeIdentifier, OpenApiTypeIdentifier)>),
 }
 
 impl OpenApiTypeIdentifier {
     /// Writes the YAML representation of this identifier to `writer`
-    /// with initial leading `indentation`.
+    /// with initial leading `indentation`, referencing objects
+    /// relative to `schemas_path`.
     pub fn write_yaml<W: Writes>(
         &self,
         writer: &mut FmtWriter<'_, W>,
         indentation: usize,
+        schemas_path: &str,
     ) -> Result<(), StreamError> {
         match self {
             OpenApiTypeIdentifier::Unformatted(typing) => {
@@ -211,14 +473,28 @@ impl OpenApiTypeIdentifier {
             }
             OpenApiTypeIdentifier::ObjectReference(reference) => {
                 write_indentation(writer, indentation)?;
-                let _ = writeln!(writer, "$ref: '#/components/schemas/{reference}'");
+                let _ = writeln!(writer, "$ref: '{schemas_path}{reference}'");
+            }
+            OpenApiTypeIdentifier::Enumerated(variants) => {
+                write_indentation(writer, indentation)?;
+                let _ = writeln!(writer, "type: string");
+                write_indentation(writer, indentation)?;
+                let _ = writeln!(writer, "enum:");
+                for variant in variants {
+                    write_indentation(writer, indentation + YAML_INDENTATION_STEP)?;
+                    let _ = writeln!(writer, "- '{variant}'");
+                }
             }
             OpenApiTypeIdentifier::Array(open_api_type_identifier) => {
                 write_indentation(writer, indentation)?;
                 let _ = writeln!(writer, "type: array");
                 write_indentation(writer, indentation)?;
                 let _ = writeln!(writer, "items:");
-                open_api_type_identifier.write_yaml(writer, indentation + YAML_INDENTATION_STEP)?;
+                open_api_type_identifier.write_yaml(
+                    writer,
+                    indentation + YAML_INDENTATION_STEP,
+                    schemas_path,
+                )?;
             }
             OpenApiTypeIdentifier::Map(type_identifiers) => {
                 write_indentation(writer, indentation)?;
@@ -234,9 +510,11 @@ impl OpenApiTypeIdentifier {
                 let _ = writeln!(writer, "type: array");
                 write_indentation(writer, indentation + (YAML_INDENTATION_STEP * 2))?;
                 let _ = writeln!(writer, "items:");
-                type_identifiers
-                    .0
-                    .write_yaml(writer, indentation + (YAML_INDENTATION_STEP * 3))?;
+                type_identifiers.0.write_yaml(
+                    writer,
+                    indentation + (YAML_INDENTATION_STEP * 3),
+                    schemas_path,
+                )?;
 
                 // Values list.
                 write_indentation(writer, indentation + YAML_INDENTATION_STEP)?;
@@ -245,9 +523,11 @@ impl OpenApiTypeIdentifier {
                 let _ = writeln!(writer, "type: array");
                 write_indentation(writer, indentation + (YAML_INDENTATION_STEP * 2))?;
                 let _ = writeln!(writer, "items:");
-                type_identifiers
-                    .1
-                    .write_yaml(writer, indentation + (YAML_INDENTATION_STEP * 3))?;
+                type_identifiers.1.write_yaml(
+                    writer,
+                    indentation + (YAML_INDENTATION_STEP * 3),
+                    schemas_path,
+                )?;
             }
         };
 
@@ -266,3 +546,136 @@ fn write_indentation<W: Writes>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::EnumType,
+    };
+
+    #[test]
+    fn generates_v31_spec() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut yaml = vec![];
+        generate_spec_v31(&coda, &mut yaml).unwrap();
+        let yaml = alloc::string::String::from_utf8_lossy(&yaml);
+
+        assert!(yaml.starts_with(
+            "openapi: 3.1.0\njsonSchemaDialect: https://json-schema.org/draft/2020-12/schema\n"
+        ));
+        assert!(yaml.contains(
+            "\n  schemas:\n    MyCodaData:\n      $schema: https://json-schema.org/draft/2020-12/schema\n"
+        ));
+
+        // Data types are referenced from the coda's `$defs`.
+        assert!(yaml.contains(
+            "\n      oneOf:\n        - $ref: '#/components/schemas/MyCodaData/$defs/Unspecified'\n        - $ref: '#/components/schemas/MyCodaData/$defs/MyNestedDataType'\n        - $ref: '#/components/schemas/MyCodaData/$defs/MyDataType'\n"
+        ));
+        assert!(yaml.contains(
+            "\n      discriminator:\n        propertyName: __ordinal\n        mapping:\n          '0': '#/components/schemas/MyCodaData/$defs/Unspecified'\n          '1': '#/components/schemas/MyCodaData/$defs/MyNestedDataType'\n          '2': '#/components/schemas/MyCodaData/$defs/MyDataType'\n      $defs:\n        Unspecified:\n"
+        ));
+        assert!(yaml.contains(
+            "\n              $ref: '#/components/schemas/MyCodaData/$defs/MyNestedDataType'\n"
+        ));
+
+        // Data types are strict, with constant ordinals.
+        assert!(yaml
+            .contains("\n          properties:\n            __ordinal:\n              const: 2\n"));
+        assert_eq!(
+            3,
+            yaml.matches("\n          unevaluatedProperties: false\n")
+                .count()
+        );
+        assert!(!yaml.contains("enum:"));
+
+        // Optional fields aren't required.
+        assert!(!yaml.contains("            - optional_field\n"));
+    }
+
+    #[test]
+    fn formats_bytes() {
+        let mut yaml = vec![];
+        open_api_type(&Type::Bytes)
+            .write_yaml(&mut FmtWriter::from(&mut yaml), 0, "")
+            .unwrap();
+        assert_eq!(
+            "type: string\nformat: byte\n",
+            alloc::string::String::from_utf8_lossy(&yaml)
+        );
+    }
+
+    #[test]
+    fn lists_enum_variants() {
+        let mut yaml = vec![];
+        open_api_type(&Type::Enum(EnumType::new(
+            "Status".into(),
+            &["Active".into(), "Deleted".into()],
+        )))
+        .write_yaml(&mut FmtWriter::from(&mut yaml), 0, "")
+        .unwrap();
+        assert_eq!(
+            "type: string\nenum:\n  - 'Active'\n  - 'Deleted'\n",
+            alloc::string::String::from_utf8_lossy(&yaml)
+        );
+    }
+
+    #[test]
+    fn describes_deprecations() {
+        let coda = parse("# `Users` Coda\n\n## `Account` Data\n\n+ `name` text\n\n## `User` Data\n\n> Deprecated: Use \"Account\".\n\n+ `id` u32\n+ `old_id` deprecated u64\n+ `nickname` optional deprecated text\n\n    > Deprecated: Use `Account.name`.\n\n+ `account` deprecated Account\n").unwrap();
+
+        let mut yaml = vec![];
+        generate_spec(&coda, &mut yaml).unwrap();
+        let yaml = alloc::string::String::from_utf8_lossy(&yaml);
+        assert!(yaml.contains("\n        > Deprecated: Use \"Account\".\n      deprecated: true\n"));
+        assert!(yaml.contains("\n          type: integer\n          deprecated: true\n"));
+        assert!(yaml.contains("\n          type: string\n          deprecated: true\n"));
+
+        // References can't be deprecated in OpenAPI 3.0.
+        assert_eq!(3, yaml.matches("deprecated: true").count());
+
+        let mut yaml = vec![];
+        generate_spec_v31(&coda, &mut yaml).unwrap();
+        let yaml = alloc::string::String::from_utf8_lossy(&yaml);
+        assert!(yaml.contains("\n              $ref: '#/components/schemas/UsersData/$defs/Account'\n              deprecated: true\n"));
+        assert_eq!(4, yaml.matches("deprecated: true").count());
+    }
+
+    #[test]
+    fn describes_defaults() {
+        let coda = parse("# `Jobs` Coda\n\n## `Job` Data\n\n+ `retries` u32 = 3\n+ `total` u128 = 12\n+ `name` optional text = \"the \\\"first\\\" job\"\n+ `status` one of [Queued, Running] = Running\n+ `started_at` timestamp = 1700000000000\n").unwrap();
+
+        let mut yaml = vec![];
+        generate_spec(&coda, &mut yaml).unwrap();
+        let yaml = alloc::string::String::from_utf8_lossy(&yaml);
+        assert!(yaml.contains("\n          type: integer\n          default: 3\n"));
+        assert!(yaml.contains("\n          format: uint128\n          default: '12'\n"));
+        assert!(yaml
+            .contains("\n          type: string\n          default: \"the \\\"first\\\" job\"\n"));
+        assert!(yaml.contains("\n            - 'Running'\n          default: 'Running'\n"));
+
+        // Timestamp defaults aren't date-times.
+        assert!(yaml.contains("\n          format: date-time\n        name:\n"));
+        assert_eq!(4, yaml.matches("default:").count());
+
+        let mut yaml = vec![];
+        generate_spec_v31(&coda, &mut yaml).unwrap();
+        let yaml = alloc::string::String::from_utf8_lossy(&yaml);
+        assert!(yaml.contains("\n              type: integer\n              default: 3\n"));
+        assert_eq!(4, yaml.matches("default:").count());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        let mut yaml = vec![];
+        open_api_type(&Type::Timestamp)
+            .write_yaml(&mut FmtWriter::from(&mut yaml), 0, "")
+            .unwrap();
+        assert_eq!(
+            "type: string\nformat: date-time\n",
+            alloc::string::String::from_utf8_lossy(&yaml)
+        );
+    }
+}
@@ -0,0 +1,512 @@
+//! Protocol Buffer (`.proto`) generators.
+//!
+//! ## What's Here
+//!
+//! For a given coda, a `proto3` file will be
+//! generated containing:
+//!
+//! - A `message` for each data type, with one field for
+//!   each of the data type's fields. Field numbers match
+//!   the order of each field in the coda's encoding (blob
+//!   fields first, then data fields), starting at `1`.
+//!
+//! - A `{Coda}Data` message containing a `oneof` with one
+//!   field for each data type, numbered by the data type's
+//!   ordinal. Unspecified data is represented by an unset `oneof`.
+//!
+//! - An `enum` for each enum type, with values numbered
+//!   by the variants' ordinals and prefixed with the
+//!   enum's name (e.g., `USER_STATUS_ACTIVE = 0`).
+//!
+//! Protobuf has no 128-bit or 8/16-bit numbers, so 128-bit
+//! numbers become their raw, little-endian `bytes`, and
+//! smaller numbers are widened to 32 bits. Unspecified
+//! data becomes the `bytes` of its raw encoding.
+//!
+//! Protobuf doesn't support nested `repeated` or `map`
+//! fields, nor maps with non-integral, non-`string` keys;
+//! those are wrapped in generated `ListOf...`/`MapOf...`
+//! messages, with maps of unsupported keys becoming a
+//! `repeated` list of key-value entries.
+//!
+//! Generated messages describe the _shape_ of codas data;
+//! they aren't compatible with the [`codec`](crate::codec).
+use core::fmt::Write;
+
+use alloc::{collections::BTreeMap, format, string::String};
+
+use crate::{
+    stream::{FmtWriter, StreamError, Writes},
+    types::{Coda, Text, Type},
+};
+
+/// Generates the `proto3` messages for `coda`.
+pub fn generate_proto(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Wrapper messages for nested lists and maps,
+    // by name, generated after all data types.
+    let mut wrappers = BTreeMap::new();
+
+    // Generate file header.
+    let _ = writeln!(writer, "// Code generated by codas. DO NOT EDIT.");
+    let _ = writeln!(writer, "//");
+    let _ = writeln!(
+        writer,
+        "// Caveat: proto3 and codas have different default and optional"
+    );
+    let _ = writeln!(
+        writer,
+        "// semantics. Proto3 omits scalar fields set to their zero value, and"
+    );
+    let _ = writeln!(
+        writer,
+        "// only `optional` fields track presence; codas always encodes every"
+    );
+    let _ = writeln!(
+        writer,
+        "// field, and only optional coda fields may be absent.\n"
+    );
+    let _ = writeln!(writer, "syntax = \"proto3\";\n");
+    let _ = writeln!(
+        writer,
+        "package {};",
+        proto_snake_name(&coda.global_name.replace(['.', '/', '-', ' '], "_"))
+    );
+
+    // Generate coda union.
+    let _ = writeln!(writer);
+    write_proto_doc(&mut writer, 0, coda_type_docs);
+    let _ = writeln!(writer, "message {coda_type_name} {{");
+    let _ = writeln!(writer, "  oneof data {{");
+    for data_type in coda.iter() {
+        let ordinal = data_type.format().as_data_format().ordinal;
+        let _ = writeln!(
+            writer,
+            "    {} {} = {ordinal};",
+            data_type.name,
+            proto_snake_name(&data_type.name)
+        );
+    }
+    let _ = writeln!(writer, "  }}");
+    let _ = writeln!(writer, "}}");
+
+    // Generate data types.
+    for data_type in coda.iter() {
+        let data_type_docs = match &data_type.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+
+        let _ = writeln!(writer);
+        write_proto_doc(&mut writer, 0, data_type_docs);
+        let _ = writeln!(writer, "message {} {{", data_type.name);
+        for (i, field) in data_type.iter().enumerate() {
+            let field_docs = match &field.docs {
+                Some(docs) => docs.trim(),
+                None => "Undocumented Field. How could you? ;~;",
+            };
+            if i > 0 {
+                let _ = writeln!(writer);
+            }
+            write_proto_doc(&mut writer, 2, field_docs);
+
+            let field_type = proto_field_type(&field.typing, field.optional, &mut wrappers);
+            let _ = writeln!(
+                writer,
+                "  {field_type} {} = {};",
+                proto_field_name(&field.name),
+                i + 1
+            );
+        }
+        let _ = writeln!(writer, "}}");
+    }
+
+    // Generate enums.
+    for typing in coda.enums() {
+        let prefix = proto_snake_name(&typing.name).to_uppercase();
+        let _ = writeln!(writer);
+        let _ = writeln!(writer, "enum {} {{", typing.name);
+        for (ordinal, variant) in typing.variants.iter().enumerate() {
+            let _ = writeln!(
+                writer,
+                "  {prefix}_{} = {ordinal};",
+                proto_snake_name(variant).to_uppercase()
+            );
+        }
+        let _ = writeln!(writer, "}}");
+    }
+
+    // Generate wrappers.
+    for (name, fields) in wrappers {
+        let _ = writeln!(writer);
+        let _ = writeln!(writer, "message {name} {{");
+        let _ = write!(writer, "{fields}");
+        let _ = writeln!(writer, "}}");
+    }
+
+    Ok(())
+}
+
+/// Writes a Protobuf comment with leading
+/// `indentation` (in spaces) containing `docs` to `writer`.
+fn write_proto_doc<W: Writes>(writer: &mut FmtWriter<'_, W>, indentation: usize, docs: &str) {
+    let spaces = " ".repeat(indentation);
+    for line in docs.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            let _ = writeln!(writer, "{spaces}//");
+        } else {
+            let _ = writeln!(writer, "{spaces}// {line}");
+        }
+    }
+}
+
+/// Returns `name` (e.g., a `PascalCase` type name) in `snake_case`.
+fn proto_snake_name(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if previous_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            previous_lower = false;
+        } else {
+            snake.push(c);
+            previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+    snake
+}
+
+/// Returns `name` as a Protobuf field name.
+///
+/// Names starting with a digit are prefixed with `f_`,
+/// since Protobuf identifiers can't start with a digit.
+fn proto_field_name(name: &str) -> Text {
+    let name = name.replace('-', "_");
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("f_{name}").into()
+    } else {
+        name.into()
+    }
+}
+
+/// Returns the (possibly labeled) Protobuf type of
+/// a field of `type`, adding any wrappers it
+/// requires to `wrappers`.
+fn proto_field_type(typing: &Type, optional: bool, wrappers: &mut BTreeMap<Text, Text>) -> Text {
+    match typing {
+        // Optional lists and maps are wrapped,
+        // since they can't be labeled `optional`.
+        Type::List(_) | Type::Map(_) if optional => {
+            format!("optional {}", proto_type(typing, wrappers)).into()
+        }
+        Type::List(typing) => format!("repeated {}", proto_type(typing, wrappers)).into(),
+        Type::Map(typing) => proto_map_type(&typing.0, &typing.1, wrappers),
+        _ if optional => format!("optional {}", proto_type(typing, wrappers)).into(),
+        _ => proto_type(typing, wrappers),
+    }
+}
+
+/// Returns the unlabeled Protobuf type of a map from
+/// `key` to `value`, adding any wrappers it requires to `wrappers`.
+fn proto_map_type(key: &Type, value: &Type, wrappers: &mut BTreeMap<Text, Text>) -> Text {
+    let value_type = proto_type(value, wrappers);
+    match key {
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::I64
+        | Type::UVar
+        | Type::IVar
+        | Type::Timestamp
+        | Type::Bool
+        | Type::Char
+        | Type::Text => format!("map<{}, {value_type}>", proto_type(key, wrappers)).into(),
+
+        // Other keys are encoded as a list of entries.
+        _ => {
+            let key_type = proto_type(key, wrappers);
+            let entry_name: Text = format!(
+                "EntryOf{}To{}",
+                proto_wrapper_name(key),
+                proto_wrapper_name(value)
+            )
+            .into();
+            wrappers.insert(
+                entry_name.clone(),
+                format!("  {key_type} key = 1;\n  {value_type} value = 2;\n").into(),
+            );
+            format!("repeated {entry_name}").into()
+        }
+    }
+}
+
+/// Returns the unlabeled Protobuf type of `type`,
+/// adding any wrappers it requires to `wrappers`.
+///
+/// If `type` is a [`Type::Data`], the data's name
+/// will be interpreted as a Protobuf message name.
+fn proto_type(typing: &Type, wrappers: &mut BTreeMap<Text, Text>) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("bytes"),
+        Type::U8 => Text::Static("uint32"),
+        Type::U16 => Text::Static("uint32"),
+        Type::U32 => Text::Static("uint32"),
+        Type::U64 => Text::Static("uint64"),
+        Type::I8 => Text::Static("int32"),
+        Type::I16 => Text::Static("int32"),
+        Type::I32 => Text::Static("int32"),
+        // Timestamps are milliseconds since the Unix epoch.
+        Type::I64 | Type::Timestamp => Text::Static("int64"),
+        Type::U128 => Text::Static("bytes"),
+        Type::I128 => Text::Static("bytes"),
+        // Protobuf's 64-bit integers are already variable-length,
+        // with `sint64` ZigZag-encoding signed integers.
+        Type::UVar => Text::Static("uint64"),
+        Type::IVar => Text::Static("sint64"),
+        Type::F32 => Text::Static("float"),
+        Type::F64 => Text::Static("double"),
+        Type::Bool => Text::Static("bool"),
+        // Characters are their Unicode scalar value.
+        Type::Char => Text::Static("uint32"),
+        Type::Text => Text::Static("string"),
+        Type::Bytes => Text::Static("bytes"),
+        Type::Data(typing) => typing.name.clone(),
+        Type::Enum(typing) => typing.name.clone(),
+
+        // Lists and maps are wrapped in a message
+        // when they can't be a field's type.
+        Type::List(_) | Type::Map(_) => {
+            let name = proto_wrapper_name(typing);
+            if !wrappers.contains_key(&name) {
+                let field_type = proto_field_type(typing, false, wrappers);
+                let field_name = match typing {
+                    Type::List(_) => "items",
+                    _ => "entries",
+                };
+                wrappers.insert(
+                    name.clone(),
+                    format!("  {field_type} {field_name} = 1;\n").into(),
+                );
+            }
+            name
+        }
+    }
+}
+
+/// Returns the name of `type` within the
+/// names of generated wrapper messages.
+fn proto_wrapper_name(typing: &Type) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("Unspecified"),
+        Type::U8 => Text::Static("U8"),
+        Type::U16 => Text::Static("U16"),
+        Type::U32 => Text::Static("U32"),
+        Type::U64 => Text::Static("U64"),
+        Type::I8 => Text::Static("I8"),
+        Type::I16 => Text::Static("I16"),
+        Type::I32 => Text::Static("I32"),
+        Type::I64 => Text::Static("I64"),
+        Type::U128 => Text::Static("U128"),
+        Type::I128 => Text::Static("I128"),
+        Type::UVar => Text::Static("UVar"),
+        Type::IVar => Text::Static("IVar"),
+        Type::F32 => Text::Static("F32"),
+        Type::F64 => Text::Static("F64"),
+        Type::Bool => Text::Static("Bool"),
+        Type::Char => Text::Static("Char"),
+        Type::Text => Text::Static("Text"),
+        Type::Bytes => Text::Static("Bytes"),
+        Type::Timestamp => Text::Static("Timestamp"),
+        Type::Data(typing) => typing.name.clone(),
+        Type::Enum(typing) => typing.name.clone(),
+        Type::List(typing) => format!("ListOf{}", proto_wrapper_name(typing)).into(),
+        Type::Map(typing) => format!(
+            "MapOf{}To{}",
+            proto_wrapper_name(&typing.0),
+            proto_wrapper_name(&typing.1)
+        )
+        .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::EnumType,
+    };
+
+    #[test]
+    fn generates_proto() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut proto = vec![];
+        generate_proto(&coda, &mut proto).unwrap();
+        let proto = alloc::string::String::from_utf8_lossy(&proto);
+
+        assert!(proto.contains("\nsyntax = \"proto3\";\n\npackage my_coda;\n"));
+        assert!(proto.contains("// Caveat: proto3 and codas have different default and optional\n"));
+
+        // The coda is a oneof of its data types, by ordinal.
+        assert!(proto.contains(
+            "\nmessage MyCodaData {\n  oneof data {\n    MyNestedDataType my_nested_data_type = 1;\n    MyDataType my_data_type = 2;\n  }\n}\n"
+        ));
+
+        // Fields are numbered in order, blob fields first.
+        assert!(proto.contains("\n  float floaty_field = 1;\n"));
+        assert!(proto.contains("\n  repeated string listy_field = 2;\n"));
+        assert!(proto.contains("\n  int32 integral_field = 1;\n"));
+        assert!(proto.contains("\n  string textual_field = 2;\n"));
+        assert!(proto.contains("\n  MyNestedDataType nested_field = 3;\n"));
+        assert!(proto.contains("\n  optional uint64 optional_field = 4;\n"));
+        assert!(proto.contains("\n  repeated ListOfListOfI32 f_3d_field = 5;\n"));
+        assert!(proto.contains("\n  map<string, int32> map_field = 6;\n"));
+        assert!(proto.contains("\n  bytes unspecified_field = 7;\n"));
+
+        // Nested lists are wrapped.
+        assert!(proto.contains(
+            "\nmessage ListOfI32 {\n  repeated int32 items = 1;\n}\n\nmessage ListOfListOfI32 {\n  repeated ListOfI32 items = 1;\n}\n"
+        ));
+    }
+
+    #[test]
+    fn wraps_unsupported_maps() {
+        let mut wrappers = BTreeMap::new();
+        assert_eq!(
+            "repeated EntryOfF32ToListOfText",
+            &*proto_field_type(
+                &Type::Map((Type::F32, Type::List(Type::Text.into())).into()),
+                false,
+                &mut wrappers
+            )
+        );
+        assert_eq!(
+            "optional MapOfTextToU8",
+            &*proto_field_type(
+                &Type::Map((Type::Text, Type::U8).into()),
+                true,
+                &mut wrappers
+            )
+        );
+
+        assert_eq!(
+            "  float key = 1;\n  ListOfText value = 2;\n",
+            &*wrappers["EntryOfF32ToListOfText"]
+        );
+        assert_eq!(
+            "  map<string, uint32> entries = 1;\n",
+            &*wrappers["MapOfTextToU8"]
+        );
+        assert_eq!("  repeated string items = 1;\n", &*wrappers["ListOfText"]);
+        assert_eq!(
+            "map<int64, string>",
+            &*proto_field_type(
+                &Type::Map((Type::Timestamp, Type::Text).into()),
+                false,
+                &mut wrappers
+            )
+        );
+        assert_eq!(
+            "repeated ListOfTimestamp",
+            &*proto_field_type(
+                &Type::List(Type::List(Type::Timestamp.into()).into()),
+                false,
+                &mut wrappers
+            )
+        );
+        assert_eq!(
+            "  repeated int64 items = 1;\n",
+            &*wrappers["ListOfTimestamp"]
+        );
+
+        // Bytes can't be map keys.
+        assert_eq!(
+            "repeated EntryOfBytesToU8",
+            &*proto_field_type(
+                &Type::Map((Type::Bytes, Type::U8).into()),
+                false,
+                &mut wrappers
+            )
+        );
+        assert_eq!(
+            "  bytes key = 1;\n  uint32 value = 2;\n",
+            &*wrappers["EntryOfBytesToU8"]
+        );
+    }
+
+    #[test]
+    fn generates_enums() {
+        let coda =
+            parse("# `Accounts` Coda\n\n## `User` Data\n\n+ `status` one of [Active, Deleted]\n")
+                .unwrap();
+        let mut proto = vec![];
+        generate_proto(&coda, &mut proto).unwrap();
+        let proto = alloc::string::String::from_utf8_lossy(&proto);
+
+        assert!(proto.contains("\n  UserStatus status = 1;\n"));
+        assert!(proto.contains(
+            "\nenum UserStatus {\n  USER_STATUS_ACTIVE = 0;\n  USER_STATUS_DELETED = 1;\n}\n"
+        ));
+
+        // Enums can't be map keys.
+        let typing = Type::Enum(EnumType::new("Status".into(), &["Active".into()]));
+        let mut wrappers = BTreeMap::new();
+        assert_eq!(
+            "repeated EntryOfStatusToText",
+            &*proto_field_type(
+                &Type::Map((typing, Type::Text).into()),
+                false,
+                &mut wrappers
+            )
+        );
+    }
+
+    /// Checks that generated protos are accepted by `protoc`, if it's installed.
+    #[test]
+    fn generates_valid_proto() {
+        use std::process::Command;
+
+        if Command::new("protoc").arg("--version").output().is_err() {
+            return;
+        }
+
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut proto = vec![];
+        generate_proto(&coda, &mut proto).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("codas-protobuf-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("my_coda.proto"), proto).unwrap();
+        let output = Command::new("protoc")
+            .arg("--proto_path")
+            .arg(&dir)
+            .arg("--descriptor_set_out")
+            .arg(dir.join("my_coda.pb"))
+            .arg("my_coda.proto")
+            .output()
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(
+            output.status.success(),
+            "{}",
+            alloc::string::String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
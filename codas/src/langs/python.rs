@@ -8,7 +8,18 @@
 //! - A base `class` for the coda, which all of
 //!   the coda's types extend from.
 //!
-//! - A `class` for each data type.
+//! - A `class` for each data type; either with
+//!   explicit properties ([`generate_types`]), as
+//!   a `dataclass` ([`generate_types_dataclass`]), or
+//!   as a pydantic model ([`generate_pydantic`]).
+//!
+//! - An `enum.Enum` for each enum type, with each
+//!   variant valued by its ordinal. Since codecs aren't
+//!   generated, unknown ordinals are rejected by `enum`
+//!   itself: `UserStatus(7)` raises a `ValueError`.
+//!
+//! - A `.pyi` stub declaring the types of the classes
+//!   generated by [`generate_types`] ([`generate_stubs`]).
 //!
 //! **Codecs are not generated.** They will be
 //! generated once there is a native Python library
@@ -21,7 +32,7 @@ use indoc::writedoc;
 
 use crate::{
     stream::{FmtWriter, StreamError, Writes},
-    types::{Coda, Text, Type, Unspecified},
+    types::{Coda, DataField, DataType, Text, Type, Unspecified},
 };
 
 /// Generates the Python types for `coda`,
@@ -36,6 +47,12 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
 
     let mut writer = FmtWriter::from(stream);
 
+    // Generate imports.
+    let enum_import = python_enum_import(coda);
+    if !enum_import.is_empty() {
+        let _ = writeln!(writer, "{enum_import}\n");
+    }
+
     // Generate coda base class.
     let _ = writedoc!(
         writer,
@@ -49,12 +66,12 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
     "#
     );
 
+    // Generate enums.
+    write_python_enums(&mut writer, coda);
+
     // Generate coda data type classes.
-    for (ordinal, typing) in [Unspecified::DATA_TYPE]
-        .iter()
-        .chain(coda.iter())
-        .enumerate()
-    {
+    for typing in coda.iter_all() {
+        let ordinal = typing.ordinal();
         // Extract type metadata.
         let data_type_name = typing.name.trim();
         let data_type_docs = match &typing.docs {
@@ -84,15 +101,16 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
 
         // Generate field default value assignments.
         for field in typing.iter() {
-            let default_value = match field.optional {
-                true => Text::Static("None"),
-                false => python_default_val(&field.typing),
+            let default_value = match (python_field_default(field), field.optional) {
+                (Some(default), _) => default,
+                (None, true) => Text::Static("None"),
+                (None, false) => python_default_val(&field.typing),
             };
 
             let _ = writeln!(
                 writer,
                 "        self._{} = {}",
-                field.name.trim(),
+                python_field_name(field),
                 default_value,
             );
         }
@@ -103,7 +121,7 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
         for (ordinal, field) in typing.iter().enumerate() {
             // Field ordinals are 1-indexed.
             let ordinal = ordinal + 1;
-            let field_name = field.name.trim();
+            let field_name = python_field_name(field);
             let field_type = python_type(&field.typing);
             let field_docs = match &field.docs {
                 Some(docs) => docs.trim(),
@@ -116,6 +134,12 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
                 None => Text::from(""),
             };
 
+            // Generate deprecation warnings.
+            let deprecation_warning: Text = match python_deprecation_warning(typing, field) {
+                Some(warning) => format!("import warnings\n        {warning}\n        ").into(),
+                None => "".into(),
+            };
+
             // Generate getter and setter.
             if field.optional {
                 let _ = writedoc!(
@@ -131,7 +155,7 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
 
                     @{field_name}.setter
                     def {field_name}(self, value: {field_type} | None):
-                        if value is None:
+                        {deprecation_warning}if value is None:
                             self._{field_name} = None
                         else:
                             {type_check}
@@ -153,18 +177,565 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
     
                     @{field_name}.setter
                     def {field_name}(self, value: {field_type}):
-                        {type_check}
+                        {deprecation_warning}{type_check}
                         self._{field_name} = value
     
                 "#
                 );
             }
+
+            // Generate `datetime` accessors for timestamps.
+            if field.typing == Type::Timestamp {
+                let (optional, none_check) = match field.optional {
+                    true => (" | None", "None if value is None else "),
+                    false => ("", ""),
+                };
+
+                let _ = writedoc!(
+                    writer,
+                    r#"
+                # Field {ordinal} (as a `datetime`)
+                    @property
+                    def {field_name}_datetime(self) -> "datetime.datetime{optional}":
+                        """
+                        `{field_name}` as a UTC `datetime`.
+                        """
+                        import datetime
+                        value = self._{field_name}
+                        return {none_check}datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc) + datetime.timedelta(milliseconds=value)
+
+                    @{field_name}_datetime.setter
+                    def {field_name}_datetime(self, value: "datetime.datetime{optional}"):
+                        import datetime
+                        self.{field_name} = {none_check}(value - datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)) // datetime.timedelta(milliseconds=1)
+
+                "#
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the Python types for `coda` as
+/// `dataclass`es, writing them to `stream`.
+///
+/// Fields with mutable defaults (lists, maps, and
+/// nested data) are declared with a `default_factory`,
+/// and optional fields default to `None`. Range checks
+/// on numeric fields are performed in `__post_init__`.
+pub fn generate_types_dataclass(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Generate imports and the coda base class.
+    let _ = writedoc!(
+        writer,
+        r#"
+    from __future__ import annotations
+
+    import dataclasses
+    {}from typing import Optional
+
+
+    class {coda_type_name}:
+        """
+    {}
+        """
+        pass
+
+    "#,
+        python_enum_import(coda),
+        python_indent(coda_type_docs, 1),
+    );
+
+    // Generate enums.
+    write_python_enums(&mut writer, coda);
+
+    // Generate coda data type classes.
+    for typing in coda.iter_all() {
+        let ordinal = typing.ordinal();
+        // Extract type metadata.
+        let data_type_name = typing.name.trim();
+        let data_type_docs = match &typing.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+
+        // Generate class header.
+        let _ = writedoc!(
+            writer,
+            r#"
+
+        # Data {ordinal}
+        @dataclasses.dataclass
+        class {data_type_name}({coda_type_name}):
+            """
+        {}
+            """
+        "#,
+            python_indent(data_type_docs, 1),
+        );
+
+        // Insert pass statement if the data
+        // type has _no_ fields.
+        if typing.iter().count() == 0 {
+            let _ = writeln!(writer, "    pass");
+            continue;
+        }
+
+        // Generate field declarations.
+        for (ordinal, field) in typing.iter().enumerate() {
+            // Field ordinals are 1-indexed.
+            let ordinal = ordinal + 1;
+            let field_name = python_field_name(field);
+            let field_type = python_type(&field.typing);
+            let field_docs = match &field.docs {
+                Some(docs) => docs.trim(),
+                None => "Undocumented Field. How could you? ;~;",
+            };
+
+            let declaration = if let Some(default) = python_field_default(field) {
+                match field.optional {
+                    true => format!("Optional[{field_type}] = {default}"),
+                    false => format!("{field_type} = {default}"),
+                }
+            } else if field.optional {
+                format!("Optional[{field_type}] = None")
+            } else {
+                match python_default_factory(&field.typing) {
+                    Some(factory) => {
+                        format!("{field_type} = dataclasses.field(default_factory={factory})")
+                    }
+                    None => format!("{field_type} = {}", python_default_val(&field.typing)),
+                }
+            };
+
+            let _ = writedoc!(
+                writer,
+                r#"
+
+                # Field {ordinal}
+                {field_name}: {declaration}
+                """
+            {}
+                """
+            "#,
+                python_indent(field_docs, 1),
+            );
+        }
+
+        // Generate range checks.
+        let checks: alloc::vec::Vec<_> = typing
+            .iter()
+            .filter_map(|field| Some((field, python_type_check(&field.typing)?)))
+            .collect();
+        if checks.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(writer);
+        let _ = writeln!(writer, "    def __post_init__(self):");
+        for (field, type_check) in checks {
+            let field_name = python_field_name(field);
+            let _ = writeln!(writer, "        value = self.{field_name}");
+            if field.optional {
+                let _ = writeln!(writer, "        if value is not None:");
+                let _ = writeln!(writer, "            {type_check}");
+            } else {
+                let _ = writeln!(writer, "        {type_check}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the Python types for `coda` as
+/// [pydantic](https://docs.pydantic.dev/) v2 models,
+/// writing them to `stream`.
+///
+/// Each data type becomes a `BaseModel` whose numeric
+/// fields are constrained to their coda type's range.
+/// The coda itself becomes a `RootModel` over a union of
+/// every data type, discriminated by the data's ordinal
+/// (serialized as `__ordinal`).
+pub fn generate_pydantic(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_local_name = coda.local_name.trim();
+    let coda_type_name = format!("{coda_local_name}Data");
+    let coda_model_name = format!("{coda_local_name}Model");
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Generate imports and the coda base model.
+    let _ = writedoc!(
+        writer,
+        r#"
+    from __future__ import annotations
+
+    {}from typing import Annotated, Any, Literal, Optional, Union
+
+    from pydantic import BaseModel, ConfigDict, Field, RootModel
+
+
+    class {coda_model_name}(BaseModel):
+        """
+    {}
+        """
+        model_config = ConfigDict(populate_by_name=True)
+
+    "#,
+        python_enum_import(coda),
+        python_indent(coda_type_docs, 1),
+    );
+
+    // Generate enums.
+    write_python_enums(&mut writer, coda);
+
+    // Generate coda data type models.
+    let mut data_type_names = alloc::vec::Vec::new();
+    for typing in coda.iter_all() {
+        let ordinal = typing.ordinal();
+        // Extract type metadata.
+        let data_type_name = typing.name.trim();
+        let data_type_docs = match &typing.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+        data_type_names.push(data_type_name);
+
+        // Generate model header.
+        let _ = writedoc!(
+            writer,
+            r#"
+
+        # Data {ordinal}
+        class {data_type_name}({coda_model_name}):
+            """
+        {}
+            """
+
+            codas_ordinal: Literal[{ordinal}] = Field(default={ordinal}, alias="__ordinal")
+        "#,
+            python_indent(data_type_docs, 1),
+        );
+
+        // Generate field declarations.
+        for (ordinal, field) in typing.iter().enumerate() {
+            // Field ordinals are 1-indexed.
+            let ordinal = ordinal + 1;
+            let field_name = python_field_name(field);
+            let mut field_type = pydantic_type(&field.typing);
+            if field.optional {
+                field_type = format!("Optional[{field_type}]").into();
+            }
+            let field_docs = match &field.docs {
+                Some(docs) => docs.trim(),
+                None => "Undocumented Field. How could you? ;~;",
+            };
+
+            // Renamed fields are aliased to their coda name.
+            if field_name != field.name.trim() {
+                field_type = format!(
+                    "Annotated[{field_type}, Field(alias={})]",
+                    python_string(field.name.trim())
+                )
+                .into();
+            }
+
+            let declaration = if let Some(default) = python_field_default(field) {
+                format!("{field_type} = {default}")
+            } else if field.optional {
+                format!("{field_type} = None")
+            } else {
+                match python_default_factory(&field.typing) {
+                    Some(factory) => format!("{field_type} = Field(default_factory={factory})"),
+                    None => format!("{field_type} = {}", python_default_val(&field.typing)),
+                }
+            };
+
+            let _ = writedoc!(
+                writer,
+                r#"
+
+                # Field {ordinal}
+                {field_name}: {declaration}
+                """
+            {}
+                """
+            "#,
+                python_indent(field_docs, 1),
+            );
+        }
+    }
+
+    // Generate the coda's discriminated union.
+    let data_type_union = format!("Union[{}]", data_type_names.join(", "));
+    let _ = writedoc!(
+        writer,
+        r#"
+
+
+    class {coda_type_name}(RootModel[{data_type_union}]):
+        """
+    {}
+        """
+
+        root: Annotated[{data_type_union}, Field(discriminator="codas_ordinal")]
+    "#,
+        python_indent(coda_type_docs, 1),
+    );
+
+    Ok(())
+}
+
+/// Generates a Python stub (`.pyi`) for the types generated
+/// by [`generate_types`] for `coda`, writing it to `stream`.
+pub fn generate_stubs(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Generate imports and the coda base class.
+    if coda
+        .iter()
+        .any(|typing| typing.iter().any(|field| field.typing == Type::Timestamp))
+    {
+        let _ = writeln!(writer, "import datetime");
+    }
+    let _ = write!(writer, "{}", python_enum_import(coda));
+    let _ = writedoc!(
+        writer,
+        r#"
+    from typing import Dict, List, Optional
+
+    class {coda_type_name}: ...
+    "#
+    );
+
+    // Generate enums.
+    for typing in coda.enums() {
+        let _ = writeln!(writer);
+        let _ = writeln!(writer, "class {}(enum.Enum):", typing.name);
+        for (ordinal, variant) in typing.variants.iter().enumerate() {
+            let _ = writeln!(writer, "    {variant} = {ordinal}");
+        }
+    }
+
+    // Generate coda data type classes.
+    for typing in coda.iter_all() {
+        let data_type_name = typing.name.trim();
+        let _ = writedoc!(
+            writer,
+            r#"
+
+        class {data_type_name}({coda_type_name}):
+            def __init__(self) -> None: ...
+        "#
+        );
+
+        // Generate field properties.
+        for field in typing.iter() {
+            let field_name = python_field_name(field);
+            let mut field_type = python_stub_type(&field.typing);
+            if field.optional {
+                field_type = format!("Optional[{field_type}]").into();
+            }
+
+            let _ = writeln!(writer, "    @property");
+            let _ = writeln!(writer, "    def {field_name}(self) -> {field_type}: ...");
+            let _ = writeln!(writer, "    @{field_name}.setter");
+            let _ = writeln!(
+                writer,
+                "    def {field_name}(self, value: {field_type}) -> None: ..."
+            );
+
+            // Generate `datetime` accessors for timestamps.
+            if field.typing == Type::Timestamp {
+                let mut datetime_type = Text::Static("datetime.datetime");
+                if field.optional {
+                    datetime_type = format!("Optional[{datetime_type}]").into();
+                }
+
+                let _ = writeln!(writer, "    @property");
+                let _ = writeln!(
+                    writer,
+                    "    def {field_name}_datetime(self) -> {datetime_type}: ..."
+                );
+                let _ = writeln!(writer, "    @{field_name}_datetime.setter");
+                let _ = writeln!(
+                    writer,
+                    "    def {field_name}_datetime(self, value: {datetime_type}) -> None: ..."
+                );
+            }
         }
     }
 
     Ok(())
 }
 
+/// Returns the statement importing `enum`,
+/// iff `coda` contains any enums.
+fn python_enum_import(coda: &Coda) -> &'static str {
+    if coda.enums().is_empty() {
+        ""
+    } else {
+        "import enum\n"
+    }
+}
+
+/// Writes an `enum.Enum` class for
+/// each enum in `coda` to `writer`.
+fn write_python_enums<W: Writes>(writer: &mut FmtWriter<'_, W>, coda: &Coda) {
+    for typing in coda.enums() {
+        let enum_name = &typing.name;
+        let _ = writedoc!(
+            writer,
+            r#"
+
+        class {enum_name}(enum.Enum):
+            """
+            Variants of `{enum_name}`, valued by their ordinals.
+            """
+        "#
+        );
+        for (ordinal, variant) in typing.variants.iter().enumerate() {
+            let _ = writeln!(writer, "    {variant} = {ordinal}");
+        }
+    }
+}
+
+/// Python keywords, which can't be used as field names.
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Returns the Python identifier of `field`.
+///
+/// Field names which aren't valid Python identifiers
+/// (like `3d_field`, or the keyword `class`)
+/// are prefixed with `f_` (like `f_3d_field`).
+fn python_field_name(field: &DataField) -> Text {
+    let name = field.name.trim();
+    if name.starts_with(|c: char| c.is_ascii_digit()) || PYTHON_KEYWORDS.contains(&name) {
+        format!("f_{name}").into()
+    } else {
+        alloc::string::String::from(name).into()
+    }
+}
+
+/// Returns the Python identifier of `type` to use
+/// in stubs, which (unlike [`python_type`]) uses
+/// `typing` generics for lists and maps.
+fn python_stub_type(typing: &Type) -> Text {
+    match typing {
+        Type::List(typing) => {
+            let typing = python_stub_type(typing.as_ref());
+            format!("List[{typing}]").into()
+        }
+        Type::Map(typing) => {
+            let key_typing = python_stub_type(&typing.as_ref().0);
+            let value_typing = python_stub_type(&typing.as_ref().1);
+            format!("Dict[{key_typing}, {value_typing}]").into()
+        }
+        typing => python_type(typing),
+    }
+}
+
+/// Returns the pydantic annotation of `type`, which (unlike
+/// [`python_type`]) constrains numbers to their type's range.
+fn pydantic_type(typing: &Type) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("Any"),
+        Type::List(typing) => {
+            let typing = pydantic_type(typing.as_ref());
+            format!("list[{typing}]").into()
+        }
+        Type::Map(typing) => {
+            let key_typing = pydantic_type(&typing.as_ref().0);
+            let value_typing = pydantic_type(&typing.as_ref().1);
+            format!("dict[{key_typing}, {value_typing}]").into()
+        }
+        typing => match python_bounds(typing) {
+            Some((min, max)) => format!(
+                "Annotated[{}, Field(ge={min}, le={max})]",
+                python_type(typing)
+            )
+            .into(),
+            None => python_type(typing),
+        },
+    }
+}
+
+/// Returns the inclusive `(min, max)` bounds
+/// of `type`, iff `type` is numeric.
+fn python_bounds(typing: &Type) -> Option<(&'static str, &'static str)> {
+    match typing {
+        Type::U8 => Some(("0", "255")),
+        Type::U16 => Some(("0", "65535")),
+        Type::U32 => Some(("0", "4294967295")),
+        Type::U64 | Type::UVar => Some(("0", "18446744073709551615")),
+        Type::I8 => Some(("-128", "127")),
+        Type::I16 => Some(("-32768", "32767")),
+        Type::I32 => Some(("-2147483648", "2147483647")),
+        Type::I64 | Type::IVar | Type::Timestamp => {
+            Some(("-9223372036854775808", "9223372036854775807"))
+        }
+        Type::U128 => Some(("0", "340282366920938463463374607431768211455")),
+        Type::I128 => Some((
+            "-170141183460469231731687303715884105728",
+            "170141183460469231731687303715884105727",
+        )),
+        Type::F32 => Some(("-3.4028235e38", "3.4028235e38")),
+        Type::F64 => Some(("-1.7976931348623157e308", "1.7976931348623157e308")),
+        _ => None,
+    }
+}
+
+/// Returns the Python expression of the `default_factory`
+/// for fields of `type`, iff the type's default value is
+/// mutable (and may not be shared between instances).
+fn python_default_factory(typing: &Type) -> Option<Text> {
+    match typing {
+        Type::Data(typing) => Some(typing.name.clone()),
+        Type::List(_) => Some(Text::Static("list")),
+        Type::Map(_) => Some(Text::Static("dict")),
+        _ => None,
+    }
+}
+
+/// Returns `text` with every line indented
+/// by `depth` levels of Python indentation.
+fn python_indent(text: &str, depth: usize) -> Text {
+    let indentation = "    ".repeat(depth);
+    let mut indented = alloc::string::String::with_capacity(text.len());
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            indented.push('\n');
+        }
+        if !line.trim().is_empty() {
+            indented += &indentation;
+            indented += line;
+        }
+    }
+    indented.into()
+}
+
 /// Returns the Python literal of `type`'s default value.
 fn python_default_val(typing: &Type) -> Text {
     match typing {
@@ -177,16 +748,96 @@ fn python_default_val(typing: &Type) -> Text {
         Type::I16 => Text::Static("0"),
         Type::I32 => Text::Static("0"),
         Type::I64 => Text::Static("0"),
+        Type::U128 => Text::Static("0"),
+        Type::I128 => Text::Static("0"),
+        Type::UVar => Text::Static("0"),
+        Type::IVar => Text::Static("0"),
         Type::F32 => Text::Static("0.0"),
         Type::F64 => Text::Static("0.0"),
         Type::Bool => Text::Static("False"),
         Type::Text => Text::Static("\"\""),
+        Type::Char => Text::Static("\"\\0\""),
+        Type::Bytes => Text::Static("b\"\""),
+        Type::Timestamp => Text::Static("0"),
         Type::Data(typing) => format!("{}()", typing.name.trim()).into(),
+        Type::Enum(typing) => match typing.variant(0) {
+            Some(variant) => format!("{}.{variant}", typing.name).into(),
+            None => format!("{}(0)", typing.name).into(),
+        },
         Type::List(_) => Text::Static("[]"),
         Type::Map(_) => Text::Static("{}"),
     }
 }
 
+/// Returns the Python literal of the default
+/// value declared by `field`, if any.
+fn python_field_default(field: &DataField) -> Option<Text> {
+    Some(match field.default.as_ref()? {
+        Unspecified::U8(value) => format!("{value}").into(),
+        Unspecified::U16(value) => format!("{value}").into(),
+        Unspecified::U32(value) => format!("{value}").into(),
+        Unspecified::U64(value) => format!("{value}").into(),
+        Unspecified::U128(value) => format!("{value}").into(),
+        Unspecified::I8(value) => format!("{value}").into(),
+        Unspecified::I16(value) => format!("{value}").into(),
+        Unspecified::I32(value) => format!("{value}").into(),
+        Unspecified::I64(value) => format!("{value}").into(),
+        Unspecified::I128(value) => format!("{value}").into(),
+        Unspecified::UVar(value) => format!("{}", value.0).into(),
+        Unspecified::IVar(value) => format!("{}", value.0).into(),
+        Unspecified::Timestamp(value) => format!("{value}").into(),
+        Unspecified::F32(value) => format!("{value:?}").into(),
+        Unspecified::F64(value) => format!("{value:?}").into(),
+        Unspecified::Bool(true) => Text::Static("True"),
+        Unspecified::Bool(false) => Text::Static("False"),
+        Unspecified::Char(value) => python_string(value.encode_utf8(&mut [0; 4])),
+        Unspecified::Text(value) => python_string(value),
+        Unspecified::Enum { typing, variant } => {
+            format!("{}.{}", typing.name, typing.variant(*variant)?).into()
+        }
+        _ => return None,
+    })
+}
+
+/// Returns the Python statement warning that `field`
+/// of `typing` is deprecated, if it is.
+fn python_deprecation_warning(typing: &DataType, field: &DataField) -> Option<Text> {
+    if !field.deprecated {
+        return None;
+    }
+
+    let mut message = format!("`{}.{}` is deprecated", typing.name, field.name);
+    if let Some(note) = &field.deprecation_note {
+        message += &format!(": {note}");
+    }
+
+    Some(
+        format!(
+            "warnings.warn({}, DeprecationWarning, stacklevel=2)",
+            python_string(&message)
+        )
+        .into(),
+    )
+}
+
+/// Returns `value` as a quoted and escaped Python string.
+fn python_string(value: &str) -> Text {
+    let mut string = alloc::string::String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => string += "\\\"",
+            '\\' => string += "\\\\",
+            '\n' => string += "\\n",
+            '\r' => string += "\\r",
+            '\t' => string += "\\t",
+            c if c.is_control() => string += &format!("\\u{:04x}", c as u32),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string.into()
+}
+
 /// Returns a Python fragment enforcing the
 /// expected invariatns for a type.
 ///
@@ -206,7 +857,7 @@ fn python_type_check(typing: &Type) -> Option<Text> {
         Type::U32 => Some(Text::Static(
             "if not 0 <= value <= 4294967295: raise ValueError(\"u32 must be >= 0 and <= 4294967295\")",
         )),
-        Type::U64 => Some(Text::Static(
+        Type::U64 | Type::UVar => Some(Text::Static(
             "if not 0 <= value <= 18446744073709551615: raise ValueError(\"u64 must be >= 0 and <= 18446744073709551615\")",
         )),
         Type::I8 => Some(Text::Static(
@@ -218,9 +869,15 @@ fn python_type_check(typing: &Type) -> Option<Text> {
         Type::I32 => Some(Text::Static(
             "if not -2147483648 <= value <= 2147483647: raise ValueError(\"i32 must be >= -2147483648 and <= 2147483647\")",
         )),
-        Type::I64 => Some(Text::Static(
+        Type::I64 | Type::IVar => Some(Text::Static(
             "if not -9223372036854775808 <= value <= 9223372036854775807: raise ValueError(\"i64 must be >= -9223372036854775808 and <= 9223372036854775807\")",
         )),
+        Type::U128 => Some(Text::Static(
+            "if not 0 <= value <= 340282366920938463463374607431768211455: raise ValueError(\"u128 must be >= 0 and <= 340282366920938463463374607431768211455\")",
+        )),
+        Type::I128 => Some(Text::Static(
+            "if not -170141183460469231731687303715884105728 <= value <= 170141183460469231731687303715884105727: raise ValueError(\"i128 must be >= -170141183460469231731687303715884105728 and <= 170141183460469231731687303715884105727\")",
+        )),
         Type::F32 => Some(Text::Static(
             "if not -3.4028235e38 <= value <= 3.4028235e38: raise ValueError(\"f32 must be >= -3.4028235e38 and <= 3.4028235e38\")",
         )),
@@ -229,7 +886,15 @@ fn python_type_check(typing: &Type) -> Option<Text> {
         )),
         Type::Bool => None,
         Type::Text => None,
+        Type::Char => Some(Text::Static(
+            "if len(value) != 1: raise ValueError(\"char must be a single character\")",
+        )),
+        Type::Bytes => None,
+        Type::Timestamp => Some(Text::Static(
+            "if not -9223372036854775808 <= value <= 9223372036854775807: raise ValueError(\"timestamp must be >= -9223372036854775808 and <= 9223372036854775807\")",
+        )),
         Type::Data(_) => None,
+        Type::Enum(_) => None,
         Type::List(_) => None,
         Type::Map(_) => None,
     }
@@ -251,11 +916,19 @@ fn python_type(typing: &Type) -> Text {
         Type::I16 => Text::Static("int"),
         Type::I32 => Text::Static("int"),
         Type::I64 => Text::Static("int"),
+        Type::U128 => Text::Static("int"),
+        Type::I128 => Text::Static("int"),
+        Type::UVar => Text::Static("int"),
+        Type::IVar => Text::Static("int"),
         Type::F32 => Text::Static("float"),
         Type::F64 => Text::Static("float"),
         Type::Bool => Text::Static("bool"),
         Type::Text => Text::Static("str"),
+        Type::Char => Text::Static("str"),
+        Type::Bytes => Text::Static("bytes"),
+        Type::Timestamp => Text::Static("int"),
         Type::Data(typing) => typing.name.clone(),
+        Type::Enum(typing) => typing.name.clone(),
         Type::List(typing) => {
             let typing = python_type(typing.as_ref());
             format!("list[{typing}]").into()
@@ -267,3 +940,243 @@ fn python_type(typing: &Type) -> Text {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse::{parse, tests::TEST_CODA_MARKDOWN};
+
+    #[test]
+    fn maps_bytes() {
+        assert_eq!("bytes", &*python_type(&Type::Bytes));
+        assert_eq!("b\"\"", &*python_default_val(&Type::Bytes));
+        assert_eq!(None, python_type_check(&Type::Bytes));
+        assert_eq!(None, python_default_factory(&Type::Bytes));
+    }
+
+    #[test]
+    fn maps_timestamps() {
+        assert_eq!("int", &*python_type(&Type::Timestamp));
+        assert_eq!("0", &*python_default_val(&Type::Timestamp));
+        assert!(python_type_check(&Type::Timestamp)
+            .unwrap()
+            .starts_with("if not -9223372036854775808 <= value <= 9223372036854775807:"));
+        assert_eq!(
+            "Annotated[int, Field(ge=-9223372036854775808, le=9223372036854775807)]",
+            &*pydantic_type(&Type::Timestamp)
+        );
+    }
+
+    #[test]
+    fn generates_timestamp_accessors() {
+        let coda = parse(
+            "# `Log` Coda\n\n## `Entry` Data\n\n+ `created_at` timestamp\n+ `deleted_at` optional timestamp",
+        )
+        .unwrap();
+
+        let mut python = vec![];
+        generate_types(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.contains("\n        self._created_at = 0\n"));
+        assert!(python.contains(
+            "\n    def created_at(self, value: int):\n        if not -9223372036854775808 <= value <= 9223372036854775807:"
+        ));
+        assert!(python.contains("\n    def created_at_datetime(self) -> \"datetime.datetime\":\n"));
+        assert!(python.contains(
+            "\n        return datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc) + datetime.timedelta(milliseconds=value)\n"
+        ));
+        assert!(python.contains(
+            "\n    def deleted_at_datetime(self, value: \"datetime.datetime | None\"):\n        import datetime\n        self.deleted_at = None if value is None else (value - "
+        ));
+
+        let mut python = vec![];
+        generate_stubs(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.starts_with("import datetime\nfrom typing import Dict, List, Optional\n"));
+        assert!(python.contains("def created_at_datetime(self) -> datetime.datetime: ..."));
+        assert!(python.contains(
+            "def deleted_at_datetime(self, value: Optional[datetime.datetime]) -> None: ..."
+        ));
+    }
+
+    #[test]
+    fn generates_stubs() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut python = vec![];
+        generate_stubs(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+
+        assert!(python
+            .starts_with("from typing import Dict, List, Optional\n\nclass MyCodaData: ...\n"));
+        assert!(python
+            .contains("\nclass Unspecified(MyCodaData):\n    def __init__(self) -> None: ...\n"));
+        assert!(python.contains(
+            "\n    @property\n    def listy_field(self) -> List[str]: ...\n    @listy_field.setter\n    def listy_field(self, value: List[str]) -> None: ...\n"
+        ));
+        assert!(python.contains("def optional_field(self) -> Optional[int]: ..."));
+        assert!(python.contains("def nested_field(self) -> MyNestedDataType: ..."));
+        assert!(python.contains("def map_field(self) -> Dict[str, int]: ..."));
+        assert!(python.contains("def unspecified_field(self) -> object: ..."));
+        assert!(python.contains("def f_3d_field(self) -> List[List[List[int]]]: ..."));
+
+        let coda = parse("# `Files` Coda\n\n## `File` Data\n\n+ `class` one of [Text, Binary]\n+ `contents` bytes\n+ `modified` optional timestamp\n").unwrap();
+        let mut python = vec![];
+        generate_stubs(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.starts_with(
+            "import datetime\nimport enum\nfrom typing import Dict, List, Optional\n"
+        ));
+        assert!(python.contains("\nclass FileClass(enum.Enum):\n    Text = 0\n    Binary = 1\n"));
+        assert!(python.contains("def f_class(self) -> FileClass: ..."));
+        assert!(python.contains("def contents(self) -> bytes: ..."));
+        assert!(python.contains("def modified(self) -> Optional[int]: ..."));
+        assert!(python.contains(
+            "def modified_datetime(self, value: Optional[datetime.datetime]) -> None: ..."
+        ));
+    }
+
+    #[test]
+    fn generates_enums() {
+        let coda = parse(
+            "# `Accounts` Coda\n\n## `User` Data\n\n+ `status` one of [Active, Suspended, Deleted]\n+ `role` optional one of [Admin]",
+        )
+        .unwrap();
+
+        let mut python = vec![];
+        generate_types(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.starts_with("import enum\n\n"));
+        assert!(python.contains(
+            "\nclass UserStatus(enum.Enum):\n    \"\"\"\n    Variants of `UserStatus`, valued by their ordinals.\n    \"\"\"\n    Active = 0\n    Suspended = 1\n    Deleted = 2\n"
+        ));
+        assert!(python.contains("\n        self._status = UserStatus.Active\n"));
+        assert!(python.contains("\n    def role(self) -> UserRole | None:\n"));
+
+        let mut python = vec![];
+        generate_types_dataclass(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.contains("\nimport dataclasses\nimport enum\nfrom typing import Optional\n"));
+        assert!(python.contains("\n    status: UserStatus = UserStatus.Active\n"));
+
+        let mut python = vec![];
+        generate_pydantic(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.contains("\nimport enum\nfrom typing import Annotated,"));
+        assert!(python.contains("\nclass UserRole(enum.Enum):\n"));
+        assert!(python.contains("\n    role: Optional[UserRole] = None\n"));
+
+        let mut python = vec![];
+        generate_stubs(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.starts_with("import enum\nfrom typing import Dict, List, Optional\n"));
+        assert!(python.contains("\nclass UserRole(enum.Enum):\n    Admin = 0\n"));
+        assert!(python.contains("def status(self) -> UserStatus: ..."));
+    }
+
+    #[test]
+    fn generates_deprecations() {
+        let coda = parse("# `Users` Coda\n\n## `Account` Data\n\n+ `name` text\n\n## `User` Data\n\n> Deprecated: Use \"Account\".\n\n+ `id` u32\n+ `old_id` deprecated u64\n+ `nickname` optional deprecated text\n\n    > Deprecated: Use `Account.name`.\n\n+ `account` deprecated Account\n").unwrap();
+        let mut python = vec![];
+        generate_types(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+
+        assert!(python.contains("\n    def old_id(self, value: int):\n        import warnings\n        warnings.warn(\"`User.old_id` is deprecated\", DeprecationWarning, stacklevel=2)\n        if not "));
+        assert!(python.contains("\n        warnings.warn(\"`User.nickname` is deprecated: Use `Account.name`.\", DeprecationWarning, stacklevel=2)\n        if value is None:\n"));
+        assert!(!python.contains("def id(self, value: int):\n        import warnings"));
+        assert_eq!(3, python.matches("warnings.warn(").count());
+    }
+
+    #[test]
+    fn generates_defaults() {
+        let coda = parse("# `Jobs` Coda\n\n## `Job` Data\n\n+ `retries` u32 = 3\n+ `total` u128 = 12\n+ `name` optional text = \"the \\\"first\\\" job\"\n+ `status` one of [Queued, Running] = Running\n+ `started_at` timestamp = 1700000000000\n").unwrap();
+
+        let mut python = vec![];
+        generate_types(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.contains("\n        self._retries = 3\n"));
+        assert!(python.contains("\n        self._name = \"the \\\"first\\\" job\"\n"));
+        assert!(python.contains("\n        self._status = JobStatus.Running\n"));
+        assert!(python.contains("\n        self._started_at = 1700000000000\n"));
+
+        let mut python = vec![];
+        generate_types_dataclass(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.contains("\n    retries: int = 3\n"));
+        assert!(python.contains("\n    name: Optional[str] = \"the \\\"first\\\" job\"\n"));
+
+        let mut python = vec![];
+        generate_pydantic(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+        assert!(python.contains("\n    status: JobStatus = JobStatus.Running\n"));
+        assert!(python.contains("\n    total: Annotated[int, Field(ge=0, le=340282366920938463463374607431768211455)] = 12\n"));
+    }
+
+    #[test]
+    fn generates_dataclasses() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut python = vec![];
+        generate_types_dataclass(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+
+        assert!(python.starts_with("from __future__ import annotations\n"));
+        assert!(python.contains(
+            "\n# Data 2\n@dataclasses.dataclass\nclass MyDataType(MyCodaData):\n    \"\"\"\n    An example Markdown Data Type.\n    \"\"\"\n"
+        ));
+        assert!(python.contains("\n    textual_field: str = \"\"\n"));
+        assert!(python.contains(
+            "\n    nested_field: MyNestedDataType = dataclasses.field(default_factory=MyNestedDataType)\n"
+        ));
+        assert!(python.contains("\n    optional_field: Optional[int] = None\n"));
+        assert!(python.contains(
+            "\n    map_field: dict[str, int] = dataclasses.field(default_factory=dict)\n"
+        ));
+
+        // Range checks are performed after initialization.
+        assert!(python.contains(
+            "\n    def __post_init__(self):\n        value = self.integral_field\n        if not -2147483648 <= value <= 2147483647:"
+        ));
+        assert!(python.contains(
+            "\n        value = self.optional_field\n        if value is not None:\n            if not 0 <= value <= 18446744073709551615:"
+        ));
+    }
+
+    #[test]
+    fn generates_pydantic_models() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut python = vec![];
+        generate_pydantic(&coda, &mut python).unwrap();
+        let python = alloc::string::String::from_utf8_lossy(&python);
+
+        assert!(python.starts_with("from __future__ import annotations\n"));
+        assert!(python.contains(
+            "\nclass MyCodaModel(BaseModel):\n    \"\"\"\n    An example Markdown Coda.\n    \"\"\"\n    model_config = ConfigDict(populate_by_name=True)\n"
+        ));
+        assert!(python.contains(
+            "\n# Data 2\nclass MyDataType(MyCodaModel):\n    \"\"\"\n    An example Markdown Data Type.\n    \"\"\"\n\n    codas_ordinal: Literal[2] = Field(default=2, alias=\"__ordinal\")\n"
+        ));
+
+        // Numbers are constrained to their type's range.
+        assert!(python.contains(
+            "\n    integral_field: Annotated[int, Field(ge=-2147483648, le=2147483647)] = 0\n"
+        ));
+        assert!(python.contains(
+            "\n    optional_field: Optional[Annotated[int, Field(ge=0, le=18446744073709551615)]] = None\n"
+        ));
+        assert!(python.contains(
+            "\n    map_field: dict[str, Annotated[int, Field(ge=-2147483648, le=2147483647)]] = Field(default_factory=dict)\n"
+        ));
+        assert!(python.contains(
+            "\n    nested_field: MyNestedDataType = Field(default_factory=MyNestedDataType)\n"
+        ));
+        assert!(python.contains("\n    unspecified_field: Any = None\n"));
+
+        // The coda is a union discriminated by ordinal.
+        assert!(python.contains(
+            "\nclass MyCodaData(RootModel[Union[Unspecified, MyNestedDataType, MyDataType]]):\n"
+        ));
+        assert!(python.contains(
+            "\n    root: Annotated[Union[Unspecified, MyNestedDataType, MyDataType], Field(discriminator=\"codas_ordinal\")]\n"
+        ));
+    }
+}
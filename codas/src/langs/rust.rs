@@ -8,7 +8,17 @@
 //! - An `enum` containing variants for each data
 //!   type documented by the coda.
 //! - A `struct` for each data type.
-//! - Codecs for the `enum` and every `struct`.
+//! - A `#[non_exhaustive]` `enum` for each enum declared
+//!   by a field, convertable to and from the `u16`
+//!   ordinals of its variants.
+//! - Codecs for the `enum`s and every `struct`.
+//! - A `data_type` function for every `struct`,
+//!   returning the struct's `DataType`.
+//!
+//! Structs derive `Default`, unless any of their fields
+//! declare a default value: those structs implement
+//! `Default` by initializing each field to its declared
+//! default value (or its type's default value, if none).
 //!
 //! The code generated by this implementation assumes
 //! that the `codas` crate (with _at least_ `default`
@@ -16,21 +26,69 @@
 use alloc::{format, string::String, vec};
 
 use crate::{
-    codec::WritesEncodable,
+    codec::{Format, WritesEncodable},
     stream::{StreamError, Writes},
-    types::{Coda, Text, Type, Unspecified},
+    types::{Coda, EnumType, Text, Type, Unspecified},
 };
 
+/// Options for [`generate_types`].
+#[derive(Default, Debug, Clone)]
+pub struct RustCodegenOptions {
+    /// Iff true, the generated types will be convertable
+    /// to and from serde-equivalent types.
+    pub serde: bool,
+
+    /// Iff true, the generated types will
+    /// implement [`core::fmt::Display`].
+    ///
+    /// Structs are displayed like `Name { field: value }`,
+    /// where each field is displayed with its own `Display`
+    /// implementation if it has one, or its `Debug`
+    /// implementation otherwise (like lists, maps, optional
+    /// fields, and unspecified data). The coda enum is
+    /// displayed as its variant's ordinal, followed by
+    /// the variant's data.
+    pub display: bool,
+
+    /// Iff true, a `NameBuilder` will be generated for
+    /// each generated struct `Name`, with a consuming
+    /// setter method for each field and a `build`
+    /// method returning the built struct.
+    pub builder_pattern: bool,
+
+    /// Iff true, the generated coda `enum` will decode
+    /// data with ordinals unknown to the coda into its
+    /// `Unspecified` variant, preserving the data's header
+    /// and encoded bytes so they can be re-encoded verbatim.
+    ///
+    /// Otherwise, decoding data with unknown
+    /// ordinals returns an error.
+    pub preserve_unknown: bool,
+
+    /// Path of the `codas` crate, prefixing all
+    /// references to it in the generated code.
+    ///
+    /// If `None`, the path is `codas`.
+    pub codas_path: Option<Text>,
+
+    /// Path of the module the generated code is placed
+    /// in, prefixing references to the generated types.
+    ///
+    /// If `None`, the path is `self`.
+    pub module_path: Option<Text>,
+}
+
 /// Generates the rust types for `coda`,
 /// writing them to `stream`.
-///
-/// Iff `with_serde`, the generated types
-/// will be convertable to and from serde-equivalent types.
 pub fn generate_types(
     coda: &Coda,
     stream: &mut impl Writes,
-    with_serde: bool,
+    options: &RustCodegenOptions,
 ) -> Result<(), StreamError> {
+    // Resolve paths referenced by the generated code.
+    let codas = options.codas_path.as_deref().unwrap_or("codas");
+    let module = options.module_path.as_deref().unwrap_or("self");
+
     // Extract coda metadata.
     let coda_type_name = format!("{}Data", coda.local_name.trim());
     let coda_type_docs = match &coda.docs {
@@ -41,6 +99,17 @@ pub fn generate_types(
     // Escape double-quotes in docs.
     let coda_type_docs = coda_type_docs.replace('"', "\\\"");
 
+    // Generated code refers to deprecated types and fields
+    // internally, which shouldn't warn on their own.
+    let allow_deprecated = if coda
+        .iter()
+        .any(|typing| typing.deprecated || typing.iter().any(|field| field.deprecated))
+    {
+        "#[allow(deprecated)]\n"
+    } else {
+        ""
+    };
+
     // Track the generated struct and variant fragments.
     let mut enum_variants = vec![];
     let mut enum_variant_ordinals_raw = vec![];
@@ -49,18 +118,15 @@ pub fn generate_types(
     let mut enum_variant_header_encoders = vec![];
     let mut enum_variant_decoders = vec![];
     let mut enum_variant_converters = vec![];
+    let mut enum_variant_displays = vec![];
     let mut type_structs = vec![];
 
     // Extract data types, which implicitly include
     // the [`Unspecified`] type.
-    for (expected_ordinal, typing) in [Unspecified::DATA_TYPE]
-        .iter()
-        .chain(coda.iter())
-        .enumerate()
-    {
-        // Validate ordinal.
-        let type_ordinal = typing.format().as_data_format().ordinal;
-        assert_eq!(expected_ordinal, type_ordinal as usize);
+    for typing in coda.iter_all() {
+        // Extract ordinal, which may not
+        // follow the previous type's ordinal.
+        let type_ordinal = typing.ordinal();
         enum_variant_ordinals_raw.push(type_ordinal);
 
         // Extract type metadata.
@@ -78,17 +144,25 @@ pub fn generate_types(
         // we don't re-generate it for every coda.
         let (struct_fqn, struct_name) = if type_ordinal == 0 {
             (
-                "codas::types::Unspecified".into(),
+                format!("{codas}::types::Unspecified"),
                 Text::from("Unspecified"),
             )
         } else {
-            (format!("self::{type_name}"), type_name.clone())
+            (format!("{module}::{type_name}"), type_name.clone())
         };
 
         // Extract field name-type pairings.
+        let blob_field_count = typing
+            .iter()
+            .filter(|field| !field.optional && !field.typing.format().is_structured())
+            .count();
         let mut type_fields = vec![];
+        let mut display_fields = vec![];
+        let mut dynamic_fields = vec![];
+        let mut default_fields = vec![];
+        let mut deprecated_fields = vec![];
         for field in typing.iter() {
-            let mut field_type = get_rust_type(&field.typing);
+            let mut field_type = get_rust_type(&field.typing, codas);
             if field.optional {
                 field_type = format!("Option<{field_type}>").into();
             }
@@ -99,7 +173,24 @@ pub fn generate_types(
                 field.docs.clone(),
                 field.flattened,
             ));
+            display_fields.push((
+                field.name.clone(),
+                !field.optional && is_rust_displayable(&field.typing),
+            ));
+            dynamic_fields.push((field.name.clone(), field.optional));
+            default_fields.push((
+                field.name.clone(),
+                field
+                    .default
+                    .as_ref()
+                    .and_then(|default| get_rust_value(default, codas)),
+            ));
+            deprecated_fields.push(get_rust_deprecation(
+                field.deprecated,
+                &field.deprecation_note,
+            ));
         }
+        let has_defaults = default_fields.iter().any(|(_, default)| default.is_some());
 
         // Generate enum variant.
         let enum_variant = format!(
@@ -150,13 +241,13 @@ pub fn generate_types(
         // Generate enum variant converters.
         let enum_converter = format!(
             r#"
-            impl From<{struct_fqn}> for {coda_type_name} {{
+            {allow_deprecated}impl From<{struct_fqn}> for {coda_type_name} {{
                 fn from(data: {struct_fqn}) -> {coda_type_name} {{
                     {coda_type_name}::{struct_name}(data)
                 }}
             }}
 
-            impl codas::types::TryAsFormat<{struct_fqn}> for {coda_type_name} {{
+            {allow_deprecated}impl {codas}::types::TryAsFormat<{struct_fqn}> for {coda_type_name} {{
                 type Error = u8;
 
                 fn try_as_format(&self) -> Result<&{struct_fqn}, Self::Error> {{
@@ -170,6 +261,17 @@ pub fn generate_types(
         );
         enum_variant_converters.push(enum_converter);
 
+        // Generate enum variant display.
+        let data_display = if type_ordinal == 0 { "{:?}" } else { "{}" };
+        let enum_display = format!(
+            r#"
+            Self::{struct_name}(data) => {{
+                write!(f, "{type_ordinal}: {data_display}", data)
+            }}
+        "#
+        );
+        enum_variant_displays.push(enum_display);
+
         // If this type is Unspecified, don't generate
         // any struct or codec; it already exists.
         if type_ordinal == 0 {
@@ -179,19 +281,33 @@ pub fn generate_types(
         // Generate struct and codec.
         let mut type_struct = String::default();
         type_struct += &format!("#[doc = \"{type_docs}\"]\n");
-        if with_serde {
+        if let Some(deprecation) = get_rust_deprecation(typing.deprecated, &typing.deprecation_note)
+        {
+            type_struct += &deprecation;
+        }
+        if options.serde {
             type_struct += "#[derive(serde::Serialize, serde::Deserialize)]\n";
         }
-        type_struct += "#[derive(Default, Clone, Debug, PartialEq)]\n";
+        if has_defaults {
+            type_struct += "#[derive(Clone, Debug, PartialEq)]\n";
+        } else {
+            type_struct += "#[derive(Default, Clone, Debug, PartialEq)]\n";
+        }
         type_struct += &format!("pub struct {struct_name} {{\n");
-        for (name, typing, docs, flattened) in &type_fields {
+        for ((name, typing, docs, flattened), deprecation) in
+            type_fields.iter().zip(&deprecated_fields)
+        {
             if let Some(docs) = docs {
                 // Escape double-quotes in docs.
                 let docs = docs.replace('"', "\\\"");
                 type_struct += &format!("#[doc = \"{docs}\"]\n");
             }
 
-            if *flattened && with_serde {
+            if let Some(deprecation) = deprecation {
+                type_struct += deprecation;
+            }
+
+            if *flattened && options.serde {
                 type_struct += "#[serde(flatten)]\n";
             }
 
@@ -199,22 +315,44 @@ pub fn generate_types(
         }
         type_struct += "}";
 
+        // Default impl.
+        if has_defaults {
+            type_struct += allow_deprecated;
+            type_struct += &format!("impl Default for {struct_name} {{\n");
+            type_struct += "fn default() -> Self {\n";
+            type_struct += "Self {\n";
+            for ((name, default), (_, optional)) in default_fields.iter().zip(&dynamic_fields) {
+                match default {
+                    Some(default) if *optional => {
+                        type_struct += &format!("{name}: Some({default}),\n")
+                    }
+                    Some(default) => type_struct += &format!("{name}: {default},\n"),
+                    None => type_struct += &format!("{name}: Default::default(),\n"),
+                }
+            }
+            type_struct += "}\n";
+            type_struct += "}\n";
+            type_struct += "}\n";
+        }
+
         // Encoder impl.
-        type_struct += &format!("impl codas::codec::Encodable for {struct_name} {{\n");
+        type_struct += allow_deprecated;
+        type_struct += &format!("impl {codas}::codec::Encodable for {struct_name} {{\n");
 
         // `FORMAT`
         type_struct += &format!(
-            "const FORMAT: codas::codec::Format = codas::codec::Format::data({type_ordinal})"
+            "const FORMAT: {codas}::codec::Format = {codas}::codec::Format::data({type_ordinal})"
         );
         for (_, typing, _, _) in &type_fields {
-            type_struct += &format!("\n.with(<{typing} as codas::codec::Encodable>::FORMAT)");
+            type_struct += &format!("\n.with(<{typing} as {codas}::codec::Encodable>::FORMAT)");
         }
         type_struct += ";\n";
 
         // `fn encode`
-        type_struct +=
-            "fn encode(&self, writer: &mut (impl codas::codec::WritesEncodable + ?Sized),)\n";
-        type_struct += "-> core::result::Result<(), codas::codec::CodecError> {\n";
+        type_struct += &format!(
+            "fn encode(&self, writer: &mut (impl {codas}::codec::WritesEncodable + ?Sized),)\n"
+        );
+        type_struct += &format!("-> core::result::Result<(), {codas}::codec::CodecError> {{\n");
         for (name, _, _, _) in &type_fields {
             type_struct += &format!("writer.write_data(&self.{name})?;\n");
         }
@@ -223,29 +361,192 @@ pub fn generate_types(
         type_struct += "}\n";
 
         // Decoder impl.
-        type_struct += &format!("impl codas::codec::Decodable for {struct_name} {{\n");
+        type_struct += allow_deprecated;
+        type_struct += &format!("impl {codas}::codec::Decodable for {struct_name} {{\n");
 
         // `fn decode`
         type_struct += "fn decode(\n";
         type_struct += "&mut self,\n";
-        type_struct += "reader: &mut (impl codas::codec::ReadsDecodable + ?Sized),\n";
-        type_struct += "header: Option<codas::codec::DataHeader>,\n";
-        type_struct += ") -> core::result::Result<(), codas::codec::CodecError> {\n";
-        type_struct += &format!("let _ = Self::ensure_header(header, &[{type_ordinal}])?;\n");
-        for (name, _, _, _) in &type_fields {
+        type_struct += &format!("reader: &mut (impl {codas}::codec::ReadsDecodable + ?Sized),\n");
+        type_struct += &format!("header: Option<{codas}::codec::DataHeader>,\n");
+        type_struct += &format!(") -> core::result::Result<(), {codas}::codec::CodecError> {{\n");
+        type_struct += "let header = Self::ensure_format(header)?;\n";
+        let format = format!("<Self as {codas}::codec::Encodable>::FORMAT");
+        for (i, (name, _, _, _)) in type_fields.iter().enumerate() {
+            // Skip any blob fields unknown to this decoder,
+            // which are encoded after all known blob fields.
+            if i == blob_field_count {
+                type_struct += &format!("reader.skip_excess_blob(&header, {format})?;\n");
+            }
             type_struct += &format!("reader.read_data_into(&mut self.{name})?;\n");
         }
+        if blob_field_count == type_fields.len() {
+            type_struct += &format!("reader.skip_excess_blob(&header, {format})?;\n");
+        }
+        type_struct += &format!("reader.skip_excess_data(&header, {format})?;\n");
         type_struct += "Ok(())\n";
         type_struct += "}\n";
         type_struct += "}\n";
 
+        // Builder.
+        if options.builder_pattern {
+            let builder_name = format!("{struct_name}Builder");
+            type_struct += &format!("#[doc = \"Builder of [`{struct_name}`]s.\"]\n");
+            type_struct += allow_deprecated;
+            type_struct += "#[derive(Default, Clone, Debug, PartialEq)]\n";
+            type_struct += &format!("pub struct {builder_name} {{\n");
+            type_struct += &format!("data: {struct_name},\n");
+            type_struct += "}\n";
+
+            type_struct += allow_deprecated;
+
+            type_struct += &format!("impl {builder_name} {{\n");
+            for (name, typing, _, _) in &type_fields {
+                type_struct += &format!("#[doc = \"Sets [`{struct_name}::{name}`].\"]\n");
+                type_struct += &format!("pub fn {name}(mut self, {name}: {typing}) -> Self {{\n");
+                type_struct += &format!("self.data.{name} = {name};\n");
+                type_struct += "self\n";
+                type_struct += "}\n";
+            }
+            type_struct += &format!("#[doc = \"Returns the built [`{struct_name}`].\"]\n");
+            type_struct += &format!("pub fn build(self) -> {struct_name} {{\n");
+            type_struct += "self.data\n";
+            type_struct += "}\n";
+            type_struct += "}\n";
+
+            type_struct += allow_deprecated;
+
+            type_struct += &format!("impl {struct_name} {{\n");
+            type_struct += &format!("#[doc = \"Returns a new [`{builder_name}`].\"]\n");
+            type_struct += &format!("pub fn builder() -> {builder_name} {{\n");
+            type_struct += &format!("{builder_name}::default()\n");
+            type_struct += "}\n";
+            type_struct += "}\n";
+        }
+
+        // Data type.
+        type_struct += allow_deprecated;
+        type_struct += &format!("impl {struct_name} {{\n");
+        type_struct += &format!("#[doc = \"Returns the data type of [`{struct_name}`]s.\"]\n");
+        type_struct += &format!("pub fn data_type() -> {codas}::types::DataType {{\n");
+        type_struct += &format!(
+            "{codas}::types::DataType::new({}, {}, {type_ordinal}, &[], &[])\n",
+            get_rust_text(type_name, codas),
+            get_rust_docs(&typing.docs, codas),
+        );
+        for (field, (_, default)) in typing.iter().zip(&default_fields) {
+            type_struct += &format!(".with({codas}::types::DataField {{\n");
+            type_struct += &format!("name: {},\n", get_rust_text(&field.name, codas));
+            type_struct += &format!("docs: {},\n", get_rust_docs(&field.docs, codas));
+            type_struct += &format!("typing: {},\n", get_rust_typing(&field.typing, codas));
+            type_struct += &format!("optional: {},\n", field.optional);
+            type_struct += &format!("flattened: {},\n", field.flattened);
+            match default {
+                Some(default) => {
+                    type_struct += &format!(
+                        "default: Some({codas}::types::dynamic::DynamicValue::to_dynamic(&{default})),\n"
+                    )
+                }
+                None => type_struct += "default: None,\n",
+            }
+            type_struct += &format!("deprecated: {},\n", field.deprecated);
+            type_struct += &format!(
+                "deprecation_note: {},\n",
+                get_rust_docs(&field.deprecation_note, codas)
+            );
+            type_struct += "})\n";
+        }
+        type_struct += "}\n";
+        type_struct += "}\n";
+
+        // Dynamic conversions.
+        type_struct += allow_deprecated;
+        type_struct +=
+            &format!("impl {codas}::types::dynamic::DynamicValue for {struct_name} {{\n");
+        type_struct += &format!("fn to_dynamic(&self) -> {codas}::types::Unspecified {{\n");
+        type_struct +=
+            &format!("{codas}::types::Unspecified::Map(core::iter::IntoIterator::into_iter([\n");
+        for (name, _) in &dynamic_fields {
+            type_struct += &format!("({codas}::types::Text::from(\"{name}\"), ");
+            type_struct +=
+                &format!("{codas}::types::dynamic::DynamicValue::to_dynamic(&self.{name})),\n");
+        }
+        type_struct += "]).collect())\n";
+        type_struct += "}\n";
+        type_struct += &format!("fn try_from_dynamic(value: &{codas}::types::Unspecified)\n");
+        type_struct += &format!(
+            "-> core::result::Result<Self, {codas}::types::dynamic::TryFromDynamicError> {{\n"
+        );
+        if dynamic_fields.is_empty() {
+            type_struct += &format!("{codas}::types::dynamic::dynamic_fields(value)?;\n");
+        } else {
+            type_struct +=
+                &format!("let fields = {codas}::types::dynamic::dynamic_fields(value)?;\n");
+        }
+        type_struct += "Ok(Self {\n";
+        for (name, optional) in &dynamic_fields {
+            let getter = if *optional {
+                "optional_field"
+            } else {
+                "required_field"
+            };
+            type_struct +=
+                &format!("{name}: {codas}::types::dynamic::{getter}(fields, \"{name}\")?,\n");
+        }
+        type_struct += "})\n";
+        type_struct += "}\n";
+        type_struct += "}\n";
+        type_struct += allow_deprecated;
+        type_struct += &format!("impl From<&{struct_name}> for {codas}::types::Unspecified {{\n");
+        type_struct += &format!("fn from(value: &{struct_name}) -> Self {{\n");
+        type_struct += &format!("{codas}::types::dynamic::DynamicValue::to_dynamic(value)\n");
+        type_struct += "}\n";
+        type_struct += "}\n";
+        type_struct += allow_deprecated;
+        type_struct +=
+            &format!("impl TryFrom<&{codas}::types::Unspecified> for {struct_name} {{\n");
+        type_struct += &format!("type Error = {codas}::types::dynamic::TryFromDynamicError;\n");
+        type_struct += &format!("fn try_from(value: &{codas}::types::Unspecified) -> core::result::Result<Self, Self::Error> {{\n");
+        type_struct += &format!("{codas}::types::dynamic::DynamicValue::try_from_dynamic(value)\n");
+        type_struct += "}\n";
+        type_struct += "}\n";
+
+        // Display impl.
+        if options.display {
+            type_struct += allow_deprecated;
+            type_struct += &format!("impl core::fmt::Display for {struct_name} {{\n");
+            type_struct +=
+                "fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n";
+            type_struct += &format!("write!(f, \"{struct_name}");
+            for (i, (name, displayable)) in display_fields.iter().enumerate() {
+                type_struct += if i == 0 { " {{ " } else { ", " };
+                type_struct += &format!("{name}: {}", if *displayable { "{}" } else { "{:?}" });
+            }
+            if !display_fields.is_empty() {
+                type_struct += " }}";
+            }
+            type_struct += "\"";
+            for (name, _) in &display_fields {
+                type_struct += &format!(", self.{name}");
+            }
+            type_struct += ")\n";
+            type_struct += "}\n";
+            type_struct += "}\n";
+        }
+
         type_structs.push(type_struct);
     }
 
+    // Generate field enums.
+    for typing in coda.enums() {
+        type_structs.push(generate_enum(typing, codas, options));
+    }
+
     // Generate coda enum.
     let mut coda_enum = String::default();
     coda_enum += &format!("#[doc = \"{coda_type_docs}\"]\n");
-    if with_serde {
+    coda_enum += allow_deprecated;
+    if options.serde {
         coda_enum += "#[derive(serde::Serialize, serde::Deserialize)]\n";
     }
     coda_enum += "#[derive(Clone, Debug, PartialEq)]\n";
@@ -259,6 +560,7 @@ pub fn generate_types(
     coda_enum += "}\n";
 
     // Enum impls.
+    coda_enum += allow_deprecated;
     coda_enum += &format!("impl {coda_type_name} {{\n");
 
     // `CODA_BYTES`
@@ -285,14 +587,18 @@ pub fn generate_types(
     coda_enum += "}\n";
 
     // Enum encoder.
-    coda_enum += &format!("impl codas::codec::Encodable for {coda_type_name} {{\n");
+    coda_enum += allow_deprecated;
+    coda_enum += &format!("impl {codas}::codec::Encodable for {coda_type_name} {{\n");
 
     // `FORMAT`
-    coda_enum += "const FORMAT: codas::codec::Format = codas::codec::Format::Fluid;\n";
+    coda_enum +=
+        &format!("const FORMAT: {codas}::codec::Format = {codas}::codec::Format::Fluid;\n");
 
     // `fn encode`
-    coda_enum += "fn encode(&self, writer: &mut (impl codas::codec::WritesEncodable + ?Sized),)\n";
-    coda_enum += "-> core::result::Result<(), codas::codec::CodecError> {\n";
+    coda_enum += &format!(
+        "fn encode(&self, writer: &mut (impl {codas}::codec::WritesEncodable + ?Sized),)\n"
+    );
+    coda_enum += &format!("-> core::result::Result<(), {codas}::codec::CodecError> {{\n");
     coda_enum += "match self {\n";
     for variant in enum_variant_encoders {
         coda_enum += &variant;
@@ -303,9 +609,10 @@ pub fn generate_types(
 
     // `fn encoded_header`
 
-    coda_enum +=
-        "fn encode_header(&self, writer: &mut (impl codas::codec::WritesEncodable + ?Sized),)\n";
-    coda_enum += "-> core::result::Result<(), codas::codec::CodecError> {\n";
+    coda_enum += &format!(
+        "fn encode_header(&self, writer: &mut (impl {codas}::codec::WritesEncodable + ?Sized),)\n"
+    );
+    coda_enum += &format!("-> core::result::Result<(), {codas}::codec::CodecError> {{\n");
     coda_enum += "match self {\n";
     for variant in enum_variant_header_encoders {
         coda_enum += &variant;
@@ -317,21 +624,28 @@ pub fn generate_types(
     coda_enum += "}\n";
 
     // Enum decoder.
-    coda_enum += &format!("impl codas::codec::Decodable for {coda_type_name} {{\n");
+    coda_enum += allow_deprecated;
+    coda_enum += &format!("impl {codas}::codec::Decodable for {coda_type_name} {{\n");
 
     // `fn decode`
     coda_enum += "fn decode(\n";
     coda_enum += "&mut self,\n";
-    coda_enum += "reader: &mut (impl codas::codec::ReadsDecodable + ?Sized),\n";
-    coda_enum += "header: Option<codas::codec::DataHeader>,\n";
-    coda_enum += ") -> core::result::Result<(), codas::codec::CodecError> {\n";
+    coda_enum += &format!("reader: &mut (impl {codas}::codec::ReadsDecodable + ?Sized),\n");
+    coda_enum += &format!("header: Option<{codas}::codec::DataHeader>,\n");
+    coda_enum += &format!(") -> core::result::Result<(), {codas}::codec::CodecError> {{\n");
 
     // Ensure header.
-    coda_enum += "let header = Self::ensure_header(header, &[\n";
-    for ordinal in enum_variant_ordinals_raw {
-        coda_enum += &format!("{ordinal},");
+    if options.preserve_unknown {
+        coda_enum += "let Some(header) = header else {\n";
+        coda_enum += "return Self::ensure_header(header, &[]).map(|_| ());\n";
+        coda_enum += "};\n";
+    } else {
+        coda_enum += "let header = Self::ensure_header(header, &[\n";
+        for ordinal in enum_variant_ordinals_raw {
+            coda_enum += &format!("{ordinal},");
+        }
+        coda_enum += "])?;\n";
     }
-    coda_enum += "])?;\n";
 
     // Decode variants.
     coda_enum += "match header.format.ordinal {\n";
@@ -339,19 +653,46 @@ pub fn generate_types(
         coda_enum += &variant;
         coda_enum += ",\n";
     }
-    coda_enum += "_ => unreachable!(),\n";
+    if options.preserve_unknown {
+        // Unspecified data captures unknown data
+        // types' headers and bytes verbatim.
+        coda_enum += "_ => {\n";
+        coda_enum += &format!("let mut data = {codas}::types::Unspecified::default();\n");
+        coda_enum += "data.decode(reader, Some(header))?;\n";
+        coda_enum += "*self = Self::Unspecified(data);\n";
+        coda_enum += "Ok(())\n";
+        coda_enum += "}\n";
+    } else {
+        coda_enum += "_ => unreachable!(),\n";
+    }
     coda_enum += "}\n";
     coda_enum += "}\n";
 
     coda_enum += "}\n";
 
     // Enum default.
+    coda_enum += allow_deprecated;
     coda_enum += &format!("impl core::default::Default for {coda_type_name} {{\n");
     coda_enum += &format!("fn default() -> {coda_type_name} {{\n");
-    coda_enum += "Self::Unspecified(codas::types::Unspecified::default())\n";
+    coda_enum += &format!("Self::Unspecified({codas}::types::Unspecified::default())\n");
     coda_enum += "}\n";
     coda_enum += "}\n";
 
+    // Enum display.
+    if options.display {
+        coda_enum += allow_deprecated;
+        coda_enum += &format!("impl core::fmt::Display for {coda_type_name} {{\n");
+        coda_enum += "fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n";
+        coda_enum += "match self {\n";
+        for variant in enum_variant_displays {
+            coda_enum += &variant;
+            coda_enum += ",\n";
+        }
+        coda_enum += "}\n";
+        coda_enum += "}\n";
+        coda_enum += "}\n";
+    }
+
     // Generate final code from the enum.
     let mut codegen = coda_enum;
 
@@ -370,14 +711,170 @@ pub fn generate_types(
     stream.write_all(codegen.as_bytes())
 }
 
+/// Returns the native Rust `enum` (and its codecs)
+/// for the enum `typing`.
+///
+/// Decoding an ordinal that isn't one of the enum's
+/// variants returns a [`CodecError::InvalidEnumVariant`](crate::codec::CodecError::InvalidEnumVariant).
+fn generate_enum(typing: &EnumType, codas: &str, options: &RustCodegenOptions) -> String {
+    let enum_name = &typing.name;
+    let variant_list: vec::Vec<String> = typing
+        .variants
+        .iter()
+        .map(|variant| format!("`{variant}`"))
+        .collect();
+
+    let mut type_enum = String::default();
+    type_enum += &format!(
+        "#[doc = \"One of the variants {}.\"]\n",
+        variant_list.join(", ")
+    );
+    if options.serde {
+        type_enum += "#[derive(serde::Serialize, serde::Deserialize)]\n";
+    }
+    type_enum += "#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]\n";
+    type_enum += "#[non_exhaustive]\n";
+    type_enum += "#[repr(u16)]\n";
+    type_enum += &format!("pub enum {enum_name} {{\n");
+    for (ordinal, variant) in typing.variants.iter().enumerate() {
+        if ordinal == 0 {
+            type_enum += "#[default]\n";
+        }
+        type_enum += &format!("{variant} = {ordinal},\n");
+    }
+    type_enum += "}\n";
+
+    // Enum type and names.
+    type_enum += &format!("impl {enum_name} {{\n");
+    type_enum += &format!("#[doc = \"Returns the enum type of [`{enum_name}`]s.\"]\n");
+    type_enum += &format!("pub fn enum_type() -> {codas}::types::EnumType {{\n");
+    type_enum += &format!(
+        "{codas}::types::EnumType::new({}, &[\n",
+        get_rust_text(enum_name, codas)
+    );
+    for variant in &typing.variants {
+        type_enum += &format!("{},\n", get_rust_text(variant, codas));
+    }
+    type_enum += "])\n";
+    type_enum += "}\n";
+    type_enum += "#[doc = \"Returns the name of this variant.\"]\n";
+    type_enum += "pub const fn name(&self) -> &'static str {\n";
+    type_enum += "match self {\n";
+    for variant in &typing.variants {
+        type_enum += &format!("Self::{variant} => {variant:?},\n");
+    }
+    type_enum += "}\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+
+    // Ordinal conversions.
+    type_enum += &format!("impl TryFrom<u16> for {enum_name} {{\n");
+    type_enum += "type Error = u16;\n";
+    type_enum += "fn try_from(ordinal: u16) -> core::result::Result<Self, Self::Error> {\n";
+    type_enum += "match ordinal {\n";
+    for (ordinal, variant) in typing.variants.iter().enumerate() {
+        type_enum += &format!("{ordinal} => Ok(Self::{variant}),\n");
+    }
+    type_enum += "_ => Err(ordinal),\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+    type_enum += &format!("impl From<{enum_name}> for u16 {{\n");
+    type_enum += &format!("fn from(value: {enum_name}) -> u16 {{\n");
+    type_enum += "value as u16\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+
+    // Encoder impl.
+    type_enum += &format!("impl {codas}::codec::Encodable for {enum_name} {{\n");
+    type_enum += &format!(
+        "const FORMAT: {codas}::codec::Format = <u16 as {codas}::codec::Encodable>::FORMAT;\n"
+    );
+    type_enum += &format!(
+        "fn encode(&self, writer: &mut (impl {codas}::codec::WritesEncodable + ?Sized),)\n"
+    );
+    type_enum += &format!("-> core::result::Result<(), {codas}::codec::CodecError> {{\n");
+    type_enum += &format!("{codas}::codec::Encodable::encode(&u16::from(*self), writer)\n");
+    type_enum += "}\n";
+    type_enum += "}\n";
+
+    // Decoder impl.
+    type_enum += &format!("impl {codas}::codec::Decodable for {enum_name} {{\n");
+    type_enum += "fn decode(\n";
+    type_enum += "&mut self,\n";
+    type_enum += &format!("reader: &mut (impl {codas}::codec::ReadsDecodable + ?Sized),\n");
+    type_enum += &format!("header: Option<{codas}::codec::DataHeader>,\n");
+    type_enum += &format!(") -> core::result::Result<(), {codas}::codec::CodecError> {{\n");
+    type_enum += "let mut ordinal = 0u16;\n";
+    type_enum += &format!("{codas}::codec::Decodable::decode(&mut ordinal, reader, header)?;\n");
+    type_enum += "*self = Self::try_from(ordinal)\n";
+    type_enum += &format!(
+        ".map_err(|ordinal| {codas}::codec::CodecError::InvalidEnumVariant {{ ordinal }})?;\n"
+    );
+    type_enum += "Ok(())\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+
+    // Dynamic conversions.
+    type_enum += &format!("impl {codas}::types::dynamic::DynamicValue for {enum_name} {{\n");
+    type_enum += &format!("fn to_dynamic(&self) -> {codas}::types::Unspecified {{\n");
+    type_enum += &format!("{codas}::types::Unspecified::Enum {{\n");
+    type_enum += "typing: Self::enum_type(),\n";
+    type_enum += "variant: u16::from(*self),\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+    type_enum += &format!("fn try_from_dynamic(value: &{codas}::types::Unspecified)\n");
+    type_enum += &format!(
+        "-> core::result::Result<Self, {codas}::types::dynamic::TryFromDynamicError> {{\n"
+    );
+    type_enum += "match value {\n";
+    type_enum += &format!(
+        "{codas}::types::Unspecified::Enum {{ variant, .. }} => Self::try_from(*variant)\n"
+    );
+    type_enum += &format!(
+        ".map_err(|ordinal| {codas}::types::dynamic::TryFromDynamicError::UnknownVariant {{ ordinal }}),\n"
+    );
+    type_enum +=
+        &format!("_ => Err({codas}::types::dynamic::TryFromDynamicError::UnexpectedType {{\n");
+    type_enum += "expected: \"enum\",\n";
+    type_enum += "actual: value.as_type().into(),\n";
+    type_enum += "}),\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+    type_enum += "}\n";
+
+    // Display impl.
+    if options.display {
+        type_enum += &format!("impl core::fmt::Display for {enum_name} {{\n");
+        type_enum += "fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n";
+        type_enum += "f.write_str(self.name())\n";
+        type_enum += "}\n";
+        type_enum += "}\n";
+    }
+
+    type_enum
+}
+
+/// Returns true iff the native Rust type of
+/// `typing` implements [`core::fmt::Display`].
+///
+/// Generated data types are assumed to implement
+/// `Display`, since they're generated with the
+/// same [`RustCodegenOptions`].
+fn is_rust_displayable(typing: &Type) -> bool {
+    !matches!(typing, Type::Unspecified | Type::List(..) | Type::Map(..))
+}
+
 /// Returns the native Rust identifier of `type`.
 ///
-/// If `type` is a [`codas::spec::Type::Data`], the
-/// data's name will be interpereted as a
-/// native Rust identifier.
-fn get_rust_type(typing: &Type) -> Text {
+/// If `type` is a [`codas::spec::Type::Data`] or
+/// [`codas::spec::Type::Enum`], the data's (or enum's)
+/// name will be interpereted as a native Rust identifier.
+///
+/// Types from the `codas` crate are prefixed with `codas`.
+fn get_rust_type(typing: &Type, codas: &str) -> Text {
     match typing {
-        Type::Unspecified => Text::Static("codas::types::Unspecified"),
+        Type::Unspecified => format!("{codas}::types::Unspecified").into(),
         Type::U8 => Text::Static("u8"),
         Type::U16 => Text::Static("u16"),
         Type::U32 => Text::Static("u32"),
@@ -386,19 +883,289 @@ fn get_rust_type(typing: &Type) -> Text {
         Type::I16 => Text::Static("i16"),
         Type::I32 => Text::Static("i32"),
         Type::I64 => Text::Static("i64"),
+        Type::U128 => Text::Static("u128"),
+        Type::I128 => Text::Static("i128"),
         Type::F32 => Text::Static("f32"),
         Type::F64 => Text::Static("f64"),
         Type::Bool => Text::Static("bool"),
-        Type::Text => Text::Static("codas::types::Text"),
+        Type::Char => Text::Static("char"),
+        Type::UVar => format!("{codas}::types::number::VarU64").into(),
+        Type::IVar => format!("{codas}::types::number::VarI64").into(),
+        Type::Text => format!("{codas}::types::Text").into(),
+        Type::Bytes => Text::Static("alloc::vec::Vec<u8>"),
+        Type::Timestamp => format!("{codas}::types::Timestamp").into(),
+        Type::Enum(typing) => typing.name.clone(),
         Type::Data(typing) => typing.name.clone(),
         Type::List(typing) => {
-            let typing = get_rust_type(typing.as_ref());
+            let typing = get_rust_type(typing.as_ref(), codas);
             format!("alloc::vec::Vec<{typing}>").into()
         }
         Type::Map(typing) => {
-            let key_typing = get_rust_type(&typing.as_ref().0);
-            let value_typing = get_rust_type(&typing.as_ref().1);
+            let key_typing = get_rust_type(&typing.as_ref().0, codas);
+            let value_typing = get_rust_type(&typing.as_ref().1, codas);
             format!("alloc::collections::BTreeMap<{key_typing}, {value_typing}>").into()
         }
     }
 }
+
+/// Returns a native Rust expression evaluating
+/// to a [`Type`] equal to `typing`.
+///
+/// Data types are evaluated with the `data_type`
+/// function generated for their struct, unless
+/// they're fluid (like forward references).
+fn get_rust_typing(typing: &Type, codas: &str) -> Text {
+    let variant = match typing {
+        Type::Unspecified => "Unspecified",
+        Type::U8 => "U8",
+        Type::U16 => "U16",
+        Type::U32 => "U32",
+        Type::U64 => "U64",
+        Type::I8 => "I8",
+        Type::I16 => "I16",
+        Type::I32 => "I32",
+        Type::I64 => "I64",
+        Type::U128 => "U128",
+        Type::I128 => "I128",
+        Type::F32 => "F32",
+        Type::F64 => "F64",
+        Type::Bool => "Bool",
+        Type::Char => "Char",
+        Type::UVar => "UVar",
+        Type::IVar => "IVar",
+        Type::Text => "Text",
+        Type::Bytes => "Bytes",
+        Type::Timestamp => "Timestamp",
+        Type::Data(typing) if matches!(typing.format(), Format::Fluid) => {
+            return format!(
+                "{codas}::types::Type::Data({codas}::types::DataType::new_fluid({}, {}))",
+                get_rust_text(&typing.name, codas),
+                get_rust_docs(&typing.docs, codas)
+            )
+            .into();
+        }
+        Type::Data(typing) => {
+            return format!("{codas}::types::Type::Data({}::data_type())", typing.name).into();
+        }
+        Type::Enum(typing) => {
+            return format!("{codas}::types::Type::Enum({}::enum_type())", typing.name).into();
+        }
+        Type::List(typing) => {
+            let typing = get_rust_typing(typing.as_ref(), codas);
+            return format!("{codas}::types::Type::List(alloc::boxed::Box::new({typing}))").into();
+        }
+        Type::Map(typing) => {
+            let key_typing = get_rust_typing(&typing.as_ref().0, codas);
+            let value_typing = get_rust_typing(&typing.as_ref().1, codas);
+            return format!(
+                "{codas}::types::Type::Map(alloc::boxed::Box::new(({key_typing}, {value_typing})))"
+            )
+            .into();
+        }
+    };
+
+    format!("{codas}::types::Type::{variant}").into()
+}
+
+/// Returns a native Rust expression evaluating to
+/// the native value of the default value `value`,
+/// or `None` if `value` has no native literal.
+fn get_rust_value(value: &Unspecified, codas: &str) -> Option<String> {
+    Some(match value {
+        Unspecified::U8(value) => format!("{value}u8"),
+        Unspecified::U16(value) => format!("{value}u16"),
+        Unspecified::U32(value) => format!("{value}u32"),
+        Unspecified::U64(value) => format!("{value}u64"),
+        Unspecified::U128(value) => format!("{value}u128"),
+        Unspecified::I8(value) => format!("{value}i8"),
+        Unspecified::I16(value) => format!("{value}i16"),
+        Unspecified::I32(value) => format!("{value}i32"),
+        Unspecified::I64(value) => format!("{value}i64"),
+        Unspecified::I128(value) => format!("{value}i128"),
+        Unspecified::F32(value) => format!("{value:?}f32"),
+        Unspecified::F64(value) => format!("{value:?}f64"),
+        Unspecified::UVar(value) => format!("{codas}::types::number::VarU64({})", value.0),
+        Unspecified::IVar(value) => format!("{codas}::types::number::VarI64({})", value.0),
+        Unspecified::Timestamp(value) => format!("{codas}::types::Timestamp({value})"),
+        Unspecified::Bool(value) => format!("{value}"),
+        Unspecified::Char(value) => format!("{value:?}"),
+        Unspecified::Text(value) => get_rust_text(value, codas),
+        Unspecified::Enum { typing, variant } => {
+            format!(
+                "{}::{}",
+                typing.name,
+                typing.variants.get(*variant as usize)?
+            )
+        }
+        _ => return None,
+    })
+}
+
+/// Returns a native Rust expression evaluating
+/// to a [`Text`] containing `text`.
+fn get_rust_text(text: &str, codas: &str) -> String {
+    format!("{codas}::types::Text::from({text:?})")
+}
+
+/// Returns a native Rust expression evaluating
+/// to optional [`Text`] containing `docs`.
+fn get_rust_docs(docs: &Option<Text>, codas: &str) -> String {
+    match docs {
+        Some(docs) => format!("Some({})", get_rust_text(docs, codas)),
+        None => String::from("None"),
+    }
+}
+
+/// Returns the `#[deprecated]` attribute for an item
+/// with `deprecated` status and `note`, if any.
+fn get_rust_deprecation(deprecated: bool, note: &Option<Text>) -> Option<String> {
+    match (deprecated, note) {
+        (false, _) => None,
+        (true, Some(note)) => Some(format!("#[deprecated(note = {:?})]\n", note.as_str())),
+        (true, None) => Some(String::from("#[deprecated]\n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse::{parse, tests::TEST_CODA_MARKDOWN};
+
+    #[test]
+    fn prefixes_paths() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let options = RustCodegenOptions {
+            codas_path: Some("my_codas".into()),
+            module_path: Some("crate::generated".into()),
+            ..Default::default()
+        };
+        let mut rust = vec![];
+        generate_types(&coda, &mut rust, &options).unwrap();
+        let rust = String::from_utf8_lossy(&rust);
+
+        assert!(rust.contains("impl my_codas::codec::Encodable for MyDataType"));
+        assert!(rust.contains("From<crate::generated::MyDataType>"));
+        assert!(!rust.contains("self::MyDataType"));
+        assert!(rust.contains("pub fn data_type() -> my_codas::types::DataType"));
+        assert!(rust.contains("_ => unreachable!()"));
+
+        // Unknown data is preserved on request.
+        let options = RustCodegenOptions {
+            preserve_unknown: true,
+            ..options
+        };
+        let mut rust = vec![];
+        generate_types(&coda, &mut rust, &options).unwrap();
+        let rust = String::from_utf8_lossy(&rust);
+        assert!(!rust.contains("unreachable!()"));
+        assert!(rust.contains("*self = Self::Unspecified(data);"));
+        assert_eq!(
+            rust.matches("codas::").count(),
+            rust.matches("my_codas::").count()
+        );
+    }
+
+    #[test]
+    fn maps_bytes() {
+        assert_eq!(
+            "alloc::vec::Vec<u8>",
+            &*get_rust_type(&Type::Bytes, "codas")
+        );
+        assert_eq!(
+            "codas::types::Type::Bytes",
+            &*get_rust_typing(&Type::Bytes, "codas")
+        );
+    }
+
+    #[test]
+    fn generates_explicit_ordinals() {
+        let coda =
+            parse("# `Shapes` Coda\n\n## `Square` Data 7\n\n+ `side` f32\n\n## `Circle` Data 2\n")
+                .unwrap();
+        let mut rust = vec![];
+        let options = RustCodegenOptions {
+            preserve_unknown: false,
+            ..Default::default()
+        };
+        generate_types(&coda, &mut rust, &options).unwrap();
+        let rust = String::from_utf8_lossy(&rust);
+
+        assert!(rust.contains("Self::Circle(..) => 2,\nSelf::Square(..) => 7,\n"));
+        assert!(rust.contains("let header = Self::ensure_header(header, &[\n0,2,7,])?;\n"));
+        assert!(rust.contains("codas::codec::Format::data(7)"));
+    }
+
+    #[test]
+    fn generates_deprecations() {
+        let coda = parse("# `Users` Coda\n\n## `Account` Data\n\n+ `name` text\n\n## `User` Data\n\n> Deprecated: Use \"Account\".\n\n+ `id` u32\n+ `old_id` deprecated u64\n+ `nickname` optional deprecated text\n\n    > Deprecated: Use `Account.name`.\n\n+ `account` deprecated Account\n").unwrap();
+        let mut rust = vec![];
+        generate_types(&coda, &mut rust, &RustCodegenOptions::default()).unwrap();
+        let rust = String::from_utf8_lossy(&rust);
+
+        assert!(rust.contains("#[deprecated(note = \"Use \\\"Account\\\".\")]\n#[derive(Default, Clone, Debug, PartialEq)]\npub struct User {"));
+        assert!(rust.contains("pub id: u32,\n#[deprecated]\npub old_id: u64,\n"));
+        assert!(rust.contains("#[deprecated(note = \"Use `Account.name`.\")]\npub nickname: Option<codas::types::Text>,\n"));
+        assert!(rust.contains("deprecated: true,\ndeprecation_note: Some(codas::types::Text::from(\"Use `Account.name`.\")),\n"));
+
+        // Generated code doesn't warn about its own deprecations.
+        assert!(rust.contains("#[allow(deprecated)]\nimpl codas::codec::Encodable for User {"));
+        assert!(rust.contains(
+            "#[allow(deprecated)]\n#[derive(Clone, Debug, PartialEq)]\npub enum UsersData {"
+        ));
+
+        // Codas without deprecations don't allow them.
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut rust = vec![];
+        generate_types(&coda, &mut rust, &RustCodegenOptions::default()).unwrap();
+        assert!(!String::from_utf8_lossy(&rust).contains("deprecated)]"));
+    }
+
+    #[test]
+    fn generates_defaults() {
+        let coda = parse(
+            "# `Jobs` Coda\n\n## `Job` Data\n\n+ `retries` u32 = 3\n+ `name` optional text = \"first\"\n+ `status` one of [Queued, Running] = Running\n+ `count` uvar\n\n## `Empty` Data\n\n+ `id` u32\n",
+        )
+        .unwrap();
+        let mut rust = vec![];
+        generate_types(&coda, &mut rust, &RustCodegenOptions::default()).unwrap();
+        let rust = String::from_utf8_lossy(&rust);
+
+        // Types with defaults implement `Default` explicitly...
+        assert!(rust.contains("#[derive(Clone, Debug, PartialEq)]\npub struct Job {"));
+        assert!(rust.contains(
+            "impl Default for Job {\nfn default() -> Self {\nSelf {\nretries: 3u32,\nstatus: JobStatus::Running,\nname: Some(codas::types::Text::from(\"first\")),\ncount: Default::default(),\n}"
+        ));
+        assert!(rust
+            .contains("default: Some(codas::types::dynamic::DynamicValue::to_dynamic(&3u32)),\n"));
+
+        // ...while types without defaults derive it.
+        assert!(rust.contains("#[derive(Default, Clone, Debug, PartialEq)]\npub struct Empty {"));
+        assert!(!rust.contains("impl Default for Empty"));
+
+        assert_eq!(
+            Some("-5i64".into()),
+            get_rust_value(&Unspecified::I64(-5), "codas")
+        );
+        assert_eq!(
+            Some("0.5f32".into()),
+            get_rust_value(&Unspecified::F32(0.5), "codas")
+        );
+        assert_eq!(
+            Some("'\\''".into()),
+            get_rust_value(&Unspecified::Char('\''), "codas")
+        );
+    }
+
+    #[test]
+    fn maps_timestamps() {
+        assert_eq!(
+            "codas::types::Timestamp",
+            &*get_rust_type(&Type::Timestamp, "codas")
+        );
+        assert_eq!(
+            "codas::types::Type::Timestamp",
+            &*get_rust_typing(&Type::Timestamp, "codas")
+        );
+    }
+}
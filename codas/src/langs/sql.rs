@@ -2,16 +2,27 @@
 //!
 //! SQL code generators for codas, with a focus
 //! on supporting the DuckDB SQL dialect.
+//!
+//! ## Migrations
+//!
+//! [`generate_migration`] generates the statements
+//! migrating tables between two versions of a coda, in
+//! any [`SqlDialect`]. Each data type is assumed to be
+//! stored in a table named after the data type, with a
+//! column for each of the data type's fields.
+//!
+//! Data types are matched between versions by their
+//! ordinal, and fields are matched by their name.
 
 use core::fmt::Write;
 
-use alloc::{format, string::String};
+use alloc::{format, string::String, vec::Vec};
 
 use indoc::writedoc;
 
 use crate::{
     stream::{FmtWriter, StreamError, Writes},
-    types::{Coda, Text, Type, Unspecified},
+    types::{Coda, Text, Type},
 };
 
 /// Generates the SQL types for `coda`, writing them to `stream`.
@@ -19,11 +30,8 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
     let mut writer = FmtWriter::from(stream);
 
     // Generate coda data type statements.
-    for (ordinal, typing) in [Unspecified::DATA_TYPE]
-        .iter()
-        .chain(coda.iter())
-        .enumerate()
-    {
+    for typing in coda.iter_all() {
+        let ordinal = typing.ordinal();
         let data_type_name = typing.name.trim();
 
         // Build a string containing all fields.
@@ -65,22 +73,274 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
     Ok(())
 }
 
+/// SQL dialects supported by [`generate_migration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// [PostgreSQL](https://www.postgresql.org/).
+    PostgreSql,
+
+    /// [SQLite](https://sqlite.org/) (3.35 or newer).
+    ///
+    /// SQLite can't alter an existing column's type or
+    /// nullability; migrations in this dialect contain
+    /// a comment in place of those statements.
+    Sqlite,
+}
+
+/// Generates the SQL statements migrating the tables of `old_coda`
+/// to the tables of `new_coda` in `dialect`, writing them to `stream`.
+///
+/// - Data types in `old_coda` with a different name in
+///   `new_coda` have their tables renamed.
+/// - Data types only in `new_coda` have their tables created,
+///   while data types only in `old_coda` have their tables dropped.
+/// - Fields only in `new_coda` are added as columns. Required
+///   fields' columns are `NOT NULL`, with a default
+///   value matching the field's type.
+/// - Fields only in `old_coda` have their columns dropped.
+/// - Fields with a different type or optionality in
+///   `new_coda` have their columns altered.
+///
+/// To generate the statements creating all of
+/// `new_coda`'s tables, migrate from an empty coda.
+pub fn generate_migration(
+    old_coda: &Coda,
+    new_coda: &Coda,
+    dialect: SqlDialect,
+    stream: &mut impl Writes,
+) -> Result<(), StreamError> {
+    let mut writer = FmtWriter::from(stream);
+
+    for new_type in new_coda.iter() {
+        let ordinal = new_type.format().as_data_format().ordinal;
+        let table = sql_identifier(&new_type.name);
+        let old_type = old_coda
+            .iter()
+            .find(|old_type| old_type.format().as_data_format().ordinal == ordinal);
+
+        let _ = writeln!(writer, "\n-- Data {ordinal}.");
+
+        // Create tables for new data types.
+        let Some(old_type) = old_type else {
+            let columns: Vec<String> = new_type
+                .iter()
+                .map(|field| {
+                    let mut column = format!(
+                        "  {} {}",
+                        sql_identifier(&field.name),
+                        sql_type(&field.typing, dialect)
+                    );
+                    if !field.optional {
+                        column += " NOT NULL";
+                    }
+                    column
+                })
+                .collect();
+
+            if columns.is_empty() {
+                let _ = writeln!(writer, "-- Data with no fields isn't stored in a table.");
+            } else {
+                let _ = writeln!(writer, "CREATE TABLE {table} (");
+                let _ = writeln!(writer, "{}", columns.join(",\n"));
+                let _ = writeln!(writer, ");");
+            }
+            continue;
+        };
+
+        // Rename tables for renamed data types.
+        if old_type.name != new_type.name {
+            let _ = writeln!(
+                writer,
+                "ALTER TABLE {} RENAME TO {table};",
+                sql_identifier(&old_type.name)
+            );
+        }
+
+        // Drop columns for removed fields.
+        for old_field in old_type.iter() {
+            if !new_type.iter().any(|field| field.name == old_field.name) {
+                let _ = writeln!(
+                    writer,
+                    "ALTER TABLE {table} DROP COLUMN {};",
+                    sql_identifier(&old_field.name)
+                );
+            }
+        }
+
+        for new_field in new_type.iter() {
+            let column = sql_identifier(&new_field.name);
+            let column_type = sql_type(&new_field.typing, dialect);
+            let default = sql_default(&new_field.typing, dialect);
+
+            // Add columns for new fields.
+            let Some(old_field) = old_type.iter().find(|field| field.name == new_field.name) else {
+                if new_field.optional {
+                    let _ = writeln!(
+                        writer,
+                        "ALTER TABLE {table} ADD COLUMN {column} {column_type} DEFAULT NULL;"
+                    );
+                } else {
+                    let _ = writeln!(
+                        writer,
+                        "ALTER TABLE {table} ADD COLUMN {column} {column_type} NOT NULL DEFAULT {default};"
+                    );
+                }
+                continue;
+            };
+
+            // Alter columns for changed fields.
+            let type_changed = sql_type(&old_field.typing, dialect) != column_type;
+            let optionality_changed = old_field.optional != new_field.optional;
+            match dialect {
+                SqlDialect::PostgreSql => {
+                    if type_changed {
+                        let _ = writeln!(
+                            writer,
+                            "ALTER TABLE {table} ALTER COLUMN {column} TYPE {column_type} USING {column}::{column_type};"
+                        );
+                    }
+                    if optionality_changed && new_field.optional {
+                        let _ = writeln!(
+                            writer,
+                            "ALTER TABLE {table} ALTER COLUMN {column} DROP NOT NULL;"
+                        );
+                    } else if optionality_changed {
+                        let _ = writeln!(
+                            writer,
+                            "UPDATE {table} SET {column} = {default} WHERE {column} IS NULL;"
+                        );
+                        let _ = writeln!(
+                            writer,
+                            "ALTER TABLE {table} ALTER COLUMN {column} SET NOT NULL;"
+                        );
+                    }
+                }
+                SqlDialect::Sqlite => {
+                    if type_changed {
+                        let _ = writeln!(
+                            writer,
+                            "-- SQLite can't alter column types: {column} is now {column_type}."
+                        );
+                    }
+                    if optionality_changed {
+                        let nullability = if new_field.optional {
+                            "NULL"
+                        } else {
+                            "NOT NULL"
+                        };
+                        let _ = writeln!(
+                            writer,
+                            "-- SQLite can't alter column nullability: {column} is now {nullability}."
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Drop tables for removed data types.
+    for old_type in old_coda.iter() {
+        let ordinal = old_type.format().as_data_format().ordinal;
+        if new_coda
+            .iter()
+            .any(|new_type| new_type.format().as_data_format().ordinal == ordinal)
+        {
+            continue;
+        }
+
+        let _ = writeln!(writer, "\n-- Data {ordinal}.");
+        if old_type.iter().next().is_some() {
+            let _ = writeln!(writer, "DROP TABLE {};", sql_identifier(&old_type.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `name` as a quoted SQL identifier.
+fn sql_identifier(name: &str) -> Text {
+    format!("\"{}\"", name.replace('"', "\"\"")).into()
+}
+
+/// Returns the column type of `type` in `dialect`.
+///
+/// Nested data, lists, and maps are stored as JSON.
+/// Enums are stored as their variants' ordinals.
+fn sql_type(typing: &Type, dialect: SqlDialect) -> Text {
+    match dialect {
+        SqlDialect::PostgreSql => match typing {
+            Type::Unspecified | Type::Bytes => Text::Static("BYTEA"),
+            Type::U8 | Type::I8 | Type::I16 => Text::Static("SMALLINT"),
+            Type::U16 | Type::I32 | Type::Enum(_) => Text::Static("INTEGER"),
+            Type::U32 | Type::I64 | Type::IVar | Type::Timestamp => Text::Static("BIGINT"),
+            Type::U64 | Type::U128 | Type::I128 | Type::UVar => Text::Static("NUMERIC"),
+            Type::F32 => Text::Static("REAL"),
+            Type::F64 => Text::Static("DOUBLE PRECISION"),
+            Type::Bool => Text::Static("BOOLEAN"),
+            Type::Text => Text::Static("TEXT"),
+            Type::Char => Text::Static("CHAR(1)"),
+            Type::Data(_) | Type::List(_) | Type::Map(_) => Text::Static("JSONB"),
+        },
+        SqlDialect::Sqlite => match typing {
+            Type::Unspecified | Type::Bytes => Text::Static("BLOB"),
+            Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::I8
+            | Type::I16
+            | Type::I32
+            | Type::I64
+            | Type::IVar
+            | Type::Timestamp
+            | Type::Bool
+            | Type::Enum(_) => Text::Static("INTEGER"),
+            Type::U64 | Type::U128 | Type::I128 | Type::UVar => Text::Static("TEXT"),
+            Type::F32 | Type::F64 => Text::Static("REAL"),
+            Type::Text | Type::Char => Text::Static("TEXT"),
+            Type::Data(_) | Type::List(_) | Type::Map(_) => Text::Static("TEXT"),
+        },
+    }
+}
+
+/// Returns the default value of a column
+/// storing `type` in `dialect`.
+fn sql_default(typing: &Type, dialect: SqlDialect) -> Text {
+    match (typing, dialect) {
+        (Type::Unspecified | Type::Bytes, SqlDialect::PostgreSql) => Text::Static("''::BYTEA"),
+        (Type::Unspecified | Type::Bytes, SqlDialect::Sqlite) => Text::Static("X''"),
+        (Type::U64 | Type::U128 | Type::I128 | Type::UVar, SqlDialect::Sqlite) => {
+            Text::Static("'0'")
+        }
+        (Type::F32 | Type::F64, _) => Text::Static("0.0"),
+        (Type::Bool, SqlDialect::PostgreSql) => Text::Static("FALSE"),
+        (Type::Text | Type::Char, _) => Text::Static("''"),
+        (Type::Data(_) | Type::Map(_), _) => Text::Static("'{}'"),
+        (Type::List(_), _) => Text::Static("'[]'"),
+        _ => Text::Static("0"),
+    }
+}
+
 /// Returns the native SQL identifier of a type.
+///
+/// Enums are stored as their variants' ordinals.
 fn duckdb_type(typing: &Type) -> Text {
     match typing {
-        Type::Unspecified => Text::Static("BLOB"),
+        Type::Unspecified | Type::Bytes => Text::Static("BLOB"),
         Type::U8 => Text::Static("UTINYINT"),
-        Type::U16 => Text::Static("USMALLINT"),
+        Type::U16 | Type::Enum(_) => Text::Static("USMALLINT"),
         Type::U32 => Text::Static("UINTEGER"),
-        Type::U64 => Text::Static("UBIGINT"),
+        Type::U64 | Type::UVar => Text::Static("UBIGINT"),
         Type::I8 => Text::Static("TINYINT"),
         Type::I16 => Text::Static("SMALLINT"),
         Type::I32 => Text::Static("INTEGER"),
-        Type::I64 => Text::Static("BIGINT"),
+        Type::I64 | Type::IVar | Type::Timestamp => Text::Static("BIGINT"),
+        Type::U128 => Text::Static("UHUGEINT"),
+        Type::I128 => Text::Static("HUGEINT"),
         Type::F32 => Text::Static("FLOAT"),
         Type::F64 => Text::Static("DOUBLE"),
         Type::Bool => Text::Static("BOOLEAN"),
         Type::Text => Text::Static("VARCHAR"),
+        Type::Char => Text::Static("VARCHAR(1)"),
         Type::Data(typing) => typing.name.clone(),
         Type::List(typing) => {
             let inner = duckdb_type(typing);
@@ -98,7 +358,10 @@ fn duckdb_type(typing: &Type) -> Text {
 mod tests {
     use super::*;
 
-    use crate::parse::{parse, tests::TEST_CODA_MARKDOWN};
+    use crate::{
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::EnumType,
+    };
 
     #[test]
     fn smoke() {
@@ -133,4 +396,151 @@ CREATE TYPE MyDataType AS STRUCT (
             sql.trim()
         );
     }
+
+    /// The original version of a coda for migration tests.
+    const OLD_CODA_MARKDOWN: &str = r#"
+# `Inventory` Coda
+
+## `Item` Data
+
++ `name` text
++ `count` u32
++ `legacy` text
++ `notes` optional text
+
+## `Shelf` Data
+
++ `label` text
+
+## `Bin` Data
+
++ `size` u8
+"#;
+
+    /// A new version of [`OLD_CODA_MARKDOWN`].
+    const NEW_CODA_MARKDOWN: &str = r#"
+# `Inventory` Coda
+
+## `Product` Data
+
++ `name` text
++ `count` i64
++ `notes` text
++ `price` f64
++ `barcode` optional text
+
+## `Shelf` Data
+
++ `label` text
+"#;
+
+    fn migration(old_coda: &Coda, new_coda: &Coda, dialect: SqlDialect) -> String {
+        let mut sql = Vec::new();
+        generate_migration(old_coda, new_coda, dialect, &mut sql).unwrap();
+        String::from_utf8(sql).unwrap()
+    }
+
+    #[test]
+    fn maps_bytes() {
+        assert_eq!("BYTEA", &*sql_type(&Type::Bytes, SqlDialect::PostgreSql));
+        assert_eq!("BLOB", &*sql_type(&Type::Bytes, SqlDialect::Sqlite));
+        assert_eq!("BLOB", &*duckdb_type(&Type::Bytes));
+        assert_eq!("X''", &*sql_default(&Type::Bytes, SqlDialect::Sqlite));
+    }
+
+    #[test]
+    fn maps_timestamps() {
+        // Timestamps are stored as milliseconds since the Unix epoch.
+        assert_eq!(
+            "BIGINT",
+            &*sql_type(&Type::Timestamp, SqlDialect::PostgreSql)
+        );
+        assert_eq!("INTEGER", &*sql_type(&Type::Timestamp, SqlDialect::Sqlite));
+        assert_eq!("BIGINT", &*duckdb_type(&Type::Timestamp));
+        assert_eq!("0", &*sql_default(&Type::Timestamp, SqlDialect::Sqlite));
+    }
+
+    #[test]
+    fn maps_enums() {
+        // Enums are stored as their variants' ordinals.
+        let typing = Type::Enum(EnumType::new("Status".into(), &["Active".into()]));
+        assert_eq!("INTEGER", &*sql_type(&typing, SqlDialect::PostgreSql));
+        assert_eq!("INTEGER", &*sql_type(&typing, SqlDialect::Sqlite));
+        assert_eq!("USMALLINT", &*duckdb_type(&typing));
+        assert_eq!("0", &*sql_default(&typing, SqlDialect::PostgreSql));
+    }
+
+    #[test]
+    fn generates_postgresql_migrations() {
+        let empty = Coda::new("Inventory".into(), "Inventory".into(), None, &[]);
+        let old = parse(OLD_CODA_MARKDOWN).unwrap();
+        let new = parse(NEW_CODA_MARKDOWN).unwrap();
+
+        let sql = migration(&empty, &old, SqlDialect::PostgreSql);
+        assert!(sql.contains(
+            "\n-- Data 1.\nCREATE TABLE \"Item\" (\n  \"count\" BIGINT NOT NULL,\n  \"name\" TEXT NOT NULL,\n  \"legacy\" TEXT NOT NULL,\n  \"notes\" TEXT\n);\n"
+        ));
+
+        let sql = migration(&old, &new, SqlDialect::PostgreSql);
+        assert_eq!(
+            r#"
+-- Data 1.
+ALTER TABLE "Item" RENAME TO "Product";
+ALTER TABLE "Product" DROP COLUMN "legacy";
+ALTER TABLE "Product" ADD COLUMN "price" DOUBLE PRECISION NOT NULL DEFAULT 0.0;
+UPDATE "Product" SET "notes" = '' WHERE "notes" IS NULL;
+ALTER TABLE "Product" ALTER COLUMN "notes" SET NOT NULL;
+ALTER TABLE "Product" ADD COLUMN "barcode" TEXT DEFAULT NULL;
+
+-- Data 2.
+
+-- Data 3.
+DROP TABLE "Bin";
+"#,
+            sql
+        );
+    }
+
+    #[test]
+    fn generates_sqlite_migrations() {
+        let empty = Coda::new("Inventory".into(), "Inventory".into(), None, &[]);
+        let old = parse(OLD_CODA_MARKDOWN).unwrap();
+        let new = parse(NEW_CODA_MARKDOWN).unwrap();
+
+        // Create the original tables, with some data.
+        let db = rusqlite::Connection::open_in_memory().unwrap();
+        db.execute_batch(&migration(&empty, &old, SqlDialect::Sqlite))
+            .unwrap();
+        db.execute_batch(
+            r#"
+            INSERT INTO "Item" ("count", "name", "legacy") VALUES (3, 'cupcake', 'old');
+            INSERT INTO "Bin" ("size") VALUES (1);
+            "#,
+        )
+        .unwrap();
+
+        // Migrate the tables.
+        let sql = migration(&old, &new, SqlDialect::Sqlite);
+        assert!(
+            sql.contains("-- SQLite can't alter column nullability: \"notes\" is now NOT NULL.\n")
+        );
+        db.execute_batch(&sql).unwrap();
+
+        // Existing data has defaults for new fields.
+        let product: (String, i64, f64, Option<String>) = db
+            .query_row(
+                r#"SELECT "name", "count", "price", "barcode" FROM "Product""#,
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(("cupcake".into(), 3, 0.0, None), product);
+
+        // Removed fields and data types are gone. The removed
+        // column is unquoted, since SQLite treats unknown quoted
+        // identifiers as string literals.
+        assert!(db.prepare(r#"SELECT legacy FROM "Product""#).is_err());
+        assert!(db.prepare(r#"SELECT * FROM "Bin""#).is_err());
+        assert!(db.prepare(r#"SELECT * FROM "Item""#).is_err());
+    }
 }
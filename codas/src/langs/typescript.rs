@@ -7,6 +7,10 @@
 //!
 //! - A `class` for each data type.
 //!
+//! - A `type` for each enum type, which is a union of
+//!   its variants' names, and a `const object` of the
+//!   same name listing its `variants` by ordinal.
+//!
 //! - A `type` for the coda, which is a union of
 //!   the classes generated for each data type.
 //!
@@ -17,20 +21,44 @@
 //! - An `interface` for the parameter to the matching
 //!   utility function.
 //!
-//! **Codecs are not generated.** They will be
-//! generated once there is a native Typescript library
-//! for encoding and decoding coda-encoded data.
+//! Each generated `class` has an `encode()` method and a
+//! `static decode(bytes)` function that are compatible with
+//! the [`codec`](crate::codec). These codecs import the
+//! [`RUNTIME`] from a `codas-runtime.ts` file next to
+//! the generated code (see [`generate_runtime`]).
+//!
+//! Unspecified data is decoded as the raw `Uint8Array`
+//! of its encoding (or `undefined`, for default data),
+//! and may be re-encoded as-is.
+//!
+//! [Zod](https://zod.dev) schemas for validating JSON
+//! representations of each data type may be generated
+//! separately with [`generate_zod`].
 use core::fmt::Write;
 
-use alloc::format;
+use alloc::{format, string::String, vec::Vec};
 
 use indoc::writedoc;
 
 use crate::{
+    codec::Format,
     stream::{FmtWriter, StreamError, Writes},
-    types::{Coda, Text, Type, Unspecified},
+    types::{Coda, DataField, Text, Type, Unspecified},
 };
 
+/// Source of the Typescript runtime imported by
+/// codecs generated by [`generate_types`].
+pub const RUNTIME: &str = include_str!("typescript/codas-runtime.ts");
+
+/// File name of the [`RUNTIME`], which must be written next
+/// to any Typescript generated by [`generate_types`].
+pub const RUNTIME_FILE_NAME: &str = "codas-runtime.ts";
+
+/// Writes the Typescript [`RUNTIME`] to `stream`.
+pub fn generate_runtime(stream: &mut impl Writes) -> Result<(), StreamError> {
+    stream.write_all(RUNTIME.as_bytes())
+}
+
 /// Generates the Typescript types for `coda`.
 pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
     // Extract coda metadata.
@@ -42,21 +70,33 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
 
     let mut writer = FmtWriter::from(stream);
 
+    // Import the runtime.
+    let _ = writeln!(
+        writer,
+        "import {{ Reader, Writer }} from \"./{}\";\n",
+        RUNTIME_FILE_NAME.trim_end_matches(".ts")
+    );
+
     // Generate coda const.
     write_typescript_doc(&mut writer, 0, coda_type_docs)?;
     let _ = writedoc!(
         writer,
         r#"
-        const {coda_type_name} = {{
+        export const {coda_type_name} = {{
         "#
     );
-    for data_type in [Unspecified::DATA_TYPE].iter().chain(coda.iter()) {
+    for data_type in coda.iter_all() {
         let data_type_name = &data_type.name;
         let data_type_docs = match &data_type.docs {
             Some(docs) => docs.trim(),
             None => "Undocumented Data. How could you? ;~;",
         };
-        write_typescript_doc(&mut writer, 4, data_type_docs)?;
+        let data_type_docs = typescript_deprecated_doc(
+            data_type_docs,
+            data_type.deprecated,
+            &data_type.deprecation_note,
+        );
+        write_typescript_doc(&mut writer, 4, &data_type_docs)?;
 
         let _ = writeln!(writer, "    {data_type_name}(): {data_type_name} {{");
         let _ = writeln!(writer, "        return new {data_type_name}();");
@@ -86,8 +126,8 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
         "    match<R>(data: {coda_type_name}, matcher: {coda_type_name}Matcher<R>): R {{"
     );
     let _ = writeln!(writer, "        switch (data.__ordinal) {{");
-    for (ordinal, data_type) in coda.iter().enumerate() {
-        let ordinal = ordinal + 1;
+    for data_type in coda.iter() {
+        let ordinal = data_type.ordinal();
         let data_type_name = &data_type.name;
         let _ = writeln!(writer, "            case {ordinal}: return matcher.{data_type_name} ? matcher.{data_type_name}(data as {data_type_name}) : matcher.Unspecified();");
     }
@@ -95,10 +135,35 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
     let _ = writeln!(writer, "        }}");
     let _ = writeln!(writer, "    }},");
 
+    // Generate decoder.
+    let _ = writeln!(writer);
+    write_typescript_doc(
+        &mut writer,
+        4,
+        &format!("Decodes {{@link {coda_type_name}}} from `bytes`.\n\n@remarks\n\nData of an unknown type is decoded as {{@link Unspecified}}."),
+    )?;
+    let _ = writeln!(writer, "    decode(bytes: Uint8Array): {coda_type_name} {{");
+    let _ = writeln!(writer, "        const reader = new Reader(bytes);");
+    let _ = writeln!(writer, "        switch (reader.peekOrdinal()) {{");
+    for data_type in coda.iter() {
+        let ordinal = data_type.ordinal();
+        let data_type_name = &data_type.name;
+        let _ = writeln!(
+            writer,
+            "            case {ordinal}: return {data_type_name}.decodeFrom(reader);"
+        );
+    }
+    let _ = writeln!(
+        writer,
+        "            default: return Unspecified.decodeFrom(reader);"
+    );
+    let _ = writeln!(writer, "        }}");
+    let _ = writeln!(writer, "    }},");
+
     let _ = write!(writer, "}};\n\n");
 
     // Generate coda type.
-    let _ = write!(writer, "type {coda_type_name} = Unspecified");
+    let _ = write!(writer, "export type {coda_type_name} = Unspecified");
     for data_type in coda.iter() {
         let _ = write!(writer, " | {}", data_type.name);
     }
@@ -125,7 +190,7 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
          * The functions may return any kind of data, but all
          * functions must return the _same_ type of data.
          */
-        interface {coda_type_name}Matcher<R> {{
+        export interface {coda_type_name}Matcher<R> {{
 
             /**
              * Handler for {{@link Unspecified}} or otherwise unknown data.
@@ -148,19 +213,56 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
     }
     let _ = write!(writer, "}}\n\n");
 
+    // Generate enums.
+    for typing in coda.enums() {
+        let enum_name = &typing.name;
+        let variants = typing
+            .variants
+            .iter()
+            .map(|variant| format!("\"{variant}\""))
+            .collect::<Vec<_>>();
+        write_typescript_doc(&mut writer, 0, &format!("Variants of `{enum_name}`."))?;
+        let _ = write!(
+            writer,
+            "export type {enum_name} = {};\n\n",
+            variants.join(" | ")
+        );
+        write_typescript_doc(
+            &mut writer,
+            0,
+            &format!("Ordinals of {{@link {enum_name}}}'s variants."),
+        )?;
+        let _ = writeln!(writer, "export const {enum_name} = {{");
+        write_typescript_doc(&mut writer, 4, "Every variant, indexed by its ordinal.")?;
+        let _ = writeln!(writer, "    variants: [{}] as const,", variants.join(", "));
+        let _ = writeln!(writer);
+        write_typescript_doc(&mut writer, 4, "Returns the ordinal of `variant`.")?;
+        let _ = writeln!(writer, "    ordinalOf(variant: {enum_name}): number {{");
+        let _ = writeln!(
+            writer,
+            "        return {enum_name}.variants.indexOf(variant);"
+        );
+        let _ = writeln!(writer, "    }},");
+        let _ = write!(writer, "}};\n\n");
+    }
+
     // Generate data interfaces.
-    for data_type in [Unspecified::DATA_TYPE].iter().chain(coda.iter()) {
-        let ordinal = data_type.format().as_data_format().ordinal;
+    for data_type in coda.iter_all() {
+        let format = data_type.format().as_data_format();
+        let ordinal = format.ordinal;
 
         // Declaration and ordinal.
         let data_type_name = &data_type.name;
+        let data_type_docs = typescript_deprecated_doc(
+            &format!("Class corresponding to {{@link {coda_type_name}.{data_type_name}}}."),
+            data_type.deprecated,
+            &data_type.deprecation_note,
+        );
+        write_typescript_doc(&mut writer, 0, &data_type_docs)?;
         let _ = writedoc!(
             writer,
             r#"
-            /**
-             * Class corresponding to {{@link {coda_type_name}.{data_type_name}}}.
-             */
-            class {data_type_name} {{
+            export class {data_type_name} {{
                 readonly __ordinal: {ordinal} = {ordinal};
         "#
         );
@@ -172,23 +274,219 @@ pub fn generate_types(coda: &Coda, stream: &mut impl Writes) -> Result<(), Strea
                 Some(docs) => docs.trim(),
                 None => "Undocumented Field. How could you? ;~;",
             };
+            let field_docs =
+                typescript_deprecated_doc(field_docs, field.deprecated, &field.deprecation_note);
             let field_type = typescript_type(&field.typing);
             let _ = writeln!(writer);
-            write_typescript_doc(&mut writer, 4, field_docs)?;
+            write_typescript_doc(&mut writer, 4, &field_docs)?;
 
-            if field.optional {
+            if let Some(field_default) = typescript_field_default(field) {
+                let optional = if field.optional { "?" } else { "" };
+                let _ = writeln!(
+                    writer,
+                    "    {field_name}{optional}: {field_type} = {field_default};"
+                );
+            } else if field.optional {
                 let _ = writeln!(writer, "    {field_name}?: {field_type} = undefined;");
             } else {
                 let field_default = typescript_default_val(&field.typing);
                 let _ = writeln!(writer, "    {field_name}: {field_type} = {field_default};");
             }
+
+            // Timestamps (in milliseconds since the Unix
+            // epoch) are also accessible as `Date`s.
+            if field.typing == Type::Timestamp {
+                let _ = writeln!(writer);
+                write_typescript_doc(
+                    &mut writer,
+                    4,
+                    &format!("{{@link {data_type_name}.{field_name}}} as a `Date`."),
+                )?;
+                let (date_type, to_date, from_date) = match field.optional {
+                    true => (
+                        "Date | undefined",
+                        format!("this.{field_name} === undefined ? undefined : new Date(this.{field_name})"),
+                        "value === undefined ? undefined : value.getTime()",
+                    ),
+                    false => (
+                        "Date",
+                        format!("new Date(this.{field_name})"),
+                        "value.getTime()",
+                    ),
+                };
+                let _ = writeln!(writer, "    get {field_name}Date(): {date_type} {{");
+                let _ = writeln!(writer, "        return {to_date};");
+                let _ = writeln!(writer, "    }}");
+                let _ = writeln!(writer, "    set {field_name}Date(value: {date_type}) {{");
+                let _ = writeln!(writer, "        this.{field_name} = {from_date};");
+                let _ = writeln!(writer, "    }}");
+            }
+        }
+
+        // Encoder.
+        let _ = writeln!(writer);
+        write_typescript_doc(
+            &mut writer,
+            4,
+            "Encodes this data into a new array of bytes.",
+        )?;
+        let _ = writeln!(writer, "    encode(): Uint8Array {{");
+        let _ = writeln!(writer, "        const writer = new Writer();");
+        let _ = writeln!(writer, "        this.encodeInto(writer);");
+        let _ = writeln!(writer, "        return writer.finish();");
+        let _ = writeln!(writer, "    }}");
+        let _ = writeln!(writer);
+        write_typescript_doc(
+            &mut writer,
+            4,
+            "Encodes this data, with its header, into `writer`.",
+        )?;
+        let _ = writeln!(writer, "    encodeInto(writer: Writer): void {{");
+        if ordinal == 0 {
+            // Unspecified data is encoded as default data.
+            let _ = writeln!(writer, "        writer.writeHeader(0, 0, 0, 0);");
+        } else {
+            let _ = writeln!(
+                writer,
+                "        writer.writeHeader(1, {}, {}, {ordinal});",
+                format.blob_size, format.data_fields
+            );
+        }
+        for field in data_type.iter() {
+            let value = format!("this.{}", field.name);
+            let encoder = if field.optional {
+                typescript_optional_encoder(&field.typing, &value)
+            } else {
+                typescript_encoder(&field.typing, &value)
+            };
+            let _ = writeln!(writer, "        {encoder};");
+        }
+        let _ = writeln!(writer, "    }}");
+
+        // Decoder.
+        let _ = writeln!(writer);
+        write_typescript_doc(&mut writer, 4, "Decodes data from `bytes`.")?;
+        let _ = writeln!(
+            writer,
+            "    static decode(bytes: Uint8Array): {data_type_name} {{"
+        );
+        let _ = writeln!(
+            writer,
+            "        return {data_type_name}.decodeFrom(new Reader(bytes));"
+        );
+        let _ = writeln!(writer, "    }}");
+        let _ = writeln!(writer);
+        write_typescript_doc(
+            &mut writer,
+            4,
+            "Decodes data, with its header, from `reader`.",
+        )?;
+        let _ = writeln!(
+            writer,
+            "    static decodeFrom(reader: Reader): {data_type_name} {{"
+        );
+        if ordinal == 0 {
+            // Unspecified data is skipped.
+            let _ = writeln!(writer, "        reader.skipData();");
+        } else {
+            let _ = writeln!(
+                writer,
+                "        reader.ensureOrdinal(reader.readHeader(), {ordinal});"
+            );
+        }
+        let _ = writeln!(writer, "        const data = new {data_type_name}();");
+        for field in data_type.iter() {
+            let decoder = if field.optional {
+                typescript_optional_decoder(&field.typing)
+            } else {
+                typescript_decoder(&field.typing)
+            };
+            let _ = writeln!(writer, "        data.{} = {decoder};", field.name);
         }
+        let _ = writeln!(writer, "        return data;");
+        let _ = writeln!(writer, "    }}");
+
         let _ = write!(writer, "}}\n\n");
     }
 
     Ok(())
 }
 
+/// Generates [Zod](https://zod.dev) schemas for `coda`.
+///
+/// Each data type's schema is exported as `{Type}Schema`,
+/// and collected into an exported `schemas` object. The
+/// coda's schema is exported as `{Coda}DataSchema`, a union
+/// of every data type's schema.
+///
+/// Schemas validate the JSON representation of each type's
+/// class from [`generate_types`] (including its `__ordinal`).
+pub fn generate_zod(coda: &Coda, stream: &mut impl Writes) -> Result<(), StreamError> {
+    // Extract coda metadata.
+    let coda_type_name = format!("{}Data", coda.local_name.trim());
+    let coda_type_docs = match &coda.docs {
+        Some(docs) => docs.trim(),
+        None => "Undocumented Coda. How could you? ;~;",
+    };
+
+    let mut writer = FmtWriter::from(stream);
+
+    // Import Zod.
+    let _ = writeln!(writer, "import {{ z }} from \"zod\";\n");
+
+    // Generate data type schemas.
+    for data_type in coda.iter_all() {
+        let ordinal = data_type.format().as_data_format().ordinal;
+        let data_type_name = &data_type.name;
+        let data_type_docs = match &data_type.docs {
+            Some(docs) => docs.trim(),
+            None => "Undocumented Data. How could you? ;~;",
+        };
+        write_typescript_doc(&mut writer, 0, data_type_docs)?;
+        let _ = writeln!(writer, "export const {data_type_name}Schema = z.object({{");
+        let _ = writeln!(
+            writer,
+            "    __ordinal: z.literal({ordinal}).default({ordinal}),"
+        );
+        for field in data_type.iter() {
+            let field_name = &field.name;
+            let field_schema = zod_schema(&field.typing);
+            if field.optional {
+                let _ = writeln!(writer, "    \"{field_name}\": {field_schema}.optional(),");
+            } else {
+                let _ = writeln!(writer, "    \"{field_name}\": {field_schema},");
+            }
+        }
+        let _ = write!(writer, "}});\n\n");
+    }
+
+    // Generate schema collection.
+    write_typescript_doc(
+        &mut writer,
+        0,
+        &format!("Schemas for each data type in {{@link {coda_type_name}Schema}}."),
+    )?;
+    let _ = writeln!(writer, "export const schemas = {{");
+    for data_type in coda.iter_all() {
+        let data_type_name = &data_type.name;
+        let _ = writeln!(writer, "    {data_type_name}: {data_type_name}Schema,");
+    }
+    let _ = write!(writer, "}};\n\n");
+
+    // Generate coda schema.
+    write_typescript_doc(&mut writer, 0, coda_type_docs)?;
+    let _ = write!(
+        writer,
+        "export const {coda_type_name}Schema = z.union([schemas.Unspecified"
+    );
+    for data_type in coda.iter() {
+        let _ = write!(writer, ", schemas.{}", data_type.name);
+    }
+    let _ = writeln!(writer, "]);");
+
+    Ok(())
+}
+
 /// Writes a Typescript multi-line doc with leading
 /// `indentation` containing `docs` to `writer`.
 fn write_typescript_doc<W: Writes>(
@@ -213,6 +511,16 @@ fn write_typescript_doc<W: Writes>(
     Ok(())
 }
 
+/// Returns `docs` with a trailing `@deprecated` tag
+/// (and `note`) if `deprecated` is true.
+fn typescript_deprecated_doc(docs: &str, deprecated: bool, note: &Option<Text>) -> String {
+    match (deprecated, note) {
+        (false, _) => String::from(docs),
+        (true, Some(note)) => format!("{docs}\n\n@deprecated {note}"),
+        (true, None) => format!("{docs}\n\n@deprecated"),
+    }
+}
+
 /// Writes a number of spaces to `writer` equal to `indentation`.
 fn write_indentation<W: Writes>(
     writer: &mut FmtWriter<'_, W>,
@@ -225,6 +533,191 @@ fn write_indentation<W: Writes>(
     Ok(())
 }
 
+/// Returns a Typescript expression encoding `value`
+/// of `type` (and its header, if any) into `writer`.
+fn typescript_encoder(typing: &Type, value: &str) -> Text {
+    match typing {
+        Type::Unspecified => format!("writer.writeUnspecified({value})").into(),
+        Type::U8 => format!("writer.writeU8({value})").into(),
+        Type::U16 => format!("writer.writeU16({value})").into(),
+        Type::U32 => format!("writer.writeU32({value})").into(),
+        Type::U64 => format!("writer.writeU64({value})").into(),
+        Type::I8 => format!("writer.writeI8({value})").into(),
+        Type::I16 => format!("writer.writeI16({value})").into(),
+        Type::I32 => format!("writer.writeI32({value})").into(),
+        Type::I64 | Type::Timestamp => format!("writer.writeI64({value})").into(),
+        Type::U128 => format!("writer.writeU128({value})").into(),
+        Type::I128 => format!("writer.writeI128({value})").into(),
+        Type::F32 => format!("writer.writeF32({value})").into(),
+        Type::F64 => format!("writer.writeF64({value})").into(),
+        Type::Bool => format!("writer.writeBool({value})").into(),
+        Type::Text => format!("writer.writeText({value})").into(),
+        Type::Char => format!("writer.writeChar({value})").into(),
+        Type::Bytes => format!("writer.writeBytes({value})").into(),
+        Type::UVar => format!("writer.writeUVar({value})").into(),
+        Type::IVar => format!("writer.writeIVar({value})").into(),
+        Type::Data(_) => format!("{value}.encodeInto(writer)").into(),
+        Type::Enum(typing) => format!("writer.writeEnum({}.variants, {value})", typing.name).into(),
+        Type::List(typing) => {
+            let format = Format::data(0).with(typing.format()).as_data_format();
+            let item_encoder = typescript_encoder(typing, "item");
+            format!(
+                "writer.writeList({value}, {}, {}, (item) => {item_encoder})",
+                format.blob_size, format.data_fields
+            )
+            .into()
+        }
+        Type::Map(typing) => {
+            let key_format = Format::data(0).with(typing.0.format()).as_data_format();
+            let value_format = Format::data(0).with(typing.1.format()).as_data_format();
+            let key_encoder = typescript_encoder(&typing.0, "key");
+            let value_encoder = typescript_encoder(&typing.1, "value");
+            format!(
+                "writer.writeMap({value}, [{}, {}], [{}, {}], (key) => {key_encoder}, (value) => {value_encoder})",
+                key_format.blob_size,
+                key_format.data_fields,
+                value_format.blob_size,
+                value_format.data_fields
+            )
+            .into()
+        }
+    }
+}
+
+/// Returns a Typescript expression encoding optional
+/// `value` of `type` (and its header) into `writer`.
+fn typescript_optional_encoder(typing: &Type, value: &str) -> Text {
+    let format = Format::data(0).with(typing.format()).as_data_format();
+    let value_encoder = typescript_encoder(typing, "value");
+    format!(
+        "writer.writeOptional({value}, {}, {}, (value) => {value_encoder})",
+        format.blob_size, format.data_fields
+    )
+    .into()
+}
+
+/// Returns a Typescript expression decoding
+/// data of `type` (and its header, if any)
+/// from `reader`.
+fn typescript_decoder(typing: &Type) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("reader.readUnspecified()"),
+        Type::U8 => Text::Static("reader.readU8()"),
+        Type::U16 => Text::Static("reader.readU16()"),
+        Type::U32 => Text::Static("reader.readU32()"),
+        Type::U64 => Text::Static("reader.readU64()"),
+        Type::I8 => Text::Static("reader.readI8()"),
+        Type::I16 => Text::Static("reader.readI16()"),
+        Type::I32 => Text::Static("reader.readI32()"),
+        Type::I64 | Type::Timestamp => Text::Static("reader.readI64()"),
+        Type::U128 => Text::Static("reader.readU128()"),
+        Type::I128 => Text::Static("reader.readI128()"),
+        Type::F32 => Text::Static("reader.readF32()"),
+        Type::F64 => Text::Static("reader.readF64()"),
+        Type::Bool => Text::Static("reader.readBool()"),
+        Type::Text => Text::Static("reader.readText()"),
+        Type::Char => Text::Static("reader.readChar()"),
+        Type::Bytes => Text::Static("reader.readBytes()"),
+        Type::UVar => Text::Static("reader.readUVar()"),
+        Type::IVar => Text::Static("reader.readIVar()"),
+        Type::Data(typing) => format!("{}.decodeFrom(reader)", typing.name).into(),
+        Type::Enum(typing) => format!("reader.readEnum({}.variants)", typing.name).into(),
+        Type::List(typing) => {
+            let item_decoder = typescript_decoder(typing);
+            format!("reader.readList(() => {item_decoder})").into()
+        }
+        Type::Map(typing) => {
+            let key_decoder = typescript_decoder(&typing.0);
+            let value_decoder = typescript_decoder(&typing.1);
+            format!("reader.readMap(() => {key_decoder}, () => {value_decoder})").into()
+        }
+    }
+}
+
+/// Returns a Typescript expression decoding optional
+/// data of `type` (and its header) from `reader`.
+fn typescript_optional_decoder(typing: &Type) -> Text {
+    let value_decoder = typescript_decoder(typing);
+    format!("reader.readOptional(() => {value_decoder})").into()
+}
+
+/// Returns the Zod schema validating the
+/// JSON representation of `type`.
+fn zod_schema(typing: &Type) -> Text {
+    match typing {
+        Type::Unspecified => Text::Static("z.unknown()"),
+        Type::U8 => Text::Static("z.number().int().min(0).max(255)"),
+        Type::U16 => Text::Static("z.number().int().min(0).max(65535)"),
+        Type::U32 => Text::Static("z.number().int().min(0).max(4294967295)"),
+        // 64-bit numbers are bounded by the range of
+        // integers that are exactly representable in
+        // Typescript's `number` type.
+        Type::U64 | Type::UVar => {
+            Text::Static("z.number().int().min(0).max(Number.MAX_SAFE_INTEGER)")
+        }
+        Type::I8 => Text::Static("z.number().int().min(-128).max(127)"),
+        Type::I16 => Text::Static("z.number().int().min(-32768).max(32767)"),
+        Type::I32 => Text::Static("z.number().int().min(-2147483648).max(2147483647)"),
+        Type::I64 | Type::IVar | Type::Timestamp => Text::Static(
+            "z.number().int().min(Number.MIN_SAFE_INTEGER).max(Number.MAX_SAFE_INTEGER)",
+        ),
+        Type::U128 => Text::Static("z.bigint().min(0n).max(340282366920938463463374607431768211455n)"),
+        Type::I128 => Text::Static("z.bigint().min(-170141183460469231731687303715884105728n).max(170141183460469231731687303715884105727n)"),
+        Type::F32 => Text::Static("z.number()"),
+        Type::F64 => Text::Static("z.number()"),
+        Type::Bool => Text::Static("z.boolean()"),
+        Type::Text => Text::Static("z.string()"),
+        Type::Char => Text::Static("z.string().refine((value) => [...value].length === 1)"),
+        // Bytes are Base64-encoded strings, with optional padding.
+        Type::Bytes => Text::Static("z.string().regex(/^[A-Za-z0-9+/]*={0,2}$/)"),
+        // Data is validated lazily, since the
+        // data's schema may be declared later.
+        Type::Data(typing) => format!("z.lazy(() => {}Schema)", typing.name).into(),
+        Type::Enum(typing) => {
+            let variants = typing
+                .variants
+                .iter()
+                .map(|variant| format!("\"{variant}\""))
+                .collect::<Vec<_>>();
+            format!("z.enum([{}])", variants.join(", ")).into()
+        }
+        Type::List(typing) => format!("z.array({})", zod_schema(typing)).into(),
+        Type::Map(typing) => format!(
+            "z.record({}, {})",
+            zod_key_schema(&typing.0),
+            zod_schema(&typing.1)
+        )
+        .into(),
+    }
+}
+
+/// Returns the Zod schema validating the keys
+/// of a JSON object representing a map with
+/// keys of `type`.
+///
+/// JSON object keys are always strings: Numeric keys
+/// are coerced to numbers, and all other non-text keys
+/// are validated as plain strings.
+fn zod_key_schema(typing: &Type) -> Text {
+    match typing {
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::I64
+        | Type::UVar
+        | Type::IVar
+        | Type::Timestamp => zod_schema(typing)
+            .replacen("z.number()", "z.coerce.number()", 1)
+            .into(),
+        Type::F32 | Type::F64 => Text::Static("z.coerce.number()"),
+        _ => Text::Static("z.string()"),
+    }
+}
+
 /// Returns the Typescript literal of `type`'s default value.
 fn typescript_default_val(typing: &Type) -> Text {
     match typing {
@@ -237,19 +730,72 @@ fn typescript_default_val(typing: &Type) -> Text {
         Type::I16 => Text::Static("0"),
         Type::I32 => Text::Static("0"),
         Type::I64 => Text::Static("0"),
+        Type::U128 => Text::Static("0n"),
+        Type::I128 => Text::Static("0n"),
+        Type::UVar => Text::Static("0"),
+        Type::IVar => Text::Static("0"),
         Type::F32 => Text::Static("0.0"),
         Type::F64 => Text::Static("0.0"),
         Type::Bool => Text::Static("false"),
         Type::Text => Text::Static("\"\""),
+        Type::Char => Text::Static("\"\\0\""),
+        Type::Bytes => Text::Static("new Uint8Array()"),
+        Type::Timestamp => Text::Static("0"),
         Type::Data(typing) => {
             let name = &typing.name;
             format!("new {name}()").into()
         }
+        Type::Enum(typing) => format!("{}.variants[0]", typing.name).into(),
         Type::List(_) => Text::Static("[]"),
         Type::Map(_) => Text::Static("new Map()"),
     }
 }
 
+/// Returns the Typescript literal of the default
+/// value declared by `field`, if any.
+fn typescript_field_default(field: &DataField) -> Option<Text> {
+    Some(match field.default.as_ref()? {
+        Unspecified::U8(value) => format!("{value}").into(),
+        Unspecified::U16(value) => format!("{value}").into(),
+        Unspecified::U32(value) => format!("{value}").into(),
+        Unspecified::U64(value) => format!("{value}").into(),
+        Unspecified::U128(value) => format!("{value}n").into(),
+        Unspecified::I8(value) => format!("{value}").into(),
+        Unspecified::I16(value) => format!("{value}").into(),
+        Unspecified::I32(value) => format!("{value}").into(),
+        Unspecified::I64(value) => format!("{value}").into(),
+        Unspecified::I128(value) => format!("{value}n").into(),
+        Unspecified::UVar(value) => format!("{}", value.0).into(),
+        Unspecified::IVar(value) => format!("{}", value.0).into(),
+        Unspecified::Timestamp(value) => format!("{value}").into(),
+        Unspecified::F32(value) => format!("{value:?}").into(),
+        Unspecified::F64(value) => format!("{value:?}").into(),
+        Unspecified::Bool(value) => format!("{value}").into(),
+        Unspecified::Char(value) => typescript_string(value.encode_utf8(&mut [0; 4])),
+        Unspecified::Text(value) => typescript_string(value),
+        Unspecified::Enum { typing, variant } => typescript_string(typing.variant(*variant)?),
+        _ => return None,
+    })
+}
+
+/// Returns `value` as a quoted and escaped Typescript string.
+fn typescript_string(value: &str) -> Text {
+    let mut string = alloc::string::String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => string += "\\\"",
+            '\\' => string += "\\\\",
+            '\n' => string += "\\n",
+            '\r' => string += "\\r",
+            '\t' => string += "\\t",
+            c if c.is_control() => string += &format!("\\u{:04x}", c as u32),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string.into()
+}
+
 /// Returns the native Typescript identifier of `type`.
 ///
 /// If `type` is a [`codas::spec::Type::Data`], the
@@ -266,11 +812,18 @@ fn typescript_type(typing: &Type) -> Text {
         Type::I16 => Text::Static("number"),
         Type::I32 => Text::Static("number"),
         Type::I64 => Text::Static("number"),
+        Type::U128 => Text::Static("bigint"),
+        Type::I128 => Text::Static("bigint"),
+        Type::UVar => Text::Static("number"),
+        Type::IVar => Text::Static("number"),
         Type::F32 => Text::Static("number"),
         Type::F64 => Text::Static("number"),
         Type::Bool => Text::Static("boolean"),
-        Type::Text => Text::Static("string"),
+        Type::Text | Type::Char => Text::Static("string"),
+        Type::Bytes => Text::Static("Uint8Array"),
+        Type::Timestamp => Text::Static("number"),
         Type::Data(typing) => typing.name.clone(),
+        Type::Enum(typing) => typing.name.clone(),
         Type::List(typing) => {
             let typing = typescript_type(typing.as_ref());
             format!("Array<{typing}>").into()
@@ -282,3 +835,230 @@ fn typescript_type(typing: &Type) -> Text {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use super::*;
+
+    use crate::{
+        codec::{encode_to_vec, WritesEncodable},
+        parse::{parse, tests::TEST_CODA_MARKDOWN},
+        types::{
+            binary::hex_from_bytes,
+            number::{VarI64, VarU64},
+        },
+    };
+
+    #[test]
+    fn generates_codecs() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut typescript = vec![];
+        generate_types(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+
+        assert!(typescript.starts_with("import { Reader, Writer } from \"./codas-runtime\";\n"));
+        assert!(typescript.contains("\n            case 2: return MyDataType.decodeFrom(reader);\n            default: return Unspecified.decodeFrom(reader);\n"));
+
+        // Unspecified data is encoded as default data.
+        assert!(typescript.contains("\n        writer.writeHeader(0, 0, 0, 0);\n"));
+        assert!(typescript
+            .contains("\n        reader.skipData();\n        const data = new Unspecified();\n"));
+
+        // Fields are encoded in order, blob fields first.
+        assert!(typescript.contains(
+            "\n        writer.writeHeader(1, 4, 6, 2);\n        writer.writeI32(this.integral_field);\n        writer.writeText(this.textual_field);\n        this.nested_field.encodeInto(writer);\n        writer.writeOptional(this.optional_field, 8, 0, (value) => writer.writeU64(value));\n"
+        ));
+        assert!(typescript.contains(
+            "\n        writer.writeList(this.3d_field, 0, 1, (item) => writer.writeList(item, 0, 1, (item) => writer.writeList(item, 4, 0, (item) => writer.writeI32(item))));\n"
+        ));
+        assert!(typescript.contains(
+            "\n        writer.writeMap(this.map_field, [0, 1], [4, 0], (key) => writer.writeText(key), (value) => writer.writeI32(value));\n        writer.writeUnspecified(this.unspecified_field);\n"
+        ));
+
+        // Fields are decoded in the same order.
+        assert!(typescript.contains(
+            "\n        reader.ensureOrdinal(reader.readHeader(), 2);\n        const data = new MyDataType();\n        data.integral_field = reader.readI32();\n        data.textual_field = reader.readText();\n        data.nested_field = MyNestedDataType.decodeFrom(reader);\n        data.optional_field = reader.readOptional(() => reader.readU64());\n"
+        ));
+        assert!(typescript.contains(
+            "\n        data.map_field = reader.readMap(() => reader.readText(), () => reader.readI32());\n"
+        ));
+    }
+
+    #[test]
+    fn generates_timestamp_accessors() {
+        let coda = parse(
+            "# `Log` Coda\n\n## `Entry` Data\n\n+ `created_at` timestamp\n+ `deleted_at` optional timestamp",
+        )
+        .unwrap();
+        let mut typescript = vec![];
+        generate_types(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+
+        // Timestamps are encoded like `i64`s.
+        assert!(typescript.contains("\n    created_at: number = 0;\n"));
+        assert!(typescript.contains("\n        writer.writeI64(this.created_at);\n"));
+        assert!(typescript.contains("\n        data.created_at = reader.readI64();\n"));
+
+        // Timestamps are accessible as dates.
+        assert!(typescript.contains(
+            "\n    get created_atDate(): Date {\n        return new Date(this.created_at);\n    }\n    set created_atDate(value: Date) {\n        this.created_at = value.getTime();\n    }\n"
+        ));
+        assert!(typescript.contains(
+            "\n    get deleted_atDate(): Date | undefined {\n        return this.deleted_at === undefined ? undefined : new Date(this.deleted_at);\n    }\n"
+        ));
+    }
+
+    #[test]
+    fn generates_enums() {
+        let coda = parse(
+            "# `Accounts` Coda\n\n## `User` Data\n\n+ `status` one of [Active, Suspended, Deleted]\n+ `role` optional one of [Admin]",
+        )
+        .unwrap();
+        let mut typescript = vec![];
+        generate_types(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+
+        // Enums are unions of their variants' names...
+        assert!(typescript
+            .contains("\nexport type UserStatus = \"Active\" | \"Suspended\" | \"Deleted\";\n"));
+        assert!(typescript.contains(
+            "\nexport const UserStatus = {\n    /**\n     * Every variant, indexed by its ordinal.\n     */\n    variants: [\"Active\", \"Suspended\", \"Deleted\"] as const,\n"
+        ));
+        assert!(typescript.contains("\n    status: UserStatus = UserStatus.variants[0];\n"));
+        assert!(typescript.contains("\n    role?: UserRole = undefined;\n"));
+
+        // ...encoded as their variants' ordinals.
+        assert!(
+            typescript.contains("\n        writer.writeEnum(UserStatus.variants, this.status);\n")
+        );
+        assert!(typescript.contains(
+            "\n        data.role = reader.readOptional(() => reader.readEnum(UserRole.variants));\n"
+        ));
+
+        let mut typescript = vec![];
+        generate_zod(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+        assert!(typescript
+            .contains("\n    \"status\": z.enum([\"Active\", \"Suspended\", \"Deleted\"]),\n"));
+    }
+
+    #[test]
+    fn generates_deprecations() {
+        let coda = parse("# `Users` Coda\n\n## `Account` Data\n\n+ `name` text\n\n## `User` Data\n\n> Deprecated: Use \"Account\".\n\n+ `id` u32\n+ `old_id` deprecated u64\n+ `nickname` optional deprecated text\n\n    > Deprecated: Use `Account.name`.\n\n+ `account` deprecated Account\n").unwrap();
+        let mut typescript = vec![];
+        generate_types(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+
+        assert!(typescript.contains("\n * Class corresponding to {@link UsersData.User}.\n * \n * @deprecated Use \"Account\".\n */\nexport class User {"));
+        assert!(typescript.contains("\n     * @deprecated\n     */\n    old_id: number = 0;\n"));
+        assert!(typescript.contains("\n     * @deprecated Use `Account.name`.\n     */\n    nickname?: string = undefined;\n"));
+        assert!(!typescript.contains("@deprecated\n     */\n    id: number"));
+    }
+
+    #[test]
+    fn generates_defaults() {
+        let coda = parse("# `Jobs` Coda\n\n## `Job` Data\n\n+ `retries` u32 = 3\n+ `total` u128 = 12\n+ `name` optional text = \"the \\\"first\\\" job\"\n+ `status` one of [Queued, Running] = Running\n+ `started_at` timestamp = 1700000000000\n").unwrap();
+        let mut typescript = vec![];
+        generate_types(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+
+        assert!(typescript.contains("\n    retries: number = 3;\n"));
+        assert!(typescript.contains("\n    total: bigint = 12n;\n"));
+        assert!(typescript.contains("\n    name?: string = \"the \\\"first\\\" job\";\n"));
+        assert!(typescript.contains("\n    status: JobStatus = \"Running\";\n"));
+        assert!(typescript.contains("\n    started_at: number = 1700000000000;\n"));
+    }
+
+    #[test]
+    fn generates_zod_schemas() {
+        let coda = parse(TEST_CODA_MARKDOWN).unwrap();
+        let mut typescript = vec![];
+        generate_zod(&coda, &mut typescript).unwrap();
+        let typescript = alloc::string::String::from_utf8_lossy(&typescript);
+
+        assert!(typescript.starts_with("import { z } from \"zod\";\n"));
+        assert!(typescript.contains(
+            "\nexport const MyNestedDataTypeSchema = z.object({\n    __ordinal: z.literal(1).default(1),\n    \"floaty_field\": z.number(),\n    \"listy_field\": z.array(z.string()),\n});\n"
+        ));
+        assert!(typescript.contains(
+            "\n    \"integral_field\": z.number().int().min(-2147483648).max(2147483647),\n    \"textual_field\": z.string(),\n    \"nested_field\": z.lazy(() => MyNestedDataTypeSchema),\n    \"optional_field\": z.number().int().min(0).max(Number.MAX_SAFE_INTEGER).optional(),\n"
+        ));
+        assert!(typescript.contains(
+            "\n    \"map_field\": z.record(z.string(), z.number().int().min(-2147483648).max(2147483647)),\n    \"unspecified_field\": z.unknown(),\n"
+        ));
+        assert!(typescript.contains(
+            "\nexport const schemas = {\n    Unspecified: UnspecifiedSchema,\n    MyNestedDataType: MyNestedDataTypeSchema,\n    MyDataType: MyDataTypeSchema,\n};\n"
+        ));
+        assert!(typescript.ends_with(
+            "\nexport const MyCodaDataSchema = z.union([schemas.Unspecified, schemas.MyNestedDataType, schemas.MyDataType]);\n"
+        ));
+
+        assert_eq!(
+            "z.string().regex(/^[A-Za-z0-9+/]*={0,2}$/)",
+            &*zod_schema(&Type::Bytes)
+        );
+        assert_eq!(
+            "z.number().int().min(Number.MIN_SAFE_INTEGER).max(Number.MAX_SAFE_INTEGER)",
+            &*zod_schema(&Type::Timestamp)
+        );
+
+        // Numeric map keys are coerced from JSON object keys.
+        assert_eq!(
+            "z.coerce.number().int().min(0).max(255)",
+            &*zod_key_schema(&Type::U8)
+        );
+    }
+
+    /// Checks the encodings expected by `codas-runtime.test.ts`.
+    #[test]
+    fn runtime_fixtures_match_codec() {
+        let encoded = encode_to_vec(&Text::from("hi")).unwrap();
+        assert_eq!("02000000010000006869", &*hex_from_bytes(&encoded));
+
+        let encoded = encode_to_vec(&vec![0xDEu8, 0xAD]).unwrap();
+        assert_eq!("0200000001000000dead", &*hex_from_bytes(&encoded));
+
+        let encoded = encode_to_vec(&'🦀').unwrap();
+        assert_eq!("80f90100", &*hex_from_bytes(&encoded));
+
+        // Enums are encoded as their variants' `u16` ordinals.
+        let encoded = encode_to_vec(&1u16).unwrap();
+        assert_eq!("0100", &*hex_from_bytes(&encoded));
+
+        let mut encoded = vec![];
+        encoded.write_data(&VarU64(300)).unwrap();
+        encoded.write_data(&VarI64(-65)).unwrap();
+        assert_eq!(
+            "02000000010000eeac0202000000010000ed8101",
+            &*hex_from_bytes(&encoded)
+        );
+
+        let mut encoded = vec![];
+        encoded.write_data(&Some(0u32)).unwrap();
+        encoded.write_data(&None::<u32>).unwrap();
+        assert_eq!(
+            "0100000004000000000000000000000000000000",
+            &*hex_from_bytes(&encoded)
+        );
+
+        let mut encoded = vec![];
+        encoded
+            .write_data(&BTreeMap::from([
+                (Text::from("b"), 2i32),
+                (Text::from("a"), 1),
+            ]))
+            .unwrap();
+        assert_eq!(
+            "0100000000000200020000000000010001000000010000006101000000010000006202000000040000000100000002000000",
+            &*hex_from_bytes(&encoded)
+        );
+
+        let encoded = encode_to_vec(&vec![vec![1i16, -2], vec![]]).unwrap();
+        assert_eq!(
+            "020000000000010002000000020000000100feff0000000002000000",
+            &*hex_from_bytes(&encoded)
+        );
+    }
+}
@@ -0,0 +1,157 @@
+/**
+ * Tests for the Typescript runtime, runnable with `tsc` and Node.
+ *
+ * @remarks
+ *
+ * Each expected encoding in this file is also checked
+ * against the Rust codec by `codas::langs::typescript`'s
+ * tests, so that data encoded by either codec can be
+ * decoded by the other.
+ */
+import assert from "node:assert";
+
+import { Reader, Writer } from "./codas-runtime";
+
+function hex(bytes: Uint8Array): string {
+    return Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+function bytes(hex: string): Uint8Array {
+    return Uint8Array.from(hex.match(/../g) ?? [], (byte) => parseInt(byte, 16));
+}
+
+// Text.
+{
+    const writer = new Writer();
+    writer.writeText("hi");
+    assert.equal(hex(writer.finish()), "02000000010000006869");
+    assert.equal(new Reader(bytes("02000000010000006869")).readText(), "hi");
+}
+
+// Bytes.
+{
+    const writer = new Writer();
+    writer.writeBytes(Uint8Array.of(0xde, 0xad));
+    assert.equal(hex(writer.finish()), "0200000001000000dead");
+    assert.deepEqual(new Reader(bytes("0200000001000000dead")).readBytes(), Uint8Array.of(0xde, 0xad));
+}
+
+// Characters.
+{
+    const writer = new Writer();
+    writer.writeChar("🦀");
+    assert.equal(hex(writer.finish()), "80f90100");
+    assert.equal(new Reader(bytes("80f90100")).readChar(), "🦀");
+    assert.throws(() => new Reader(bytes("00d80000")).readChar(), /unicode scalar value/);
+}
+
+// Enums.
+{
+    const variants = ["Active", "Deleted"] as const;
+    const writer = new Writer();
+    writer.writeEnum(variants, "Deleted");
+    assert.equal(hex(writer.finish()), "0100");
+    assert.equal(new Reader(bytes("0100")).readEnum(variants), "Deleted");
+    assert.throws(() => new Reader(bytes("0200")).readEnum(variants), /known enum variant/);
+    assert.throws(() => new Writer().writeEnum(variants, "Unknown"), /known enum variant/);
+}
+
+// Variable-length integers.
+{
+    const writer = new Writer();
+    writer.writeUVar(300);
+    writer.writeIVar(-65);
+    const encoded = "02000000010000eeac02" + "02000000010000ed8101";
+    assert.equal(hex(writer.finish()), encoded);
+
+    const reader = new Reader(bytes(encoded));
+    assert.equal(reader.readUVar(), 300);
+    assert.equal(reader.readIVar(), -65);
+    assert.throws(() => new Reader(bytes("02000000010000ee8000")).readUVar(), /invalid/);
+}
+
+// Present and absent optional numbers.
+{
+    const writer = new Writer();
+    writer.writeOptional(0, 4, 0, (value) => writer.writeU32(value));
+    writer.writeOptional(undefined, 4, 0, (value: number) => writer.writeU32(value));
+    const encoded = "010000000400000000000000" + "0000000000000000";
+    assert.equal(hex(writer.finish()), encoded);
+
+    const reader = new Reader(bytes(encoded));
+    assert.equal(reader.readOptional(() => reader.readU32()), 0);
+    assert.equal(reader.readOptional(() => reader.readU32()), undefined);
+}
+
+// Maps are sorted by their keys.
+{
+    const writer = new Writer();
+    writer.writeMap(
+        new Map([["b", 2], ["a", 1]]),
+        [0, 1],
+        [4, 0],
+        (key) => writer.writeText(key),
+        (value) => writer.writeI32(value),
+    );
+    const encoded =
+        "0100000000000200" +
+        "0200000000000100" +
+        "010000000100000061" +
+        "010000000100000062" +
+        "0200000004000000" +
+        "0100000002000000";
+    assert.equal(hex(writer.finish()), encoded);
+
+    const reader = new Reader(bytes(encoded));
+    const map = reader.readMap(() => reader.readText(), () => reader.readI32());
+    assert.deepStrictEqual(map, new Map([["a", 1], ["b", 2]]));
+}
+
+// Lists of lists.
+{
+    const writer = new Writer();
+    writer.writeList([[1, -2], []], 0, 1, (item) =>
+        writer.writeList(item, 2, 0, (item) => writer.writeI16(item)),
+    );
+    const encoded = "0200000000000100" + "02000000020000000100feff" + "0000000002000000";
+    assert.equal(hex(writer.finish()), encoded);
+
+    const reader = new Reader(bytes(encoded));
+    const lists = reader.readList(() => reader.readList(() => reader.readI16()));
+    assert.deepStrictEqual(lists, [[1, -2], []]);
+}
+
+// Big numbers.
+{
+    const writer = new Writer();
+    writer.writeU64(2 ** 40);
+    writer.writeI128(-2n);
+    writer.writeU128((1n << 127n) + 5n);
+
+    const reader = new Reader(writer.finish());
+    assert.equal(reader.readU64(), 2 ** 40);
+    assert.equal(reader.readI128(), -2n);
+    assert.equal(reader.readU128(), (1n << 127n) + 5n);
+}
+
+// Unspecified data is read as its raw encoding.
+{
+    const encoded = "0100000000000101" + "02000000010000006869";
+    const reader = new Reader(bytes(encoded + "0000000000000000"));
+    const data = reader.readUnspecified();
+    assert.equal(hex(data as Uint8Array), encoded);
+    assert.equal(reader.readUnspecified(), undefined);
+
+    const writer = new Writer();
+    writer.writeUnspecified(data);
+    writer.writeUnspecified(undefined);
+    assert.equal(hex(writer.finish()), encoded + "0000000000000000");
+}
+
+// Truncated data can't be read.
+{
+    const reader = new Reader(bytes("0200000001000000"));
+    assert.throws(() => reader.readText(), /unexpected end of stream/);
+}
+
+console.log("codas-runtime: ok");
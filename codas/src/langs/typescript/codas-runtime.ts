@@ -0,0 +1,660 @@
+/**
+ * Runtime for encoding and decoding coda-encoded data,
+ * used by Typescript generated from codas.
+ *
+ * @remarks
+ *
+ * This runtime mirrors the `codas::codec` module of the
+ * Rust `codas` crate. All multi-byte values are little-endian.
+ */
+
+/**
+ * Size of an encoded {@link DataHeader}, in bytes.
+ */
+export const DATA_HEADER_SIZE = 8;
+
+/**
+ * Ordinal of the header preceding variable-length unsigned integers.
+ */
+export const UVAR_ORDINAL = 238;
+
+/**
+ * Ordinal of the header preceding variable-length signed integers.
+ */
+export const IVAR_ORDINAL = 237;
+
+/**
+ * Maximum number of bytes in a variable-length integer.
+ */
+const VAR_INT_MAX_LENGTH = 10;
+
+/**
+ * Header preceding each sequence of structured data.
+ */
+export class DataHeader {
+    constructor(
+        /** Number of data in the sequence. */
+        public count: number = 0,
+        /** Total size of each datum's blob fields, in bytes. */
+        public blobSize: number = 0,
+        /** Number of data fields following each datum's blob fields. */
+        public dataFields: number = 0,
+        /** Ordinal identifier of the data's type. */
+        public ordinal: number = 0,
+    ) {}
+}
+
+/**
+ * Error thrown when data can't be encoded or decoded.
+ */
+export class CodecError extends Error {
+    constructor(message: string) {
+        super(message);
+        this.name = "CodecError";
+    }
+}
+
+/**
+ * Writes coda-encoded data into a growable buffer.
+ */
+export class Writer {
+    private bytes: Uint8Array = new Uint8Array(64);
+    private view: DataView = new DataView(this.bytes.buffer);
+    private length: number = 0;
+
+    /**
+     * Returns a copy of all bytes written so far.
+     */
+    finish(): Uint8Array {
+        return this.bytes.slice(0, this.length);
+    }
+
+    /**
+     * Writes a {@link DataHeader}.
+     */
+    writeHeader(count: number, blobSize: number, dataFields: number, ordinal: number): void {
+        if (!Number.isInteger(count) || count < 0 || count > 0xffffffff) {
+            throw new CodecError(`sequence length ${count} exceeds maximum count (4294967295)`);
+        }
+        this.reserve(DATA_HEADER_SIZE);
+        this.view.setUint32(this.length, count, true);
+        this.view.setUint16(this.length + 4, blobSize, true);
+        this.view.setUint8(this.length + 6, dataFields);
+        this.view.setUint8(this.length + 7, ordinal);
+        this.length += DATA_HEADER_SIZE;
+    }
+
+    /**
+     * Writes `bytes` as-is.
+     */
+    writeRaw(bytes: Uint8Array): void {
+        this.reserve(bytes.length);
+        this.bytes.set(bytes, this.length);
+        this.length += bytes.length;
+    }
+
+    writeU8(value: number): void {
+        this.reserve(1);
+        this.view.setUint8(this.length, value);
+        this.length += 1;
+    }
+
+    writeU16(value: number): void {
+        this.reserve(2);
+        this.view.setUint16(this.length, value, true);
+        this.length += 2;
+    }
+
+    writeU32(value: number): void {
+        this.reserve(4);
+        this.view.setUint32(this.length, value, true);
+        this.length += 4;
+    }
+
+    writeU64(value: number): void {
+        this.reserve(8);
+        this.view.setBigUint64(this.length, BigInt(value), true);
+        this.length += 8;
+    }
+
+    writeI8(value: number): void {
+        this.reserve(1);
+        this.view.setInt8(this.length, value);
+        this.length += 1;
+    }
+
+    writeI16(value: number): void {
+        this.reserve(2);
+        this.view.setInt16(this.length, value, true);
+        this.length += 2;
+    }
+
+    writeI32(value: number): void {
+        this.reserve(4);
+        this.view.setInt32(this.length, value, true);
+        this.length += 4;
+    }
+
+    writeI64(value: number): void {
+        this.reserve(8);
+        this.view.setBigInt64(this.length, BigInt(value), true);
+        this.length += 8;
+    }
+
+    writeU128(value: bigint): void {
+        this.reserve(16);
+        this.view.setBigUint64(this.length, BigInt.asUintN(64, value), true);
+        this.view.setBigUint64(this.length + 8, BigInt.asUintN(64, value >> 64n), true);
+        this.length += 16;
+    }
+
+    writeI128(value: bigint): void {
+        this.writeU128(BigInt.asUintN(128, value));
+    }
+
+    writeF32(value: number): void {
+        this.reserve(4);
+        this.view.setFloat32(this.length, value, true);
+        this.length += 4;
+    }
+
+    writeF64(value: number): void {
+        this.reserve(8);
+        this.view.setFloat64(this.length, value, true);
+        this.length += 8;
+    }
+
+    writeBool(value: boolean): void {
+        this.writeU8(value ? 1 : 0);
+    }
+
+    /**
+     * Writes `value` as a variable-length (LEB128)
+     * unsigned integer, with its header.
+     */
+    writeUVar(value: number): void {
+        this.writeVarInt(BigInt(value), UVAR_ORDINAL);
+    }
+
+    /**
+     * Writes `value` as a ZigZag- and variable-length (LEB128)
+     * encoded signed integer, with its header.
+     */
+    writeIVar(value: number): void {
+        const signed = BigInt(value);
+        this.writeVarInt(signed >= 0n ? signed << 1n : (-signed << 1n) - 1n, IVAR_ORDINAL);
+    }
+
+    /**
+     * Writes the variable-length encoding of
+     * `value`, with a header containing `ordinal`.
+     */
+    private writeVarInt(value: bigint, ordinal: number): void {
+        const bytes: Array<number> = [];
+        do {
+            let byte = Number(value & 0x7fn);
+            value >>= 7n;
+            if (value !== 0n) {
+                byte |= 0x80;
+            }
+            bytes.push(byte);
+        } while (value !== 0n);
+        this.writeHeader(bytes.length, 1, 0, ordinal);
+        this.writeRaw(Uint8Array.from(bytes));
+    }
+
+    /**
+     * Writes the first character of `value`
+     * as a 32-bit Unicode scalar value.
+     */
+    writeChar(value: string): void {
+        this.writeU32(value.codePointAt(0) ?? 0);
+    }
+
+    /**
+     * Writes the 16-bit ordinal of `value` within `variants`.
+     */
+    writeEnum(variants: readonly string[], value: string): void {
+        const ordinal = variants.indexOf(value);
+        if (ordinal < 0) {
+            throw new CodecError(`${value} is not a known enum variant`);
+        }
+        this.writeU16(ordinal);
+    }
+
+    /**
+     * Writes `value` as UTF-8 encoded text, with its header.
+     */
+    writeText(value: string): void {
+        const bytes = TEXT_ENCODER.encode(value);
+        this.writeHeader(bytes.length, 1, 0, 0);
+        this.writeRaw(bytes);
+    }
+
+    /**
+     * Writes `value` as opaque bytes, with its header.
+     */
+    writeBytes(value: Uint8Array): void {
+        this.writeHeader(value.length, 1, 0, 0);
+        this.writeRaw(value);
+    }
+
+    /**
+     * Writes unspecified data, with its header.
+     *
+     * @remarks
+     *
+     * `undefined` is written as default (empty) data; otherwise,
+     * `value` must be the raw bytes of some coda-encoded data,
+     * like those returned by {@link Reader.readUnspecified}.
+     */
+    writeUnspecified(value: unknown): void {
+        if (value === undefined) {
+            this.writeHeader(0, 0, 0, 0);
+        } else if (value instanceof Uint8Array) {
+            this.writeRaw(value);
+        } else {
+            throw new CodecError("unspecified data must be undefined or a Uint8Array");
+        }
+    }
+
+    /**
+     * Writes a list of `items`, with its header, where each item
+     * has a format of `blobSize` bytes and `dataFields` data fields.
+     */
+    writeList<T>(
+        items: Array<T>,
+        blobSize: number,
+        dataFields: number,
+        writeItem: (item: T) => void,
+    ): void {
+        this.writeHeader(items.length, blobSize, dataFields, 0);
+        for (const item of items) {
+            writeItem(item);
+        }
+    }
+
+    /**
+     * Writes a map of `entries`, with its header, as a
+     * list of keys followed by a list of values, each
+     * sorted in the order of their keys.
+     */
+    writeMap<K, V>(
+        entries: Map<K, V>,
+        keyFormat: [number, number],
+        valueFormat: [number, number],
+        writeKey: (key: K) => void,
+        writeValue: (value: V) => void,
+    ): void {
+        const sorted = [...entries.entries()].sort(([a], [b]) => compareKeys(a, b));
+        this.writeHeader(1, 0, 2, 0);
+        this.writeList(sorted.map(([key]) => key), keyFormat[0], keyFormat[1], writeKey);
+        this.writeList(sorted.map(([, value]) => value), valueFormat[0], valueFormat[1], writeValue);
+    }
+
+    /**
+     * Writes an optional `value`, with its header, where
+     * the value has a format of `blobSize` bytes and
+     * `dataFields` data fields.
+     */
+    writeOptional<T>(
+        value: T | undefined,
+        blobSize: number,
+        dataFields: number,
+        writeValue: (value: T) => void,
+    ): void {
+        if (value === undefined) {
+            this.writeHeader(0, 0, 0, 0);
+        } else {
+            this.writeHeader(1, blobSize, dataFields, 0);
+            writeValue(value);
+        }
+    }
+
+    /**
+     * Ensures at least `additional` more bytes can be written.
+     */
+    private reserve(additional: number): void {
+        const required = this.length + additional;
+        if (required <= this.bytes.length) {
+            return;
+        }
+
+        let capacity = this.bytes.length * 2;
+        while (capacity < required) {
+            capacity *= 2;
+        }
+        const bytes = new Uint8Array(capacity);
+        bytes.set(this.bytes.subarray(0, this.length));
+        this.bytes = bytes;
+        this.view = new DataView(bytes.buffer);
+    }
+}
+
+/**
+ * Reads coda-encoded data from an array of bytes.
+ */
+export class Reader {
+    private readonly view: DataView;
+    private offset: number = 0;
+
+    constructor(private readonly bytes: Uint8Array) {
+        this.view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+    }
+
+    /**
+     * Returns the number of bytes read so far.
+     */
+    position(): number {
+        return this.offset;
+    }
+
+    /**
+     * Reads a {@link DataHeader}.
+     */
+    readHeader(): DataHeader {
+        const at = this.advance(DATA_HEADER_SIZE);
+        return new DataHeader(
+            this.view.getUint32(at, true),
+            this.view.getUint16(at + 4, true),
+            this.view.getUint8(at + 6),
+            this.view.getUint8(at + 7),
+        );
+    }
+
+    /**
+     * Returns the ordinal of the next {@link DataHeader},
+     * without reading it.
+     */
+    peekOrdinal(): number {
+        if (this.offset + DATA_HEADER_SIZE > this.bytes.length) {
+            throw new CodecError("unexpected end of stream");
+        }
+        return this.view.getUint8(this.offset + 7);
+    }
+
+    /**
+     * Throws unless `header` has one of `ordinals`.
+     */
+    ensureOrdinal(header: DataHeader, ...ordinals: Array<number>): DataHeader {
+        if (!ordinals.includes(header.ordinal)) {
+            throw new CodecError(`unsupported data format (ordinal ${header.ordinal})`);
+        }
+        return header;
+    }
+
+    /**
+     * Reads `length` bytes as-is.
+     */
+    readRaw(length: number): Uint8Array {
+        const at = this.advance(length);
+        return this.bytes.slice(at, at + length);
+    }
+
+    readU8(): number {
+        return this.view.getUint8(this.advance(1));
+    }
+
+    readU16(): number {
+        return this.view.getUint16(this.advance(2), true);
+    }
+
+    readU32(): number {
+        return this.view.getUint32(this.advance(4), true);
+    }
+
+    readU64(): number {
+        return Number(this.view.getBigUint64(this.advance(8), true));
+    }
+
+    readI8(): number {
+        return this.view.getInt8(this.advance(1));
+    }
+
+    readI16(): number {
+        return this.view.getInt16(this.advance(2), true);
+    }
+
+    readI32(): number {
+        return this.view.getInt32(this.advance(4), true);
+    }
+
+    readI64(): number {
+        return Number(this.view.getBigInt64(this.advance(8), true));
+    }
+
+    readU128(): bigint {
+        const at = this.advance(16);
+        const low = this.view.getBigUint64(at, true);
+        const high = this.view.getBigUint64(at + 8, true);
+        return (high << 64n) | low;
+    }
+
+    readI128(): bigint {
+        return BigInt.asIntN(128, this.readU128());
+    }
+
+    readF32(): number {
+        return this.view.getFloat32(this.advance(4), true);
+    }
+
+    readF64(): number {
+        return this.view.getFloat64(this.advance(8), true);
+    }
+
+    readBool(): boolean {
+        return this.readU8() === 1;
+    }
+
+    /**
+     * Reads a variable-length (LEB128) unsigned
+     * integer, with its header.
+     */
+    readUVar(): number {
+        return Number(this.readVarInt(UVAR_ORDINAL));
+    }
+
+    /**
+     * Reads a ZigZag- and variable-length (LEB128)
+     * encoded signed integer, with its header.
+     */
+    readIVar(): number {
+        const zigzag = this.readVarInt(IVAR_ORDINAL);
+        return Number((zigzag & 1n) === 0n ? zigzag >> 1n : -((zigzag + 1n) >> 1n));
+    }
+
+    /**
+     * Reads the canonical variable-length encoding of
+     * an integer, with a header containing `ordinal`.
+     */
+    private readVarInt(ordinal: number): bigint {
+        const header = this.ensureOrdinal(this.readHeader(), ordinal);
+        if (
+            header.blobSize !== 1 ||
+            header.dataFields !== 0 ||
+            header.count === 0 ||
+            header.count > VAR_INT_MAX_LENGTH
+        ) {
+            throw new CodecError("expected to decode a variable-length integer");
+        }
+        const at = this.advance(header.count);
+        let value = 0n;
+        for (let i = 0; i < header.count; i++) {
+            const byte = this.bytes[at + i];
+            const last = i === header.count - 1;
+            const continues = (byte & 0x80) !== 0;
+            const overflows = i === VAR_INT_MAX_LENGTH - 1 && byte > 1;
+            const redundant = last && i > 0 && byte === 0;
+            if (continues === last || overflows || redundant) {
+                throw new CodecError("invalid variable-length integer");
+            }
+            value |= BigInt(byte & 0x7f) << BigInt(7 * i);
+        }
+        return value;
+    }
+
+    /**
+     * Reads a 32-bit Unicode scalar value as a character.
+     */
+    readChar(): string {
+        const value = this.readU32();
+        if (value > 0x10ffff || (value >= 0xd800 && value <= 0xdfff)) {
+            throw new CodecError("expected to decode a unicode scalar value");
+        }
+        return String.fromCodePoint(value);
+    }
+
+    /**
+     * Reads a 16-bit ordinal of one of `variants`.
+     */
+    readEnum<V extends string>(variants: readonly V[]): V {
+        const ordinal = this.readU16();
+        if (ordinal >= variants.length) {
+            throw new CodecError(`${ordinal} is not the ordinal of a known enum variant`);
+        }
+        return variants[ordinal];
+    }
+
+    /**
+     * Reads UTF-8 encoded text, with its header.
+     */
+    readText(): string {
+        const header = this.ensureOrdinal(this.readHeader(), 0);
+        if (header.blobSize !== 1 || header.dataFields !== 0) {
+            throw new CodecError("expected to decode text");
+        }
+        const at = this.advance(header.count);
+        return TEXT_DECODER.decode(this.bytes.subarray(at, at + header.count));
+    }
+
+    /**
+     * Reads opaque bytes, with their header.
+     */
+    readBytes(): Uint8Array {
+        const header = this.ensureOrdinal(this.readHeader(), 0);
+        if (header.blobSize !== 1 || header.dataFields !== 0) {
+            throw new CodecError("expected to decode bytes");
+        }
+        const at = this.advance(header.count);
+        return this.bytes.slice(at, at + header.count);
+    }
+
+    /**
+     * Reads unspecified data, with its header.
+     *
+     * @remarks
+     *
+     * Default (empty) data is returned as `undefined`; all other
+     * data is returned as the raw bytes of its encoding.
+     */
+    readUnspecified(): unknown {
+        const start = this.offset;
+        if (this.peekCount() === 0 && this.peekOrdinal() === 0) {
+            this.skipData();
+            return undefined;
+        }
+        this.skipData();
+        return this.bytes.slice(start, this.offset);
+    }
+
+    /**
+     * Reads a list, with its header, reading each item with `readItem`.
+     */
+    readList<T>(readItem: () => T): Array<T> {
+        const header = this.ensureOrdinal(this.readHeader(), 0);
+        const items: Array<T> = [];
+        for (let i = 0; i < header.count; i++) {
+            items.push(readItem());
+        }
+        return items;
+    }
+
+    /**
+     * Reads a map, with its header, reading each
+     * key with `readKey` and value with `readValue`.
+     */
+    readMap<K, V>(readKey: () => K, readValue: () => V): Map<K, V> {
+        this.ensureOrdinal(this.readHeader(), 0);
+        const keys = this.readList(readKey);
+        const values = this.readList(readValue);
+        if (keys.length !== values.length) {
+            throw new CodecError(`a map has ${keys.length} keys but ${values.length} values`);
+        }
+        return new Map(keys.map((key, i) => [key, values[i]]));
+    }
+
+    /**
+     * Reads an optional value, with its header,
+     * reading the value (if any) with `readValue`.
+     */
+    readOptional<T>(readValue: () => T): T | undefined {
+        const header = this.ensureOrdinal(this.readHeader(), 0);
+        if (header.count === 0) {
+            return undefined;
+        }
+        return readValue();
+    }
+
+    /**
+     * Skips the next sequence of data, with its header.
+     */
+    skipData(): void {
+        const header = this.readHeader();
+        for (let i = 0; i < header.count; i++) {
+            this.advance(header.blobSize);
+            for (let j = 0; j < header.dataFields; j++) {
+                this.skipData();
+            }
+        }
+    }
+
+    /**
+     * Returns the count of the next {@link DataHeader},
+     * without reading it.
+     */
+    private peekCount(): number {
+        if (this.offset + DATA_HEADER_SIZE > this.bytes.length) {
+            throw new CodecError("unexpected end of stream");
+        }
+        return this.view.getUint32(this.offset, true);
+    }
+
+    /**
+     * Advances past the next `length` bytes,
+     * returning the offset they start at.
+     */
+    private advance(length: number): number {
+        const at = this.offset;
+        if (at + length > this.bytes.length) {
+            throw new CodecError("unexpected end of stream");
+        }
+        this.offset += length;
+        return at;
+    }
+}
+
+const TEXT_ENCODER = new TextEncoder();
+const TEXT_DECODER = new TextDecoder("utf-8", { fatal: true });
+
+/**
+ * Compares map keys in the same order as the Rust codec,
+ * which sorts text by its UTF-8 encoded bytes.
+ */
+function compareKeys(a: unknown, b: unknown): number {
+    if (typeof a === "string" && typeof b === "string") {
+        const left = TEXT_ENCODER.encode(a);
+        const right = TEXT_ENCODER.encode(b);
+        const length = Math.min(left.length, right.length);
+        for (let i = 0; i < length; i++) {
+            if (left[i] !== right[i]) {
+                return left[i] - right[i];
+            }
+        }
+        return left.length - right.length;
+    }
+
+    if (a === b) {
+        return 0;
+    }
+    return (a as number | bigint | boolean) < (b as number | bigint | boolean) ? -1 : 1;
+}
@@ -5,6 +5,8 @@
 extern crate alloc;
 
 pub mod codec;
+#[cfg(any(feature = "examples", test))]
+pub mod examples;
 #[cfg(any(feature = "langs", test))]
 pub mod langs;
 #[cfg(any(feature = "parse", test))]
@@ -7,26 +7,206 @@
 //! the exact APIs are subject to change, and may
 //! not be well-optimized.
 
-use core::{iter::Peekable, ops::Range};
+use core::{fmt::Display, iter::Peekable, ops::Range};
 
-use logos::{Lexer, Logos};
+use alloc::{string::String, vec::Vec};
+
+use logos::{Logos, SpannedIter};
 use snafu::Snafu;
 use token::Token;
 
-use crate::types::{Coda, DataField, DataType, Text, Type};
+use crate::types::{
+    deprecation_from_docs,
+    number::{VarI64, VarU64},
+    Coda, DataField, DataType, EnumType, Text, Type, TypeError, Unspecified,
+};
 
 mod token;
 
-/// Maximum user-defined ordinal value (built-in types occupy 242–255).
-const MAX_USER_ORDINAL: u8 = 241;
+/// Maximum user-defined ordinal value.
+///
+/// Ordinals 224–255 are reserved for built-in types
+/// (see [`Type::ordinal`]), which count down from 255.
+const MAX_USER_ORDINAL: u8 = 223;
 
 /// Parses `markdown` into a [`Coda`].
+///
+/// If `markdown` contains more than one coda,
+/// only the first coda is returned.
 pub fn parse(markdown: &str) -> Result<Coda, ParseError> {
-    // Parse the raw coda from the markdown.
+    parse_many(markdown)?
+        .into_iter()
+        .next()
+        .ok_or(ParseError::ExpectedCoda { location: None })
+}
+
+/// Parses `markdown` into a [`Coda`] like [`parse`],
+/// rejecting fields which refer to data types that
+/// aren't declared anywhere in the coda with
+/// [`ParseError::UnresolvedType`].
+///
+/// [`parse`] instead resolves these fields as
+/// data with an unspecified format.
+pub fn parse_strict(markdown: &str) -> Result<Coda, ParseError> {
+    let (markdown, parsed_codas) = parse_raw(markdown)?;
+
+    match parsed_codas.into_iter().next() {
+        Some(parsed_coda) => build_coda(markdown, parsed_coda, &[], true),
+        None => Err(ParseError::ExpectedCoda { location: None }),
+    }
+}
+
+/// Parses `markdown` into all of the [`Coda`]s it contains.
+///
+/// Each coda starts at a `# Name Coda` header, and ends
+/// at the next coda header or the end of `markdown`.
+/// Data types in each coda may only refer to other
+/// data types defined in the same coda; imports
+/// are only resolved by [`parse_with_loader`].
+pub fn parse_many(markdown: &str) -> Result<Vec<Coda>, ParseError> {
+    let (markdown, parsed_codas) = parse_raw(markdown)?;
+
+    parsed_codas
+        .into_iter()
+        .map(|parsed_coda| build_coda(markdown, parsed_coda, &[], false))
+        .collect()
+}
+
+/// Parses `markdown` and returns it in the
+/// canonical format of [`Coda::to_markdown`].
+///
+/// Every coda in `markdown` is formatted, and
+/// their imports are preserved (but not loaded).
+pub fn format(markdown: &str) -> Result<String, ParseError> {
+    let (markdown, parsed_codas) = parse_raw(markdown)?;
+
+    let mut formatted = String::new();
+    for parsed_coda in parsed_codas {
+        let imports: Vec<_> = parsed_coda
+            .imports
+            .iter()
+            .map(|import| (import.name.clone(), import.path.clone()))
+            .collect();
+        let coda = build_coda(markdown, parsed_coda, &[], false)?;
+
+        if !formatted.is_empty() {
+            formatted.push('\n');
+        }
+        coda.write_markdown(&imports, &mut formatted);
+    }
+
+    Ok(formatted)
+}
+
+/// Parses `markdown`, loaded from `path`, into a
+/// [`Coda`], loading any codas it imports with `loader`.
+///
+/// Codas are imported with a declaration at the top
+/// of a coda, like ``> import `Other` from "other.md"``.
+/// `loader` is invoked with the path of each import
+/// (like `other.md`), and must return the markdown
+/// containing the imported coda. The data types of
+/// imported codas may then be referenced by the
+/// importing coda's data types.
+///
+/// If an import can't be loaded, `loader` should return
+/// [`ParseError::MissingImport`] without a location; the
+/// error is returned with the import's name and location.
+///
+/// Imports are loaded recursively; if a coda
+/// (directly or indirectly) imports itself (or
+/// `path`), [`ParseError::CircularImport`] is returned.
+pub fn parse_with_loader(
+    markdown: &str,
+    path: &str,
+    loader: impl Fn(&str) -> Result<String, ParseError>,
+) -> Result<Coda, ParseError> {
+    parse_many_with_loader(markdown, &loader, &mut alloc::vec![path.into()])?
+        .into_iter()
+        .next()
+        .ok_or(ParseError::ExpectedCoda { location: None })
+}
+
+/// Parses `markdown` into all of the [`Coda`]s it contains,
+/// loading any codas they import with `loader`.
+///
+/// `importing` contains the paths of all codas
+/// currently being loaded, and is used to
+/// detect circular imports.
+fn parse_many_with_loader(
+    markdown: &str,
+    loader: &dyn Fn(&str) -> Result<String, ParseError>,
+    importing: &mut Vec<Text>,
+) -> Result<Vec<Coda>, ParseError> {
+    let (markdown, parsed_codas) = parse_raw(markdown)?;
+
+    let mut codas = alloc::vec![];
+    for parsed_coda in parsed_codas {
+        let mut imported = alloc::vec![];
+        for import in parsed_coda.imports.iter() {
+            if importing.contains(&import.path) {
+                return Err(ParseError::CircularImport {
+                    path: import.path.clone(),
+                    location: Some(import.location.clone()),
+                });
+            }
+
+            let loaded = loader(&import.path).map_err(|error| match error {
+                ParseError::MissingImport { location: None, .. } => ParseError::MissingImport {
+                    name: import.name.clone(),
+                    path: import.path.clone(),
+                    location: Some(import.location.clone()),
+                },
+                error => error,
+            })?;
+            importing.push(import.path.clone());
+            let imported_codas = parse_many_with_loader(&loaded, loader, importing)?;
+            importing.pop();
+
+            match imported_codas
+                .into_iter()
+                .find(|coda| coda.global_name == import.name)
+            {
+                Some(coda) => imported.push(coda),
+                None => {
+                    return Err(ParseError::MissingImport {
+                        name: import.name.clone(),
+                        path: import.path.clone(),
+                        location: Some(import.location.clone()),
+                    })
+                }
+            }
+        }
+
+        codas.push(build_coda(markdown, parsed_coda, &imported, false)?);
+    }
+
+    Ok(codas)
+}
+
+/// Parses the raw codas from `markdown`, returning
+/// them alongside the trimmed `markdown` that
+/// their spans refer to.
+fn parse_raw(markdown: &str) -> Result<(&str, Vec<ParsedCoda>), ParseError> {
+    let source = markdown;
     let markdown = markdown.trim();
-    let mut parser = Parser::new(markdown);
-    let parsed_coda = parser.parse()?;
+    let mut parser = Parser::new_within(source, markdown);
+    Ok((markdown, parser.parse()?))
+}
 
+/// Builds a [`Coda`] from a `parsed_coda` contained
+/// in `markdown`, resolving references to data
+/// types in the coda and its `imported` codas.
+///
+/// If `strict`, references to data types which
+/// aren't declared by the coda or its imports are
+/// rejected, instead of resolved as unspecified data.
+fn build_coda(
+    markdown: &str,
+    parsed_coda: ParsedCoda,
+    imported: &[Coda],
+    strict: bool,
+) -> Result<Coda, ParseError> {
     // Prepare an in-memory coda.
     let docs = if parsed_coda.docs.is_empty() {
         None
@@ -35,23 +215,91 @@ pub fn parse(markdown: &str) -> Result<Coda, ParseError> {
     };
     let mut coda = Coda::new(parsed_coda.global_name, parsed_coda.local_name, docs, &[]);
 
-    // Create data types.
-    for (ordinal, parsed_data) in parsed_coda.data.into_iter().enumerate() {
-        // User-defined ordinals start at 1 (0 reserved for Unspecified,
-        // 242-255 reserved for built-in system types).
-        let ordinal = ordinal + 1;
-        if ordinal > MAX_USER_ORDINAL as usize {
-            return Err(ParseError::TooManyDataTypes {
-                max: MAX_USER_ORDINAL,
-            });
-        }
-        let ordinal = ordinal as u8;
+    // Reject duplicate names before building any data types.
+    ensure_unique_names(&parsed_coda.data, &parsed_coda.enums)?;
+
+    // Build enums first, since they don't refer to other types.
+    for parsed_enum in parsed_coda.enums {
+        coda.enums.push(build_enum(markdown, parsed_enum)?);
+    }
+
+    // Data types are numbered in the order they were
+    // declared in, unless they all declare an ordinal.
+    let mut ordinals = Ordinals {
+        explicit: parsed_coda.data.iter().any(|data| data.ordinal.is_some()),
+        used: alloc::vec![],
+    };
+
+    // User-defined ordinals start at 1 (0 reserved for Unspecified,
+    // 224-255 reserved for built-in system types).
+    let mut pending = alloc::vec![];
+    for (i, parsed_data) in parsed_coda.data.into_iter().enumerate() {
+        let ordinal = ordinals.next(
+            i + 1,
+            parsed_data.ordinal,
+            &parsed_data.name,
+            &parsed_data.location,
+        )?;
+        pending.push(Some((parsed_data, ordinal)));
+    }
+
+    // Build data types after the data types they refer to,
+    // so that fields may refer to data types declared later.
+    let mut builder = DataTypeBuilder {
+        markdown,
+        imported,
+        strict,
+        coda,
+        pending,
+        path: alloc::vec![],
+    };
+    for i in 0..builder.pending.len() {
+        builder.build(i)?;
+    }
+    let mut coda = builder.coda;
+
+    // Codas list their data in ascending order by ordinal.
+    coda.data.sort_by_key(|data| data.ordinal());
+
+    Ok(coda)
+}
+
+/// Data types of a coda while it's built.
+struct DataTypeBuilder<'a> {
+    /// The markdown containing the coda.
+    markdown: &'a str,
+
+    /// Codas imported by the coda.
+    imported: &'a [Coda],
+
+    /// True if references to unknown data types are rejected.
+    strict: bool,
+
+    /// The coda, containing every data type built so far.
+    coda: Coda,
+
+    /// Data types which haven't been built yet, with their ordinals.
+    pending: Vec<Option<(ParsedDataType, u8)>>,
+
+    /// Names of the data types currently being built,
+    /// each referring to the data type after it.
+    path: Vec<Text>,
+}
+
+impl DataTypeBuilder<'_> {
+    /// Builds the pending data type at `index` (if it
+    /// hasn't already been built), adding it to the coda.
+    fn build(&mut self, index: usize) -> Result<(), ParseError> {
+        let Some((parsed_data, ordinal)) = self.pending[index].take() else {
+            return Ok(());
+        };
+        self.path.push(parsed_data.name.clone());
 
         // Extract docs.
         let docs = if parsed_data.docs.is_empty() {
             None
         } else {
-            Some(markdown[parsed_data.docs].trim().into())
+            Some(self.markdown[parsed_data.docs].trim().into())
         };
 
         // Extract fields.
@@ -61,116 +309,581 @@ pub fn parse(markdown: &str) -> Result<Coda, ParseError> {
             let docs = if parsed_field.docs.is_empty() {
                 None
             } else {
-                Some(markdown[parsed_field.docs].trim().into())
-            };
-
-            // Shorthand type resolver.
-            let resolve_typing = |typing: Text| match coda.type_from_name(&typing) {
-                Some(typing) => typing,
-                None => Type::Data(DataType::new_fluid(typing, None)),
+                Some(self.markdown[parsed_field.docs.clone()].trim().into())
             };
 
             // Extract typing.
             let typing = match parsed_field.typing {
-                ParsedFieldType::Scalar(typing) => resolve_typing(typing),
-                ParsedFieldType::List(dimensions, typing) => {
-                    let mut typing = resolve_typing(typing);
+                ParsedFieldType::Scalar(ref typing) => {
+                    self.resolve(typing.clone(), &data, &parsed_field)?
+                }
+                ParsedFieldType::List(dimensions, ref typing) => {
+                    let mut typing = self.resolve(typing.clone(), &data, &parsed_field)?;
                     for _ in 0..dimensions {
                         typing = Type::List(typing.into());
                     }
                     typing
                 }
-                ParsedFieldType::Map(key_typing, value_typing) => {
-                    Type::Map((resolve_typing(key_typing), resolve_typing(value_typing)).into())
-                }
+                ParsedFieldType::Map(ref key_typing, ref value_typing) => Type::Map(
+                    (
+                        self.resolve(key_typing.clone(), &data, &parsed_field)?,
+                        self.resolve(value_typing.clone(), &data, &parsed_field)?,
+                    )
+                        .into(),
+                ),
+                ParsedFieldType::Enum(variants) => Type::Enum(EnumType::new(
+                    EnumType::field_name(&data.name, &parsed_field.name),
+                    &variants,
+                )),
+            };
+
+            // Extract default.
+            let default = match parsed_field.default {
+                Some(literal) => Some(parse_default(&literal, &typing).ok_or_else(|| {
+                    ParseError::InvalidDefault {
+                        type_name: data.name.clone(),
+                        field_name: parsed_field.name.clone(),
+                        literal,
+                        expected: typing.description(),
+                        location: parsed_field.location.clone(),
+                    }
+                })?),
+                None => None,
             };
 
-            data = data.with(DataField {
-                name: parsed_field.name,
-                docs,
-                typing,
-                optional: parsed_field.optional,
-                flattened: parsed_field.flattened,
+            // Extract deprecation.
+            let (deprecated, deprecation_note) = deprecation_from_docs(docs.as_deref());
+
+            let type_name = data.name.clone();
+            let field_name = parsed_field.name.clone();
+            data = data
+                .try_with(DataField {
+                    name: parsed_field.name,
+                    docs,
+                    typing,
+                    optional: parsed_field.optional,
+                    flattened: parsed_field.flattened,
+                    default,
+                    deprecated: parsed_field.deprecated || deprecated,
+                    deprecation_note,
+                })
+                .map_err(|error| match error {
+                    TypeError::DuplicateField { .. } => ParseError::DuplicateFieldName {
+                        type_name,
+                        field_name,
+                        location: parsed_field.location,
+                    },
+                    TypeError::FieldOverflow { .. } => ParseError::FormatOverflow {
+                        type_name,
+                        field_name,
+                        location: parsed_field.location,
+                    },
+                    TypeError::FluidType { .. } => ParseError::UnexpectedError {
+                        location: parsed_field.location,
+                    },
+                })?;
+        }
+
+        self.path.pop();
+        self.coda.data.push(data);
+        Ok(())
+    }
+
+    /// Resolves the type named `name` of `field` in `data`,
+    /// building it first if it's a pending data type.
+    fn resolve(
+        &mut self,
+        name: Text,
+        data: &DataType,
+        field: &ParsedField,
+    ) -> Result<Type, ParseError> {
+        if let Some(index) = self.pending.iter().position(|pending| {
+            matches!(pending, Some((parsed, _)) if parsed.name.eq_ignore_ascii_case(&name))
+        }) {
+            self.build(index)?;
+        } else if let Some(start) = self
+            .path
+            .iter()
+            .position(|other| other.eq_ignore_ascii_case(&name))
+        {
+            // Data which (indirectly) contains itself
+            // could never finish being described.
+            let mut path = self.path[start..].to_vec();
+            path.push(name);
+            return Err(ParseError::RecursiveType {
+                path,
+                location: field.location.clone(),
+            });
+        }
+
+        if let Some(typing) = self.coda.type_from_name(&name) {
+            return Ok(typing);
+        }
+        if let Some(typing) = self
+            .imported
+            .iter()
+            .find_map(|coda| coda.type_from_name(&name))
+        {
+            return Ok(typing);
+        }
+
+        if self.strict {
+            return Err(ParseError::UnresolvedType {
+                type_name: data.name.clone(),
+                field_name: field.name.clone(),
+                name,
+                location: field.location.clone(),
             });
         }
 
-        coda.data.push(data);
+        Ok(Type::Data(DataType::new_fluid(name, None)))
     }
+}
 
-    Ok(coda)
+/// Ordinals assigned to the data
+/// types of a coda while it's built.
+struct Ordinals {
+    /// True if the coda's data types
+    /// declare explicit ordinals.
+    explicit: bool,
+
+    /// The ordinals assigned so far.
+    used: Vec<u8>,
+}
+
+impl Ordinals {
+    /// Returns the ordinal of the data type named `type_name`
+    /// at `position` in its coda, which declared the ordinal
+    /// `declared` (if any) at `location`.
+    fn next(
+        &mut self,
+        position: usize,
+        declared: Option<u64>,
+        type_name: &Text,
+        location: &Option<SourceLocation>,
+    ) -> Result<u8, ParseError> {
+        let ordinal = match declared {
+            None if self.explicit => {
+                return Err(ParseError::MixedOrdinals {
+                    type_name: type_name.clone(),
+                    location: location.clone(),
+                })
+            }
+            None => checked_ordinal(position, location)?,
+            Some(ordinal) if ordinal == 0 || ordinal > MAX_USER_ORDINAL as u64 => {
+                return Err(ParseError::InvalidOrdinal {
+                    type_name: type_name.clone(),
+                    ordinal,
+                    max: MAX_USER_ORDINAL,
+                    location: location.clone(),
+                })
+            }
+            Some(ordinal) => ordinal as u8,
+        };
+
+        if self.used.contains(&ordinal) {
+            return Err(ParseError::DuplicateOrdinal {
+                type_name: type_name.clone(),
+                ordinal,
+                location: location.clone(),
+            });
+        }
+        self.used.push(ordinal);
+
+        Ok(ordinal)
+    }
+}
+
+/// Returns an error if any two data types or enums in
+/// `parsed_data` and `parsed_enums` (including the enums
+/// declared by fields, named by [`EnumType::field_name`])
+/// share a name,
+/// if any two fields in the same data type share a name,
+/// or if any two variants of a field's enum share a name.
+///
+/// Type names are compared case-insensitively,
+/// matching how field types are resolved.
+fn ensure_unique_names(
+    parsed_data: &[ParsedDataType],
+    parsed_enums: &[ParsedEnum],
+) -> Result<(), ParseError> {
+    let field_enum_names: Vec<(Text, &Option<SourceLocation>)> = parsed_data
+        .iter()
+        .flat_map(|data| {
+            data.fields
+                .iter()
+                .filter(|field| matches!(field.typing, ParsedFieldType::Enum(_)))
+                .map(|field| {
+                    (
+                        EnumType::field_name(&data.name, &field.name),
+                        &field.location,
+                    )
+                })
+        })
+        .collect();
+    let type_names = parsed_data
+        .iter()
+        .map(|data| (&data.name, &data.location))
+        .chain(
+            parsed_enums
+                .iter()
+                .map(|parsed_enum| (&parsed_enum.name, &parsed_enum.location)),
+        )
+        .chain(
+            field_enum_names
+                .iter()
+                .map(|(name, location)| (name, *location)),
+        );
+    for (i, (name, location)) in type_names.clone().enumerate() {
+        let mut preceding = type_names.clone().take(i);
+        if preceding.any(|(other, _)| other.eq_ignore_ascii_case(name)) {
+            return Err(ParseError::DuplicateTypeName {
+                type_name: name.clone(),
+                location: location.clone(),
+            });
+        }
+    }
+
+    for data in parsed_data {
+        for (i, field) in data.fields.iter().enumerate() {
+            if data.fields[..i]
+                .iter()
+                .any(|other| other.name == field.name)
+            {
+                return Err(ParseError::DuplicateFieldName {
+                    type_name: data.name.clone(),
+                    field_name: field.name.clone(),
+                    location: field.location.clone(),
+                });
+            }
+
+            if let ParsedFieldType::Enum(variants) = &field.typing {
+                for (j, variant) in variants.iter().enumerate() {
+                    if variants[..j].contains(variant) {
+                        return Err(ParseError::DuplicateVariantName {
+                            type_name: data.name.clone(),
+                            field_name: field.name.clone(),
+                            variant_name: variant.clone(),
+                            location: field.location.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the default value of a field of `typing`
+/// written as `literal`, or `None` if `literal` isn't
+/// a valid value of `typing`.
+///
+/// Numbers and timestamps (in milliseconds since the
+/// Unix epoch) are written as Rust-style literals without
+/// suffixes, booleans as `true` or `false`, text in double
+/// quotes, characters in single quotes, and enums as the
+/// name of one of their variants. Other types don't
+/// support default values.
+fn parse_default(literal: &str, typing: &Type) -> Option<Unspecified> {
+    Some(match typing {
+        Type::U8 => Unspecified::U8(literal.parse().ok()?),
+        Type::U16 => Unspecified::U16(literal.parse().ok()?),
+        Type::U32 => Unspecified::U32(literal.parse().ok()?),
+        Type::U64 => Unspecified::U64(literal.parse().ok()?),
+        Type::U128 => Unspecified::U128(literal.parse().ok()?),
+        Type::I8 => Unspecified::I8(literal.parse().ok()?),
+        Type::I16 => Unspecified::I16(literal.parse().ok()?),
+        Type::I32 => Unspecified::I32(literal.parse().ok()?),
+        Type::I64 => Unspecified::I64(literal.parse().ok()?),
+        Type::I128 => Unspecified::I128(literal.parse().ok()?),
+        Type::UVar => Unspecified::UVar(VarU64(literal.parse().ok()?)),
+        Type::IVar => Unspecified::IVar(VarI64(literal.parse().ok()?)),
+        Type::F32 => Unspecified::F32(parse_float(literal)?),
+        Type::F64 => Unspecified::F64(parse_float(literal)?),
+        Type::Timestamp => Unspecified::Timestamp(literal.parse().ok()?),
+        Type::Bool => match literal {
+            "true" => Unspecified::Bool(true),
+            "false" => Unspecified::Bool(false),
+            _ => return None,
+        },
+        Type::Text => Unspecified::Text(parse_quoted(literal, '"')?.into()),
+        Type::Char => {
+            let text = parse_quoted(literal, '\'')?;
+            let mut chars = text.chars();
+            let char = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            Unspecified::Char(char)
+        }
+        Type::Enum(typing) => Unspecified::Enum {
+            typing: typing.clone(),
+            variant: typing.ordinal_of(literal.trim_matches('`'))?,
+        },
+        _ => return None,
+    })
+}
+
+/// Returns the finite float written as `literal`.
+fn parse_float<T: core::str::FromStr>(literal: &str) -> Option<T> {
+    // Only accept literals which start and end with a digit,
+    // rejecting non-finite values like `inf` and `NaN`.
+    let digits = literal.trim_start_matches(['-', '+']);
+    if !digits.starts_with(|c: char| c.is_ascii_digit())
+        || !digits.ends_with(|c: char| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    literal.parse().ok()
+}
+
+/// Returns the contents of `literal` between `quote`s,
+/// with any escaped characters (`\\`, `\n`, `\t`,
+/// or an escaped `quote`) unescaped.
+fn parse_quoted(literal: &str, quote: char) -> Option<String> {
+    let contents = literal.strip_prefix(quote)?.strip_suffix(quote)?;
+
+    let mut unquoted = String::with_capacity(contents.len());
+    let mut chars = contents.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next()? {
+                'n' => unquoted.push('\n'),
+                't' => unquoted.push('\t'),
+                char if char == '\\' || char == quote => unquoted.push(char),
+                _ => return None,
+            },
+            char if char == quote => return None,
+            char => unquoted.push(char),
+        }
+    }
+
+    Some(unquoted)
+}
+
+/// Returns `ordinal` as a user-defined data type ordinal,
+/// or an error if there are too many data types (reported
+/// at the `location` of the data type with `ordinal`).
+fn checked_ordinal(ordinal: usize, location: &Option<SourceLocation>) -> Result<u8, ParseError> {
+    if ordinal > MAX_USER_ORDINAL as usize {
+        return Err(ParseError::TooManyDataTypes {
+            max: MAX_USER_ORDINAL,
+            location: location.clone(),
+        });
+    }
+
+    Ok(ordinal as u8)
+}
+
+/// Builds an [`EnumType`] from a `parsed_enum`
+/// contained in `markdown`.
+///
+/// Enums aren't data, so they can't declare an
+/// ordinal. Since values of enums are encoded as
+/// the ordinals of their variants, each variant's
+/// value must be its position in the enum.
+fn build_enum(markdown: &str, parsed_enum: ParsedEnum) -> Result<EnumType, ParseError> {
+    if parsed_enum.ordinal.is_some() {
+        return Err(ParseError::UnexpectedEnumOrdinal {
+            type_name: parsed_enum.name,
+            location: parsed_enum.location,
+        });
+    }
+
+    let mut typing = EnumType::new(parsed_enum.name, &[]);
+    if !parsed_enum.docs.is_empty() {
+        typing.docs = Some(markdown[parsed_enum.docs].trim().into());
+    }
+
+    for (ordinal, variant) in parsed_enum.variants.into_iter().enumerate() {
+        if variant.value != ordinal as u64 || ordinal > u16::MAX as usize {
+            return Err(ParseError::InvalidVariantValue {
+                type_name: typing.name,
+                variant_name: variant.name,
+                value: variant.value,
+                expected: ordinal as u64,
+                location: variant.location,
+            });
+        }
+
+        typing.variants.push(variant.name);
+        if !variant.docs.is_empty() {
+            typing.variant_docs.resize(ordinal, None);
+            typing
+                .variant_docs
+                .push(Some(markdown[variant.docs].trim().into()));
+        }
+    }
+
+    Ok(typing)
 }
 
 /// A Markdown parser for codas.
 struct Parser<'lexer> {
+    /// The complete source text containing
+    /// the text being parsed.
+    source: &'lexer str,
+
+    /// The offset of the text being
+    /// parsed within the `source`.
+    offset: usize,
+
     /// The token lexer being parsed.
-    lexer: Peekable<Lexer<'lexer, Token<'lexer>>>,
+    lexer: Peekable<SpannedIter<'lexer, Token<'lexer>>>,
 }
 
 impl<'lexer> Parser<'lexer> {
     /// Creates a new parser for `text`.
+    #[cfg(test)]
     fn new(text: &'lexer str) -> Self {
+        Self::new_within(text, text)
+    }
+
+    /// Creates a new parser for `text`, which
+    /// must be a subslice of `source`.
+    fn new_within(source: &'lexer str, text: &'lexer str) -> Self {
+        let offset = text.as_ptr() as usize - source.as_ptr() as usize;
+        debug_assert!(offset + text.len() <= source.len());
+
         Self {
-            lexer: Token::lexer(text).peekable(),
+            source,
+            offset,
+            lexer: Token::lexer(text).spanned().peekable(),
         }
     }
 
-    /// Parses the next [`Coda`] from the text.
-    fn parse(&mut self) -> Result<ParsedCoda, ParseError> {
-        Ok(self.take_coda()?.unwrap())
+    /// Returns the location of the text being parsed
+    /// at `span`, skipping any leading whitespace.
+    fn location(&self, span: Range<usize>) -> SourceLocation {
+        let span = (span.start + self.offset)..(span.end + self.offset);
+        let slice = &self.source[span.clone()];
+        let start = span.start + (slice.len() - slice.trim_start().len());
+        let start = if start < span.end { start } else { span.start };
+
+        let preceding = &self.source[..start];
+        let line = preceding.matches('\n').count() + 1;
+        let line_start = preceding.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = preceding[line_start..].chars().count() + 1;
+
+        SourceLocation { line, column, span }
+    }
+
+    /// Returns the location of the next token,
+    /// or `None` if there are no more tokens.
+    fn peek_location(&mut self) -> Option<SourceLocation> {
+        let span = self.lexer.peek().map(|(_, span)| span.clone())?;
+        Some(self.location(span))
+    }
+
+    /// Parses all [`Coda`]s from the text.
+    fn parse(&mut self) -> Result<Vec<ParsedCoda>, ParseError> {
+        let mut codas = alloc::vec![];
+
+        while let Some(coda) = self.take_coda()? {
+            codas.push(coda);
+
+            // Stop at the first token that
+            // doesn't start another coda.
+            if !matches!(self.lexer.peek(), Some((Ok(Token::Coda(..)), _))) {
+                break;
+            }
+        }
+
+        Ok(codas)
     }
 
     /// Takes the next [`Token::Coda`].
     fn take_coda(&mut self) -> Result<Option<ParsedCoda>, ParseError> {
+        let location = self.peek_location();
         let name = match self.lexer.next() {
-            Some(Ok(Token::Coda(name))) => name,
-            _ => return Err(ParseError::ExpectedCoda),
+            Some((Ok(Token::Coda(name)), _)) => name,
+            _ => return Err(ParseError::ExpectedCoda { location }),
         };
 
         let mut coda = ParsedCoda {
             global_name: name.0.into(),
             local_name: name.1.into(),
             docs: 0..0,
+            imports: alloc::vec![],
             data: alloc::vec![],
+            enums: alloc::vec![],
         };
 
-        // Parse docs.
+        // Parse imports, which may appear
+        // before or after the coda's docs.
+        self.take_imports(&mut coda.imports);
         let (docs, whitespace) = self.take_docs_lines()?;
-        assert!(docs.is_empty() || whitespace == 0);
+        self.take_imports(&mut coda.imports);
         if !docs.is_empty() && whitespace != 0 {
-            return Err(ParseError::UnexpectedDocsIndentation { actual: whitespace });
+            return Err(ParseError::UnexpectedDocsIndentation {
+                actual: whitespace,
+                location: Some(self.location(docs)),
+            });
         }
         coda.docs = docs;
 
-        // Parse data types.
-        while let Some(data_type) = self.take_data()? {
-            coda.data.push(data_type);
+        // Parse data types and enums.
+        loop {
+            if let Some(data_type) = self.take_data()? {
+                coda.data.push(data_type);
+            } else if let Some(parsed_enum) = self.take_enum()? {
+                coda.enums.push(parsed_enum);
+            } else {
+                break;
+            }
         }
 
         Ok(Some(coda))
     }
 
+    /// Takes all of the next contiguous [`Token::Import`]s.
+    fn take_imports(&mut self, imports: &mut Vec<ParsedImport>) {
+        while let Some((Ok(Token::Import((name, path))), span)) = self.lexer.peek() {
+            let (name, path, span) = ((*name).into(), (*path).into(), span.clone());
+            self.lexer.next();
+
+            imports.push(ParsedImport {
+                name,
+                path,
+                location: self.location(span),
+            });
+        }
+    }
+
     /// Takes the next [`Token::Data`].
     fn take_data(&mut self) -> Result<Option<ParsedDataType>, ParseError> {
-        let name = match self.lexer.peek() {
-            Some(Ok(Token::Data(name))) => {
-                let name = (*name).into();
+        let location = self.peek_location();
+        let (name, ordinal) = match self.lexer.peek() {
+            Some((Ok(Token::Data((name, ordinal))), _)) => {
+                let header = ((*name).into(), *ordinal);
                 self.lexer.next();
-                name
+                header
+            }
+            None | Some((Ok(..), _)) => return Ok(None),
+            _ => {
+                return Err(ParseError::ExpectedDataType {
+                    location: self.peek_location(),
+                })
             }
-            None | Some(Ok(..)) => return Ok(None),
-            _ => return Err(ParseError::ExpectedDataType),
         };
 
         let mut data_type = ParsedDataType {
             name,
+            ordinal,
             docs: 0..0,
             fields: alloc::vec![],
+            location,
         };
 
         // Parse the data's docs.
         let (docs, whitespace) = self.take_docs_lines()?;
         if !docs.is_empty() && whitespace != 0 {
-            return Err(ParseError::UnexpectedDocsIndentation { actual: whitespace });
+            return Err(ParseError::UnexpectedDocsIndentation {
+                actual: whitespace,
+                location: Some(self.location(docs)),
+            });
         }
         data_type.docs = docs;
 
@@ -182,16 +895,90 @@ impl<'lexer> Parser<'lexer> {
         Ok(Some(data_type))
     }
 
+    /// Takes the next [`Token::Enum`].
+    fn take_enum(&mut self) -> Result<Option<ParsedEnum>, ParseError> {
+        let location = self.peek_location();
+        let (name, ordinal) = match self.lexer.peek() {
+            Some((Ok(Token::Enum((name, ordinal))), _)) => {
+                let header = ((*name).into(), *ordinal);
+                self.lexer.next();
+                header
+            }
+            None | Some((Ok(..), _)) => return Ok(None),
+            _ => {
+                return Err(ParseError::ExpectedEnum {
+                    location: self.peek_location(),
+                })
+            }
+        };
+
+        let mut parsed_enum = ParsedEnum {
+            name,
+            ordinal,
+            docs: 0..0,
+            variants: alloc::vec![],
+            location,
+        };
+
+        // Parse the enum's docs.
+        let (docs, whitespace) = self.take_docs_lines()?;
+        if !docs.is_empty() && whitespace != 0 {
+            return Err(ParseError::UnexpectedDocsIndentation {
+                actual: whitespace,
+                location: Some(self.location(docs)),
+            });
+        }
+        parsed_enum.docs = docs;
+
+        // Parse the enum's variants.
+        let mut location = self.peek_location();
+        while let Some((Ok(Token::EnumVariant((name, value))), _)) = self.lexer.peek() {
+            let mut variant = ParsedEnumVariant {
+                name: (*name).into(),
+                docs: 0..0,
+                value: *value,
+                location,
+            };
+            self.lexer.next();
+
+            // Parse the variant's docs.
+            let (docs, whitespace) = self.take_docs_lines()?;
+            if !docs.is_empty() && whitespace == 0 {
+                return Err(ParseError::ExpectedDocsIndentation {
+                    minimum_expected: 1,
+                    location: Some(self.location(docs)),
+                });
+            }
+            variant.docs = docs;
+
+            parsed_enum.variants.push(variant);
+            location = self.peek_location();
+        }
+
+        Ok(Some(parsed_enum))
+    }
+
     /// Takes the next [`Token::DataField`].
     fn take_data_field(&mut self) -> Result<Option<ParsedField>, ParseError> {
+        let location = self.peek_location();
         let mut field = match self.lexer.peek() {
-            Some(Ok(Token::DataField(field))) => {
+            Some((Ok(Token::DataField(Ok(field))), _)) => {
                 let field = field.clone();
                 self.lexer.next();
                 field
             }
-            None | Some(Ok(..)) => return Ok(None),
-            _ => return Err(ParseError::ExpectedDataField),
+            Some((Ok(Token::DataField(Err(details))), _)) => {
+                return Err(ParseError::MalformedField {
+                    details: details.clone(),
+                    location,
+                })
+            }
+            None | Some((Ok(..), _)) => return Ok(None),
+            _ => {
+                return Err(ParseError::ExpectedDataField {
+                    location: self.peek_location(),
+                })
+            }
         };
 
         // Parse the fields' docs.
@@ -199,9 +986,11 @@ impl<'lexer> Parser<'lexer> {
         if !docs.is_empty() && whitespace == 0 {
             return Err(ParseError::ExpectedDocsIndentation {
                 minimum_expected: 1,
+                location: Some(self.location(docs)),
             });
         }
         field.docs = docs;
+        field.location = location;
 
         Ok(Some(field))
     }
@@ -212,7 +1001,7 @@ impl<'lexer> Parser<'lexer> {
         let mut leading_whitespace = 0;
         let mut range = 0..0;
 
-        while let Some(token) = self.lexer.peek() {
+        while let Some((token, span)) = self.lexer.peek() {
             match token {
                 Ok(Token::DocsLine((line, line_range, line_whitespace))) => {
                     // Init.
@@ -236,7 +1025,12 @@ impl<'lexer> Parser<'lexer> {
 
                 Ok(..) => break,
 
-                _ => return Err(ParseError::UnexpectedError),
+                _ => {
+                    let span = span.clone();
+                    return Err(ParseError::UnexpectedError {
+                        location: Some(self.location(span)),
+                    });
+                }
             }
         }
 
@@ -250,15 +1044,68 @@ struct ParsedCoda {
     global_name: Text,
     local_name: Text,
     docs: Range<usize>,
+    imports: alloc::vec::Vec<ParsedImport>,
     data: alloc::vec::Vec<ParsedDataType>,
+    enums: alloc::vec::Vec<ParsedEnum>,
+}
+
+/// Import of a [`Coda`] parsed from text.
+#[derive(Clone, Debug, PartialEq)]
+struct ParsedImport {
+    /// The global name of the imported coda.
+    name: Text,
+
+    /// The path to load the imported coda from.
+    path: Text,
+
+    /// The location of the import declaration.
+    location: SourceLocation,
 }
 
 /// [`DataType`] parsed from text.
 #[derive(Clone, Debug, PartialEq)]
 struct ParsedDataType {
     name: Text,
+
+    /// The data type's explicit ordinal, if any.
+    ordinal: Option<u64>,
+
     docs: Range<usize>,
     fields: alloc::vec::Vec<ParsedField>,
+
+    /// The location of the data type's header.
+    location: Option<SourceLocation>,
+}
+
+/// Enumeration parsed from text.
+#[derive(Clone, Debug, PartialEq)]
+struct ParsedEnum {
+    name: Text,
+
+    /// The enum's (invalid) explicit ordinal, if any.
+    ordinal: Option<u64>,
+
+    docs: Range<usize>,
+    variants: alloc::vec::Vec<ParsedEnumVariant>,
+
+    /// The location of the enum's header.
+    location: Option<SourceLocation>,
+}
+
+/// Variant of a [`ParsedEnum`] parsed from text.
+#[derive(Clone, Debug, PartialEq)]
+struct ParsedEnumVariant {
+    name: Text,
+
+    /// The span of the lexer's contents
+    /// containing the variant's docs.
+    docs: Range<usize>,
+
+    /// The variant's value.
+    value: u64,
+
+    /// The location of the variant's declaration.
+    location: Option<SourceLocation>,
 }
 
 /// [`DataField`] parsed from text.
@@ -278,6 +1125,15 @@ struct ParsedField {
 
     /// True if the field is flattened.
     flattened: bool,
+
+    /// True if the field is deprecated by keyword.
+    deprecated: bool,
+
+    /// The literal of the field's default value, if any.
+    default: Option<Text>,
+
+    /// The location of the field's declaration.
+    location: Option<SourceLocation>,
 }
 
 /// Unresolved typing of a [`ParsedField`].
@@ -291,65 +1147,297 @@ enum ParsedFieldType {
 
     /// A mapping of one type to another.
     Map(Text, Text),
+
+    /// One of a list of named variants.
+    Enum(Vec<Text>),
 }
 
 /// Enumeration of errors that may occur when parsing codas.
 #[derive(Debug, Snafu)]
 pub enum ParseError {
-    #[snafu(display("Expected to parse a Coda header."))]
-    ExpectedCoda,
+    #[snafu(display("Expected to parse a Coda header{}.", At(location)))]
+    ExpectedCoda { location: Option<SourceLocation> },
+
+    #[snafu(display("Expected to parse a Data type header{}.", At(location)))]
+    ExpectedDataType { location: Option<SourceLocation> },
 
-    #[snafu(display("Expected to parse a Data type header."))]
-    ExpectedDataType,
+    #[snafu(display("Expected to parse a Data Field{}.", At(location)))]
+    ExpectedDataField { location: Option<SourceLocation> },
 
-    #[snafu(display("Expected to parse a Data Field."))]
-    ExpectedDataField,
+    #[snafu(display("Malformed Data Field: {details}{}.", At(location)))]
+    MalformedField {
+        details: Text,
+        location: Option<SourceLocation>,
+    },
+
+    #[snafu(display("Expected to parse an Enum header{}.", At(location)))]
+    ExpectedEnum { location: Option<SourceLocation> },
 
     #[snafu(display(
-        "Expected to parse docs with no spaces of indentation, instead of {actual}."
+        "Expected to parse docs with no spaces of indentation, instead of {actual}{}.",
+        At(location)
     ))]
-    UnexpectedDocsIndentation { actual: usize },
+    UnexpectedDocsIndentation {
+        actual: usize,
+        location: Option<SourceLocation>,
+    },
 
     #[snafu(display(
-        "Expected to parse docs with at least {minimum_expected} space(s) of indentation, not 0."
+        "Expected to parse docs with at least {minimum_expected} space(s) of indentation, not 0{}.",
+        At(location)
     ))]
-    ExpectedDocsIndentation { minimum_expected: usize },
-
-    #[snafu(display("Too many data types: maximum is {max}."))]
-    TooManyDataTypes { max: u8 },
+    ExpectedDocsIndentation {
+        minimum_expected: usize,
+        location: Option<SourceLocation>,
+    },
 
-    #[snafu(display("An unexpected error occurred while parsing the source text."))]
-    UnexpectedError,
-}
+    #[snafu(display("Too many data types: maximum is {max}{}.", At(location)))]
+    TooManyDataTypes {
+        max: u8,
+        location: Option<SourceLocation>,
+    },
 
-#[cfg(test)]
-pub(crate) mod tests {
-    use pretty_assertions::assert_eq;
+    #[snafu(display(
+        "The ordinal {ordinal} of `{type_name}` must be between 1 and {max}{}.",
+        At(location)
+    ))]
+    InvalidOrdinal {
+        type_name: Text,
+        ordinal: u64,
+        max: u8,
+        location: Option<SourceLocation>,
+    },
 
-    use super::*;
+    #[snafu(display(
+        "The ordinal {ordinal} of `{type_name}` is already used by another data type{}.",
+        At(location)
+    ))]
+    DuplicateOrdinal {
+        type_name: Text,
+        ordinal: u8,
+        location: Option<SourceLocation>,
+    },
 
-    pub const TEST_CODA_MARKDOWN: &str = r#"
-# `MyCoda` Coda
+    #[snafu(display(
+        "The data type `{type_name}` has no ordinal, but other data types in its coda do; either every data type in a coda has an explicit ordinal, or none do{}.",
+        At(location)
+    ))]
+    MixedOrdinals {
+        type_name: Text,
+        location: Option<SourceLocation>,
+    },
 
-An example Markdown Coda.
+    #[snafu(display(
+        "The enum `{type_name}` can't have an ordinal, since only data types are numbered{}.",
+        At(location)
+    ))]
+    UnexpectedEnumOrdinal {
+        type_name: Text,
+        location: Option<SourceLocation>,
+    },
 
-## `MyNestedDataType` Data
+    #[snafu(display(
+        "The variant `{variant_name}` of `{type_name}` must have the value {expected} (its position in the enum), not {value}{}.",
+        At(location)
+    ))]
+    InvalidVariantValue {
+        type_name: Text,
+        variant_name: Text,
+        value: u64,
+        expected: u64,
+        location: Option<SourceLocation>,
+    },
 
-A data type for nesting inside [`MyDataType`].
+    #[snafu(display(
+        "The data type `{type_name}` is defined more than once{}.",
+        At(location)
+    ))]
+    DuplicateTypeName {
+        type_name: Text,
+        location: Option<SourceLocation>,
+    },
 
-+ `floaty_field` f32
+    #[snafu(display(
+        "The field `{field_name}` is defined more than once in `{type_name}`{}.",
+        At(location)
+    ))]
+    DuplicateFieldName {
+        type_name: Text,
+        field_name: Text,
+        location: Option<SourceLocation>,
+    },
 
-    A 32-bit floating-point field.
+    #[snafu(display(
+        "The variant `{variant_name}` is defined more than once in `{type_name}.{field_name}`{}.",
+        At(location)
+    ))]
+    DuplicateVariantName {
+        type_name: Text,
+        field_name: Text,
+        variant_name: Text,
+        location: Option<SourceLocation>,
+    },
 
-+ `listy_field` list of text
+    #[snafu(display(
+        "The field `{field_name}` makes `{type_name}` too large: data may contain at most {} bytes of blob fields and {} data fields{}.",
+        u16::MAX,
+        u8::MAX,
+        At(location)
+    ))]
+    FormatOverflow {
+        type_name: Text,
+        field_name: Text,
+        location: Option<SourceLocation>,
+    },
 
-    A list of data with the same type.
+    #[snafu(display(
+        "The default value `{literal}` of `{type_name}.{field_name}` isn't a valid {expected}{}.",
+        At(location)
+    ))]
+    InvalidDefault {
+        type_name: Text,
+        field_name: Text,
+        literal: Text,
+        expected: Text,
+        location: Option<SourceLocation>,
+    },
 
-    > This field has some fancy nested docs, too.
+    #[snafu(display(
+        "The field `{field_name}` of `{type_name}` refers to `{name}`, which isn't a known type{}.",
+        At(location)
+    ))]
+    UnresolvedType {
+        type_name: Text,
+        field_name: Text,
+        name: Text,
+        location: Option<SourceLocation>,
+    },
 
-    Like, _really_ fancy. <3
+    #[snafu(display(
+        "The data type `{}` contains itself through `{}`{}.",
+        path[0],
+        path.join("` -> `"),
+        At(location)
+    ))]
+    RecursiveType {
+        path: Vec<Text>,
+        location: Option<SourceLocation>,
+    },
+
+    #[snafu(display("The import of `{path}` is circular{}.", At(location)))]
+    CircularImport {
+        path: Text,
+        location: Option<SourceLocation>,
+    },
+
+    #[snafu(display("No coda named `{name}` was found in `{path}`{}.", At(location)))]
+    MissingImport {
+        name: Text,
+        path: Text,
+        location: Option<SourceLocation>,
+    },
 
-## `MyDataType` Data
+    #[snafu(display(
+        "An unexpected error occurred while parsing the source text{}.",
+        At(location)
+    ))]
+    UnexpectedError { location: Option<SourceLocation> },
+}
+
+impl ParseError {
+    /// Returns the location in the source text
+    /// where the error occurred, if known.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            ParseError::ExpectedCoda { location }
+            | ParseError::ExpectedDataType { location }
+            | ParseError::ExpectedDataField { location }
+            | ParseError::MalformedField { location, .. }
+            | ParseError::ExpectedEnum { location }
+            | ParseError::UnexpectedDocsIndentation { location, .. }
+            | ParseError::ExpectedDocsIndentation { location, .. }
+            | ParseError::TooManyDataTypes { location, .. }
+            | ParseError::InvalidOrdinal { location, .. }
+            | ParseError::DuplicateOrdinal { location, .. }
+            | ParseError::MixedOrdinals { location, .. }
+            | ParseError::UnexpectedEnumOrdinal { location, .. }
+            | ParseError::InvalidVariantValue { location, .. }
+            | ParseError::DuplicateTypeName { location, .. }
+            | ParseError::DuplicateFieldName { location, .. }
+            | ParseError::DuplicateVariantName { location, .. }
+            | ParseError::FormatOverflow { location, .. }
+            | ParseError::InvalidDefault { location, .. }
+            | ParseError::UnresolvedType { location, .. }
+            | ParseError::RecursiveType { location, .. }
+            | ParseError::CircularImport { location, .. }
+            | ParseError::MissingImport { location, .. }
+            | ParseError::UnexpectedError { location } => location.as_ref(),
+        }
+    }
+}
+
+/// Location of some text within a coda's source text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// The line containing the text, starting from `1`.
+    pub line: usize,
+
+    /// The column of the first character of
+    /// the text in its line, starting from `1`.
+    pub column: usize,
+
+    /// The span of bytes in the source
+    /// text containing the text.
+    pub span: Range<usize>,
+}
+
+/// Formats an optional [`SourceLocation`]
+/// as a suffix of a [`ParseError`] message.
+struct At<'a>(&'a Option<SourceLocation>);
+
+impl Display for At<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(location) => write!(f, " at line {}, column {}", location.line, location.column),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Returns the markdown of a coda containing one
+    /// data type named `data_type` with `fields`.
+    fn data_markdown(data_type: &str, fields: &str) -> String {
+        format!("# `MyCoda` Coda\n\n## `{data_type}` Data\n\n{fields}")
+    }
+
+    pub const TEST_CODA_MARKDOWN: &str = r#"
+# `MyCoda` Coda
+
+An example Markdown Coda.
+
+## `MyNestedDataType` Data
+
+A data type for nesting inside [`MyDataType`].
+
++ `floaty_field` f32
+
+    A 32-bit floating-point field.
+
++ `listy_field` list of text
+
+    A list of data with the same type.
+
+    > This field has some fancy nested docs, too.
+
+    Like, _really_ fancy. <3
+
+## `MyDataType` Data
 
 An example Markdown Data Type.
 
@@ -407,6 +1495,9 @@ An example Markdown Data Type.
             typing: Type::F32,
             optional: false,
             flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
         })
         .with(DataField {
             name: "listy_field".into(),
@@ -414,6 +1505,9 @@ An example Markdown Data Type.
             typing: Type::List(Type::Text.into()),
             optional: false,
             flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
         });
         expected.data.push(nested_data_type.clone());
 
@@ -432,6 +1526,9 @@ An example Markdown Data Type.
                 typing: Type::Text,
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             })
             .with(DataField {
                 name: "integral_field".into(),
@@ -439,6 +1536,9 @@ An example Markdown Data Type.
                 typing: Type::I32,
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             })
             .with(DataField {
                 name: "nested_field".into(),
@@ -446,6 +1546,9 @@ An example Markdown Data Type.
                 typing: Type::Data(nested_data_type),
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             })
             .with(DataField {
                 name: "optional_field".into(),
@@ -453,6 +1556,9 @@ An example Markdown Data Type.
                 typing: Type::U64,
                 optional: true,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             })
             .with(DataField {
                 name: "3d_field".into(),
@@ -460,6 +1566,9 @@ An example Markdown Data Type.
                 typing: Type::List(Type::List(Type::List(Type::I32.into()).into()).into()),
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             })
             .with(DataField {
                 name: "map_field".into(),
@@ -467,6 +1576,9 @@ An example Markdown Data Type.
                 typing: Type::Map((Type::Text, Type::I32).into()),
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             })
             .with(DataField {
                 name: "unspecified_field".into(),
@@ -474,6 +1586,9 @@ An example Markdown Data Type.
                 typing: Type::Unspecified,
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             }),
         );
 
@@ -485,7 +1600,7 @@ An example Markdown Data Type.
     #[test]
     fn parses_markdown_into_intermediate_representation() -> Result<(), ParseError> {
         let mut parser = Parser::new(TEST_CODA_MARKDOWN);
-        let coda = parser.parse()?;
+        let coda = parser.parse()?.remove(0);
 
         assert_eq!("MyCoda", coda.global_name);
         assert_eq!("MyCoda", coda.local_name);
@@ -606,16 +1721,959 @@ An example Markdown Data Type.
     fn parses_coda_local_names() -> Result<(), ParseError> {
         // Test without Coda suffix.
         let mut parser = Parser::new("# `codas.dev:names/local/Test`");
-        let coda = parser.parse()?;
+        let coda = parser.parse()?.remove(0);
         assert_eq!("codas.dev:names/local/Test", coda.global_name);
         assert_eq!("Test", coda.local_name);
 
         // Test with Coda suffix.
         let mut parser = Parser::new("# `codas.dev:names/local/Test` Coda");
-        let coda = parser.parse()?;
+        let coda = parser.parse()?.remove(0);
         assert_eq!("codas.dev:names/local/Test", coda.global_name);
         assert_eq!("Test", coda.local_name);
 
         Ok(())
     }
+
+    #[test]
+    fn reports_error_locations() {
+        // Field docs must be indented.
+        let markdown = r#"
+# `MyCoda` Coda
+
+## `MyDataType` Data
+
++ `textual_field` text
+
+Some docs which aren't indented.
+"#;
+        let error = parse(markdown).unwrap_err();
+        assert!(matches!(error, ParseError::ExpectedDocsIndentation { .. }));
+        let location = error.location().expect("location");
+        assert_eq!(8, location.line);
+        assert_eq!(1, location.column);
+        assert!(error.to_string().contains("at line 8, column 1"));
+
+        // Codas must start with a coda header.
+        let error = parse("\n\n## `MyDataType` Data").unwrap_err();
+        assert!(matches!(error, ParseError::ExpectedCoda { .. }));
+        assert_eq!(3, error.location().expect("location").line);
+
+        // Data type docs must not be indented.
+        let error = parse(&data_markdown("MyDataType", "  Indented docs.\n")).unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::UnexpectedDocsIndentation { actual: 2, .. }
+        ));
+        let location = error.location().expect("location");
+        assert_eq!((5, 3), (location.line, location.column));
+
+        // Malformed fields are reported where they start.
+        let error = parse(&data_markdown("MyDataType", "+ `id` u32\n+ `name`\n")).unwrap_err();
+        let location = error.location().expect("location");
+        assert_eq!((6, 1), (location.line, location.column), "{error:?}");
+
+        // Data types past the last ordinal are reported
+        // at the first data type which doesn't fit.
+        let mut markdown = String::from("# `MyCoda` Coda\n");
+        for i in 0..=MAX_USER_ORDINAL as usize {
+            markdown.push_str(&format!("\n## `Data{i}` Data\n"));
+        }
+        let error = parse(&markdown).unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::TooManyDataTypes {
+                max: MAX_USER_ORDINAL,
+                ..
+            }
+        ));
+        assert_eq!(
+            3 + 2 * MAX_USER_ORDINAL as usize,
+            error.location().expect("location").line
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_fields() {
+        // Maps without a value type.
+        let error = parse(&data_markdown("MyDataType", "+ `x` map of u32\n")).unwrap_err();
+        assert!(
+            matches!(error, ParseError::MalformedField { .. }),
+            "{error:?}"
+        );
+        let location = error.location().expect("location");
+        assert_eq!((5, 1), (location.line, location.column));
+
+        // Scalars with more than one type.
+        let error = parse(&data_markdown("MyDataType", "+ `x` u32 u64\n")).unwrap_err();
+        assert!(
+            matches!(error, ParseError::MalformedField { .. }),
+            "{error:?}"
+        );
+        let location = error.location().expect("location");
+        assert_eq!((5, 1), (location.line, location.column));
+    }
+
+    #[test]
+    fn parses_many_codas() -> Result<(), ParseError> {
+        let markdown = r#"
+# `Request` Coda
+
+## `Ping` Data
+
++ `nonce` u64
+
+# `Response` Coda
+
+Responses to requests.
+
+## `Pong` Data
+
++ `nonce` u64
+
++ `ping` Ping
+"#;
+
+        let codas = parse_many(markdown)?;
+        assert_eq!(2, codas.len());
+
+        let request = &codas[0];
+        assert_eq!("Request", request.global_name);
+        assert_eq!(1, request.data.len());
+        assert_eq!("Ping", request.data[0].name);
+
+        let response = &codas[1];
+        assert_eq!("Response", response.global_name);
+        assert_eq!(Some("Responses to requests.".into()), response.docs);
+        assert_eq!(1, response.data.len());
+        assert_eq!("Pong", response.data[0].name);
+
+        // References to types in other codas aren't resolved.
+        let ping = response.data[0].iter().nth(1).expect("ping field");
+        assert_eq!(
+            Type::Data(DataType::new_fluid("Ping".into(), None)),
+            ping.typing
+        );
+
+        // Parsing a single coda only returns the first.
+        assert_eq!(request, &parse(markdown)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_imports() -> Result<(), ParseError> {
+        let markdown = r#"
+# `Shapes` Coda
+
+> import `Shared` from "shared.md"
+
+Shapes built from shared points.
+
+## `Line` Data
+
++ `start` Point
++ `end` Point
+"#;
+
+        let loader = |path: &str| -> Result<String, ParseError> {
+            match path {
+                "shared.md" => Ok("# `Shared` Coda\n\n## `Point` Data\n\n+ `x` i32\n".into()),
+                _ => Err(ParseError::MissingImport {
+                    name: Text::default(),
+                    path: path.into(),
+                    location: None,
+                }),
+            }
+        };
+
+        // Imported types are resolved.
+        let coda = parse_with_loader(markdown, "shapes.md", loader)?;
+        assert_eq!(Some("Shapes built from shared points.".into()), coda.docs);
+        let line = coda.iter().next().expect("line");
+        for field in line.iter() {
+            assert!(matches!(
+                &field.typing,
+                Type::Data(data) if data.name == "Point" && data.iter().count() == 1
+            ));
+        }
+
+        // Imported types aren't resolved without a loader.
+        let coda = parse(markdown)?;
+        let line = coda.iter().next().expect("line");
+        let start = line.iter().next().expect("start");
+        assert_eq!(
+            Type::Data(DataType::new_fluid("Point".into(), None)),
+            start.typing
+        );
+
+        // Imports which can't be loaded are reported where they're declared.
+        let missing = markdown.replace("shared.md", "missing.md");
+        let error = parse_with_loader(&missing, "shapes.md", loader).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::MissingImport { name, path, location: Some(location) }
+                    if name == "Shared" && path == "missing.md" && location.line == 4
+            ),
+            "{error}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_circular_imports() {
+        let loader = |path: &str| -> Result<String, ParseError> {
+            match path {
+                "a.md" => Ok("# `A` Coda\n\n> import `B` from \"b.md\"\n".into()),
+                "b.md" => Ok("# `B` Coda\n\n> import `A` from \"a.md\"\n".into()),
+                "self.md" => Ok("# `Self` Coda\n\n> import `Self` from \"self.md\"\n".into()),
+                _ => Err(ParseError::UnexpectedError { location: None }),
+            }
+        };
+
+        // The root coda's path is part of the cycle.
+        let error = parse_with_loader(&loader("a.md").unwrap(), "a.md", loader).unwrap_err();
+        assert!(
+            matches!(&error, ParseError::CircularImport { path, .. } if path == "a.md"),
+            "{error}"
+        );
+
+        // Codas importing themselves are rejected before loading anything.
+        let error = parse_with_loader(&loader("self.md").unwrap(), "self.md", |_| unreachable!())
+            .unwrap_err();
+        assert!(
+            matches!(&error, ParseError::CircularImport { path, .. } if path == "self.md"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn distinguishes_enum_and_data_headers() {
+        let tokens = |markdown: &'static str| {
+            Token::lexer(markdown)
+                .filter_map(Result::ok)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            alloc::vec![Token::Enum(("Foo", None))],
+            tokens("\n### `Foo` Enum")
+        );
+        assert_eq!(
+            alloc::vec![Token::Data(("Foo", None))],
+            tokens("\n## `Foo` Data")
+        );
+        assert_eq!(
+            alloc::vec![Token::Data(("Foo", Some(7)))],
+            tokens("\n## `Foo` Data 7")
+        );
+        assert_eq!(
+            alloc::vec![Token::Enum(("Foo", Some(12)))],
+            tokens("\n### `Foo` Enum  12")
+        );
+        assert!(matches!(
+            tokens("\n### `Foo` Data").as_slice(),
+            [Token::DocsLine(..)]
+        ));
+        assert!(matches!(
+            tokens("\n## `Foo` Enum").as_slice(),
+            [Token::DocsLine(..)]
+        ));
+        assert_eq!(
+            alloc::vec![Token::EnumVariant(("foo", 42))],
+            tokens("\n+ `foo` = 42")
+        );
+    }
+
+    #[test]
+    fn parses_enums() -> Result<(), ParseError> {
+        let markdown = r#"
+# `Shapes` Coda
+
+Headers like ### `Foo` Data are just docs.
+
+## `Point` Data
+
++ `x` i32
+
+### `Color` Enum
+
+A color.
+
++ `red` = 0
+
+    The color red.
+
++ `blue` = 1
+
+## `Circle` Data
+
++ `center` Point
++ `color` color = blue
++ `palette` list of Color
+"#;
+
+        let coda = parse(markdown)?;
+        let names: Vec<_> = coda.iter().map(|data| data.name.clone()).collect();
+        assert_eq!(alloc::vec!["Point", "Circle"], names);
+
+        // Enums aren't data, so they aren't numbered.
+        let circle = coda.iter().nth(1).expect("circle");
+        assert_eq!(2, circle.format().as_data_format().ordinal);
+
+        let color = coda.enums()[0].clone();
+        assert_eq!("Color", color.name);
+        assert_eq!(Some("A color.".into()), color.docs);
+        assert_eq!(
+            alloc::vec![Text::from("red"), "blue".into()],
+            color.variants
+        );
+        assert_eq!(Some(&"The color red.".into()), color.variant_docs(0));
+        assert_eq!(None, color.variant_docs(1));
+
+        // Enums can be referenced by data types.
+        let field = |name: &str| circle.iter().find(|field| field.name == name).expect(name);
+        let color_field = field("color");
+        assert_eq!(Type::Enum(color.clone()), color_field.typing);
+        assert_eq!(
+            Some(Unspecified::Enum {
+                typing: color.clone(),
+                variant: 1
+            }),
+            color_field.default
+        );
+        assert_eq!(
+            Type::List(Type::Enum(color).into()),
+            field("palette").typing
+        );
+
+        // Enums are encoded with their coda.
+        let encoded = crate::codec::encode_to_vec(&coda).expect("encoded");
+        let decoded: Coda = crate::codec::decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(coda, decoded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_enums() {
+        let error = parse("# `Shapes` Coda\n\n### `Color` Enum 3\n\n+ `red` = 0\n").unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::UnexpectedEnumOrdinal { type_name, location: Some(location) }
+                    if type_name == "Color" && location.line == 3
+            ),
+            "{error}"
+        );
+
+        // Variants are valued by their ordinal.
+        let error = parse("# `Shapes` Coda\n\n### `Color` Enum\n\n+ `red` = 0\n+ `blue` = 42\n")
+            .unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::InvalidVariantValue {
+                    type_name,
+                    variant_name,
+                    value: 42,
+                    expected: 1,
+                    location: Some(location),
+                } if type_name == "Color" && variant_name == "blue" && location.line == 6
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_field_names() {
+        let markdown = data_markdown("Point", "+ `x` i32\n+ `y` i32\n+ `x` i64\n");
+
+        let error = parse(&markdown).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::DuplicateFieldName { type_name, field_name, .. }
+                    if type_name == "Point" && field_name == "x"
+            ),
+            "{error}"
+        );
+        let location = error.location().expect("location");
+        assert_eq!((7, 1), (location.line, location.column));
+    }
+
+    #[test]
+    fn rejects_duplicate_type_names() {
+        let markdown = data_markdown("Point", "+ `x` i32\n\n### `point` Enum\n\n+ `origin` = 0\n");
+
+        let error = parse(&markdown).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::DuplicateTypeName { type_name, .. } if type_name == "point"
+            ),
+            "{error}"
+        );
+        let location = error.location().expect("location");
+        assert_eq!((7, 1), (location.line, location.column));
+    }
+
+    #[test]
+    fn parses_128_bit_integers() -> Result<(), ParseError> {
+        let markdown = data_markdown("Id", "+ `high` u128\n+ `low` i128\n");
+
+        let coda = parse(&markdown)?;
+        let id = coda.iter().next().expect("data type");
+        let fields: Vec<_> = id.iter().map(|field| &field.typing).collect();
+        assert_eq!(alloc::vec![&Type::U128, &Type::I128], fields);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_bytes() -> Result<(), ParseError> {
+        let markdown = data_markdown("Blob", "+ `data` bytes\n+ `more` list of bytes\n");
+
+        let coda = parse(&markdown)?;
+        let blob = coda.iter().next().expect("data type");
+        let fields: Vec<_> = blob.iter().map(|field| &field.typing).collect();
+        assert_eq!(
+            alloc::vec![&Type::Bytes, &Type::List(Type::Bytes.into())],
+            fields
+        );
+
+        // Bytes are data fields, like text.
+        let format = blob.format().as_data_format();
+        assert_eq!((0, 2), (format.blob_size, format.data_fields));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_timestamps() -> Result<(), ParseError> {
+        let markdown = data_markdown(
+            "Entry",
+            "+ `created_at` timestamp\n+ `edits` list of timestamp\n",
+        );
+
+        let coda = parse(&markdown)?;
+        let entry = coda.iter().next().expect("data type");
+        let fields: Vec<_> = entry.iter().map(|field| &field.typing).collect();
+        assert_eq!(
+            alloc::vec![&Type::Timestamp, &Type::List(Type::Timestamp.into())],
+            fields
+        );
+
+        // Timestamps are blob fields, like `i64`s.
+        let format = entry.format().as_data_format();
+        assert_eq!((8, 1), (format.blob_size, format.data_fields));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_enum_fields() -> Result<(), ParseError> {
+        let markdown = data_markdown("User", "+ `name` text\n+ `account_status` one of [Active, `Suspended`,Deleted]\n+ `role` optional One Of [ Admin ]\n");
+
+        let coda = parse(&markdown)?;
+        let user = coda.iter().next().expect("data type");
+        let status = user.field("account_status").expect("field");
+        assert_eq!(
+            Type::Enum(EnumType::new(
+                "UserAccountStatus".into(),
+                &["Active".into(), "Suspended".into(), "Deleted".into()]
+            )),
+            status.typing
+        );
+        let role = user.field("role").expect("field");
+        assert!(role.optional);
+        assert_eq!(
+            Type::Enum(EnumType::new("UserRole".into(), &["Admin".into()])),
+            role.typing
+        );
+
+        // Enums are blob fields, like `u16`s.
+        let data_format = user.format().as_data_format();
+        assert_eq!((2, 2), (data_format.blob_size, data_format.data_fields));
+
+        // Enums are written back to markdown as they're declared.
+        let formatted = format(&markdown)?;
+        assert!(formatted.contains("+ `account_status` one of [Active, Suspended, Deleted]"));
+        assert_eq!(coda, parse(&formatted)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_duplicate_variant_names() {
+        let markdown = data_markdown("User", "+ `status` one of [Active, Active]\n");
+        assert!(matches!(
+            parse(&markdown),
+            Err(ParseError::DuplicateVariantName { variant_name, .. }) if variant_name == "Active"
+        ));
+
+        // Enums declared by fields are named like data types.
+        let markdown = "# `Accounts` Coda\n\n## `User` Data\n\n+ `status` one of [Active]\n\n## `UserStatus` Data\n\n+ `id` u32\n";
+        assert!(matches!(
+            parse(markdown),
+            Err(ParseError::DuplicateTypeName { type_name, .. }) if type_name == "UserStatus"
+        ));
+    }
+
+    #[test]
+    fn parses_variable_length_integers() -> Result<(), ParseError> {
+        let markdown = data_markdown("Counter", "+ `count` uvar\n+ `delta` ivar\n");
+
+        let coda = parse(&markdown)?;
+        let counter = coda.iter().next().expect("data type");
+        let fields: Vec<_> = counter.iter().map(|field| &field.typing).collect();
+        assert_eq!(alloc::vec![&Type::UVar, &Type::IVar], fields);
+
+        // Variable-length integers are data fields, not blobs.
+        let format = counter.format().as_data_format();
+        assert_eq!((0, 2), (format.blob_size, format.data_fields));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_default_values() -> Result<(), ParseError> {
+        let markdown = r#"# `Jobs` Coda
+
+## `Job` Data
+
++ `retries` u32 = 3
++ `offset` i8 = -128
++ `weight` f64 = 0.5
++ `count` uvar = 7
++ `enabled` bool = true
++ `name` text = "the \"first\" job\n"
++ `grade` char = 'A'
++ `status` one of [Queued, Running] = Running
++ `started_at` optional timestamp = 1700000000000
++ `label` text
+"#;
+
+        let coda = parse(markdown)?;
+        let job = coda.iter().next().expect("data type");
+        let default = |name: &str| job.field(name).expect("field").default.clone();
+        assert_eq!(Some(Unspecified::U32(3)), default("retries"));
+        assert_eq!(Some(Unspecified::I8(-128)), default("offset"));
+        assert_eq!(Some(Unspecified::F64(0.5)), default("weight"));
+        assert_eq!(Some(Unspecified::UVar(VarU64(7))), default("count"));
+        assert_eq!(Some(Unspecified::Bool(true)), default("enabled"));
+        assert_eq!(
+            Some(Unspecified::Text("the \"first\" job\n".into())),
+            default("name")
+        );
+        assert_eq!(Some(Unspecified::Char('A')), default("grade"));
+        assert!(matches!(
+            default("status"),
+            Some(Unspecified::Enum { typing, variant: 1 }) if typing.name == "JobStatus"
+        ));
+        assert_eq!(
+            Some(Unspecified::Timestamp(1_700_000_000_000)),
+            default("started_at")
+        );
+        assert!(job.field("started_at").expect("field").optional);
+        assert_eq!(None, default("label"));
+
+        // Defaults don't affect the format of data.
+        let format = job.format().as_data_format();
+        assert_eq!((20, 4), (format.blob_size, format.data_fields));
+
+        // Defaults are written back to markdown as they're declared.
+        let formatted = super::format(markdown)?;
+        assert!(formatted.contains("+ `name` text = \"the \\\"first\\\" job\\n\"\n"));
+        assert!(formatted.contains("+ `status` one of [Queued, Running] = Running\n"));
+        assert_eq!(coda, parse(&formatted)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parses_explicit_ordinals() -> Result<(), ParseError> {
+        let markdown = r#"# `Shapes` Coda
+
+## `Square` Data 7
+
++ `side` f32
+
+### `Color` Enum
+
++ `Red` = 0
+
+## `Circle` Data 2
+
++ `radius` f32
+"#;
+
+        let coda = parse(markdown)?;
+        let ordinals: Vec<_> = coda
+            .iter()
+            .map(|data| (data.name.as_str(), data.ordinal()))
+            .collect();
+        assert_eq!(alloc::vec![("Circle", 2), ("Square", 7)], ordinals);
+
+        // Reordering the document doesn't change any ordinals.
+        let reordered = r#"# `Shapes` Coda
+
+## `Circle` Data 2
+
++ `radius` f32
+
+## `Square` Data 7
+
++ `side` f32
+
+### `Color` Enum
+
++ `Red` = 0
+"#;
+        assert_eq!(coda, parse(reordered)?);
+
+        // Explicit ordinals are written back to markdown...
+        let formatted = super::format(markdown)?;
+        assert!(formatted.contains("\n## `Circle` Data 2\n"));
+        assert!(formatted.contains("\n## `Square` Data 7\n"));
+        assert_eq!(coda, parse(&formatted)?);
+
+        // ...unless they're the same as the implicit ordinals.
+        let implicit = parse("# `Shapes` Coda\n\n## `Circle` Data 1\n\n## `Square` Data 2\n")?;
+        assert!(implicit.to_markdown().contains("\n## `Circle` Data\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_ordinals() {
+        let error =
+            parse("# `Shapes` Coda\n\n## `Circle` Data 2\n\n## `Square` Data 2\n").unwrap_err();
+        assert!(matches!(
+            &error,
+            ParseError::DuplicateOrdinal { type_name, ordinal: 2, location: Some(location) }
+                if type_name == "Square" && location.line == 5
+        ));
+
+        let error =
+            parse("# `Shapes` Coda\n\n## `Circle` Data 2\n\n## `Square` Data\n").unwrap_err();
+        assert!(matches!(
+            &error,
+            ParseError::MixedOrdinals { type_name, .. } if type_name == "Square"
+        ));
+
+        for ordinal in ["0", "224", "234", "99999999999999999999999"] {
+            let error =
+                parse(&format!("# `Shapes` Coda\n\n## `Circle` Data {ordinal}\n")).unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    ParseError::InvalidOrdinal {
+                        max: MAX_USER_ORDINAL,
+                        ..
+                    }
+                ),
+                "{error:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reserves_built_in_ordinals() {
+        // User ordinals never decode as built-in types.
+        for ordinal in 1..=MAX_USER_ORDINAL {
+            assert_eq!(None, Type::from_ordinal(ordinal), "{ordinal}");
+        }
+
+        // Codas using the last user ordinal round-trip.
+        let coda = parse(&format!(
+            "# `Shapes` Coda\n\n## `Circle` Data {MAX_USER_ORDINAL}\n\n+ `radius` u32\n"
+        ))
+        .unwrap();
+        let encoded = crate::codec::encode_to_vec(&coda).unwrap();
+        let decoded: Coda = crate::codec::decode_from_slice(&encoded).unwrap();
+        assert_eq!(coda, decoded);
+    }
+
+    #[test]
+    fn rejects_unresolved_types_when_strict() {
+        for typing in ["Usr", "list of Usr", "map of text to Usr"] {
+            let markdown = data_markdown("User", &format!("+ `id` u32\n+ `friend` {typing}\n"));
+
+            let error = parse_strict(&markdown).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    ParseError::UnresolvedType { type_name, field_name, name, location: Some(location) }
+                        if type_name == "User" && field_name == "friend" && name == "Usr" && location.line == 6
+                ),
+                "{error:?}"
+            );
+
+            // Lenient parsing resolves unknown types as unspecified data.
+            parse(&markdown).unwrap();
+        }
+    }
+
+    #[test]
+    fn resolves_forward_references() -> Result<(), ParseError> {
+        let markdown = "# `Shapes` Coda\n\n## `A` Data\n\n+ `b` B\n+ `bs` list of B\n\n## `B` Data\n\n+ `c` optional C\n\n## `C` Data\n\n+ `id` u32\n";
+        let coda = parse_strict(markdown)?;
+
+        // Data types keep the ordinals of their declarations.
+        let names: Vec<&str> = coda.iter().map(|data| data.name.as_ref()).collect();
+        assert_eq!(vec!["A", "B", "C"], names);
+        assert_eq!(
+            vec![1, 2, 3],
+            coda.iter().map(|data| data.ordinal()).collect::<Vec<_>>()
+        );
+
+        let a = &coda.data[0];
+        let b = &coda.data[1];
+        let c = &coda.data[2];
+        assert_eq!(
+            Some(Type::Data(b.clone())),
+            a.field("b").map(|f| f.typing.clone())
+        );
+        assert_eq!(
+            Some(Type::List(Type::Data(b.clone()).into())),
+            a.field("bs").map(|f| f.typing.clone())
+        );
+        assert_eq!(
+            Some(Type::Data(c.clone())),
+            b.field("c").map(|f| f.typing.clone())
+        );
+        assert!(a.format().as_data_format().data_fields > 0);
+
+        // Forward references survive formatting.
+        assert_eq!(coda, parse(&coda.to_markdown())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_recursive_types() {
+        let error = parse(&data_markdown("Node", "+ `children` list of Node\n")).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::RecursiveType { path, location: Some(location) }
+                    if path == &["Node", "Node"] && location.line == 5
+            ),
+            "{error:?}"
+        );
+
+        let error = parse(
+            "# `Graph` Coda\n\n## `A` Data\n\n+ `b` B\n\n## `B` Data\n\n+ `c` optional C\n\n## `C` Data\n\n+ `a` map of text to A\n",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::RecursiveType { path, .. } if path == &["A", "B", "C", "A"]
+            ),
+            "{error:?}"
+        );
+        assert_eq!(
+            "The data type `A` contains itself through `A` -> `B` -> `C` -> `A` at line 13, column 1.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn parses_deprecations() -> Result<(), ParseError> {
+        let markdown = r#"# `Users` Coda
+
+## `User` Data
+
+> Deprecated: Use `Account` instead.
+
++ `id` u32
++ `old_id` deprecated u64
++ `nickname` optional deprecated text
+
+    Nicknames are unused.
+
+    > Deprecated: Use `Account.name`.
+
+## `Account` Data
+
++ `name` text
+"#;
+
+        let coda = parse(markdown)?;
+        let user = coda
+            .iter()
+            .find(|typing| typing.name == "User")
+            .expect("data type");
+        assert!(user.deprecated);
+        assert_eq!(
+            Some(Text::from("Use `Account` instead.")),
+            user.deprecation_note
+        );
+
+        let field = |name: &str| user.field(name).expect("field");
+        assert!(!field("id").deprecated);
+        assert!(field("old_id").deprecated);
+        assert_eq!(None, field("old_id").deprecation_note);
+        assert!(field("nickname").deprecated);
+        assert!(field("nickname").optional);
+        assert_eq!(
+            Some(Text::from("Use `Account.name`.")),
+            field("nickname").deprecation_note
+        );
+
+        let account = coda
+            .iter()
+            .find(|typing| typing.name == "Account")
+            .expect("data type");
+        assert!(!account.deprecated);
+        assert!(!account.field("name").expect("field").deprecated);
+
+        // Deprecations are written back to markdown.
+        let formatted = super::format(markdown)?;
+        assert!(formatted.contains("+ `old_id` deprecated u64\n"));
+        assert!(formatted.contains("+ `nickname` optional deprecated text\n"));
+        assert_eq!(coda, parse(&formatted)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_default_values() {
+        for (typing, literal) in [
+            ("u8", "256"),
+            ("u32", "-1"),
+            ("i32", "3.5"),
+            ("f32", "inf"),
+            ("f64", "one"),
+            ("bool", "True"),
+            ("text", "unquoted"),
+            ("text", "\"unterminated"),
+            ("text", r#""bad \escape""#),
+            ("char", "'ab'"),
+            ("one of [Active]", "Deleted"),
+            ("list of u8", "[]"),
+            ("u32", ""),
+        ] {
+            let markdown = data_markdown(
+                "Job",
+                &format!("+ `id` u32\n+ `field` {typing} = {literal}\n"),
+            );
+            let error = parse(&markdown).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    ParseError::InvalidDefault { type_name, field_name, literal: actual, .. }
+                        if type_name == "Job" && field_name == "field" && actual == literal
+                ),
+                "{typing} = {literal}: {error}"
+            );
+            assert_eq!(6, error.location().expect("location").line);
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_data_types() {
+        let mut markdown = data_markdown("Big", "");
+        for i in 0..256 {
+            markdown += &format!("+ `field_{i}` text\n");
+        }
+
+        let error = parse(&markdown).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                ParseError::FormatOverflow { type_name, field_name, .. }
+                    if type_name == "Big" && field_name == "field_255"
+            ),
+            "{error}"
+        );
+        let location = error.location().expect("location");
+        assert_eq!(260, location.line);
+    }
+
+    #[test]
+    fn formats_markdown() -> Result<(), ParseError> {
+        let markdown = r#"
+# `Shapes` Coda
+> import `Shared` from "shared.md"
+Shapes built from shared points.
+
+
+## `line` Data
+A line.
++ `label` optional text
+  The line's label,
+  which is optional.
++ `start` Point
++ `length` f32
+### `color` Enum
++ `red` = 0
+  The color red.
++ `blue` = 1
+## `Circle` Data
++ `color` color
++ `radii` 2d list of f32
+"#;
+
+        let formatted = format(markdown)?;
+        assert_eq!(
+            r#"# `Shapes` Coda
+
+> import `Shared` from "shared.md"
+
+Shapes built from shared points.
+
+## `Line` Data
+
+A line.
+
++ `length` f32
++ `label` optional text
+
+    The line's label,
+    which is optional.
+
++ `start` Point
+
+## `Circle` Data
+
++ `color` Color
++ `radii` 2d list of f32
+
+### `Color` Enum
+
++ `red` = 0
+
+    The color red.
+
++ `blue` = 1
+"#,
+            formatted
+        );
+
+        // Formatting is idempotent.
+        assert_eq!(formatted, format(&formatted)?);
+
+        // Formatting preserves the names and formats of data types.
+        let coda = parse(&formatted)?;
+        assert_eq!(
+            coda.to_markdown(),
+            parse(&coda.to_markdown())?.to_markdown()
+        );
+        let original = parse(markdown)?;
+        for (original, formatted) in original.iter().zip(coda.iter()) {
+            assert!(original.name.eq_ignore_ascii_case(&formatted.name));
+            assert_eq!(original.docs, formatted.docs);
+            assert_eq!(original.format(), formatted.format());
+        }
+        assert_eq!(
+            original.enums()[0].variant_docs,
+            coda.enums()[0].variant_docs
+        );
+
+        Ok(())
+    }
 }
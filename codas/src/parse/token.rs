@@ -1,11 +1,13 @@
 //! Markdown lexical analysis and
 //! tokenization utilities.
-use alloc::vec;
+use alloc::{format, vec};
 
 use core::ops::Range;
 
 use logos::{Logos, Span};
 
+use crate::types::Text;
+
 use super::{ParsedField, ParsedFieldType};
 
 /// Enumeration of tokens that can be
@@ -83,7 +85,10 @@ pub enum Token<'a> {
     /// This token marks the beginning of
     /// a data type, where `TheDataName` is
     /// the name of the specified type.
-    #[regex(r"(?&linebreak)##(?&space)`(?&data_id)`(?&space)(?i)(data)", |lex| {
+    ///
+    /// The header may end with the data type's
+    /// explicit ordinal, like ``## `TheDataName` Data 7``.
+    #[regex(r"(?&linebreak)##(?&space)`(?&data_id)`(?&space)(?i)(data)((?&space)+[0-9]+)?", |lex| {
         let slice = lex.slice();
 
         let slice = slice.trim(); // trim whitespace
@@ -91,14 +96,87 @@ pub enum Token<'a> {
         let slice = slice.trim(); // trim whitespace
 
         // Slice should contain:
-        // `DataName` Data
+        // `DataName` Data [ordinal]
         let mut split = slice.split_whitespace();
         let data_name = split.next().unwrap();
+        let _ = split.next();
 
         // Trim leading and trailing grave characters.
-        &data_name[1..data_name.len() - 1]
+        (&data_name[1..data_name.len() - 1], split.next().map(parse_ordinal))
+    })]
+    Data((&'a str, Option<u64>)),
+
+    /// ``### `TheEnumName` Enum``
+    ///
+    /// This token marks the beginning of
+    /// an enumeration, where `TheEnumName` is
+    /// the name of the specified enum.
+    ///
+    /// Like [`Token::Data`], the header may end with
+    /// an explicit ordinal, which the parser rejects
+    /// (since enums aren't numbered like data types).
+    #[regex(r"(?&linebreak)###(?&space)`(?&data_id)`(?&space)(?i)(enum)((?&space)+[0-9]+)?", |lex| {
+        let slice = lex.slice();
+
+        let slice = slice.trim(); // trim whitespace
+        let slice = &slice[3..]; // trim leading ###
+        let slice = slice.trim(); // trim whitespace
+
+        // Slice should contain:
+        // `EnumName` Enum [ordinal]
+        let mut split = slice.split_whitespace();
+        let enum_name = split.next().unwrap();
+        let _ = split.next();
+
+        // Trim leading and trailing grave characters.
+        (&enum_name[1..enum_name.len() - 1], split.next().map(parse_ordinal))
+    })]
+    Enum((&'a str, Option<u64>)),
+
+    /// ``+ `the_variant_name` = 42``
+    ///
+    /// This token marks a variant of an enum,
+    /// where `the_variant_name` is the name of
+    /// the variant, and `42` is its value.
+    #[regex(r"(?&linebreak)\+(?&space)`(?&field_id)`(?&space)*=(?&space)*[0-9]+(?&space)*", |lex| {
+        let slice = lex.slice();
+
+        // Slice should contain:
+        // + `the_variant_name` = 42
+        let mut names = slice.split('`');
+        let _ = names.next();
+        let name = names.next().unwrap();
+
+        let value = slice.rsplit('=').next().unwrap().trim();
+        Some((name, value.parse().ok()?))
+    })]
+    EnumVariant((&'a str, u64)),
+
+    /// ``> import `The.Coda/Name` from "path/to/coda.md"``
+    ///
+    /// This token declares that the data types in
+    /// the coda named `The.Coda/Name`, loaded from
+    /// `path/to/coda.md`, may be referenced by this
+    /// coda's data types.
+    ///
+    /// Each tuple contains the name of the
+    /// imported coda, and the path to load it from.
+    #[regex(r#"(?&linebreak)>(?&space)import(?&space)`(?&coda_id)`(?&space)from(?&space)"[^"\r\n]*"(?&space)*"#, |lex| {
+        let slice = lex.slice();
+
+        // Slice should contain:
+        // > import `The.Coda/Name` from "path/to/coda.md"
+        let mut names = slice.split('`');
+        let _ = names.next();
+        let name = names.next().unwrap();
+
+        let mut paths = slice.split('"');
+        let _ = paths.next();
+        let path = paths.next().unwrap();
+
+        (name, path)
     })]
-    Data(&'a str),
+    Import((&'a str, &'a str)),
 
     /// ``+ `the_field_name` optional [N]d list of TheDataType``
     ///
@@ -111,15 +189,20 @@ pub enum Token<'a> {
     /// keywords or sub-tokens:
     ///
     /// - `optional`, indicating the field is semantically optional.
+    /// - `deprecated`, indicating the field should no longer be used.
     /// - `list of`, indicating the field is semantically a list.
     /// - `[N]d`, indicating the field is semantically a list with `N` dimensions
+    /// - `= literal`, following `TheDataType`, declaring the field's default value.
     ///
     /// `TheDataType` may optionally be written as a Markdown
     /// link, like: `[TheDataType](#link-to-the-datatype)`.
+    ///
+    /// If the field's typing is malformed (like ``+ `x` u32 u64``),
+    /// this token contains a description of the mistake instead.
     #[regex(r"(?&linebreak)\+(?&space)`(?&field_id)`(?&space)(?&to_end_of_line)", |lex| {
         parse_data_field(lex.slice())
     })]
-    DataField(ParsedField),
+    DataField(Result<ParsedField, Text>),
 
     /// Matches any non-token text on a newline, with
     /// zero or more leading spaces.
@@ -149,11 +232,14 @@ pub enum Token<'a> {
 /// ``+ `the_field_name` map of TheDataType to TheDataType``
 /// ``+ `the_field_name` [N]d list of TheDataType``
 /// ``+ `the_field_name` TheDataType``
+/// ``+ `the_field_name` one of [TheVariant, AnotherVariant]``
+/// ``+ `the_field_name` TheDataType = literal``
 #[derive(Logos, Debug, PartialEq)]
 #[logos(subpattern space = r"[^\S\r\n]")]
 #[logos(subpattern linebreak = r"[\r\n|\r|\n]+")]
 #[logos(subpattern data_id = r"[a-zA-Z0-9_-]+")]
 #[logos(subpattern field_id = r"[a-zA-Z0-9_-]+")]
+#[logos(subpattern variant_id = r"`?[a-zA-Z_][a-zA-Z0-9_]*`?")]
 pub enum DataFieldToken<'a> {
     /// This token marks the beginning of a data
     /// field, containing its name.
@@ -181,6 +267,10 @@ pub enum DataFieldToken<'a> {
     #[regex(r"(?i)flattened(?&space)")]
     Flattened,
 
+    /// This token indicates a field is deprecated.
+    #[regex(r"(?i)deprecated(?&space)")]
+    Deprecated,
+
     /// This token indicates a field is
     /// semantically a list.
     ///
@@ -213,6 +303,26 @@ pub enum DataFieldToken<'a> {
     #[regex(r"(?i)map(?&space)of(?&space)")]
     Map,
 
+    /// This token indicates a field is an enum,
+    /// containing the names of the enum's variants
+    /// in ascending order by ordinal.
+    ///
+    /// Variant names may optionally be
+    /// surrounded by grave characters.
+    #[regex(r"(?i)one(?&space)+of(?&space)+\[(?&space)*(?&variant_id)((?&space)*,(?&space)*(?&variant_id))*(?&space)*\]", |lex| {
+        let slice = lex.slice();
+
+        // Trim everything outside of the brackets.
+        let start = slice.find('[').unwrap();
+        let slice = &slice[start + 1..slice.len() - 1];
+
+        slice
+            .split(',')
+            .map(|variant| variant.trim().trim_matches('`'))
+            .collect::<vec::Vec<_>>()
+    })]
+    OneOf(vec::Vec<&'a str>),
+
     /// This token contains the fully-qualified
     /// type of a field.
     #[regex(r"(?i)(to(?&space))?\[`(?&data_id)`\]\([^)]*\)", |lex| {
@@ -237,57 +347,93 @@ pub enum DataFieldToken<'a> {
         split.last().unwrap()
     })]
     FieldType(&'a str),
+
+    /// This token contains the (unparsed) literal
+    /// of a field's default value, which extends
+    /// to the end of the field's declaration.
+    #[regex(r"=[^\r\n]*", |lex| lex.slice()[1..].trim())]
+    Default(&'a str),
+}
+
+/// Parses the explicit `ordinal` of a [`Token::Data`]
+/// or [`Token::Enum`], saturating at [`u64::MAX`] (which
+/// is rejected as an ordinal when the coda is built).
+fn parse_ordinal(ordinal: &str) -> u64 {
+    ordinal.parse().unwrap_or(u64::MAX)
 }
 
 /// Parser for a [`Token::DataField`] via a [`DataFieldToken`].
-fn parse_data_field(slice: &str) -> ParsedField {
+fn parse_data_field(slice: &str) -> Result<ParsedField, Text> {
     let lexer = DataFieldToken::lexer(slice);
 
     let mut name = slice;
     let mut optional = false;
     let mut flattened = false;
+    let mut deprecated = false;
     let mut list_dimensions = 0;
     let mut typing = vec![];
     let mut is_map = false;
+    let mut variants = None;
+    let mut default = None;
 
     for token in lexer.filter_map(|t| t.ok()) {
         match token {
             DataFieldToken::FieldName(field_name) => name = field_name,
             DataFieldToken::Optional => optional = true,
             DataFieldToken::Flattened => flattened = true,
+            DataFieldToken::Deprecated => deprecated = true,
             DataFieldToken::List(dimensions) => list_dimensions = dimensions,
             DataFieldToken::Map => is_map = true,
+            DataFieldToken::OneOf(names) => {
+                variants = Some(names.into_iter().map(Into::into).collect());
+            }
             DataFieldToken::FieldType(type_name) => {
                 typing.push(type_name.into());
             }
+            DataFieldToken::Default(literal) => default = Some(literal.into()),
         }
     }
 
-    let typing = match (list_dimensions, is_map, typing.len()) {
+    let typing = match (list_dimensions, is_map, typing.len(), variants) {
         // A scalar.
-        (0, false, 1) => ParsedFieldType::Scalar(typing.pop().unwrap()),
+        (0, false, 1, None) => ParsedFieldType::Scalar(typing.pop().unwrap()),
 
         // A list.
-        (n, false, 1) if n > 0 => ParsedFieldType::List(n, typing.pop().unwrap()),
+        (n, false, 1, None) if n > 0 => ParsedFieldType::List(n, typing.pop().unwrap()),
+
+        // An enum.
+        (0, false, 0, Some(variants)) => ParsedFieldType::Enum(variants),
 
         // A map.
-        (0, true, 2) => {
+        (0, true, 2, None) => {
             let value_typing = typing.pop().unwrap();
             let key_typing = typing.pop().unwrap();
             ParsedFieldType::Map(key_typing, value_typing)
         }
 
         // A mistake.
-        (dimensions, is_map, length) => {
-            todo!("malformed field: {dimensions:?} - {is_map} - {length}");
+        (_, _, length, Some(_)) if length > 0 => {
+            return Err("a field with variants can't also declare a type".into());
+        }
+        (dimensions, true, _, _) if dimensions > 0 => {
+            return Err("a field can't be both a list and a map".into());
+        }
+        (_, true, length, _) => {
+            return Err(format!("a map declares 2 types (a key and a value), not {length}").into());
+        }
+        (_, false, length, _) => {
+            return Err(format!("a field declares 1 type, not {length}").into());
         }
     };
 
-    ParsedField {
+    Ok(ParsedField {
         name: name.into(),
         docs: Range::default(),
         typing,
         optional,
         flattened,
-    }
+        deprecated,
+        default,
+        location: None,
+    })
 }
@@ -1,7 +1,7 @@
 //! Utilities for reading and writing streams of
 //! binary data, with optional support for `std::io::Read`
 //! and `std::io::Write` on platforms supporting them.
-use snafu::Snafu;
+use crate::codec::{CodecError, Decodable, ReadsDecodable};
 
 /// A thing that reads from a stream of bytes.
 pub trait Reads {
@@ -92,9 +92,7 @@ where
             | std::io::ErrorKind::ConnectionAborted
             | std::io::ErrorKind::BrokenPipe => StreamError::Closed,
             std::io::ErrorKind::UnexpectedEof => StreamError::Empty,
-            _ => StreamError::Other {
-                message: "Unexpected IO Error",
-            },
+            _ => StreamError::Io { source: e.into() },
         })
     }
 
@@ -104,13 +102,240 @@ where
             | std::io::ErrorKind::ConnectionAborted
             | std::io::ErrorKind::BrokenPipe => StreamError::Closed,
             std::io::ErrorKind::UnexpectedEof => StreamError::Empty,
-            _ => StreamError::Other {
-                message: "Unexpected IO Error",
-            },
+            _ => StreamError::Io { source: e.into() },
         })
     }
 }
 
+/// [`Reads`] wrapper that tracks the number
+/// of bytes read from a stream.
+///
+/// Errors returned by [`TrackedReader::read_data_at`]
+/// carry the [offset](crate::codec::CodecError::offset)
+/// into the stream where decoding failed:
+///
+/// ```rust
+/// # use codas::stream::TrackedReader;
+/// # use codas::types::Text;
+/// let mut reader = TrackedReader::new(&[0u8, 1, 2][..]);
+/// let error = reader.read_data_at::<Text>().unwrap_err();
+/// assert_eq!(Some(0), error.offset());
+/// ```
+pub struct TrackedReader<R: Reads> {
+    reader: R,
+    offset: u64,
+}
+
+impl<R: Reads> TrackedReader<R> {
+    /// Returns a new reader tracking bytes read from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self { reader, offset: 0 }
+    }
+
+    /// Returns the total number of bytes read so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Consumes this reader, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads and decodes a sequence of data into
+    /// a new, default instance of `T`, populating
+    /// any error with the offset where decoding failed.
+    pub fn read_data_at<T: Decodable + Default>(&mut self) -> Result<T, CodecError> {
+        self.read_data().map_err(|e| e.with_offset(self.offset))
+    }
+}
+
+impl<R: Reads> Reads for TrackedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+        let read = Reads::read(&mut self.reader, buf)?;
+        self.offset += read as u64;
+        Ok(read)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), StreamError> {
+        Reads::read_exact(&mut self.reader, buf)?;
+        self.offset += buf.len() as u64;
+        Ok(())
+    }
+}
+
+/// Default size in bytes of the buffers
+/// of [`BufferedReads`] and [`BufferedWrites`].
+pub const DEFAULT_BUFFER_SIZE: usize = 8 * 1024;
+
+/// [`Reads`] wrapper that reads from a stream
+/// in batches of up to `N` bytes.
+///
+/// Decoding data issues many small reads (one or more per
+/// field); buffering them reduces the number of reads issued
+/// to the underlying stream, which can drastically improve
+/// performance when reading from sockets or files.
+///
+/// ```rust
+/// # use codas::stream::BufferedReads;
+/// # use codas::types::Text;
+/// # use codas::codec::{ReadsDecodable, WritesEncodable};
+/// let mut encoded = vec![];
+/// encoded.write_data(&Text::from("cupcakes!")).unwrap();
+///
+/// let mut reader = BufferedReads::<_>::new(encoded.as_slice());
+/// let decoded: Text = reader.read_data().unwrap();
+/// assert_eq!("cupcakes!", decoded);
+/// ```
+pub struct BufferedReads<R: Reads, const N: usize = DEFAULT_BUFFER_SIZE> {
+    reader: R,
+    buffer: [u8; N],
+
+    /// Range of `buffer` containing bytes
+    /// which haven't been read yet.
+    start: usize,
+    end: usize,
+}
+
+impl<R: Reads, const N: usize> BufferedReads<R, N> {
+    /// Returns a new reader buffering reads from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: [0; N],
+            start: 0,
+            end: 0,
+        }
+    }
+
+    /// Returns the bytes which have been read from the
+    /// underlying stream, but not yet from this reader.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.start..self.end]
+    }
+
+    /// Consumes this reader, returning the wrapped reader.
+    ///
+    /// Any [`Self::buffered`] bytes are discarded.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Reads, const N: usize> Reads for BufferedReads<R, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+        // Bypass the buffer if it's empty, and wouldn't
+        // be able to hold all of the requested bytes.
+        if self.start == self.end && buf.len() >= N {
+            return self.reader.read(buf);
+        }
+
+        // Refill the buffer if it's empty.
+        if self.start == self.end {
+            self.end = self.reader.read(&mut self.buffer)?;
+            self.start = 0;
+        }
+
+        let read = buf.len().min(self.end - self.start);
+        buf[..read].copy_from_slice(&self.buffer[self.start..self.start + read]);
+        self.start += read;
+        Ok(read)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), StreamError> {
+        let mut read = 0;
+
+        while read < buf.len() {
+            match Reads::read(self, &mut buf[read..])? {
+                0 => return Err(StreamError::Empty),
+                n => read += n,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// [`Reads`] wrapper that reads no more than
+/// a fixed budget of bytes from a stream.
+///
+/// Once the budget is exhausted, reads fail with
+/// [`StreamError::Other`] _without_ reading from the
+/// underlying stream. Unlike a [`LimitedReader`](crate::codec::LimitedReader),
+/// whose limits apply to each decoded data, this budget
+/// applies to every byte read by this reader over its
+/// lifetime, making it suitable for capping the total
+/// number of bytes consumed from an untrusted stream.
+///
+/// ```rust
+/// # use codas::stream::LimitedReads;
+/// # use codas::types::Text;
+/// # use codas::codec::{ReadsDecodable, WritesEncodable};
+/// let mut encoded = vec![];
+/// encoded.write_data(&Text::from("cupcakes!")).unwrap();
+///
+/// // Headers alone are 8 bytes, so the text can't be read.
+/// let mut reader = LimitedReads::new(encoded.as_slice(), 12);
+/// assert!(reader.read_data::<Text>().is_err());
+/// assert_eq!(0, reader.remaining());
+/// ```
+pub struct LimitedReads<R: Reads> {
+    reader: R,
+    remaining: usize,
+}
+
+impl<R: Reads> LimitedReads<R> {
+    /// Returns a new reader which reads no more
+    /// than `limit` bytes from `reader`.
+    pub fn new(reader: R, limit: usize) -> Self {
+        Self {
+            reader,
+            remaining: limit,
+        }
+    }
+
+    /// Returns the number of bytes which
+    /// may still be read from this reader.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Consumes this reader, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Reads> Reads for LimitedReads<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+        if self.remaining == 0 && !buf.is_empty() {
+            return Err(READ_LIMIT_EXCEEDED);
+        }
+
+        let limit = buf.len().min(self.remaining);
+        let read = self.reader.read(&mut buf[..limit])?;
+        self.remaining -= read;
+        Ok(read)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), StreamError> {
+        if buf.len() > self.remaining {
+            self.remaining = 0;
+            return Err(READ_LIMIT_EXCEEDED);
+        }
+
+        self.reader.read_exact(buf)?;
+        self.remaining -= buf.len();
+        Ok(())
+    }
+}
+
+/// Error returned by [`LimitedReads`]
+/// once its budget is exhausted.
+const READ_LIMIT_EXCEEDED: StreamError = StreamError::Other {
+    message: "read limit exceeded",
+};
+
 /// A thing that writes to a stream of bytes.
 pub trait Writes {
     /// Writes bytes from `buf`, returning the number
@@ -144,6 +369,10 @@ pub trait Writes {
         feature = "langs-sql",
         feature = "langs-typescript",
         feature = "langs-open-api",
+        feature = "langs-golang",
+        feature = "langs-protobuf",
+        feature = "langs-json-schema",
+        feature = "langs-graphql",
         test
     )),
     allow(dead_code)
@@ -191,9 +420,7 @@ where
             | std::io::ErrorKind::ConnectionAborted
             | std::io::ErrorKind::BrokenPipe
             | std::io::ErrorKind::UnexpectedEof => StreamError::Closed,
-            _ => StreamError::Other {
-                message: "Unexpected IO Error",
-            },
+            _ => StreamError::Io { source: e.into() },
         })?;
 
         // If an implementor of std::io::Write returns
@@ -213,16 +440,156 @@ where
             | std::io::ErrorKind::ConnectionAborted
             | std::io::ErrorKind::BrokenPipe
             | std::io::ErrorKind::UnexpectedEof => StreamError::Closed,
-            _ => StreamError::Other {
-                message: "Unexpected IO Error",
-            },
+            _ => StreamError::Io { source: e.into() },
         })
     }
 }
 
+/// [`Writes`] wrapper that counts the
+/// number of bytes written to a stream.
+///
+/// ```rust
+/// # use codas::stream::CountingWrites;
+/// # use codas::types::Text;
+/// # use codas::codec::WritesEncodable;
+/// let mut writer = CountingWrites::new(vec![]);
+/// writer.write_data(&Text::from("cupcakes!")).unwrap();
+/// assert_eq!(8 + 9, writer.bytes_written());
+/// assert_eq!(8 + 9, writer.into_inner().len());
+/// ```
+pub struct CountingWrites<W: Writes> {
+    writer: W,
+    bytes_written: usize,
+}
+
+impl<W: Writes> CountingWrites<W> {
+    /// Returns a new writer counting bytes written to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            bytes_written: 0,
+        }
+    }
+
+    /// Returns the total number of bytes written so far.
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    /// Consumes this writer, returning the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Writes> Writes for CountingWrites<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        let written = self.writer.write(buf)?;
+        self.bytes_written += written;
+        Ok(written)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), StreamError> {
+        self.writer.write_all(buf)?;
+        self.bytes_written += buf.len();
+        Ok(())
+    }
+}
+
+/// [`Writes`] wrapper that writes to a stream
+/// in batches of up to `N` bytes.
+///
+/// Encoding data issues many small writes (one or more per
+/// field); buffering them reduces the number of writes issued
+/// to the underlying stream, which can drastically improve
+/// performance when writing to sockets or files.
+///
+/// Buffered bytes are only written to the underlying stream
+/// when the buffer is full, or when [`Self::flush`] is called.
+/// Any bytes which are still buffered when this writer
+/// is dropped are discarded.
+///
+/// ```rust
+/// # use codas::stream::BufferedWrites;
+/// # use codas::types::Text;
+/// # use codas::codec::{ReadsDecodable, WritesEncodable};
+/// let mut encoded = vec![];
+/// let mut writer = BufferedWrites::<_>::new(&mut encoded);
+/// writer.write_data(&Text::from("cupcakes!")).unwrap();
+/// writer.flush().unwrap();
+///
+/// let decoded: Text = encoded.as_slice().read_data().unwrap();
+/// assert_eq!("cupcakes!", decoded);
+/// ```
+pub struct BufferedWrites<W: Writes, const N: usize = DEFAULT_BUFFER_SIZE> {
+    writer: W,
+    buffer: [u8; N],
+
+    /// Number of bytes at the start of `buffer`
+    /// which haven't been written to `writer` yet.
+    len: usize,
+}
+
+impl<W: Writes, const N: usize> BufferedWrites<W, N> {
+    /// Returns a new writer buffering writes to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Writes all buffered bytes to the underlying stream.
+    pub fn flush(&mut self) -> Result<(), StreamError> {
+        if self.len > 0 {
+            self.writer.write_all(&self.buffer[..self.len])?;
+            self.len = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the bytes which have been written to
+    /// this writer, but not yet to the underlying stream.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+
+    /// Flushes this writer, and then consumes
+    /// it, returning the wrapped writer.
+    pub fn into_inner(mut self) -> Result<W, StreamError> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Writes, const N: usize> Writes for BufferedWrites<W, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), StreamError> {
+        // Make room for the bytes in the buffer.
+        if buf.len() > N - self.len {
+            self.flush()?;
+        }
+
+        // Bypass the buffer if it can't hold the bytes.
+        if buf.len() >= N {
+            return self.writer.write_all(buf);
+        }
+
+        self.buffer[self.len..self.len + buf.len()].copy_from_slice(buf);
+        self.len += buf.len();
+        Ok(())
+    }
+}
+
 /// Enumeration of errors that may occur while
 /// reading and/or writing streams of data.
-#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[derive(Debug, Clone)]
 pub enum StreamError {
     /// The stream is empty and will not
     /// receive any more data.
@@ -233,6 +600,319 @@ pub enum StreamError {
     Closed,
 
     /// Uncategorized error.
-    #[snafu(display("{message}"))]
     Other { message: &'static str },
+
+    /// Uncategorized error from an [`std::io`] stream.
+    #[cfg(any(feature = "std", test))]
+    Io {
+        source: std::sync::Arc<std::io::Error>,
+    },
+}
+
+// Implemented manually (instead of deriving `Snafu`)
+// because `Snafu` doesn't support variants which are
+// only present with some features enabled.
+impl core::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty"),
+            Self::Closed => write!(f, "Closed"),
+            Self::Other { message } => write!(f, "{message}"),
+            #[cfg(any(feature = "std", test))]
+            Self::Io { source } => write!(f, "{source}"),
+        }
+    }
+}
+
+impl snafu::Error for StreamError {
+    fn source(&self) -> Option<&(dyn snafu::Error + 'static)> {
+        match self {
+            #[cfg(any(feature = "std", test))]
+            Self::Io { source } => Some(&**source),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for StreamError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Empty, Self::Empty) | (Self::Closed, Self::Closed) => true,
+            (Self::Other { message: a }, Self::Other { message: b }) => a == b,
+
+            // IO errors aren't comparable, so they're
+            // only equal if they're the same error.
+            #[cfg(any(feature = "std", test))]
+            (Self::Io { source: a }, Self::Io { source: b }) => std::sync::Arc::ptr_eq(a, b),
+
+            _ => false,
+        }
+    }
+}
+
+impl Eq for StreamError {}
+
+/// Converts a [`StreamError`] into an [`std::io::Error`]
+/// containing the original error, with a kind of:
+///
+/// - [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) for an empty stream.
+/// - [`ConnectionReset`](std::io::ErrorKind::ConnectionReset) for a closed stream.
+/// - [`Other`](std::io::ErrorKind::Other) for uncategorized errors.
+///
+/// Errors which originated from an [`std::io`] stream
+/// are converted back into their original [`std::io::Error`].
+#[cfg(any(feature = "std", test))]
+impl From<StreamError> for std::io::Error {
+    fn from(value: StreamError) -> Self {
+        let kind = match value {
+            StreamError::Empty => std::io::ErrorKind::UnexpectedEof,
+            StreamError::Closed => std::io::ErrorKind::ConnectionReset,
+            StreamError::Other { .. } => std::io::ErrorKind::Other,
+            StreamError::Io { source } => {
+                return match std::sync::Arc::try_unwrap(source) {
+                    Ok(error) => error,
+                    Err(source) => std::io::Error::new(source.kind(), StreamError::Io { source }),
+                };
+            }
+        };
+
+        std::io::Error::new(kind, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codec::{encode_to_vec, Encodable, WritesEncodable},
+        types::Text,
+    };
+
+    use super::*;
+
+    #[test]
+    fn tracks_offset_of_errors() {
+        // Encode two texts; the second
+        // text's header spans bytes 16..24.
+        let texts = vec![Text::from(""), Text::from("offset")];
+        let mut encoded = vec![];
+        encoded.write_data(&texts).unwrap();
+
+        // Decode a known-good encoding.
+        let mut reader = TrackedReader::new(encoded.as_slice());
+        assert_eq!(texts, reader.read_data_at::<Vec<Text>>().unwrap());
+        assert_eq!(encoded.len() as u64, reader.offset());
+
+        // Corrupt the second text's blob size.
+        encoded[20] = 2;
+        let mut reader = TrackedReader::new(encoded.as_slice());
+        let error = reader.read_data_at::<Vec<Text>>().unwrap_err();
+        assert!(
+            matches!(error, CodecError::UnexpectedDataFormat { .. }),
+            "expected UnexpectedDataFormat, got {error:?}"
+        );
+        let offset = error.offset().expect("offset");
+        assert!((20..=24).contains(&offset), "unexpected offset {offset}");
+        assert!(error.to_string().ends_with(&format!("at byte {offset}")));
+
+        // Errors decoded without tracking have no offset.
+        let error = encoded.as_slice().read_data::<Vec<Text>>().unwrap_err();
+        assert_eq!(None, error.offset());
+    }
+
+    #[test]
+    fn converts_to_and_from_io_errors() {
+        use std::error::Error;
+
+        // Categorized errors convert to their closest kind.
+        let error = std::io::Error::from(StreamError::Empty);
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error.kind());
+        let error = std::io::Error::from(StreamError::Closed);
+        assert_eq!(std::io::ErrorKind::ConnectionReset, error.kind());
+        assert_eq!(
+            Some(&StreamError::Closed),
+            error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<StreamError>())
+        );
+        let error = std::io::Error::from(StreamError::Other { message: "oops" });
+        assert_eq!(std::io::ErrorKind::Other, error.kind());
+        assert_eq!("oops", error.to_string());
+
+        // Uncategorized IO errors are preserved by
+        // streams, and by any codec errors they cause.
+        let mut reader = FailingReader;
+        let error = reader.read_data::<u32>().unwrap_err();
+        let stream_error = error
+            .source()
+            .and_then(|e| e.downcast_ref::<StreamError>())
+            .expect("stream error")
+            .clone();
+        let io_error = stream_error
+            .source()
+            .and_then(|e| e.downcast_ref::<std::io::Error>())
+            .expect("io error");
+        assert_eq!(std::io::ErrorKind::PermissionDenied, io_error.kind());
+        assert_eq!(
+            std::io::ErrorKind::PermissionDenied,
+            std::io::Error::from(error).kind()
+        );
+
+        // ...and can be converted back into IO errors.
+        let boxed: Box<dyn Error> = Box::new(stream_error.clone());
+        assert_eq!("denied", boxed.to_string());
+        let io_error = std::io::Error::from(stream_error);
+        assert_eq!(std::io::ErrorKind::PermissionDenied, io_error.kind());
+        assert_eq!("denied", io_error.to_string());
+    }
+
+    #[test]
+    fn buffers_reads_and_writes() {
+        let texts: Vec<Text> = (0..64).map(|i| format!("text #{i}").into()).collect();
+        let mut unbuffered = CountingStream::default();
+        unbuffered.write_data(&texts).unwrap();
+        let unbuffered_writes = unbuffered.operations;
+
+        // Writes through a buffer should issue far
+        // fewer writes to the underlying stream.
+        let mut writer = BufferedWrites::<_, 64>::new(CountingStream::default());
+        writer.write_data(&texts).unwrap();
+        assert!(!writer.buffered().is_empty());
+        writer.flush().unwrap();
+        assert!(writer.buffered().is_empty());
+        let mut stream = writer.into_inner().unwrap();
+        assert_eq!(unbuffered.bytes, stream.bytes);
+        assert!(stream.operations * 4 < unbuffered_writes);
+
+        // Reads through a buffer should issue far
+        // fewer reads from the underlying stream.
+        stream.operations = 0;
+        let mut reader = BufferedReads::<_, 64>::new(stream);
+        assert_eq!(texts, reader.read_data::<Vec<Text>>().unwrap());
+        assert!(reader.buffered().is_empty());
+        assert!(reader.reader.operations * 4 < unbuffered_writes);
+
+        // Reading past the end of the stream fails.
+        assert_eq!(
+            Err(StreamError::Empty),
+            Reads::read_exact(&mut reader, &mut [0; 1])
+        );
+
+        // Writes larger than the buffer bypass it.
+        let mut writer = BufferedWrites::<_, 4>::new(CountingStream::default());
+        writer.write_all(&[1, 2]).unwrap();
+        writer.write_all(&[3, 4, 5, 6, 7, 8]).unwrap();
+        assert!(writer.buffered().is_empty());
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], writer.writer.bytes);
+        assert_eq!(2, writer.writer.operations);
+
+        // Reads larger than the buffer bypass it,
+        // once the buffer has been emptied.
+        let mut stream = writer.into_inner().unwrap();
+        stream.operations = 0;
+        let mut reader = BufferedReads::<_, 4>::new(stream);
+        let mut buf = [0; 6];
+        Reads::read_exact(&mut reader, &mut buf[..2]).unwrap();
+        assert_eq!([1, 2], buf[..2]);
+        assert_eq!(&[3, 4], reader.buffered());
+        Reads::read_exact(&mut reader, &mut buf).unwrap();
+        assert_eq!([3, 4, 5, 6, 7, 8], buf);
+        assert_eq!(2, reader.reader.operations);
+    }
+
+    #[test]
+    fn counts_writes() {
+        let mut writer = CountingWrites::new(vec![]);
+        assert_eq!(0, writer.bytes_written());
+        writer.write_all(&[1, 2, 3]).unwrap();
+        assert_eq!(3, writer.bytes_written());
+        assert_eq!(2, Writes::write(&mut writer, &[4, 5]).unwrap());
+        assert_eq!(5, writer.bytes_written());
+
+        let text = Text::from("counted");
+        writer.write_data(&text).unwrap();
+        assert_eq!(5 + text.encoded_len(), writer.bytes_written());
+        assert_eq!(writer.bytes_written(), writer.into_inner().len());
+    }
+
+    #[test]
+    fn limits_reads() {
+        let bytes = [1u8, 2, 3, 4, 5, 6];
+
+        // Reads are truncated to the remaining budget.
+        let mut reader = LimitedReads::new(&bytes[..], 4);
+        let mut buf = [0; 3];
+        assert_eq!(3, Reads::read(&mut reader, &mut buf).unwrap());
+        assert_eq!(1, reader.remaining());
+        assert_eq!(1, Reads::read(&mut reader, &mut buf).unwrap());
+        assert_eq!([4, 2, 3], buf);
+        assert_eq!(0, reader.remaining());
+
+        // Exhausted budgets fail without reading.
+        let error = Reads::read(&mut reader, &mut buf).unwrap_err();
+        assert_eq!(
+            StreamError::Other {
+                message: "read limit exceeded"
+            },
+            error
+        );
+        assert_eq!([5, 6], reader.into_inner());
+
+        // Exact reads exceeding the budget fail without reading.
+        let mut reader = LimitedReads::new(&bytes[..], 4);
+        assert!(Reads::read_exact(&mut reader, &mut [0; 5]).is_err());
+        assert_eq!(0, reader.remaining());
+        assert_eq!(bytes, reader.into_inner());
+
+        // Decoding stops once the budget is exhausted.
+        let encoded = encode_to_vec(&Text::from("limited")).unwrap();
+        let mut reader = LimitedReads::new(encoded.as_slice(), encoded.len());
+        assert_eq!("limited", reader.read_data::<Text>().unwrap());
+        let mut reader = LimitedReads::new(encoded.as_slice(), encoded.len() - 1);
+        assert!(matches!(
+            reader.read_data::<Text>(),
+            Err(CodecError::Stream {
+                source: StreamError::Other { .. },
+                ..
+            })
+        ));
+    }
+
+    /// Stream which reads back the bytes written to it,
+    /// counting every read or write issued to it.
+    #[derive(Default)]
+    struct CountingStream {
+        bytes: Vec<u8>,
+        read: usize,
+        operations: usize,
+    }
+
+    impl Reads for CountingStream {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+            self.operations += 1;
+            let read = buf.len().min(self.bytes.len() - self.read);
+            buf[..read].copy_from_slice(&self.bytes[self.read..self.read + read]);
+            self.read += read;
+            Ok(read)
+        }
+    }
+
+    impl Writes for CountingStream {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+            self.operations += 1;
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    /// [`std::io::Read`] which always fails.
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "denied",
+            ))
+        }
+    }
 }
@@ -9,22 +9,38 @@
 //! not be well-optimized.
 use core::convert::Infallible;
 
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use snafu::Snafu;
 
 use crate::codec::{
-    CodecError, DataFormat, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
-    UnexpectedDataFormatSnafu, WritesEncodable,
+    CodecError, Compatibility, DataFormat, DataHeader, Decodable, Encodable, Format,
+    ReadsDecodable, UnexpectedDataFormatSnafu, WritesEncodable,
 };
 
 pub mod binary;
 pub mod cryptography;
+pub mod diff;
 pub mod dynamic;
 pub mod list;
 pub mod map;
+mod markdown;
 pub mod number;
+pub mod pointer;
 mod text;
+mod timestamp;
+pub mod tuple;
+pub use diff::{check_coda_compat, diff, IncompatibilityReason};
 pub use dynamic::Unspecified;
 pub use text::*;
+pub use timestamp::*;
 
 /// Enumeration of available built in types.
 #[non_exhaustive]
@@ -52,6 +68,18 @@ pub enum Type {
     /// Signed (positive or negative) 64-bit number.
     I64,
 
+    /// Unsigned (positive) 128-bit number.
+    U128,
+    /// Signed (positive or negative) 128-bit number.
+    I128,
+
+    /// Unsigned (positive) 64-bit number,
+    /// encoded with a variable length.
+    UVar,
+    /// Signed (positive or negative) 64-bit number,
+    /// encoded with a variable length.
+    IVar,
+
     /// 32-bit floating point (decimal) number.
     F32,
     /// 64-bit floating point (decimal) number.
@@ -63,6 +91,22 @@ pub enum Type {
     /// UTF-8 encoded text.
     Text,
 
+    /// A single character (Unicode scalar value).
+    Char,
+
+    /// Opaque bytes, encoded like [`Type::Text`]
+    /// but without any UTF-8 validation.
+    Bytes,
+
+    /// Point in time, encoded as a signed 64-bit
+    /// number of milliseconds since the Unix epoch.
+    Timestamp,
+
+    /// One of a fixed set of named variants
+    /// with [`EnumType`], encoded as the
+    /// [`u16`] ordinal of the variant.
+    Enum(EnumType),
+
     /// Data with [`DataType`].
     Data(DataType),
 
@@ -97,6 +141,80 @@ impl Type {
             Type::Data(data) => data.format.as_data_format().ordinal,
             Type::List(_) => 243,
             Type::Map(_) => 242,
+            Type::U128 => 241,
+            Type::I128 => 240,
+            Type::Char => 239,
+            Type::UVar => 238,
+            Type::IVar => 237,
+            Type::Bytes => 236,
+            Type::Timestamp => 235,
+            Type::Enum(_) => 234,
+        }
+    }
+
+    /// Returns the [`Compatibility`] of data with this
+    /// type with a decoder expecting data with `reader`.
+    ///
+    /// Data types (including those within lists and maps)
+    /// are compared with [`DataType::compatible_with`],
+    /// and enums with [`EnumType::compatible_with`];
+    /// bytes and lists of `u8`s (which share an encoding)
+    /// are identical, as are timestamps and `i64`s; all
+    /// other types must be identical.
+    pub fn compatible_with(&self, reader: &Type) -> Compatibility {
+        match (self, reader) {
+            (Type::Timestamp, Type::I64) | (Type::I64, Type::Timestamp) => Compatibility::Identical,
+            (Type::Enum(_), Type::U16) => Compatibility::Identical,
+            (Type::Bytes, Type::List(item)) | (Type::List(item), Type::Bytes)
+                if **item == Type::U8 =>
+            {
+                Compatibility::Identical
+            }
+            (Type::Data(writer), Type::Data(reader)) => writer.compatible_with(reader),
+            (Type::Enum(writer), Type::Enum(reader)) => writer.compatible_with(reader),
+            (Type::List(writer), Type::List(reader)) => writer.compatible_with(reader),
+            (Type::Map(writer), Type::Map(reader)) => writer
+                .0
+                .compatible_with(&reader.0)
+                .and(writer.1.compatible_with(&reader.1)),
+            (writer, reader) if writer == reader => Compatibility::Identical,
+            (writer, reader) => Compatibility::incompatible(format!(
+                "{} can't be decoded as {}",
+                writer.description(),
+                reader.description()
+            )),
+        }
+    }
+
+    /// Returns a short, human-readable description of this type.
+    pub(crate) fn description(&self) -> Text {
+        match self {
+            Type::Data(data) => data.name.clone(),
+            Type::Enum(typing) => typing.name.clone(),
+            Type::List(typing) => format!("list of {}", typing.description()).into(),
+            Type::Map(typing) => format!(
+                "map of {} to {}",
+                typing.0.description(),
+                typing.1.description()
+            )
+            .into(),
+            typing => format!("{typing:?}").to_lowercase().into(),
+        }
+    }
+
+    /// Adds every enum within this type which isn't
+    /// already in `enums` (by name) to `enums`.
+    fn collect_enums<'a>(&'a self, enums: &mut Vec<&'a EnumType>) {
+        match self {
+            Type::Enum(typing) if !enums.iter().any(|other| other.name == typing.name) => {
+                enums.push(typing);
+            }
+            Type::List(typing) => typing.collect_enums(enums),
+            Type::Map(typing) => {
+                typing.0.collect_enums(enums);
+                typing.1.collect_enums(enums);
+            }
+            _ => {}
         }
     }
 
@@ -107,7 +225,8 @@ impl Type {
     ///
     /// List and Map return placeholder inner types
     /// ([`Type::Unspecified`]) since the ordinal alone doesn't
-    /// describe the element/key/value types.
+    /// describe the element/key/value types; likewise, Enum
+    /// returns an unnamed enum with no variants.
     pub fn from_ordinal(ordinal: u8) -> Option<Self> {
         match ordinal {
             0 => Some(Type::Unspecified),
@@ -125,6 +244,14 @@ impl Type {
             244 => Some(Type::Text),
             243 => Some(Type::List(Type::Unspecified.into())),
             242 => Some(Type::Map((Type::Unspecified, Type::Unspecified).into())),
+            241 => Some(Type::U128),
+            240 => Some(Type::I128),
+            239 => Some(Type::Char),
+            238 => Some(Type::UVar),
+            237 => Some(Type::IVar),
+            236 => Some(Type::Bytes),
+            235 => Some(Type::Timestamp),
+            234 => Some(Type::Enum(EnumType::default())),
             _ => None,
         }
     }
@@ -141,10 +268,18 @@ impl Type {
             Type::I16 => i16::FORMAT,
             Type::I32 => i32::FORMAT,
             Type::I64 => i64::FORMAT,
+            Type::U128 => u128::FORMAT,
+            Type::I128 => i128::FORMAT,
             Type::F32 => f32::FORMAT,
             Type::F64 => f64::FORMAT,
             Type::Bool => bool::FORMAT,
             Type::Text => Text::FORMAT,
+            Type::Char => char::FORMAT,
+            Type::UVar => number::VarU64::FORMAT,
+            Type::IVar => number::VarI64::FORMAT,
+            Type::Bytes => <[u8]>::FORMAT,
+            Type::Timestamp => Timestamp::FORMAT,
+            Type::Enum(_) => u16::FORMAT,
             Type::Data(data) => data.format,
             Type::List(typing) => typing.format().as_data_format().as_format(),
 
@@ -173,10 +308,17 @@ impl Type {
             "i16" => Some(Type::I16),
             "i32" => Some(Type::I32),
             "i64" => Some(Type::I64),
+            "u128" => Some(Type::U128),
+            "i128" => Some(Type::I128),
             "f32" => Some(Type::F32),
             "f64" => Some(Type::F64),
             "bool" => Some(Type::Bool),
             "text" => Some(Type::Text),
+            "bytes" => Some(Type::Bytes),
+            "char" => Some(Type::Char),
+            "uvar" => Some(Type::UVar),
+            "ivar" => Some(Type::IVar),
+            "timestamp" => Some(Type::Timestamp),
             _ => None,
         }
     }
@@ -197,6 +339,11 @@ pub struct Coda {
 
     /// Data in ascending order by ordinal.
     pub(crate) data: Vec<DataType>,
+
+    /// Enums declared by the coda itself (instead of
+    /// by its data types' fields), in the order
+    /// they're declared in.
+    pub(crate) enums: Vec<EnumType>,
 }
 
 impl Coda {
@@ -207,9 +354,16 @@ impl Coda {
             local_name,
             docs,
             data: Vec::from(data),
+            enums: vec![],
         }
     }
 
+    /// Returns this coda, also declaring the enum `typing`.
+    pub fn with_enum(mut self, typing: EnumType) -> Self {
+        self.enums.push(typing);
+        self
+    }
+
     /// Returns an iterator over all data types in the coda.
     ///
     /// The implicit [`crate::types::Unspecified`] data type
@@ -218,17 +372,247 @@ impl Coda {
         self.data.iter()
     }
 
-    /// Returns the data type with `name`,
+    /// Returns an iterator over all data types in the coda,
+    /// starting with the implicit [`crate::types::Unspecified`]
+    /// data type (at ordinal `0`), in ascending order by ordinal.
+    pub fn iter_all(&self) -> impl Iterator<Item = &DataType> {
+        static UNSPECIFIED: DataType = Unspecified::DATA_TYPE;
+        core::iter::once(&UNSPECIFIED).chain(self.data.iter())
+    }
+
+    /// Returns the number of data types in the coda,
+    /// including the implicit [`crate::types::Unspecified`]
+    /// data type (i.e., the number of types yielded by
+    /// [`Self::iter_all`]).
+    pub fn data_types_count_including_unspecified(&self) -> usize {
+        self.data.len() + 1
+    }
+
+    /// Returns every enum declared by the coda, followed
+    /// by every enum declared by the fields of the coda's
+    /// data types (including those within lists and maps),
+    /// in the order they're declared.
+    ///
+    /// Enums are compared by name, so each enum
+    /// is returned only once.
+    pub fn enums(&self) -> Vec<&EnumType> {
+        let mut enums: Vec<&EnumType> = self.enums.iter().collect();
+        for field in self.data.iter().flat_map(DataType::iter) {
+            field.typing.collect_enums(&mut enums);
+        }
+        enums
+    }
+
+    /// Returns the number of data types in the coda,
+    /// excluding the implicit [`crate::types::Unspecified`]
+    /// data type.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true iff the coda has no data types.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the data type named `name`, if any.
+    ///
+    /// Names are compared case-sensitively; refer to
+    /// [`Self::data_type_ignore_ascii_case`] for
+    /// matching names the way the parser does.
+    pub fn data_type(&self, name: &str) -> Option<&DataType> {
+        self.data.iter().find(|data| data.name == name)
+    }
+
+    /// Returns the data type named `name`, if
+    /// any, ignoring the ASCII case of names.
+    pub fn data_type_ignore_ascii_case(&self, name: &str) -> Option<&DataType> {
+        self.data
+            .iter()
+            .find(|data| data.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the data type with `ordinal`, if any.
+    pub fn data_type_by_ordinal(&self, ordinal: u8) -> Option<&DataType> {
+        self.data.iter().find(|data| data.ordinal() == ordinal)
+    }
+
+    /// Returns the data type or enum with `name`,
     /// if it is known by the coda.
     #[cfg(feature = "parse")]
     pub(crate) fn type_from_name(&self, name: &str) -> Option<Type> {
-        for data in self.data.iter() {
-            if data.name.eq_ignore_ascii_case(name) {
-                return Some(Type::Data(data.clone()));
+        if let Some(data) = self.data_type_ignore_ascii_case(name) {
+            return Some(Type::Data(data.clone()));
+        }
+
+        match self
+            .enums
+            .iter()
+            .find(|typing| typing.name.eq_ignore_ascii_case(name))
+        {
+            Some(typing) => Some(Type::Enum(typing.clone())),
+            None => Type::from_name(name),
+        }
+    }
+
+    /// Validates the structure of the coda, returning
+    /// every issue found (or an empty list if there
+    /// were none).
+    ///
+    /// Issues with [`ValidationSeverity::Error`] make the
+    /// coda unusable for code generation: duplicate type
+    /// or field names, and fields referring to data types
+    /// that don't exist.
+    ///
+    /// Issues with [`ValidationSeverity::Warning`] don't
+    /// prevent the coda from being used, but may not mean
+    /// what the author intended: fields referring to data
+    /// types declared _later_ in the coda (which are treated
+    /// as data with an unspecified format), and data types
+    /// with no fields.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+
+        for (i, data) in self.data.iter().enumerate() {
+            if self.data[..i]
+                .iter()
+                .any(|other| other.name.eq_ignore_ascii_case(&data.name))
+            {
+                issues.push(ValidationIssue::error(
+                    &data.name,
+                    None,
+                    format!("data type `{}` is declared more than once", data.name),
+                ));
+            }
+
+            if data.iter().next().is_none() {
+                issues.push(ValidationIssue::warning(
+                    &data.name,
+                    None,
+                    format!("data type `{}` has no fields", data.name),
+                ));
+            }
+
+            let fields: Vec<&DataField> = data.iter().collect();
+            for (j, field) in fields.iter().enumerate() {
+                if fields[..j]
+                    .iter()
+                    .any(|other| other.name.eq_ignore_ascii_case(&field.name))
+                {
+                    issues.push(ValidationIssue::error(
+                        &data.name,
+                        Some(&field.name),
+                        format!("field `{}` is declared more than once", field.name),
+                    ));
+                }
+
+                self.validate_type(&field.typing, data, field, &mut issues);
             }
         }
 
-        Type::from_name(name)
+        issues
+    }
+
+    /// Validates that every data type referred to by
+    /// `typing` (which is the type of `field` in `data`)
+    /// is known by the coda.
+    fn validate_type(
+        &self,
+        typing: &Type,
+        data: &DataType,
+        field: &DataField,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        match typing {
+            Type::Data(reference) if matches!(reference.format, Format::Fluid) => {
+                if self
+                    .data
+                    .iter()
+                    .any(|other| other.name.eq_ignore_ascii_case(&reference.name))
+                {
+                    issues.push(ValidationIssue::warning(
+                        &data.name,
+                        Some(&field.name),
+                        format!(
+                            "field `{}` refers to `{}` before it's declared, so it will be treated as unspecified data",
+                            field.name, reference.name
+                        ),
+                    ));
+                } else {
+                    issues.push(ValidationIssue::error(
+                        &data.name,
+                        Some(&field.name),
+                        format!(
+                            "field `{}` refers to unknown type `{}`",
+                            field.name, reference.name
+                        ),
+                    ));
+                }
+            }
+            Type::List(item) => self.validate_type(item, data, field, issues),
+            Type::Map(map) => {
+                self.validate_type(&map.0, data, field, issues);
+                self.validate_type(&map.1, data, field, issues);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Issue found by [`Coda::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// How severe the issue is.
+    pub severity: ValidationSeverity,
+
+    /// Name of the data type containing the issue.
+    pub data_type: Text,
+
+    /// Name of the field containing the issue,
+    /// if the issue is specific to one field.
+    pub field: Option<Text>,
+
+    /// Description of the issue.
+    pub message: Text,
+}
+
+impl ValidationIssue {
+    fn error(data_type: &Text, field: Option<&Text>, message: String) -> Self {
+        Self {
+            severity: ValidationSeverity::Error,
+            data_type: data_type.clone(),
+            field: field.cloned(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(data_type: &Text, field: Option<&Text>, message: String) -> Self {
+        Self {
+            severity: ValidationSeverity::Warning,
+            ..Self::error(data_type, field, message)
+        }
+    }
+}
+
+impl core::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Severity of a [`ValidationIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValidationSeverity {
+    Warning,
+    Error,
+}
+
+impl core::fmt::Display for ValidationSeverity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ValidationSeverity::Warning => write!(f, "warning"),
+            ValidationSeverity::Error => write!(f, "error"),
+        }
     }
 }
 
@@ -261,11 +645,32 @@ pub struct DataType {
 
     /// The encoding format of data with this type.
     format: Format,
+
+    /// Worked [`Example`]s declared in [`Self::docs`].
+    ///
+    /// Examples are derived entirely from the data
+    /// type's docs, and are not separately encoded.
+    pub examples: Vec<Example>,
+
+    /// True if the data type is deprecated: its docs
+    /// contain a `> Deprecated: reason` line.
+    ///
+    /// Like [`Self::examples`], deprecations are derived
+    /// entirely from the data type's docs.
+    pub deprecated: bool,
+
+    /// The reason the data type is deprecated, if any.
+    pub deprecation_note: Option<Text>,
 }
 
 impl DataType {
     /// Returns a new fixed data type with
     /// `name`, `ordinal`, `blob_fields`, and `data_fields`.
+    ///
+    /// # Panics
+    ///
+    /// Iff the fields' formats overflow the data type's
+    /// format (refer to [`Format::checked_with`]).
     pub fn new(
         name: Text,
         docs: Option<Text>,
@@ -276,21 +681,26 @@ impl DataType {
         // Build a new encoding format for the data.
         let mut format = Format::data(ordinal);
 
-        // Add blob fields to the format.
-        let mut i = 0;
-        while i < blob_fields.len() {
-            let field = &blob_fields[i];
-            format = format.with(field.typing.format());
-            i += 1;
+        // Add blob fields, followed by data fields, to the format.
+        for field in blob_fields.iter().chain(data_fields) {
+            format = match format.checked_with(field.typing.format()) {
+                Some(format) => format,
+                None => panic!(
+                    "{}",
+                    FieldOverflowSnafu {
+                        type_name: name.clone(),
+                        field_name: field.name.clone(),
+                    }
+                    .build()
+                ),
+            };
         }
 
-        // Add data fields to the format.
-        let mut i = 0;
-        while i < data_fields.len() {
-            let field = &data_fields[i];
-            format = format.with(field.typing.format());
-            i += 1;
-        }
+        let examples = match &docs {
+            Some(docs) => Example::from_docs(docs),
+            None => vec![],
+        };
+        let (deprecated, deprecation_note) = deprecation_from_docs(docs.as_deref());
 
         Self {
             name,
@@ -298,6 +708,9 @@ impl DataType {
             blob_fields: Vec::from(blob_fields),
             data_fields: Vec::from(data_fields),
             format,
+            examples,
+            deprecated,
+            deprecation_note,
         }
     }
 
@@ -309,6 +722,9 @@ impl DataType {
             blob_fields: vec![],
             data_fields: vec![],
             format: Format::Fluid,
+            examples: vec![],
+            deprecated: false,
+            deprecation_note: None,
         }
     }
 
@@ -317,39 +733,300 @@ impl DataType {
         self.blob_fields.iter().chain(self.data_fields.iter())
     }
 
+    /// Returns the field named `name`, if any.
+    ///
+    /// Names are compared case-sensitively; refer to
+    /// [`Self::field_ignore_ascii_case`] for matching
+    /// names the way the parser does.
+    pub fn field(&self, name: &str) -> Option<&DataField> {
+        self.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the field named `name`, if any,
+    /// ignoring the ASCII case of names.
+    pub fn field_ignore_ascii_case(&self, name: &str) -> Option<&DataField> {
+        self.iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the type's fields keyed by name, for
+    /// repeatedly looking up fields by name (as by
+    /// [`Self::field`]) without scanning every field.
+    pub fn fields_by_name(&self) -> BTreeMap<&str, &DataField> {
+        self.iter()
+            .map(|field| (field.name.as_ref(), field))
+            .collect()
+    }
+
+    /// Returns the 1-indexed position of the field
+    /// named `name` in the order fields are encoded
+    /// (refer to [`Self::iter`]), if any.
+    ///
+    /// Names are compared case-sensitively; refer to
+    /// [`Self::field_ordinal_ignore_ascii_case`] for
+    /// matching names the way the parser does.
+    pub fn field_ordinal(&self, name: &str) -> Option<usize> {
+        self.iter()
+            .position(|field| field.name == name)
+            .map(|position| position + 1)
+    }
+
+    /// Returns the 1-indexed position of the field
+    /// named `name`, if any, ignoring the ASCII case
+    /// of names; refer to [`Self::field_ordinal`].
+    pub fn field_ordinal_ignore_ascii_case(&self, name: &str) -> Option<usize> {
+        self.iter()
+            .position(|field| field.name.eq_ignore_ascii_case(name))
+            .map(|position| position + 1)
+    }
+
+    /// Returns the type's ordinal.
+    ///
+    /// Fluid data types have the ordinal of
+    /// [`crate::types::Unspecified`] data.
+    pub const fn ordinal(&self) -> u8 {
+        self.format.as_data_format().ordinal
+    }
+
     /// Adds a new `field` to the type.
-    pub fn with(mut self, field: DataField) -> Self {
+    ///
+    /// # Panics
+    ///
+    /// Iff the field can't be added to the
+    /// type; refer to [`Self::try_with`].
+    pub fn with(self, field: DataField) -> Self {
+        match self.try_with(field) {
+            Ok(data_type) => data_type,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Adds a new `field` to the type, returning:
+    ///
+    /// - [`TypeError::FluidType`] if the type is fluid.
+    /// - [`TypeError::DuplicateField`] if the type
+    ///   already has a field with the same name.
+    /// - [`TypeError::FieldOverflow`] if the type's format
+    ///   would contain more than [`u16::MAX`] bytes of blob
+    ///   fields or more than [`u8::MAX`] data fields.
+    pub fn try_with(mut self, field: DataField) -> Result<Self, TypeError> {
         if matches!(self.format, Format::Fluid) {
-            todo!("it should be an error to add fields to a type defined as fluid")
+            return FluidTypeSnafu {
+                type_name: self.name,
+                field_name: field.name,
+            }
+            .fail();
+        }
+
+        if self.iter().any(|other| other.name == field.name) {
+            return DuplicateFieldSnafu {
+                type_name: self.name,
+                field_name: field.name,
+            }
+            .fail();
         }
 
         let field_format = field.typing.format();
 
         // Optional fields are wrapped into a data field
         // so they get a header for presence signaling.
-        if field.optional {
-            let boxed_format = Format::data(0).with(field_format);
-            self.format = self.format.with(boxed_format);
-            self.data_fields.push(field);
+        let field_format = if field.optional {
+            Format::data(0).with(field_format)
         } else {
-            self.format = self.format.with(field_format);
-            match field_format {
-                Format::Blob(..) => {
-                    self.blob_fields.push(field);
-                }
-                Format::Data(..) | Format::Fluid => {
-                    self.data_fields.push(field);
-                }
-            };
-        }
+            field_format
+        };
+        let Some(format) = self.format.checked_with(field_format) else {
+            return FieldOverflowSnafu {
+                type_name: self.name,
+                field_name: field.name,
+            }
+            .fail();
+        };
+        self.format = format;
 
-        self
+        match field_format {
+            Format::Blob(..) => {
+                self.blob_fields.push(field);
+            }
+            Format::Data(..) | Format::Fluid => {
+                self.data_fields.push(field);
+            }
+        };
+
+        Ok(self)
     }
 
     /// Returns the type's encoding format.
     pub const fn format(&self) -> &Format {
         &self.format
     }
+
+    /// Returns the [`Compatibility`] of data with this
+    /// type with a decoder expecting data with `reader`.
+    ///
+    /// Fields are matched by position, since fields may
+    /// be renamed. Data with additional trailing blob or data
+    /// fields is tolerated; data with missing or mismatched
+    /// fields is incompatible.
+    pub fn compatible_with(&self, reader: &DataType) -> Compatibility {
+        let format_compatibility = self.format.compatible_with(&reader.format);
+        if !format_compatibility.is_compatible()
+            || matches!(self.format, Format::Fluid)
+            || matches!(reader.format, Format::Fluid)
+        {
+            return format_compatibility;
+        }
+
+        let mut compatibility = Compatibility::Identical;
+        for (kind, writer_fields, reader_fields) in [
+            ("blob", &self.blob_fields, &reader.blob_fields),
+            ("data", &self.data_fields, &reader.data_fields),
+        ] {
+            if let Some(missing) = reader_fields.get(writer_fields.len()) {
+                return Compatibility::incompatible(format!(
+                    "data is missing the {kind} field `{}`",
+                    missing.name
+                ));
+            }
+
+            if writer_fields.len() > reader_fields.len() {
+                compatibility = compatibility.and(Compatibility::ReaderTolerates);
+            }
+
+            for (writer_field, reader_field) in writer_fields.iter().zip(reader_fields.iter()) {
+                let field_compatibility = if writer_field.optional != reader_field.optional {
+                    Compatibility::incompatible("the field's optionality changed")
+                } else {
+                    writer_field.typing.compatible_with(&reader_field.typing)
+                };
+
+                compatibility = match compatibility.and(field_compatibility) {
+                    Compatibility::Incompatible { reason } => {
+                        return Compatibility::incompatible(format!(
+                            "{kind} field `{}` can't be decoded as `{}`: {reason}",
+                            writer_field.name, reader_field.name
+                        ));
+                    }
+                    compatibility => compatibility,
+                };
+            }
+        }
+
+        compatibility
+    }
+}
+
+/// A worked example of data with a [`DataType`].
+///
+/// Examples are declared in a data type's docs as a
+/// fenced `json example` code block, followed by a
+/// fenced `hex encoded` code block containing the
+/// expected encoding of the example's data:
+///
+/// ````markdown
+/// ```json example
+/// { "number": 1 }
+/// ```
+///
+/// ```hex encoded
+/// 01000000 0400 00 01 01000000
+/// ```
+/// ````
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Example {
+    /// JSON representation of the example's data.
+    pub json: Text,
+
+    /// Hexadecimal encoding of the example's data,
+    /// if an encoding was declared for the example.
+    ///
+    /// Any whitespace in the hexadecimal
+    /// text has already been removed.
+    pub hex: Option<Text>,
+}
+
+impl Example {
+    /// Returns all examples declared in `docs`.
+    pub fn from_docs(docs: &str) -> Vec<Example> {
+        let mut examples: Vec<Example> = vec![];
+
+        // The info string and contents of the
+        // fenced code block being scanned, if any.
+        let mut block: Option<(&str, String)> = None;
+
+        for line in docs.lines() {
+            let trimmed = line.trim();
+
+            let Some((info, contents)) = &mut block else {
+                // Scan for the start of a code block.
+                if let Some(info) = trimmed.strip_prefix("```") {
+                    block = Some((info.trim(), String::new()));
+                }
+                continue;
+            };
+
+            // Accumulate the code block's contents.
+            if trimmed != "```" {
+                contents.push_str(trimmed);
+                contents.push('\n');
+                continue;
+            }
+
+            // Close the code block.
+            let mut info = info.split_whitespace();
+            match (info.next(), info.next()) {
+                (Some("json"), Some("example")) => examples.push(Example {
+                    json: contents.trim().to_string().into(),
+                    hex: None,
+                }),
+
+                // Encodings are paired with the immediately
+                // preceding example, if it has no encoding.
+                (Some("hex"), Some("encoded")) => {
+                    if let Some(example @ Example { hex: None, .. }) = examples.last_mut() {
+                        let hex: String = contents.split_whitespace().collect();
+                        example.hex = Some(hex.into());
+                    }
+                }
+
+                _ => {}
+            }
+
+            block = None;
+        }
+
+        examples
+    }
+}
+
+/// Returns `(deprecated, deprecation_note)` for `docs`.
+///
+/// Docs are deprecated if any of their lines is a
+/// `> Deprecated: reason` quote, whose note is its `reason`
+/// (if not empty). The `: reason` is optional.
+pub(crate) fn deprecation_from_docs(docs: Option<&str>) -> (bool, Option<Text>) {
+    for line in docs.unwrap_or_default().lines() {
+        let Some(quote) = line.trim().strip_prefix('>') else {
+            continue;
+        };
+        let quote = quote.trim_start();
+        let Some(marker) = quote.get(.."deprecated".len()) else {
+            continue;
+        };
+        if !marker.eq_ignore_ascii_case("deprecated") {
+            continue;
+        }
+
+        let note = quote["deprecated".len()..].trim();
+        match note.strip_prefix(':') {
+            Some(note) if !note.trim().is_empty() => return (true, Some(note.trim().into())),
+            Some(_) => return (true, None),
+            None if note.is_empty() => return (true, None),
+            None => continue,
+        }
+    }
+
+    (false, None)
 }
 
 /// A field in a [`DataType`].
@@ -376,6 +1053,139 @@ pub struct DataField {
     /// compatibility between coda-defined data and
     /// legacy systems.
     pub flattened: bool,
+
+    /// Value the field is initialized to when
+    /// its data is constructed, if declared
+    /// (like ``+ `retries` u32 = 3``).
+    ///
+    /// Like [`Self::flattened`], this property has
+    /// _no_ effect on the encoding or decoding of a field.
+    pub default: Option<Unspecified>,
+
+    /// True if the field is deprecated: it's still encoded
+    /// and decoded, but should no longer be used.
+    ///
+    /// Fields are deprecated by a `deprecated` keyword
+    /// (like ``+ `old_id` deprecated u64``), or by a
+    /// `> Deprecated: reason` line in their docs.
+    pub deprecated: bool,
+
+    /// The reason the field is deprecated, if any.
+    ///
+    /// Deprecation notes are derived entirely from
+    /// the field's docs, and are not separately encoded.
+    pub deprecation_note: Option<Text>,
+}
+
+/// Enumeration of named variants, like
+/// the type of a [`DataField`] declared as
+/// ``+ `status` one of [Active, Suspended]``.
+///
+/// Values of an enum are encoded as the [`u16`]
+/// ordinal of their variant: the variant's
+/// position in [`Self::variants`], starting at `0`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct EnumType {
+    /// The name of the enum.
+    pub name: Text,
+
+    pub docs: Option<Text>,
+
+    /// Names of the enum's variants,
+    /// in ascending order by ordinal.
+    pub variants: Vec<Text>,
+
+    /// Docs of the enum's variants, in the same
+    /// order as [`Self::variants`]. Variants past
+    /// the end of this list have no docs.
+    pub variant_docs: Vec<Option<Text>>,
+}
+
+impl EnumType {
+    /// Returns a new enum with `name` and `variants`.
+    pub fn new(name: Text, variants: &[Text]) -> Self {
+        Self {
+            name,
+            docs: None,
+            variants: Vec::from(variants),
+            variant_docs: vec![],
+        }
+    }
+
+    /// Returns the name of the enum declared by the field
+    /// `field_name` in the data type `type_name`: the data
+    /// type's name, followed by the field's name in PascalCase
+    /// (like `UserAccountStatus` for `User.account_status`).
+    pub(crate) fn field_name(type_name: &str, field_name: &str) -> Text {
+        let mut name = String::from(type_name);
+        for word in field_name.split(['_', '-']) {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                name.extend(first.to_uppercase());
+                name.extend(chars);
+            }
+        }
+
+        name.into()
+    }
+
+    /// Returns the docs of the variant with `ordinal`, if any.
+    pub fn variant_docs(&self, ordinal: u16) -> Option<&Text> {
+        self.variant_docs.get(ordinal as usize)?.as_ref()
+    }
+
+    /// Returns the name of the variant with `ordinal`, if any.
+    pub fn variant(&self, ordinal: u16) -> Option<&Text> {
+        self.variants.get(ordinal as usize)
+    }
+
+    /// Returns the ordinal of the variant named `name`, if any.
+    pub fn ordinal_of(&self, name: &str) -> Option<u16> {
+        self.variants
+            .iter()
+            .position(|variant| variant == name)
+            .map(|ordinal| ordinal as u16)
+    }
+
+    /// Returns the [`Compatibility`] of values of this
+    /// enum with a decoder expecting values of `reader`.
+    ///
+    /// Variants are matched by ordinal, since variants
+    /// may be renamed. Readers may know of additional
+    /// trailing variants; values of enums with variants
+    /// unknown to the reader are incompatible.
+    pub fn compatible_with(&self, reader: &EnumType) -> Compatibility {
+        match self.variants.get(reader.variants.len()) {
+            Some(unknown) => Compatibility::incompatible(format!(
+                "the variant `{unknown}` of {} is unknown to {}",
+                self.name, reader.name
+            )),
+            None => Compatibility::Identical,
+        }
+    }
+}
+
+/// Error returned when a [`DataType`] can't be built.
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum TypeError {
+    /// Fields were added to a data type with a fluid
+    /// format, which is defined without any fields.
+    #[snafu(display("can't add `{field_name}` to the fluid data type `{type_name}`"))]
+    FluidType { type_name: Text, field_name: Text },
+
+    /// A field was added to a data type which
+    /// already has a field with the same name.
+    #[snafu(display("`{type_name}` already has a field named `{field_name}`"))]
+    DuplicateField { type_name: Text, field_name: Text },
+
+    /// A data type's format would contain more than [`u16::MAX`]
+    /// bytes of blob fields or more than [`u8::MAX`] data fields.
+    #[snafu(display(
+        "the format of `{type_name}` overflows when adding `{field_name}` (data may contain at most {} bytes of blob fields and {} data fields)",
+        u16::MAX,
+        u8::MAX
+    ))]
+    FieldOverflow { type_name: Text, field_name: Text },
 }
 
 /// A thing that _might_ contain data with a
@@ -413,6 +1223,7 @@ impl Encodable for Type {
     fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
         match self {
             Type::Data(typing) => writer.write_data(typing),
+            Type::Enum(typing) => writer.write_data(typing),
             Type::List(typing) => writer.write_data(typing.as_ref()),
             Type::Map(typing) => {
                 writer.write_data(&typing.as_ref().0)?;
@@ -420,7 +1231,8 @@ impl Encodable for Type {
                 Ok(())
             }
 
-            // Only data types contain additional encoded info.
+            // Only data types, enums, lists, and maps
+            // contain additional encoded info.
             _ => Ok(()),
         }
     }
@@ -434,7 +1246,7 @@ impl Encodable for Type {
                 .with(Type::FORMAT)
                 .with(Type::FORMAT)
                 .as_data_format(),
-            Type::Data(..) | Type::List(_) => Format::data(self.ordinal())
+            Type::Data(..) | Type::Enum(_) | Type::List(_) => Format::data(self.ordinal())
                 .with(Type::FORMAT)
                 .as_data_format(),
             _ => Format::data(self.ordinal()).as_data_format(),
@@ -454,6 +1266,7 @@ impl Decodable for Type {
             UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: None::<DataHeader>,
+                offset: None,
             }
             .build()
         })?;
@@ -463,6 +1276,7 @@ impl Decodable for Type {
             return UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: Some(header),
+                offset: None,
             }
             .fail();
         }
@@ -474,6 +1288,7 @@ impl Decodable for Type {
                     return UnexpectedDataFormatSnafu {
                         expected: Self::FORMAT,
                         actual: Some(header),
+                        offset: None,
                     }
                     .fail();
                 }
@@ -481,12 +1296,27 @@ impl Decodable for Type {
                 reader.read_data_into(&mut typing)?;
                 *self = Type::List(typing.into());
             }
+            Some(Type::Enum(_)) => {
+                // Enum: blob_size=0, data_fields=1 (inner EnumType).
+                if header.format.blob_size != 0 || header.format.data_fields != 1 {
+                    return UnexpectedDataFormatSnafu {
+                        expected: Self::FORMAT,
+                        actual: Some(header),
+                        offset: None,
+                    }
+                    .fail();
+                }
+                let mut typing = EnumType::default();
+                reader.read_data_into(&mut typing)?;
+                *self = Type::Enum(typing);
+            }
             Some(Type::Map(_)) => {
                 // Map: blob_size=0, data_fields=2 (key Type + value Type).
                 if header.format.blob_size != 0 || header.format.data_fields != 2 {
                     return UnexpectedDataFormatSnafu {
                         expected: Self::FORMAT,
                         actual: Some(header),
+                        offset: None,
                     }
                     .fail();
                 }
@@ -502,6 +1332,7 @@ impl Decodable for Type {
                     return UnexpectedDataFormatSnafu {
                         expected: Self::FORMAT,
                         actual: Some(header),
+                        offset: None,
                     }
                     .fail();
                 }
@@ -514,6 +1345,7 @@ impl Decodable for Type {
                     return UnexpectedDataFormatSnafu {
                         expected: Self::FORMAT,
                         actual: Some(header),
+                        offset: None,
                     }
                     .fail();
                 }
@@ -532,7 +1364,8 @@ impl Encodable for Coda {
         .with(Text::FORMAT)
         .with(Text::FORMAT)
         .with(Text::FORMAT)
-        .with(Vec::<DataType>::FORMAT);
+        .with(Vec::<DataType>::FORMAT)
+        .with(Vec::<EnumType>::FORMAT);
 
     fn encode(
         &self,
@@ -542,6 +1375,7 @@ impl Encodable for Coda {
         writer.write_data(&self.local_name)?;
         writer.write_data(&self.docs)?;
         writer.write_data(&self.data)?;
+        writer.write_data(&self.enums)?;
         Ok(())
     }
 }
@@ -558,6 +1392,7 @@ impl Decodable for Coda {
         reader.read_data_into(&mut self.local_name)?;
         reader.read_data_into(&mut self.docs)?;
         reader.read_data_into(&mut self.data)?;
+        reader.read_data_into(&mut self.enums)?;
 
         Ok(())
     }
@@ -595,24 +1430,34 @@ impl Decodable for DataType {
         reader.read_data_into(&mut self.data_fields)?;
         reader.read_data_into(&mut self.format)?;
 
+        self.examples = match &self.docs {
+            Some(docs) => Example::from_docs(docs),
+            None => vec![],
+        };
+        (self.deprecated, self.deprecation_note) = deprecation_from_docs(self.docs.as_deref());
+
         Ok(())
     }
 }
 
 impl Encodable for DataField {
     const FORMAT: Format = Format::data(0)
+        .with(bool::FORMAT)
         .with(bool::FORMAT)
         .with(bool::FORMAT)
         .with(Text::FORMAT)
         .with(Option::<Text>::FORMAT)
-        .with(Type::FORMAT);
+        .with(Type::FORMAT)
+        .with(Option::<Unspecified>::FORMAT);
 
     fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
         writer.write_data(&self.optional)?;
         writer.write_data(&self.flattened)?;
+        writer.write_data(&self.deprecated)?;
         writer.write_data(&self.name)?;
         writer.write_data(&self.docs)?;
         writer.write_data(&self.typing)?;
+        writer.write_data(&self.default)?;
         Ok(())
     }
 }
@@ -623,12 +1468,71 @@ impl Decodable for DataField {
         reader: &mut (impl ReadsDecodable + ?Sized),
         header: Option<DataHeader>,
     ) -> Result<(), CodecError> {
-        let _ = Self::ensure_header(header, &[0])?;
+        let header = Self::ensure_header(header, &[0])?;
         reader.read_data_into(&mut self.optional)?;
         reader.read_data_into(&mut self.flattened)?;
+
+        // Fields encoded before deprecations were
+        // introduced aren't deprecated.
+        self.deprecated = false;
+        if header.format.blob_size > 2 {
+            reader.read_data_into(&mut self.deprecated)?;
+        }
+        reader.skip_excess_blob(&header, Self::FORMAT)?;
+
         reader.read_data_into(&mut self.name)?;
         reader.read_data_into(&mut self.docs)?;
         reader.read_data_into(&mut self.typing)?;
+
+        // Fields encoded before defaults were
+        // introduced don't have a default.
+        self.default = None;
+        if header.format.data_fields > 3 {
+            reader.read_data_into(&mut self.default)?;
+        }
+
+        reader.skip_excess_data(&header, Self::FORMAT)?;
+
+        // Enum values don't encode their typing.
+        if let (Some(Unspecified::Enum { typing, .. }), Type::Enum(enum_type)) =
+            (&mut self.default, &self.typing)
+        {
+            *typing = enum_type.clone();
+        }
+
+        self.deprecation_note = deprecation_from_docs(self.docs.as_deref()).1;
+
+        Ok(())
+    }
+}
+
+impl Encodable for EnumType {
+    const FORMAT: Format = Format::data(0)
+        .with(Text::FORMAT)
+        .with(Vec::<Text>::FORMAT)
+        .with(Option::<Text>::FORMAT)
+        .with(Vec::<Option<Text>>::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        writer.write_data(&self.name)?;
+        writer.write_data(&self.variants)?;
+        writer.write_data(&self.docs)?;
+        writer.write_data(&self.variant_docs)?;
+        Ok(())
+    }
+}
+
+impl Decodable for EnumType {
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let _ = Self::ensure_header(header, &[0])?;
+        reader.read_data_into(&mut self.name)?;
+        reader.read_data_into(&mut self.variants)?;
+        reader.read_data_into(&mut self.docs)?;
+        reader.read_data_into(&mut self.variant_docs)?;
         Ok(())
     }
 }
@@ -662,6 +1566,10 @@ where
     }
 }
 
+/// Options are decoded as `None` _only_ when they were
+/// encoded as `None`: `Some` value is never collapsed
+/// into `None` by its value, so `Some(f32::NAN)` decodes
+/// to `Some` NaN (with identical bits).
 impl<T> Decodable for Option<T>
 where
     T: Decodable + Default + 'static,
@@ -675,6 +1583,7 @@ where
             UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: None::<DataHeader>,
+                offset: None,
             }
             .build()
         })?;
@@ -684,6 +1593,7 @@ where
             return UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: Some(h),
+                offset: None,
             }
             .fail();
         }
@@ -694,6 +1604,7 @@ where
                 return UnexpectedDataFormatSnafu {
                     expected: Self::FORMAT,
                     actual: Some(h),
+                    offset: None,
                 }
                 .fail();
             }
@@ -704,6 +1615,7 @@ where
                 return UnexpectedDataFormatSnafu {
                     expected: Self::FORMAT,
                     actual: Some(h),
+                    offset: None,
                 }
                 .fail();
             }
@@ -717,10 +1629,13 @@ where
 }
 
 #[cfg(test)]
-mod tests {
-    use crate::codec::{Decodable, WritesEncodable};
+pub(crate) mod tests {
+    use crate::codec::{decode_from_slice, encode_to_vec, Decodable, WritesEncodable};
 
-    use super::*;
+    use super::{
+        dynamic::{DynamicValue, TryFromDynamicError},
+        *,
+    };
 
     /// Sample data structure for testing type manipulation APIs.
     #[derive(Clone, Debug, Default, PartialEq)]
@@ -742,6 +1657,9 @@ mod tests {
                     typing: Type::I32,
                     optional: false,
                     flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
                 },
                 DataField {
                     name: Text::from("floaty"),
@@ -749,6 +1667,9 @@ mod tests {
                     typing: Type::F64,
                     optional: false,
                     flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
                 },
             ];
 
@@ -759,6 +1680,9 @@ mod tests {
                     typing: Type::List(Type::Text.into()),
                     optional: false,
                     flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
                 },
                 DataField {
                     name: Text::from("text"),
@@ -766,6 +1690,9 @@ mod tests {
                     typing: Type::Text,
                     optional: false,
                     flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
                 },
                 DataField {
                     name: Text::from("nested"),
@@ -773,6 +1700,9 @@ mod tests {
                     typing: Type::Data(NestedTestData::typing()),
                     optional: false,
                     flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
                 },
                 DataField {
                     name: Text::from("two_d"),
@@ -780,6 +1710,9 @@ mod tests {
                     typing: Type::List(Type::List(Type::Text.into()).into()),
                     optional: false,
                     flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
                 },
             ];
 
@@ -845,6 +1778,9 @@ mod tests {
                 typing: Type::Bool,
                 optional: false,
                 flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
             }];
 
             let data_fields = vec![];
@@ -886,13 +1822,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn codes_optional_nans() {
+        let value = Some(f32::NAN);
+        let encoded = encode_to_vec(&value).unwrap();
+        let decoded: Option<f32> = decode_from_slice(&encoded).unwrap();
+        assert_eq!(Some(f32::NAN.to_bits()), decoded.map(f32::to_bits));
+
+        let value = Some(-f64::NAN);
+        let encoded = encode_to_vec(&value).unwrap();
+        let decoded: Option<f64> = decode_from_slice(&encoded).unwrap();
+        assert_eq!(Some((-f64::NAN).to_bits()), decoded.map(f64::to_bits));
+
+        let encoded = encode_to_vec(&None::<f64>).unwrap();
+        let decoded: Option<f64> = decode_from_slice(&encoded).unwrap();
+        assert!(decoded.is_none());
+    }
+
+    #[test]
+    fn checks_data_type_compatibility() {
+        let field = |name: &str, typing: Type| DataField {
+            name: name.to_string().into(),
+            docs: None,
+            typing,
+            optional: false,
+            flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
+        };
+        let data_type = DataType::new("Data".into(), None, 1, &[], &[])
+            .with(field("number", Type::I32))
+            .with(field("text", Type::Text))
+            .with(field("nested", Type::Data(NestedTestData::typing())));
+        assert_eq!(
+            Compatibility::Identical,
+            data_type.compatible_with(&data_type)
+        );
+
+        // Renamed fields are identical.
+        let renamed = DataType::new("Renamed".into(), None, 1, &[], &[])
+            .with(field("count", Type::I32))
+            .with(field("label", Type::Text))
+            .with(field("inner", Type::Data(NestedTestData::typing())));
+        assert_eq!(
+            Compatibility::Identical,
+            renamed.compatible_with(&data_type)
+        );
+
+        // Added fields, including within nested
+        // data, are tolerated.
+        let added = data_type
+            .clone()
+            .with(field("flag", Type::Bool))
+            .with(field("list", Type::List(Type::Text.into())));
+        assert_eq!(
+            Compatibility::ReaderTolerates,
+            added.compatible_with(&data_type)
+        );
+        let nested_added = DataType::new("Data".into(), None, 1, &[], &[])
+            .with(field("number", Type::I32))
+            .with(field("text", Type::Text))
+            .with(field(
+                "nested",
+                Type::Data(NestedTestData::typing().with(field("extra", Type::U8))),
+            ));
+        assert_eq!(
+            Compatibility::ReaderTolerates,
+            nested_added.compatible_with(&data_type)
+        );
+
+        // Removed fields aren't.
+        let removed = DataType::new("Data".into(), None, 1, &[], &[])
+            .with(field("number", Type::I32))
+            .with(field("nested", Type::Data(NestedTestData::typing())));
+        assert!(matches!(
+            removed.compatible_with(&data_type),
+            Compatibility::Incompatible { reason } if reason.contains("missing")
+        ));
+        assert!(!data_type.compatible_with(&added).is_compatible());
+
+        // Neither are reordered fields.
+        let reordered = DataType::new("Data".into(), None, 1, &[], &[])
+            .with(field("number", Type::I32))
+            .with(field("nested", Type::Data(NestedTestData::typing())))
+            .with(field("text", Type::Text));
+        assert!(matches!(
+            reordered.compatible_with(&data_type),
+            Compatibility::Incompatible { reason } if reason.starts_with("data field `nested` can't be decoded as `text`")
+        ));
+
+        // Nor are changed ordinals.
+        let reordinaled = DataType::new("Data".into(), None, 2, &[], &[])
+            .with(field("number", Type::I32))
+            .with(field("text", Type::Text))
+            .with(field("nested", Type::Data(NestedTestData::typing())));
+        assert!(!reordinaled.compatible_with(&data_type).is_compatible());
+    }
+
     #[test]
     pub fn data_type_codec() {
         let data_type = TestData::typing();
 
-        let mut encoded_data_type = vec![];
-        encoded_data_type.write_data(&data_type).unwrap();
-        let decoded_data_type = encoded_data_type.as_slice().read_data().unwrap();
+        let encoded_data_type = encode_to_vec(&data_type).unwrap();
+        let decoded_data_type = decode_from_slice(&encoded_data_type).unwrap();
 
         assert_eq!(data_type, decoded_data_type);
     }
@@ -903,21 +1936,21 @@ mod tests {
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
         println!("encoded");
-        let decoded_option = data.as_slice().read_data().expect("decoded");
+        let decoded_option = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded_option);
 
         // Do None values decode as None?
         let option: Option<u32> = None;
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded_option = data.as_slice().read_data().expect("decoded");
+        let decoded_option = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded_option);
 
         // Default values round-trip as Some(default).
         let option: Option<u32> = Some(0);
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded_option: Option<u32> = data.as_slice().read_data().expect("decoded");
+        let decoded_option: Option<u32> = decode_from_slice(&data).expect("decoded");
         assert_eq!(Some(0), decoded_option);
     }
 
@@ -927,21 +1960,21 @@ mod tests {
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
         println!("encoded");
-        let decoded_option = data.as_slice().read_data().expect("decoded");
+        let decoded_option = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded_option);
 
         // Do None values decode as None?
         let option: Option<Text> = None;
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded_option = data.as_slice().read_data().expect("decoded");
+        let decoded_option = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded_option);
 
         // Default values round-trip as Some(default).
         let option: Option<Text> = Some("".into());
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded_option: Option<Text> = data.as_slice().read_data().expect("decoded");
+        let decoded_option: Option<Text> = decode_from_slice(&data).expect("decoded");
         assert_eq!(Some("".into()), decoded_option);
     }
 
@@ -951,28 +1984,28 @@ mod tests {
         let option: Option<Option<u32>> = None;
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Option<u32>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Option<u32>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
 
         // Some(None)
         let option: Option<Option<u32>> = Some(None);
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Option<u32>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Option<u32>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
 
         // Some(Some(0)) — the previously unrepresentable case
         let option: Option<Option<u32>> = Some(Some(0));
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Option<u32>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Option<u32>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
 
         // Some(Some(42))
         let option: Option<Option<u32>> = Some(Some(42));
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Option<u32>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Option<u32>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
     }
 
@@ -982,24 +2015,356 @@ mod tests {
         let option: Option<Vec<u16>> = None;
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Vec<u16>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Vec<u16>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
 
         // Some(vec![])
         let option: Option<Vec<u16>> = Some(vec![]);
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Vec<u16>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Vec<u16>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
 
         // Some(vec![42])
         let option: Option<Vec<u16>> = Some(vec![42]);
         let mut data = vec![];
         data.write_data(&option).expect("encoded");
-        let decoded: Option<Vec<u16>> = data.as_slice().read_data().expect("decoded");
+        let decoded: Option<Vec<u16>> = decode_from_slice(&data).expect("decoded");
         assert_eq!(option, decoded);
     }
 
+    #[test]
+    fn codes_128_bit_types() {
+        for typing in [Type::U128, Type::I128] {
+            let mut data = vec![];
+            data.write_data(&typing).expect("encoded");
+            let decoded: Type = decode_from_slice(&data).expect("decoded");
+            assert_eq!(typing, decoded);
+        }
+
+        assert_eq!(Some(Type::U128), Type::from_name("u128"));
+        assert_eq!(Some(Type::I128), Type::from_name("i128"));
+    }
+
+    #[test]
+    fn codes_bytes() {
+        let typing = Type::Bytes;
+        let mut data = vec![];
+        data.write_data(&typing).expect("encoded");
+        let decoded: Type = decode_from_slice(&data).expect("decoded");
+        assert_eq!(typing, decoded);
+        assert_eq!(Some(Type::Bytes), Type::from_name("bytes"));
+
+        // Bytes are encoded like text, without UTF-8 validation.
+        assert_eq!(Type::Text.format(), Type::Bytes.format());
+        let bytes: Vec<u8> = vec![0xFF, 0x00, 0xC3];
+        let mut encoded = vec![];
+        encoded.write_data(&bytes).expect("encoded");
+        let mut text = vec![];
+        text.write_data(&Text::from("abc")).expect("encoded");
+        assert_eq!(text[..8], encoded[..8]);
+        assert_eq!(&[0xFF, 0x00, 0xC3], &encoded[8..]);
+        let decoded: Vec<u8> = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(bytes, decoded);
+
+        // Bytes share an encoding with lists of `u8`s.
+        let list = Type::List(Type::U8.into());
+        assert_eq!(list.format(), Type::Bytes.format());
+        assert_eq!(Compatibility::Identical, list.compatible_with(&Type::Bytes));
+        assert_eq!(Compatibility::Identical, Type::Bytes.compatible_with(&list));
+        assert!(!Type::Bytes.compatible_with(&Type::Text).is_compatible());
+    }
+
+    #[test]
+    fn codes_timestamp() {
+        let typing = Type::Timestamp;
+        let mut data = vec![];
+        data.write_data(&typing).expect("encoded");
+        let decoded: Type = decode_from_slice(&data).expect("decoded");
+        assert_eq!(typing, decoded);
+        assert_eq!(Some(Type::Timestamp), Type::from_name("timestamp"));
+
+        // Timestamps share an encoding with `i64`s.
+        assert_eq!(Type::I64.format(), Type::Timestamp.format());
+        assert_eq!(
+            Compatibility::Identical,
+            Type::I64.compatible_with(&Type::Timestamp)
+        );
+        assert_eq!(
+            Compatibility::Identical,
+            Type::Timestamp.compatible_with(&Type::I64)
+        );
+        assert!(!Type::Timestamp.compatible_with(&Type::U64).is_compatible());
+    }
+
+    #[test]
+    fn codes_enums() {
+        let status = EnumType::new(
+            "Status".into(),
+            &["Active".into(), "Suspended".into(), "Deleted".into()],
+        );
+        let typing = Type::Enum(status.clone());
+        let mut data = vec![];
+        data.write_data(&typing).expect("encoded");
+        let decoded: Type = decode_from_slice(&data).expect("decoded");
+        assert_eq!(typing, decoded);
+        assert_eq!(Some(&Text::from("Suspended")), status.variant(1));
+        assert_eq!(None, status.variant(3));
+        assert_eq!(Some(2), status.ordinal_of("Deleted"));
+
+        // Enums share an encoding with `u16`s,
+        // but not every `u16` is a known variant.
+        assert_eq!(Type::U16.format(), typing.format());
+        assert_eq!(Compatibility::Identical, typing.compatible_with(&Type::U16));
+        assert!(!Type::U16.compatible_with(&typing).is_compatible());
+
+        // Readers may know of more variants than writers...
+        let fewer = Type::Enum(EnumType::new(
+            "OldStatus".into(),
+            &["Active".into(), "Suspended".into()],
+        ));
+        assert_eq!(Compatibility::Identical, fewer.compatible_with(&typing));
+
+        // ...but not fewer.
+        assert!(matches!(
+            typing.compatible_with(&fewer),
+            Compatibility::Incompatible { reason } if reason.contains("`Deleted`")
+        ));
+    }
+
+    #[test]
+    fn codes_field_defaults() {
+        let status = EnumType::new("Status".into(), &["Active".into(), "Deleted".into()]);
+        for (typing, default) in [
+            (Type::U32, Some(Unspecified::U32(3))),
+            (Type::Text, Some(Unspecified::Text("retry".into()))),
+            (
+                Type::Enum(status.clone()),
+                Some(Unspecified::Enum {
+                    typing: status,
+                    variant: 1,
+                }),
+            ),
+            (Type::Bool, None),
+        ] {
+            let field = DataField {
+                name: "field".into(),
+                docs: None,
+                typing,
+                optional: false,
+                flattened: false,
+                default,
+                deprecated: false,
+                deprecation_note: None,
+            };
+            let mut data = vec![];
+            data.write_data(&field).expect("encoded");
+            let decoded: DataField = decode_from_slice(&data).expect("decoded");
+            assert_eq!(field, decoded);
+        }
+
+        // Fields encoded without defaults have no default.
+        let mut data = vec![];
+        DataHeader {
+            count: 1,
+            format: Format::data(0)
+                .with(bool::FORMAT)
+                .with(bool::FORMAT)
+                .with(Text::FORMAT)
+                .with(Option::<Text>::FORMAT)
+                .with(Type::FORMAT)
+                .as_data_format(),
+        }
+        .encode(&mut data)
+        .expect("encoded");
+        data.write_data(&true).expect("encoded");
+        data.write_data(&false).expect("encoded");
+        data.write_data(&Text::from("field")).expect("encoded");
+        data.write_data(&None::<Text>).expect("encoded");
+        data.write_data(&Type::U32).expect("encoded");
+        let decoded: DataField = decode_from_slice(&data).expect("decoded");
+        assert_eq!("field", decoded.name);
+        assert!(decoded.optional);
+        assert_eq!(None, decoded.default);
+        assert!(!decoded.deprecated);
+    }
+
+    #[test]
+    fn codes_field_deprecations() {
+        let field = DataField {
+            name: "field".into(),
+            docs: Some("Old.\n\n> Deprecated: Use `other`.".into()),
+            typing: Type::U32,
+            optional: false,
+            flattened: false,
+            default: None,
+            deprecated: true,
+            deprecation_note: Some("Use `other`.".into()),
+        };
+        let mut data = vec![];
+        data.write_data(&field).expect("encoded");
+        let decoded: DataField = decode_from_slice(&data).expect("decoded");
+        assert_eq!(field, decoded);
+
+        // Deprecation notes are derived from docs.
+        assert_eq!(
+            (true, Some(Text::from("Use `other`."))),
+            deprecation_from_docs(field.docs.as_deref())
+        );
+        assert_eq!((true, None), deprecation_from_docs(Some("> DEPRECATED")));
+        assert_eq!((true, None), deprecation_from_docs(Some("> Deprecated:  ")));
+        assert_eq!(
+            (false, None),
+            deprecation_from_docs(Some("> Deprecatedness is fine."))
+        );
+        assert_eq!((false, None), deprecation_from_docs(Some("Deprecated: no")));
+        assert_eq!((false, None), deprecation_from_docs(None));
+    }
+
+    #[test]
+    fn rejects_oversized_data_types() {
+        let mut data_type = DataType::new(Text::from("Big"), None, 1, &[], &[]);
+        for i in 0..8191 {
+            data_type = data_type
+                .try_with(DataField {
+                    name: alloc::format!("field_{i}").into(),
+                    docs: None,
+                    typing: Type::U64,
+                    optional: false,
+                    flattened: false,
+                    default: None,
+                    deprecated: false,
+                    deprecation_note: None,
+                })
+                .expect("field fits");
+        }
+        assert_eq!(65528, data_type.format().as_data_format().blob_size);
+
+        let error = data_type
+            .try_with(DataField {
+                name: Text::from("field_8191"),
+                docs: None,
+                typing: Type::U64,
+                optional: false,
+                flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
+            })
+            .unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                TypeError::FieldOverflow { type_name, field_name }
+                    if type_name == "Big" && field_name == "field_8191"
+            ),
+            "{error}"
+        );
+    }
+
+    #[cfg(feature = "parse")]
+    #[test]
+    fn looks_up_types_and_fields() {
+        let coda = crate::parse::parse(crate::parse::tests::TEST_CODA_MARKDOWN).unwrap();
+        assert_eq!(2, coda.len());
+        assert!(!coda.is_empty());
+
+        let data = coda.data_type("MyDataType").unwrap();
+        assert_eq!(2, data.ordinal());
+        assert_eq!(Some(data), coda.data_type_by_ordinal(2));
+        assert_eq!(None, coda.data_type_by_ordinal(3));
+        assert_eq!(None, coda.data_type("mydatatype"));
+        assert_eq!(Some(data), coda.data_type_ignore_ascii_case("mydatatype"));
+
+        // Fields are numbered in encoding order, with blob
+        // fields (like `integral_field`) before data fields.
+        assert_eq!(Type::I32, data.field("integral_field").unwrap().typing);
+        assert_eq!(Some(1), data.field_ordinal("integral_field"));
+        assert_eq!(Some(2), data.field_ordinal("textual_field"));
+        assert_eq!(None, data.field("Textual_Field"));
+        assert_eq!(None, data.field_ordinal("Textual_Field"));
+        assert_eq!(
+            data.field("textual_field"),
+            data.field_ignore_ascii_case("Textual_Field")
+        );
+        assert_eq!(
+            Some(2),
+            data.field_ordinal_ignore_ascii_case("Textual_Field")
+        );
+
+        let fields = data.fields_by_name();
+        assert_eq!(data.iter().count(), fields.len());
+        for field in data.iter() {
+            assert_eq!(Some(&field), fields.get(&*field.name));
+        }
+        assert_eq!(None, fields.get("Textual_Field"));
+    }
+
+    #[test]
+    fn rejects_fields_on_fluid_types() {
+        let field = |name: &str, typing: Type| DataField {
+            name: name.to_string().into(),
+            docs: None,
+            typing,
+            optional: false,
+            flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
+        };
+        let error = DataType::new_fluid(Text::from("Fluid"), None)
+            .try_with(field("number", Type::I32))
+            .unwrap_err();
+        assert_eq!(
+            TypeError::FluidType {
+                type_name: "Fluid".into(),
+                field_name: "number".into(),
+            },
+            error
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_fields() {
+        let field = |name: &str, typing: Type| DataField {
+            name: name.to_string().into(),
+            docs: None,
+            typing,
+            optional: false,
+            flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
+        };
+        let data_type = DataType::new(Text::from("Data"), None, 1, &[], &[])
+            .with(field("number", Type::I32))
+            .with(field("text", Type::Text));
+
+        // Fields are unique across blob and data fields.
+        for typing in [Type::I32, Type::Text] {
+            let error = data_type
+                .clone()
+                .try_with(field("number", typing))
+                .unwrap_err();
+            assert_eq!(
+                TypeError::DuplicateField {
+                    type_name: "Data".into(),
+                    field_name: "number".into(),
+                },
+                error
+            );
+        }
+    }
+
+    /// Verifies that the formats of variable-length
+    /// integers are tagged with their type's ordinal.
+    #[test]
+    fn var_int_formats_match_ordinals() {
+        for typing in [Type::UVar, Type::IVar] {
+            assert_eq!(typing.ordinal(), typing.format().as_data_format().ordinal);
+        }
+    }
+
     /// Verifies that `ordinal()` and `from_ordinal()` are consistent:
     /// for every ordinal 0–255, if `from_ordinal` returns `Some(t)`,
     /// then `t.ordinal()` equals the original ordinal.
@@ -1016,4 +2381,262 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn iterates_all_data_types() {
+        let data = [
+            DataType::new(Text::from("First"), None, 1, &[], &[]),
+            DataType::new(Text::from("Second"), None, 2, &[], &[]),
+        ];
+        let coda = Coda::new(Text::from("Test"), Text::from("Test"), None, &data);
+
+        let all: Vec<_> = coda.iter_all().collect();
+        assert_eq!(3, all.len());
+        assert_eq!(all.len(), coda.data_types_count_including_unspecified());
+        assert_eq!(&Unspecified::DATA_TYPE, all[0]);
+        for (ordinal, data_type) in all.iter().enumerate() {
+            assert_eq!(ordinal as u8, data_type.format().as_data_format().ordinal);
+        }
+
+        // `iter` still skips `Unspecified`.
+        assert_eq!(all[1..], coda.iter().collect::<Vec<_>>()[..]);
+
+        let empty = Coda::default();
+        assert_eq!(1, empty.iter_all().count());
+        assert_eq!(1, empty.data_types_count_including_unspecified());
+    }
+
+    #[test]
+    fn validates_codas() {
+        let field = |name: &'static str, typing: Type| DataField {
+            name: Text::from(name),
+            typing,
+            ..Default::default()
+        };
+        let fluid = |name: &'static str| Type::Data(DataType::new_fluid(Text::from(name), None));
+
+        let first = DataType::new(
+            Text::from("First"),
+            None,
+            1,
+            &[field("number", Type::U32), field("Number", Type::U8)],
+            &[
+                field("later", fluid("Second")),
+                field("missing", Type::List(fluid("Missing").into())),
+            ],
+        );
+        let second = DataType::new(Text::from("Second"), None, 2, &[], &[]);
+        let coda = Coda::new(
+            Text::from("Test"),
+            Text::from("Test"),
+            None,
+            &[first, second.clone(), second],
+        );
+
+        let issues: Vec<_> = coda
+            .validate()
+            .into_iter()
+            .map(|issue| {
+                (
+                    issue.severity,
+                    issue.data_type.to_string(),
+                    issue.field.map(|field| field.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    ValidationSeverity::Error,
+                    "First".to_string(),
+                    Some("Number".to_string())
+                ),
+                (
+                    ValidationSeverity::Warning,
+                    "First".to_string(),
+                    Some("later".to_string())
+                ),
+                (
+                    ValidationSeverity::Error,
+                    "First".to_string(),
+                    Some("missing".to_string())
+                ),
+                (ValidationSeverity::Warning, "Second".to_string(), None),
+                (ValidationSeverity::Error, "Second".to_string(), None),
+                (ValidationSeverity::Warning, "Second".to_string(), None),
+            ],
+            issues
+        );
+
+        let valid = Coda::new(
+            Text::from("Test"),
+            Text::from("Test"),
+            None,
+            &[DataType::new(
+                Text::from("Valid"),
+                None,
+                1,
+                &[field("number", Type::U32)],
+                &[],
+            )],
+        );
+        assert!(valid.validate().is_empty());
+    }
+
+    impl DynamicValue for TestData {
+        fn to_dynamic(&self) -> Unspecified {
+            Unspecified::Map(
+                [
+                    ("number", self.number.to_dynamic()),
+                    ("floaty", self.floaty.to_dynamic()),
+                    ("text_list", self.text_list.to_dynamic()),
+                    ("text", self.text.to_dynamic()),
+                    ("nested", self.nested.to_dynamic()),
+                    ("two_d", self.two_d.to_dynamic()),
+                ]
+                .into_iter()
+                .map(|(name, value)| (Text::from(name), value))
+                .collect(),
+            )
+        }
+
+        fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+            let fields = dynamic::dynamic_fields(value)?;
+            Ok(Self {
+                number: dynamic::required_field(fields, "number")?,
+                floaty: dynamic::required_field(fields, "floaty")?,
+                text_list: dynamic::required_field(fields, "text_list")?,
+                text: dynamic::required_field(fields, "text")?,
+                nested: dynamic::required_field(fields, "nested")?,
+                two_d: dynamic::required_field(fields, "two_d")?,
+            })
+        }
+    }
+
+    impl DynamicValue for NestedTestData {
+        fn to_dynamic(&self) -> Unspecified {
+            Unspecified::Map(
+                [(Text::from("boolean"), self.boolean.to_dynamic())]
+                    .into_iter()
+                    .collect(),
+            )
+        }
+
+        fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+            let fields = dynamic::dynamic_fields(value)?;
+            Ok(Self {
+                boolean: dynamic::required_field(fields, "boolean")?,
+            })
+        }
+    }
+
+    #[test]
+    fn accesses_dynamic_paths() {
+        let data = TestData {
+            number: -7,
+            floaty: 1.5,
+            text_list: vec!["one".into(), "two".into()],
+            text: "text".into(),
+            nested: NestedTestData { boolean: true },
+            two_d: vec![vec!["a".into()], vec!["b".into(), "c".into()]],
+        };
+        let mut dynamic = data.to_dynamic();
+
+        assert_eq!(
+            Some(-7),
+            dynamic.get("number").and_then(Unspecified::as_i64)
+        );
+        assert_eq!(
+            Some(1.5),
+            dynamic.get("floaty").and_then(Unspecified::as_f64)
+        );
+        assert_eq!(
+            Some("text"),
+            dynamic.get("text").and_then(Unspecified::as_text)
+        );
+        assert_eq!(
+            Some(2),
+            dynamic
+                .get("text_list")
+                .and_then(Unspecified::as_list)
+                .map(<[_]>::len)
+        );
+        assert_eq!(
+            Some(true),
+            dynamic
+                .get_path("nested.boolean")
+                .and_then(Unspecified::as_bool)
+        );
+        assert_eq!(
+            Some("b"),
+            dynamic
+                .get_path("two_d[1][0]")
+                .and_then(Unspecified::as_text)
+        );
+        assert_eq!(None, dynamic.get("missing"));
+        assert_eq!(None, dynamic.get_path("two_d[2]"));
+        assert_eq!(None, dynamic.get_path("two_d[x]"));
+
+        // Set existing values.
+        dynamic
+            .set_path("nested.boolean", Unspecified::Bool(false))
+            .unwrap();
+        dynamic
+            .set_path("two_d[0][0]", Unspecified::Text("z".into()))
+            .unwrap();
+        let changed = TestData::try_from_dynamic(&dynamic).unwrap();
+        assert!(!changed.nested.boolean);
+        assert_eq!(vec![Text::from("z")], changed.two_d[0]);
+
+        // Create intermediate values.
+        let mut dynamic = Unspecified::Default;
+        dynamic.set_path("a.b[2]", Unspecified::U8(1)).unwrap();
+        assert_eq!(
+            Some(1),
+            dynamic.get_path("a.b[2]").and_then(Unspecified::as_i64)
+        );
+        assert_eq!(Some(&Unspecified::Default), dynamic.get_path("a.b[0]"));
+
+        assert_eq!(
+            Err(TryFromDynamicError::UnexpectedType {
+                expected: "data",
+                actual: Type::U8.into(),
+            }),
+            dynamic.set_path("a.b[2].c", Unspecified::Default)
+        );
+        assert_eq!(
+            Err(TryFromDynamicError::InvalidPath {
+                path: "a..b".into()
+            }),
+            dynamic.set_path("a..b", Unspecified::Default)
+        );
+    }
+
+    /// Verifies that typed data converts to and from
+    /// dynamic data matching its data type, without
+    /// an intermediate encoding.
+    #[test]
+    fn converts_typed_data_to_and_from_dynamic() {
+        let data = TestData {
+            number: 1,
+            floaty: 60.90,
+            text_list: vec!["one".into(), "two".into()],
+            text: "hello".into(),
+            nested: NestedTestData { boolean: true },
+            two_d: vec![vec!["three".into()], vec!["four".into(), "five".into()]],
+        };
+
+        let dynamic = data.to_dynamic();
+        dynamic
+            .verify_type(&Type::Data(TestData::typing()))
+            .unwrap();
+        assert_eq!(data, TestData::try_from_dynamic(&dynamic).unwrap());
+
+        // Fields are converted directly, not as opaque data.
+        assert_eq!(
+            Some(&NestedTestData { boolean: true }.to_dynamic()),
+            dynamic.get("nested")
+        );
+        assert!(matches!(dynamic.get("nested"), Some(Unspecified::Map(_))));
+    }
 }
@@ -7,73 +7,39 @@
 //!
 //! - Hexadecimal (4 bits per character).
 //! - Crockford Base32 (5 bits per character).
+//! - Unpadded Base64 (6 bits per character).
 //!
 //! In general, Hexadecimal is preferable for
 //! debugging (it's a _bit_ more human readable),
 //! while Base32 is preferable for network
 //! transmissions (it's a _bit_ more efficient).
 //!
-//! > _Note_: Base32 is used over Base64 due to
+//! > _Note_: Base32 is preferred over Base64 due to
 //! > it's simpler padding-free codec, at the
 //! > cost of reduced efficiency (Base64 encodes
-//! > 6 bits per character).
+//! > 6 bits per character). Base64 is provided
+//! > for compatibility with web and crypto APIs.
 use core::fmt::{Debug, Formatter, Write};
 
 use snafu::Snafu;
 
-use crate::{
-    codec::{
-        CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
-        UnexpectedDataFormatSnafu, WritesEncodable,
-    },
-    types::Text,
-};
-
-// Fixed-size `[u8; SIZE]` codec.
-impl<const SIZE: usize> Encodable for [u8; SIZE] {
-    /// Encoded as a [`Format::Data`] containing a
-    /// [`Format::Blob(SIZE)`](Format::Blob).
-    const FORMAT: Format = {
-        assert!(
-            SIZE <= u16::MAX as usize,
-            "SIZE exceeds maximum blob size (u16::MAX)"
-        );
-        Format::data(0).with(Format::Blob(SIZE as u16))
-    };
-
-    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
-        writer.write_all(self)?;
-        Ok(())
-    }
-}
-
-impl<const SIZE: usize> Decodable for [u8; SIZE] {
-    fn decode(
-        &mut self,
-        reader: &mut (impl ReadsDecodable + ?Sized),
-        header: Option<DataHeader>,
-    ) -> Result<(), CodecError> {
-        let header = Self::ensure_header(header, &[0])?;
-
-        if header.count != 1
-            || header.format.blob_size != SIZE as u16
-            || header.format.data_fields != 0
-        {
-            return UnexpectedDataFormatSnafu {
-                expected: Self::FORMAT,
-                actual: Some(header),
-            }
-            .fail();
-        }
-
-        reader.read_exact(self)?;
-        Ok(())
-    }
-}
+use crate::types::Text;
 
 /// Macro which generates a [new type](https://doc.rust-lang.org/rust-by-example/generics/new_types.html)
 /// a struct wrapping a fixed-size `[u8]` array,
 /// enabling sype-safe sharing.
+///
+/// Equality of the generated type is checked in constant
+/// time (see [`constant_time_eq`](crate::types::binary::constant_time_eq)), so that comparing
+/// sensitive bytes (like hashes, keys, and signatures)
+/// doesn't leak _where_ the bytes differ. Non-sensitive
+/// bytes may use [`sized_byte_array_fast!`](crate::sized_byte_array_fast)
+/// instead, which compares bytes with `==`.
+///
+/// Only equality is constant-time: the generated type's
+/// [`Ord`] and [`PartialOrd`] implementations compare
+/// bytes in variable time, and shouldn't be used to
+/// compare sensitive bytes.
 #[macro_export]
 macro_rules! sized_byte_array {
     (
@@ -86,6 +52,23 @@ macro_rules! sized_byte_array {
         // Fixed size of the array
         $array_size:expr
     ) => {
+        $crate::sized_byte_array!(@common $(#[$meta])* $type_name, $array_size);
+
+        impl core::cmp::PartialEq for $type_name {
+            fn eq(&self, other: &Self) -> core::primitive::bool {
+                $crate::types::binary::constant_time_eq(&self.0, &other.0)
+            }
+        }
+    };
+
+    // Generates everything _except_ a `PartialEq`
+    // implementation for a sized byte array.
+    (
+        @common
+        $(#[$meta:meta])*
+        $type_name:ident,
+        $array_size:expr
+    ) => {
 
         $(#[$meta])*
         #[repr(transparent)]
@@ -100,11 +83,13 @@ macro_rules! sized_byte_array {
             pub const NULL: Self = Self([0; $array_size]);
 
             /// Decodes a `hex` string into these bytes.
+            #[allow(clippy::wrong_self_convention)]
             pub fn from_hex(&mut self, hex: &str) -> Result<(), $crate::types::binary::BinaryError> {
                 $crate::types::binary::fixed_bytes_from_hex(hex, &mut self.0)
             }
 
             /// Encodes a hex string from these bytes.
+            #[allow(clippy::wrong_self_convention)]
             pub fn to_hex(&self) -> $crate::types::Text {
                 $crate::types::binary::hex_from_bytes(&self.0)
             }
@@ -169,11 +154,6 @@ macro_rules! sized_byte_array {
         }
 
         impl core::cmp::Eq for $type_name {}
-        impl core::cmp::PartialEq for $type_name {
-            fn eq(&self, other: &Self) -> core::primitive::bool {
-                self.0 == other.0
-            }
-        }
 
         impl core::cmp::Ord for $type_name {
             fn cmp(&self, other: &Self) -> core::cmp::Ordering {
@@ -211,6 +191,10 @@ macro_rules! sized_byte_array {
             fn encode(&self, writer: &mut (impl $crate::codec::WritesEncodable + ?Sized)) -> Result<(), $crate::codec::CodecError> {
                 self.0.encode(writer)
             }
+
+            fn encoded_len(&self) -> usize {
+                self.0.encoded_len()
+            }
         }
 
         impl $crate::codec::Decodable for $type_name {
@@ -225,6 +209,81 @@ macro_rules! sized_byte_array {
     };
 }
 
+/// Variant of [`sized_byte_array!`](crate::sized_byte_array)
+/// which compares bytes with `==` instead of in constant time.
+///
+/// Only use this macro for bytes which aren't sensitive
+/// to timing side-channels (e.g., non-secret identifiers).
+#[macro_export]
+macro_rules! sized_byte_array_fast {
+    (
+        // Optional type metadata (e.g., docs).
+        $(#[$meta:meta])*
+
+        // Type name of the array
+        $type_name:ident,
+
+        // Fixed size of the array
+        $array_size:expr
+    ) => {
+        $crate::sized_byte_array!(@common $(#[$meta])* $type_name, $array_size);
+
+        impl core::cmp::PartialEq for $type_name {
+            fn eq(&self, other: &Self) -> core::primitive::bool {
+                self.0 == other.0
+            }
+        }
+    };
+}
+
+/// Returns true iff `a` and `b` contain the same bytes.
+///
+/// Every byte is compared, regardless of where (or whether)
+/// `a` and `b` differ, so that the duration of the comparison
+/// doesn't depend on the contents of either slice. Only the
+/// _lengths_ of `a` and `b` are compared in variable time.
+///
+/// With the `subtle` feature enabled, bytes are compared
+/// with [`subtle::ConstantTimeEq`]; otherwise, bytes are
+/// compared with a best-effort branchless fold.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    constant_time_eq_inspected(a, b, |_, _| {})
+}
+
+/// Returns [`constant_time_eq`] of `a` and `b`,
+/// invoking `inspect` with each pair of bytes
+/// as they're compared.
+#[inline(always)]
+fn constant_time_eq_inspected(a: &[u8], b: &[u8], mut inspect: impl FnMut(u8, u8)) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    #[cfg(feature = "subtle")]
+    {
+        use subtle::ConstantTimeEq;
+
+        let mut equal = subtle::Choice::from(1);
+        for (a, b) in a.iter().zip(b) {
+            equal &= a.ct_eq(b);
+            inspect(*a, *b);
+        }
+
+        equal.into()
+    }
+
+    #[cfg(not(feature = "subtle"))]
+    {
+        let mut difference = 0u8;
+        for (a, b) in a.iter().zip(b) {
+            difference |= core::hint::black_box(a ^ b);
+            inspect(*a, *b);
+        }
+
+        difference == 0
+    }
+}
+
 /// Decodes a vector of bytes from a `hex` into `bytes`.
 ///
 /// If an error is returned, the contents of `bytes` are undefined.
@@ -384,9 +443,6 @@ pub fn base32_from_bytes(bytes: &[u8]) -> Text {
 ///
 /// Decodes `base32`-encoded text into bytes.
 pub fn base32_to_bytes(base32: Text) -> Result<alloc::vec::Vec<u8>, BinaryError> {
-    if !base32.is_ascii() {
-        todo!()
-    }
     let base32 = base32.as_bytes();
 
     let output_length = base32.len() * BASE32_BITS_PER_CHAR / 8;
@@ -453,6 +509,127 @@ const fn base32_char_index(character: u8) -> Result<u8, BinaryError> {
     }
 }
 
+/// ## Unstable
+///
+/// An alphabet of characters used by Base64-encoded text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Base64Alphabet {
+    /// The standard alphabet, using `+` and `/`
+    /// for the 62nd and 63rd characters.
+    #[default]
+    Standard,
+
+    /// The URL- and filename-safe alphabet, using `-`
+    /// and `_` for the 62nd and 63rd characters.
+    UrlSafe,
+}
+
+impl Base64Alphabet {
+    /// Returns the lookup table of this alphabet's character codes.
+    const fn lut(self) -> &'static [u8; 64] {
+        match self {
+            Self::Standard => &BASE64_STANDARD_LUT,
+            Self::UrlSafe => &BASE64_URL_SAFE_LUT,
+        }
+    }
+
+    /// Returns the index in this alphabet's
+    /// lookup table corresponding to `character`.
+    const fn char_index(self, character: u8) -> Option<u8> {
+        match (self, character) {
+            (_, c @ b'A'..=b'Z') => Some(c - b'A'),
+            (_, c @ b'a'..=b'z') => Some(c - b'a' + 26),
+            (_, c @ b'0'..=b'9') => Some(c - b'0' + 52),
+            (Self::Standard, b'+') | (Self::UrlSafe, b'-') => Some(62),
+            (Self::Standard, b'/') | (Self::UrlSafe, b'_') => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// ## Unstable
+///
+/// Encodes `bytes` into a new Base64-encoded text
+/// using `alphabet`, as described by [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648),
+/// but _without_ padding characters (`=`).
+pub fn base64_from_bytes(bytes: &[u8], alphabet: Base64Alphabet) -> Text {
+    let lut = alphabet.lut();
+    let mut base64 = alloc::string::String::with_capacity((bytes.len() * 4).div_ceil(3));
+
+    for chunk in bytes.chunks(3) {
+        // Compress the chunk into a number (24 bits).
+        let mut condensed_chunk: u32 = 0;
+        for i in 0..3 {
+            condensed_chunk = (condensed_chunk << 8) | *chunk.get(i).unwrap_or(&0) as u32;
+        }
+
+        // Push one character for every 6 bits
+        // of the chunk containing input bytes.
+        for i in 0..=chunk.len() {
+            let index = (condensed_chunk >> (BASE64_BITS_PER_CHAR * (3 - i))) & 0x3F;
+            base64.push(lut[index as usize] as char);
+        }
+    }
+
+    base64.into()
+}
+
+/// ## Unstable
+///
+/// Decodes `base64`-encoded text using `alphabet` into bytes.
+///
+/// Any trailing padding characters (`=`) are ignored.
+pub fn base64_to_bytes(
+    base64: Text,
+    alphabet: Base64Alphabet,
+) -> Result<alloc::vec::Vec<u8>, BinaryError> {
+    let base64 = base64.as_bytes();
+    let unpadded_length = base64.len() - base64.iter().rev().take_while(|c| **c == b'=').count();
+    let base64 = &base64[..unpadded_length];
+    if base64.len() % 4 == 1 {
+        return Err(BinaryError::UnevenBase64 {
+            actual: base64.len(),
+        });
+    }
+
+    let mut bytes = alloc::vec::Vec::with_capacity(base64.len() * BASE64_BITS_PER_CHAR / 8);
+    for (chunk_index, chunk) in base64.chunks(4).enumerate() {
+        // Compress the chunk into a number (24 bits).
+        let mut condensed_chunk: u32 = 0;
+        for i in 0..4 {
+            let index = match chunk.get(i) {
+                Some(&character) => alphabet.char_index(character),
+                None => Some(0),
+            };
+            let index = index.ok_or_else(|| BinaryError::InvalidBase64Character {
+                character: chunk[i],
+                position: chunk_index * 4 + i,
+            })?;
+            condensed_chunk = (condensed_chunk << BASE64_BITS_PER_CHAR) | index as u32;
+        }
+
+        // Extract one byte for every
+        // 8 bits of input characters.
+        for i in 0..chunk.len() - 1 {
+            bytes.push((condensed_chunk >> (8 * (2 - i))) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// The number of bits encoded by each
+/// character in Base64-encoded text.
+const BASE64_BITS_PER_CHAR: usize = 6;
+
+/// Lookup table for standard Base64 character codes.
+static BASE64_STANDARD_LUT: [u8; 64] =
+    *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Lookup table for URL-safe Base64 character codes.
+static BASE64_URL_SAFE_LUT: [u8; 64] =
+    *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
 #[derive(Snafu, Debug)]
 pub enum BinaryError {
     #[snafu(display(
@@ -467,6 +644,16 @@ pub enum BinaryError {
 
     #[snafu(display("hexadecimal string contained an unexpected character code: {character}"))]
     UnexpectedHexCharacter { character: u8 },
+
+    #[snafu(display(
+        "base64 text contained an invalid character code {character} at position {position}"
+    ))]
+    InvalidBase64Character { character: u8, position: usize },
+
+    #[snafu(display(
+        "expected base64 text containing a whole number of bytes, not {actual} character(s)"
+    ))]
+    UnevenBase64 { actual: usize },
 }
 
 #[cfg(test)]
@@ -475,6 +662,39 @@ mod test {
 
     use super::*;
 
+    /// Returns [`constant_time_eq`] of `a` and `b`, and
+    /// the number of bytes compared to determine it.
+    fn count_compared_bytes(a: &[u8], b: &[u8]) -> (bool, usize) {
+        let mut compared = 0;
+        let equal = constant_time_eq_inspected(a, b, |_, _| compared += 1);
+        (equal, compared)
+    }
+
+    #[test]
+    fn test_sized_byte_array_constant_time_eq() {
+        sized_byte_array!(TestSecret, 64);
+        let secret = TestSecret([7; 64]);
+        let same = secret;
+
+        // Equal bytes are all compared.
+        assert!(secret == same);
+        assert_eq!((true, 64), count_compared_bytes(&secret.0, &same.0));
+
+        // Bytes differing at any index are all compared,
+        // regardless of where the difference occurs.
+        for i in [0, 1, 31, 63] {
+            let mut other = secret;
+            other[i] = 0;
+            assert!(secret != other);
+            assert_eq!((false, 64), count_compared_bytes(&secret.0, &other.0));
+        }
+
+        // Slices of different lengths are never equal,
+        // and aren't compared byte-by-byte.
+        assert!(!constant_time_eq(&[1, 2], &[1, 2, 3]));
+        assert_eq!((false, 0), count_compared_bytes(&[1, 2], &[1, 2, 3]));
+    }
+
     #[test]
     fn test_sized_byte_array_codec() {
         sized_byte_array!(TestArray, 9);
@@ -530,6 +750,66 @@ mod test {
         assert_eq!(TEST_HEX_UTF8_STR, hex);
     }
 
+    #[test]
+    fn test_base64() {
+        // Test vectors from RFC 4648 §10, without padding.
+        let vectors = [
+            ("", ""),
+            ("f", "Zg"),
+            ("fo", "Zm8"),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg"),
+            ("fooba", "Zm9vYmE"),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (bytes, base64) in vectors {
+            for alphabet in [Base64Alphabet::Standard, Base64Alphabet::UrlSafe] {
+                assert_eq!(base64, base64_from_bytes(bytes.as_bytes(), alphabet));
+                assert_eq!(
+                    bytes.as_bytes(),
+                    base64_to_bytes(base64.into(), alphabet).unwrap()
+                );
+            }
+        }
+
+        // Padded text is decoded.
+        assert_eq!(
+            b"fo",
+            base64_to_bytes("Zm8=".into(), Base64Alphabet::Standard)
+                .unwrap()
+                .as_slice()
+        );
+
+        // Alphabets differ in their 62nd and 63rd characters.
+        let bytes = [0xFB, 0xFF, 0xBF];
+        assert_eq!("+/+/", base64_from_bytes(&bytes, Base64Alphabet::Standard));
+        assert_eq!("-_-_", base64_from_bytes(&bytes, Base64Alphabet::UrlSafe));
+        assert_eq!(
+            bytes.as_slice(),
+            base64_to_bytes("-_-_".into(), Base64Alphabet::UrlSafe).unwrap()
+        );
+        assert!(matches!(
+            base64_to_bytes("-_-_".into(), Base64Alphabet::Standard),
+            Err(BinaryError::InvalidBase64Character {
+                character: b'-',
+                position: 0
+            })
+        ));
+
+        // Invalid text is rejected.
+        assert!(matches!(
+            base64_to_bytes("Zm9v!".into(), Base64Alphabet::Standard),
+            Err(BinaryError::UnevenBase64 { actual: 5 })
+        ));
+        assert!(matches!(
+            base64_to_bytes("Zm9v Yg".into(), Base64Alphabet::Standard),
+            Err(BinaryError::InvalidBase64Character {
+                character: b' ',
+                position: 4
+            })
+        ));
+    }
+
     #[test]
     fn test_base32() {
         assert_eq!(
@@ -578,5 +858,11 @@ mod test {
                 .as_slice()
             )
         );
+
+        // Non-ASCII text is rejected.
+        assert!(matches!(
+            base32_to_bytes("Z0Z0Z0Zé".into()),
+            Err(BinaryError::UnexpectedHexCharacter { character: 0xC3 })
+        ));
     }
 }
@@ -4,7 +4,7 @@
 ///
 /// These types may be split out into a separate crate in the future,
 /// and have experimental APIs.
-use argon2::Argon2;
+use argon2::{Algorithm, Argon2, Params, Version};
 use chacha20poly1305::{
     aead::{Aead, Payload},
     AeadCore, ChaCha20Poly1305, Key, KeyInit, Nonce,
@@ -14,7 +14,7 @@ use rand_core::OsRng;
 use snafu::Snafu;
 
 use crate::{
-    codec::{CodecError, Decodable, Encodable, Format, WritesEncodable},
+    codec::{CodecError, DataHeader, Decodable, Encodable, Format, WritesEncodable},
     sized_byte_array,
     stream::Writes,
     types::binary::hex_from_bytes,
@@ -58,6 +58,16 @@ impl CryptoHasher {
         self.hasher.update(bytes);
     }
 
+    /// Encodes `value` (including its header) directly
+    /// into the in-progress hash.
+    ///
+    /// The hash is identical to hashing the bytes written
+    /// by [`WritesEncodable::write_data`], without
+    /// allocating those bytes.
+    pub fn update_encodable<T: Encodable + ?Sized>(&mut self, value: &T) -> Result<(), CodecError> {
+        self.write_data(value)
+    }
+
     /// Completes the hash and consumes `self`, returning it as [HashBytes].
     pub fn finalize(self) -> HashBytes {
         HashBytes::from(*self.hasher.finalize().as_bytes())
@@ -69,6 +79,15 @@ impl CryptoHasher {
     }
 }
 
+/// Returns the hash of `value`'s encoding.
+///
+/// See [`CryptoHasher::update_encodable`].
+pub fn hash_encodable<T: Encodable + ?Sized>(value: &T) -> Result<HashBytes, CodecError> {
+    let mut hasher = CryptoHasher::default();
+    hasher.update_encodable(value)?;
+    Ok(hasher.finalize())
+}
+
 impl Writes for CryptoHasher {
     fn write(&mut self, buf: &[u8]) -> Result<usize, crate::stream::StreamError> {
         self.hasher.update(buf);
@@ -105,6 +124,40 @@ impl CryptoKeys {
         }
     }
 
+    /// Deterministically derives a pair of keys from `seed`.
+    ///
+    /// The private key is derived from `seed` via `Argon2`
+    /// with a fixed salt, so the same seed always
+    /// derives the same keys.
+    pub fn generate_from_seed(seed: &[u8]) -> Result<Self, CryptoError> {
+        let mut private_key = PrivateKeyBytes::default();
+        argon2()?.hash_password_into(seed, KEY_SEED_SALT, &mut private_key.0)?;
+        Self::from_private(private_key)
+    }
+
+    /// Deterministically derives the child pair of
+    /// keys at `index` from these (parent) keys.
+    ///
+    /// The child's private key is the hash of the parent's
+    /// private key and `index`, so that each parent key
+    /// can derive a tree of keys.
+    pub fn generate_child(&self, index: u32) -> Self {
+        let mut hasher = CryptoHasher::default();
+        hasher.write(KEY_CHILD_CONTEXT);
+        hasher.write(&self.signer.private_key.to_bytes());
+        hasher.write(&index.to_le_bytes());
+        let signer = SigningKey::from_bytes(&hasher.finalize().0);
+        let verifier = signer.verifying_key();
+        CryptoKeys {
+            signer: CryptoSigner {
+                private_key: signer,
+            },
+            verifier: CryptoVerifier {
+                public_key: verifier,
+            },
+        }
+    }
+
     /// Tries to load a pair of keys from
     /// `private_key`.
     pub fn from_private(private_key: PrivateKeyBytes) -> Result<Self, CryptoError> {
@@ -128,6 +181,97 @@ impl CryptoKeys {
         bytes.copy_from_slice(private_key);
         bytes
     }
+
+    /// Consumes these (old) keys, returning a rotation
+    /// to `new_keys` that proves the old keys
+    /// authorized the transition to the new keys.
+    pub fn rotate_to(self, new_keys: CryptoKeys) -> Result<CryptoKeyRotation, CryptoError> {
+        let new_public_key = new_keys.public_key_bytes();
+        let proof = self.sign(&[KEY_ROTATION_CONTEXT, &new_public_key[..]])?;
+
+        Ok(CryptoKeyRotation {
+            old_verifier: self.verifier,
+            new_signer: new_keys.signer,
+            proof,
+        })
+    }
+}
+
+/// Context prepended to the public key signed by
+/// the old key of a [`CryptoKeyRotation`], so that
+/// the signature can't be mistaken for any other.
+const KEY_ROTATION_CONTEXT: &[u8] = b"codas key rotation";
+
+/// Salt used to derive keys from a seed in
+/// [`CryptoKeys::generate_from_seed`].
+const KEY_SEED_SALT: &[u8] = b"codas key seed";
+
+/// Context prepended to a parent private key when
+/// deriving a [`CryptoKeys::generate_child`] key.
+const KEY_CHILD_CONTEXT: &[u8] = b"codas child key";
+
+/// Memory cost (in KiB), iterations, and parallelism
+/// of every key derived via [`argon2`].
+const ARGON2_COSTS: (u32, u32, u32) = (19 * 1024, 2, 1);
+
+/// Returns the Argon2id (version `0x13`) instance which
+/// derives keys from seeds and passwords.
+///
+/// The algorithm, version, and [`ARGON2_COSTS`] are
+/// pinned (instead of using the `argon2` crate's
+/// defaults), since changing any of them changes
+/// every key derived from the same input.
+fn argon2() -> Result<Argon2<'static>, CryptoError> {
+    let (memory, iterations, parallelism) = ARGON2_COSTS;
+    let params = Params::new(memory, iterations, parallelism, None)?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+/// A rotation from an old pair of [`CryptoKeys`] to a new
+/// pair, created by [`CryptoKeys::rotate_to`].
+///
+/// During a transition window, data signed by the new keys
+/// with [`Self::sign_transition`] can be verified by anyone
+/// who only knows the old keys, via
+/// [`CryptoVerifier::verify_with_rotation`].
+pub struct CryptoKeyRotation {
+    old_verifier: CryptoVerifier,
+    new_signer: CryptoSigner,
+
+    /// Signature by the old keys of the new keys' public key.
+    proof: SignatureBytes,
+}
+
+impl CryptoKeyRotation {
+    /// Returns the verifier of the old keys.
+    pub fn old_verifier(&self) -> &CryptoVerifier {
+        &self.old_verifier
+    }
+
+    /// Signs `data` with the new keys, returning the
+    /// signature accompanied by the proof that the
+    /// old keys authorized the new keys.
+    pub fn sign_transition(&self, data: &[&[u8]]) -> Result<TransitionSignature, CryptoError> {
+        Ok(TransitionSignature {
+            public_key: self.new_signer.public_key_bytes(),
+            signature: self.new_signer.sign(data)?,
+            proof: self.proof,
+        })
+    }
+}
+
+impl HasCryptoPublicKey for CryptoKeyRotation {
+    /// Returns the public key of the new keys.
+    fn public_key_bytes(&self) -> PublicKeyBytes {
+        self.new_signer.public_key_bytes()
+    }
+}
+
+impl CryptoSigns for CryptoKeyRotation {
+    /// Signs `message` with the new keys.
+    fn sign(&self, message: &[&[u8]]) -> Result<SignatureBytes, CryptoError> {
+        self.new_signer.sign(message)
+    }
 }
 
 /// Signing (private) key which
@@ -143,6 +287,34 @@ pub struct CryptoVerifier {
     public_key: VerifyingKey,
 }
 
+impl CryptoVerifier {
+    /// Verifies `signature` against `message`, returning `Ok` iff:
+    ///
+    /// - `signature` is plain [`SignatureBytes`] that are valid
+    ///   and correspond to this verifier's public key, or
+    /// - `signature` is a [`TransitionSignature`] whose proof is
+    ///   valid and corresponds to this verifier's public key,
+    ///   and whose signature is valid and corresponds to the
+    ///   public key authorized by the proof.
+    pub fn verify_with_rotation(
+        &self,
+        message: &[&[u8]],
+        signature: impl Into<RotatingSignature>,
+    ) -> Result<(), CryptoError> {
+        match signature.into() {
+            RotatingSignature::Signature(signature) => self.verify(message, &signature),
+            RotatingSignature::Transition(transition) => {
+                self.verify(
+                    &[KEY_ROTATION_CONTEXT, &transition.public_key[..]],
+                    &transition.proof,
+                )?;
+                CryptoVerifier::try_from(&transition.public_key)?
+                    .verify(message, &transition.signature)
+            }
+        }
+    }
+}
+
 impl TryFrom<&PublicKeyBytes> for CryptoVerifier {
     type Error = CryptoError;
 
@@ -241,6 +413,74 @@ impl Decodable for CryptoCert {
     }
 }
 
+/// [`SignatureBytes`] created by the new keys of a
+/// [`CryptoKeyRotation`], accompanied by the proof
+/// that the old keys authorized the new keys.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransitionSignature {
+    /// The public key of the new keys.
+    pub public_key: PublicKeyBytes,
+
+    /// The signature, by the new keys.
+    pub signature: SignatureBytes,
+
+    /// The signature, by the old keys, of [`Self::public_key`].
+    pub proof: SignatureBytes,
+}
+
+impl Encodable for TransitionSignature {
+    const FORMAT: Format = Format::data(0)
+        .with(PublicKeyBytes::FORMAT)
+        .with(SignatureBytes::FORMAT)
+        .with(SignatureBytes::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        writer.write_data(&self.public_key)?;
+        writer.write_data(&self.signature)?;
+        writer.write_data(&self.proof)?;
+        Ok(())
+    }
+}
+
+impl Decodable for TransitionSignature {
+    fn decode(
+        &mut self,
+        reader: &mut (impl crate::codec::ReadsDecodable + ?Sized),
+        header: Option<crate::codec::DataHeader>,
+    ) -> Result<(), CodecError> {
+        let header = Self::ensure_header(header, &[0])?;
+        reader.read_data_into(&mut self.public_key)?;
+        reader.read_data_into(&mut self.signature)?;
+        reader.read_data_into(&mut self.proof)?;
+        reader.skip_excess_data(&header, Self::FORMAT)?;
+        Ok(())
+    }
+}
+
+/// Either plain [`SignatureBytes`] or a [`TransitionSignature`],
+/// as verified by [`CryptoVerifier::verify_with_rotation`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RotatingSignature {
+    /// A signature by the old keys.
+    Signature(SignatureBytes),
+
+    /// A signature by the new keys, with proof
+    /// of the transition from the old keys.
+    Transition(TransitionSignature),
+}
+
+impl From<SignatureBytes> for RotatingSignature {
+    fn from(signature: SignatureBytes) -> Self {
+        Self::Signature(signature)
+    }
+}
+
+impl From<TransitionSignature> for RotatingSignature {
+    fn from(transition: TransitionSignature) -> Self {
+        Self::Transition(transition)
+    }
+}
+
 /// A thing that can be represented as a cryptographic hash.
 pub trait HasCryptoHash {
     /// Writes `self`'s cryptographhically hashable
@@ -360,40 +600,76 @@ pub struct EncryptedData {
 
     /// Encrypted data.
     data: alloc::vec::Vec<u8>,
+
+    /// Unencrypted additional authenticated data
+    /// bound to the encrypted data.
+    aad: alloc::vec::Vec<u8>,
 }
 
 impl EncryptedData {
     /// Encrypts `data` with `key`, returning a new encrypted data.
+    ///
+    /// The nonce is used as the additional authenticated data.
     pub fn new(key: &[u8], data: &[u8]) -> Result<Self, CryptoError> {
+        Self::encrypt(key, data, None)
+    }
+
+    /// Encrypts `data` with `key`, binding the encrypted
+    /// data to the additional authenticated data `aad`.
+    ///
+    /// `aad` is stored unencrypted alongside the encrypted data,
+    /// and decryption fails if the `aad` has been modified.
+    pub fn new_with_aad(key: &[u8], data: &[u8], aad: &[u8]) -> Result<Self, CryptoError> {
+        Self::encrypt(key, data, Some(aad))
+    }
+
+    /// Encrypts `data` with `key` and `aad`, using
+    /// the nonce as the `aad` if none is provided.
+    fn encrypt(key: &[u8], data: &[u8], aad: Option<&[u8]>) -> Result<Self, CryptoError> {
         // Generate a nonce for key derivation and encryption.
         let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = aad.unwrap_or(&nonce);
 
         // Derive key.
         let mut derived_key = [0u8; 32];
-        Argon2::default().hash_password_into(key, nonce.as_ref(), &mut derived_key)?;
+        argon2()?.hash_password_into(key, nonce.as_ref(), &mut derived_key)?;
         let cipher = ChaCha20Poly1305::new(&Key::from(derived_key));
 
-        // Encrypt the data, attaching the nonce as unencrypted additional associated data.
-        let encrypted = cipher.encrypt(
-            &nonce,
-            Payload {
-                msg: data,
-                aad: &nonce,
-            },
-        )?;
+        // Encrypt the data, attaching the unencrypted additional associated data.
+        let encrypted = cipher.encrypt(&nonce, Payload { msg: data, aad })?;
 
         Ok(Self {
             nonce: nonce.into(),
             data: encrypted,
+            aad: aad.to_vec(),
         })
     }
 
+    /// Returns the additional authenticated
+    /// data bound to this data.
+    pub fn aad(&self) -> &[u8] {
+        &self.aad
+    }
+
     /// Decrypts this data with `key`, returning
     /// the decrypted data.
     pub fn decrypt(&self, key: &[u8]) -> Result<alloc::vec::Vec<u8>, CryptoError> {
+        self.decrypt_with_aad(key, &self.aad)
+    }
+
+    /// Decrypts this data with `key` and the additional
+    /// authenticated data `aad`, returning the decrypted data.
+    ///
+    /// Decryption fails unless `aad` matches the
+    /// `aad` this data was encrypted with.
+    pub fn decrypt_with_aad(
+        &self,
+        key: &[u8],
+        aad: &[u8],
+    ) -> Result<alloc::vec::Vec<u8>, CryptoError> {
         // Derive key.
         let mut derived_key = [0u8; 32];
-        Argon2::default().hash_password_into(key, self.nonce.as_ref(), &mut derived_key)?;
+        argon2()?.hash_password_into(key, self.nonce.as_ref(), &mut derived_key)?;
         let cipher = ChaCha20Poly1305::new(&Key::from(derived_key));
 
         // Decrypt the data.
@@ -401,49 +677,101 @@ impl EncryptedData {
             Nonce::from_slice(&self.nonce),
             Payload {
                 msg: &self.data,
-                aad: &self.nonce,
+                aad,
             },
         )?;
 
         Ok(decrypted)
     }
 
-    /// Returns a string containing the nonce and
-    /// encrypted data in HEX format, separated by
-    /// a `-` character.
+    /// Returns a string containing the nonce, encrypted
+    /// data, and additional authenticated data in HEX
+    /// format, separated by `-` characters.
+    ///
+    /// If the nonce is the `aad`, the `aad` is omitted,
+    /// exactly as data was encoded before `aad`s were stored.
     pub fn to_hex(&self) -> alloc::string::String {
+        if self.has_legacy_aad() {
+            return alloc::format!(
+                "{}-{}",
+                hex_from_bytes(&self.nonce),
+                hex_from_bytes(&self.data)
+            );
+        }
+
         alloc::format!(
-            "{}-{}",
+            "{}-{}-{}",
             hex_from_bytes(&self.nonce),
-            hex_from_bytes(&self.data)
+            hex_from_bytes(&self.data),
+            hex_from_bytes(&self.aad)
         )
     }
 
     /// Returns a new encrypted data by decoding a
-    /// string containing a `nonce-data` pair, where
-    /// the `nonce` and `data` are HEX-encoded.
+    /// string containing a `nonce-data-aad` triple,
+    /// where each segment is HEX-encoded.
+    ///
+    /// Strings containing only a `nonce-data` pair
+    /// are decoded with the nonce as the `aad`.
     pub fn from_hex(hex: &str) -> Result<Self, CryptoError> {
-        let (nonce, key) = hex.split_once('-').ok_or(CryptoError::Malformed)?;
-        let nonce = super::binary::bytes_from_hex(nonce).map_err(|_| CryptoError::Malformed)?;
-        let key = super::binary::bytes_from_hex(key).map_err(|_| CryptoError::Malformed)?;
+        let mut segments = hex.splitn(3, '-');
+        let mut next_segment = || {
+            segments.next().map(|segment| {
+                super::binary::bytes_from_hex(segment).map_err(|_| CryptoError::Malformed)
+            })
+        };
+        let nonce: [u8; 12] = next_segment()
+            .ok_or(CryptoError::Malformed)??
+            .try_into()
+            .map_err(|_| CryptoError::Malformed)?;
+        let data = next_segment().ok_or(CryptoError::Malformed)??;
+        let aad = next_segment().unwrap_or_else(|| Ok(nonce.to_vec()))?;
+
+        Ok(EncryptedData { nonce, data, aad })
+    }
 
-        Ok(EncryptedData {
-            nonce: nonce.try_into().map_err(|_| CryptoError::Malformed)?,
-            data: key,
-        })
+    /// Returns true iff the nonce is the `aad`, as it
+    /// was for all data encrypted before `aad`s were stored.
+    fn has_legacy_aad(&self) -> bool {
+        self.aad == self.nonce
     }
 }
 
+/// Format of encrypted data encoded without an `aad`.
+const LEGACY_ENCRYPTED_DATA_FORMAT: Format = Format::data(0)
+    .with(<[u8; 12]>::FORMAT)
+    .with(alloc::vec::Vec::<u8>::FORMAT);
+
 impl Encodable for EncryptedData {
-    const FORMAT: Format = Format::data(0)
-        .with(<[u8; 12]>::FORMAT)
-        .with(alloc::vec::Vec::<u8>::FORMAT);
+    const FORMAT: Format = LEGACY_ENCRYPTED_DATA_FORMAT.with(alloc::vec::Vec::<u8>::FORMAT);
 
+    /// Encrypted data whose nonce is its `aad` is encoded
+    /// without the `aad`, in [`LEGACY_ENCRYPTED_DATA_FORMAT`].
     fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
         writer.write_data(&self.nonce)?;
         writer.write_data(&self.data)?;
+        if !self.has_legacy_aad() {
+            writer.write_data(&self.aad)?;
+        }
         Ok(())
     }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        let format = if self.has_legacy_aad() {
+            LEGACY_ENCRYPTED_DATA_FORMAT
+        } else {
+            Self::FORMAT
+        };
+
+        DataHeader {
+            count: 1,
+            format: format.as_data_format(),
+        }
+        .encode(writer)
+    }
 }
 
 impl Decodable for EncryptedData {
@@ -452,9 +780,18 @@ impl Decodable for EncryptedData {
         reader: &mut (impl crate::codec::ReadsDecodable + ?Sized),
         header: Option<crate::codec::DataHeader>,
     ) -> Result<(), CodecError> {
-        Self::ensure_header(header, &[0])?;
+        let header = Self::ensure_header(header, &[0])?;
         reader.read_data_into(&mut self.nonce)?;
         reader.read_data_into(&mut self.data)?;
+
+        // Data encoded without an `aad` used the nonce as its `aad`.
+        if header.format.data_fields > 2 {
+            reader.read_data_into(&mut self.aad)?;
+        } else {
+            self.aad = self.nonce.to_vec();
+        }
+
+        reader.skip_excess_data(&header, Self::FORMAT)?;
         Ok(())
     }
 }
@@ -500,10 +837,132 @@ impl From<chacha20poly1305::Error> for CryptoError {
 #[cfg(test)]
 mod tests {
 
-    use crate::codec::ReadsDecodable;
+    use crate::{
+        codec::{decode_from_slice, encode_to_vec, DataHeader},
+        types::Text,
+    };
 
     use super::*;
 
+    #[test]
+    fn hashes_encodables() {
+        let value = Text::from("cupcakes!");
+        let other = Text::from("pancakes!");
+        let hash = hash_encodable(&value).unwrap();
+        assert_eq!(hash, hash_encodable(&value).unwrap());
+        assert_ne!(hash, hash_encodable(&other).unwrap());
+
+        // Hashes are identical to hashes of encoded bytes.
+        let bytes = encode_to_vec(&value).unwrap();
+        let mut hasher = CryptoHasher::default();
+        hasher.write(&bytes);
+        assert_eq!(hash, hasher.finalize());
+    }
+
+    #[test]
+    fn generates_keys_from_seeds() {
+        let keys = CryptoKeys::generate_from_seed(b"test").unwrap();
+        let public_key = keys.public_key_bytes();
+        assert_eq!(
+            public_key,
+            CryptoKeys::generate_from_seed(b"test")
+                .unwrap()
+                .public_key_bytes()
+        );
+        assert_ne!(
+            public_key,
+            CryptoKeys::generate_from_seed(b"tests")
+                .unwrap()
+                .public_key_bytes()
+        );
+
+        // Children are deterministic and distinct.
+        let child = keys.generate_child(0);
+        assert_eq!(
+            child.public_key_bytes(),
+            keys.generate_child(0).public_key_bytes()
+        );
+        assert_ne!(child.public_key_bytes(), public_key);
+        assert_ne!(
+            child.public_key_bytes(),
+            keys.generate_child(1).public_key_bytes()
+        );
+
+        // Keys derived from the same seed never change.
+        assert_eq!(
+            "21371fd17f8cfa19da41476eec5c5d7434ce76d29d0757e7fa5bd237d970731b",
+            public_key.to_hex()
+        );
+        assert_eq!(
+            "e562ff22a6d35b743385ceeaf45df5784adcf6c370778232260b76827ccace97",
+            child.public_key_bytes().to_hex()
+        );
+
+        // Children can sign and verify.
+        let message: &[&[u8]] = &[b"i'm a child."];
+        let signature = child.sign(message).unwrap();
+        assert!(child.verify(message, &signature).is_ok());
+    }
+
+    #[test]
+    fn rotates_keys() {
+        let message: &[&[u8]] = &[b"i'm ", b"so signed."];
+        let old_keys = CryptoKeys::generate();
+        let old_verifier = old_keys.verifier;
+        let old_signature = old_keys.sign(message).unwrap();
+        let rotation = old_keys.rotate_to(CryptoKeys::generate()).unwrap();
+
+        // Old and transitional signatures are both accepted.
+        old_verifier
+            .verify_with_rotation(message, old_signature)
+            .unwrap();
+        let transition = rotation.sign_transition(message).unwrap();
+        old_verifier
+            .verify_with_rotation(message, transition)
+            .unwrap();
+        assert!(old_verifier
+            .verify_with_rotation(&[b"i'm not signed."], transition)
+            .is_err());
+
+        // Plain signatures from the new keys aren't
+        // accepted without the proof of transition.
+        let new_signature = rotation.sign(message).unwrap();
+        assert!(old_verifier
+            .verify_with_rotation(message, new_signature)
+            .is_err());
+        CryptoVerifier::try_from(&rotation.public_key_bytes())
+            .unwrap()
+            .verify(message, &new_signature)
+            .unwrap();
+
+        // Transitions to keys the old keys didn't authorize are rejected.
+        let mut forged = transition;
+        let impostor = CryptoKeys::generate();
+        forged.public_key = impostor.public_key_bytes();
+        forged.signature = impostor.sign(message).unwrap();
+        assert!(old_verifier.verify_with_rotation(message, forged).is_err());
+
+        // Transitions from other old keys are rejected.
+        let other_rotation = CryptoKeys::generate()
+            .rotate_to(CryptoKeys::generate())
+            .unwrap();
+        let other_transition = other_rotation.sign_transition(message).unwrap();
+        assert!(old_verifier
+            .verify_with_rotation(message, other_transition)
+            .is_err());
+
+        // Transition signatures survive encoding.
+        let encoded = encode_to_vec(&transition).unwrap();
+        let header_size = DataHeader::FORMAT.as_data_format().blob_size as usize;
+        assert_eq!(
+            header_size * 4 + PublicKeyBytes::SIZE + SignatureBytes::SIZE * 2,
+            encoded.len()
+        );
+        let decoded: TransitionSignature = decode_from_slice(&encoded).unwrap();
+        assert_eq!(transition, decoded);
+        old_verifier.verify_with_rotation(message, decoded).unwrap();
+    }
+
     #[test]
     fn encrypted_data() {
         let key = b"cupc4k3s";
@@ -528,6 +987,32 @@ mod tests {
         assert!(encrypted_too.decrypt(key).is_err());
     }
 
+    #[test]
+    fn encrypted_data_with_aad() {
+        let key = b"cupc4k3s";
+        let message = b"i'm so secret.";
+        let aad = b"from alice";
+
+        // Test encryption happy-path.
+        let encrypted = EncryptedData::new_with_aad(key, message, aad).unwrap();
+        assert_eq!(aad, encrypted.aad());
+        let decrypted = encrypted.decrypt(key).unwrap();
+        assert_eq!(message, decrypted.as_slice());
+        let decrypted = encrypted.decrypt_with_aad(key, aad).unwrap();
+        assert_eq!(message, decrypted.as_slice());
+
+        // Test that a mismatched AAD breaks decryption.
+        assert!(encrypted.decrypt_with_aad(key, b"from bob").is_err());
+        assert!(encrypted.decrypt_with_aad(key, &encrypted.nonce).is_err());
+
+        // Test that data without a custom AAD
+        // is bound to its nonce.
+        let encrypted = EncryptedData::new(key, message).unwrap();
+        assert_eq!(&encrypted.nonce, encrypted.aad());
+        let decrypted = encrypted.decrypt_with_aad(key, &encrypted.nonce).unwrap();
+        assert_eq!(message, decrypted.as_slice());
+    }
+
     #[test]
     fn encrypted_data_codas_codec() {
         let key = b"p4nc4k3s";
@@ -537,15 +1022,46 @@ mod tests {
         let encrypted = EncryptedData::new(key, message).unwrap();
 
         // Encode the message payload.
-        let mut encoded = vec![];
-        encoded.write_data(&encrypted).unwrap();
+        let encoded = encode_to_vec(&encrypted).unwrap();
 
         // Decode the message payload.
-        let decoded: EncryptedData = encoded.as_slice().read_data().unwrap();
+        let decoded: EncryptedData = decode_from_slice(&encoded).unwrap();
 
         // Test that the decoded data is still well-formatted.
         let decrypted = decoded.decrypt(key).unwrap();
         assert_eq!(message, decrypted.as_slice());
+
+        // Encode the message payload without its AAD,
+        // as it was encoded before AADs were stored.
+        let mut legacy = vec![];
+        legacy
+            .write_data(&DataHeader {
+                count: 1,
+                format: Format::data(0)
+                    .with(<[u8; 12]>::FORMAT)
+                    .with(alloc::vec::Vec::<u8>::FORMAT)
+                    .as_data_format(),
+            })
+            .unwrap();
+        legacy.write_data(&encrypted.nonce).unwrap();
+        legacy.write_data(&encrypted.data).unwrap();
+
+        // Test that data bound to its nonce
+        // is still encoded without its AAD.
+        assert_eq!(legacy, encoded);
+
+        // Test that the legacy payload is bound to its nonce.
+        let decoded: EncryptedData = decode_from_slice(&legacy).unwrap();
+        assert_eq!(&encrypted.nonce, decoded.aad());
+        let decrypted = decoded.decrypt(key).unwrap();
+        assert_eq!(message, decrypted.as_slice());
+
+        // Test that custom AADs survive the round-trip.
+        let encrypted = EncryptedData::new_with_aad(key, message, b"context").unwrap();
+        let decoded: EncryptedData =
+            decode_from_slice(&encode_to_vec(&encrypted).unwrap()).unwrap();
+        assert_eq!(b"context", decoded.aad());
+        assert_eq!(message, decoded.decrypt(key).unwrap().as_slice());
     }
 
     #[test]
@@ -556,11 +1072,12 @@ mod tests {
         // Encrypt a message.
         let encrypted = EncryptedData::new(key, message).unwrap();
 
-        // Convert the message to hexadecimal.
+        // Convert the message to hexadecimal, which omits
+        // the AAD (like legacy data) since it's the nonce.
         let encoded = encrypted.to_hex();
+        assert_eq!(1, encoded.matches('-').count());
 
-        let mut bytes = vec![];
-        bytes.write_data(&encrypted).unwrap();
+        let bytes = encode_to_vec(&encrypted).unwrap();
         eprintln!("raw hex: {encoded}");
         eprintln!("\n\ncoda hx: {}", hex_from_bytes(&bytes));
 
@@ -568,5 +1085,25 @@ mod tests {
         let decoded = EncryptedData::from_hex(&encoded).unwrap();
         assert_eq!(encrypted.nonce, decoded.nonce);
         assert_eq!(encrypted.data, decoded.data);
+        assert_eq!(encrypted.aad, decoded.aad);
+
+        // Test that custom AADs survive the round-trip.
+        let encrypted = EncryptedData::new_with_aad(key, message, b"context").unwrap();
+        let decoded = EncryptedData::from_hex(&encrypted.to_hex()).unwrap();
+        assert_eq!(b"context", decoded.aad());
+        assert_eq!(message, decoded.decrypt(key).unwrap().as_slice());
+
+        // Test that legacy `nonce-data` pairs are bound to their nonce.
+        let legacy = alloc::format!(
+            "{}-{}",
+            hex_from_bytes(&encrypted.nonce),
+            hex_from_bytes(&encrypted.data)
+        );
+        let decoded = EncryptedData::from_hex(&legacy).unwrap();
+        assert_eq!(&encrypted.nonce, decoded.aad());
+
+        // Test that malformed strings are rejected.
+        assert!(EncryptedData::from_hex("").is_err());
+        assert!(EncryptedData::from_hex(&hex_from_bytes(&encrypted.nonce)).is_err());
     }
 }
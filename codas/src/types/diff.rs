@@ -0,0 +1,697 @@
+//! Differences between versions of a [`Coda`].
+//!
+//! ## Unstable
+use core::fmt::Display;
+
+use alloc::vec::Vec;
+
+use crate::codec::Compatibility;
+
+use super::{Coda, DataField, DataType, Text};
+
+/// Returns the differences between an `old`
+/// and a `new` version of a coda.
+///
+/// Refer to [`Coda::diff`] for more info.
+pub fn diff(old: &Coda, new: &Coda) -> CodaDiff {
+    old.diff(new)
+}
+
+/// Checks that data encoded with every data type in a
+/// `new` version of a coda can be decoded by decoders of
+/// the same data type (matched by name) in the `old` coda,
+/// returning the reasons for any incompatibilities.
+///
+/// Data types only in one coda are ignored; refer
+/// to [`diff`] for a more complete comparison.
+pub fn check_coda_compat(old: &Coda, new: &Coda) -> Result<(), Vec<IncompatibilityReason>> {
+    let mut reasons = Vec::new();
+
+    for old_type in old.iter() {
+        let Some(new_type) = new.data_type_ignore_ascii_case(&old_type.name) else {
+            continue;
+        };
+
+        if let Compatibility::Incompatible { reason } = new_type.compatible_with(old_type) {
+            reasons.push(IncompatibilityReason {
+                data_type: new_type.name.clone(),
+                reason,
+            });
+        }
+    }
+
+    if reasons.is_empty() {
+        Ok(())
+    } else {
+        Err(reasons)
+    }
+}
+
+/// Reason a data type in a new version of a coda is
+/// incompatible with its old version, returned by
+/// [`check_coda_compat`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncompatibilityReason {
+    /// Name of the data type in the new coda.
+    pub data_type: Text,
+
+    /// Why the data type is incompatible.
+    pub reason: Text,
+}
+
+impl Display for IncompatibilityReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "'{}' is not backward-compatible: {}",
+            self.data_type, self.reason
+        )
+    }
+}
+
+impl Coda {
+    /// Returns the differences between this
+    /// coda and a `new` version of it.
+    ///
+    /// Data types and fields are matched by name. Data
+    /// types only in one coda which share an ordinal are
+    /// matched as renamed types. The [`Compatibility`] of
+    /// each modified data type is that of data encoded with
+    /// the `new` data type being decoded by the old data
+    /// type's decoders (refer to [`DataType::compatible_with`]).
+    pub fn diff(&self, new: &Coda) -> CodaDiff {
+        let mut diff = CodaDiff::default();
+
+        for old_type in self.iter() {
+            let new_type = new.data_type_ignore_ascii_case(&old_type.name).or_else(|| {
+                // Types which were only renamed keep their ordinal.
+                let renamed = new.data_type_by_ordinal(old_type.ordinal())?;
+                self.data_type_ignore_ascii_case(&renamed.name)
+                    .is_none()
+                    .then_some(renamed)
+            });
+
+            match new_type {
+                Some(new_type) => {
+                    if !old_type.name.eq_ignore_ascii_case(&new_type.name) {
+                        diff.renamed
+                            .push((old_type.name.clone(), new_type.name.clone()));
+                    }
+
+                    if new_type.deprecated && !old_type.deprecated {
+                        diff.deprecated.push(new_type.name.clone());
+                    }
+
+                    let type_diff = DataTypeDiff::new(old_type, new_type);
+                    if !type_diff.is_empty() {
+                        diff.modified.push(type_diff);
+                    }
+                }
+                None => diff.removed.push(old_type.name.clone()),
+            }
+        }
+
+        for new_type in new.iter() {
+            let renamed = diff
+                .renamed
+                .iter()
+                .any(|(_, renamed)| *renamed == new_type.name);
+            if !renamed && self.data_type_ignore_ascii_case(&new_type.name).is_none() {
+                diff.added.push(new_type.name.clone());
+            }
+        }
+
+        diff
+    }
+}
+
+/// Differences between two versions of a
+/// coda, returned by [`Coda::diff`].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CodaDiff {
+    /// Names of data types only in the new coda.
+    pub added: Vec<Text>,
+
+    /// Names of data types only in the old coda.
+    pub removed: Vec<Text>,
+
+    /// Data types which were renamed without
+    /// changing their ordinal, as `(old, new)` names.
+    ///
+    /// Renamed types which also changed are
+    /// listed in [`Self::modified`].
+    pub renamed: Vec<(Text, Text)>,
+
+    /// Names of data types in both codas which are
+    /// deprecated in the new coda, but not the old coda.
+    pub deprecated: Vec<Text>,
+
+    /// Data types in both codas which differ.
+    pub modified: Vec<DataTypeDiff>,
+}
+
+impl CodaDiff {
+    /// Returns true iff the codas are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.deprecated.is_empty()
+            && self.modified.is_empty()
+    }
+
+    /// Returns true iff data encoded with the new coda
+    /// can't be decoded by decoders of the old coda:
+    /// a data type was removed, or a modified data type
+    /// is breaking (refer to [`DataTypeDiff::is_breaking`]).
+    ///
+    /// Renaming or deprecating a data type doesn't change
+    /// its encoding, so renames and deprecations alone
+    /// aren't breaking.
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || self.modified.iter().any(DataTypeDiff::is_breaking)
+    }
+}
+
+/// Prints the differences in a human-readable
+/// summary, one change per line.
+impl Display for CodaDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for name in &self.added {
+            writeln!(f, "+ Added type '{name}'")?;
+        }
+
+        for name in &self.removed {
+            writeln!(f, "- Removed type '{name}' (breaking)")?;
+        }
+
+        for (old, new) in &self.renamed {
+            writeln!(f, "~ Renamed type '{old}' to '{new}'")?;
+        }
+
+        for name in &self.deprecated {
+            writeln!(f, "~ Deprecated type '{name}'")?;
+        }
+
+        for modified in &self.modified {
+            write!(f, "{modified}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Differences between two versions of a [`DataType`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTypeDiff {
+    /// Name of the data type in the new coda.
+    pub name: Text,
+
+    /// Ordinal of the data type in the old coda.
+    pub old_ordinal: u8,
+
+    /// Ordinal of the data type in the new coda.
+    pub new_ordinal: u8,
+
+    /// Fields only in the new data type.
+    pub added: Vec<DataField>,
+
+    /// Fields only in the old data type.
+    pub removed: Vec<DataField>,
+
+    /// Fields in both data types whose type (or
+    /// optionality) changed, as `(old, new)` pairs.
+    pub retyped: Vec<(DataField, DataField)>,
+
+    /// Names of fields in both data types whose
+    /// position changed relative to the other fields
+    /// in both data types, with their `(old, new)`
+    /// positions in the data types.
+    pub reordered: Vec<(Text, usize, usize)>,
+
+    /// Names of fields in both data types which are
+    /// deprecated in the new data type, but not the old.
+    pub deprecated: Vec<Text>,
+
+    /// Compatibility of data encoded with the new data
+    /// type with decoders of the old data type.
+    pub compatibility: Compatibility,
+}
+
+impl DataTypeDiff {
+    /// Returns the differences between
+    /// `old` and `new` versions of a data type.
+    fn new(old: &DataType, new: &DataType) -> Self {
+        let mut diff = Self {
+            name: new.name.clone(),
+            old_ordinal: old.ordinal(),
+            new_ordinal: new.ordinal(),
+            added: Vec::new(),
+            removed: Vec::new(),
+            retyped: Vec::new(),
+            reordered: Vec::new(),
+            deprecated: Vec::new(),
+            compatibility: new.compatible_with(old),
+        };
+
+        for old_field in old.iter() {
+            match new.field_ignore_ascii_case(&old_field.name) {
+                Some(new_field) => {
+                    if new_field.deprecated && !old_field.deprecated {
+                        diff.deprecated.push(new_field.name.clone());
+                    }
+
+                    if old_field.typing != new_field.typing
+                        || old_field.optional != new_field.optional
+                    {
+                        diff.retyped.push((old_field.clone(), new_field.clone()));
+                    }
+                }
+                None => diff.removed.push(old_field.clone()),
+            }
+        }
+
+        for new_field in new.iter() {
+            if old.field_ignore_ascii_case(&new_field.name).is_none() {
+                diff.added.push(new_field.clone());
+            }
+        }
+
+        // Fields are reordered if the fields in both
+        // data types are in a different relative order,
+        // ignoring fields which were added or removed.
+        let old_shared = old
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| new.field_ignore_ascii_case(&field.name).is_some());
+        let new_shared = new
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| old.field_ignore_ascii_case(&field.name).is_some());
+        for ((old_position, old_field), (_, new_field)) in old_shared.zip(new_shared) {
+            if !old_field.name.eq_ignore_ascii_case(&new_field.name) {
+                let new_position = new
+                    .iter()
+                    .position(|field| field.name.eq_ignore_ascii_case(&old_field.name))
+                    .unwrap_or_default();
+                diff.reordered
+                    .push((old_field.name.clone(), old_position, new_position));
+            }
+        }
+
+        diff
+    }
+
+    /// Returns true iff data encoded with the new data type
+    /// can't be decoded by decoders of the old data type:
+    /// its ordinal changed, or fields were removed, retyped,
+    /// or reordered, or its formats are incompatible.
+    ///
+    /// Appending or deprecating fields isn't breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.old_ordinal != self.new_ordinal
+            || !self.removed.is_empty()
+            || !self.retyped.is_empty()
+            || !self.reordered.is_empty()
+            || !self.compatibility.is_compatible()
+    }
+
+    /// Returns true iff the data types are
+    /// identical (aside from their docs).
+    fn is_empty(&self) -> bool {
+        self.old_ordinal == self.new_ordinal
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.retyped.is_empty()
+            && self.reordered.is_empty()
+            && self.deprecated.is_empty()
+            && self.compatibility == Compatibility::Identical
+    }
+}
+
+impl Display for DataTypeDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = &self.name;
+
+        if self.old_ordinal != self.new_ordinal {
+            writeln!(
+                f,
+                "~ Modified type '{name}': changed ordinal from {} to {}",
+                self.old_ordinal, self.new_ordinal
+            )?;
+        }
+
+        for field in &self.added {
+            writeln!(
+                f,
+                "~ Modified type '{name}': added field '{}' ({})",
+                field.name,
+                FieldTyping(field)
+            )?;
+        }
+
+        for field in &self.removed {
+            writeln!(f, "- Removed field '{}' from '{name}'", field.name)?;
+        }
+
+        for (old, new) in &self.retyped {
+            writeln!(
+                f,
+                "~ Modified type '{name}': changed field '{}' from {} to {}",
+                new.name,
+                FieldTyping(old),
+                FieldTyping(new)
+            )?;
+        }
+
+        for field in &self.deprecated {
+            writeln!(f, "~ Modified type '{name}': deprecated field '{field}'")?;
+        }
+
+        for (field, old_position, new_position) in &self.reordered {
+            writeln!(
+                f,
+                "~ Modified type '{name}': moved field '{field}' from position {old_position} to {new_position}"
+            )?;
+        }
+
+        if self.is_breaking() {
+            let reason = match &self.compatibility {
+                Compatibility::Incompatible { reason } => reason.clone(),
+                _ => Text::from("fields were removed, retyped, or reordered"),
+            };
+            writeln!(f, "  '{name}' is not backward-compatible: {reason}")
+        } else {
+            writeln!(f, "  '{name}' is backward-compatible")
+        }
+    }
+}
+
+/// Formats the type of a [`DataField`],
+/// including its optionality.
+struct FieldTyping<'a>(&'a DataField);
+
+impl Display for FieldTyping<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.0.optional {
+            write!(f, "optional ")?;
+        }
+
+        write!(f, "{}", self.0.typing.description())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::types::Type;
+
+    use super::*;
+
+    /// Returns a data type named `name` with
+    /// `ordinal` and data `fields`.
+    fn data(name: &'static str, ordinal: u8, fields: &[(&'static str, Type)]) -> DataType {
+        let fields: Vec<DataField> = fields
+            .iter()
+            .map(|(name, typing)| DataField {
+                name: Text::from(name),
+                typing: typing.clone(),
+                ..Default::default()
+            })
+            .collect();
+        DataType::new(Text::from(name), None, ordinal, &[], &fields)
+    }
+
+    fn coda(data: &[DataType]) -> Coda {
+        Coda::new(Text::from("Test"), Text::from("Test"), None, data)
+    }
+
+    #[test]
+    fn diffs_codas() {
+        let old = coda(&[
+            data(
+                "User",
+                1,
+                &[("name", Type::Text), ("legacy_id", Type::Text)],
+            ),
+            data("Legacy", 2, &[("id", Type::Text)]),
+        ]);
+        assert!(old.diff(&old).is_empty());
+
+        // Appending fields and types isn't breaking.
+        let new = coda(&[
+            data(
+                "User",
+                1,
+                &[
+                    ("name", Type::Text),
+                    ("legacy_id", Type::Text),
+                    ("email", Type::Text),
+                ],
+            ),
+            data("Legacy", 2, &[("id", Type::Text)]),
+            data("Event", 3, &[("at", Type::Text)]),
+        ]);
+        let diff = old.diff(&new);
+        assert!(!diff.is_breaking(), "{diff}");
+        assert_eq!(
+            "+ Added type 'Event'\n\
+             ~ Modified type 'User': added field 'email' (text)\n  \
+             'User' is backward-compatible\n",
+            diff.to_string()
+        );
+
+        // Removing types and fields is breaking.
+        let new = coda(&[data("User", 1, &[("name", Type::Text)])]);
+        let diff = old.diff(&new);
+        assert!(diff.is_breaking());
+        assert_eq!(alloc::vec![Text::from("Legacy")], diff.removed);
+        assert_eq!(1, diff.modified.len());
+        assert_eq!("legacy_id", diff.modified[0].removed[0].name.to_string());
+        assert!(!diff.modified[0].compatibility.is_compatible());
+
+        // Retyping fields and reassigning ordinals is breaking.
+        let new = coda(&[
+            data("Legacy", 1, &[("id", Type::U64)]),
+            data(
+                "User",
+                2,
+                &[("name", Type::Text), ("legacy_id", Type::Text)],
+            ),
+        ]);
+        let diff = old.diff(&new);
+        assert!(diff.is_breaking());
+        assert_eq!(2, diff.modified.len());
+        assert_eq!(
+            (1, 2),
+            (diff.modified[0].old_ordinal, diff.modified[0].new_ordinal)
+        );
+        assert_eq!(1, diff.modified[1].retyped.len());
+        assert!(!diff.modified[1].compatibility.is_compatible());
+    }
+
+    #[test]
+    fn diffs_added_and_removed_types() {
+        let old = coda(&[data("User", 1, &[("name", Type::Text)])]);
+        let new = coda(&[
+            data("User", 1, &[("name", Type::Text)]),
+            data("Event", 2, &[("at", Type::U64)]),
+        ]);
+
+        let diff = super::diff(&old, &new);
+        assert_eq!(alloc::vec![Text::from("Event")], diff.added);
+        assert!(!diff.is_breaking());
+
+        let diff = super::diff(&new, &old);
+        assert_eq!(alloc::vec![Text::from("Event")], diff.removed);
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn diffs_renamed_types() {
+        let old = coda(&[data("User", 1, &[("name", Type::Text)])]);
+        let new = coda(&[data("Account", 1, &[("name", Type::Text)])]);
+
+        let diff = super::diff(&old, &new);
+        assert_eq!(
+            alloc::vec![(Text::from("User"), Text::from("Account"))],
+            diff.renamed
+        );
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert!(diff.modified.is_empty());
+        assert!(!diff.is_breaking());
+        assert_eq!("~ Renamed type 'User' to 'Account'\n", diff.to_string());
+
+        // Renamed types are diffed like any other type.
+        let new = coda(&[data("Account", 1, &[("name", Type::U32)])]);
+        let diff = super::diff(&old, &new);
+        assert_eq!(1, diff.renamed.len());
+        assert_eq!(1, diff.modified[0].retyped.len());
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn diffs_added_and_removed_fields() {
+        let old = coda(&[data("User", 1, &[("name", Type::Text)])]);
+        let new = coda(&[data(
+            "User",
+            1,
+            &[("name", Type::Text), ("email", Type::Text)],
+        )]);
+
+        let diff = super::diff(&old, &new);
+        assert_eq!("email", diff.modified[0].added[0].name.to_string());
+        assert!(!diff.is_breaking());
+
+        let diff = super::diff(&new, &old);
+        assert_eq!("email", diff.modified[0].removed[0].name.to_string());
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn diffs_deprecations() {
+        let old =
+            crate::parse::parse("# `Test` Coda\n\n## `User` Data\n\n+ `id` u32\n+ `old_id` u64\n")
+                .unwrap();
+        let new = crate::parse::parse(
+            "# `Test` Coda\n\n## `User` Data\n\n> Deprecated: Use `Account`.\n\n+ `id` u32\n+ `old_id` deprecated u64\n",
+        )
+        .unwrap();
+
+        let diff = super::diff(&old, &new);
+        assert_eq!(alloc::vec![Text::from("User")], diff.deprecated);
+        assert_eq!(
+            alloc::vec![Text::from("old_id")],
+            diff.modified[0].deprecated
+        );
+        assert!(!diff.is_breaking());
+        assert_eq!(
+            "~ Deprecated type 'User'\n~ Modified type 'User': deprecated field 'old_id'\n  'User' is backward-compatible\n",
+            diff.to_string()
+        );
+
+        // Un-deprecating isn't a deprecation.
+        assert!(super::diff(&new, &old).is_empty());
+    }
+
+    #[test]
+    fn diffs_retyped_fields() {
+        let old = coda(&[data("User", 1, &[("id", Type::U32)])]);
+        let new = coda(&[data("User", 1, &[("id", Type::U64)])]);
+
+        let diff = super::diff(&old, &new);
+        let (old_field, new_field) = &diff.modified[0].retyped[0];
+        assert_eq!(
+            (&Type::U32, &Type::U64),
+            (&old_field.typing, &new_field.typing)
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn diffs_reordered_fields() {
+        let old = coda(&[data(
+            "User",
+            1,
+            &[
+                ("first", Type::Text),
+                ("legacy", Type::U8),
+                ("last", Type::Text),
+            ],
+        )]);
+
+        // Removing a field doesn't reorder the rest.
+        let new = coda(&[data(
+            "User",
+            1,
+            &[("first", Type::Text), ("last", Type::Text)],
+        )]);
+        assert!(super::diff(&old, &new).modified[0].reordered.is_empty());
+
+        // Swapping fields of the same type doesn't change
+        // the format, but does change their meaning.
+        let new = coda(&[data(
+            "User",
+            1,
+            &[
+                ("last", Type::Text),
+                ("legacy", Type::U8),
+                ("first", Type::Text),
+            ],
+        )]);
+        let diff = super::diff(&old, &new);
+        let modified = &diff.modified[0];
+        assert!(modified.compatibility.is_compatible());
+        assert_eq!(
+            alloc::vec![(Text::from("first"), 0, 2), (Text::from("last"), 2, 0)],
+            modified.reordered
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn diffs_reassigned_ordinals() {
+        let old = coda(&[
+            data("User", 1, &[("name", Type::Text)]),
+            data("Event", 2, &[("at", Type::U64)]),
+        ]);
+        let new = coda(&[
+            data("Event", 1, &[("at", Type::U64)]),
+            data("User", 2, &[("name", Type::Text)]),
+        ]);
+
+        let diff = super::diff(&old, &new);
+        assert!(diff.renamed.is_empty());
+        let ordinals: Vec<_> = diff
+            .modified
+            .iter()
+            .map(|modified| {
+                (
+                    modified.name.to_string(),
+                    modified.old_ordinal,
+                    modified.new_ordinal,
+                )
+            })
+            .collect();
+        assert_eq!(
+            alloc::vec![("User".to_string(), 1, 2), ("Event".to_string(), 2, 1)],
+            ordinals
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn checks_coda_compatibility() {
+        let old = coda(&[
+            data("User", 1, &[("name", Type::Text)]),
+            data("Legacy", 2, &[("id", Type::U8)]),
+        ]);
+
+        // Appending optional fields and removing types is compatible.
+        let email = DataField {
+            name: Text::from("email"),
+            typing: Type::Text,
+            optional: true,
+            ..Default::default()
+        };
+        let new = coda(&[data("User", 1, &[("name", Type::Text)]).with(email)]);
+        assert_eq!(Ok(()), check_coda_compat(&old, &new));
+
+        // Retyping fields isn't.
+        let new = coda(&[
+            data("User", 1, &[("name", Type::U32)]),
+            data("Legacy", 2, &[("id", Type::U8)]),
+        ]);
+        let reasons = check_coda_compat(&old, &new).unwrap_err();
+        assert_eq!(1, reasons.len());
+        assert_eq!("User", reasons[0].data_type.to_string());
+        assert!(
+            reasons[0]
+                .to_string()
+                .starts_with("'User' is not backward-compatible: "),
+            "{}",
+            reasons[0]
+        );
+    }
+}
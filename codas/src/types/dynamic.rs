@@ -1,5 +1,8 @@
 //! ## Unstable
+
+use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::format;
 use alloc::vec::Vec;
 
 use crate::codec::{
@@ -7,7 +10,17 @@ use crate::codec::{
     UnexpectedDataFormatSnafu, UnsupportedDataFormatSnafu, WritesEncodable,
 };
 
-use super::{Text, Type};
+use snafu::Snafu;
+
+use super::{
+    number::{VarI64, VarU64},
+    DataField, DataType, EnumType, Text, Type,
+};
+
+pub mod json;
+pub use json::{from_json, from_json_in, to_json, JsonError};
+pub mod typed;
+pub use typed::{decode_typed, TypedDecodeError};
 
 /// A value whose type is not specified.
 ///
@@ -35,11 +48,37 @@ pub enum Unspecified {
     I32(i32),
     U64(u64),
     I64(i64),
+    U128(u128),
+    I128(i128),
     F32(f32),
     F64(f64),
     Bool(bool),
+    Char(char),
+    UVar(VarU64),
+    IVar(VarI64),
     Text(Text),
 
+    /// Opaque bytes.
+    Bytes(Vec<u8>),
+
+    /// Milliseconds since the Unix epoch
+    /// (refer to [`super::Timestamp`]).
+    Timestamp(i64),
+
+    /// The ordinal of a variant of an enum.
+    ///
+    /// Enums are encoded as their variant's ordinal
+    /// alone, so enums decoded without knowing their
+    /// type have an unnamed `typing` with no variants.
+    /// Ordinals aren't checked against the `typing`'s
+    /// variants, so ordinals unknown to the `typing`
+    /// (like those of variants added by newer codas)
+    /// are preserved as-is.
+    Enum {
+        typing: EnumType,
+        variant: u16,
+    },
+
     /// List of dynamic values.
     List(Vec<Unspecified>),
 
@@ -75,10 +114,18 @@ impl Unspecified {
             Unspecified::I32(_) => Type::I32,
             Unspecified::U64(_) => Type::U64,
             Unspecified::I64(_) => Type::I64,
+            Unspecified::U128(_) => Type::U128,
+            Unspecified::I128(_) => Type::I128,
             Unspecified::F32(_) => Type::F32,
             Unspecified::F64(_) => Type::F64,
             Unspecified::Bool(_) => Type::Bool,
+            Unspecified::Char(_) => Type::Char,
+            Unspecified::UVar(_) => Type::UVar,
+            Unspecified::IVar(_) => Type::IVar,
             Unspecified::Text(_) => Type::Text,
+            Unspecified::Bytes(_) => Type::Bytes,
+            Unspecified::Timestamp(_) => Type::Timestamp,
+            Unspecified::Enum { typing, .. } => Type::Enum(typing.clone()),
             Unspecified::List(_) => Type::List(alloc::boxed::Box::new(Type::Unspecified)),
             Unspecified::Map(_) => {
                 Type::Map(alloc::boxed::Box::new((Type::Text, Type::Unspecified)))
@@ -113,10 +160,21 @@ impl Unspecified {
             Type::I32 => Unspecified::I32(0),
             Type::U64 => Unspecified::U64(0),
             Type::I64 => Unspecified::I64(0),
+            Type::U128 => Unspecified::U128(0),
+            Type::I128 => Unspecified::I128(0),
             Type::F32 => Unspecified::F32(0.0),
             Type::F64 => Unspecified::F64(0.0),
             Type::Bool => Unspecified::Bool(false),
+            Type::Char => Unspecified::Char(char::default()),
+            Type::UVar => Unspecified::UVar(VarU64::default()),
+            Type::IVar => Unspecified::IVar(VarI64::default()),
             Type::Text => Unspecified::Text(Text::default()),
+            Type::Bytes => Unspecified::Bytes(Vec::new()),
+            Type::Timestamp => Unspecified::Timestamp(0),
+            Type::Enum(typing) => Unspecified::Enum {
+                typing: typing.clone(),
+                variant: 0,
+            },
             Type::Data(typing) => Unspecified::Data {
                 header: DataHeader {
                     count: 0,
@@ -137,6 +195,7 @@ impl Unspecified {
             Unspecified::Text(_) => 244,
             Unspecified::List(_) => 243,
             Unspecified::Map(_) => 242,
+            Unspecified::Enum { .. } => 234,
             // Data preserves the original wire ordinal.
             Unspecified::Data { header, .. } => header.format.ordinal,
             // All other variants delegate to their Type's ordinal.
@@ -148,14 +207,554 @@ impl Unspecified {
     fn scalar_blob_size(&self) -> u16 {
         match self {
             Unspecified::U8(_) | Unspecified::I8(_) | Unspecified::Bool(_) => 1,
-            Unspecified::U16(_) | Unspecified::I16(_) => 2,
-            Unspecified::U32(_) | Unspecified::I32(_) | Unspecified::F32(_) => 4,
-            Unspecified::U64(_) | Unspecified::I64(_) | Unspecified::F64(_) => 8,
+            Unspecified::U16(_) | Unspecified::I16(_) | Unspecified::Enum { .. } => 2,
+            Unspecified::U32(_)
+            | Unspecified::I32(_)
+            | Unspecified::F32(_)
+            | Unspecified::Char(_) => 4,
+            Unspecified::U64(_)
+            | Unspecified::I64(_)
+            | Unspecified::F64(_)
+            | Unspecified::Timestamp(_) => 8,
+            Unspecified::U128(_) | Unspecified::I128(_) => 16,
             _ => 0,
         }
     }
 }
 
+// Accessors //////////////////////////////////////////////
+
+impl Unspecified {
+    /// Returns the value of the field `name`, if
+    /// this is an [`Unspecified::Map`] containing it.
+    pub fn get(&self, name: &str) -> Option<&Unspecified> {
+        self.as_map()?.get(name)
+    }
+
+    /// Returns the value at `path`, if it exists.
+    ///
+    /// Paths are `.`-separated field names, each of which
+    /// may be followed by one or more `[index]`es into
+    /// an [`Unspecified::List`] (like `nested.boolean`
+    /// or `two_d[1][0]`).
+    pub fn get_path(&self, path: &str) -> Option<&Unspecified> {
+        let mut value = self;
+        for segment in PathSegment::parse(path)? {
+            value = match segment {
+                PathSegment::Field(name) => value.get(name)?,
+                PathSegment::Index(index) => value.as_list()?.get(index)?,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Sets the value at `path` (refer to [`Self::get_path`])
+    /// to `value`.
+    ///
+    /// Any [`Unspecified::Default`] values along the path
+    /// are replaced with empty maps or lists, and lists
+    /// are padded with [`Unspecified::Default`]s
+    /// up to any index in the path.
+    pub fn set_path(&mut self, path: &str, value: Unspecified) -> Result<(), TryFromDynamicError> {
+        let segments = match PathSegment::parse(path) {
+            Some(segments) => segments,
+            None => return InvalidPathSnafu { path }.fail(),
+        };
+
+        let mut current = self;
+        for segment in segments {
+            current = match segment {
+                PathSegment::Field(name) => {
+                    if *current == Unspecified::Default {
+                        *current = Unspecified::Map(BTreeMap::new());
+                    }
+
+                    match current {
+                        Unspecified::Map(fields) => fields.entry(name.into()).or_default(),
+                        other => return Err(TryFromDynamicError::unexpected("data", other)),
+                    }
+                }
+                PathSegment::Index(index) => {
+                    if *current == Unspecified::Default {
+                        *current = Unspecified::List(Vec::new());
+                    }
+
+                    match current {
+                        Unspecified::List(items) => {
+                            if items.len() <= index {
+                                items.resize(index + 1, Unspecified::Default);
+                            }
+                            &mut items[index]
+                        }
+                        other => return Err(TryFromDynamicError::unexpected("list", other)),
+                    }
+                }
+            };
+        }
+
+        *current = value;
+        Ok(())
+    }
+
+    /// Returns this value as an `i64`, if it's an integer
+    /// within the range of an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Unspecified::U8(value) => Some(value.into()),
+            Unspecified::I8(value) => Some(value.into()),
+            Unspecified::U16(value) => Some(value.into()),
+            Unspecified::I16(value) => Some(value.into()),
+            Unspecified::U32(value) => Some(value.into()),
+            Unspecified::I32(value) => Some(value.into()),
+            Unspecified::U64(value) => value.try_into().ok(),
+            Unspecified::I64(value) => Some(value),
+            Unspecified::U128(value) => value.try_into().ok(),
+            Unspecified::I128(value) => value.try_into().ok(),
+            Unspecified::UVar(value) => u64::from(value).try_into().ok(),
+            Unspecified::IVar(value) => Some(value.into()),
+            Unspecified::Timestamp(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as an `f64`,
+    /// if it's a floating-point number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Unspecified::F32(value) => Some(value.into()),
+            Unspecified::F64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as text, if it's text.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Unspecified::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns this value's bytes, if it's bytes.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Unspecified::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a `bool`, if it's a `bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Unspecified::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns this value's items, if it's a list.
+    pub fn as_list(&self) -> Option<&[Unspecified]> {
+        match self {
+            Unspecified::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns this value's entries, if it's a map.
+    pub fn as_map(&self) -> Option<&BTreeMap<Text, Unspecified>> {
+        match self {
+            Unspecified::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+/// Segment of a path passed to [`Unspecified::get_path`].
+enum PathSegment<'a> {
+    /// A field of an [`Unspecified::Map`].
+    Field(&'a str),
+
+    /// An index into an [`Unspecified::List`].
+    Index(usize),
+}
+
+impl<'a> PathSegment<'a> {
+    /// Parses the segments of `path`,
+    /// or returns `None` if it's invalid.
+    fn parse(path: &'a str) -> Option<Vec<PathSegment<'a>>> {
+        let mut segments = Vec::new();
+        for (i, part) in path.split('.').enumerate() {
+            let (name, mut indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+
+            // Only the first part of a path may start with an index.
+            if !name.is_empty() {
+                segments.push(PathSegment::Field(name));
+            } else if i > 0 || indices.is_empty() {
+                return None;
+            }
+
+            while !indices.is_empty() {
+                let (index, rest) = indices.strip_prefix('[')?.split_once(']')?;
+                segments.push(PathSegment::Index(index.parse().ok()?));
+                indices = rest;
+            }
+        }
+
+        Some(segments)
+    }
+}
+
+// Verification ///////////////////////////////////////////
+
+impl Unspecified {
+    /// Inserts `value` as the field `name` of this
+    /// value, which represents data with `data_type`.
+    ///
+    /// Iff `data_type` has no field `name`, or `value`
+    /// doesn't match the field's type (refer to
+    /// [`Self::verify_type`]), an error is returned
+    /// and this value is unchanged.
+    ///
+    /// If this value is [`Unspecified::Default`], it's
+    /// replaced with an empty [`Unspecified::Map`].
+    pub fn try_insert(
+        &mut self,
+        data_type: &DataType,
+        name: &str,
+        value: Unspecified,
+    ) -> Result<(), DynamicError> {
+        let field = match data_type.iter().find(|field| *field.name == *name) {
+            Some(field) => field,
+            None => {
+                return NoSuchFieldSnafu {
+                    data_type: data_type.name.clone(),
+                    field: name,
+                }
+                .fail()
+            }
+        };
+
+        if !(field.optional && value == Unspecified::Default) {
+            value.verify_field(&field.typing, &field.name)?;
+        }
+
+        if *self == Unspecified::Default {
+            *self = Unspecified::Map(BTreeMap::new());
+        }
+
+        match self {
+            Unspecified::Map(fields) => {
+                fields.insert(field.name.clone(), value);
+                Ok(())
+            }
+            other => TypeMismatchSnafu {
+                field: "",
+                expected: data_type.name.clone(),
+                actual: other.as_type(),
+            }
+            .fail(),
+        }
+    }
+
+    /// Verifies that this value can represent data with `typing`.
+    ///
+    /// Scalars and text must have the variant matching
+    /// `typing`; lists and maps are verified item-by-item,
+    /// with maps whose keys aren't text represented as
+    /// lists of `[key, value]` pairs (like [`DynamicValue`]).
+    ///
+    /// Data types are represented by [`Unspecified::Map`]s,
+    /// whose entries must each match a field of the data type,
+    /// or by opaque [`Unspecified::Data`] with the data
+    /// type's ordinal. Any value can represent
+    /// [`Type::Unspecified`] data.
+    pub fn verify_type(&self, typing: &Type) -> Result<(), DynamicError> {
+        self.verify_field(typing, "")
+    }
+
+    /// Verifies this value against `typing` (refer to
+    /// [`Self::verify_type`]), where `path` is the
+    /// path of the value within its parent data.
+    fn verify_field(&self, typing: &Type, path: &str) -> Result<(), DynamicError> {
+        let mismatch = || {
+            TypeMismatchSnafu {
+                field: path,
+                expected: typing.description(),
+                actual: self.as_type(),
+            }
+            .fail()
+        };
+
+        match (typing, self) {
+            (Type::Unspecified, _) => Ok(()),
+
+            (Type::List(item), Unspecified::List(items)) => {
+                for (i, value) in items.iter().enumerate() {
+                    value.verify_field(item, &format!("{path}[{i}]"))?;
+                }
+                Ok(())
+            }
+
+            (Type::Map(map), Unspecified::Map(entries)) if map.0 == Type::Text => {
+                for (key, value) in entries {
+                    value.verify_field(&map.1, &format!("{path}[{key}]"))?;
+                }
+                Ok(())
+            }
+            (Type::Map(map), Unspecified::List(pairs)) if map.0 != Type::Text => {
+                for (i, pair) in pairs.iter().enumerate() {
+                    let path = format!("{path}[{i}]");
+                    match pair {
+                        Unspecified::List(pair) if pair.len() == 2 => {
+                            pair[0].verify_field(&map.0, &path)?;
+                            pair[1].verify_field(&map.1, &path)?;
+                        }
+                        pair => {
+                            return TypeMismatchSnafu {
+                                field: path,
+                                expected: "a [key, value] pair",
+                                actual: pair.as_type(),
+                            }
+                            .fail()
+                        }
+                    }
+                }
+                Ok(())
+            }
+
+            (Type::Data(data_type), Unspecified::Data { header, .. }) => match data_type.format() {
+                Format::Fluid => Ok(()),
+                format if format.as_data_format().ordinal == header.format.ordinal => Ok(()),
+                _ => mismatch(),
+            },
+            (Type::Data(data_type), Unspecified::Map(_))
+                if matches!(data_type.format(), Format::Fluid) =>
+            {
+                Ok(())
+            }
+            (Type::Data(data_type), Unspecified::Map(fields)) => {
+                for (name, value) in fields {
+                    let field_path = if path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{path}.{name}").into()
+                    };
+
+                    let Some(field) = data_type.iter().find(|field| field.name == *name) else {
+                        return NoSuchFieldSnafu {
+                            data_type: data_type.name.clone(),
+                            field: field_path,
+                        }
+                        .fail();
+                    };
+
+                    if !(field.optional && *value == Unspecified::Default) {
+                        value.verify_field(&field.typing, &field_path)?;
+                    }
+                }
+                Ok(())
+            }
+
+            (Type::List(_) | Type::Map(_) | Type::Data(_), _) => mismatch(),
+            (scalar, value) if *scalar == value.as_type() => Ok(()),
+            _ => mismatch(),
+        }
+    }
+}
+
+// Merging ////////////////////////////////////////////////
+
+impl Unspecified {
+    /// Merges the fields of `other` into this value,
+    /// where both values represent data with `data_type`.
+    ///
+    /// Every field of `other` which isn't [`Unspecified::Default`]
+    /// overwrites the same field of this value; nested data is
+    /// overwritten, not merged. Iff either value doesn't match
+    /// `data_type` (refer to [`Self::verify_type`]), an error
+    /// is returned and this value is unchanged.
+    ///
+    /// If this value is [`Unspecified::Default`], it's
+    /// replaced with an empty [`Unspecified::Map`].
+    pub fn merge(&mut self, data_type: &DataType, other: &Unspecified) -> Result<(), DynamicError> {
+        self.merge_fields(data_type, other, |_, value| *value != Unspecified::Default)
+    }
+
+    /// Merges the fields of `other` into this value like
+    /// [`Self::merge`], except for fields of `other` with
+    /// their type's default value (refer to [`Self::default_of`]).
+    pub fn merge_non_default(
+        &mut self,
+        data_type: &DataType,
+        other: &Unspecified,
+    ) -> Result<(), DynamicError> {
+        self.merge_fields(data_type, other, |field, value| {
+            *value != Unspecified::Default && *value != Unspecified::default_of(&field.typing)
+        })
+    }
+
+    /// Merges the fields of `other` for which `merges`
+    /// returns true into this value (refer to [`Self::merge`]).
+    fn merge_fields(
+        &mut self,
+        data_type: &DataType,
+        other: &Unspecified,
+        merges: impl Fn(&DataField, &Unspecified) -> bool,
+    ) -> Result<(), DynamicError> {
+        let typing = Type::Data(data_type.clone());
+        for value in [&*self, other] {
+            match value {
+                Unspecified::Default => {}
+                Unspecified::Map(_) => value.verify_type(&typing)?,
+                value => {
+                    return TypeMismatchSnafu {
+                        field: "",
+                        expected: data_type.name.clone(),
+                        actual: value.as_type(),
+                    }
+                    .fail()
+                }
+            }
+        }
+
+        let Unspecified::Map(other_fields) = other else {
+            return Ok(());
+        };
+        if *self == Unspecified::Default {
+            *self = Unspecified::Map(BTreeMap::new());
+        }
+        let Unspecified::Map(fields) = self else {
+            return Ok(());
+        };
+
+        for field in data_type.iter() {
+            if let Some(value) = other_fields.get(&field.name) {
+                if merges(field, value) {
+                    fields.insert(field.name.clone(), value.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when [`Unspecified`] data
+/// doesn't match its declared type.
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum DynamicError {
+    #[snafu(display("`{data_type}` has no field `{field}`"))]
+    NoSuchField { data_type: Text, field: Text },
+
+    #[snafu(display("expected {expected} at `{field}`, but found {actual:?}"))]
+    TypeMismatch {
+        field: Text,
+        expected: Text,
+        actual: Box<Type>,
+    },
+}
+
+// Maps ///////////////////////////////////////////////////
+
+/// A mapping between unspecified keys and
+/// values with declared types, which can
+/// be built and queried dynamically.
+///
+/// Entries are stored in insertion order as a list
+/// of key-value pairs, so [`Self::get`] and
+/// [`Self::insert`] take time linear in the
+/// number of entries.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DynamicMap {
+    typing: (Type, Type),
+    entries: Vec<(Unspecified, Unspecified)>,
+}
+
+impl DynamicMap {
+    /// Returns a new, empty map from keys
+    /// with `key_typing` to values with
+    /// `value_typing`.
+    pub fn new(key_typing: Type, value_typing: Type) -> Self {
+        Self {
+            typing: (key_typing, value_typing),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Inserts `value` under `key`, replacing any
+    /// value already under an equal key.
+    ///
+    /// Iff `key` or `value` doesn't match the map's key
+    /// or value typing (refer to [`Unspecified::verify_type`]),
+    /// an error is returned and the map is unchanged.
+    pub fn insert(&mut self, key: Unspecified, value: Unspecified) -> Result<(), DynamicError> {
+        key.verify_field(&self.typing.0, "key")?;
+        value.verify_field(&self.typing.1, "value")?;
+
+        match self.entries.iter_mut().find(|(other, _)| *other == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((key, value)),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value under `key`, if any.
+    pub fn get(&self, key: &Unspecified) -> Option<&Unspecified> {
+        self.entries
+            .iter()
+            .find(|(other, _)| other == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true iff the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the map's
+    /// entries, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Unspecified, &Unspecified)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Removes all entries from the map.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Converts a map into unspecified data like [`DynamicValue`]:
+/// maps with text keys become [`Unspecified::Map`]s, and
+/// other maps become lists of `[key, value]` pairs.
+impl From<DynamicMap> for Unspecified {
+    fn from(map: DynamicMap) -> Self {
+        if map.typing.0 == Type::Text {
+            return Unspecified::Map(
+                map.entries
+                    .into_iter()
+                    .filter_map(|(key, value)| match key {
+                        Unspecified::Text(key) => Some((key, value)),
+                        _ => None,
+                    })
+                    .collect(),
+            );
+        }
+
+        Unspecified::List(
+            map.entries
+                .into_iter()
+                .map(|(key, value)| Unspecified::List(alloc::vec![key, value]))
+                .collect(),
+        )
+    }
+}
+
 // Encoders ///////////////////////////////////////////////
 impl Encodable for Unspecified {
     /// The encoding format of unspecified
@@ -174,10 +773,18 @@ impl Encodable for Unspecified {
             Unspecified::I32(v) => v.encode(writer),
             Unspecified::U64(v) => v.encode(writer),
             Unspecified::I64(v) => v.encode(writer),
+            Unspecified::U128(v) => v.encode(writer),
+            Unspecified::I128(v) => v.encode(writer),
             Unspecified::F32(v) => v.encode(writer),
             Unspecified::F64(v) => v.encode(writer),
             Unspecified::Bool(v) => v.encode(writer),
+            Unspecified::Char(v) => v.encode(writer),
+            Unspecified::UVar(v) => v.encode(writer),
+            Unspecified::IVar(v) => v.encode(writer),
             Unspecified::Text(v) => v.encode(writer),
+            Unspecified::Bytes(v) => v.encode(writer),
+            Unspecified::Timestamp(v) => v.encode(writer),
+            Unspecified::Enum { variant, .. } => variant.encode(writer),
             Unspecified::List(items) => encode_unspecified_list(items, writer),
             Unspecified::Map(map) => {
                 // Collect keys and values for wire encoding as two sub-lists.
@@ -211,9 +818,14 @@ impl Encodable for Unspecified {
             | Unspecified::I32(_)
             | Unspecified::U64(_)
             | Unspecified::I64(_)
+            | Unspecified::U128(_)
+            | Unspecified::I128(_)
             | Unspecified::F32(_)
             | Unspecified::F64(_)
-            | Unspecified::Bool(_) => DataHeader {
+            | Unspecified::Bool(_)
+            | Unspecified::Char(_)
+            | Unspecified::Timestamp(_)
+            | Unspecified::Enum { .. } => DataHeader {
                 count: 1,
                 format: DataFormat {
                     blob_size: self.scalar_blob_size(),
@@ -223,6 +835,11 @@ impl Encodable for Unspecified {
             }
             .encode(writer),
 
+            // Variable-length integers: header with the
+            // integer's own type-tagged format.
+            Unspecified::UVar(v) => v.encode_header(writer),
+            Unspecified::IVar(v) => v.encode_header(writer),
+
             Unspecified::Text(v) => DataHeader {
                 count: codec::try_count(v.len())?,
                 format: DataFormat {
@@ -232,6 +849,15 @@ impl Encodable for Unspecified {
                 },
             }
             .encode(writer),
+            Unspecified::Bytes(v) => DataHeader {
+                count: codec::try_count(v.len())?,
+                format: DataFormat {
+                    blob_size: 1,
+                    data_fields: 0,
+                    ordinal: self.type_ordinal(),
+                },
+            }
+            .encode(writer),
 
             // List: always count=1 wrapping an inner typed header.
             Unspecified::List(_) => DataHeader {
@@ -276,7 +902,11 @@ fn encode_unspecified_list(
 
     // Reject lists containing Default items — they carry no data.
     if items.iter().any(|i| matches!(i, Unspecified::Default)) {
-        return UnsupportedDataFormatSnafu { ordinal: 0u8 }.fail();
+        return UnsupportedDataFormatSnafu {
+            ordinal: 0u8,
+            offset: None,
+        }
+        .fail();
     }
 
     match Unspecified::homogeneous_ordinal(items) {
@@ -348,9 +978,10 @@ fn encode_unspecified_list(
 fn expected_scalar_blob_size(ordinal: u8) -> Option<u16> {
     match Type::from_ordinal(ordinal)? {
         Type::U8 | Type::I8 | Type::Bool => Some(1),
-        Type::U16 | Type::I16 => Some(2),
-        Type::U32 | Type::I32 | Type::F32 => Some(4),
-        Type::U64 | Type::I64 | Type::F64 => Some(8),
+        Type::U16 | Type::I16 | Type::Enum(_) => Some(2),
+        Type::U32 | Type::I32 | Type::F32 | Type::Char => Some(4),
+        Type::U64 | Type::I64 | Type::F64 | Type::Timestamp => Some(8),
+        Type::U128 | Type::I128 => Some(16),
         _ => None,
     }
 }
@@ -364,6 +995,7 @@ fn validate_scalar_format(format: DataFormat) -> Result<(), CodecError> {
             return Err(UnexpectedDataFormatSnafu {
                 expected: Format::Blob(expected),
                 actual: Some(DataHeader { count: 0, format }),
+                offset: None,
             }
             .build());
         }
@@ -401,20 +1033,22 @@ fn decode_unspecified_list(
         Some(Type::Unspecified) if inner.format.data_fields == 0 && count > 0 => {
             return UnsupportedDataFormatSnafu {
                 ordinal: inner.format.ordinal,
+                offset: None,
             }
             .fail();
         }
         Some(Type::Unspecified) if inner.format.data_fields == 0 => {}
 
-        // Structured ordinals (Text, List, Map) require at least one
+        // Structured ordinals (Text, Bytes, UVar, IVar, List, Map) require at least one
         // data field when count > 0. Reject malformed headers like
         // ordinal=Text with data_fields=0 to avoid decoding
         // self-describing elements from blob-only payload.
-        Some(Type::Text | Type::List(_) | Type::Map(_))
+        Some(Type::Text | Type::Bytes | Type::UVar | Type::IVar | Type::List(_) | Type::Map(_))
             if inner.format.data_fields == 0 && count > 0 =>
         {
             return UnsupportedDataFormatSnafu {
                 ordinal: inner.format.ordinal,
+                offset: None,
             }
             .fail();
         }
@@ -476,6 +1110,37 @@ fn decode_unspecified_list(
                 items.push(Unspecified::I64(v));
             }
         }
+        Some(Type::Timestamp) => {
+            for _ in 0..count {
+                let mut v = 0i64;
+                v.decode(reader, None)?;
+                items.push(Unspecified::Timestamp(v));
+            }
+        }
+        Some(Type::Enum(typing)) => {
+            for _ in 0..count {
+                let mut variant = 0u16;
+                variant.decode(reader, None)?;
+                items.push(Unspecified::Enum {
+                    typing: typing.clone(),
+                    variant,
+                });
+            }
+        }
+        Some(Type::U128) => {
+            for _ in 0..count {
+                let mut v = 0u128;
+                v.decode(reader, None)?;
+                items.push(Unspecified::U128(v));
+            }
+        }
+        Some(Type::I128) => {
+            for _ in 0..count {
+                let mut v = 0i128;
+                v.decode(reader, None)?;
+                items.push(Unspecified::I128(v));
+            }
+        }
         Some(Type::F32) => {
             for _ in 0..count {
                 let mut v = 0.0f32;
@@ -497,6 +1162,13 @@ fn decode_unspecified_list(
                 items.push(Unspecified::Bool(v));
             }
         }
+        Some(Type::Char) => {
+            for _ in 0..count {
+                let mut v = char::default();
+                v.decode(reader, None)?;
+                items.push(Unspecified::Char(v));
+            }
+        }
 
         // Structured, heterogeneous, or unknown: each element
         // carries its own self-describing header.
@@ -607,11 +1279,15 @@ impl Decodable for Unspecified {
 
         match Type::from_ordinal(header.format.ordinal) {
             Some(Type::Unspecified) => {
-                // Skip any data that might be present.
-                for _ in 0..header.count {
-                    reader.skip_blob(header.format.blob_size as usize)?;
-                    for _ in 0..header.format.data_fields {
-                        reader.skip_data()?;
+                // Skip any data that might be present; data
+                // with an empty format has nothing to skip,
+                // no matter how many data the header declares.
+                if header.format.blob_size != 0 || header.format.data_fields != 0 {
+                    for _ in 0..header.count {
+                        reader.skip_blob(header.format.blob_size as usize)?;
+                        for _ in 0..header.format.data_fields {
+                            reader.skip_data()?;
+                        }
                     }
                 }
                 *self = Unspecified::Default;
@@ -625,9 +1301,32 @@ impl Decodable for Unspecified {
             Some(Type::I16) => *self = decode_scalar_or_list(reader, header, Unspecified::I16)?,
             Some(Type::I32) => *self = decode_scalar_or_list(reader, header, Unspecified::I32)?,
             Some(Type::I64) => *self = decode_scalar_or_list(reader, header, Unspecified::I64)?,
+            Some(Type::U128) => *self = decode_scalar_or_list(reader, header, Unspecified::U128)?,
+            Some(Type::I128) => *self = decode_scalar_or_list(reader, header, Unspecified::I128)?,
             Some(Type::F32) => *self = decode_scalar_or_list(reader, header, Unspecified::F32)?,
             Some(Type::F64) => *self = decode_scalar_or_list(reader, header, Unspecified::F64)?,
             Some(Type::Bool) => *self = decode_scalar_or_list(reader, header, Unspecified::Bool)?,
+            Some(Type::Char) => *self = decode_scalar_or_list(reader, header, Unspecified::Char)?,
+            Some(Type::Timestamp) => {
+                *self = decode_scalar_or_list(reader, header, Unspecified::Timestamp)?
+            }
+            Some(Type::Enum(_)) => {
+                *self = decode_scalar_or_list(reader, header, |variant| Unspecified::Enum {
+                    typing: EnumType::default(),
+                    variant,
+                })?
+            }
+
+            Some(Type::UVar) => {
+                let mut v = VarU64::default();
+                v.decode(reader, Some(header))?;
+                *self = Unspecified::UVar(v);
+            }
+            Some(Type::IVar) => {
+                let mut v = VarI64::default();
+                v.decode(reader, Some(header))?;
+                *self = Unspecified::IVar(v);
+            }
 
             Some(Type::Text) => {
                 // Create a copy of the original header with the
@@ -646,6 +1345,22 @@ impl Decodable for Unspecified {
                 *self = Unspecified::Text(v);
             }
 
+            Some(Type::Bytes) => {
+                // Bytes are always blob_size=1, data_fields=0.
+                if header.format.blob_size != 1 || header.format.data_fields != 0 {
+                    return UnexpectedDataFormatSnafu {
+                        expected: Type::Bytes.format(),
+                        actual: Some(header),
+                        offset: None,
+                    }
+                    .fail();
+                }
+
+                let mut v = Vec::new();
+                codec::read_bytes_into(reader, &mut v, header.count as usize)?;
+                *self = Unspecified::Bytes(v);
+            }
+
             Some(Type::List(_)) => {
                 // Validate outer header: List is always count=1, blob_size=0, data_fields=1.
                 if header.count != 1
@@ -655,6 +1370,7 @@ impl Decodable for Unspecified {
                     return UnexpectedDataFormatSnafu {
                         expected: Format::data(243).with(Format::Fluid),
                         actual: Some(header),
+                        offset: None,
                     }
                     .fail();
                 }
@@ -671,6 +1387,7 @@ impl Decodable for Unspecified {
                     return UnexpectedDataFormatSnafu {
                         expected: Format::data(242).with(Format::Fluid).with(Format::Fluid),
                         actual: Some(header),
+                        offset: None,
                     }
                     .fail();
                 }
@@ -729,6 +1446,293 @@ impl Decodable for Unspecified {
     }
 }
 
+// Conversions ///////////////////////////////////////////
+
+/// A value which can be converted to and
+/// from [`Unspecified`] data without loss.
+///
+/// Data types are converted to and from
+/// [`Unspecified::Map`]s of their field names
+/// to their field values; Rust code generated
+/// from a coda implements this trait (alongside
+/// [`From`] and [`TryFrom`]) for each data type.
+pub trait DynamicValue: Sized {
+    /// Returns this value as unspecified data.
+    fn to_dynamic(&self) -> Unspecified;
+
+    /// Returns the value contained by `value`.
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError>;
+}
+
+/// Error returned when [`Unspecified`] data can't
+/// be converted into a [`DynamicValue`].
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum TryFromDynamicError {
+    #[snafu(display("missing required field `{field}`"))]
+    MissingField { field: &'static str },
+
+    #[snafu(display("expected {expected}, but found {actual:?}"))]
+    UnexpectedType {
+        expected: &'static str,
+        actual: Box<Type>,
+    },
+
+    #[snafu(display("`{path}` isn't a valid path"))]
+    InvalidPath { path: Text },
+
+    #[snafu(display("{ordinal} is not the ordinal of a known enum variant"))]
+    UnknownVariant { ordinal: u16 },
+}
+
+impl TryFromDynamicError {
+    /// Returns an error for an `actual` value
+    /// which wasn't an `expected` value.
+    fn unexpected(expected: &'static str, actual: &Unspecified) -> Self {
+        UnexpectedTypeSnafu {
+            expected,
+            actual: actual.as_type(),
+        }
+        .build()
+    }
+}
+
+/// Returns the fields of `value`, if
+/// it's an [`Unspecified::Map`].
+pub fn dynamic_fields(
+    value: &Unspecified,
+) -> Result<&BTreeMap<Text, Unspecified>, TryFromDynamicError> {
+    match value {
+        Unspecified::Map(fields) => Ok(fields),
+        _ => Err(TryFromDynamicError::unexpected("data", value)),
+    }
+}
+
+/// Returns the value of the required field
+/// `name` in `fields`.
+pub fn required_field<T: DynamicValue>(
+    fields: &BTreeMap<Text, Unspecified>,
+    name: &'static str,
+) -> Result<T, TryFromDynamicError> {
+    match fields.get(name) {
+        Some(value) => T::try_from_dynamic(value),
+        None => MissingFieldSnafu { field: name }.fail(),
+    }
+}
+
+/// Returns the value of the optional field `name`
+/// in `fields`, or `None` if the field is missing.
+pub fn optional_field<T: DynamicValue>(
+    fields: &BTreeMap<Text, Unspecified>,
+    name: &'static str,
+) -> Result<Option<T>, TryFromDynamicError> {
+    match fields.get(name) {
+        Some(value) => Option::<T>::try_from_dynamic(value),
+        None => Ok(None),
+    }
+}
+
+/// Implements [`DynamicValue`] for
+/// scalars with matching [`Unspecified`]
+/// variants.
+macro_rules! scalar_dynamic_values {
+    ($($variant:ident($typing:ty) $name:literal),+ $(,)?) => {$(
+        impl DynamicValue for $typing {
+            fn to_dynamic(&self) -> Unspecified {
+                Unspecified::$variant(*self)
+            }
+
+            fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+                match value {
+                    Unspecified::$variant(value) => Ok(*value),
+                    _ => Err(TryFromDynamicError::unexpected($name, value)),
+                }
+            }
+        }
+    )+};
+}
+
+scalar_dynamic_values!(
+    U8(u8) "u8",
+    U16(u16) "u16",
+    U32(u32) "u32",
+    U64(u64) "u64",
+    I8(i8) "i8",
+    I16(i16) "i16",
+    I32(i32) "i32",
+    I64(i64) "i64",
+    U128(u128) "u128",
+    I128(i128) "i128",
+    F32(f32) "f32",
+    F64(f64) "f64",
+    Bool(bool) "bool",
+    Char(char) "char",
+    UVar(VarU64) "uvar",
+    IVar(VarI64) "ivar",
+);
+
+impl DynamicValue for Text {
+    fn to_dynamic(&self) -> Unspecified {
+        Unspecified::Text(self.clone())
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        match value {
+            Unspecified::Text(value) => Ok(value.clone()),
+            _ => Err(TryFromDynamicError::unexpected("text", value)),
+        }
+    }
+}
+
+impl DynamicValue for super::Timestamp {
+    fn to_dynamic(&self) -> Unspecified {
+        Unspecified::Timestamp(self.0)
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        match value {
+            Unspecified::Timestamp(value) => Ok(Self(*value)),
+            _ => Err(TryFromDynamicError::unexpected("timestamp", value)),
+        }
+    }
+}
+
+impl DynamicValue for Unspecified {
+    fn to_dynamic(&self) -> Unspecified {
+        self.clone()
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        Ok(value.clone())
+    }
+}
+
+/// `None` is converted to (and from)
+/// [`Unspecified::Default`].
+impl<T: DynamicValue> DynamicValue for Option<T> {
+    fn to_dynamic(&self) -> Unspecified {
+        match self {
+            Some(value) => value.to_dynamic(),
+            None => Unspecified::Default,
+        }
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        match value {
+            Unspecified::Default => Ok(None),
+            value => T::try_from_dynamic(value).map(Some),
+        }
+    }
+}
+
+/// Lists are converted to [`Unspecified::List`]s; lists
+/// of [`u8`] may also be converted from [`Unspecified::Bytes`].
+impl<T: DynamicValue> DynamicValue for Vec<T> {
+    fn to_dynamic(&self) -> Unspecified {
+        Unspecified::List(self.iter().map(T::to_dynamic).collect())
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        match value {
+            Unspecified::List(items) => items.iter().map(T::try_from_dynamic).collect(),
+            Unspecified::Bytes(bytes) => bytes
+                .iter()
+                .map(|byte| T::try_from_dynamic(&Unspecified::U8(*byte)))
+                .collect(),
+            _ => Err(TryFromDynamicError::unexpected("list", value)),
+        }
+    }
+}
+
+/// Maps with text keys are converted to [`Unspecified::Map`]s;
+/// since those maps may _only_ have text keys, all other maps
+/// are converted to [`Unspecified::List`]s of key-value pairs
+/// (each pair being a two-item list).
+impl<K: DynamicValue + Ord, V: DynamicValue> DynamicValue for BTreeMap<K, V> {
+    fn to_dynamic(&self) -> Unspecified {
+        map_to_dynamic(self.iter())
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        match value {
+            Unspecified::Map(entries) => entries
+                .iter()
+                .map(|(key, value)| {
+                    Ok((
+                        K::try_from_dynamic(&Unspecified::Text(key.clone()))?,
+                        V::try_from_dynamic(value)?,
+                    ))
+                })
+                .collect(),
+            Unspecified::List(entries) => entries
+                .iter()
+                .map(|entry| match entry {
+                    Unspecified::List(pair) if pair.len() == 2 => Ok((
+                        K::try_from_dynamic(&pair[0])?,
+                        V::try_from_dynamic(&pair[1])?,
+                    )),
+                    _ => Err(TryFromDynamicError::unexpected("key-value pair", entry)),
+                })
+                .collect(),
+            _ => Err(TryFromDynamicError::unexpected("map", value)),
+        }
+    }
+}
+
+/// Hash maps are converted like [`BTreeMap`]s,
+/// with their entries sorted by key.
+#[cfg(any(feature = "std", test))]
+impl<K, V> DynamicValue for std::collections::HashMap<K, V>
+where
+    K: DynamicValue + Ord + core::hash::Hash,
+    V: DynamicValue,
+{
+    fn to_dynamic(&self) -> Unspecified {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        map_to_dynamic(entries.into_iter())
+    }
+
+    fn try_from_dynamic(value: &Unspecified) -> Result<Self, TryFromDynamicError> {
+        Ok(BTreeMap::<K, V>::try_from_dynamic(value)?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Converts a map's `entries`, sorted by key,
+/// into unspecified data like [`BTreeMap::to_dynamic`].
+fn map_to_dynamic<'a, K, V>(entries: impl Iterator<Item = (&'a K, &'a V)>) -> Unspecified
+where
+    K: DynamicValue + 'a,
+    V: DynamicValue + 'a,
+{
+    let entries: Vec<(Unspecified, Unspecified)> = entries
+        .map(|(key, value)| (key.to_dynamic(), value.to_dynamic()))
+        .collect();
+
+    if entries
+        .iter()
+        .all(|(key, _)| matches!(key, Unspecified::Text(_)))
+    {
+        Unspecified::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| match key {
+                    Unspecified::Text(key) => (key, value),
+                    _ => unreachable!("all keys are text"),
+                })
+                .collect(),
+        )
+    } else {
+        Unspecified::List(
+            entries
+                .into_iter()
+                .map(|(key, value)| Unspecified::List(alloc::vec![key, value]))
+                .collect(),
+        )
+    }
+}
+
 // Serde ///////////////////////////////////////////////
 
 #[cfg(feature = "serde")]
@@ -744,10 +1748,24 @@ impl serde::Serialize for Unspecified {
             Unspecified::I32(v) => v.serialize(serializer),
             Unspecified::U64(v) => v.serialize(serializer),
             Unspecified::I64(v) => v.serialize(serializer),
+            Unspecified::U128(v) => v.serialize(serializer),
+            Unspecified::I128(v) => v.serialize(serializer),
             Unspecified::F32(v) => v.serialize(serializer),
             Unspecified::F64(v) => v.serialize(serializer),
             Unspecified::Bool(v) => v.serialize(serializer),
+            Unspecified::Char(v) => v.serialize(serializer),
+            Unspecified::UVar(v) => v.0.serialize(serializer),
+            Unspecified::IVar(v) => v.0.serialize(serializer),
             Unspecified::Text(v) => v.serialize(serializer),
+            Unspecified::Bytes(v) => serializer.serialize_bytes(v),
+            Unspecified::Timestamp(v) => v.serialize(serializer),
+
+            // Enums serialize as the name of their
+            // variant, if it's known, or its ordinal.
+            Unspecified::Enum { typing, variant } => match typing.variant(*variant) {
+                Some(name) => name.serialize(serializer),
+                None => variant.serialize(serializer),
+            },
             Unspecified::List(items) => {
                 use serde::ser::SerializeSeq;
                 let mut seq = serializer.serialize_seq(Some(items.len()))?;
@@ -850,6 +1868,14 @@ impl<'de> serde::de::Visitor<'de> for UnspecifiedVisitor {
         Ok(Unspecified::I64(v))
     }
 
+    fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Ok(Unspecified::U128(v))
+    }
+
+    fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        Ok(Unspecified::I128(v))
+    }
+
     fn visit_f32<E: serde::de::Error>(self, v: f32) -> Result<Self::Value, E> {
         Ok(Unspecified::F32(v))
     }
@@ -866,6 +1892,14 @@ impl<'de> serde::de::Visitor<'de> for UnspecifiedVisitor {
         Ok(Unspecified::Text(v.into()))
     }
 
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Unspecified::Bytes(v.into()))
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Unspecified::Bytes(v))
+    }
+
     fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
         let mut items = Vec::new();
         while let Some(elem) = seq.next_element::<Unspecified>()? {
@@ -885,7 +1919,7 @@ impl<'de> serde::de::Visitor<'de> for UnspecifiedVisitor {
 
 #[cfg(test)]
 mod tests {
-    use crate::codec::ReadsDecodable;
+    use crate::codec::{decode_from_slice, encode_to_vec, ReadsDecodable};
 
     use super::*;
 
@@ -901,17 +1935,39 @@ mod tests {
             Unspecified::I32(-50_000),
             Unspecified::U64(1_000_000),
             Unspecified::I64(-999_999),
-            Unspecified::F32(3.14),
-            Unspecified::F64(2.718281828),
+            Unspecified::U128(u128::MAX),
+            Unspecified::I128(i128::MIN),
+            Unspecified::F32(3.25),
+            Unspecified::F64(2.5),
             Unspecified::Bool(true),
             Unspecified::Bool(false),
+            Unspecified::Char('🦀'),
+            Unspecified::UVar(VarU64(u64::MAX)),
+            Unspecified::IVar(VarI64(-300)),
             Unspecified::Text("hello world".into()),
             Unspecified::Text("".into()),
+            Unspecified::Bytes(alloc::vec![0xFF, 0x00, 0xC3]),
+            Unspecified::Bytes(alloc::vec![]),
+            Unspecified::Timestamp(1_700_000_000_123),
+            Unspecified::Timestamp(-86_400_000),
+            Unspecified::Enum {
+                typing: EnumType::default(),
+                variant: 2,
+            },
+            Unspecified::List(alloc::vec![
+                Unspecified::Enum {
+                    typing: EnumType::default(),
+                    variant: 0,
+                },
+                Unspecified::Enum {
+                    typing: EnumType::default(),
+                    variant: u16::MAX,
+                },
+            ]),
         ];
 
         for original in &cases {
-            let mut bytes = alloc::vec![];
-            bytes.write_data(original)?;
+            let bytes = encode_to_vec(original)?;
 
             let mut decoded = Unspecified::Default;
             let header: DataHeader = (&mut bytes.as_slice()).read_data()?;
@@ -935,8 +1991,7 @@ mod tests {
             Unspecified::Bool(true),
         ]);
 
-        let mut bytes = alloc::vec![];
-        bytes.write_data(&original)?;
+        let bytes = encode_to_vec(&original)?;
 
         let mut decoded = Unspecified::Default;
         (&mut bytes.as_slice()).read_data_into(&mut decoded)?;
@@ -955,8 +2010,7 @@ mod tests {
             Unspecified::U32(30),
         ]);
 
-        let mut bytes = alloc::vec![];
-        bytes.write_data(&original)?;
+        let bytes = encode_to_vec(&original)?;
 
         // Verify compact encoding: outer(8) + inner(8) + 3*4 = 28 bytes.
         assert_eq!(
@@ -976,8 +2030,7 @@ mod tests {
             Unspecified::Text("world".into()),
         ]);
 
-        let mut bytes = alloc::vec![];
-        bytes.write_data(&original)?;
+        let bytes = encode_to_vec(&original)?;
 
         let mut decoded = Unspecified::Default;
         (&mut bytes.as_slice()).read_data_into(&mut decoded)?;
@@ -994,8 +2047,7 @@ mod tests {
         map.insert(Text::from("b"), Unspecified::Bool(true));
         let original = Unspecified::Map(map);
 
-        let mut bytes = alloc::vec![];
-        bytes.write_data(&original)?;
+        let bytes = encode_to_vec(&original)?;
 
         let mut decoded = Unspecified::Default;
         (&mut bytes.as_slice()).read_data_into(&mut decoded)?;
@@ -1021,8 +2073,7 @@ mod tests {
                 alloc::vec!["five".into(), "six".into()],
             ],
         };
-        let mut static_bytes = alloc::vec![];
-        static_bytes.write_data(&test_data)?;
+        let static_bytes = encode_to_vec(&test_data)?;
 
         // Decode as Unspecified (should capture as Data).
         let mut decoded = Unspecified::Default;
@@ -1030,15 +2081,14 @@ mod tests {
         assert!(matches!(decoded, Unspecified::Data { .. }));
 
         // Re-encode the Unspecified::Data and verify bytes match.
-        let mut re_encoded = alloc::vec![];
-        re_encoded.write_data(&decoded)?;
+        let re_encoded = encode_to_vec(&decoded)?;
         assert_eq!(
             static_bytes, re_encoded,
             "typed round-trip bytes must match"
         );
 
         // Verify the re-encoded bytes decode back to the original typed data.
-        let roundtripped: TestData = re_encoded.as_slice().read_data()?;
+        let roundtripped: TestData = decode_from_slice(&re_encoded)?;
         assert_eq!(test_data, roundtripped);
 
         Ok(())
@@ -1110,8 +2160,7 @@ mod tests {
     #[test]
     pub fn default_encodes_as_zero_header() -> Result<(), CodecError> {
         let value = Unspecified::Default;
-        let mut bytes = alloc::vec![];
-        bytes.write_data(&value)?;
+        let bytes = encode_to_vec(&value)?;
         assert_eq!(8, bytes.len(), "Default should encode as one 8-byte header");
         assert!(
             bytes.iter().all(|&b| b == 0),
@@ -1125,4 +2174,281 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn dynamic_values_round_trip() {
+        let listing: Vec<Option<u16>> = alloc::vec![Some(1), None, Some(3)];
+        let dynamic = listing.to_dynamic();
+        assert_eq!(
+            Unspecified::List(alloc::vec![
+                Unspecified::U16(1),
+                Unspecified::Default,
+                Unspecified::U16(3)
+            ]),
+            dynamic
+        );
+        assert_eq!(
+            listing,
+            Vec::<Option<u16>>::try_from_dynamic(&dynamic).unwrap()
+        );
+
+        // Lists of bytes may be converted from bytes.
+        assert_eq!(
+            alloc::vec![1u8, 2, 3],
+            Vec::<u8>::try_from_dynamic(&Unspecified::Bytes(alloc::vec![1, 2, 3])).unwrap()
+        );
+
+        // Text-keyed maps become unspecified maps.
+        let mapping = BTreeMap::from([(Text::from("a"), 1.5f32), (Text::from("b"), -2.0)]);
+        let dynamic = mapping.to_dynamic();
+        assert!(matches!(dynamic, Unspecified::Map(_)));
+        assert_eq!(
+            mapping,
+            BTreeMap::<Text, f32>::try_from_dynamic(&dynamic).unwrap()
+        );
+
+        // Other maps become lists of pairs.
+        let mapping = BTreeMap::from([(7u32, Text::from("seven")), (8, Text::from("eight"))]);
+        let dynamic = mapping.to_dynamic();
+        assert!(matches!(dynamic, Unspecified::List(_)));
+        assert_eq!(
+            mapping,
+            BTreeMap::<u32, Text>::try_from_dynamic(&dynamic).unwrap()
+        );
+
+        assert_eq!(
+            Err(TryFromDynamicError::UnexpectedType {
+                expected: "u32",
+                actual: Type::I32.into(),
+            }),
+            u32::try_from_dynamic(&Unspecified::I32(7))
+        );
+        assert_eq!(
+            Err(TryFromDynamicError::MissingField { field: "x" }),
+            required_field::<u8>(&BTreeMap::new(), "x")
+        );
+        assert_eq!(Ok(None), optional_field::<u8>(&BTreeMap::new(), "x"));
+    }
+
+    #[test]
+    pub fn builds_dynamic_maps() -> Result<(), CodecError> {
+        let mut map = DynamicMap::new(Type::U32, Type::Text);
+        assert!(map.is_empty());
+
+        map.insert(Unspecified::U32(7), Unspecified::Text("seven".into()))
+            .unwrap();
+        map.insert(Unspecified::U32(8), Unspecified::Text("ate".into()))
+            .unwrap();
+        map.insert(Unspecified::U32(8), Unspecified::Text("eight".into()))
+            .unwrap();
+        assert_eq!(2, map.len());
+        assert_eq!(
+            Some(&Unspecified::Text("eight".into())),
+            map.get(&Unspecified::U32(8))
+        );
+        assert_eq!(None, map.get(&Unspecified::U32(9)));
+        assert_eq!(
+            alloc::vec![&Unspecified::U32(7), &Unspecified::U32(8)],
+            map.iter().map(|(key, _)| key).collect::<Vec<_>>()
+        );
+
+        // Entries must match the map's typings.
+        assert_eq!(
+            Err(DynamicError::TypeMismatch {
+                field: "key".into(),
+                expected: "u32".into(),
+                actual: Type::I32.into(),
+            }),
+            map.insert(Unspecified::I32(9), Unspecified::Text("nine".into()))
+        );
+        assert!(map.insert(Unspecified::U32(9), Unspecified::U8(9)).is_err());
+        assert_eq!(2, map.len());
+
+        // Maps match (and encode like) their native equivalents.
+        let native = BTreeMap::from([(7u32, Text::from("seven")), (8, Text::from("eight"))]);
+        let dynamic = Unspecified::from(map.clone());
+        assert_eq!(native.to_dynamic(), dynamic);
+        let (mut encoded, mut native_encoded) = (alloc::vec![], alloc::vec![]);
+        encoded.write_data(&dynamic)?;
+        native_encoded.write_data(&native.to_dynamic())?;
+        assert_eq!(native_encoded, encoded);
+        assert_eq!(native, BTreeMap::try_from_dynamic(&dynamic).unwrap());
+
+        let mut texts = DynamicMap::new(Type::Text, Type::F32);
+        texts
+            .insert(Unspecified::Text("a".into()), Unspecified::F32(1.5))
+            .unwrap();
+        let native = BTreeMap::from([(Text::from("a"), 1.5f32)]);
+        assert_eq!(native.to_dynamic(), Unspecified::from(texts));
+
+        map.clear();
+        assert!(map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn merges_data() {
+        use crate::types::tests::{NestedTestData, TestData};
+
+        let typing = TestData::typing();
+        let original = TestData {
+            number: 1,
+            floaty: 2.0,
+            text: "original".into(),
+            ..Default::default()
+        }
+        .to_dynamic();
+        let patch = TestData {
+            number: 0,
+            floaty: 3.0,
+            nested: NestedTestData { boolean: true },
+            ..Default::default()
+        }
+        .to_dynamic();
+
+        // Every field of the patch is merged.
+        let mut merged = original.clone();
+        merged.merge(&typing, &patch).unwrap();
+        assert_eq!(patch, merged);
+
+        // Only fields of the patch without
+        // their default values are merged.
+        let mut merged = original.clone();
+        merged.merge_non_default(&typing, &patch).unwrap();
+        assert_eq!(Some(1), merged.get("number").and_then(Unspecified::as_i64));
+        assert_eq!(
+            Some(3.0),
+            merged.get("floaty").and_then(Unspecified::as_f64)
+        );
+        assert_eq!(
+            Some("original"),
+            merged.get("text").and_then(Unspecified::as_text)
+        );
+        assert_eq!(patch.get("nested"), merged.get("nested"));
+
+        // Fields missing from the patch are left as-is.
+        let mut partial = Unspecified::Default;
+        partial
+            .try_insert(&typing, "text", Unspecified::Text("patched".into()))
+            .unwrap();
+        let mut merged = original.clone();
+        merged.merge(&typing, &partial).unwrap();
+        assert_eq!(
+            Some("patched"),
+            merged.get("text").and_then(Unspecified::as_text)
+        );
+        assert_eq!(Some(1), merged.get("number").and_then(Unspecified::as_i64));
+
+        // Default data can be patched.
+        let mut merged = Unspecified::Default;
+        merged.merge(&typing, &partial).unwrap();
+        assert_eq!(partial, merged);
+
+        // Patches must match the data type.
+        let mut merged = original.clone();
+        assert_eq!(
+            Err(DynamicError::NoSuchField {
+                data_type: "NestedTestdata".into(),
+                field: "floaty".into(),
+            }),
+            merged.merge(&NestedTestData::typing(), &patch)
+        );
+        assert_eq!(
+            Err(DynamicError::TypeMismatch {
+                field: "".into(),
+                expected: "Testdata".into(),
+                actual: Type::U8.into(),
+            }),
+            merged.merge(&typing, &Unspecified::U8(1))
+        );
+        assert_eq!(original, merged);
+    }
+
+    #[test]
+    pub fn verifies_inserts() {
+        use crate::types::tests::{NestedTestData, TestData};
+
+        let typing = TestData::typing();
+        let mut data = Unspecified::Default;
+
+        data.try_insert(&typing, "number", Unspecified::I32(7))
+            .unwrap();
+        data.try_insert(
+            &typing,
+            "two_d",
+            Unspecified::List(alloc::vec![Unspecified::List(alloc::vec![
+                Unspecified::Text("a".into())
+            ])]),
+        )
+        .unwrap();
+        data.try_insert(
+            &typing,
+            "nested",
+            NestedTestData { boolean: true }.to_dynamic(),
+        )
+        .unwrap();
+        assert_eq!(Some(7), data.get("number").and_then(Unspecified::as_i64));
+
+        // Wrong scalar type.
+        assert_eq!(
+            Err(DynamicError::TypeMismatch {
+                field: "number".into(),
+                expected: "i32".into(),
+                actual: Type::Text.into(),
+            }),
+            data.try_insert(&typing, "number", Unspecified::Text("7".into()))
+        );
+        assert_eq!(Some(7), data.get("number").and_then(Unspecified::as_i64));
+
+        // Wrong list element type.
+        assert_eq!(
+            Err(DynamicError::TypeMismatch {
+                field: "text_list[1]".into(),
+                expected: "text".into(),
+                actual: Type::U8.into(),
+            }),
+            data.try_insert(
+                &typing,
+                "text_list",
+                Unspecified::List(alloc::vec![
+                    Unspecified::Text("a".into()),
+                    Unspecified::U8(1)
+                ]),
+            )
+        );
+
+        // Unknown field name, including in nested data.
+        assert!(matches!(
+            data.try_insert(&typing, "missing", Unspecified::U8(1)),
+            Err(DynamicError::NoSuchField { .. })
+        ));
+        let mut nested = BTreeMap::new();
+        nested.insert(Text::from("missing"), Unspecified::Bool(true));
+        assert!(matches!(
+            data.try_insert(&typing, "nested", Unspecified::Map(nested)),
+            Err(DynamicError::NoSuchField { field, .. }) if &*field == "nested.missing"
+        ));
+
+        // Opaque data must have the data type's ordinal.
+        let nested_format = NestedTestData::typing().format().as_data_format();
+        let opaque = |ordinal| Unspecified::Data {
+            header: DataHeader {
+                count: 1,
+                format: DataFormat {
+                    ordinal,
+                    ..nested_format
+                },
+            },
+            raw: alloc::vec![1],
+        };
+        assert!(data
+            .try_insert(&typing, "nested", opaque(nested_format.ordinal))
+            .is_ok());
+        assert!(data
+            .try_insert(&typing, "nested", opaque(nested_format.ordinal + 1))
+            .is_err());
+
+        assert!(data.verify_type(&Type::Data(typing)).is_ok());
+    }
 }
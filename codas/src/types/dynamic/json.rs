@@ -0,0 +1,801 @@
+//! JSON representations of [`Unspecified`] data.
+//!
+//! Data is represented as JSON objects of its
+//! field names to its field values, text and
+//! characters as strings, bytes as Base64-encoded
+//! strings, timestamps as numbers of milliseconds since
+//! the Unix epoch, enums as the names of their variants
+//! (or, if the variant isn't known, its ordinal), lists
+//! as arrays, and [`Unspecified::Default`] as `null`.
+//!
+//! Like [`DynamicValue`](super::DynamicValue), maps with text
+//! keys are represented as objects, while all other maps are
+//! represented as arrays of `[key, value]` pairs.
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use snafu::Snafu;
+
+use crate::{
+    codec::Format,
+    stream::{StreamError, Writes},
+    types::{
+        binary::{base64_from_bytes, base64_to_bytes, Base64Alphabet},
+        number::{VarI64, VarU64},
+        Coda, DataType, Text, Type,
+    },
+};
+
+use super::Unspecified;
+
+/// Maximum depth of nested arrays and
+/// objects that will be parsed.
+const MAX_DEPTH: usize = 64;
+
+/// Writes `value` to `writer` as JSON.
+///
+/// Floating-point values which aren't finite,
+/// and opaque [`Unspecified::Data`], are
+/// written as `null`.
+pub fn to_json(value: &Unspecified, writer: &mut impl Writes) -> Result<(), StreamError> {
+    match value {
+        Unspecified::Default | Unspecified::Data { .. } => writer.write_all(b"null"),
+        Unspecified::U8(v) => write_number(writer, v),
+        Unspecified::I8(v) => write_number(writer, v),
+        Unspecified::U16(v) => write_number(writer, v),
+        Unspecified::I16(v) => write_number(writer, v),
+        Unspecified::U32(v) => write_number(writer, v),
+        Unspecified::I32(v) => write_number(writer, v),
+        Unspecified::U64(v) => write_number(writer, v),
+        Unspecified::I64(v) => write_number(writer, v),
+        Unspecified::U128(v) => write_number(writer, v),
+        Unspecified::I128(v) => write_number(writer, v),
+        Unspecified::UVar(v) => write_number(writer, &v.0),
+        Unspecified::IVar(v) => write_number(writer, &v.0),
+        Unspecified::Timestamp(v) => write_number(writer, v),
+        Unspecified::Enum { typing, variant } => match typing.variant(*variant) {
+            Some(name) => write_string(writer, name),
+            None => write_number(writer, variant),
+        },
+        Unspecified::F32(v) if v.is_finite() => write_number(writer, v),
+        Unspecified::F64(v) if v.is_finite() => write_number(writer, v),
+        Unspecified::F32(_) | Unspecified::F64(_) => writer.write_all(b"null"),
+        Unspecified::Bool(true) => writer.write_all(b"true"),
+        Unspecified::Bool(false) => writer.write_all(b"false"),
+        Unspecified::Char(v) => write_string(writer, v.encode_utf8(&mut [0; 4])),
+        Unspecified::Text(v) => write_string(writer, v),
+        Unspecified::Bytes(v) => {
+            write_string(writer, &base64_from_bytes(v, Base64Alphabet::Standard))
+        }
+        Unspecified::List(items) => {
+            writer.write_all(b"[")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b",")?;
+                }
+                to_json(item, writer)?;
+            }
+            writer.write_all(b"]")
+        }
+        Unspecified::Map(entries) => {
+            writer.write_all(b"{")?;
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b",")?;
+                }
+                write_string(writer, key)?;
+                writer.write_all(b":")?;
+                to_json(value, writer)?;
+            }
+            writer.write_all(b"}")
+        }
+    }
+}
+
+/// Writes `number` to `writer` in its decimal form.
+fn write_number(writer: &mut impl Writes, number: &impl ToString) -> Result<(), StreamError> {
+    writer.write_all(number.to_string().as_bytes())
+}
+
+/// Writes `text` to `writer` as a JSON string.
+fn write_string(writer: &mut impl Writes, text: &str) -> Result<(), StreamError> {
+    writer.write_all(b"\"")?;
+
+    let mut unescaped = 0;
+    for (i, byte) in text.bytes().enumerate() {
+        let escape: &[u8] = match byte {
+            b'"' => b"\\\"",
+            b'\\' => b"\\\\",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            0x08 => b"\\b",
+            0x0C => b"\\f",
+            0x00..=0x1F => b"",
+            _ => continue,
+        };
+
+        writer.write_all(&text.as_bytes()[unescaped..i])?;
+        if escape.is_empty() {
+            writer.write_all(format!("\\u{byte:04x}").as_bytes())?;
+        } else {
+            writer.write_all(escape)?;
+        }
+        unescaped = i + 1;
+    }
+
+    writer.write_all(&text.as_bytes()[unescaped..])?;
+    writer.write_all(b"\"")
+}
+
+/// Parses `json` as data with `data_type`.
+///
+/// The returned value is an [`Unspecified::Map`] of the data
+/// type's field names to their values, each of which has the
+/// [`Unspecified`] variant matching its field's type; missing
+/// optional fields are [`Unspecified::Default`].
+///
+/// Nested data types which are fluid (like forward references
+/// to other data types in a coda) are parsed as untyped values.
+pub fn from_json(data_type: &DataType, json: &str) -> Result<Unspecified, JsonError> {
+    convert(
+        &parse(json)?,
+        &Type::Data(data_type.clone()),
+        false,
+        "",
+        None,
+    )
+}
+
+/// Parses `json` as data with `data_type`, like [`from_json`],
+/// resolving fluid data types by their names in `coda`.
+///
+/// Data types which can't be resolved in `coda`
+/// are still parsed as untyped values.
+pub fn from_json_in(
+    coda: &Coda,
+    data_type: &DataType,
+    json: &str,
+) -> Result<Unspecified, JsonError> {
+    convert(
+        &parse(json)?,
+        &Type::Data(data_type.clone()),
+        false,
+        "",
+        Some(coda),
+    )
+}
+
+/// Parses `json` into a JSON value.
+fn parse(json: &str) -> Result<Json<'_>, JsonError> {
+    let mut parser = Parser {
+        json: json.as_bytes(),
+        at: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.at < parser.json.len() {
+        return parser.fail("unexpected trailing characters");
+    }
+
+    Ok(value)
+}
+
+/// Error returned by [`from_json`] and [`from_json_in`].
+#[derive(Debug, Clone, PartialEq, Snafu)]
+pub enum JsonError {
+    #[snafu(display("invalid JSON at byte {offset}: {reason}"))]
+    InvalidJson { offset: usize, reason: &'static str },
+
+    #[snafu(display("expected {expected} at `{path}`"))]
+    UnexpectedValue { path: Text, expected: Text },
+
+    #[snafu(display("missing field `{path}`"))]
+    MissingField { path: Text },
+
+    #[snafu(display("unknown field `{path}`"))]
+    UnknownField { path: Text },
+}
+
+/// A parsed, but not yet typed, JSON value.
+enum Json<'a> {
+    Null,
+    Bool(bool),
+
+    /// A number, in its original text form.
+    Number(&'a str),
+
+    String(String),
+    Array(Vec<Json<'a>>),
+    Object(Vec<(String, Json<'a>)>),
+}
+
+impl Json<'_> {
+    /// Returns a description of this value's
+    /// kind, for use in error messages.
+    fn kind(&self) -> &'static str {
+        match self {
+            Json::Null => "null",
+            Json::Bool(_) => "a boolean",
+            Json::Number(_) => "a number",
+            Json::String(_) => "a string",
+            Json::Array(_) => "an array",
+            Json::Object(_) => "an object",
+        }
+    }
+}
+
+/// Minimal parser of JSON text (per RFC 8259).
+struct Parser<'a> {
+    json: &'a [u8],
+    at: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Returns an [`JsonError::InvalidJson`] at the current byte.
+    fn fail<T>(&self, reason: &'static str) -> Result<T, JsonError> {
+        InvalidJsonSnafu {
+            offset: self.at,
+            reason,
+        }
+        .fail()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.json.get(self.at) {
+            self.at += 1;
+        }
+    }
+
+    /// Consumes `expected` if it's next in the JSON.
+    fn eat(&mut self, expected: &[u8]) -> bool {
+        if self.json[self.at..].starts_with(expected) {
+            self.at += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json<'a>, JsonError> {
+        self.skip_whitespace();
+        match self.json.get(self.at).copied() {
+            Some(b'n') if self.eat(b"null") => Ok(Json::Null),
+            Some(b't') if self.eat(b"true") => Ok(Json::Bool(true)),
+            Some(b'f') if self.eat(b"false") => Ok(Json::Bool(false)),
+            Some(b'"') => self.parse_string().map(Json::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(b'[') => {
+                self.enter()?;
+                let mut items = Vec::new();
+                if !self.eat_delimiter(b']') {
+                    loop {
+                        items.push(self.parse_value()?);
+                        if self.eat_delimiter(b']') {
+                            break;
+                        } else if !self.eat_delimiter(b',') {
+                            return self.fail("expected `,` or `]`");
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Array(items))
+            }
+            Some(b'{') => {
+                self.enter()?;
+                let mut entries = Vec::new();
+                if !self.eat_delimiter(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        if self.json.get(self.at) != Some(&b'"') {
+                            return self.fail("expected a string key");
+                        }
+                        let key = self.parse_string()?;
+                        if !self.eat_delimiter(b':') {
+                            return self.fail("expected `:`");
+                        }
+                        entries.push((key, self.parse_value()?));
+                        if self.eat_delimiter(b'}') {
+                            break;
+                        } else if !self.eat_delimiter(b',') {
+                            return self.fail("expected `,` or `}`");
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Object(entries))
+            }
+            Some(_) => self.fail("expected a value"),
+            None => self.fail("unexpected end of input"),
+        }
+    }
+
+    /// Consumes the opening delimiter of
+    /// an array or object.
+    fn enter(&mut self) -> Result<(), JsonError> {
+        if self.depth == MAX_DEPTH {
+            return self.fail("arrays or objects nested too deeply");
+        }
+
+        self.at += 1;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Consumes `delimiter`, and any whitespace
+    /// preceding it, if it's next in the JSON.
+    fn eat_delimiter(&mut self, delimiter: u8) -> bool {
+        self.skip_whitespace();
+        self.eat(&[delimiter])
+    }
+
+    fn parse_number(&mut self) -> Result<Json<'a>, JsonError> {
+        let start = self.at;
+        let digits = |parser: &mut Self| {
+            let start = parser.at;
+            while let Some(b'0'..=b'9') = parser.json.get(parser.at) {
+                parser.at += 1;
+            }
+            parser.at > start
+        };
+
+        self.eat(b"-");
+        if !self.eat(b"0") && !digits(self) {
+            return self.fail("expected digits");
+        }
+        if self.eat(b".") && !digits(self) {
+            return self.fail("expected digits after `.`");
+        }
+        if self.eat(b"e") || self.eat(b"E") {
+            if !self.eat(b"+") {
+                self.eat(b"-");
+            }
+            if !digits(self) {
+                return self.fail("expected digits in exponent");
+            }
+        }
+
+        // Numbers only contain ASCII characters.
+        let number = core::str::from_utf8(&self.json[start..self.at]).unwrap();
+        Ok(Json::Number(number))
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        // Skip the opening quote.
+        self.at += 1;
+
+        let mut bytes = Vec::new();
+        loop {
+            match self.json.get(self.at).copied() {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.at += 1;
+                    let escaped = match self.json.get(self.at).copied() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{08}',
+                        Some(b'f') => '\u{0C}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.parse_unicode_escape()?,
+                        _ => return self.fail("invalid escape sequence"),
+                    };
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                Some(0x00..=0x1F) => return self.fail("unescaped control character"),
+                Some(byte) => bytes.push(byte),
+                None => return self.fail("unterminated string"),
+            }
+            self.at += 1;
+        }
+
+        // Skip the closing quote.
+        self.at += 1;
+
+        // JSON is parsed from valid UTF-8, and
+        // escapes are encoded as valid UTF-8.
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+
+    /// Parses the `u` (and hex digits) of a `\u` escape,
+    /// leaving the parser at the escape's final digit.
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.parse_hex()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            // Surrogate pairs are escaped as two sequences.
+            self.at += 1;
+            if !self.eat(b"\\") || self.json.get(self.at) != Some(&b'u') {
+                return self.fail("expected a low surrogate");
+            }
+            let low = self.parse_hex()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return self.fail("expected a low surrogate");
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        match char::from_u32(code) {
+            Some(character) => Ok(character),
+            None => self.fail("invalid unicode escape"),
+        }
+    }
+
+    /// Parses the four hex digits following the
+    /// `u` at the current byte.
+    fn parse_hex(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .json
+            .get(self.at + 1..self.at + 5)
+            .and_then(|digits| core::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok());
+        match digits {
+            Some(code) => {
+                self.at += 4;
+                Ok(code)
+            }
+            None => self.fail("expected four hexadecimal digits"),
+        }
+    }
+}
+
+/// Converts `json` into a value of `typing`.
+///
+/// `path` describes the location of `json` in
+/// the document, and is used in error messages.
+///
+/// Fluid data types are resolved in `coda`, if any.
+fn convert(
+    json: &Json,
+    typing: &Type,
+    optional: bool,
+    path: &str,
+    coda: Option<&Coda>,
+) -> Result<Unspecified, JsonError> {
+    let unexpected = |expected: &str| {
+        UnexpectedValueSnafu {
+            path,
+            expected: format!("{expected}, found {}", json.kind()),
+        }
+        .fail()
+    };
+
+    let value = match (typing, json) {
+        (_, Json::Null) if optional => Unspecified::Default,
+        (Type::Unspecified, json) => untyped(json),
+        (Type::Data(data_type), json) if matches!(data_type.format(), Format::Fluid) => {
+            match coda
+                .and_then(|coda| coda.data_type_ignore_ascii_case(&data_type.name))
+                .filter(|resolved| !matches!(resolved.format(), Format::Fluid))
+            {
+                Some(resolved) => convert(json, &Type::Data(resolved.clone()), false, path, coda)?,
+                None => untyped(json),
+            }
+        }
+        (Type::Bool, Json::Bool(value)) => Unspecified::Bool(*value),
+        (Type::Text, Json::String(text)) => Unspecified::Text(text.clone().into()),
+        (Type::Enum(typing), Json::String(name)) => match typing.ordinal_of(name) {
+            Some(variant) => Unspecified::Enum {
+                typing: typing.clone(),
+                variant,
+            },
+            None => return unexpected(&format!("a variant of {}", typing.name)),
+        },
+        (Type::Bytes, Json::String(text)) => {
+            match base64_to_bytes(text.clone().into(), Base64Alphabet::Standard) {
+                Ok(bytes) => Unspecified::Bytes(bytes),
+                Err(_) => return unexpected("Base64-encoded bytes"),
+            }
+        }
+        (Type::Char, Json::String(text)) => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(character), None) => Unspecified::Char(character),
+                _ => return unexpected("a single character"),
+            }
+        }
+        (Type::F32, Json::Number(number)) => match number.parse() {
+            Ok(value) => Unspecified::F32(value),
+            Err(_) => return unexpected("an f32"),
+        },
+        (Type::F64, Json::Number(number)) => match number.parse() {
+            Ok(value) => Unspecified::F64(value),
+            Err(_) => return unexpected("an f64"),
+        },
+        (Type::List(item), Json::Array(items)) => {
+            let mut list = Vec::with_capacity(items.len());
+            for (i, json) in items.iter().enumerate() {
+                list.push(convert(json, item, false, &format!("{path}[{i}]"), coda)?);
+            }
+            Unspecified::List(list)
+        }
+        (Type::Map(map), Json::Object(entries)) if map.0 == Type::Text => {
+            let mut converted = BTreeMap::new();
+            for (key, json) in entries {
+                let value = convert(json, &map.1, false, &format!("{path}[{key}]"), coda)?;
+                converted.insert(key.clone().into(), value);
+            }
+            Unspecified::Map(converted)
+        }
+        (Type::Map(map), Json::Array(pairs)) if map.0 != Type::Text => {
+            let mut converted = Vec::with_capacity(pairs.len());
+            for (i, pair) in pairs.iter().enumerate() {
+                let path = format!("{path}[{i}]");
+                let [key, value] = match pair {
+                    Json::Array(pair) if pair.len() == 2 => [&pair[0], &pair[1]],
+                    _ => {
+                        return UnexpectedValueSnafu {
+                            path,
+                            expected: "a [key, value] pair",
+                        }
+                        .fail()
+                    }
+                };
+                converted.push(Unspecified::List(alloc::vec![
+                    convert(key, &map.0, false, &path, coda)?,
+                    convert(value, &map.1, false, &path, coda)?,
+                ]));
+            }
+            Unspecified::List(converted)
+        }
+        (Type::Data(data_type), Json::Object(entries)) => {
+            let mut fields = BTreeMap::new();
+            for field in data_type.iter() {
+                let field_path = if path.is_empty() {
+                    field.name.to_string()
+                } else {
+                    format!("{path}.{}", field.name)
+                };
+
+                let value = match entries.iter().find(|(name, _)| **name == *field.name) {
+                    Some((_, json)) => {
+                        convert(json, &field.typing, field.optional, &field_path, coda)?
+                    }
+                    None if field.optional => Unspecified::Default,
+                    None => return MissingFieldSnafu { path: field_path }.fail(),
+                };
+                fields.insert(field.name.clone(), value);
+            }
+
+            // Reject fields which aren't in the data type.
+            for (name, _) in entries {
+                if !data_type.iter().any(|field| *field.name == **name) {
+                    let path = if path.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{path}.{name}")
+                    };
+                    return UnknownFieldSnafu { path }.fail();
+                }
+            }
+
+            Unspecified::Map(fields)
+        }
+        (typing, Json::Number(number)) => match convert_integer(number, typing) {
+            Some(value) => value,
+            None => return unexpected(&typing.description()),
+        },
+        (typing, _) => return unexpected(&typing.description()),
+    };
+
+    Ok(value)
+}
+
+/// Converts `number` into an integer of `typing`, or returns
+/// `None` if it isn't an integer within `typing`'s range.
+fn convert_integer(number: &str, typing: &Type) -> Option<Unspecified> {
+    let value = match typing {
+        Type::U8 => Unspecified::U8(number.parse().ok()?),
+        Type::U16 => Unspecified::U16(number.parse().ok()?),
+        Type::U32 => Unspecified::U32(number.parse().ok()?),
+        Type::U64 => Unspecified::U64(number.parse().ok()?),
+        Type::U128 => Unspecified::U128(number.parse().ok()?),
+        Type::I8 => Unspecified::I8(number.parse().ok()?),
+        Type::I16 => Unspecified::I16(number.parse().ok()?),
+        Type::I32 => Unspecified::I32(number.parse().ok()?),
+        Type::I64 => Unspecified::I64(number.parse().ok()?),
+        Type::I128 => Unspecified::I128(number.parse().ok()?),
+        Type::UVar => Unspecified::UVar(VarU64(number.parse().ok()?)),
+        Type::IVar => Unspecified::IVar(VarI64(number.parse().ok()?)),
+        Type::Timestamp => Unspecified::Timestamp(number.parse().ok()?),
+        Type::Enum(typing) => Unspecified::Enum {
+            typing: typing.clone(),
+            variant: number.parse().ok()?,
+        },
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Converts `json` into a value without a
+/// type: integers are converted to the smallest
+/// of `i64`, `u64`, `i128`, or `u128` which can
+/// contain them, and all other numbers to `f64`.
+fn untyped(json: &Json) -> Unspecified {
+    match json {
+        Json::Null => Unspecified::Default,
+        Json::Bool(value) => Unspecified::Bool(*value),
+        Json::Number(number) => [Type::I64, Type::U64, Type::I128, Type::U128]
+            .iter()
+            .find_map(|typing| convert_integer(number, typing))
+            .unwrap_or_else(|| Unspecified::F64(number.parse().unwrap_or(f64::NAN))),
+        Json::String(text) => Unspecified::Text(text.clone().into()),
+        Json::Array(items) => Unspecified::List(items.iter().map(untyped).collect()),
+        Json::Object(entries) => Unspecified::Map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone().into(), untyped(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codec::encode_to_vec,
+        types::{
+            dynamic::DynamicValue,
+            tests::{NestedTestData, TestData},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn round_trips_test_data() {
+        let data = TestData {
+            number: -7,
+            floaty: 1.5,
+            text_list: alloc::vec!["one".into(), "two".into()],
+            text: "quote \" and\nnewline".into(),
+            nested: NestedTestData { boolean: true },
+            two_d: alloc::vec![alloc::vec!["a".into()], alloc::vec!["b".into(), "c".into()]],
+        };
+        let encoded = encode_to_vec(&data).unwrap();
+
+        let mut json = Vec::new();
+        to_json(&data.to_dynamic(), &mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert_eq!(
+            r#"{"floaty":1.5,"nested":{"boolean":true},"number":-7,"text":"quote \" and\nnewline","text_list":["one","two"],"two_d":[["a"],["b","c"]]}"#,
+            json
+        );
+
+        let dynamic = from_json(&TestData::typing(), &json).unwrap();
+        let decoded = TestData::try_from_dynamic(&dynamic).unwrap();
+        let reencoded = encode_to_vec(&decoded).unwrap();
+        assert_eq!(encoded, reencoded);
+    }
+
+    #[test]
+    fn parses_json() {
+        let typing = NestedTestData::typing();
+        assert_eq!(
+            Some(&Unspecified::Bool(false)),
+            from_json(&typing, " { \"boolean\" : false } ")
+                .unwrap()
+                .get("boolean")
+        );
+
+        let mut json = Vec::new();
+        to_json(&Unspecified::Text("\u{1}é🦀".into()), &mut json).unwrap();
+        assert_eq!(r#""\u0001é🦀""#.as_bytes(), json.as_slice());
+
+        let mut json = Vec::new();
+        to_json(
+            &Unspecified::Bytes(alloc::vec![0xDE, 0xAD, 0xBE, 0xEF]),
+            &mut json,
+        )
+        .unwrap();
+        assert_eq!(br#""3q2+7w""#, json.as_slice());
+        assert_eq!(
+            Unspecified::Bytes(alloc::vec![0xDE, 0xAD, 0xBE, 0xEF]),
+            convert(
+                &Json::String("3q2+7w==".into()),
+                &Type::Bytes,
+                false,
+                "bytes",
+                None
+            )
+            .unwrap()
+        );
+        let mut parser = Parser {
+            json: br#""\u0001\u00e9\ud83e\udd80\/""#,
+            at: 0,
+            depth: 0,
+        };
+        assert!(matches!(
+            parser.parse_value(),
+            Ok(Json::String(text)) if text == "\u{1}é🦀/"
+        ));
+
+        assert_eq!(
+            Err(JsonError::MissingField {
+                path: "boolean".into()
+            }),
+            from_json(&typing, "{}")
+        );
+        assert_eq!(
+            Err(JsonError::UnknownField {
+                path: "extra".into()
+            }),
+            from_json(&typing, r#"{"boolean":true,"extra":1}"#)
+        );
+        assert!(matches!(
+            from_json(&typing, r#"{"boolean":1}"#),
+            Err(JsonError::UnexpectedValue { .. })
+        ));
+        for invalid in ["", "{", r#"{"boolean":true"#, "[01]", r#""\x""#, "{} {}"] {
+            assert!(
+                matches!(
+                    from_json(&typing, invalid),
+                    Err(JsonError::InvalidJson { .. })
+                ),
+                "{invalid}"
+            );
+        }
+
+        let nested = "[".repeat(MAX_DEPTH + 1);
+        assert!(matches!(
+            from_json(&typing, &nested),
+            Err(JsonError::InvalidJson { .. })
+        ));
+    }
+
+    #[test]
+    fn converts_enums() {
+        let status = crate::types::EnumType::new(
+            "UserStatus".into(),
+            &["Active".into(), "Suspended".into()],
+        );
+        let typing =
+            DataType::new("User".into(), None, 1, &[], &[]).with(crate::types::DataField {
+                name: "status".into(),
+                docs: None,
+                typing: Type::Enum(status.clone()),
+                optional: false,
+                flattened: false,
+                default: None,
+                deprecated: false,
+                deprecation_note: None,
+            });
+
+        // Variants are written by name...
+        let json = r#"{"status":"Suspended"}"#;
+        let value = from_json(&typing, json).unwrap();
+        assert_eq!(
+            Some(&Unspecified::Enum {
+                typing: status.clone(),
+                variant: 1
+            }),
+            value.get("status")
+        );
+        let mut written = Vec::new();
+        to_json(&value, &mut written).unwrap();
+        assert_eq!(json.as_bytes(), written.as_slice());
+
+        // ...unless they're unknown, in which
+        // case they're written by ordinal.
+        let value = from_json(&typing, r#"{"status":7}"#).unwrap();
+        let mut written = Vec::new();
+        to_json(&value, &mut written).unwrap();
+        assert_eq!(br#"{"status":7}"#, written.as_slice());
+
+        assert!(matches!(
+            from_json(&typing, r#"{"status":"Deleted"}"#),
+            Err(JsonError::UnexpectedValue { .. })
+        ));
+    }
+}
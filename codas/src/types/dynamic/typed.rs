@@ -0,0 +1,540 @@
+//! Decoding of encoded data into [`Unspecified`]
+//! values described by the data types of a [`Coda`].
+//!
+//! Data is decoded into the same representation accepted by
+//! [`to_json`](super::to_json) and returned by
+//! [`from_json`](super::from_json): data types become
+//! [`Unspecified::Map`]s of their field names to their values,
+//! absent optional fields become [`Unspecified::Default`], and
+//! maps with non-text keys become lists of `[key, value]` pairs.
+//!
+//! Like natively generated decoders, data encoded by newer
+//! (or older) versions of a data type is decoded field-by-field:
+//! fields missing from the encoded data take their default
+//! values, and fields unknown to the data type are skipped.
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use snafu::{IntoError, Snafu};
+
+use crate::{
+    codec::{
+        self, CodecError, DataFormat, DataHeader, Decodable, Format, ReadsDecodable,
+        UnsupportedDataFormatSnafu, DEFAULT_MAX_DEPTH,
+    },
+    types::{
+        number::{VarI64, VarU64},
+        Coda, DataType, Text, Type,
+    },
+};
+
+use super::Unspecified;
+
+/// Reads one data of any type in `coda` from `reader`,
+/// returning the name of the data's type and its value.
+///
+/// The data's type is identified by the ordinal in its
+/// header; data with ordinal `0` is decoded as (untyped)
+/// [`Unspecified`] data. Any bytes in `reader` following
+/// the data are left unread.
+pub fn decode_typed(
+    coda: &Coda,
+    reader: &mut (impl ReadsDecodable + ?Sized),
+) -> Result<(Text, Unspecified), TypedDecodeError> {
+    let mut decoder = TypedDecoder { coda, reader };
+    let header = decoder.read::<DataHeader>("")?;
+    let ordinal = header.format.ordinal;
+
+    if ordinal == 0 {
+        let mut value = Unspecified::Default;
+        value
+            .decode(&mut *decoder.reader, Some(header))
+            .map_err(at("Unspecified"))?;
+        return Ok((Text::Static("Unspecified"), value));
+    }
+
+    let Some(data_type) = coda.data_type_by_ordinal(ordinal) else {
+        return UnknownOrdinalSnafu { ordinal }.fail();
+    };
+    let value = decoder.decode_data(data_type, header, &data_type.name, 0)?;
+
+    Ok((data_type.name.clone(), value))
+}
+
+/// Error returned by [`decode_typed`].
+#[derive(Debug, Snafu)]
+pub enum TypedDecodeError {
+    #[snafu(display("no data type has ordinal {ordinal}"))]
+    UnknownOrdinal { ordinal: u8 },
+
+    #[snafu(display("unknown data type `{name}` at `{path}`"))]
+    UnknownDataType { path: Text, name: Text },
+
+    #[snafu(display("failed to decode `{path}`: {source}"))]
+    Codec { path: Text, source: CodecError },
+}
+
+/// Returns a function attaching `path` to a [`CodecError`].
+fn at(path: &str) -> impl FnOnce(CodecError) -> TypedDecodeError + '_ {
+    move |source| {
+        CodecSnafu {
+            path: path.to_string(),
+        }
+        .into_error(source)
+    }
+}
+
+/// Decodes data according to the types in a coda.
+struct TypedDecoder<'a, R: ReadsDecodable + ?Sized> {
+    coda: &'a Coda,
+    reader: &'a mut R,
+}
+
+impl<'a, R: ReadsDecodable + ?Sized> TypedDecoder<'a, R> {
+    /// Reads a `T` from the reader.
+    fn read<T: Decodable + Default>(&mut self, path: &str) -> Result<T, TypedDecodeError> {
+        self.reader.read_data().map_err(at(path))
+    }
+
+    /// Returns the data type `data_type` refers
+    /// to, resolving fluid references by name.
+    fn resolve<'d>(
+        &self,
+        data_type: &'d DataType,
+        path: &str,
+    ) -> Result<&'d DataType, TypedDecodeError>
+    where
+        'a: 'd,
+    {
+        if !matches!(data_type.format(), Format::Fluid) {
+            return Ok(data_type);
+        }
+
+        match self.coda.data_type_ignore_ascii_case(&data_type.name) {
+            Some(resolved) => Ok(resolved),
+            None => UnknownDataTypeSnafu {
+                path: path.to_string(),
+                name: data_type.name.clone(),
+            }
+            .fail(),
+        }
+    }
+
+    /// Decodes the data of `data_type` following `header`.
+    fn decode_data(
+        &mut self,
+        data_type: &DataType,
+        header: DataHeader,
+        path: &str,
+        depth: u32,
+    ) -> Result<Unspecified, TypedDecodeError> {
+        if header.format.ordinal != data_type.ordinal() {
+            return Err(at(path)(
+                UnsupportedDataFormatSnafu {
+                    ordinal: header.format.ordinal,
+                    offset: None,
+                }
+                .build(),
+            ));
+        }
+
+        let fields: Vec<_> = data_type
+            .iter()
+            .map(|field| {
+                let path = format!("{path}.{}", field.name);
+                (&field.typing, field.optional, path)
+            })
+            .collect();
+
+        let values = if header.count == 0 {
+            fields
+                .iter()
+                .map(|(typing, optional, path)| self.default_value(typing, *optional, path, depth))
+                .collect::<Result<_, _>>()?
+        } else {
+            let values = self.decode_instance(header.format, &fields, path, depth)?;
+
+            // Data types are singular; skip any extra data.
+            for _ in 1..header.count {
+                self.reader
+                    .skip_data_with_format(header.format)
+                    .map_err(at(path))?;
+            }
+
+            values
+        };
+
+        Ok(Unspecified::Map(
+            data_type
+                .iter()
+                .map(|field| field.name.clone())
+                .zip(values)
+                .collect(),
+        ))
+    }
+
+    /// Decodes one instance of data with `format` containing
+    /// `fields` (each a type, whether it's optional, and its path),
+    /// returning the values of each field in order.
+    ///
+    /// `fields` must list blob fields before data fields.
+    fn decode_instance(
+        &mut self,
+        format: DataFormat,
+        fields: &[(&Type, bool, String)],
+        path: &str,
+        depth: u32,
+    ) -> Result<Vec<Unspecified>, TypedDecodeError> {
+        if depth >= DEFAULT_MAX_DEPTH {
+            return Err(at(path)(CodecError::DepthLimitExceeded));
+        }
+
+        let mut blob = Vec::new();
+        codec::read_bytes_into(&mut *self.reader, &mut blob, format.blob_size as usize)
+            .map_err(at(path))?;
+
+        let mut values = Vec::with_capacity(fields.len());
+        let mut blob_offset = 0;
+        let mut data_fields = 0;
+        for (typing, optional, path) in fields {
+            let value = match typing.format() {
+                Format::Blob(size) if !optional => {
+                    let size = size as usize;
+                    let value = match blob.get(blob_offset..blob_offset + size) {
+                        Some(bytes) => decode_scalar(typing, bytes).map_err(at(path))?,
+                        None => Unspecified::default_of(typing),
+                    };
+                    blob_offset += size;
+                    value
+                }
+                _ if data_fields < format.data_fields => {
+                    data_fields += 1;
+                    self.decode_value(typing, *optional, path, depth + 1)?
+                }
+                _ => self.default_value(typing, *optional, path, depth + 1)?,
+            };
+            values.push(value);
+        }
+
+        // Skip any data fields this decoder doesn't know about.
+        for _ in data_fields..format.data_fields {
+            self.reader.skip_data().map_err(at(path))?;
+        }
+
+        Ok(values)
+    }
+
+    /// Decodes a value of `typing` (and its header, if any).
+    fn decode_value(
+        &mut self,
+        typing: &Type,
+        optional: bool,
+        path: &str,
+        depth: u32,
+    ) -> Result<Unspecified, TypedDecodeError> {
+        if optional {
+            let header = self.read::<DataHeader>(path)?;
+            if header.count == 0 {
+                return Ok(Unspecified::Default);
+            }
+
+            let fields = [(typing, false, path.to_string())];
+            let mut values = self.decode_instance(header.format, &fields, path, depth)?;
+            return Ok(values.pop().unwrap_or_default());
+        }
+
+        let value = match typing {
+            Type::Unspecified => self.read::<Unspecified>(path)?,
+            Type::UVar => Unspecified::UVar(self.read::<VarU64>(path)?),
+            Type::IVar => Unspecified::IVar(self.read::<VarI64>(path)?),
+            Type::Text => Unspecified::Text(self.read::<Text>(path)?),
+            Type::Bytes => Unspecified::Bytes(self.read::<Vec<u8>>(path)?),
+            Type::Data(data_type) => {
+                let data_type = self.resolve(data_type, path)?;
+                let header = self.read::<DataHeader>(path)?;
+                self.decode_data(data_type, header, path, depth)?
+            }
+            Type::List(item) => {
+                let header = self.read_list_header(path)?;
+                let mut items = Vec::new();
+                for i in 0..header.count {
+                    let fields = [(&**item, false, format!("{path}[{i}]"))];
+                    let mut values = self.decode_instance(header.format, &fields, path, depth)?;
+                    items.push(values.pop().unwrap_or_default());
+                }
+                Unspecified::List(items)
+            }
+            Type::Map(map) => {
+                let header = self.read_list_header(path)?;
+                if header.count == 0 {
+                    return Ok(Unspecified::Map(BTreeMap::new()));
+                }
+
+                // Maps are encoded as a list of keys
+                // followed by a list of values.
+                let key_typing = Type::List(map.0.clone().into());
+                let value_typing = Type::List(map.1.clone().into());
+                let fields = [
+                    (&key_typing, false, format!("{path}.keys")),
+                    (&value_typing, false, format!("{path}.values")),
+                ];
+                let mut values = self
+                    .decode_instance(header.format, &fields, path, depth)?
+                    .into_iter();
+                let mut next_list = || match values.next() {
+                    Some(Unspecified::List(items)) => items,
+                    _ => Vec::new(),
+                };
+                let (keys, values) = (next_list(), next_list());
+                if keys.len() != values.len() {
+                    return Err(at(path)(CodecError::MapLengthMismatch {
+                        keys: keys.len(),
+                        values: values.len(),
+                    }));
+                }
+
+                // Like native maps, only maps with text keys
+                // are represented as unspecified maps.
+                if keys.iter().all(|key| matches!(key, Unspecified::Text(_))) {
+                    let mut entries = BTreeMap::new();
+                    for (key, value) in keys.into_iter().zip(values) {
+                        if let Unspecified::Text(key) = key {
+                            entries.insert(key, value);
+                        }
+                    }
+                    Unspecified::Map(entries)
+                } else {
+                    Unspecified::List(
+                        keys.into_iter()
+                            .zip(values)
+                            .map(|(key, value)| Unspecified::List(alloc::vec![key, value]))
+                            .collect(),
+                    )
+                }
+            }
+            scalar => {
+                let mut blob = Vec::new();
+                let size = scalar.format().as_data_format().blob_size as usize;
+                codec::read_bytes_into(&mut *self.reader, &mut blob, size).map_err(at(path))?;
+                decode_scalar(scalar, &blob).map_err(at(path))?
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Reads the header of a list (or map), which
+    /// must have the ordinal of untyped data.
+    fn read_list_header(&mut self, path: &str) -> Result<DataHeader, TypedDecodeError> {
+        let header = self.read::<DataHeader>(path)?;
+        if header.format.ordinal != 0 {
+            return Err(at(path)(
+                UnsupportedDataFormatSnafu {
+                    ordinal: header.format.ordinal,
+                    offset: None,
+                }
+                .build(),
+            ));
+        }
+
+        Ok(header)
+    }
+
+    /// Returns the default value of a field of `typing`,
+    /// used for fields missing from encoded data.
+    fn default_value(
+        &self,
+        typing: &Type,
+        optional: bool,
+        path: &str,
+        depth: u32,
+    ) -> Result<Unspecified, TypedDecodeError> {
+        if optional {
+            return Ok(Unspecified::Default);
+        }
+
+        match typing {
+            Type::Data(data_type) => {
+                if depth >= DEFAULT_MAX_DEPTH {
+                    return Err(at(path)(CodecError::DepthLimitExceeded));
+                }
+
+                let data_type = self.resolve(data_type, path)?;
+                let mut fields = BTreeMap::new();
+                for field in data_type.iter() {
+                    let path = format!("{path}.{}", field.name);
+                    let value =
+                        self.default_value(&field.typing, field.optional, &path, depth + 1)?;
+                    fields.insert(field.name.clone(), value);
+                }
+                Ok(Unspecified::Map(fields))
+            }
+            typing => Ok(Unspecified::default_of(typing)),
+        }
+    }
+}
+
+/// Decodes a value of scalar `typing` from its `blob`.
+fn decode_scalar(typing: &Type, mut blob: &[u8]) -> Result<Unspecified, CodecError> {
+    let value = match typing {
+        Type::U8 => Unspecified::U8(blob.read_data()?),
+        Type::U16 => Unspecified::U16(blob.read_data()?),
+        Type::U32 => Unspecified::U32(blob.read_data()?),
+        Type::U64 => Unspecified::U64(blob.read_data()?),
+        Type::I8 => Unspecified::I8(blob.read_data()?),
+        Type::I16 => Unspecified::I16(blob.read_data()?),
+        Type::I32 => Unspecified::I32(blob.read_data()?),
+        Type::I64 => Unspecified::I64(blob.read_data()?),
+        Type::U128 => Unspecified::U128(blob.read_data()?),
+        Type::I128 => Unspecified::I128(blob.read_data()?),
+        Type::F32 => Unspecified::F32(blob.read_data()?),
+        Type::F64 => Unspecified::F64(blob.read_data()?),
+        Type::Bool => Unspecified::Bool(blob.read_data()?),
+        Type::Char => Unspecified::Char(blob.read_data()?),
+        Type::Timestamp => Unspecified::Timestamp(blob.read_data()?),
+        Type::Enum(typing) => Unspecified::Enum {
+            typing: typing.clone(),
+            variant: blob.read_data()?,
+        },
+        typing => Unspecified::default_of(typing),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codec::{Encodable, WritesEncodable},
+        types::{
+            dynamic::DynamicValue,
+            tests::{NestedTestData, TestData},
+            DataField,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn decodes_test_data() {
+        let coda = Coda::new(
+            "Test".into(),
+            "Test".into(),
+            None,
+            &[TestData::typing(), NestedTestData::typing()],
+        );
+        let data = TestData {
+            number: -7,
+            floaty: 1.5,
+            text_list: alloc::vec!["one".into(), "two".into()],
+            text: "text".into(),
+            nested: NestedTestData { boolean: true },
+            two_d: alloc::vec![alloc::vec!["a".into()], alloc::vec!["b".into(), "c".into()]],
+        };
+        let mut encoded = vec![];
+        encoded.write_data(&data).unwrap();
+
+        let (name, value) = decode_typed(&coda, &mut encoded.as_slice()).unwrap();
+        assert_eq!("Testdata", name);
+        assert_eq!(data, TestData::try_from_dynamic(&value).unwrap());
+
+        // Errors include the path of the data being decoded.
+        let truncated = &encoded[..encoded.len() - 1];
+        let error = decode_typed(&coda, &mut &truncated[..]).unwrap_err();
+        assert!(
+            matches!(&error, TypedDecodeError::Codec { path, .. } if path == "Testdata.two_d[1][1]"),
+            "{error}"
+        );
+
+        // Data types are identified by their ordinal.
+        encoded[7] = 9;
+        assert!(matches!(
+            decode_typed(&coda, &mut encoded.as_slice()),
+            Err(TypedDecodeError::UnknownOrdinal { ordinal: 9 })
+        ));
+    }
+
+    #[test]
+    fn decodes_optional_and_map_fields() {
+        let field = |name: &'static str, typing: Type, optional: bool| DataField {
+            name: name.into(),
+            docs: None,
+            typing,
+            optional,
+            flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
+        };
+        let data_type = DataType::new("Mapped".into(), None, 1, &[], &[])
+            .with(field("present", Type::U32, true))
+            .with(field("absent", Type::U32, true))
+            .with(field(
+                "mapped",
+                Type::Map((Type::Text, Type::I32).into()),
+                false,
+            ))
+            .with(field(
+                "numbered",
+                Type::Map((Type::U8, Type::Bool).into()),
+                false,
+            ));
+        let coda = Coda::new(
+            "Test".into(),
+            "Test".into(),
+            None,
+            core::slice::from_ref(&data_type),
+        );
+
+        let mut encoded = vec![];
+        encoded
+            .write_data(&DataHeader {
+                count: 1,
+                format: data_type.format().as_data_format(),
+            })
+            .unwrap();
+        encoded.write_data(&Some(7u32)).unwrap();
+        encoded.write_data(&None::<u32>).unwrap();
+        encoded
+            .write_data(&BTreeMap::from([(Text::from("a"), 1i32)]))
+            .unwrap();
+        encoded.write_data(&BTreeMap::from([(2u8, true)])).unwrap();
+
+        let (_, value) = decode_typed(&coda, &mut encoded.as_slice()).unwrap();
+        assert_eq!(Some(&Unspecified::U32(7)), value.get("present"));
+        assert_eq!(Some(&Unspecified::Default), value.get("absent"));
+        assert_eq!(
+            Some(&Unspecified::Map(BTreeMap::from([(
+                Text::from("a"),
+                Unspecified::I32(1)
+            )]))),
+            value.get("mapped")
+        );
+        assert_eq!(
+            Some(&Unspecified::List(alloc::vec![Unspecified::List(
+                alloc::vec![Unspecified::U8(2), Unspecified::Bool(true)]
+            )])),
+            value.get("numbered")
+        );
+
+        // Fields missing from older data take default values.
+        let mut encoded = vec![];
+        encoded
+            .write_data(&DataHeader {
+                count: 1,
+                format: Format::data(1).with(Option::<u32>::FORMAT).as_data_format(),
+            })
+            .unwrap();
+        encoded.write_data(&Some(7u32)).unwrap();
+        let (_, value) = decode_typed(&coda, &mut encoded.as_slice()).unwrap();
+        assert_eq!(Some(&Unspecified::U32(7)), value.get("present"));
+        assert_eq!(
+            Some(&Unspecified::Map(BTreeMap::new())),
+            value.get("mapped")
+        );
+    }
+}
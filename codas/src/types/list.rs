@@ -1,12 +1,18 @@
 //! List data types (including `[u8]` and `Option`).
 
+use core::any::Any;
+
 use alloc::vec::Vec;
 
 use crate::codec::{
-    self, CodecError, DataFormat, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
-    WritesEncodable,
+    self, CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
+    UnexpectedDataFormatSnafu, WritesEncodable,
 };
 
+/// Maximum number of elements a [`Vec`] will reserve
+/// space for before decoding its elements.
+const MAX_RESERVED_ELEMENTS: usize = 1024;
+
 impl Encodable for [u8] {
     /// Encoded as a sequence of [`Format::Data`],
     /// each containing a single [`u8`] from the slice.
@@ -21,15 +27,11 @@ impl Encodable for [u8] {
         &self,
         writer: &mut (impl WritesEncodable + ?Sized),
     ) -> Result<(), CodecError> {
-        DataHeader {
-            count: codec::try_count(self.len())?,
-            format: DataFormat {
-                blob_size: 1,
-                data_fields: 0,
-                ordinal: 0,
-            },
-        }
-        .encode(writer)
+        writer.write_data_header_for::<u8>(codec::try_count(self.len())?)
+    }
+
+    fn encoded_len(&self) -> usize {
+        DataHeader::FORMAT.as_data_format().blob_size as usize + self.len()
     }
 }
 
@@ -40,12 +42,17 @@ where
     /// Encoded as a sequence of [`Format::Data`], each
     /// containing a single `T` from the vector.
     ///
-    /// A `Vec<u8>` has the same encoding as a `[u8]`.
+    /// A `Vec<u8>` has the same encoding as a `[u8]`,
+    /// and is written in a single pass.
     const FORMAT: Format = Format::data(0).with(T::FORMAT);
 
     fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        if let Some(bytes) = (self as &dyn Any).downcast_ref::<Vec<u8>>() {
+            return bytes.as_slice().encode(writer);
+        }
+
         for item in self {
-            writer.write_data(item)?;
+            writer.write_data_value(item)?;
         }
 
         Ok(())
@@ -55,11 +62,16 @@ where
         &self,
         writer: &mut (impl WritesEncodable + ?Sized),
     ) -> Result<(), CodecError> {
-        DataHeader {
-            count: codec::try_count(self.len())?,
-            format: Self::FORMAT.as_data_format(),
-        }
-        .encode(writer)
+        writer.write_data_header_for::<T>(codec::try_count(self.len())?)
+    }
+
+    fn encoded_len(&self) -> usize {
+        let items_len = match T::FORMAT {
+            Format::Blob(size) => self.len() * size as usize,
+            _ => self.iter().map(Encodable::encoded_len).sum::<usize>(),
+        };
+
+        DataHeader::FORMAT.as_data_format().blob_size as usize + items_len
     }
 }
 
@@ -74,12 +86,22 @@ where
     ) -> Result<(), CodecError> {
         let header = Self::ensure_header(header, &[0])?;
 
+        // Bytes encoded like a `[u8]` are read in a single pass.
+        if let Some(bytes) = (self as &mut dyn Any).downcast_mut::<Vec<u8>>() {
+            if header.format == <[u8]>::FORMAT.as_data_format() {
+                bytes.clear();
+                return codec::read_bytes_into(reader, bytes, header.count as usize);
+            }
+        }
+
         // To mitigate repeat allocations, reserve
         // space for any elements in excess of this
-        // vector's current capacity.
+        // vector's current capacity, up to a limit (since
+        // the count hasn't been verified against the data).
         let count = header.count as usize;
-        if self.capacity() < count {
-            self.reserve_exact(count - self.capacity());
+        let reserved = count.min(MAX_RESERVED_ELEMENTS);
+        if self.capacity() < reserved {
+            self.reserve_exact(reserved - self.capacity());
         }
 
         // Decode all elements.
@@ -100,10 +122,110 @@ where
     }
 }
 
+impl<T, const SIZE: usize> Encodable for [T; SIZE]
+where
+    T: Encodable + 'static,
+{
+    /// Encoded as a sequence of exactly `SIZE` [`Format::Data`],
+    /// each containing a single `T` from the array.
+    ///
+    /// Arrays of single-byte blobs (like `[u8; SIZE]`)
+    /// are instead encoded as a single [`Format::Data`]
+    /// containing a [`Format::Blob(SIZE)`](Format::Blob).
+    const FORMAT: Format = match T::FORMAT {
+        Format::Blob(1) => {
+            assert!(
+                SIZE <= u16::MAX as usize,
+                "SIZE exceeds maximum blob size (u16::MAX)"
+            );
+            Format::data(0).with(Format::Blob(SIZE as u16))
+        }
+        _ => Format::data(0).with(T::FORMAT),
+    };
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        if let Some(bytes) = (self as &dyn Any).downcast_ref::<[u8; SIZE]>() {
+            writer.write_all(bytes)?;
+            return Ok(());
+        }
+
+        for item in self {
+            writer.write_data_value(item)?;
+        }
+
+        Ok(())
+    }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        let count = match T::FORMAT {
+            Format::Blob(1) => 1,
+            _ => codec::try_count(SIZE)?,
+        };
+
+        DataHeader {
+            count,
+            format: Self::FORMAT.as_data_format(),
+        }
+        .encode(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        let items_len = match T::FORMAT {
+            Format::Blob(size) => SIZE * size as usize,
+            _ => self.iter().map(Encodable::encoded_len).sum::<usize>(),
+        };
+
+        DataHeader::FORMAT.as_data_format().blob_size as usize + items_len
+    }
+}
+
+impl<T, const SIZE: usize> Decodable for [T; SIZE]
+where
+    T: Decodable + 'static,
+{
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let header = Self::ensure_header(header, &[0])?;
+
+        // Arrays must contain exactly `SIZE` elements.
+        let expected_count = match T::FORMAT {
+            Format::Blob(1) => 1,
+            _ => SIZE as u64,
+        };
+        if header.count as u64 != expected_count || header.format != Self::FORMAT.as_data_format() {
+            return UnexpectedDataFormatSnafu {
+                expected: Self::FORMAT,
+                actual: Some(header),
+                offset: None,
+            }
+            .fail();
+        }
+
+        if let Some(bytes) = (self as &mut dyn Any).downcast_mut::<[u8; SIZE]>() {
+            return reader.read_exact(bytes);
+        }
+
+        for item in self {
+            reader.read_data_into(item)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        codec::{Encodable, Format, ReadsDecodable, WritesEncodable},
+        codec::{
+            self, decode_from_slice, CodecError, DataHeader, Encodable, Format, ReadsDecodable,
+            WritesEncodable,
+        },
         types::Text,
     };
 
@@ -115,10 +237,71 @@ mod test {
         let value = &[8u8, 3, 7][..];
         let mut encoded = vec![];
         encoded.write_data(value).expect("encoded");
-        let decoded: Vec<u8> = encoded.as_slice().read_data().expect("decoded");
+        let decoded: Vec<u8> = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded.as_slice());
     }
 
+    #[test]
+    fn codes_bytes_like_other_sequences() {
+        let value: Vec<u8> = (0..=255).cycle().take(1024).collect();
+
+        // Bytes are encoded like a sequence of `u8` data.
+        let mut expected = vec![];
+        value.encode_header(&mut expected).expect("encoded");
+        for byte in &value {
+            expected.write_data(byte).expect("encoded");
+        }
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        assert_eq!(expected, encoded);
+        assert_eq!(encoded.len(), value.encoded_len());
+
+        // Bytes are decoded like a sequence of `u8` data.
+        let mut decoded = vec![1u8; 2048];
+        encoded
+            .as_slice()
+            .read_data_into(&mut decoded)
+            .expect("decoded");
+        assert_eq!(value, decoded);
+        let decoded: Vec<u8> = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+        let mut decoded = vec![];
+        let mut reader = encoded.as_slice();
+        let header: DataHeader = reader.read_data().expect("header");
+        for _ in 0..header.count {
+            decoded.push(reader.read_data::<u8>().expect("decoded"));
+        }
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn codes_sequences_longer_than_u16() {
+        let value = vec![7u8; 70_000];
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let header: DataHeader = decode_from_slice(&encoded).expect("header");
+        assert_eq!(70_000, header.count);
+        let decoded: Vec<u8> = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+
+        let value: Text = "codas".repeat(100 * 1024 / 5).into();
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let decoded: Text = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(100 * 1024, decoded.len());
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn rejects_counts_longer_than_u32() {
+        assert_eq!(u32::MAX, codec::try_count(u32::MAX as usize).unwrap());
+        assert!(matches!(
+            codec::try_count(u32::MAX as usize + 1),
+            Err(CodecError::CountOverflow { length }) if length == u32::MAX as usize + 1
+        ));
+    }
+
     #[test]
     fn codes_unstructured_vecs() {
         assert_eq!(Format::data(0).with(Format::Blob(4)), <Vec<u32>>::FORMAT);
@@ -126,7 +309,7 @@ mod test {
         let value = vec![7u32, 8, 9];
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded: Vec<u32> = encoded.as_slice().read_data().expect("decoded");
+        let decoded: Vec<u32> = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -140,7 +323,64 @@ mod test {
         let value = vec![Text::from("Hello, world!")];
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded: Vec<Text> = encoded.as_slice().read_data().expect("decoded");
+        let decoded: Vec<Text> = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn codes_fixed_size_arrays() {
+        // Arrays are encoded like vectors of the same length.
+        let value = [7u32, 8, 9, 10];
+        assert_eq!(<Vec<u32>>::FORMAT, <[u32; 4]>::FORMAT);
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let mut expected = vec![];
+        expected.write_data(&value.to_vec()).expect("encoded");
+        assert_eq!(expected, encoded);
+        assert_eq!(encoded.len(), value.encoded_len());
+        let decoded: [u32; 4] = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+
+        let value = [Text::from("Hello,"), Text::from("world!")];
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        assert_eq!(encoded.len(), value.encoded_len());
+        let decoded: [Text; 2] = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+
+        // Byte arrays are encoded as a single blob.
+        let value = [1u8, 2, 3, 4];
+        assert_eq!(Format::data(0).with(Format::Blob(4)), <[u8; 4]>::FORMAT);
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let header: DataHeader = decode_from_slice(&encoded).expect("header");
+        assert_eq!(1, header.count);
+        assert_eq!(12, encoded.len());
+        let decoded: [u8; 4] = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
+
+    #[test]
+    fn rejects_arrays_of_other_lengths() {
+        let mut encoded = vec![];
+        encoded.write_data(&vec![7u32, 8, 9]).expect("encoded");
+        let result = encoded.as_slice().read_data::<[u32; 4]>();
+        assert!(
+            matches!(result, Err(CodecError::UnexpectedDataFormat { .. })),
+            "expected UnexpectedDataFormat, got {result:?}"
+        );
+        let result = encoded.as_slice().read_data::<[u32; 2]>();
+        assert!(
+            matches!(result, Err(CodecError::UnexpectedDataFormat { .. })),
+            "expected UnexpectedDataFormat, got {result:?}"
+        );
+
+        let mut encoded = vec![];
+        encoded.write_data(&[1u8, 2, 3]).expect("encoded");
+        let result = encoded.as_slice().read_data::<[u8; 4]>();
+        assert!(
+            matches!(result, Err(CodecError::UnexpectedDataFormat { .. })),
+            "expected UnexpectedDataFormat, got {result:?}"
+        );
+    }
 }
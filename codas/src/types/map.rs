@@ -1,9 +1,14 @@
+//! Map data types (like `BTreeMap` and `HashMap`).
+//!
+//! All maps share the same encoding, so data
+//! encoded from one kind of map can be decoded
+//! into any other kind of map.
 use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
 use crate::codec::{
-    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
-    UnexpectedDataFormatSnafu, WritesEncodable,
+    try_count, CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
+    WritesEncodable,
 };
 
 impl<K, V> Encodable for BTreeMap<K, V>
@@ -19,10 +24,7 @@ where
         .with(Vec::<V>::FORMAT);
 
     fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
-        writer.write_data(&self.keys().cloned().collect::<Vec<K>>())?;
-        writer.write_data(&self.values().cloned().collect::<Vec<V>>())?;
-
-        Ok(())
+        encode_entries(writer, self.keys(), self.values())
     }
 }
 
@@ -45,17 +47,63 @@ where
         let keys: Vec<K> = reader.read_data()?;
         let values: Vec<V> = reader.read_data()?;
 
-        // Reject mismatched lengths to avoid silent data loss from zip().
-        if keys.len() != values.len() {
-            return UnexpectedDataFormatSnafu {
-                expected: Self::FORMAT,
-                actual: None,
-            }
-            .fail();
+        // Insert (key, value) pairs.
+        ensure_same_lengths(&keys, &values)?;
+        for (key, value) in keys.into_iter().zip(values) {
+            self.insert(key, value);
         }
 
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<K, V> Encodable for std::collections::HashMap<K, V>
+where
+    K: Encodable + Ord + core::hash::Hash + Clone + 'static,
+    V: Encodable + Clone + 'static,
+{
+    /// Encoded exactly like a [`BTreeMap`], with
+    /// keys sorted in ascending order.
+    const FORMAT: Format = BTreeMap::<K, V>::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        // Sort entries so that encodings are deterministic.
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+
+        encode_entries(
+            writer,
+            entries.iter().map(|(k, _)| *k),
+            entries.iter().map(|(_, v)| *v),
+        )
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl<K, V> Decodable for std::collections::HashMap<K, V>
+where
+    K: Default + Decodable + Ord + core::hash::Hash + Clone + 'static,
+    V: Default + Decodable + Clone + 'static,
+{
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let _ = Self::ensure_header(header, &[0])?;
+
+        // Reset the map.
+        self.clear();
+
+        // Collect all keys and values.
+        let keys: Vec<K> = reader.read_data()?;
+        let values: Vec<V> = reader.read_data()?;
+
         // Insert (key, value) pairs.
-        for (key, value) in keys.into_iter().zip(values.into_iter()) {
+        ensure_same_lengths(&keys, &values)?;
+        self.reserve(keys.len());
+        for (key, value) in keys.into_iter().zip(values) {
             self.insert(key, value);
         }
 
@@ -63,13 +111,56 @@ where
     }
 }
 
+/// Encodes `keys` as a list followed by `values` as a list,
+/// writing each key and value in place (without first
+/// collecting them into a [`Vec`]).
+fn encode_entries<'a, K, V>(
+    writer: &mut (impl WritesEncodable + ?Sized),
+    keys: impl ExactSizeIterator<Item = &'a K>,
+    values: impl ExactSizeIterator<Item = &'a V>,
+) -> Result<(), CodecError>
+where
+    K: Encodable + 'a,
+    V: Encodable + 'a,
+{
+    writer.write_data_header_for::<K>(try_count(keys.len())?)?;
+    for key in keys {
+        writer.write_data_value(key)?;
+    }
+
+    writer.write_data_header_for::<V>(try_count(values.len())?)?;
+    for value in values {
+        writer.write_data_value(value)?;
+    }
+
+    Ok(())
+}
+
+/// Returns [`CodecError::MapLengthMismatch`] if there
+/// aren't exactly as many `keys` as `values`,
+/// avoiding silent data loss when zipping them.
+fn ensure_same_lengths<K, V>(keys: &[K], values: &[V]) -> Result<(), CodecError> {
+    if keys.len() != values.len() {
+        return Err(CodecError::MapLengthMismatch {
+            keys: keys.len(),
+            values: values.len(),
+        });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::collections::BTreeMap;
+    use std::collections::HashMap;
 
     use crate::{
-        codec::{ReadsDecodable, WritesEncodable},
-        types::Text,
+        codec::{decode_from_slice, CodecError, DataHeader, ReadsDecodable, WritesEncodable},
+        types::{
+            dynamic::{decode_typed, DynamicValue, Unspecified},
+            Coda, DataField, DataType, Text, Type,
+        },
     };
 
     #[test]
@@ -81,7 +172,7 @@ mod tests {
         let mut encoded = vec![];
         encoded.write_data(&map).expect("encoded");
 
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded = decode_from_slice(&encoded).expect("decoded");
 
         assert_eq!(map, decoded);
     }
@@ -95,7 +186,7 @@ mod tests {
         let mut encoded = vec![];
         encoded.write_data(&map).expect("encoded");
 
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded = decode_from_slice(&encoded).expect("decoded");
 
         assert_eq!(map, decoded);
     }
@@ -109,7 +200,7 @@ mod tests {
         let mut encoded = vec![];
         encoded.write_data(&map).expect("encoded");
 
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded = decode_from_slice(&encoded).expect("decoded");
 
         assert_eq!(map, decoded);
     }
@@ -123,8 +214,125 @@ mod tests {
         let mut encoded = vec![];
         encoded.write_data(&map).expect("encoded");
 
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded = decode_from_slice(&encoded).expect("decoded");
 
         assert_eq!(map, decoded);
     }
+
+    #[test]
+    fn hash_map_to_btree_map() {
+        let mut map = HashMap::new();
+        for i in 0..32u64 {
+            map.insert(i, Text::Dynamic(format!("{i}").into()));
+        }
+
+        let mut encoded = vec![];
+        encoded.write_data(&map).expect("encoded");
+
+        // Hash maps are encoded exactly like B-tree maps.
+        let btree_map: BTreeMap<u64, Text> = decode_from_slice(&encoded).expect("decoded");
+        let mut btree_encoded = vec![];
+        btree_encoded.write_data(&btree_map).expect("encoded");
+        assert_eq!(encoded, btree_encoded);
+
+        let decoded: HashMap<u64, Text> = decode_from_slice(&btree_encoded).expect("decoded");
+        assert_eq!(map, decoded);
+    }
+
+    #[test]
+    fn hash_map_to_unspecified_map() {
+        let map = HashMap::from([
+            (Text::from("b"), 2u64),
+            (Text::from("a"), 1u64),
+            (Text::from("c"), 3u64),
+        ]);
+        let expected = Unspecified::Map(BTreeMap::from([
+            (Text::from("a"), Unspecified::U64(1)),
+            (Text::from("b"), Unspecified::U64(2)),
+            (Text::from("c"), Unspecified::U64(3)),
+        ]));
+        assert_eq!(expected, map.to_dynamic());
+
+        // Encode the map as the only field of a data type.
+        let data_type = DataType::new("Mapped".into(), None, 1, &[], &[]).with(DataField {
+            name: "map".into(),
+            docs: None,
+            typing: Type::Map((Type::Text, Type::U64).into()),
+            optional: false,
+            flattened: false,
+            default: None,
+            deprecated: false,
+            deprecation_note: None,
+        });
+        let coda = Coda::new(
+            "Test".into(),
+            "Test".into(),
+            None,
+            core::slice::from_ref(&data_type),
+        );
+        let mut encoded = vec![];
+        encoded
+            .write_data(&DataHeader {
+                count: 1,
+                format: data_type.format().as_data_format(),
+            })
+            .expect("encoded");
+        encoded.write_data(&map).expect("encoded");
+
+        let (_, decoded) = decode_typed(&coda, &mut encoded.as_slice()).expect("decoded");
+        assert_eq!(Some(&expected), decoded.get("map"));
+    }
+
+    #[test]
+    fn unspecified_map_to_hash_map() {
+        let dynamic = Unspecified::Map(BTreeMap::from([
+            (Text::from("a"), Unspecified::U64(1)),
+            (Text::from("b"), Unspecified::U64(2)),
+        ]));
+
+        let map = HashMap::<Text, u64>::try_from_dynamic(&dynamic).expect("converted");
+        assert_eq!(
+            HashMap::from([(Text::from("a"), 1), (Text::from("b"), 2)]),
+            map
+        );
+
+        // The converted map encodes (and decodes)
+        // exactly like its B-tree equivalent.
+        let btree_map = BTreeMap::<Text, u64>::try_from_dynamic(&dynamic).expect("converted");
+        let (mut encoded, mut btree_encoded) = (vec![], vec![]);
+        encoded.write_data(&map).expect("encoded");
+        btree_encoded.write_data(&btree_map).expect("encoded");
+        assert_eq!(btree_encoded, encoded);
+
+        let decoded: HashMap<Text, u64> = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(dynamic, decoded.to_dynamic());
+    }
+
+    #[test]
+    fn rejects_mismatched_lengths() {
+        // Encode a map's layout by hand, with
+        // more keys than values.
+        let mut encoded = vec![];
+        encoded
+            .write_data(&(vec![1u64, 2u64], vec![Text::from("one")]))
+            .expect("encoded");
+
+        let result = encoded.as_slice().read_data::<BTreeMap<u64, Text>>();
+        assert!(
+            matches!(
+                result,
+                Err(CodecError::MapLengthMismatch { keys: 2, values: 1 })
+            ),
+            "{result:?}"
+        );
+
+        let result = encoded.as_slice().read_data::<HashMap<u64, Text>>();
+        assert!(
+            matches!(
+                result,
+                Err(CodecError::MapLengthMismatch { keys: 2, values: 1 })
+            ),
+            "{result:?}"
+        );
+    }
 }
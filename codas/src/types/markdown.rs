@@ -0,0 +1,277 @@
+//! Markdown serialization of [`Coda`]s.
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{Coda, DataType, EnumType, Text, Type, Unspecified};
+
+/// Number of spaces field and variant docs are indented by.
+const DOCS_INDENTATION: &str = "    ";
+
+impl Coda {
+    /// Returns this coda as markdown which parses
+    /// back into an equivalent coda.
+    ///
+    /// The markdown is written in a canonical format:
+    ///
+    /// - Headers, docs, and groups of fields are
+    ///   separated by exactly one blank line.
+    /// - Field docs are indented by four spaces.
+    /// - Fields are listed in the order they're
+    ///   encoded in: blob fields before data fields.
+    /// - Data type names are capitalized.
+    /// - Data type headers end with the data type's
+    ///   ordinal iff the coda's ordinals don't follow
+    ///   the order of its data types from `1`.
+    /// - Enums declared by the coda are written
+    ///   after all of its data types.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        self.write_markdown(&[], &mut markdown);
+        markdown
+    }
+
+    /// Appends this coda to `markdown` in the format
+    /// of [`Self::to_markdown`], declaring `imports`
+    /// as `(coda name, path)` pairs after its header.
+    pub(crate) fn write_markdown(&self, imports: &[(Text, Text)], markdown: &mut String) {
+        let mut blocks = alloc::vec![format!("# `{}` Coda", self.global_name)];
+
+        if !imports.is_empty() {
+            let imports: Vec<String> = imports
+                .iter()
+                .map(|(name, path)| format!("> import `{name}` from \"{path}\""))
+                .collect();
+            blocks.push(imports.join("\n"));
+        }
+
+        if let Some(docs) = self.docs.as_ref().filter(|docs| !docs.is_empty()) {
+            blocks.push(docs.to_string());
+        }
+
+        let explicit_ordinals = self
+            .iter()
+            .enumerate()
+            .any(|(i, data)| data.ordinal() as usize != i + 1);
+        for data in self.iter() {
+            let ordinal = explicit_ordinals.then(|| data.ordinal());
+            write_data(data, ordinal, &self.enums, &mut blocks);
+        }
+        for typing in &self.enums {
+            write_enum(typing, &mut blocks);
+        }
+
+        markdown.push_str(&blocks.join("\n\n"));
+        markdown.push('\n');
+    }
+}
+
+/// Appends the markdown blocks declaring `data`, with
+/// an explicit `ordinal` (if any), to `blocks`.
+///
+/// Fields typed as enums are written as the enum's
+/// name if the enum is in `declared_enums`, or if it
+/// wasn't declared by the field itself.
+fn write_data(
+    data: &DataType,
+    ordinal: Option<u8>,
+    declared_enums: &[EnumType],
+    blocks: &mut Vec<String>,
+) {
+    let name = capitalize(&data.name);
+    let docs = data.docs.as_ref().filter(|docs| !docs.is_empty());
+    let ordinal = match ordinal {
+        Some(ordinal) => format!(" {ordinal}"),
+        None => String::new(),
+    };
+
+    blocks.push(format!("## `{name}` Data{ordinal}"));
+    if let Some(docs) = docs {
+        blocks.push(docs.to_string());
+    }
+
+    let items = data.iter().map(|field| {
+        let mut line = format!("+ `{}` ", field.name);
+        if field.optional {
+            line.push_str("optional ");
+        }
+        if field.flattened {
+            line.push_str("flattened ");
+        }
+        if field.deprecated {
+            line.push_str("deprecated ");
+        }
+        match &field.typing {
+            Type::Enum(typing)
+                if typing.name == EnumType::field_name(&data.name, &field.name)
+                    && !declared_enums.iter().any(|other| other.name == typing.name) =>
+            {
+                let variants: Vec<&str> = typing.variants.iter().map(|v| v.as_ref()).collect();
+                line.push_str(&format!("one of [{}]", variants.join(", ")));
+            }
+            typing => line.push_str(&type_name(typing)),
+        }
+        if let Some(default) = &field.default {
+            line.push_str(" = ");
+            line.push_str(&default_literal(default));
+        }
+
+        let docs = field.docs.as_deref().filter(|docs| !docs.is_empty());
+        (line, docs.map(indent_docs))
+    });
+    write_items(items, blocks);
+}
+
+/// Appends the markdown blocks declaring list `items`,
+/// as `(line, indented docs)` pairs, to `blocks`.
+///
+/// Items without docs are grouped into a single block;
+/// items with docs are followed by a block of their docs.
+fn write_items(items: impl Iterator<Item = (String, Option<String>)>, blocks: &mut Vec<String>) {
+    let mut lines: Vec<String> = Vec::new();
+    for (line, docs) in items {
+        lines.push(line);
+        if let Some(docs) = docs {
+            blocks.push(lines.join("\n"));
+            blocks.push(docs);
+            lines.clear();
+        }
+    }
+
+    if !lines.is_empty() {
+        blocks.push(lines.join("\n"));
+    }
+}
+
+/// Appends the markdown blocks declaring the enum `typing`
+/// to `blocks`, valuing each variant as its ordinal.
+fn write_enum(typing: &EnumType, blocks: &mut Vec<String>) {
+    blocks.push(format!("### `{}` Enum", capitalize(&typing.name)));
+    if let Some(docs) = typing.docs.as_ref().filter(|docs| !docs.is_empty()) {
+        blocks.push(docs.to_string());
+    }
+
+    let items = typing.variants.iter().enumerate().map(|(ordinal, name)| {
+        let docs = typing
+            .variant_docs(ordinal as u16)
+            .filter(|docs| !docs.is_empty());
+        (
+            format!("+ `{name}` = {ordinal}"),
+            docs.map(|docs| indent_docs(docs)),
+        )
+    });
+    write_items(items, blocks);
+}
+
+/// Returns the markdown spelling of `typing`.
+fn type_name(typing: &Type) -> String {
+    match typing {
+        Type::Data(data) => capitalize(&data.name),
+        Type::List(_) => {
+            let mut dimensions = 0;
+            let mut item = typing;
+            while let Type::List(typing) = item {
+                dimensions += 1;
+                item = typing;
+            }
+
+            match dimensions {
+                1 => format!("list of {}", type_name(item)),
+                _ => format!("{dimensions}d list of {}", type_name(item)),
+            }
+        }
+        Type::Map(typing) => format!(
+            "map of {} to {}",
+            type_name(&typing.0),
+            type_name(&typing.1)
+        ),
+        Type::Enum(typing) => capitalize(&typing.name),
+        typing => typing.description().to_string(),
+    }
+}
+
+/// Returns the markdown literal of a field's `default` value.
+fn default_literal(default: &Unspecified) -> String {
+    match default {
+        Unspecified::U8(value) => value.to_string(),
+        Unspecified::U16(value) => value.to_string(),
+        Unspecified::U32(value) => value.to_string(),
+        Unspecified::U64(value) => value.to_string(),
+        Unspecified::U128(value) => value.to_string(),
+        Unspecified::I8(value) => value.to_string(),
+        Unspecified::I16(value) => value.to_string(),
+        Unspecified::I32(value) => value.to_string(),
+        Unspecified::I64(value) => value.to_string(),
+        Unspecified::I128(value) => value.to_string(),
+        Unspecified::UVar(value) => value.0.to_string(),
+        Unspecified::IVar(value) => value.0.to_string(),
+        Unspecified::F32(value) => format!("{value:?}"),
+        Unspecified::F64(value) => format!("{value:?}"),
+        Unspecified::Timestamp(value) => value.to_string(),
+        Unspecified::Bool(value) => value.to_string(),
+        Unspecified::Text(value) => quote(value, '"'),
+        Unspecified::Char(value) => quote(&value.to_string(), '\''),
+        Unspecified::Enum { typing, variant } => match typing.variants.get(*variant as usize) {
+            Some(name) => name.to_string(),
+            None => variant.to_string(),
+        },
+        value => format!("{value:?}"),
+    }
+}
+
+/// Returns `text` between `quote`s, escaping any
+/// backslashes, newlines, tabs, or `quote`s in `text`.
+fn quote(text: &str, quote: char) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push(quote);
+    for char in text.chars() {
+        match char {
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            char if char == '\\' || char == quote => {
+                quoted.push('\\');
+                quoted.push(char);
+            }
+            char => quoted.push(char),
+        }
+    }
+    quoted.push(quote);
+    quoted
+}
+
+/// Returns `docs` with every line indented by
+/// [`DOCS_INDENTATION`].
+///
+/// Any indentation shared by every line after the
+/// first (which has already been trimmed) is replaced.
+fn indent_docs(docs: &str) -> String {
+    let shared = docs
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or_default();
+
+    let lines: Vec<String> = docs
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match (i, line.trim().is_empty()) {
+            (_, true) => String::new(),
+            (0, false) => format!("{DOCS_INDENTATION}{line}"),
+            (_, false) => format!("{DOCS_INDENTATION}{}", &line[shared..]),
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Returns `name` with its first character capitalized.
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
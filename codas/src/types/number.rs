@@ -1,7 +1,25 @@
-//! Numeric data types (including `bool`).
+//! Numeric data types (including `bool` and `char`).
+//!
+//! ## Variable-Length Integers
+//!
+//! [`VarU64`] and [`VarI64`] encode 64-bit integers as
+//! variable-length ([LEB128](https://en.wikipedia.org/wiki/LEB128))
+//! sequences of 1-10 bytes, with small magnitudes
+//! using fewer bytes.
+//!
+//! Because their size isn't fixed, variable-length
+//! integers are encoded as structured data (with a
+//! [`DataHeader`]) in the _data fields_ of their
+//! containing data, and not in its blob. As a result,
+//! a variable-length integer is never smaller than a
+//! fixed-length integer when encoded as a field on
+//! its own; however, their distinct formats ensure
+//! decoders expecting fixed-length integers reject
+//! them, and vice-versa.
 
 use crate::codec::{
-    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable, WritesEncodable,
+    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable,
+    UnexpectedDataFormatSnafu, WritesEncodable,
 };
 
 /// Implements codec traits for a native numeric type.
@@ -30,6 +48,11 @@ macro_rules! numeric_impls {
                 writer.write_all(&self.to_le_bytes())?;
                 Ok(())
             }
+
+            #[inline(always)]
+            fn encoded_len(&self) -> usize {
+                $primitive_size
+            }
         }
 
         impl $crate::codec::Decodable for $primitive_type {
@@ -56,6 +79,12 @@ numeric_impls!(i8, 1);
 numeric_impls!(i16, 2);
 numeric_impls!(i32, 4);
 numeric_impls!(i64, 8);
+numeric_impls!(u128, 16);
+numeric_impls!(i128, 16);
+
+// Floating-point numbers are encoded bit-for-bit, so
+// every NaN (including its sign and payload) decodes
+// to a NaN with identical bits.
 numeric_impls!(f32, 4);
 numeric_impls!(f64, 8);
 
@@ -71,6 +100,11 @@ impl Encodable for bool {
             writer.write_data(&0u8)
         }
     }
+
+    #[inline(always)]
+    fn encoded_len(&self) -> usize {
+        u8::FORMAT.as_data_format().blob_size as usize
+    }
 }
 
 impl Decodable for bool {
@@ -85,18 +119,266 @@ impl Decodable for bool {
     }
 }
 
+impl Encodable for char {
+    /// Encoded as a [`u32`] containing
+    /// the character's Unicode scalar value.
+    const FORMAT: Format = u32::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        writer.write_data(&(*self as u32))
+    }
+
+    #[inline(always)]
+    fn encoded_len(&self) -> usize {
+        u32::FORMAT.as_data_format().blob_size as usize
+    }
+}
+
+impl Decodable for char {
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        Self::ensure_no_header(header)?;
+        let value = reader.read_data::<u32>()?;
+        *self = char::from_u32(value).ok_or(CodecError::InvalidChar { value })?;
+        Ok(())
+    }
+}
+
+/// Unsigned 64-bit integer encoded as
+/// a variable-length sequence of bytes.
+///
+/// See the [module docs](self) for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarU64(pub u64);
+
+/// Signed 64-bit integer encoded as
+/// a variable-length sequence of bytes.
+///
+/// Values are [ZigZag-encoded](https://protobuf.dev/programming-guides/encoding/#signed-ints)
+/// before their variable-length encoding, so that small
+/// magnitudes of either sign use fewer bytes.
+///
+/// See the [module docs](self) for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarI64(pub i64);
+
+/// Maximum number of bytes in a variable-length integer.
+const VAR_INT_MAX_LEN: usize = 10;
+
+/// Returns the variable-length encoding of `value`,
+/// and the number of bytes in the encoding.
+fn var_int_bytes(mut value: u64) -> ([u8; VAR_INT_MAX_LEN], usize) {
+    let mut bytes = [0u8; VAR_INT_MAX_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes[len] = byte;
+            return (bytes, len + 1);
+        }
+
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+/// Encodes the variable-length `value` into `writer`.
+fn encode_var_int(
+    value: u64,
+    writer: &mut (impl WritesEncodable + ?Sized),
+) -> Result<(), CodecError> {
+    let (bytes, len) = var_int_bytes(value);
+    writer.write_all(&bytes[..len])?;
+    Ok(())
+}
+
+/// Encodes the header of the variable-length
+/// `value` with `format` into `writer`.
+fn encode_var_int_header(
+    value: u64,
+    format: Format,
+    writer: &mut (impl WritesEncodable + ?Sized),
+) -> Result<(), CodecError> {
+    DataHeader {
+        count: var_int_bytes(value).1 as u32,
+        format: format.as_data_format(),
+    }
+    .encode(writer)
+}
+
+/// Decodes a variable-length value with `header`
+/// and `format` from `reader`.
+///
+/// Only canonical encodings (containing no redundant
+/// trailing bytes) of values which fit in a `u64`
+/// are decoded successfully.
+fn decode_var_int(
+    reader: &mut (impl ReadsDecodable + ?Sized),
+    header: DataHeader,
+    format: Format,
+) -> Result<u64, CodecError> {
+    if header.format != format.as_data_format()
+        || header.count == 0
+        || header.count as usize > VAR_INT_MAX_LEN
+    {
+        return UnexpectedDataFormatSnafu {
+            expected: format,
+            actual: Some(header),
+            offset: None,
+        }
+        .fail();
+    }
+
+    let len = header.count as usize;
+    let mut bytes = [0u8; VAR_INT_MAX_LEN];
+    reader.read_exact(&mut bytes[..len])?;
+
+    let mut value = 0u64;
+    for (i, byte) in bytes[..len].iter().enumerate() {
+        let last = i == len - 1;
+        let continues = byte & 0x80 != 0;
+        let overflows = i == VAR_INT_MAX_LEN - 1 && *byte > 1;
+        let redundant = last && len > 1 && *byte == 0;
+        if continues == last || overflows || redundant {
+            return Err(CodecError::InvalidVarInt);
+        }
+
+        value |= ((byte & 0x7F) as u64) << (7 * i);
+    }
+
+    Ok(value)
+}
+
+impl VarU64 {
+    /// Type ordinal of variable-length unsigned integers.
+    const ORDINAL: u8 = 238;
+}
+
+impl Encodable for VarU64 {
+    /// Encoded as a [`DataHeader`] with `count` equal to the
+    /// number of bytes in the integer's LEB128 encoding, followed
+    /// by those bytes.
+    const FORMAT: Format = Format::data(Self::ORDINAL).with(u8::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        encode_var_int(self.0, writer)
+    }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        encode_var_int_header(self.0, Self::FORMAT, writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        DataHeader::FORMAT.as_data_format().blob_size as usize + var_int_bytes(self.0).1
+    }
+}
+
+impl Decodable for VarU64 {
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let header = Self::ensure_header(header, &[Self::ORDINAL])?;
+        self.0 = decode_var_int(reader, header, Self::FORMAT)?;
+        Ok(())
+    }
+}
+
+impl From<u64> for VarU64 {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<VarU64> for u64 {
+    fn from(value: VarU64) -> Self {
+        value.0
+    }
+}
+
+impl VarI64 {
+    /// Type ordinal of variable-length signed integers.
+    const ORDINAL: u8 = 237;
+
+    /// Returns this integer's ZigZag encoding.
+    const fn zigzag(self) -> u64 {
+        ((self.0 << 1) ^ (self.0 >> 63)) as u64
+    }
+
+    /// Returns the integer with ZigZag encoding `value`.
+    const fn unzigzag(value: u64) -> Self {
+        Self((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+}
+
+impl Encodable for VarI64 {
+    /// Encoded as a [`DataHeader`] with `count` equal to the
+    /// number of bytes in the LEB128 encoding of the integer's
+    /// ZigZag encoding, followed by those bytes.
+    const FORMAT: Format = Format::data(Self::ORDINAL).with(u8::FORMAT);
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        encode_var_int(self.zigzag(), writer)
+    }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        encode_var_int_header(self.zigzag(), Self::FORMAT, writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        DataHeader::FORMAT.as_data_format().blob_size as usize + var_int_bytes(self.zigzag()).1
+    }
+}
+
+impl Decodable for VarI64 {
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let header = Self::ensure_header(header, &[Self::ORDINAL])?;
+        *self = Self::unzigzag(decode_var_int(reader, header, Self::FORMAT)?);
+        Ok(())
+    }
+}
+
+impl From<i64> for VarI64 {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<VarI64> for i64 {
+    fn from(value: VarI64) -> Self {
+        value.0
+    }
+}
+
 #[cfg(test)]
 mod test {
     use core::{f32, f64};
 
-    use crate::codec::{ReadsDecodable, WritesEncodable};
+    use crate::codec::{decode_from_slice, ReadsDecodable, WritesEncodable};
+
+    use super::*;
 
     #[test]
     fn test_u8_codec() {
         let value = 255u8;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: u8 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -105,7 +387,7 @@ mod test {
         let value = 65535u16;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: u16 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -114,7 +396,7 @@ mod test {
         let value = 4294967295u32;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: u32 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -123,7 +405,7 @@ mod test {
         let value = 18446744073709551615u64;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: u64 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -132,7 +414,7 @@ mod test {
         let value = -128i8;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: i8 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -141,7 +423,7 @@ mod test {
         let value = -32768i16;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: i16 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -150,7 +432,7 @@ mod test {
         let value = -2147483648i32;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: i32 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -159,7 +441,25 @@ mod test {
         let value = -9223372036854775808i64;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: i64 = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn test_u128_codec() {
+        let value = 340282366920938463463374607431768211455u128;
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let decoded: u128 = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn test_i128_codec() {
+        let value = -170141183460469231731687303715884105728i128;
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let decoded: i128 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -168,7 +468,7 @@ mod test {
         let value = f32::consts::PI;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: f32 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -177,7 +477,7 @@ mod test {
         let value = f64::consts::E;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: f64 = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
 
@@ -186,13 +486,159 @@ mod test {
         let value = true;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: bool = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
 
         let value = false;
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded = encoded.as_slice().read_data().expect("decoded");
+        let decoded: bool = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
+
+    #[test]
+    fn test_f32_nan_codec() {
+        let value = f32::from_bits(0xFFC0_0001);
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let decoded: f32 = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value.to_bits(), decoded.to_bits());
+    }
+
+    #[test]
+    fn test_f64_nan_codec() {
+        let value = -f64::NAN;
+        let mut encoded = vec![];
+        encoded.write_data(&value).expect("encoded");
+        let decoded: f64 = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!(value.to_bits(), decoded.to_bits());
+    }
+
+    #[test]
+    fn test_char_codec() {
+        // Characters with 1-, 2-, 3-, and 4-byte UTF-8 encodings.
+        for value in ['a', 'é', '世', '🦀', char::MAX] {
+            let mut encoded = vec![];
+            encoded.write_data(&value).expect("encoded");
+            assert_eq!((value as u32).to_le_bytes().as_slice(), encoded.as_slice());
+            let decoded: char = decode_from_slice(&encoded).expect("decoded");
+            assert_eq!(value, decoded);
+        }
+
+        // Surrogates and values beyond `char::MAX` are rejected.
+        for value in [0xD800u32, 0xDFFF, 0x11_0000, u32::MAX] {
+            let mut encoded = vec![];
+            encoded.write_data(&value).expect("encoded");
+            let result = encoded.as_slice().read_data::<char>();
+            assert!(
+                matches!(result, Err(crate::codec::CodecError::InvalidChar { value: v }) if v == value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_var_u64_codec() {
+        // Values at the boundaries of each encoded length.
+        let values = [
+            (0, 1),
+            (127, 1),
+            (128, 2),
+            (16_383, 2),
+            (16_384, 3),
+            (u32::MAX as u64, 5),
+            (1 << 32, 5),
+            (u64::MAX, 10),
+        ];
+        for (value, len) in values {
+            let value = VarU64(value);
+            let mut encoded = vec![];
+            encoded.write_data(&value).expect("encoded");
+            assert_eq!(8 + len, encoded.len());
+            assert_eq!(encoded.len(), value.encoded_len());
+            let decoded: VarU64 = decode_from_slice(&encoded).expect("decoded");
+            assert_eq!(value, decoded);
+        }
+
+        // Values are encoded as LEB128.
+        let mut encoded = vec![];
+        encoded.write_data(&VarU64(300)).expect("encoded");
+        assert_eq!([0xAC, 0x02], encoded[8..]);
+    }
+
+    #[test]
+    fn test_var_i64_codec() {
+        // Values at the boundaries of each encoded length.
+        let values = [
+            (0, 1),
+            (-1, 1),
+            (63, 1),
+            (-64, 1),
+            (64, 2),
+            (-65, 2),
+            (1 << 32, 5),
+            (i64::MAX, 10),
+            (i64::MIN, 10),
+        ];
+        for (value, len) in values {
+            let value = VarI64(value);
+            let mut encoded = vec![];
+            encoded.write_data(&value).expect("encoded");
+            assert_eq!(8 + len, encoded.len());
+            assert_eq!(encoded.len(), value.encoded_len());
+            let decoded: VarI64 = decode_from_slice(&encoded).expect("decoded");
+            assert_eq!(value, decoded);
+        }
+    }
+
+    #[test]
+    fn test_var_int_rejections() {
+        /// Returns `bytes` encoded as a variable-length integer.
+        fn var_int(bytes: &[u8]) -> Vec<u8> {
+            let mut encoded = vec![];
+            encoded
+                .write_data(&DataHeader {
+                    count: bytes.len() as u32,
+                    format: VarU64::FORMAT.as_data_format(),
+                })
+                .expect("encoded");
+            encoded.extend_from_slice(bytes);
+            encoded
+        }
+
+        // Malformed, redundant, and overflowing encodings are rejected.
+        for bytes in [
+            &[0x80][..],
+            &[0x01, 0x01],
+            &[0x80, 0x00],
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02],
+        ] {
+            let result = var_int(bytes).as_slice().read_data::<VarU64>();
+            assert!(
+                matches!(result, Err(CodecError::InvalidVarInt)),
+                "{bytes:?}"
+            );
+        }
+
+        // Encodings longer than any 64-bit integer are rejected.
+        let result = var_int(&[0x80; 11]).as_slice().read_data::<VarU64>();
+        assert!(matches!(
+            result,
+            Err(CodecError::UnexpectedDataFormat { .. })
+        ));
+
+        // Variable-length integers aren't decoded as
+        // (or confused with) other structured data.
+        let mut encoded = vec![];
+        encoded.write_data(&VarU64(1)).expect("encoded");
+        let result = encoded.as_slice().read_data::<Vec<u8>>();
+        assert!(matches!(
+            result,
+            Err(CodecError::UnsupportedDataFormat { ordinal: 238, .. })
+        ));
+        let result = encoded.as_slice().read_data::<VarI64>();
+        assert!(matches!(
+            result,
+            Err(CodecError::UnsupportedDataFormat { ordinal: 238, .. })
+        ));
+    }
 }
@@ -0,0 +1,158 @@
+//! References and smart pointers (like `&T`, `Box<T>`, and `Arc<T>`).
+//!
+//! Each of these types is encoded exactly
+//! like the data it points to.
+
+use alloc::boxed::Box;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+use crate::codec::{
+    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable, WritesEncodable,
+};
+
+impl<T> Encodable for &T
+where
+    T: Encodable + ?Sized,
+{
+    const FORMAT: Format = T::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        (**self).encode(writer)
+    }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        (**self).encode_header(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        (**self).encoded_len()
+    }
+}
+
+impl<T> Encodable for Box<T>
+where
+    T: Encodable + ?Sized,
+{
+    const FORMAT: Format = T::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        (**self).encode(writer)
+    }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        (**self).encode_header(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        (**self).encoded_len()
+    }
+}
+
+impl<T> Decodable for Box<T>
+where
+    T: Decodable,
+{
+    /// Decodes into the boxed data in-place.
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        (**self).decode(reader, header)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> Encodable for Arc<T>
+where
+    T: Encodable + ?Sized,
+{
+    const FORMAT: Format = T::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        (**self).encode(writer)
+    }
+
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        (**self).encode_header(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        (**self).encoded_len()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> Decodable for Arc<T>
+where
+    T: Decodable + Default,
+{
+    /// Decodes into a newly allocated [`Arc`], since the
+    /// data in an existing [`Arc`] may be shared.
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        let mut data = T::default();
+        data.decode(reader, header)?;
+        *self = Arc::new(data);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codec::{encode_to_vec, ReadsDecodable, WritesEncodable},
+        types::{tests::TestData, Text},
+    };
+
+    use super::*;
+
+    #[test]
+    fn arc_codec() -> Result<(), CodecError> {
+        let text = Arc::new(Text::from("shared"));
+        let shared = text.clone();
+
+        let encoded = encode_to_vec(&text)?;
+
+        // Pointers are encoded exactly like their data.
+        let expected = encode_to_vec(&Text::from("shared"))?;
+        assert_eq!(expected, encoded);
+
+        let mut decoded = shared;
+        encoded.as_slice().read_data_into(&mut decoded)?;
+        assert_eq!(text, decoded);
+        assert!(!Arc::ptr_eq(&text, &decoded));
+
+        Ok(())
+    }
+
+    #[test]
+    fn box_codec() -> Result<(), CodecError> {
+        let data = Box::new(TestData::default());
+
+        let mut encoded = vec![];
+        encoded.write_data(&data)?;
+        encoded.write_data(&&*data)?;
+
+        let mut encoded = encoded.as_slice();
+        let decoded: Box<TestData> = encoded.read_data()?;
+        assert_eq!(data, decoded);
+        let decoded: Box<TestData> = encoded.read_data()?;
+        assert_eq!(data, decoded);
+
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
 //! UTF-8 encoded text data types.
 use core::{
+    borrow::Borrow,
     fmt::{Debug, Display},
     hash::Hash,
     ops::Deref,
 };
 
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::string::String;
 use alloc::string::ToString;
 use alloc::sync::Arc;
@@ -107,14 +108,35 @@ impl Encodable for Text {
     ) -> Result<(), CodecError> {
         self.as_bytes().encode_header(writer)
     }
+
+    fn encoded_len(&self) -> usize {
+        self.as_bytes().encoded_len()
+    }
 }
 
 impl Decodable for Text {
+    /// Decodes text, failing with [`CodecError::InvalidText`]
+    /// if the text isn't valid UTF-8. To replace invalid UTF-8
+    /// instead, decode a [`LossyText`].
     fn decode(
         &mut self,
         reader: &mut (impl crate::codec::ReadsDecodable + ?Sized),
         header: Option<crate::codec::DataHeader>,
     ) -> Result<(), crate::codec::CodecError> {
+        self.decode_utf8(reader, header, false)
+    }
+}
+
+impl Text {
+    /// Decodes text from `reader`, replacing invalid
+    /// UTF-8 with [`char::REPLACEMENT_CHARACTER`]
+    /// iff `lossy` is true.
+    fn decode_utf8(
+        &mut self,
+        reader: &mut (impl crate::codec::ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+        lossy: bool,
+    ) -> Result<(), CodecError> {
         let header = Self::ensure_header(header, &[0])?;
 
         // Text is always blob_size=1, data_fields=0.
@@ -122,6 +144,7 @@ impl Decodable for Text {
             return UnexpectedDataFormatSnafu {
                 expected: Self::FORMAT,
                 actual: Some(header),
+                offset: None,
             }
             .fail();
         }
@@ -134,8 +157,8 @@ impl Decodable for Text {
 
                 // Dynamically allocate for non-empty strings.
                 } else {
-                    let mut string = String::with_capacity(header.count as usize);
-                    try_decode_string(reader, header, &mut string)?;
+                    let mut string = String::new();
+                    try_decode_string(reader, header, &mut string, lossy)?;
                     *self = Text::Dynamic(string.into());
                 }
             }
@@ -152,12 +175,12 @@ impl Decodable for Text {
                     match Arc::get_mut(text) {
                         // Read data directly into the string buffer if
                         // it's not shared.
-                        Some(text) => try_decode_string(reader, header, text)?,
+                        Some(text) => try_decode_string(reader, header, text, lossy)?,
 
                         // Dynamically allocate a new string.
                         None => {
-                            let mut string = String::with_capacity(header.count as usize);
-                            try_decode_string(reader, header, &mut string)?;
+                            let mut string = String::new();
+                            try_decode_string(reader, header, &mut string, lossy)?;
                             *self = Text::Dynamic(string.into());
                         }
                     }
@@ -172,40 +195,109 @@ impl Decodable for Text {
 /// Tries to decode the remaining string data
 /// from `reader` for `header` into `string`.
 ///
+/// If the string data isn't valid UTF-8, and `lossy`
+/// is true, invalid bytes are replaced with
+/// [`char::REPLACEMENT_CHARACTER`]; otherwise,
+/// [`CodecError::InvalidText`] is returned.
+///
 /// If decoding fails for any reason, the returned
 /// `string` will be empty.
 fn try_decode_string(
     reader: &mut (impl crate::codec::ReadsDecodable + ?Sized),
     header: DataHeader,
     string: &mut String,
+    lossy: bool,
 ) -> Result<(), CodecError> {
-    unsafe {
-        // Truncate and pad the bytes to fit the new text.
+    let replaced = unsafe {
+        // Read in the raw bytes, replacing the old text.
         let bytes = string.as_mut_vec();
-        bytes.truncate(header.count as usize);
-        bytes.reserve(header.count as usize);
-        while bytes.len() < header.count as usize {
-            bytes.push(0u8);
+        bytes.clear();
+        crate::codec::read_bytes_into(reader, bytes, header.count as usize)?;
+
+        // Validate the bytes, clearing them if they're
+        // invalid so that `string` remains valid UTF-8.
+        match alloc::str::from_utf8(bytes) {
+            Ok(_) => return Ok(()),
+            Err(_) if lossy => {
+                let replaced = String::from_utf8_lossy(bytes).into_owned();
+                bytes.clear();
+                replaced
+            }
+            Err(error) => {
+                let at = error.valid_up_to();
+                bytes.clear();
+                return Err(CodecError::InvalidText { at });
+            }
         }
+    };
 
-        // Read in the raw bytes.
-        if let Err(e) = reader.read_exact(bytes) {
-            bytes.clear();
-            return Err(e);
-        }
+    string.push_str(&replaced);
+    Ok(())
+}
 
-        // Validate the bytes.
-        if alloc::str::from_utf8(bytes).is_err() {
-            bytes.clear();
+/// [`Text`] which replaces any invalid UTF-8 with
+/// [`char::REPLACEMENT_CHARACTER`] when decoded
+/// (like [`String::from_utf8_lossy`]), instead of
+/// failing to decode.
+///
+/// Lossy text is encoded identically to [`Text`],
+/// and may be decoded wherever text is expected:
+///
+/// ```rust
+/// # use codas::types::{LossyText, Text};
+/// # use codas::codec::{CodecError, ReadsDecodable, WritesEncodable};
+/// let mut encoded = vec![];
+/// encoded.write_data(&Text::from("cupcakes!")).unwrap();
+/// encoded[8] = 0xFF;
+///
+/// let strict = encoded.as_slice().read_data::<Text>();
+/// assert!(matches!(strict, Err(CodecError::InvalidText { at: 0 })));
+///
+/// let lossy: LossyText = encoded.as_slice().read_data().unwrap();
+/// assert_eq!("\u{FFFD}upcakes!", lossy.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LossyText(pub Text);
 
-            // FIXME: If a string contains malformed UTF-8 bytes,
-            //        should decoding fail? Or should the string
-            //        be silently interpreted as empty data, which
-            //        could lead to data loss?
-        }
+impl Encodable for LossyText {
+    const FORMAT: Format = Text::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        self.0.encode(writer)
     }
 
-    Ok(())
+    fn encode_header(
+        &self,
+        writer: &mut (impl WritesEncodable + ?Sized),
+    ) -> Result<(), CodecError> {
+        self.0.encode_header(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.encoded_len()
+    }
+}
+
+impl Decodable for LossyText {
+    fn decode(
+        &mut self,
+        reader: &mut (impl crate::codec::ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        self.0.decode_utf8(reader, header, true)
+    }
+}
+
+impl From<Text> for LossyText {
+    fn from(value: Text) -> Self {
+        Self(value)
+    }
+}
+
+impl From<LossyText> for Text {
+    fn from(value: LossyText) -> Self {
+        value.0
+    }
 }
 
 // Common Traits //////////////////////
@@ -247,6 +339,33 @@ impl From<String> for Text {
     }
 }
 
+impl From<Cow<'static, str>> for Text {
+    fn from(value: Cow<'static, str>) -> Self {
+        match value {
+            Cow::Borrowed(text) => Self::Static(text),
+            Cow::Owned(text) => Self::Dynamic(text.into()),
+        }
+    }
+}
+
+impl Borrow<str> for Text {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<str> for Text {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<[u8]> for Text {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 // Self-equivalency traits ////////////
 
 impl PartialEq for Text {
@@ -289,6 +408,12 @@ impl PartialEq<&str> for Text {
     }
 }
 
+impl PartialEq<&&str> for Text {
+    fn eq(&self, other: &&&str) -> bool {
+        self.deref() == **other
+    }
+}
+
 impl PartialEq<Text> for &str {
     fn eq(&self, other: &Text) -> bool {
         *self == other.deref()
@@ -354,7 +479,7 @@ impl<'de> serde::Deserialize<'de> for Text {
 
 #[cfg(test)]
 mod test {
-    use crate::codec::ReadsDecodable;
+    use crate::codec::{decode_from_slice, ReadsDecodable};
 
     use super::*;
 
@@ -366,6 +491,7 @@ mod test {
         assert_eq!(string, text);
         assert_eq!(text, string.as_str());
         assert_eq!(string.as_str(), text);
+        assert_eq!(text, &string.as_str());
 
         let text = Text::from("World ♥️");
         let string = String::from("World ♥️");
@@ -380,6 +506,22 @@ mod test {
         assert_ne!(string, text);
         assert_ne!(text, string.as_str());
         assert_ne!(string.as_str(), text);
+        assert_ne!(text, &string.as_str());
+    }
+
+    #[test]
+    fn test_text_borrows_as_str() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(Text::from("key"), 1u32);
+        map.insert(String::from("other").into(), 2u32);
+        assert_eq!(Some(&1), map.get("key"));
+        assert_eq!(Some(&2), map.get("other"));
+        assert_eq!(None, map.get("missing"));
+
+        let borrowed: Text = Cow::Borrowed("static").into();
+        assert!(matches!(borrowed, Text::Static("static")));
+        let owned: Text = Cow::<'static, str>::Owned(String::from("owned")).into();
+        assert!(matches!(owned, Text::Dynamic(_)));
     }
 
     #[test]
@@ -387,7 +529,37 @@ mod test {
         let value = Text::from("Hello, world!");
         let mut encoded = vec![];
         encoded.write_data(&value).expect("encoded");
-        let decoded: Text = encoded.as_slice().read_data().expect("decoded");
+        let decoded: Text = decode_from_slice(&encoded).expect("decoded");
         assert_eq!(value, decoded);
     }
+
+    #[test]
+    fn test_text_invalid_utf8() {
+        // A valid text header, followed by "a" and then a
+        // lead byte with an invalid continuation byte ("(").
+        let mut encoded = vec![];
+        let header = DataHeader {
+            count: 3,
+            format: Text::FORMAT.as_data_format(),
+        };
+        encoded.write_data(&header).expect("encoded");
+        encoded.extend_from_slice(&[b'a', 0xC3, b'(']);
+
+        // Strict decoding fails, leaving reused text empty.
+        let mut decoded: Text = String::from("reused").into();
+        let result = encoded.as_slice().read_data_into(&mut decoded);
+        assert!(matches!(result, Err(CodecError::InvalidText { at: 1 })));
+        assert_eq!("", decoded);
+
+        // Lossy decoding replaces invalid bytes.
+        let decoded: LossyText = decode_from_slice(&encoded).expect("decoded");
+        assert_eq!("a\u{FFFD}(", decoded.0);
+
+        // Lossy text encodes like text.
+        let mut lossy = vec![];
+        lossy.write_data(&decoded).expect("encoded");
+        let mut strict = vec![];
+        strict.write_data(&decoded.0).expect("encoded");
+        assert_eq!(strict, lossy);
+    }
 }
@@ -0,0 +1,166 @@
+//! Points in time.
+use core::fmt::Display;
+
+use crate::codec::{
+    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable, WritesEncodable,
+};
+
+/// Point in time, stored as a signed number of
+/// milliseconds since the Unix epoch
+/// (`1970-01-01T00:00:00Z`).
+///
+/// Timestamps are encoded exactly like an [`i64`],
+/// so a timestamp may be read as an `i64` (and
+/// vice-versa) without any loss of information.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    /// The Unix epoch (`1970-01-01T00:00:00Z`).
+    pub const EPOCH: Timestamp = Timestamp(0);
+
+    /// Returns the timestamp `millis` milliseconds
+    /// after (or, if negative, before) the Unix epoch.
+    pub const fn from_millis(millis: i64) -> Self {
+        Self(millis)
+    }
+
+    /// Returns the number of milliseconds between
+    /// the Unix epoch and this timestamp.
+    pub const fn as_millis(&self) -> i64 {
+        self.0
+    }
+}
+
+impl Encodable for Timestamp {
+    /// Encoded as an [`i64`].
+    const FORMAT: Format = i64::FORMAT;
+
+    fn encode(&self, writer: &mut (impl WritesEncodable + ?Sized)) -> Result<(), CodecError> {
+        self.0.encode(writer)
+    }
+}
+
+impl Decodable for Timestamp {
+    fn decode(
+        &mut self,
+        reader: &mut (impl ReadsDecodable + ?Sized),
+        header: Option<DataHeader>,
+    ) -> Result<(), CodecError> {
+        self.0.decode(reader, header)
+    }
+}
+
+impl From<i64> for Timestamp {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Timestamp> for i64 {
+    fn from(value: Timestamp) -> Self {
+        value.0
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}ms", self.0)
+    }
+}
+
+/// Times beyond the range of a timestamp
+/// are clamped to the nearest timestamp.
+#[cfg(any(feature = "std", test))]
+impl From<std::time::SystemTime> for Timestamp {
+    fn from(value: std::time::SystemTime) -> Self {
+        let millis = match value.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => after.as_millis() as i128,
+            Err(before) => -(before.duration().as_millis() as i128),
+        };
+
+        Self(millis.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl From<Timestamp> for std::time::SystemTime {
+    fn from(value: Timestamp) -> Self {
+        let offset = core::time::Duration::from_millis(value.0.unsigned_abs());
+        if value.0 >= 0 {
+            std::time::UNIX_EPOCH + offset
+        } else {
+            std::time::UNIX_EPOCH - offset
+        }
+    }
+}
+
+// Serde traits ///////////////////////
+
+/// Serialized as milliseconds since the Unix epoch.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self(i64::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::codec::{decode_from_slice, WritesEncodable};
+
+    use super::*;
+
+    #[test]
+    fn codes_timestamps() {
+        for millis in [0, 1_700_000_000_123, -86_400_000, i64::MIN, i64::MAX] {
+            let timestamp = Timestamp(millis);
+            let mut encoded = vec![];
+            encoded.write_data(&timestamp).expect("encoded");
+
+            // Timestamps are encoded exactly like `i64`s.
+            let mut expected = vec![];
+            expected.write_data(&millis).expect("encoded");
+            assert_eq!(expected, encoded);
+
+            let decoded: Timestamp = decode_from_slice(&encoded).expect("decoded");
+            assert_eq!(timestamp, decoded);
+            let decoded: i64 = decode_from_slice(&encoded).expect("decoded");
+            assert_eq!(millis, decoded);
+        }
+    }
+
+    #[test]
+    fn converts_system_times() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        for millis in [0, 1_700_000_000_123, -86_400_000] {
+            let time: SystemTime = Timestamp(millis).into();
+            assert_eq!(Timestamp(millis), Timestamp::from(time));
+        }
+
+        let time = UNIX_EPOCH + Duration::from_millis(1_500);
+        assert_eq!(Timestamp(1_500), time.into());
+        let time = UNIX_EPOCH - Duration::from_millis(1_500);
+        assert_eq!(Timestamp(-1_500), time.into());
+
+        // Sub-millisecond precision is truncated.
+        let time = UNIX_EPOCH + Duration::from_micros(1_999);
+        assert_eq!(Timestamp(1), time.into());
+    }
+}
@@ -0,0 +1,126 @@
+//! Tuple data types (like `(A, B)`).
+
+use crate::codec::{
+    CodecError, DataHeader, Decodable, Encodable, Format, ReadsDecodable, WritesEncodable,
+};
+
+/// Implements codec traits for a tuple.
+macro_rules! tuple_impls {
+    (
+        /// Pairs of each element's type and index.
+        $($element_type:ident $element_index:tt),+
+    ) => {
+        impl<$($element_type),+> Encodable for ($($element_type,)+)
+        where
+            $($element_type: Encodable),+
+        {
+            /// Encoded as a [`Format::Data`] containing
+            /// each element of the tuple, in order.
+            const FORMAT: Format = Format::data(0)$(.with($element_type::FORMAT))+;
+
+            fn encode(
+                &self,
+                writer: &mut (impl WritesEncodable + ?Sized),
+            ) -> Result<(), CodecError> {
+                $(writer.write_data(&self.$element_index)?;)+
+                Ok(())
+            }
+        }
+
+        impl<$($element_type),+> Decodable for ($($element_type,)+)
+        where
+            $($element_type: Decodable),+
+        {
+            fn decode(
+                &mut self,
+                reader: &mut (impl ReadsDecodable + ?Sized),
+                header: Option<DataHeader>,
+            ) -> Result<(), CodecError> {
+                let _ = Self::ensure_header(header, &[0])?;
+                $(reader.read_data_into(&mut self.$element_index)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+tuple_impls!(A 0);
+tuple_impls!(A 0, B 1);
+tuple_impls!(A 0, B 1, C 2);
+tuple_impls!(A 0, B 1, C 2, D 3);
+tuple_impls!(A 0, B 1, C 2, D 3, E 4);
+tuple_impls!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_impls!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_impls!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        codec::{decode_from_slice, encode_to_vec, DataFormat, ReadsDecodable},
+        types::Text,
+    };
+
+    use super::*;
+
+    #[test]
+    fn pair_codec() -> Result<(), CodecError> {
+        let pair = (7u32, Text::from("seven"));
+
+        let encoded = encode_to_vec(&pair)?;
+
+        // The number is a blob field,
+        // and the text is a data field.
+        let header: DataHeader = decode_from_slice(&encoded)?;
+        assert_eq!(
+            DataHeader {
+                count: 1,
+                format: DataFormat {
+                    blob_size: 4,
+                    data_fields: 1,
+                    ordinal: 0,
+                },
+            },
+            header
+        );
+
+        let decoded: (u32, Text) = decode_from_slice(&encoded)?;
+        assert_eq!(pair, decoded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_codec() -> Result<(), CodecError> {
+        let nested = ((1u8, 2u8), Vec::from([Text::from("a"), Text::from("b")]));
+
+        let encoded = encode_to_vec(&nested)?;
+
+        let header: DataHeader = decode_from_slice(&encoded)?;
+        assert_eq!(0, header.format.blob_size);
+        assert_eq!(2, header.format.data_fields);
+
+        // The inner pair is encoded first, after the header.
+        let inner_header: DataHeader = (&encoded[8..]).read_data()?;
+        assert_eq!(2, inner_header.format.blob_size);
+        assert_eq!(0, inner_header.format.data_fields);
+
+        let decoded: ((u8, u8), Vec<Text>) = decode_from_slice(&encoded)?;
+        assert_eq!(nested, decoded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn octuple_codec() -> Result<(), CodecError> {
+        let octuple = (1u8, 2u16, 3u32, 4u64, -5i8, 6.0f32, true, Text::from("8"));
+
+        let encoded = encode_to_vec(&octuple)?;
+
+        let decoded: (u8, u16, u32, u64, i8, f32, bool, Text) = decode_from_slice(&encoded)?;
+        assert_eq!(octuple, decoded);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(test)]
+//! Integration tests of the async codec over `tokio` sockets.
+
+extern crate alloc;
+
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use codas::{
+    codec::{
+        asynchronous::{AsyncReadsDecodable, AsyncWritesEncodable},
+        CodecError,
+    },
+    types::Text,
+};
+
+codas_macros::export_coda!("codas/tests/test_coda.md");
+
+#[tokio::test]
+pub async fn test_async_codec_over_tcp() -> Result<(), CodecError> {
+    // Create our request data.
+    let request_data = TestMessage {
+        number: 9000,
+        text_list: vec!["I like cake.".into()],
+        text: "Hello, Codecs!".into(),
+    };
+
+    // We'll add this string to the response,
+    // and increment the response number by 1.
+    let additional_string = Text::from("The cake is a lie.");
+
+    // Create our _expected_ response data.
+    let expected_response_data = TestMessage {
+        number: 9001,
+        text_list: vec!["I like cake.".into(), additional_string.clone()],
+        text: "Hello, Codecs!".into(),
+    };
+
+    // Create TCP listener on an arbitrary port, configuring
+    // it to echo our request with some additional data.
+    let listener = TcpListener::bind("0.0.0.0:0").await.unwrap();
+    let listener_port = listener.local_addr().unwrap().port();
+    let expected_request_data = request_data.clone();
+    let server: JoinHandle<Result<(), CodecError>> = tokio::spawn(async move {
+        // Accept the first client request and decode it.
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request_data: TestMessage = socket.read_data().await?;
+        assert_eq!(expected_request_data, request_data);
+
+        // Append additional data to the test data.
+        request_data.number += 1;
+        request_data.text_list.push(additional_string);
+
+        // Send it back to the client.
+        socket.write_data(&request_data).await?;
+
+        Ok(())
+    });
+
+    // Create a TCP client connection to the listener
+    // and send some encoded data.
+    let mut client = TcpStream::connect(format!("127.0.0.1:{listener_port}"))
+        .await
+        .unwrap();
+    client.write_data(&request_data).await?;
+
+    // Decode the response.
+    let response_data: TestMessage = client.read_data().await?;
+    assert_eq!(expected_response_data, response_data);
+
+    // Join server to ensure no errors occurred on it's side.
+    server.await.unwrap()?;
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 #![cfg(test)]
 //! Integration tests of the codec over `std` IO sockets.
 
+extern crate alloc;
+
 use std::{
     net::{TcpListener, TcpStream},
     thread::JoinHandle,
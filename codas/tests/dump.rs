@@ -0,0 +1,69 @@
+//! Tests that dumping encoded data never allocates.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use codas::{
+    codec::{dump, CodecError, DumpOptions, WritesEncodable},
+    types::Text,
+};
+
+/// Allocator which counts all allocations.
+struct CountingAllocator;
+
+/// Number of allocations made by [`CountingAllocator`].
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// [`core::fmt::Write`] which discards everything written to it.
+struct Discard;
+
+impl core::fmt::Write for Discard {
+    fn write_str(&mut self, _: &str) -> core::fmt::Result {
+        Ok(())
+    }
+}
+
+#[test]
+fn dumps_large_data_without_allocating() -> Result<(), CodecError> {
+    // Encode many records containing nested data.
+    let texts: Vec<Text> = (0..1_000)
+        .map(|i| format!("text number {i}").into())
+        .collect();
+    let mut bytes = vec![];
+    for _ in 0..1_000 {
+        bytes.write_data(&texts)?;
+    }
+
+    for blobs in [true, false] {
+        let options = DumpOptions {
+            blobs,
+            ..Default::default()
+        };
+
+        let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+        let stats = dump(&mut bytes.as_slice(), &mut Discard, &options)?;
+        assert_eq!(0, ALLOCATIONS.load(Ordering::SeqCst) - allocations);
+
+        assert_eq!(1_000 * 1_001, stats.records);
+        assert_eq!(bytes.len() as u64, stats.bytes);
+        assert_eq!(2, stats.max_depth);
+    }
+
+    Ok(())
+}
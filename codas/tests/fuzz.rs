@@ -0,0 +1,184 @@
+//! Fuzz tests asserting that decoding arbitrary
+//! (untrusted) bytes never panics or hangs.
+//!
+//! Inputs are generated from a fixed seed, so that any
+//! failure is reproducible: each failing input is
+//! printed as hex before its decoder runs.
+
+use codas::{
+    codec::{encode_to_vec, DataFormat, DataHeader, Decodable, ReadsDecodable, WritesEncodable},
+    types::{binary::hex_from_bytes, Coda, DataType, Text, Type, Unspecified},
+};
+
+/// Number of inputs generated by each fuzzing strategy.
+const ITERATIONS: usize = 4096;
+
+/// Coda encoded as a seed for mutated inputs.
+const SEED_CODA: &str = r#"
+# `Fuzz` Coda
+
+Data used to seed fuzzed inputs.
+
+## `Scalars` Data
+
++ `number` i32
++ `big` u128
++ `letter` char
++ `flag` bool
++ `ratio` f64
+
+## `Nested` Data
+
+Data with nested _data_ fields.
+
++ `name` text
++ `maybe` optional text
++ `numbers` list of u64
++ `lookup` map of text to i16
++ `scalars` Scalars
++ `many` list of Scalars
+"#;
+
+#[test]
+fn decodes_random_bytes() {
+    let mut rng = XorShift::new(0x00C0_DA5F_022E);
+    for _ in 0..ITERATIONS {
+        let len = rng.below(96);
+        let input = rng.bytes(len);
+        decode_all(&input);
+    }
+}
+
+#[test]
+fn decodes_random_bytes_after_headers() {
+    let mut rng = XorShift::new(0x1EAD_E25F_022E);
+    for _ in 0..ITERATIONS {
+        // Prefix random bytes with a well-formed (but arbitrary)
+        // header, so that decoders reach past their first read.
+        let header = DataHeader {
+            count: match rng.below(4) {
+                0 => rng.next_u64() as u32,
+                _ => rng.below(4) as u32,
+            },
+            format: DataFormat {
+                blob_size: rng.below(24) as u16,
+                data_fields: rng.below(6) as u8,
+                ordinal: match rng.below(2) {
+                    0 => rng.next_u64() as u8,
+                    _ => 255 - rng.below(20) as u8,
+                },
+            },
+        };
+
+        let mut input = vec![];
+        input.write_data(&header).unwrap();
+        let len = rng.below(128);
+        input.extend(rng.bytes(len));
+        decode_all(&input);
+    }
+}
+
+#[test]
+fn decodes_mutated_encodings() {
+    let mut rng = XorShift::new(0x003A_7ED5_EED5);
+
+    // Encode seeds which exercise every decoder.
+    let coda = codas::parse::parse(SEED_CODA).unwrap();
+    let mut seeds = vec![];
+    let encoded = encode_to_vec(&coda).unwrap();
+    seeds.push(encoded);
+    for data_type in coda.iter() {
+        let encoded = encode_to_vec(data_type).unwrap();
+        seeds.push(encoded);
+    }
+    let dynamic = Unspecified::Map(
+        [
+            (
+                Text::from("list"),
+                Unspecified::List(vec![Unspecified::U8(1), Unspecified::U8(2)]),
+            ),
+            (Text::from("text"), Unspecified::Text("hi".into())),
+            (Text::from("char"), Unspecified::Char('🦀')),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    let encoded = encode_to_vec(&dynamic).unwrap();
+    seeds.push(encoded);
+
+    for i in 0..ITERATIONS {
+        let mut input = seeds[i % seeds.len()].clone();
+
+        // Apply a handful of random mutations.
+        for _ in 0..=rng.below(4) {
+            let at = rng.below(input.len().max(1));
+            match rng.below(5) {
+                // Flip a bit.
+                0 if !input.is_empty() => input[at] ^= 1 << rng.below(8),
+                // Replace a byte.
+                1 if !input.is_empty() => input[at] = rng.next_u64() as u8,
+                // Insert a byte.
+                2 => input.insert(at.min(input.len()), rng.next_u64() as u8),
+                // Remove a byte.
+                3 if !input.is_empty() => {
+                    input.remove(at);
+                }
+                // Truncate.
+                _ => input.truncate(at),
+            }
+        }
+
+        decode_all(&input);
+    }
+}
+
+/// Decodes `input` with every decoder under test,
+/// discarding the results: only panics or hangs
+/// will fail the calling test.
+fn decode_all(input: &[u8]) {
+    // Print the input so it's visible if a decoder panics.
+    println!("decoding {}", hex_from_bytes(input));
+
+    decode::<Coda>(input);
+    decode::<DataType>(input);
+    decode::<Type>(input);
+    decode::<Unspecified>(input);
+    decode::<Vec<Text>>(input);
+
+    let mut reader = input;
+    let _ = reader.skip_data();
+}
+
+/// Decodes `input` as a `T`, discarding the result.
+fn decode<T: Decodable + Default>(mut input: &[u8]) {
+    let _ = input.read_data::<T>();
+}
+
+/// Minimal, deterministic pseudo-random number
+/// generator ("xorshift64").
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number in `0..max`, or `0` if `max` is `0`.
+    fn below(&mut self, max: usize) -> usize {
+        match max {
+            0 => 0,
+            max => (self.next_u64() % max as u64) as usize,
+        }
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next_u64() as u8).collect()
+    }
+}
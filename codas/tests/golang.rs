@@ -0,0 +1,133 @@
+#![cfg(test)]
+//! Integration tests of Go codecs generated by `langs::golang`.
+//!
+//! These tests build and run Go code, and are
+//! skipped if the `go` toolchain isn't installed.
+
+extern crate alloc;
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use codas::{
+    codec::{decode_from_slice, encode_to_vec},
+    langs::golang,
+    parse::parse,
+    types::Text,
+};
+
+codas_macros::export_coda!("codas/tests/test_coda.md");
+
+/// Go program which decodes a `TestMessage` from stdin,
+/// appends some data to it, and encodes it to stdout.
+const TEST_MAIN: &str = r#"package main
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+
+	"codastest/codas"
+)
+
+func main() {
+	data, err := codas.DecodeTestData(bufio.NewReader(os.Stdin))
+	if err != nil {
+		panic(err)
+	}
+	message, ok := data.(*codas.TestMessage)
+	if !ok {
+		panic(fmt.Sprintf("unexpected data with ordinal %d", data.Ordinal()))
+	}
+
+	message.Number++
+	message.TextList = append(message.TextList, "The cake is a lie.")
+
+	writer := bufio.NewWriter(os.Stdout)
+	if err := message.Encode(writer); err != nil {
+		panic(err)
+	}
+	if err := writer.Flush(); err != nil {
+		panic(err)
+	}
+}
+"#;
+
+#[test]
+pub fn test_golang_codec_round_trip() {
+    if Command::new("go").arg("version").output().is_err() {
+        eprintln!("skipping Go codec test: `go` isn't installed");
+        return;
+    }
+
+    // Generate a Go module containing the coda.
+    let coda = parse(include_str!("test_coda.md")).unwrap();
+    let mut types = vec![];
+    golang::generate_types(&coda, &mut types).unwrap();
+    let mut runtime = vec![];
+    golang::generate_runtime(&mut runtime).unwrap();
+
+    let module = std::env::temp_dir().join(format!("codas-golang-{}", std::process::id()));
+    std::fs::create_dir_all(module.join("codas")).unwrap();
+    std::fs::write(module.join("go.mod"), "module codastest\n\ngo 1.21\n").unwrap();
+    std::fs::write(module.join("main.go"), TEST_MAIN).unwrap();
+    std::fs::write(module.join("codas").join("test.go"), types).unwrap();
+    std::fs::write(
+        module.join("codas").join(golang::RUNTIME_FILE_NAME),
+        runtime,
+    )
+    .unwrap();
+
+    // The generated code should be vetted.
+    let vet = Command::new("go")
+        .args(["vet", "./..."])
+        .current_dir(&module)
+        .output()
+        .unwrap();
+    assert!(
+        vet.status.success(),
+        "{}",
+        String::from_utf8_lossy(&vet.stderr)
+    );
+
+    // Encode a message in Rust.
+    let request_data = TestMessage {
+        number: 9000,
+        text_list: vec!["I like cake.".into()],
+        text: "Hello, Codecs!".into(),
+    };
+    let request = encode_to_vec(&request_data).unwrap();
+
+    // Decode, modify, and re-encode the message in Go.
+    let mut go = Command::new("go")
+        .arg("run")
+        .arg(".")
+        .current_dir(&module)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    go.stdin.take().unwrap().write_all(&request).unwrap();
+    let output = go.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Decode the Go-encoded response in Rust.
+    let response_data: TestMessage = decode_from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        TestMessage {
+            number: 9001,
+            text_list: vec!["I like cake.".into(), Text::from("The cake is a lie.")],
+            text: "Hello, Codecs!".into(),
+        },
+        response_data
+    );
+
+    let _ = std::fs::remove_dir_all(&module);
+}
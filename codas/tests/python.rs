@@ -0,0 +1,168 @@
+#![cfg(test)]
+//! Integration tests of Python code generated by `langs::python`.
+//!
+//! These tests run Python code, and are skipped if
+//! `python3` (or a Python package they require)
+//! isn't installed.
+
+use std::{path::Path, process::Command};
+
+use codas::{langs::python, parse::parse};
+
+/// Coda containing every kind of field
+/// which needs special handling in Python.
+const TEST_CODA: &str = r#"# `Files` Coda
+
+## `File` Data
+
++ `class` one of [Text, Binary]
++ `contents` bytes
++ `modified` optional timestamp
++ `2d_sizes` 2d list of u32
++ `tags` map of text to i32
+"#;
+
+/// Python program using the types generated
+/// by [`python::generate_types`] for [`TEST_CODA`].
+const TEST_MAIN: &str = r#"import datetime
+from typing import Dict, List, Optional
+
+from files import File, FileClass
+
+file = File()
+file.f_class = FileClass.Binary
+file.contents = b"hello"
+file.modified_datetime = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+file.f_2d_sizes = [[1, 2], [3]]
+file.tags = {"a": 1}
+
+kind: FileClass = file.f_class
+contents: bytes = file.contents
+modified: Optional[int] = file.modified
+sizes: List[List[int]] = file.f_2d_sizes
+tags: Dict[str, int] = file.tags
+
+assert kind == FileClass.Binary
+assert contents == b"hello"
+assert modified == 1704067200000
+assert sizes == [[1, 2], [3]]
+assert tags == {"a": 1}
+"#;
+
+/// Python program round-tripping data through
+/// the pydantic models generated by
+/// [`python::generate_pydantic`] for [`TEST_CODA`].
+const TEST_PYDANTIC_MAIN: &str = r#"import pydantic
+
+from files import File, FileClass, FilesData
+
+data = {
+    "__ordinal": 1,
+    "class": 1,
+    "contents": b"hello",
+    "modified": 1704067200000,
+    "2d_sizes": [[1, 2], [3]],
+    "tags": {"a": 1},
+}
+
+# Models round-trip through Python objects...
+file = File.model_validate(data)
+assert file.f_class == FileClass.Binary
+assert file.f_2d_sizes == [[1, 2], [3]]
+dumped = file.model_dump(by_alias=True)
+assert dumped == {**data, "class": FileClass.Binary}, dumped
+assert File.model_validate(dumped) == file
+
+# ...and JSON.
+assert File.model_validate_json(file.model_dump_json(by_alias=True)) == file
+
+# Data is discriminated by its ordinal.
+assert FilesData.model_validate(data).root == file
+
+# Numbers are constrained to their coda type's range.
+try:
+    File.model_validate({"2d_sizes": [[-1]]})
+    raise AssertionError("validated a negative u32")
+except pydantic.ValidationError:
+    pass
+"#;
+
+#[test]
+pub fn test_python_types_and_stubs() {
+    if !has_python_module("sys") {
+        eprintln!("skipping Python types test: `python3` isn't installed");
+        return;
+    }
+
+    // Generate a Python module and stub for the coda.
+    let coda = parse(TEST_CODA).unwrap();
+    let mut types = vec![];
+    python::generate_types(&coda, &mut types).unwrap();
+    let mut stubs = vec![];
+    python::generate_stubs(&coda, &mut stubs).unwrap();
+
+    let module = std::env::temp_dir().join(format!("codas-python-{}", std::process::id()));
+    std::fs::create_dir_all(&module).unwrap();
+    std::fs::write(module.join("files.py"), types).unwrap();
+    std::fs::write(module.join("files.pyi"), stubs).unwrap();
+    std::fs::write(module.join("main.py"), TEST_MAIN).unwrap();
+
+    // The generated types should work as declared.
+    run_python(&module, &["main.py"]);
+
+    // The generated stubs should type-check the program
+    // using the types, if `mypy` is installed.
+    if has_python_module("mypy") {
+        run_python(&module, &["-m", "mypy", "--strict", "main.py"]);
+    } else {
+        eprintln!("skipping Python stub type check: `mypy` isn't installed");
+    }
+
+    let _ = std::fs::remove_dir_all(&module);
+}
+
+#[test]
+pub fn test_pydantic_round_trip() {
+    if !has_python_module("pydantic") {
+        eprintln!("skipping pydantic test: `python3` or `pydantic` isn't installed");
+        return;
+    }
+
+    // Generate a Python module of pydantic models for the coda.
+    let coda = parse(TEST_CODA).unwrap();
+    let mut models = vec![];
+    python::generate_pydantic(&coda, &mut models).unwrap();
+
+    let module = std::env::temp_dir().join(format!("codas-pydantic-{}", std::process::id()));
+    std::fs::create_dir_all(&module).unwrap();
+    std::fs::write(module.join("files.py"), models).unwrap();
+    std::fs::write(module.join("main.py"), TEST_PYDANTIC_MAIN).unwrap();
+
+    run_python(&module, &["main.py"]);
+
+    let _ = std::fs::remove_dir_all(&module);
+}
+
+/// Returns true iff `python3` can import `module`.
+fn has_python_module(module: &str) -> bool {
+    Command::new("python3")
+        .args(["-c", &format!("import {module}")])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Runs `python3` with `args` in `directory`,
+/// asserting that it succeeds.
+fn run_python(directory: &Path, args: &[&str]) {
+    let output = Command::new("python3")
+        .args(args)
+        .current_dir(directory)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,33 @@
+//! Golden-file tests for the codec's canonical test vectors.
+//!
+//! If an encoding changes _intentionally_, the golden
+//! file can be regenerated with:
+//!
+//! ```sh
+//! codabase vectors --target codas/tests/vectors.json
+//! ```
+
+use codas::codec::vectors::{compare, test_vectors, vectors_from_json, vectors_to_json};
+
+/// Canonical encodings of all test vectors.
+const GOLDEN_VECTORS: &str = include_str!("vectors.json");
+
+#[test]
+fn encodings_match_golden_vectors() {
+    let expected = vectors_from_json(GOLDEN_VECTORS).unwrap();
+    let mismatches = compare(&expected, &test_vectors());
+
+    let report: Vec<String> = mismatches.iter().map(|m| m.to_string()).collect();
+    assert!(
+        mismatches.is_empty(),
+        "test vectors don't match `tests/vectors.json`:\n{}",
+        report.join("\n")
+    );
+}
+
+#[test]
+fn golden_vectors_are_up_to_date() {
+    // Vector values aren't compared by `compare`, so
+    // check the golden file is exactly as rendered.
+    assert_eq!(GOLDEN_VECTORS, &*vectors_to_json(&test_vectors()));
+}